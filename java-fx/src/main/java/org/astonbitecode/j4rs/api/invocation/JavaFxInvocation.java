@@ -84,6 +84,11 @@ public class JavaFxInvocation<T> implements Instance<T> {
         return jsonInvocation.field(fieldName);
     }
 
+    @Override
+    public void setField(String fieldName, InvocationArg arg) {
+        jsonInvocation.setField(fieldName, arg);
+    }
+
     @Override
     public String getJson() {
         return jsonInvocation.getJson();
@@ -23,6 +23,8 @@ import javafx.scene.Scene;
 import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
 import org.astonbitecode.j4rs.api.jfx.errors.ComponentNotFoundException;
 
+import java.util.List;
+
 public interface FxController extends Initializable {
     /**
      * This will be called when the initialize method of {@link Initializable} is
@@ -55,6 +57,15 @@ public interface FxController extends Initializable {
      */
     Node getNodeById(String id) throws ComponentNotFoundException;
 
+    /**
+     * Retrieves the nodes with the given IDs, in the order of the IDs.
+     *
+     * @param ids The ids of the nodes to retrieve.
+     * @return A {@link List} with the {@link Node}s found.
+     * @throws ComponentNotFoundException In case that any of the nodes is not found.
+     */
+    List<Node> getNodesByIds(String... ids) throws ComponentNotFoundException;
+
     /**
      * Sets a scene for this controller.
      *
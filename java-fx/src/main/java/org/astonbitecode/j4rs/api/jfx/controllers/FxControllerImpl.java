@@ -23,6 +23,8 @@ import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
 import org.astonbitecode.j4rs.api.jfx.errors.ComponentNotFoundException;
 
 import java.net.URL;
+import java.util.ArrayList;
+import java.util.List;
 import java.util.ResourceBundle;
 import java.util.concurrent.atomic.AtomicBoolean;
 import java.util.concurrent.atomic.AtomicReference;
@@ -66,6 +68,25 @@ public class FxControllerImpl implements FxController {
         throw new ComponentNotFoundException(String.format("Node with id %s was not found.", id));
     }
 
+    @Override
+    public List<Node> getNodesByIds(String... ids) throws ComponentNotFoundException {
+        List<Node> nodes = new ArrayList<>(ids.length);
+        List<String> notFound = new ArrayList<>();
+        for (String id : ids) {
+            Node node = scene != null ? scene.lookup("#" + id) : null;
+            if (node != null) {
+                nodes.add(node);
+            } else {
+                notFound.add(id);
+            }
+        }
+        if (!notFound.isEmpty()) {
+            throw new ComponentNotFoundException(
+                    String.format("Nodes with ids %s were not found.", String.join(", ", notFound)));
+        }
+        return nodes;
+    }
+
     @Override
     public void setScene(Scene scene) {
         this.scene = scene;
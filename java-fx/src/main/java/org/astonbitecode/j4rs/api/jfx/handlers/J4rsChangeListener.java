@@ -0,0 +1,35 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.jfx.handlers;
+
+import javafx.beans.value.ChangeListener;
+import javafx.beans.value.ObservableValue;
+import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
+
+/**
+ * Forwards the new values of an {@link ObservableValue} to Rust.
+ * <p>
+ * Changes to null are not forwarded. When the Rust side closes the channel, the listener removes itself.
+ */
+public class J4rsChangeListener<T> extends NativeCallbackToRustChannelSupport implements ChangeListener<T> {
+    @Override
+    public void changed(ObservableValue<? extends T> observable, T oldValue, T newValue) {
+        if (isChannelClosed()) {
+            observable.removeListener(this);
+        } else if (newValue != null) {
+            doCallback(newValue);
+        }
+    }
+}
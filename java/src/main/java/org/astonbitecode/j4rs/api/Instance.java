@@ -15,9 +15,14 @@
 package org.astonbitecode.j4rs.api;
 
 import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.api.invocation.EdtInvocation;
+import org.astonbitecode.j4rs.api.invocation.InstanceGenerator;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
+import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
+import org.astonbitecode.j4rs.api.invocation.TimedInvocation;
 import org.astonbitecode.j4rs.api.value.JsonValueFactory;
 import org.astonbitecode.j4rs.errors.InvocationException;
+import org.astonbitecode.j4rs.errors.InvocationTimeoutException;
 import org.astonbitecode.j4rs.utils.Utils;
 
 public interface Instance<T> extends ObjectValue, JsonValue {
@@ -40,6 +45,72 @@ public interface Instance<T> extends ObjectValue, JsonValue {
      */
     Instance invokeStatic(String methodName, InvocationArg... args);
 
+    /**
+     * Invokes the method of the instance of the class that is set for this {@link Instance}, which has exactly the
+     * specified parameter types. Useful for disambiguating overloaded methods.
+     * <p>
+     * Static methods can be invoked as well.
+     *
+     * @param methodName The method name
+     * @param paramTypes The fully qualified class names (or primitive names) of the declared parameters of the method
+     * @param args       The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation
+     */
+    Instance invokeWithSignature(String methodName, String[] paramTypes, InvocationArg... args);
+
+    /**
+     * Invokes a method that is declared in the interface <code>interfaceName</code>, or in its superinterfaces.
+     * <p>
+     * The method is resolved using the interface instead of the class of the instance. This way, default interface
+     * methods and methods of instances whose classes are not accessible (e.g. private classes that implement a public
+     * interface) can be invoked. Static interface methods can be invoked as well.
+     *
+     * @param interfaceName The fully qualified name of the interface
+     * @param methodName    The method name
+     * @param args          The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation
+     */
+    Instance invokeOnInterface(String interfaceName, String methodName, InvocationArg... args);
+
+    /**
+     * Invokes a method of the instance of the class that is set for this {@link Instance}, in a separate thread.
+     * If the invocation does not complete within <code>timeoutMillis</code>, the thread that executes it is
+     * interrupted and an {@link InvocationTimeoutException} is thrown.
+     *
+     * @param timeoutMillis The timeout in milliseconds
+     * @param methodName    The method name
+     * @param args          The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation
+     */
+    default Instance invokeWithTimeout(long timeoutMillis, String methodName, InvocationArg... args) {
+        return TimedInvocation.invoke(() -> this.invoke(methodName, args), "method " + methodName, timeoutMillis);
+    }
+
+    /**
+     * Invokes a method of the instance of the class that is set for this {@link Instance}, in the AWT event
+     * dispatching thread, and waits for the invocation to complete.
+     *
+     * @param methodName The method name
+     * @param args       The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation
+     */
+    default Instance invokeOnEdt(String methodName, InvocationArg... args) {
+        return EdtInvocation.invoke(() -> this.invoke(methodName, args), "method " + methodName);
+    }
+
+    /**
+     * Invokes a method of the instance of the class that is set for this {@link Instance}, like
+     * {@link #invoke(String, InvocationArg...)}, so that void methods and methods that returned null can be told apart.
+     *
+     * @param methodName The method name
+     * @param args       The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation, null if the method returned null,
+     * or {@link Utils#voidInvocationResult()} if the method is void
+     */
+    default Instance invokeForResult(String methodName, InvocationArg... args) {
+        return Utils.toInvocationResult(this.invoke(methodName, args));
+    }
+
     /**
      * Invokes asynchronously a method of the instance of the class that is set for
      * this {@link Instance}. The result of the invocation must be a
@@ -91,6 +162,18 @@ public interface Instance<T> extends ObjectValue, JsonValue {
      */
     void initializeCallbackChannel(long channelAddress);
 
+    /**
+     * Closes the callback channel of this {@link Instance}, if its object is a
+     * {@link org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport}.
+     * Rust calls this when the receiving side of the channel is closed.
+     */
+    default void closeCallbackChannel() {
+        Object object = this.getOrDeserializeJavaObject();
+        if (object instanceof NativeCallbackToRustChannelSupport) {
+            ((NativeCallbackToRustChannelSupport) object).closeChannel();
+        }
+    }
+
     /**
      * Retrieves the instance held under the Field fieldName
      *
@@ -99,6 +182,15 @@ public interface Instance<T> extends ObjectValue, JsonValue {
      */
     Instance field(String fieldName);
 
+    /**
+     * Sets the value of the Field fieldName. If the {@link Instance} holds a
+     * class, the Field is expected to be static.
+     *
+     * @param fieldName The name of the field to set
+     * @param arg       The {@link InvocationArg} that holds the value to set
+     */
+    void setField(String fieldName, InvocationArg arg);
+
     /**
      * Casts a the object that is contained in a Instance to an object of class
      * clazz.
@@ -143,6 +235,48 @@ public interface Instance<T> extends ObjectValue, JsonValue {
         }
     }
 
+    /**
+     * Returns the name of the runtime class of the object that is contained in
+     * this {@link Instance}. If the object is null, the name of the class that is
+     * set for this {@link Instance} is returned.
+     *
+     * @return The fully qualified class name
+     */
+    default String getRuntimeClassName() {
+        T object = this.getOrDeserializeJavaObject();
+        return object != null ? object.getClass().getName() : this.getObjectClassName();
+    }
+
+    /**
+     * Checks whether the object that is contained in this {@link Instance} is an
+     * instance of the class className.
+     *
+     * @param className The fully qualified name of the class or interface
+     * @return true if the object is not null and is an instance of className
+     */
+    default boolean isInstanceOf(String className) {
+        try {
+            Class<?> clazz = Utils.forNameEnhanced(className);
+            return clazz.isInstance(this.getOrDeserializeJavaObject());
+        } catch (ClassNotFoundException error) {
+            throw new InvocationException("Cannot find class " + className, error);
+        }
+    }
+
+    /**
+     * Returns the names of the runtime class of the object that is contained in
+     * this {@link Instance}, of all its superclasses and of all the interfaces it
+     * implements.
+     *
+     * @return A {@link Instance} that contains a {@link java.util.List} of class
+     *         names
+     */
+    default Instance getClassHierarchy() {
+        T object = this.getOrDeserializeJavaObject();
+        Class<?> clazz = object != null ? object.getClass() : this.getObjectClass();
+        return InstanceGenerator.create(Utils.classHierarchy(clazz), java.util.List.class);
+    }
+
     default <U> boolean checkEquals(Instance<U> other) {
         T a = this.getOrDeserializeJavaObject();
         U b = other.getOrDeserializeJavaObject();
@@ -0,0 +1,183 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.async;
+
+import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
+
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+
+/**
+ * Subscribes to a <code>java.util.concurrent.Flow.Publisher</code>, or a Reactive Streams
+ * <code>org.reactivestreams.Publisher</code>, and sends the published items to a Rust channel.
+ * <p>
+ * The publishers are handled reflectively, so that there is no dependency to the Reactive Streams API
+ * and no need for Java 9 or later.
+ * <p>
+ * When the publisher completes or fails, the {@link #terminal()} object is sent to the channel.
+ * In case of failure, the error is available via {@link #error()}.
+ */
+public class RustPublisherSubscriber extends NativeCallbackToRustChannelSupport {
+    // The names of the Publisher, Subscriber and Subscription interfaces that are supported
+    private static final String[][] REACTIVE_INTERFACES = {
+            {"java.util.concurrent.Flow$Publisher", "java.util.concurrent.Flow$Subscriber", "java.util.concurrent.Flow$Subscription"},
+            {"org.reactivestreams.Publisher", "org.reactivestreams.Subscriber", "org.reactivestreams.Subscription"}};
+
+    private final Object terminal = new Object();
+    private volatile Throwable error = null;
+    private String subscriptionInterfaceName;
+    // Guarded by this
+    private Object subscription = null;
+    private long pendingRequests = 0;
+    private boolean cancelled = false;
+
+    /**
+     * @return The object that is sent to the channel when the publisher completes or fails
+     */
+    public Object terminal() {
+        return terminal;
+    }
+
+    /**
+     * @return The error of the publisher, or null if there is no error
+     */
+    public Throwable error() {
+        return error;
+    }
+
+    /**
+     * Subscribes to a publisher.
+     *
+     * @param publisher A <code>java.util.concurrent.Flow.Publisher</code> or an <code>org.reactivestreams.Publisher</code>
+     * @throws Exception If the publisher is not supported or the subscription fails
+     */
+    public void subscribe(Object publisher) throws Exception {
+        for (String[] names : REACTIVE_INTERFACES) {
+            Class<?> publisherInterface = findInterface(publisher.getClass(), names[0]);
+            if (publisherInterface != null) {
+                ClassLoader classLoader = publisherInterface.getClassLoader() != null ?
+                        publisherInterface.getClassLoader() : RustPublisherSubscriber.class.getClassLoader();
+                Class<?> subscriberInterface = Class.forName(names[1], false, classLoader);
+                subscriptionInterfaceName = names[2];
+                Object subscriber = Proxy.newProxyInstance(
+                        classLoader,
+                        new Class<?>[]{subscriberInterface},
+                        (proxy, method, args) -> handle(proxy, method, args));
+                invoke(publisherInterface.getMethod("subscribe", subscriberInterface), publisher, subscriber);
+                return;
+            }
+        }
+        throw new IllegalArgumentException(publisher.getClass().getName()
+                + " is neither a java.util.concurrent.Flow.Publisher, nor an org.reactivestreams.Publisher");
+    }
+
+    /**
+     * Requests more items from the publisher.
+     *
+     * @param n The number of the items
+     * @throws Exception If the request fails
+     */
+    public synchronized void request(long n) throws Exception {
+        if (subscription == null) {
+            pendingRequests += n;
+        } else if (!cancelled) {
+            invokeSubscription("request", n);
+        }
+    }
+
+    /**
+     * Cancels the subscription.
+     *
+     * @throws Exception If the cancellation fails
+     */
+    public synchronized void cancel() throws Exception {
+        if (!cancelled) {
+            cancelled = true;
+            if (subscription != null) {
+                invokeSubscription("cancel");
+            }
+        }
+    }
+
+    private Object handle(Object proxy, Method method, Object[] args) throws Exception {
+        switch (method.getName()) {
+            case "onSubscribe":
+                onSubscribe(args[0]);
+                return null;
+            case "onNext":
+                doCallback(args[0]);
+                return null;
+            case "onError":
+                error = (Throwable) args[0];
+                doCallback(terminal);
+                return null;
+            case "onComplete":
+                doCallback(terminal);
+                return null;
+            case "equals":
+                return proxy == args[0];
+            case "hashCode":
+                return System.identityHashCode(proxy);
+            default:
+                return "RustPublisherSubscriber@" + Integer.toHexString(System.identityHashCode(proxy));
+        }
+    }
+
+    private synchronized void onSubscribe(Object subscription) throws Exception {
+        this.subscription = subscription;
+        if (cancelled) {
+            invokeSubscription("cancel");
+        } else if (pendingRequests > 0) {
+            long n = pendingRequests;
+            pendingRequests = 0;
+            invokeSubscription("request", n);
+        }
+    }
+
+    private void invokeSubscription(String methodName, Object... args) throws Exception {
+        Class<?> subscriptionInterface = findInterface(subscription.getClass(), subscriptionInterfaceName);
+        if (subscriptionInterface == null) {
+            throw new IllegalStateException("The subscription is not a " + subscriptionInterfaceName);
+        }
+        Class<?>[] parameterTypes = args.length == 0 ? new Class<?>[0] : new Class<?>[]{long.class};
+        invoke(subscriptionInterface.getMethod(methodName, parameterTypes), subscription, args);
+    }
+
+    private static void invoke(Method method, Object target, Object... args) throws Exception {
+        try {
+            method.invoke(target, args);
+        } catch (InvocationTargetException error) {
+            Throwable cause = error.getCause();
+            throw cause instanceof Exception ? (Exception) cause : error;
+        }
+    }
+
+    // Finds the interface with the given name that is implemented by the class, or any of its superclasses
+    static Class<?> findInterface(Class<?> clazz, String interfaceName) {
+        for (Class<?> c = clazz; c != null; c = c.getSuperclass()) {
+            for (Class<?> i : c.getInterfaces()) {
+                if (i.getName().equals(interfaceName)) {
+                    return i;
+                }
+                Class<?> found = findInterface(i, interfaceName);
+                if (found != null) {
+                    return found;
+                }
+            }
+        }
+        return null;
+    }
+}
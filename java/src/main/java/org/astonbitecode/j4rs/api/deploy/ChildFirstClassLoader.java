@@ -0,0 +1,85 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import java.io.IOException;
+import java.net.URL;
+import java.net.URLClassLoader;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.Enumeration;
+import java.util.List;
+
+/**
+ * A classloader that looks for classes and resources in its own URLs before delegating to its parent.
+ * <p>
+ * The classes of the JDK and of j4rs are always loaded by the parent, so that they are shared
+ * between the classloader and the rest of the JVM.
+ */
+public final class ChildFirstClassLoader extends URLClassLoader {
+    private static final String[] ParentFirstPrefixes = {"java.", "javax.", "jdk.", "sun.", "org.astonbitecode.j4rs."};
+
+    static {
+        ClassLoader.registerAsParallelCapable();
+    }
+
+    public ChildFirstClassLoader(URL[] urls, ClassLoader parent) {
+        super(urls, parent);
+    }
+
+    @Override
+    protected Class<?> loadClass(String name, boolean resolve) throws ClassNotFoundException {
+        synchronized (getClassLoadingLock(name)) {
+            Class<?> clazz = findLoadedClass(name);
+            if (clazz == null) {
+                if (isParentFirst(name)) {
+                    clazz = getParent().loadClass(name);
+                } else {
+                    try {
+                        clazz = findClass(name);
+                    } catch (ClassNotFoundException error) {
+                        clazz = getParent().loadClass(name);
+                    }
+                }
+            }
+            if (resolve) {
+                resolveClass(clazz);
+            }
+            return clazz;
+        }
+    }
+
+    @Override
+    public URL getResource(String name) {
+        URL url = findResource(name);
+        return url != null ? url : getParent().getResource(name);
+    }
+
+    @Override
+    public Enumeration<URL> getResources(String name) throws IOException {
+        List<URL> urls = new ArrayList<>(Collections.list(findResources(name)));
+        urls.addAll(Collections.list(getParent().getResources(name)));
+        return Collections.enumeration(urls);
+    }
+
+    private static boolean isParentFirst(String name) {
+        for (String prefix : ParentFirstPrefixes) {
+            if (name.startsWith(prefix)) {
+                return true;
+            }
+        }
+        return false;
+    }
+}
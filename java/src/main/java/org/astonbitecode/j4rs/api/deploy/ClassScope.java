@@ -0,0 +1,69 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import java.io.Closeable;
+import java.io.File;
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.net.MalformedURLException;
+import java.net.URL;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+/**
+ * An isolated scope of classes, loaded by a {@link ChildFirstClassLoader} out of its own jars and directories.
+ * <p>
+ * While a scope is entered by a thread, the classes that are referenced by name in the j4rs invocations
+ * of the thread are loaded by the classloader of the scope.
+ */
+public class ClassScope implements Closeable {
+    private final ChildFirstClassLoader classLoader;
+
+    public ClassScope(String[] paths) throws FileNotFoundException, MalformedURLException {
+        URL[] urls = new URL[paths.length];
+        for (int i = 0; i < paths.length; i++) {
+            File entry = new File(paths[i]);
+            if (!entry.exists()) {
+                throw new FileNotFoundException("Cannot add " + paths[i] + " to the class scope: it does not exist");
+            }
+            urls[i] = entry.getAbsoluteFile().toURI().toURL();
+        }
+        this.classLoader = new ChildFirstClassLoader(urls, ClassLoader.getSystemClassLoader());
+    }
+
+    public ClassLoader getClassLoader() {
+        return classLoader;
+    }
+
+    /**
+     * Makes the current thread load the classes of the j4rs invocations from this scope, until {@link #exit()} is called.
+     */
+    public void enter() {
+        Utils.pushScopedClassLoader(classLoader);
+    }
+
+    /**
+     * Makes the current thread load the classes of the j4rs invocations from the scope that was active before {@link #enter()}.
+     */
+    public void exit() {
+        Utils.popScopedClassLoader();
+    }
+
+    @Override
+    public void close() throws IOException {
+        classLoader.close();
+    }
+}
@@ -14,8 +14,25 @@
  */
 package org.astonbitecode.j4rs.api.deploy;
 
+import org.astonbitecode.j4rs.utils.Utils;
+
+import javax.xml.XMLConstants;
+import javax.xml.parsers.DocumentBuilderFactory;
+import javax.xml.parsers.ParserConfigurationException;
 import java.io.File;
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.net.JarURLConnection;
 import java.net.MalformedURLException;
+import java.net.URISyntaxException;
+import java.net.URL;
+import java.nio.ByteBuffer;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.ArrayList;
+import java.util.LinkedHashSet;
+import java.util.List;
+import java.util.Set;
 
 public class DeployUtils {
     /**
@@ -30,4 +47,128 @@ public class DeployUtils {
             classLoader.add(jar.toURI().toURL());
         }
     }
+
+    /**
+     * Creates a DocumentBuilderFactory for parsing XML that is downloaded or read from the file system.
+     * DOCTYPE declarations and external entities are not allowed, so that the parsing is not exposed to XXE attacks.
+     * @return The DocumentBuilderFactory
+     * @throws ParserConfigurationException In case the XML parser does not support the secure configuration
+     */
+    static DocumentBuilderFactory secureDocumentBuilderFactory() throws ParserConfigurationException {
+        DocumentBuilderFactory builderFactory = DocumentBuilderFactory.newInstance();
+        builderFactory.setFeature(XMLConstants.FEATURE_SECURE_PROCESSING, true);
+        builderFactory.setFeature("http://apache.org/xml/features/disallow-doctype-decl", true);
+        builderFactory.setFeature("http://xml.org/sax/features/external-general-entities", false);
+        builderFactory.setFeature("http://xml.org/sax/features/external-parameter-entities", false);
+        builderFactory.setFeature("http://apache.org/xml/features/nonvalidating/load-external-dtd", false);
+        builderFactory.setXIncludeAware(false);
+        builderFactory.setExpandEntityReferences(false);
+        return builderFactory;
+    }
+
+    /**
+     * Adds a jar, or a directory that contains classes, to the classpath
+     * @param path The path of the jar or directory to add
+     * @throws FileNotFoundException In case the path does not exist
+     * @throws MalformedURLException In case the path is not valid
+     */
+    public static void addClasspathEntry(String path) throws FileNotFoundException, MalformedURLException {
+        File entry = new File(path);
+        if (!entry.exists()) {
+            throw new FileNotFoundException("Cannot add " + path + " to the classpath: it does not exist");
+        }
+        j4rsClassLoader().add(entry.getAbsoluteFile().toURI().toURL());
+    }
+
+    /**
+     * Defines a class out of its bytecode
+     * @param name The binary name of the class
+     * @param bytecode The bytes of the class file
+     * @return The defined class
+     */
+    public static Class<?> defineClass(String name, ByteBuffer bytecode) {
+        return j4rsClassLoader().define(name, bytecode);
+    }
+
+    /**
+     * Lists the entries of the classpath: the ones of the <code>java.class.path</code> system property,
+     * followed by the ones that are added to the j4rs classloader, if it is the system classloader.
+     * @return The paths of the jars and directories of the classpath
+     */
+    public static List<String> effectiveClasspath() {
+        Set<String> entries = new LinkedHashSet<>();
+        for (String entry : System.getProperty("java.class.path", "").split(File.pathSeparator)) {
+            if (!entry.isEmpty()) {
+                entries.add(new File(entry).getAbsolutePath());
+            }
+        }
+        if (ClassLoader.getSystemClassLoader() instanceof J4rsClassLoader) {
+            for (URL url : ((J4rsClassLoader) ClassLoader.getSystemClassLoader()).getURLs()) {
+                Path path = toPath(url);
+                if (path != null) {
+                    entries.add(path.toString());
+                }
+            }
+        }
+        return new ArrayList<>(entries);
+    }
+
+    /**
+     * Finds the jar, or the directory, that provides a class, as seen by the classloader that loads the classes
+     * of the invocations.
+     * @param className The fully qualified name of the class
+     * @return The path of the jar or directory, or null if the class is not found,
+     * or is not provided by a jar or directory (like the classes of the JDK)
+     * @throws IOException In case the jar of the class cannot be accessed
+     */
+    public static String whichJarProvides(String className) throws IOException {
+        String resourceName = className.replace('.', '/') + ".class";
+        URL resource = Utils.currentClassLoader().getResource(resourceName);
+        if (resource == null) {
+            return null;
+        } else if (resource.getProtocol().equals("jar")) {
+            Path jar = toPath(((JarURLConnection) resource.openConnection()).getJarFileURL());
+            return jar != null ? jar.toString() : null;
+        } else {
+            Path classFile = toPath(resource);
+            if (classFile == null) {
+                return null;
+            }
+            // The root of the directory is above the directories of the package
+            Path root = classFile;
+            for (int i = 0; i < resourceName.split("/").length && root != null; i++) {
+                root = root.getParent();
+            }
+            return root != null ? root.toString() : null;
+        }
+    }
+
+    /**
+     * Returns the name of the class of the classloader that loads the classes of the invocations.
+     * @return The class name of the classloader
+     */
+    public static String currentClassLoaderName() {
+        return Utils.currentClassLoader().getClass().getName();
+    }
+
+    // Returns null for the URLs that are not files
+    private static Path toPath(URL url) {
+        if (!url.getProtocol().equals("file")) {
+            return null;
+        }
+        try {
+            return Paths.get(url.toURI());
+        } catch (URISyntaxException | IllegalArgumentException error) {
+            return null;
+        }
+    }
+
+    private static J4rsClassLoader j4rsClassLoader() {
+        ClassLoader systemClassLoader = ClassLoader.getSystemClassLoader();
+        if (!(systemClassLoader instanceof J4rsClassLoader)) {
+            throw new IllegalStateException(
+                    "The J4rsClassLoader is not the system classloader. Was the Jvm built with the default classloader?");
+        }
+        return (J4rsClassLoader) systemClassLoader;
+    }
 }
@@ -0,0 +1,208 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import com.fasterxml.jackson.core.type.TypeReference;
+import com.fasterxml.jackson.databind.ObjectMapper;
+
+import java.io.File;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.security.MessageDigest;
+import java.security.NoSuchAlgorithmException;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.TreeMap;
+
+/**
+ * Records the SHA-256 checksums of the deployed artifacts, in order to be able to verify their integrity later.
+ * <p>
+ * The records are kept in the file {@value #RECORDS_FILE_NAME} of each deployment target directory.
+ * Maven artifacts are recorded along with their coordinates, so that they can be downloaded again.
+ */
+public class DeploymentRecords {
+    static final String RECORDS_FILE_NAME = "j4rs-deployments.json";
+    static final String STATUS_OK = "ok";
+    static final String STATUS_MISSING = "missing";
+    static final String STATUS_CORRUPT = "corrupt";
+
+    private static final ObjectMapper mapper = new ObjectMapper();
+    private static final TypeReference<TreeMap<String, Map<String, String>>> recordsTypeRef =
+            new TypeReference<TreeMap<String, Map<String, String>>>() {
+            };
+
+    /**
+     * Records a deployed file.
+     *
+     * @param deployTarget The directory of the deployment
+     * @param fileName     The name of the deployed file
+     * @throws IOException In case the file cannot be read or the records cannot be written
+     */
+    static void record(String deployTarget, String fileName) throws IOException {
+        record(deployTarget, fileName, new HashMap<>());
+    }
+
+    /**
+     * Records a deployed Maven artifact.
+     */
+    static void recordMaven(String deployTarget, String fileName, String groupId, String artifactId, String version,
+                            String qualifier, String repoBase) throws IOException {
+        Map<String, String> record = new HashMap<>();
+        record.put("groupId", groupId);
+        record.put("artifactId", artifactId);
+        record.put("version", version);
+        record.put("qualifier", qualifier);
+        record.put("repoBase", repoBase);
+        record(deployTarget, fileName, record);
+    }
+
+    private static synchronized void record(String deployTarget, String fileName, Map<String, String> record)
+            throws IOException {
+        record.put("sha256", sha256(new File(deployTarget, fileName)));
+        TreeMap<String, Map<String, String>> records = readRecords(deployTarget);
+        records.put(fileName, record);
+        mapper.writerWithDefaultPrettyPrinter().writeValue(new File(deployTarget, RECORDS_FILE_NAME), records);
+    }
+
+    /**
+     * Verifies the recorded files of a directory against their checksums.
+     *
+     * @param deployTarget The directory to verify
+     * @return The result of the verification for each recorded file
+     * @throws IOException In case the records cannot be read
+     */
+    public static synchronized List<Map<String, String>> verify(String deployTarget) throws IOException {
+        List<Map<String, String>> results = new ArrayList<>();
+        for (Map.Entry<String, Map<String, String>> entry : readRecords(deployTarget).entrySet()) {
+            File file = new File(deployTarget, entry.getKey());
+            String expected = entry.getValue().get("sha256");
+            String actual = file.exists() ? sha256(file) : null;
+
+            Map<String, String> result = new HashMap<>();
+            result.put("file", entry.getKey());
+            result.put("expectedSha256", expected);
+            result.put("actualSha256", actual);
+            if (actual == null) {
+                result.put("status", STATUS_MISSING);
+            } else if (actual.equals(expected)) {
+                result.put("status", STATUS_OK);
+            } else {
+                result.put("status", STATUS_CORRUPT);
+            }
+            results.add(result);
+        }
+        return results;
+    }
+
+    /**
+     * Downloads again the recorded Maven artifacts of a directory that are missing or corrupt.
+     * Files that are not Maven artifacts cannot be repaired.
+     *
+     * @param deployTarget The directory to repair
+     * @return The result of the verification for each recorded file, after the repair
+     * @throws IOException In case the records cannot be read or an artifact cannot be downloaded
+     */
+    public static List<Map<String, String>> repair(String deployTarget) throws IOException {
+        TreeMap<String, Map<String, String>> records;
+        synchronized (DeploymentRecords.class) {
+            records = readRecords(deployTarget);
+        }
+        for (Map<String, String> result : verify(deployTarget)) {
+            Map<String, String> record = records.get(result.get("file"));
+            if (!STATUS_OK.equals(result.get("status")) && record.get("artifactId") != null) {
+                Files.deleteIfExists(new File(deployTarget, result.get("file")).toPath());
+                new SimpleMavenDeployer(record.get("repoBase"), false, deployTarget).deploy(record.get("groupId"),
+                        record.get("artifactId"), record.get("version"), record.get("qualifier"));
+            }
+        }
+        return verify(deployTarget);
+    }
+
+    /**
+     * Writes a lock file with the Maven artifacts that are recorded in a directory, along with their checksums.
+     *
+     * @param deployTarget The directory of the deployments
+     * @param lockPath     The path of the lock file to write
+     * @throws IOException In case the records cannot be read or the lock file cannot be written
+     */
+    public static synchronized void exportLock(String deployTarget, String lockPath) throws IOException {
+        List<Map<String, String>> locked = new ArrayList<>();
+        for (Map<String, String> record : readRecords(deployTarget).values()) {
+            if (record.get("artifactId") != null) {
+                locked.add(record);
+            }
+        }
+        mapper.writerWithDefaultPrettyPrinter().writeValue(new File(lockPath), locked);
+    }
+
+    /**
+     * Deploys the Maven artifacts of a lock file, verifying that their checksums are the locked ones.
+     *
+     * @param lockPath     The path of the lock file
+     * @param deployTarget The directory to deploy the artifacts to
+     * @throws IOException In case an artifact cannot be deployed or its checksum is not the locked one
+     */
+    public static void deployLocked(String lockPath, String deployTarget) throws IOException {
+        List<Map<String, String>> locked = mapper.readValue(new File(lockPath),
+                new TypeReference<List<Map<String, String>>>() {
+                });
+        for (Map<String, String> lockedArtifact : locked) {
+            SimpleMavenDeployer deployer = new SimpleMavenDeployer(lockedArtifact.get("repoBase"), deployTarget);
+            String fileName = deployer.generateArtifactName(lockedArtifact.get("artifactId"),
+                    lockedArtifact.get("version"), lockedArtifact.get("qualifier"));
+            File file = new File(deployTarget, fileName);
+            String expected = lockedArtifact.get("sha256");
+            if (file.exists() && !sha256(file).equals(expected)) {
+                Files.delete(file.toPath());
+            }
+            deployer.deploy(lockedArtifact.get("groupId"), lockedArtifact.get("artifactId"),
+                    lockedArtifact.get("version"), lockedArtifact.get("qualifier"));
+            String actual = sha256(file);
+            if (!actual.equals(expected)) {
+                throw new IOException(String.format("The checksum of %s is %s, but %s is locked", fileName, actual,
+                        expected));
+            }
+        }
+    }
+
+    private static TreeMap<String, Map<String, String>> readRecords(String deployTarget) throws IOException {
+        File recordsFile = new File(deployTarget, RECORDS_FILE_NAME);
+        if (!recordsFile.exists()) {
+            return new TreeMap<>();
+        }
+        return mapper.readValue(recordsFile, recordsTypeRef);
+    }
+
+    static String sha256(File file) throws IOException {
+        try (InputStream inputStream = Files.newInputStream(file.toPath())) {
+            MessageDigest digest = MessageDigest.getInstance("SHA-256");
+            byte[] buffer = new byte[8192];
+            int read;
+            while ((read = inputStream.read(buffer)) != -1) {
+                digest.update(buffer, 0, read);
+            }
+            StringBuilder hex = new StringBuilder();
+            for (byte b : digest.digest()) {
+                hex.append(String.format("%02x", b));
+            }
+            return hex.toString();
+        } catch (NoSuchAlgorithmException error) {
+            throw new IllegalStateException(error);
+        }
+    }
+}
@@ -54,6 +54,7 @@ public class FileSystemDeployer {
         try (FileOutputStream fileOutputStream = new FileOutputStream(fullJarDeployPath)) {
             fileOutputStream.getChannel().transferFrom(readableByteChannel, 0, Long.MAX_VALUE);
         }
+        DeploymentRecords.record(deployTarget, jarFile.getName());
         DeployUtils.addToClasspath(fullJarDeployPath);
     }
 }
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.IOException;
+import java.net.URL;
+import java.nio.channels.Channels;
+import java.nio.channels.ReadableByteChannel;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+
+/**
+ * Deploys artifacts from repositories that use an Ivy layout, like the ones that are used by Ivy and Gradle.
+ * <p>
+ * The layout is described by a pattern containing the tokens <code>[organisation]</code>, <code>[orgPath]</code>,
+ * <code>[module]</code>, <code>[revision]</code>, <code>[artifact]</code>, <code>[type]</code>,
+ * <code>[ext]</code> and <code>[classifier]</code>.
+ * Parts of the pattern that are enclosed in parentheses are optional: they are omitted if any of the tokens
+ * they contain has no value.
+ */
+public class IvyDeployer {
+    /**
+     * The default pattern of Ivy repositories.
+     */
+    public static final String IVY_PATTERN = "[organisation]/[module]/[revision]/[type]s/[artifact]-[revision](-[classifier]).[ext]";
+    /**
+     * The default pattern of Gradle's <code>ivy</code> layout.
+     */
+    public static final String GRADLE_PATTERN = "[organisation]/[module]/[revision]/[artifact]-[revision](-[classifier])(.[ext])";
+    private static final Pattern OPTIONAL_PATTERN = Pattern.compile("\\(([^()]*)\\)");
+    private static final Pattern TOKEN_PATTERN = Pattern.compile("\\[([a-zA-Z]+)]");
+
+    private final String repoBase;
+    private final String pattern;
+    private final String deployTarget;
+
+    public IvyDeployer(String repoBase, String pattern, String deployTarget) {
+        this.repoBase = repoBase.endsWith("/") ? repoBase.substring(0, repoBase.length() - 1) : repoBase;
+        this.pattern = pattern == null || pattern.isEmpty() ? IVY_PATTERN : pattern;
+        this.deployTarget = deployTarget;
+        new File(deployTarget).mkdirs();
+    }
+
+    public void deploy(String organisation, String module, String revision, String artifact, String type, String ext,
+                       String classifier) throws IOException {
+        Map<String, String> tokens = new HashMap<>();
+        tokens.put("organisation", organisation);
+        tokens.put("orgPath", organisation.replace(".", "/"));
+        tokens.put("module", module);
+        tokens.put("revision", revision);
+        tokens.put("artifact", artifact == null || artifact.isEmpty() ? module : artifact);
+        tokens.put("type", type == null || type.isEmpty() ? "jar" : type);
+        tokens.put("ext", ext == null || ext.isEmpty() ? "jar" : ext);
+        tokens.put("classifier", classifier);
+
+        String fileName = expand("[artifact]-[revision](-[classifier]).[ext]", tokens);
+        String fullDeployPath = deployTarget + File.separator + fileName;
+        if (!new File(fullDeployPath).exists()) {
+            String urlString = repoBase + "/" + expand(pattern, tokens);
+            ReadableByteChannel readableByteChannel = Channels.newChannel(new URL(urlString).openStream());
+            try (FileOutputStream fileOutputStream = new FileOutputStream(fullDeployPath)) {
+                fileOutputStream.getChannel().transferFrom(readableByteChannel, 0, Long.MAX_VALUE);
+            }
+            DeploymentRecords.record(deployTarget, fileName);
+        }
+
+        DeployUtils.addToClasspath(fullDeployPath);
+    }
+
+    static String expand(String pattern, Map<String, String> tokens) {
+        Matcher optionalMatcher = OPTIONAL_PATTERN.matcher(pattern);
+        StringBuffer withOptionals = new StringBuffer();
+        while (optionalMatcher.find()) {
+            String optional = optionalMatcher.group(1);
+            Matcher tokenMatcher = TOKEN_PATTERN.matcher(optional);
+            boolean allDefined = true;
+            while (tokenMatcher.find()) {
+                String value = tokens.get(tokenMatcher.group(1));
+                allDefined &= value != null && !value.isEmpty();
+            }
+            optionalMatcher.appendReplacement(withOptionals, Matcher.quoteReplacement(allDefined ? optional : ""));
+        }
+        optionalMatcher.appendTail(withOptionals);
+
+        Matcher tokenMatcher = TOKEN_PATTERN.matcher(withOptionals.toString());
+        StringBuffer expanded = new StringBuffer();
+        while (tokenMatcher.find()) {
+            String value = tokens.get(tokenMatcher.group(1));
+            tokenMatcher.appendReplacement(expanded, Matcher.quoteReplacement(value == null ? "" : value));
+        }
+        tokenMatcher.appendTail(expanded);
+        return expanded.toString();
+    }
+
+    public String getRepoBase() {
+        return repoBase;
+    }
+
+    public String getPattern() {
+        return pattern;
+    }
+}
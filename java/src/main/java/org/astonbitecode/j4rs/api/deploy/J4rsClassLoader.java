@@ -16,6 +16,8 @@ package org.astonbitecode.j4rs.api.deploy;
 
 import java.net.URL;
 import java.net.URLClassLoader;
+import java.nio.ByteBuffer;
+import java.security.ProtectionDomain;
 
 public final class J4rsClassLoader extends URLClassLoader {
     public J4rsClassLoader(ClassLoader offeredClassLoader) {
@@ -25,4 +27,8 @@ public final class J4rsClassLoader extends URLClassLoader {
     void add(URL url) {
         addURL(url);
     }
+
+    Class<?> define(String name, ByteBuffer bytecode) {
+        return defineClass(name, bytecode, (ProtectionDomain) null);
+    }
 }
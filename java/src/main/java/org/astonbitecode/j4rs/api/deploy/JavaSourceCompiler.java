@@ -0,0 +1,160 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.astonbitecode.j4rs.errors.InvocationException;
+
+import javax.tools.Diagnostic;
+import javax.tools.DiagnosticCollector;
+import javax.tools.FileObject;
+import javax.tools.ForwardingJavaFileManager;
+import javax.tools.JavaCompiler;
+import javax.tools.JavaFileObject;
+import javax.tools.SimpleJavaFileObject;
+import javax.tools.StandardJavaFileManager;
+import javax.tools.ToolProvider;
+import java.io.ByteArrayOutputStream;
+import java.io.File;
+import java.io.IOException;
+import java.io.OutputStream;
+import java.net.URI;
+import java.nio.ByteBuffer;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Locale;
+import java.util.Map;
+import java.util.stream.Collectors;
+
+/**
+ * Compiles Java sources in memory and defines the resulting classes in the j4rs classloader.
+ */
+public class JavaSourceCompiler {
+    /**
+     * Compiles Java sources against the effective classpath and defines the resulting classes,
+     * including the nested ones, in the j4rs classloader.
+     *
+     * @param classNames The fully qualified names of the top-level classes of the sources
+     * @param sources    The sources, in the order of the <code>classNames</code>
+     * @return The names of the defined classes
+     * @throws IOException If the compiler cannot be used
+     */
+    public static List<String> compileAndLoad(String[] classNames, String[] sources) throws IOException {
+        if (classNames.length != sources.length) {
+            throw new IllegalArgumentException("Every source needs one class name");
+        }
+        JavaCompiler compiler = ToolProvider.getSystemJavaCompiler();
+        if (compiler == null) {
+            throw new IllegalStateException("No Java compiler is available. Compiling needs a JDK, not a JRE");
+        }
+
+        List<JavaFileObject> compilationUnits = new ArrayList<>();
+        for (int i = 0; i < classNames.length; i++) {
+            compilationUnits.add(new SourceFile(classNames[i], sources[i]));
+        }
+        DiagnosticCollector<JavaFileObject> diagnostics = new DiagnosticCollector<>();
+        List<String> options = Arrays.asList(
+                "-classpath", String.join(File.pathSeparator, DeployUtils.effectiveClasspath()));
+        Map<String, ByteArrayOutputStream> classFiles = new LinkedHashMap<>();
+        try (StandardJavaFileManager standardFileManager = compiler.getStandardFileManager(diagnostics, Locale.ENGLISH, null);
+             ClassFileManager fileManager = new ClassFileManager(standardFileManager, classFiles)) {
+            boolean success = compiler.getTask(null, fileManager, diagnostics, options, null, compilationUnits).call();
+            if (!success) {
+                String errors = diagnostics.getDiagnostics().stream()
+                        .filter(diagnostic -> diagnostic.getKind() == Diagnostic.Kind.ERROR)
+                        .map(JavaSourceCompiler::format)
+                        .collect(Collectors.joining("\n"));
+                throw new InvocationException("Could not compile the Java sources:\n" + errors);
+            }
+        }
+
+        return define(classFiles);
+    }
+
+    // Defines the classes, postponing the ones whose superclasses or interfaces are not defined yet
+    private static List<String> define(Map<String, ByteArrayOutputStream> classFiles) {
+        List<String> defined = new ArrayList<>();
+        List<String> pending = new ArrayList<>(classFiles.keySet());
+        while (!pending.isEmpty()) {
+            List<String> postponed = new ArrayList<>();
+            NoClassDefFoundError lastError = null;
+            for (String name : pending) {
+                try {
+                    DeployUtils.defineClass(name, ByteBuffer.wrap(classFiles.get(name).toByteArray()));
+                    defined.add(name);
+                } catch (NoClassDefFoundError error) {
+                    postponed.add(name);
+                    lastError = error;
+                }
+            }
+            if (postponed.size() == pending.size()) {
+                throw new InvocationException("Could not define the compiled classes " + postponed, lastError);
+            }
+            pending = postponed;
+        }
+        return defined;
+    }
+
+    private static String format(Diagnostic<? extends JavaFileObject> diagnostic) {
+        String source = diagnostic.getSource() != null ? diagnostic.getSource().getName() : "";
+        return String.format("%s:%d: %s", source, diagnostic.getLineNumber(), diagnostic.getMessage(Locale.ENGLISH));
+    }
+
+    private static class SourceFile extends SimpleJavaFileObject {
+        private final String source;
+
+        SourceFile(String className, String source) {
+            super(URI.create("string:///" + className.replace('.', '/') + Kind.SOURCE.extension), Kind.SOURCE);
+            this.source = source;
+        }
+
+        @Override
+        public CharSequence getCharContent(boolean ignoreEncodingErrors) {
+            return source;
+        }
+    }
+
+    private static class ClassFile extends SimpleJavaFileObject {
+        private final ByteArrayOutputStream bytes;
+
+        ClassFile(String className, ByteArrayOutputStream bytes) {
+            super(URI.create("bytes:///" + className.replace('.', '/') + Kind.CLASS.extension), Kind.CLASS);
+            this.bytes = bytes;
+        }
+
+        @Override
+        public OutputStream openOutputStream() {
+            return bytes;
+        }
+    }
+
+    // Keeps the compiled classes in memory
+    private static class ClassFileManager extends ForwardingJavaFileManager<StandardJavaFileManager> {
+        private final Map<String, ByteArrayOutputStream> classFiles;
+
+        ClassFileManager(StandardJavaFileManager fileManager, Map<String, ByteArrayOutputStream> classFiles) {
+            super(fileManager);
+            this.classFiles = classFiles;
+        }
+
+        @Override
+        public JavaFileObject getJavaFileForOutput(Location location, String className, JavaFileObject.Kind kind, FileObject sibling) {
+            ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+            classFiles.put(className, bytes);
+            return new ClassFile(className, bytes);
+        }
+    }
+}
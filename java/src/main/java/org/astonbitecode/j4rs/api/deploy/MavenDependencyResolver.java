@@ -0,0 +1,405 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.Node;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import javax.xml.parsers.DocumentBuilderFactory;
+import javax.xml.parsers.ParserConfigurationException;
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.*;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+import java.util.stream.Collectors;
+
+/**
+ * Deploys a Maven artifact together with its transitive dependencies.
+ * <p>
+ * The POMs are parsed in order to find the dependencies, taking into account parent POMs, properties,
+ * dependencyManagement sections, scopes, optional dependencies and exclusions.
+ * Version conflicts are resolved the Maven way: the nearest declaration wins.
+ */
+public class MavenDependencyResolver {
+    private static final Pattern PROPERTY_PATTERN = Pattern.compile("\\$\\{([^}]+)}");
+    private static final Set<String> TRANSITIVE_SCOPES = new HashSet<>(Arrays.asList("compile", "runtime"));
+
+    private final List<SimpleMavenDeployer> deployers;
+    private final PomSource pomSource;
+    private final Map<String, PomModel> pomCache = new HashMap<>();
+
+    public MavenDependencyResolver(List<String> repoBases, String deployTarget) {
+        this(repoBases.stream()
+                .map(repoBase -> new SimpleMavenDeployer(repoBase, deployTarget))
+                .collect(Collectors.toList()));
+    }
+
+    /**
+     * Creates a resolver that uses the given deployers, which may be already configured with credentials or proxies.
+     * The deployers are tried in order for every artifact.
+     */
+    public MavenDependencyResolver(List<SimpleMavenDeployer> deployers) {
+        this.deployers = deployers;
+        this.pomSource = this::openPomFromRepos;
+    }
+
+    MavenDependencyResolver(PomSource pomSource) {
+        this.deployers = Collections.emptyList();
+        this.pomSource = pomSource;
+    }
+
+    /**
+     * Deploys the artifact and all of its transitive runtime dependencies.
+     *
+     * @return The coordinates (groupId:artifactId:version[:classifier]) of the deployed jars
+     */
+    public List<String> deploy(String groupId, String artifactId, String version, String qualifier) throws IOException {
+        List<String> deployed = new ArrayList<>();
+        for (Coordinates coordinates : resolve(groupId, artifactId, version, qualifier)) {
+            if (coordinates.hasJar) {
+                deployJar(coordinates);
+                deployed.add(coordinates.toString());
+            }
+        }
+        return deployed;
+    }
+
+    /**
+     * Resolves the dependency graph of an artifact, without deploying anything.
+     * The first element of the returned List is the artifact itself.
+     */
+    List<Coordinates> resolve(String groupId, String artifactId, String version, String qualifier) throws IOException {
+        List<Coordinates> resolved = new ArrayList<>();
+        Set<String> visited = new HashSet<>();
+        Deque<GraphNode> queue = new ArrayDeque<>();
+
+        Coordinates root = new Coordinates(groupId, artifactId, version, qualifier, true);
+        queue.add(new GraphNode(root, Collections.emptySet()));
+        visited.add(root.key());
+
+        while (!queue.isEmpty()) {
+            GraphNode node = queue.poll();
+            PomModel pom = loadPom(node.coordinates.groupId, node.coordinates.artifactId, node.coordinates.version);
+            // A pom packaging does not have any jar to deploy
+            Coordinates coordinates = "pom".equals(pom.packaging) ?
+                    node.coordinates.withoutJar() :
+                    node.coordinates;
+            resolved.add(coordinates);
+
+            for (Dependency dependency : pom.dependencies) {
+                if (dependency.optional || !TRANSITIVE_SCOPES.contains(dependency.scope)
+                        || isExcluded(node.exclusions, dependency.groupId, dependency.artifactId)
+                        || !visited.add(dependency.key())) {
+                    continue;
+                }
+                if (dependency.version == null || dependency.version.isEmpty()) {
+                    throw new IOException(String.format("Could not determine the version of %s (needed by %s)",
+                            dependency.key(), node.coordinates));
+                }
+                Set<String> exclusions = new HashSet<>(node.exclusions);
+                exclusions.addAll(dependency.exclusions);
+                queue.add(new GraphNode(new Coordinates(dependency.groupId, dependency.artifactId, dependency.version,
+                        dependency.classifier, !"pom".equals(dependency.type)), exclusions));
+            }
+        }
+
+        return resolved;
+    }
+
+    private void deployJar(Coordinates coordinates) throws IOException {
+        IOException lastError = null;
+        for (SimpleMavenDeployer deployer : deployers) {
+            try {
+                deployer.deploy(coordinates.groupId, coordinates.artifactId, coordinates.version, coordinates.classifier);
+                return;
+            } catch (IOException error) {
+                lastError = error;
+            }
+        }
+        throw new IOException("Could not deploy " + coordinates, lastError);
+    }
+
+    private InputStream openPomFromRepos(String groupId, String artifactId, String version) throws IOException {
+        IOException lastError = null;
+        for (SimpleMavenDeployer deployer : deployers) {
+            try {
+                return deployer.openPom(groupId, artifactId, version);
+            } catch (IOException error) {
+                lastError = error;
+            }
+        }
+        throw new IOException(String.format("Could not find the POM of %s:%s:%s", groupId, artifactId, version), lastError);
+    }
+
+    private PomModel loadPom(String groupId, String artifactId, String version) throws IOException {
+        String key = String.format("%s:%s:%s", groupId, artifactId, version);
+        PomModel cached = pomCache.get(key);
+        if (cached != null) {
+            return cached;
+        }
+
+        Element project;
+        try (InputStream inputStream = pomSource.open(groupId, artifactId, version)) {
+            DocumentBuilderFactory builderFactory = DeployUtils.secureDocumentBuilderFactory();
+            builderFactory.setNamespaceAware(false);
+            Document document = builderFactory.newDocumentBuilder().parse(inputStream);
+            project = document.getDocumentElement();
+        } catch (ParserConfigurationException | SAXException error) {
+            throw new IOException("Could not parse the POM of " + key, error);
+        }
+
+        PomModel pom = new PomModel();
+        Map<String, String> properties = new HashMap<>();
+
+        PomModel parentPom = null;
+        Element parent = child(project, "parent");
+        if (parent != null) {
+            parentPom = loadPom(text(parent, "groupId"), text(parent, "artifactId"), text(parent, "version"));
+            properties.putAll(parentPom.properties);
+            pom.managedDependencies.putAll(parentPom.managedDependencies);
+            properties.put("project.parent.groupId", parentPom.properties.get("project.groupId"));
+            properties.put("project.parent.version", parentPom.properties.get("project.version"));
+        }
+
+        Element propertiesElement = child(project, "properties");
+        if (propertiesElement != null) {
+            for (Element property : children(propertiesElement)) {
+                properties.put(property.getTagName(), property.getTextContent().trim());
+            }
+        }
+        String projectGroupId = Optional.ofNullable(text(project, "groupId")).orElse(groupId);
+        String projectVersion = Optional.ofNullable(text(project, "version")).orElse(version);
+        properties.put("project.groupId", projectGroupId);
+        properties.put("project.artifactId", artifactId);
+        properties.put("project.version", projectVersion);
+        properties.put("pom.groupId", projectGroupId);
+        properties.put("pom.version", projectVersion);
+        properties.put("groupId", projectGroupId);
+        properties.put("version", projectVersion);
+        pom.properties.putAll(properties);
+        pom.packaging = Optional.ofNullable(text(project, "packaging")).orElse("jar");
+
+        Element dependencyManagement = child(project, "dependencyManagement");
+        if (dependencyManagement != null) {
+            for (Dependency managed : parseDependencies(child(dependencyManagement, "dependencies"), properties)) {
+                if ("import".equals(managed.scope) && "pom".equals(managed.type)) {
+                    PomModel bom = loadPom(managed.groupId, managed.artifactId, managed.version);
+                    bom.managedDependencies.forEach(pom.managedDependencies::putIfAbsent);
+                } else {
+                    pom.managedDependencies.put(managed.key(), managed);
+                }
+            }
+        }
+
+        for (Dependency dependency : parseDependencies(child(project, "dependencies"), properties)) {
+            Dependency managed = pom.managedDependencies.get(dependency.key());
+            if (managed != null) {
+                if (dependency.version == null) {
+                    dependency.version = managed.version;
+                }
+                if (dependency.scope == null) {
+                    dependency.scope = managed.scope;
+                }
+                dependency.exclusions.addAll(managed.exclusions);
+            }
+            if (dependency.scope == null) {
+                dependency.scope = "compile";
+            }
+            dependency.version = normalizeVersion(dependency.version);
+            pom.dependencies.add(dependency);
+        }
+        // The dependencies of the parent are inherited, unless redeclared
+        if (parentPom != null) {
+            Set<String> declared = pom.dependencies.stream().map(Dependency::key).collect(Collectors.toSet());
+            parentPom.dependencies.stream()
+                    .filter(dependency -> !declared.contains(dependency.key()))
+                    .forEach(pom.dependencies::add);
+        }
+
+        pomCache.put(key, pom);
+        return pom;
+    }
+
+    private static List<Dependency> parseDependencies(Element dependenciesElement, Map<String, String> properties) {
+        List<Dependency> dependencies = new ArrayList<>();
+        if (dependenciesElement == null) {
+            return dependencies;
+        }
+        for (Element element : children(dependenciesElement)) {
+            if (!"dependency".equals(element.getTagName())) {
+                continue;
+            }
+            Dependency dependency = new Dependency();
+            dependency.groupId = interpolate(text(element, "groupId"), properties);
+            dependency.artifactId = interpolate(text(element, "artifactId"), properties);
+            dependency.version = interpolate(text(element, "version"), properties);
+            dependency.scope = interpolate(text(element, "scope"), properties);
+            dependency.type = Optional.ofNullable(interpolate(text(element, "type"), properties)).orElse("jar");
+            dependency.classifier = Optional.ofNullable(interpolate(text(element, "classifier"), properties)).orElse("");
+            dependency.optional = "true".equals(interpolate(text(element, "optional"), properties));
+            Element exclusions = child(element, "exclusions");
+            if (exclusions != null) {
+                for (Element exclusion : children(exclusions)) {
+                    dependency.exclusions.add(interpolate(text(exclusion, "groupId"), properties) + ":"
+                            + interpolate(text(exclusion, "artifactId"), properties));
+                }
+            }
+            dependencies.add(dependency);
+        }
+        return dependencies;
+    }
+
+    static String interpolate(String value, Map<String, String> properties) {
+        if (value == null) {
+            return null;
+        }
+        String result = value;
+        // Bounded, in order to protect against properties that reference each other
+        for (int i = 0; i < 10 && result.contains("${"); i++) {
+            Matcher matcher = PROPERTY_PATTERN.matcher(result);
+            StringBuffer sb = new StringBuffer();
+            while (matcher.find()) {
+                String replacement = properties.get(matcher.group(1));
+                matcher.appendReplacement(sb, Matcher.quoteReplacement(replacement != null ? replacement : matcher.group()));
+            }
+            matcher.appendTail(sb);
+            if (sb.toString().equals(result)) {
+                break;
+            }
+            result = sb.toString();
+        }
+        return result;
+    }
+
+    /**
+     * Version ranges are not resolved against the repository metadata; the lower bound (or the upper, if there is no
+     * lower one) is used instead.
+     */
+    static String normalizeVersion(String version) {
+        if (version == null || !(version.startsWith("[") || version.startsWith("("))) {
+            return version;
+        }
+        String range = version.substring(1, version.length() - 1);
+        return Arrays.stream(range.split(","))
+                .map(String::trim)
+                .filter(s -> !s.isEmpty())
+                .findFirst()
+                .orElse(version);
+    }
+
+    static boolean isExcluded(Set<String> exclusions, String groupId, String artifactId) {
+        return exclusions.contains(groupId + ":" + artifactId)
+                || exclusions.contains(groupId + ":*")
+                || exclusions.contains("*:" + artifactId)
+                || exclusions.contains("*:*");
+    }
+
+    private static List<Element> children(Element element) {
+        List<Element> elements = new ArrayList<>();
+        NodeList nodes = element.getChildNodes();
+        for (int i = 0; i < nodes.getLength(); i++) {
+            Node node = nodes.item(i);
+            if (node.getNodeType() == Node.ELEMENT_NODE) {
+                elements.add((Element) node);
+            }
+        }
+        return elements;
+    }
+
+    private static Element child(Element element, String name) {
+        for (Element child : children(element)) {
+            if (name.equals(child.getTagName())) {
+                return child;
+            }
+        }
+        return null;
+    }
+
+    private static String text(Element element, String name) {
+        Element child = child(element, name);
+        return child != null ? child.getTextContent().trim() : null;
+    }
+
+    @FunctionalInterface
+    interface PomSource {
+        InputStream open(String groupId, String artifactId, String version) throws IOException;
+    }
+
+    static class Coordinates {
+        final String groupId;
+        final String artifactId;
+        final String version;
+        final String classifier;
+        final boolean hasJar;
+
+        Coordinates(String groupId, String artifactId, String version, String classifier, boolean hasJar) {
+            this.groupId = groupId;
+            this.artifactId = artifactId;
+            this.version = version;
+            this.classifier = classifier == null ? "" : classifier;
+            this.hasJar = hasJar;
+        }
+
+        Coordinates withoutJar() {
+            return new Coordinates(groupId, artifactId, version, classifier, false);
+        }
+
+        String key() {
+            return groupId + ":" + artifactId;
+        }
+
+        @Override
+        public String toString() {
+            String coordinates = String.format("%s:%s:%s", groupId, artifactId, version);
+            return classifier.isEmpty() ? coordinates : coordinates + ":" + classifier;
+        }
+    }
+
+    private static class GraphNode {
+        final Coordinates coordinates;
+        final Set<String> exclusions;
+
+        GraphNode(Coordinates coordinates, Set<String> exclusions) {
+            this.coordinates = coordinates;
+            this.exclusions = exclusions;
+        }
+    }
+
+    private static class Dependency {
+        String groupId;
+        String artifactId;
+        String version;
+        String scope;
+        String type;
+        String classifier;
+        boolean optional;
+        final Set<String> exclusions = new HashSet<>();
+
+        String key() {
+            return groupId + ":" + artifactId;
+        }
+    }
+
+    private static class PomModel {
+        String packaging;
+        final Map<String, String> properties = new HashMap<>();
+        final Map<String, Dependency> managedDependencies = new HashMap<>();
+        final List<Dependency> dependencies = new ArrayList<>();
+    }
+}
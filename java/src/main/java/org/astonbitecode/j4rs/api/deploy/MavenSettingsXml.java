@@ -0,0 +1,182 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.Node;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import javax.xml.parsers.ParserConfigurationException;
+import java.io.File;
+import java.io.FileInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+
+/**
+ * The parts of a Maven <code>settings.xml</code> that are relevant for downloading artifacts:
+ * the credentials of the servers and the active proxy.
+ * <p>
+ * Encrypted passwords are not supported.
+ */
+class MavenSettingsXml {
+    static final String DEFAULT_LOCATION = System.getProperty("user.home") + File.separator + ".m2" + File.separator
+            + "settings.xml";
+    private static final Pattern PLACEHOLDER_PATTERN = Pattern.compile("\\$\\{([^}]+)}");
+
+    private final Map<String, Server> servers = new HashMap<>();
+    private Proxy proxy;
+
+    private MavenSettingsXml() {
+    }
+
+    /**
+     * Loads the settings from the specified path. If the path is null or empty, the default location
+     * (<code>~/.m2/settings.xml</code>) is used. A missing file results to empty settings.
+     */
+    static MavenSettingsXml load(String path) throws IOException {
+        File file = new File(path == null || path.isEmpty() ? DEFAULT_LOCATION : path);
+        if (!file.exists()) {
+            return new MavenSettingsXml();
+        }
+        try (InputStream inputStream = new FileInputStream(file)) {
+            return parse(inputStream);
+        }
+    }
+
+    static MavenSettingsXml parse(InputStream inputStream) throws IOException {
+        MavenSettingsXml settings = new MavenSettingsXml();
+        Element root;
+        try {
+            Document document = DeployUtils.secureDocumentBuilderFactory().newDocumentBuilder().parse(inputStream);
+            root = document.getDocumentElement();
+        } catch (ParserConfigurationException | SAXException error) {
+            throw new IOException("Could not parse the Maven settings", error);
+        }
+
+        Element servers = child(root, "servers");
+        if (servers != null) {
+            for (Element element : children(servers, "server")) {
+                Server server = new Server(text(element, "username"), text(element, "password"));
+                settings.servers.put(text(element, "id"), server);
+            }
+        }
+
+        Element proxies = child(root, "proxies");
+        if (proxies != null) {
+            for (Element element : children(proxies, "proxy")) {
+                if (!"false".equals(text(element, "active"))) {
+                    String port = text(element, "port");
+                    settings.proxy = new Proxy(
+                            text(element, "host"),
+                            port == null ? 8080 : Integer.parseInt(port),
+                            text(element, "username"),
+                            text(element, "password"),
+                            text(element, "nonProxyHosts"));
+                    break;
+                }
+            }
+        }
+
+        return settings;
+    }
+
+    Server getServer(String id) {
+        return servers.get(id);
+    }
+
+    Proxy getProxy() {
+        return proxy;
+    }
+
+    /**
+     * Replaces the <code>${env.NAME}</code> and <code>${NAME}</code> (system property) placeholders.
+     */
+    static String interpolate(String value) {
+        if (value == null) {
+            return null;
+        }
+        Matcher matcher = PLACEHOLDER_PATTERN.matcher(value);
+        StringBuffer sb = new StringBuffer();
+        while (matcher.find()) {
+            String name = matcher.group(1);
+            String replacement = name.startsWith("env.") ? System.getenv(name.substring(4)) : System.getProperty(name);
+            matcher.appendReplacement(sb, Matcher.quoteReplacement(replacement != null ? replacement : matcher.group()));
+        }
+        matcher.appendTail(sb);
+        return sb.toString();
+    }
+
+    private static Element child(Element element, String name) {
+        NodeList nodes = element.getChildNodes();
+        for (int i = 0; i < nodes.getLength(); i++) {
+            Node node = nodes.item(i);
+            if (node.getNodeType() == Node.ELEMENT_NODE && name.equals(node.getNodeName())) {
+                return (Element) node;
+            }
+        }
+        return null;
+    }
+
+    private static List<Element> children(Element element, String name) {
+        List<Element> elements = new ArrayList<>();
+        NodeList nodes = element.getChildNodes();
+        for (int i = 0; i < nodes.getLength(); i++) {
+            Node node = nodes.item(i);
+            if (node.getNodeType() == Node.ELEMENT_NODE && name.equals(node.getNodeName())) {
+                elements.add((Element) node);
+            }
+        }
+        return elements;
+    }
+
+    private static String text(Element element, String name) {
+        Element child = child(element, name);
+        return child != null ? interpolate(child.getTextContent().trim()) : null;
+    }
+
+    static class Server {
+        final String username;
+        final String password;
+
+        Server(String username, String password) {
+            this.username = username;
+            this.password = password;
+        }
+    }
+
+    static class Proxy {
+        final String host;
+        final int port;
+        final String username;
+        final String password;
+        final String nonProxyHosts;
+
+        Proxy(String host, int port, String username, String password, String nonProxyHosts) {
+            this.host = host;
+            this.port = port;
+            this.username = username;
+            this.password = password;
+            this.nonProxyHosts = nonProxyHosts;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import java.io.File;
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.net.URL;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.StandardCopyOption;
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+
+/**
+ * Jars whose classes can be reloaded while the JVM is running.
+ * <p>
+ * Each version of a jar is loaded by its own {@link ChildFirstClassLoader}. The classes that are referenced by name
+ * in the j4rs invocations are looked up in the latest versions of the reloadable jars, before the classpath.
+ * The objects that are already created keep using the classes of the version they were created with.
+ */
+public class ReloadableJars {
+    private static final Map<String, ChildFirstClassLoader> ClassLoaders = new ConcurrentHashMap<>();
+
+    /**
+     * Loads the current version of a jar, replacing any previous version of it.
+     *
+     * @param path The path of the jar
+     * @throws IOException In case the jar does not exist or cannot be read
+     */
+    public static synchronized void reload(String path) throws IOException {
+        File jar = new File(path).getAbsoluteFile();
+        if (!jar.isFile()) {
+            throw new FileNotFoundException("Cannot reload " + path + ": it does not exist");
+        }
+        // Load a copy, so that the jar can be updated again while its classes are in use
+        // and the cached contents of previous versions are not used
+        Path copy = Files.createTempFile("j4rs-reloaded-", ".jar");
+        copy.toFile().deleteOnExit();
+        Files.copy(jar.toPath(), copy, StandardCopyOption.REPLACE_EXISTING);
+        ChildFirstClassLoader classLoader = new ChildFirstClassLoader(
+                new URL[]{copy.toUri().toURL()}, ClassLoader.getSystemClassLoader());
+        // The previous classloader is not closed, as the objects of the previous version may still load classes
+        ClassLoaders.put(jar.getPath(), classLoader);
+    }
+
+    /**
+     * Loads a class from the latest version of the reloadable jar that contains it.
+     *
+     * @param className The fully qualified name of the class
+     * @return The class, or null if no reloadable jar contains it
+     * @throws ClassNotFoundException In case the class is contained in a jar, but cannot be loaded
+     */
+    public static Class<?> loadClass(String className) throws ClassNotFoundException {
+        if (ClassLoaders.isEmpty()) {
+            return null;
+        }
+        String resourceName = className.replace('.', '/') + ".class";
+        for (ChildFirstClassLoader classLoader : ClassLoaders.values()) {
+            if (classLoader.findResource(resourceName) != null) {
+                return Class.forName(className, true, classLoader);
+            }
+        }
+        return null;
+    }
+}
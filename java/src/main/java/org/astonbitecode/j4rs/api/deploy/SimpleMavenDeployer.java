@@ -27,10 +27,12 @@ import javax.xml.xpath.XPathConstants;
 import javax.xml.xpath.XPathExpressionException;
 import javax.xml.xpath.XPathFactory;
 import java.io.*;
-import java.net.MalformedURLException;
-import java.net.URL;
+import java.net.*;
 import java.nio.channels.Channels;
 import java.nio.channels.ReadableByteChannel;
+import java.nio.charset.StandardCharsets;
+import java.util.Arrays;
+import java.util.Base64;
 
 public class SimpleMavenDeployer {
     private static final String MAVEN_CENTRAL = "https://repo.maven.apache.org/maven2";
@@ -40,6 +42,10 @@ public class SimpleMavenDeployer {
     private final String repoBase;
     private final boolean checkLocalCache;
     private final String deployTarget;
+    private String username;
+    private String password;
+    private String token;
+    private MavenSettingsXml.Proxy proxy;
 
     public SimpleMavenDeployer() {
         this(MAVEN_CENTRAL, true, ".");
@@ -60,6 +66,54 @@ public class SimpleMavenDeployer {
         new File(deployTarget).mkdirs();
     }
 
+    /**
+     * Uses basic authentication when accessing the repository.
+     */
+    public SimpleMavenDeployer withCredentials(String username, String password) {
+        this.username = username;
+        this.password = password;
+        return this;
+    }
+
+    /**
+     * Uses a bearer token when accessing the repository.
+     */
+    public SimpleMavenDeployer withToken(String token) {
+        this.token = token;
+        return this;
+    }
+
+    /**
+     * Accesses the repository via an HTTP proxy. The username, password and nonProxyHosts may be null.
+     * <p>
+     * The nonProxyHosts follow the Maven format: a list of hosts separated by <code>|</code>, where <code>*</code>
+     * can be used as a wildcard.
+     */
+    public SimpleMavenDeployer withProxy(String host, int port, String username, String password, String nonProxyHosts) {
+        this.proxy = new MavenSettingsXml.Proxy(host, port, username, password, nonProxyHosts);
+        return this;
+    }
+
+    /**
+     * Applies the server credentials with the given id and the active proxy that are defined in a Maven
+     * <code>settings.xml</code>.
+     * Settings that have already been explicitly defined are not overridden.
+     *
+     * @param repoId The id of the server in the settings.xml
+     * @param path   The path of the settings.xml. If empty, <code>~/.m2/settings.xml</code> is used.
+     */
+    public SimpleMavenDeployer withSettingsXml(String repoId, String path) throws IOException {
+        MavenSettingsXml settings = MavenSettingsXml.load(path);
+        MavenSettingsXml.Server server = settings.getServer(repoId);
+        if (server != null && username == null && token == null) {
+            withCredentials(server.username, server.password);
+        }
+        if (proxy == null) {
+            proxy = settings.getProxy();
+        }
+        return this;
+    }
+
     public void deploy(String groupId, String artifactId, String version, String qualifier) throws IOException {
         String jarName = generateArtifactName(artifactId, version, qualifier);
         boolean searchRemoteRepo = true;
@@ -76,12 +130,13 @@ public class SimpleMavenDeployer {
             }
             if (searchRemoteRepo) {
                 String urlString = generateUrlTagret(groupId, artifactId, version, jarName);
-                ReadableByteChannel readableByteChannel = Channels.newChannel(new URL(urlString).openStream());
+                ReadableByteChannel readableByteChannel = Channels.newChannel(openStream(urlString));
                 try (FileOutputStream fileOutputStream = new FileOutputStream(fullJarDeployPath)) {
                     fileOutputStream.getChannel().transferFrom(readableByteChannel, 0, Long.MAX_VALUE);
                 }
             }
 
+            DeploymentRecords.recordMaven(deployTarget, jarName, groupId, artifactId, version, qualifier, repoBase);
             DeployUtils.addToClasspath(fullJarDeployPath);
         }
     }
@@ -114,30 +169,104 @@ public class SimpleMavenDeployer {
 
     String generateUrlTagret(String groupId, String artifactId, String version, String jarName) throws IOException {
         if (version.endsWith("-SNAPSHOT")) {
-            String latestSnapshotJarName = getLatestSnapshotName(groupId, artifactId, version);
+            String latestSnapshotJarName = getLatestSnapshotName(groupId, artifactId, version, "jar");
             return  String.format("%s/%s/%s/%s/%s", repoBase, groupId.replace(".", "/"), artifactId, version, latestSnapshotJarName);
         } else {
             return String.format("%s/%s/%s/%s/%s", repoBase, groupId.replace(".", "/"), artifactId, version, jarName);
         }
     }
 
-    private String getLatestSnapshotName(String groupId, String artifactId, String version) throws IOException {
+    private String getLatestSnapshotName(String groupId, String artifactId, String version, String extension) throws IOException {
         String metadataXmlUrl = String.format("%s/%s/%s/%s/%s", repoBase, groupId.replace(".", "/"), artifactId, version, "maven-metadata.xml");
-        ReadableByteChannel readableByteChannel = Channels.newChannel(new URL(metadataXmlUrl).openStream());
+        ReadableByteChannel readableByteChannel = Channels.newChannel(openStream(metadataXmlUrl));
         try (InputStream inputStream = Channels.newInputStream(readableByteChannel)) {
-            DocumentBuilderFactory builderFactory = DocumentBuilderFactory.newInstance();
+            DocumentBuilderFactory builderFactory = DeployUtils.secureDocumentBuilderFactory();
             DocumentBuilder builder = builderFactory.newDocumentBuilder();
             Document xmlDocument = builder.parse(inputStream);
             XPath xPath = XPathFactory.newInstance().newXPath();
             String timestamp = xPath.evaluate("/metadata/versioning/snapshot/timestamp", xmlDocument);
             String buildNumber = xPath.evaluate("/metadata/versioning/snapshot/buildNumber", xmlDocument);
             String snapshotVersion = version.replace("SNAPSHOT", (timestamp + "-" + buildNumber));
-            return  String.format("%s-%s.jar", artifactId, snapshotVersion);
+            return  String.format("%s-%s.%s", artifactId, snapshotVersion, extension);
         } catch (XPathExpressionException | ParserConfigurationException | SAXException e) {
             throw new RuntimeException(e);
         }
     }
 
+    /**
+     * Opens the POM of an artifact, looking first in the local Maven cache (if enabled) and then in the repository.
+     */
+    InputStream openPom(String groupId, String artifactId, String version) throws IOException {
+        String pomName = String.format("%s-%s.pom", artifactId, version);
+        if (checkLocalCache) {
+            File cachedPom = new File(generatePathTagret(M2_CACHE, groupId, artifactId, version, pomName));
+            if (cachedPom.exists()) {
+                return new FileInputStream(cachedPom);
+            }
+        }
+        String remotePomName = version.endsWith("-SNAPSHOT") ?
+                getLatestSnapshotName(groupId, artifactId, version, "pom") :
+                pomName;
+        String urlString = String.format("%s/%s/%s/%s/%s", repoBase, groupId.replace(".", "/"), artifactId, version, remotePomName);
+        return openStream(urlString);
+    }
+
+    InputStream openStream(String urlString) throws IOException {
+        URL url = new URL(urlString);
+        URLConnection connection;
+        if (proxy != null && !isNonProxyHost(url.getHost())) {
+            connection = url.openConnection(new Proxy(Proxy.Type.HTTP, new InetSocketAddress(proxy.host, proxy.port)));
+            if (proxy.username != null) {
+                connection.setRequestProperty("Proxy-Authorization", basicAuthorization(proxy.username, proxy.password));
+                // Needed for https, where the proxy is authenticated during the tunnel establishment.
+                // The Authenticator applies only to this connection; the default of the JVM is not replaced.
+                if (connection instanceof HttpURLConnection) {
+                    ((HttpURLConnection) connection).setAuthenticator(new ProxyAuthenticator(proxy));
+                }
+            }
+        } else {
+            connection = url.openConnection();
+        }
+        if (token != null) {
+            connection.setRequestProperty("Authorization", "Bearer " + token);
+        } else if (username != null) {
+            connection.setRequestProperty("Authorization", basicAuthorization(username, password));
+        }
+        return connection.getInputStream();
+    }
+
+    boolean isNonProxyHost(String host) {
+        if (proxy == null || proxy.nonProxyHosts == null || proxy.nonProxyHosts.isEmpty()) {
+            return false;
+        }
+        return Arrays.stream(proxy.nonProxyHosts.split("[|,]"))
+                .map(String::trim)
+                .filter(pattern -> !pattern.isEmpty())
+                .anyMatch(pattern -> host.matches(pattern.replace(".", "\\.").replace("*", ".*")));
+    }
+
+    private static String basicAuthorization(String username, String password) {
+        String credentials = username + ":" + (password == null ? "" : password);
+        return "Basic " + Base64.getEncoder().encodeToString(credentials.getBytes(StandardCharsets.UTF_8));
+    }
+
+    private static class ProxyAuthenticator extends Authenticator {
+        private final MavenSettingsXml.Proxy proxy;
+
+        ProxyAuthenticator(MavenSettingsXml.Proxy proxy) {
+            this.proxy = proxy;
+        }
+
+        @Override
+        protected PasswordAuthentication getPasswordAuthentication() {
+            if (getRequestorType() == RequestorType.PROXY && proxy.host.equalsIgnoreCase(getRequestingHost())) {
+                String password = proxy.password == null ? "" : proxy.password;
+                return new PasswordAuthentication(proxy.username, password.toCharArray());
+            }
+            return null;
+        }
+    }
+
     String generatePathTagret(String base, String groupId, String artifactId, String version, String jarName) {
         return String.format("%s%s%s%s%s%s%s%s%s", base, File.separator, groupId.replace(".", File.separator),
                 File.separator, artifactId, File.separator, version, File.separator, jarName);
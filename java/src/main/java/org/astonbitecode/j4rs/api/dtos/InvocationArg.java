@@ -130,6 +130,16 @@ public class InvocationArg implements Instance {
         return getInstance() != null ? getInstance().invokeStatic(methodName, args) : null;
     }
 
+    @Override
+    public Instance invokeWithSignature(String methodName, String[] paramTypes, InvocationArg... args) {
+        return getInstance() != null ? getInstance().invokeWithSignature(methodName, paramTypes, args) : null;
+    }
+
+    @Override
+    public Instance invokeOnInterface(String interfaceName, String methodName, InvocationArg... args) {
+        return getInstance() != null ? getInstance().invokeOnInterface(interfaceName, methodName, args) : null;
+    }
+
     @Override
     public void invokeAsyncToChannel(long channelAddress, String methodName, InvocationArg... args) {
         if (getInstance() != null) {
@@ -155,4 +165,11 @@ public class InvocationArg implements Instance {
     public Instance field(String fieldName) {
         return getInstance() != null ? getInstance().field(fieldName) : null;
     }
+
+    @Override
+    public void setField(String fieldName, InvocationArg arg) {
+        if (getInstance() != null) {
+            getInstance().setField(fieldName, arg);
+        }
+    }
 }
@@ -0,0 +1,169 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.eval;
+
+import jdk.jshell.ExpressionSnippet;
+import jdk.jshell.JShell;
+import jdk.jshell.JShellException;
+import jdk.jshell.Snippet;
+import jdk.jshell.SnippetEvent;
+import jdk.jshell.SourceCodeAnalysis;
+import jdk.jshell.VarSnippet;
+import org.astonbitecode.j4rs.errors.InvocationException;
+
+import java.io.File;
+import java.lang.reflect.Modifier;
+import java.util.List;
+import java.util.Locale;
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+import java.util.concurrent.atomic.AtomicInteger;
+import java.util.stream.Collectors;
+
+/**
+ * Evaluates Java code using JShell, in a persistent context.
+ * <p>
+ * The code is executed in the current JVM, so the evaluated values are the actual objects and objects of the JVM
+ * can be bound to variables of the context.
+ */
+public class JShellEvaluator implements AutoCloseable {
+    private static final AtomicInteger IDS = new AtomicInteger();
+    private static final Map<Integer, JShellEvaluator> EVALUATORS = new ConcurrentHashMap<>();
+
+    private final int id;
+    private final JShell jshell;
+    private final Map<String, Object> bindings = new ConcurrentHashMap<>();
+    private Object result;
+
+    public JShellEvaluator() {
+        this.id = IDS.incrementAndGet();
+        this.jshell = JShell.builder().executionEngine("local").build();
+        String classpath = System.getProperty("java.class.path", "");
+        for (String entry : classpath.split(File.pathSeparator)) {
+            if (!entry.isEmpty()) {
+                jshell.addToClasspath(entry);
+            }
+        }
+        EVALUATORS.put(id, this);
+    }
+
+    /**
+     * Evaluates Java code. The code may contain more than one snippets, like statements, declarations and expressions.
+     *
+     * @param code The code to evaluate.
+     * @return The value of the last snippet, or null if the last snippet has no value.
+     */
+    public synchronized Object eval(String code) {
+        Object value = null;
+        String remaining = code;
+        while (!remaining.trim().isEmpty()) {
+            SourceCodeAnalysis.CompletionInfo info = jshell.sourceCodeAnalysis().analyzeCompletion(remaining);
+            if (!info.completeness().isComplete()) {
+                throw new InvocationException("Incomplete code: " + remaining);
+            }
+            value = evalSnippet(info.source());
+            remaining = info.remaining();
+        }
+        return value;
+    }
+
+    /**
+     * Binds an object to a variable of the context.
+     *
+     * @param name  The name of the variable.
+     * @param value The object to bind.
+     */
+    public synchronized void bind(String name, Object value) {
+        bindings.put(name, value);
+        String binding = String.format("%s.binding(%d, \"%s\")", JShellEvaluator.class.getName(), id, name);
+        String type = value == null ? null : publicTypeName(value.getClass());
+        // Declare the variable with the type of the value if possible, falling back to Object
+        if (type == null || !isValid(jshell.eval(String.format("%s %s = (%s) %s;", type, name, type, binding)))) {
+            evalSnippet(String.format("Object %s = %s;", name, binding));
+        }
+    }
+
+    @Override
+    public void close() {
+        EVALUATORS.remove(id);
+        jshell.close();
+    }
+
+    /**
+     * Used by the evaluated code to retrieve the bound objects.
+     */
+    public static Object binding(int evaluatorId, String name) {
+        return EVALUATORS.get(evaluatorId).bindings.get(name);
+    }
+
+    /**
+     * Used by the evaluated code to pass the value of a snippet.
+     */
+    public static void setResult(int evaluatorId, Object value) {
+        EVALUATORS.get(evaluatorId).result = value;
+    }
+
+    private Object evalSnippet(String source) {
+        List<SnippetEvent> events = jshell.eval(source);
+        check(events);
+        String valueName = null;
+        for (SnippetEvent event : events) {
+            Snippet snippet = event.snippet();
+            if (event.causeSnippet() == null && snippet instanceof VarSnippet) {
+                valueName = ((VarSnippet) snippet).name();
+            } else if (event.causeSnippet() == null && snippet instanceof ExpressionSnippet) {
+                valueName = ((ExpressionSnippet) snippet).name();
+            }
+        }
+        if (valueName == null) {
+            return null;
+        }
+        // Retrieve the actual object of the value
+        check(jshell.eval(String.format("%s.setResult(%d, %s);", JShellEvaluator.class.getName(), id, valueName)));
+        Object value = result;
+        result = null;
+        return value;
+    }
+
+    private void check(List<SnippetEvent> events) {
+        for (SnippetEvent event : events) {
+            JShellException exception = event.exception();
+            if (exception != null) {
+                throw new InvocationException("Error while evaluating " + event.snippet().source(), exception);
+            }
+            if (event.causeSnippet() == null && event.status() == Snippet.Status.REJECTED) {
+                String diagnostics = jshell.diagnostics(event.snippet())
+                        .map(diag -> diag.getMessage(Locale.ENGLISH))
+                        .collect(Collectors.joining(", "));
+                throw new InvocationException("Invalid code " + event.snippet().source() + ": " + diagnostics);
+            }
+        }
+    }
+
+    private boolean isValid(List<SnippetEvent> events) {
+        return events.stream().allMatch(event -> event.exception() == null && event.status() == Snippet.Status.VALID);
+    }
+
+    private static String publicTypeName(Class<?> clazz) {
+        Class<?> current = clazz;
+        while (current != null) {
+            if (Modifier.isPublic(current.getModifiers()) && current.getCanonicalName() != null) {
+                return current.getCanonicalName();
+            }
+            current = current.getSuperclass();
+        }
+        return null;
+    }
+}
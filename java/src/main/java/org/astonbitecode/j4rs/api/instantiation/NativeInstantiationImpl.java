@@ -20,7 +20,10 @@ import org.astonbitecode.j4rs.api.dtos.InvocationArg;
 import org.astonbitecode.j4rs.api.dtos.InvocationArgGenerator;
 import org.astonbitecode.j4rs.api.invocation.InstanceGenerator;
 import org.astonbitecode.j4rs.api.invocation.JsonInvocationImpl;
+import org.astonbitecode.j4rs.api.invocation.MemberAccess;
+import org.astonbitecode.j4rs.api.services.json.Codec;
 import org.astonbitecode.j4rs.errors.InstantiationException;
+import org.astonbitecode.j4rs.json.JsonCodecService;
 import org.astonbitecode.j4rs.utils.Utils;
 
 import java.lang.reflect.Array;
@@ -32,6 +35,7 @@ import java.lang.reflect.WildcardType;
 import java.util.ArrayList;
 import java.util.Arrays;
 import java.util.HashMap;
+import java.util.LinkedHashMap;
 import java.util.List;
 import java.util.Map;
 import java.util.stream.Collectors;
@@ -48,6 +52,35 @@ public class NativeInstantiationImpl {
         }
     }
 
+    /**
+     * Creates an instance of the class <code>className</code>, using the constructor with the declared parameter types
+     * <code>paramTypes</code>.
+     * <p>
+     * Non-public constructors can be used if the access to non-public members is enabled in {@link MemberAccess}.
+     */
+    public static Instance instantiateWithSignature(String className, String[] paramTypes, InvocationArg... args) {
+        try {
+            Class<?> clazz = Utils.forNameEnhanced(className);
+            Class<?>[] paramClasses = new Class<?>[paramTypes.length];
+            for (int i = 0; i < paramTypes.length; i++) {
+                paramClasses[i] = Utils.forNameEnhanced(paramTypes[i]);
+            }
+            GeneratedArg[] params = generateArgObjects(args);
+            if (params.length != paramClasses.length) {
+                throw new IllegalArgumentException(String.format(
+                        "The constructor expects %d arguments, but %d were provided", paramClasses.length,
+                        params.length));
+            }
+            Object[] paramObjects = Arrays.stream(params).map(GeneratedArg::getObject).toArray();
+            Constructor<?> constructor = MemberAccess.makeAccessible(clazz.getDeclaredConstructor(paramClasses));
+            Object instance = constructor.newInstance(paramObjects);
+            return InstanceGenerator.create(instance, clazz);
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot create instance of " + className + " with signature "
+                    + Arrays.toString(paramTypes), error);
+        }
+    }
+
     public static Instance createForStatic(String className) {
         try {
             Class<?> clazz = Utils.forNameEnhanced(className);
@@ -67,6 +100,23 @@ public class NativeInstantiationImpl {
         }
     }
 
+    /**
+     * Creates a Java Array to be used as the variadic argument of a method invocation.
+     * <p>
+     * Unlike {@link #createJavaArray(String, InvocationArg...)}, the arguments do not need to be of the exact
+     * class <code>className</code>: they can be of any assignable class (or the wrappers of primitives, for
+     * primitive arrays).
+     */
+    public static Instance createJavaVarargsArray(String className, InvocationArg... args) {
+        try {
+            CreatedInstance createdInstance = createCollection(className, generateArgObjects(args),
+                    J4rsCollectionType.Array, false);
+            return new JsonInvocationImpl(createdInstance.object, createdInstance.clazz);
+        } catch (Exception error) {
+            throw new InstantiationException("Cannot create Java varargs Array of " + className, error);
+        }
+    }
+
     public static Instance createJavaList(String className, InvocationArg... args) {
         try {
             CreatedInstance createdInstance = createCollection(className, generateArgObjects(args),
@@ -88,6 +138,43 @@ public class NativeInstantiationImpl {
         }
     }
 
+    /**
+     * Creates a Java Map from a JSON array of entries. Each entry is a two-element array: <code>[key, value]</code>.
+     * <p>
+     * The keys and values are decoded to the classes <code>keyClassName</code> and <code>valueClassName</code>.
+     * If the class is <code>java.lang.Object</code>, they are kept as decoded from JSON
+     * (e.g. Strings, Numbers, Lists and Maps).
+     * The order of the entries is preserved.
+     */
+    public static Map<Object, Object> createJavaMapFromJson(String keyClassName, String valueClassName, String entriesJson) {
+        try {
+            Codec codec = JsonCodecService.getJsonCodec();
+            List<?> entries = codec.decode(entriesJson, "java.util.List");
+            Map<Object, Object> map = new LinkedHashMap<>();
+            for (Object entry : entries) {
+                List<?> keyValue = (List<?>) entry;
+                if (keyValue.size() != 2) {
+                    throw new IllegalArgumentException("Map entries should contain a key and a value");
+                }
+                map.put(decodeAs(codec, keyValue.get(0), keyClassName),
+                        decodeAs(codec, keyValue.get(1), valueClassName));
+            }
+            return map;
+        } catch (Exception error) {
+            throw new InstantiationException(
+                    String.format("Cannot create Java Map of keys %s and values %s", keyClassName, valueClassName),
+                    error);
+        }
+    }
+
+    private static Object decodeAs(Codec codec, Object value, String className) {
+        if (value == null || Object.class.getName().equals(className)) {
+            return value;
+        } else {
+            return codec.decode(codec.encode(value), className);
+        }
+    }
+
     static GeneratedArg[] generateArgObjects(InvocationArg[] args) throws Exception {
         return gen.generateArgObjects(args);
     }
@@ -172,6 +259,11 @@ public class NativeInstantiationImpl {
 
     static CreatedInstance createCollection(String className, GeneratedArg[] params, J4rsCollectionType collectionType)
             throws Exception {
+        return createCollection(className, params, collectionType, true);
+    }
+
+    static CreatedInstance createCollection(String className, GeneratedArg[] params, J4rsCollectionType collectionType,
+                                            boolean strict) throws Exception {
         boolean isJ4rsArray = className.equals(InvocationArg.CONTENTS_ARRAY);
         Class<?> clazz = isJ4rsArray ? Utils.forNameBasedOnArgs(params) : Utils.forNameEnhanced(className);
         Object arrayObj = Array.newInstance(clazz, params.length);
@@ -179,10 +271,16 @@ public class NativeInstantiationImpl {
         Class<?>[] paramTypes = Arrays.stream(params).map(param -> param.getClazz())
                 .toArray(size -> new Class<?>[size]);
 
-        if (!isJ4rsArray && !Arrays.stream(paramTypes).allMatch(type -> type.getName().equals(className))) {
+        if (!isJ4rsArray && strict && !Arrays.stream(paramTypes).allMatch(type -> type.getName().equals(className))) {
             throw new IllegalArgumentException(
                     "Could not create Java array. All the arguments should be of class " + className);
         }
+        // For primitive arrays, the unboxing and widening is performed while setting the elements
+        if (!isJ4rsArray && !strict && !clazz.isPrimitive()
+                && !Arrays.stream(paramTypes).allMatch(type -> type.isPrimitive() || clazz.isAssignableFrom(type))) {
+            throw new IllegalArgumentException(
+                    "Could not create Java array. All the arguments should be assignable to class " + className);
+        }
 
         Object[] paramObjects = Arrays.stream(params).map(param -> param.getObject()).toArray(size -> new Object[size]);
 
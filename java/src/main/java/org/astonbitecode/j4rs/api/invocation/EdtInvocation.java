@@ -0,0 +1,76 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.errors.InvocationException;
+
+import javax.swing.SwingUtilities;
+import java.lang.reflect.InvocationTargetException;
+import java.util.concurrent.Callable;
+import java.util.concurrent.atomic.AtomicReference;
+
+/**
+ * Executes invocations in the AWT event dispatching thread (EDT) and waits for their completion.
+ * <p>
+ * If the current thread is the EDT, the invocations are executed directly.
+ */
+public class EdtInvocation {
+
+    /**
+     * Executes the invocation in the EDT and returns its result.
+     *
+     * @param invocation  The invocation to execute
+     * @param description A description of the invocation, used in the error messages
+     * @return The result of the invocation
+     */
+    public static Instance invoke(Callable<Instance> invocation, String description) {
+        AtomicReference<Instance> result = new AtomicReference<>();
+        run(() -> {
+            try {
+                result.set(invocation.call());
+            } catch (RuntimeException error) {
+                throw error;
+            } catch (Exception error) {
+                throw new InvocationException("While invoking " + description, error);
+            }
+        });
+        return result.get();
+    }
+
+    /**
+     * Executes the runnable in the EDT and waits for it to complete.
+     *
+     * @param runnable The {@link Runnable} to execute
+     */
+    public static void run(Runnable runnable) {
+        if (SwingUtilities.isEventDispatchThread()) {
+            runnable.run();
+            return;
+        }
+        try {
+            SwingUtilities.invokeAndWait(runnable);
+        } catch (InvocationTargetException error) {
+            Throwable cause = error.getCause();
+            if (cause instanceof RuntimeException) {
+                throw (RuntimeException) cause;
+            }
+            throw new InvocationException("While executing in the event dispatching thread", cause);
+        } catch (InterruptedException error) {
+            Thread.currentThread().interrupt();
+            throw new InvocationException("Interrupted while waiting for the event dispatching thread", error);
+        }
+    }
+}
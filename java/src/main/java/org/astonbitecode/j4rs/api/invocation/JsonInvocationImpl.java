@@ -23,6 +23,7 @@ import org.astonbitecode.j4rs.api.dtos.InvocationArgGenerator;
 import org.astonbitecode.j4rs.api.value.JsonValueFactory;
 import org.astonbitecode.j4rs.errors.InvocationException;
 import org.astonbitecode.j4rs.rust.RustPointer;
+import org.astonbitecode.j4rs.utils.Utils;
 
 import java.lang.reflect.Field;
 import java.lang.reflect.GenericArrayType;
@@ -88,6 +89,41 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         }
     }
 
+    @Override
+    public Instance invokeWithSignature(String methodName, String[] paramTypes, InvocationArg... args) {
+        try {
+            Method methodToInvoke = findMethodWithSignature(this.clazz, methodName, paramTypes);
+            CreatedInstance createdInstance = invokeMethod(methodToInvoke, gen.generateArgObjects(args));
+            return InstanceGenerator.create(createdInstance.object, createdInstance.clazz,
+                    createdInstance.classGenTypes);
+        } catch (Exception error) {
+            throw new InvocationException("While invoking method " + methodName + " with signature "
+                    + Arrays.toString(paramTypes) + " of Class " + this.clazz.getName(), error);
+        }
+    }
+
+    @Override
+    public Instance invokeOnInterface(String interfaceName, String methodName, InvocationArg... args) {
+        try {
+            Class<?> interfaceClass = Utils.forNameEnhanced(interfaceName);
+            if (!interfaceClass.isInterface()) {
+                throw new IllegalArgumentException(interfaceName + " is not an interface");
+            }
+            if (this.object != null && !interfaceClass.isInstance(this.object)) {
+                throw new IllegalArgumentException(this.clazz.getName() + " does not implement " + interfaceName);
+            }
+            GeneratedArg[] generatedArgs = gen.generateArgObjects(args);
+            Class[] argTypes = Arrays.stream(generatedArgs).map(GeneratedArg::getClazz).toArray(size -> new Class[size]);
+            Method methodToInvoke = findMethodInHierarchy(interfaceClass, methodName, argTypes);
+            CreatedInstance createdInstance = invokeMethod(methodToInvoke, generatedArgs);
+            return InstanceGenerator.create(createdInstance.object, createdInstance.clazz,
+                    createdInstance.classGenTypes);
+        } catch (Exception error) {
+            throw new InvocationException("While invoking method " + methodName + " of interface " + interfaceName
+                    + " on Class " + this.clazz.getName(), error);
+        }
+    }
+
     @Override
     public void invokeAsyncToChannel(final long channelAddress, final String methodName, final InvocationArg... args) {
         try {
@@ -123,6 +159,8 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         // Check that the class of the invocation extends the
         // NativeCallbackToRustChannelSupport
         if (!NativeCallbackToRustChannelSupport.class.isAssignableFrom(this.clazz)) {
+            // Nobody is going to send to the channel
+            NativeCallbackToRustChannelSupport.release(channelAddress);
             throw new InvocationException("Cannot initialize callback channel for class " + this.clazz.getName()
                     + ". The class does not extend the class " + NativeCallbackToRustChannelSupport.class.getName());
         } else {
@@ -142,6 +180,16 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         }
     }
 
+    @Override
+    public void setField(String fieldName, InvocationArg arg) {
+        try {
+            setFieldValue(fieldName, gen.generateArgObjects(new InvocationArg[]{arg})[0]);
+        } catch (Exception error) {
+            throw new InvocationException(
+                    "Error while setting field " + fieldName + " of Class " + this.clazz.getName(), error);
+        }
+    }
+
     @Override
     public T getObject() {
         return object;
@@ -169,6 +217,11 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         return new CreatedInstance(field.getType(), fieldObject);
     }
 
+    void setFieldValue(String fieldName, GeneratedArg generatedArg) throws Exception {
+        Field field = this.clazz.getField(fieldName);
+        field.set(this.object, generatedArg.getObject());
+    }
+
     CreatedInstance invokeMethod(String methodName, GeneratedArg[] generatedArgs) throws Exception {
         Class[] argTypes = Arrays.stream(generatedArgs).map(invGeneratedArg -> {
             try {
@@ -185,7 +238,20 @@ public class JsonInvocationImpl<T> implements Instance<T> {
             }
         }).toArray(size -> new Object[size]);
 
-        Method methodToInvoke = findMethodInHierarchy(this.clazz, methodName, argTypes);
+        Method methodToInvoke = findMethodToInvoke(this.clazz, methodName, argTypes, argObjects);
+        return doInvokeMethod(methodToInvoke, argObjects);
+    }
+
+    CreatedInstance invokeMethod(Method methodToInvoke, GeneratedArg[] generatedArgs) throws Exception {
+        if (methodToInvoke.getParameterCount() != generatedArgs.length) {
+            throw new InvocationException(String.format("Method %s expects %d arguments, but %d were provided",
+                    methodToInvoke, methodToInvoke.getParameterCount(), generatedArgs.length));
+        }
+        Object[] argObjects = Arrays.stream(generatedArgs).map(GeneratedArg::getObject).toArray();
+        return doInvokeMethod(methodToInvoke, argObjects);
+    }
+
+    private CreatedInstance doInvokeMethod(Method methodToInvoke, Object[] argObjects) throws Exception {
         List<Type> retClassGenTypes = new ArrayList<>();
 
         Type returnType = methodToInvoke.getGenericReturnType();
@@ -195,7 +261,7 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         }
 
         Class<?> invokedMethodReturnType = methodToInvoke.getReturnType();
-        Object returnedObject = methodToInvoke.invoke(this.object, argObjects);
+        Object returnedObject = MemberAccess.makeAccessible(methodToInvoke).invoke(this.object, argObjects);
         return new CreatedInstance(invokedMethodReturnType, returnedObject, retClassGenTypes);
     }
 
@@ -215,7 +281,7 @@ public class JsonInvocationImpl<T> implements Instance<T> {
             }
         }).toArray(size -> new Object[size]);
 
-        Method methodToInvoke = findMethodInHierarchy(this.clazz, methodName, argTypes);
+        Method methodToInvoke = findMethodToInvoke(this.clazz, methodName, argTypes, argObjects);
         List<Type> retClassGenTypes = new ArrayList<>();
 
         Type returnType = methodToInvoke.getGenericReturnType();
@@ -244,6 +310,48 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         return future;
     }
 
+    /**
+     * Finds the method to invoke. If no method matches the arguments exactly and the {@link NumericCoercion} is lenient,
+     * the public method that the arguments can be coerced to with the lowest cost is returned
+     * and the <code>argObjects</code> are coerced to its parameters.
+     */
+    Method findMethodToInvoke(Class clazz, String methodName, Class[] argTypes, Object[] argObjects) throws NoSuchMethodException {
+        try {
+            return findMethodInHierarchy(clazz, methodName, argTypes);
+        } catch (NoSuchMethodException error) {
+            if (!NumericCoercion.isLenient()) {
+                throw error;
+            }
+            Method coercible = findCoercibleMethod(clazz, methodName, argTypes);
+            if (coercible == null) {
+                throw error;
+            }
+            NumericCoercion.coerce(coercible.getParameterTypes(), argObjects);
+            return coercible;
+        }
+    }
+
+    private Method findCoercibleMethod(Class<?> clazz, String methodName, Class[] argTypes) {
+        Method best = null;
+        int bestCost = Integer.MAX_VALUE;
+        for (Method method : clazz.getMethods()) {
+            if (!method.getName().equals(methodName) || method.getParameterCount() != argTypes.length) {
+                continue;
+            }
+            Class<?>[] paramTypes = method.getParameterTypes();
+            int cost = 0;
+            for (int i = 0; i < paramTypes.length && cost >= 0; i++) {
+                int paramCost = NumericCoercion.cost(argTypes[i], paramTypes[i]);
+                cost = paramCost < 0 ? -1 : cost + paramCost;
+            }
+            if (cost >= 0 && cost < bestCost) {
+                best = method;
+                bestCost = cost;
+            }
+        }
+        return best;
+    }
+
     Method findMethodInHierarchy(Class clazz, String methodName, Class[] argTypes) throws NoSuchMethodException {
         // Get the declared and methods defined in the interfaces of the class.
         Set<Method> methods = new HashSet<>(Arrays.asList(clazz.getDeclaredMethods()));
@@ -322,6 +430,32 @@ public class JsonInvocationImpl<T> implements Instance<T> {
         }
     }
 
+    static Method findMethodWithSignature(Class<?> clazz, String methodName, String[] paramTypes) throws Exception {
+        Class<?>[] paramClasses = new Class<?>[paramTypes.length];
+        for (int i = 0; i < paramTypes.length; i++) {
+            paramClasses[i] = Utils.forNameEnhanced(paramTypes[i]);
+        }
+        return findMethodWithSignature(clazz, methodName, paramClasses);
+    }
+
+    static Method findMethodWithSignature(Class<?> clazz, String methodName, Class<?>[] paramTypes) throws NoSuchMethodException {
+        try {
+            // Public methods, including the inherited ones and the default methods of the interfaces
+            return clazz.getMethod(methodName, paramTypes);
+        } catch (NoSuchMethodException error) {
+            // The non-public methods, only if their invocation is enabled
+            for (Class<?> c = clazz; c != null && MemberAccess.isEnabled(); c = c.getSuperclass()) {
+                try {
+                    return c.getDeclaredMethod(methodName, paramTypes);
+                } catch (NoSuchMethodException ignored) {
+                    // Try the superclass
+                }
+            }
+        }
+        throw new NoSuchMethodException("Method " + methodName + Arrays.toString(paramTypes) + " was not found in "
+                + clazz.getName() + " or its ancestors.");
+    }
+
     private Set<Class<?>> getInterfaces(Class<?> clazz) {
         final LinkedHashSet<Class<?>> interfacesFound = new LinkedHashSet<>();
         if (clazz != null) {
@@ -0,0 +1,58 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import java.lang.reflect.AccessibleObject;
+import java.lang.reflect.Member;
+import java.lang.reflect.Modifier;
+
+/**
+ * Controls whether the non-public constructors and methods may be invoked.
+ * <p>
+ * The access is disabled by default. When enabled, the non-public members are made accessible
+ * using {@link AccessibleObject#setAccessible(boolean)} before being invoked.
+ */
+public class MemberAccess {
+    private static volatile boolean enabled = false;
+
+    /**
+     * Enables or disables the invocation of non-public members.
+     *
+     * @param enabled true to allow invoking the non-public members
+     */
+    public static void setEnabled(boolean enabled) {
+        MemberAccess.enabled = enabled;
+    }
+
+    public static boolean isEnabled() {
+        return enabled;
+    }
+
+    /**
+     * Makes the member accessible, if the access to the non-public members is enabled and the member,
+     * or its declaring class, is not public.
+     *
+     * @param member The member to make accessible
+     * @param <T>    The type of the member
+     * @return The member
+     */
+    public static <T extends AccessibleObject & Member> T makeAccessible(T member) {
+        if (enabled && (!Modifier.isPublic(member.getModifiers())
+                || !Modifier.isPublic(member.getDeclaringClass().getModifiers()))) {
+            member.setAccessible(true);
+        }
+        return member;
+    }
+}
@@ -16,17 +16,38 @@ package org.astonbitecode.j4rs.api.invocation;
 
 import org.astonbitecode.j4rs.api.Instance;
 import org.astonbitecode.j4rs.errors.InvocationException;
+import org.astonbitecode.j4rs.rust.RustCleaner;
 import org.astonbitecode.j4rs.rust.RustPointer;
 
+import java.lang.ref.Cleaner;
 import java.util.Optional;
+import java.util.concurrent.locks.LockSupport;
+import java.util.concurrent.locks.ReadWriteLock;
+import java.util.concurrent.locks.ReentrantReadWriteLock;
 
 /**
  * Performs native callbacks to Rust channels
+ * <p>
+ * The sending side of the Rust channel is owned by this object. It is released when the channel is closed,
+ * or when this object is garbage collected.
  */
 public class NativeCallbackToRustChannelSupport {
+    // The results of the native callbacks
+    private static final int SENT = 0;
+    private static final int CLOSED = 1;
+    private static final int FULL = 2;
+    // The time to wait before retrying a callback to a full bounded channel
+    private static final long FULL_CHANNEL_WAIT_NANOS = 1_000_000L;
+
     private static native int docallbacktochannel(long channelPointerAddress, Instance inv);
 
+    private static native void releasechannel(long channelPointerAddress);
+
+    // The callbacks use the channel under the read lock, so that it is not released while being used
+    private final ReadWriteLock channelLock = new ReentrantReadWriteLock();
     private Optional<RustPointer> channelPointerOpt = Optional.empty();
+    private Cleaner.Cleanable channelRelease;
+    private volatile boolean channelClosed = false;
 
     static void initialize(String libname) {
         try {
@@ -40,19 +61,100 @@ public class NativeCallbackToRustChannelSupport {
 
     /**
      * Perform a callback
+     * <p>
+     * If the Rust channel is bounded and full, the callback either blocks until the Rust side consumes
+     * or the object is dropped, depending on how the channel was created in Rust.
+     * If the Rust channel is closed, the object is dropped.
      *
      * @param obj The {@link Object} to pass in the callback.
      */
     public void doCallback(Object obj) {
         if (channelPointerOpt.isPresent() && obj != null) {
-            docallbacktochannel(channelPointerOpt.get().getAddress(), InstanceGenerator.create(obj, obj.getClass()));
+            if (channelClosed) {
+                return;
+            }
+            Instance instance = InstanceGenerator.create(obj, obj.getClass());
+            int result = send(instance);
+            while (result == FULL && !Thread.currentThread().isInterrupted()) {
+                LockSupport.parkNanos(FULL_CHANNEL_WAIT_NANOS);
+                result = send(instance);
+            }
+            if (result == CLOSED) {
+                closeChannel();
+            }
         } else {
             throw new InvocationException(
                     "Cannot do callback. Please make sure that you don't try to access this method while being in the constructor of your class (that extends NativeCallbackSupport)");
         }
     }
 
+    private int send(Instance instance) {
+        channelLock.readLock().lock();
+        try {
+            if (channelClosed) {
+                return CLOSED;
+            }
+            return docallbacktochannel(channelPointerOpt.get().getAddress(), instance);
+        } finally {
+            channelLock.readLock().unlock();
+        }
+    }
+
+    /**
+     * Returns true if the Rust side closed the channel. Producers may check this in order to stop producing.
+     *
+     * @return true if the channel is closed.
+     */
+    public boolean isChannelClosed() {
+        return channelClosed;
+    }
+
+    /**
+     * Closes the channel and releases its sending side. Any further callbacks are dropped.
+     * <p>
+     * Rust calls this when the receiving side of the channel is closed.
+     */
+    public void closeChannel() {
+        channelLock.writeLock().lock();
+        try {
+            channelClosed = true;
+            releaseChannel();
+        } finally {
+            channelLock.writeLock().unlock();
+        }
+    }
+
     final void initPointer(RustPointer p) {
-        this.channelPointerOpt = Optional.of(p);
+        channelLock.writeLock().lock();
+        try {
+            releaseChannel();
+            this.channelPointerOpt = Optional.of(p);
+            this.channelClosed = false;
+            this.channelRelease = releasedOnCleanup(this, p.getAddress());
+        } finally {
+            channelLock.writeLock().unlock();
+        }
+    }
+
+    // Called under the write lock
+    private void releaseChannel() {
+        if (channelRelease != null) {
+            channelRelease.clean();
+            channelRelease = null;
+        }
+    }
+
+    // The cleanup action refers only to the address, so that the owner can become phantom reachable
+    private static Cleaner.Cleanable releasedOnCleanup(Object owner, long channelPointerAddress) {
+        return RustCleaner.register(owner, () -> releasechannel(channelPointerAddress));
+    }
+
+    /**
+     * Releases the sending side of a Rust channel that is not owned by any {@link NativeCallbackToRustChannelSupport}.
+     *
+     * @param channelPointerAddress The address of the sending side of the channel
+     */
+    static void release(long channelPointerAddress) {
+        releasechannel(channelPointerAddress);
     }
 }
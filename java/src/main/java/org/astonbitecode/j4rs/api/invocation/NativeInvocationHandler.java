@@ -0,0 +1,108 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.astonbitecode.j4rs.rust.RustCleaner;
+import org.astonbitecode.j4rs.rust.RustPointer;
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+
+/**
+ * An {@link InvocationHandler} that routes the method calls of a {@link Proxy}
+ * to a handler that is defined in Rust.
+ * <p>
+ * The Rust handler is dropped when this object becomes phantom reachable, in the thread of the {@link RustCleaner}.
+ */
+public class NativeInvocationHandler implements InvocationHandler {
+    private static native Instance invokenative(long handlerPointerAddress, String methodName, Instance[] args);
+
+    private static native void dropnative(long handlerPointerAddress);
+
+    private final RustPointer handlerPointer;
+    private final String interfaceName;
+
+    NativeInvocationHandler(RustPointer handlerPointer, String interfaceName) {
+        this.handlerPointer = handlerPointer;
+        this.interfaceName = interfaceName;
+    }
+
+    /**
+     * Creates a {@link Proxy} that implements the interface interfaceName and
+     * delegates all its method calls to the Rust handler that resides in the
+     * handlerPointerAddress.
+     *
+     * @param interfaceName         The fully qualified name of the interface to implement
+     * @param handlerPointerAddress The address of the Rust handler
+     * @return An {@link Object} that implements the interfaceName
+     */
+    public static Object newProxy(String interfaceName, long handlerPointerAddress) {
+        NativeInvocationHandler handler = null;
+        try {
+            Class<?> iface = Utils.forNameEnhanced(interfaceName);
+            if (!iface.isInterface()) {
+                throw new InvocationException(interfaceName + " is not an interface");
+            }
+            // From this point on, the handler is responsible to release the Rust handler
+            handler = new NativeInvocationHandler(new RustPointer(handlerPointerAddress), interfaceName);
+            droppedOnCleanup(handler, handlerPointerAddress);
+            return Proxy.newProxyInstance(iface.getClassLoader(), new Class[]{iface}, handler);
+        } catch (Exception error) {
+            if (handler == null) {
+                dropnative(handlerPointerAddress);
+            }
+            throw new InvocationException("Cannot create proxy for " + interfaceName, error);
+        }
+    }
+
+    @Override
+    public Object invoke(Object proxy, Method method, Object[] args) {
+        if (method.getDeclaringClass().equals(Object.class)) {
+            return invokeObjectMethod(proxy, method, args);
+        }
+        Object[] argsToUse = args == null ? new Object[0] : args;
+        Class<?>[] paramTypes = method.getParameterTypes();
+        Instance[] instances = new Instance[argsToUse.length];
+        for (int i = 0; i < argsToUse.length; i++) {
+            Object arg = argsToUse[i];
+            instances[i] = InstanceGenerator.create(arg, arg != null ? arg.getClass() : paramTypes[i]);
+        }
+        Instance result = invokenative(handlerPointer.getAddress(), method.getName(), instances);
+        return result != null ? result.getOrDeserializeJavaObject() : null;
+    }
+
+    private Object invokeObjectMethod(Object proxy, Method method, Object[] args) {
+        switch (method.getName()) {
+            case "equals":
+                return proxy == args[0];
+            case "hashCode":
+                return System.identityHashCode(proxy);
+            case "toString":
+                return "NativeInvocationHandler proxy for " + interfaceName + "@"
+                        + Integer.toHexString(System.identityHashCode(proxy));
+            default:
+                throw new InvocationException("Method " + method.getName() + " is not supported by j4rs proxies");
+        }
+    }
+
+    // The cleanup action refers only to the address, so that the handler can become phantom reachable
+    private static void droppedOnCleanup(NativeInvocationHandler handler, long handlerPointerAddress) {
+        RustCleaner.register(handler, () -> dropnative(handlerPointerAddress));
+    }
+}
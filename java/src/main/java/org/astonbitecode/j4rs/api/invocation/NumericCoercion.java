@@ -0,0 +1,126 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import java.util.Arrays;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Lenient coercion of the arguments of invocations.
+ * <p>
+ * When enabled, a method whose parameters do not match the types of the arguments exactly may still be invoked,
+ * if the arguments can be unboxed and widened to the parameter types, like in Java source code.
+ * For example, an {@link Integer} argument can satisfy <code>int</code>, <code>long</code>,
+ * <code>Long</code> or <code>double</code> parameters.
+ */
+public class NumericCoercion {
+    private static volatile boolean lenient = false;
+
+    private static final Map<Class<?>, Class<?>> PRIMITIVES = new HashMap<>();
+    // The primitive types that each primitive type widens to, including itself
+    private static final Map<Class<?>, List<Class<?>>> WIDENINGS = new HashMap<>();
+
+    static {
+        PRIMITIVES.put(Boolean.class, boolean.class);
+        PRIMITIVES.put(Byte.class, byte.class);
+        PRIMITIVES.put(Short.class, short.class);
+        PRIMITIVES.put(Character.class, char.class);
+        PRIMITIVES.put(Integer.class, int.class);
+        PRIMITIVES.put(Long.class, long.class);
+        PRIMITIVES.put(Float.class, float.class);
+        PRIMITIVES.put(Double.class, double.class);
+
+        WIDENINGS.put(boolean.class, Arrays.asList(boolean.class));
+        WIDENINGS.put(byte.class, Arrays.asList(byte.class, short.class, int.class, long.class, float.class, double.class));
+        WIDENINGS.put(short.class, Arrays.asList(short.class, int.class, long.class, float.class, double.class));
+        WIDENINGS.put(char.class, Arrays.asList(char.class, int.class, long.class, float.class, double.class));
+        WIDENINGS.put(int.class, Arrays.asList(int.class, long.class, float.class, double.class));
+        WIDENINGS.put(long.class, Arrays.asList(long.class, float.class, double.class));
+        WIDENINGS.put(float.class, Arrays.asList(float.class, double.class));
+        WIDENINGS.put(double.class, Arrays.asList(double.class));
+    }
+
+    /**
+     * Enables or disables the lenient coercion for all the invocations.
+     *
+     * @param lenient true to enable
+     */
+    public static void setLenient(boolean lenient) {
+        NumericCoercion.lenient = lenient;
+    }
+
+    /**
+     * @return true if the lenient coercion is enabled
+     */
+    public static boolean isLenient() {
+        return lenient;
+    }
+
+    /**
+     * Returns the cost of coercing an argument of type <code>argType</code> to a parameter of type <code>paramType</code>.
+     *
+     * @param argType   The type of the argument
+     * @param paramType The type of the parameter
+     * @return 0 if the parameter is assignable from the argument, a positive number that is lower for more
+     * specific coercions, or -1 if the argument cannot be coerced
+     */
+    static int cost(Class<?> argType, Class<?> paramType) {
+        if (paramType.isAssignableFrom(argType)) {
+            return 0;
+        }
+        List<Class<?>> widenings = WIDENINGS.get(primitive(argType));
+        int index = widenings != null ? widenings.indexOf(primitive(paramType)) : -1;
+        // Unboxing or boxing costs one more
+        return index < 0 ? -1 : index + 1;
+    }
+
+    /**
+     * Coerces the arguments to the parameter types, so that they can be passed to a reflective invocation.
+     *
+     * @param paramTypes The parameter types
+     * @param args       The arguments, which are replaced with the coerced ones
+     */
+    static void coerce(Class<?>[] paramTypes, Object[] args) {
+        for (int i = 0; i < args.length; i++) {
+            if (args[i] != null && !paramTypes[i].isInstance(args[i])) {
+                args[i] = coerce(args[i], primitive(paramTypes[i]));
+            }
+        }
+    }
+
+    private static Object coerce(Object arg, Class<?> primitiveType) {
+        Number number = arg instanceof Character ? Integer.valueOf((Character) arg) : (Number) arg;
+        if (primitiveType == short.class) {
+            return number.shortValue();
+        } else if (primitiveType == int.class) {
+            return number.intValue();
+        } else if (primitiveType == long.class) {
+            return number.longValue();
+        } else if (primitiveType == float.class) {
+            return number.floatValue();
+        } else if (primitiveType == double.class) {
+            return number.doubleValue();
+        } else {
+            // Same primitive type: the reflective invocation unboxes
+            return arg;
+        }
+    }
+
+    private static Class<?> primitive(Class<?> clazz) {
+        return clazz.isPrimitive() ? clazz : PRIMITIVES.get(clazz);
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.GeneratedArg;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.api.dtos.InvocationArgGenerator;
+import org.astonbitecode.j4rs.errors.InvocationException;
+
+import java.lang.reflect.Method;
+import java.lang.reflect.ParameterizedType;
+import java.lang.reflect.Type;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * A method invocation that is resolved once and may be executed repeatedly,
+ * without searching for the method to invoke each time.
+ */
+public class PreparedInvocation {
+    private final Object object;
+    private final Method method;
+    private final Class returnType;
+    private final List<Type> returnGenTypes;
+    private final InvocationArgGenerator gen = new InvocationArgGenerator();
+
+    /**
+     * Prepares the invocation of a method.
+     *
+     * @param instance   The {@link Instance} to invoke the method on. For static methods, this is an {@link Instance}
+     *                   of the class.
+     * @param methodName The name of the method
+     * @param paramTypes The fully qualified class names of the declared parameters of the method
+     * @throws Exception If the method is not found
+     */
+    public PreparedInvocation(Instance instance, String methodName, String[] paramTypes) throws Exception {
+        this.object = instance.getObject();
+        this.method = MemberAccess.makeAccessible(
+                JsonInvocationImpl.findMethodWithSignature(instance.getObjectClass(), methodName, paramTypes));
+        this.returnType = method.getReturnType();
+        Type genericReturnType = method.getGenericReturnType();
+        this.returnGenTypes = genericReturnType instanceof ParameterizedType
+                ? Arrays.asList(((ParameterizedType) genericReturnType).getActualTypeArguments())
+                : new ArrayList<>();
+    }
+
+    /**
+     * Invokes the prepared method.
+     *
+     * @param args The arguments to use for invoking the method
+     * @return A {@link Instance} instance containing the result of the invocation
+     */
+    public Instance invoke(InvocationArg... args) {
+        try {
+            GeneratedArg[] generatedArgs = gen.generateArgObjects(args);
+            if (method.getParameterCount() != generatedArgs.length) {
+                throw new InvocationException(String.format("Method %s expects %d arguments, but %d were provided",
+                        method, method.getParameterCount(), generatedArgs.length));
+            }
+            Object[] argObjects = new Object[generatedArgs.length];
+            for (int i = 0; i < generatedArgs.length; i++) {
+                argObjects[i] = generatedArgs[i].getObject();
+            }
+            Object returnedObject = method.invoke(object, argObjects);
+            return InstanceGenerator.create(returnedObject, returnType, returnGenTypes);
+        } catch (Exception error) {
+            throw new InvocationException("While invoking the prepared method " + method, error);
+        }
+    }
+}
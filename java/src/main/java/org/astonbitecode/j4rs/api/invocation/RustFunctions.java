@@ -0,0 +1,63 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import java.util.function.Consumer;
+import java.util.function.Function;
+import java.util.function.Predicate;
+import java.util.function.Supplier;
+
+/**
+ * Adapts callbacks that are implemented in Rust to the functional interfaces of <code>java.util.function</code>
+ * and to {@link Runnable}.
+ * <p>
+ * The returned objects are plain lambdas, so the default methods of the functional interfaces
+ * (e.g. {@link Function#andThen(Function)}) work as usual.
+ */
+public class RustFunctions {
+
+    /**
+     * A callback that is implemented in Rust, using a proxy.
+     */
+    public interface Callback {
+        /**
+         * Calls the Rust callback.
+         *
+         * @param arg The argument of the call. It is null for the functional interfaces that accept no arguments.
+         * @return The result of the call. It is null for the functional interfaces that return no result.
+         */
+        Object call(Object arg);
+    }
+
+    public static Function<Object, Object> function(Callback callback) {
+        return callback::call;
+    }
+
+    public static Consumer<Object> consumer(Callback callback) {
+        return callback::call;
+    }
+
+    public static Supplier<Object> supplier(Callback callback) {
+        return () -> callback.call(null);
+    }
+
+    public static Predicate<Object> predicate(Callback callback) {
+        return arg -> (Boolean) callback.call(arg);
+    }
+
+    public static Runnable runnable(Callback callback) {
+        return () -> callback.call(null);
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import java.util.concurrent.CountDownLatch;
+import java.util.concurrent.TimeUnit;
+
+/**
+ * A JVM shutdown hook that calls back to Rust and waits until the Rust side completes its work.
+ */
+public class RustShutdownHook extends NativeCallbackToRustChannelSupport {
+    // The maximum time to wait for the Rust side, so that a stuck hook does not prevent the JVM from halting
+    private static final long COMPLETION_TIMEOUT_SECONDS = 30;
+    private final CountDownLatch completed = new CountDownLatch(1);
+    private final Thread thread = new Thread(this::run, "j4rs-shutdown-hook");
+
+    /**
+     * Registers the hook to the {@link Runtime}.
+     */
+    public void register() {
+        Runtime.getRuntime().addShutdownHook(thread);
+    }
+
+    /**
+     * Removes the hook from the {@link Runtime}.
+     *
+     * @return true if the hook was registered
+     */
+    public boolean unregister() {
+        return Runtime.getRuntime().removeShutdownHook(thread);
+    }
+
+    /**
+     * Calls back to Rust and waits until {@link #complete()} is called, for up to 30 seconds.
+     * <p>
+     * If the Rust side has closed the channel, or the thread is interrupted, it does not wait.
+     */
+    public void run() {
+        doCallback(Boolean.TRUE);
+        if (!isChannelClosed()) {
+            try {
+                if (!completed.await(COMPLETION_TIMEOUT_SECONDS, TimeUnit.SECONDS)) {
+                    System.err.println("The Rust shutdown hooks did not complete within " + COMPLETION_TIMEOUT_SECONDS
+                            + " seconds");
+                }
+            } catch (InterruptedException error) {
+                Thread.currentThread().interrupt();
+            }
+        }
+    }
+
+    /**
+     * Called by Rust when the work of the hook is done.
+     */
+    public void complete() {
+        completed.countDown();
+    }
+}
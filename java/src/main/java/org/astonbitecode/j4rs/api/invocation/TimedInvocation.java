@@ -0,0 +1,70 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.astonbitecode.j4rs.errors.InvocationTimeoutException;
+
+import java.util.concurrent.Callable;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.Executors;
+import java.util.concurrent.Future;
+import java.util.concurrent.TimeUnit;
+import java.util.concurrent.TimeoutException;
+import java.util.concurrent.atomic.AtomicInteger;
+
+/**
+ * Executes invocations in a separate thread, bounding the time to wait for their completion.
+ */
+public class TimedInvocation {
+    private static final AtomicInteger threadCounter = new AtomicInteger(0);
+    private static final ExecutorService executor = Executors.newCachedThreadPool(runnable -> {
+        Thread thread = new Thread(runnable, "j4rs-timed-invocation-" + threadCounter.incrementAndGet());
+        thread.setDaemon(true);
+        return thread;
+    });
+
+    /**
+     * Executes the invocation and waits for its result for at most <code>timeoutMillis</code> milliseconds.
+     * If the invocation does not complete in time, the thread that executes it is interrupted.
+     *
+     * @param invocation    The invocation to execute
+     * @param description   A description of the invocation, used in the error messages
+     * @param timeoutMillis The timeout in milliseconds
+     * @return The result of the invocation
+     */
+    public static Instance invoke(Callable<Instance> invocation, String description, long timeoutMillis) {
+        Future<Instance> future = executor.submit(invocation);
+        try {
+            return future.get(timeoutMillis, TimeUnit.MILLISECONDS);
+        } catch (TimeoutException error) {
+            future.cancel(true);
+            throw new InvocationTimeoutException(
+                    "The invocation of " + description + " did not complete within " + timeoutMillis + " ms");
+        } catch (ExecutionException error) {
+            Throwable cause = error.getCause();
+            if (cause instanceof RuntimeException) {
+                throw (RuntimeException) cause;
+            }
+            throw new InvocationException("While invoking " + description, cause);
+        } catch (InterruptedException error) {
+            future.cancel(true);
+            Thread.currentThread().interrupt();
+            throw new InvocationException("Interrupted while waiting for the invocation of " + description, error);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import java.io.ByteArrayInputStream;
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.ObjectInputFilter;
+import java.io.ObjectInputStream;
+import java.io.ObjectOutputStream;
+import java.io.ObjectStreamClass;
+
+/**
+ * Serializes and deserializes objects using the Java serialization.
+ */
+public class JavaSerialization {
+
+    /**
+     * Serializes an object.
+     *
+     * @param obj The object to serialize. It must be {@link java.io.Serializable}.
+     * @return The bytes of the serialized object.
+     * @throws IOException If the object cannot be serialized.
+     */
+    public static byte[] serialize(Object obj) throws IOException {
+        ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        try (ObjectOutputStream out = new ObjectOutputStream(bytes)) {
+            out.writeObject(obj);
+        }
+        return bytes.toByteArray();
+    }
+
+    /**
+     * Deserializes an object.
+     * <p>
+     * The classes of the object are resolved using the context classloader of the current thread, if any.
+     * Only the JVM-wide filter (<code>jdk.serialFilter</code>) applies, if any. Untrusted bytes should be deserialized
+     * with {@link #deserialize(byte[], String)}.
+     *
+     * @param bytes The bytes of a serialized object.
+     * @return The deserialized object.
+     * @throws IOException            If the bytes cannot be deserialized.
+     * @throws ClassNotFoundException If a class of the serialized object cannot be found.
+     */
+    public static Object deserialize(byte[] bytes) throws IOException, ClassNotFoundException {
+        try (ObjectInputStream in = new ContextClassLoaderObjectInputStream(new ByteArrayInputStream(bytes))) {
+            return in.readObject();
+        }
+    }
+
+    /**
+     * Deserializes an object, accepting only the classes and limits that the filter pattern allows.
+     *
+     * @param bytes         The bytes of a serialized object.
+     * @param filterPattern A pattern, as defined by {@link ObjectInputFilter.Config#createFilter(String)}.
+     *                      E.g. <code>java.util.*;java.lang.*;!*</code>
+     * @return The deserialized object.
+     * @throws IOException            If the bytes cannot be deserialized, or the filter rejects them.
+     * @throws ClassNotFoundException If a class of the serialized object cannot be found.
+     */
+    public static Object deserialize(byte[] bytes, String filterPattern) throws IOException, ClassNotFoundException {
+        ObjectInputFilter filter = ObjectInputFilter.Config.createFilter(filterPattern);
+        try (ObjectInputStream in = new ContextClassLoaderObjectInputStream(new ByteArrayInputStream(bytes))) {
+            in.setObjectInputFilter(filter);
+            return in.readObject();
+        }
+    }
+
+    static class ContextClassLoaderObjectInputStream extends ObjectInputStream {
+        ContextClassLoaderObjectInputStream(InputStream in) throws IOException {
+            super(in);
+        }
+
+        @Override
+        protected Class<?> resolveClass(ObjectStreamClass desc) throws IOException, ClassNotFoundException {
+            ClassLoader classLoader = Thread.currentThread().getContextClassLoader();
+            if (classLoader != null) {
+                try {
+                    return Class.forName(desc.getName(), false, classLoader);
+                } catch (ClassNotFoundException error) {
+                    // Fall back to the default resolution
+                }
+            }
+            return super.resolveClass(desc);
+        }
+    }
+}
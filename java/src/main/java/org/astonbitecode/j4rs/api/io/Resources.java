@@ -0,0 +1,50 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Reads the resources of the classpath.
+ */
+public class Resources {
+
+    /**
+     * Reads a resource, using the classloader that loads the classes of the invocations.
+     *
+     * @param name The name of the resource, like <code>config/defaults.yaml</code>. A leading <code>/</code> is ignored.
+     * @return The bytes of the resource, or null if it is not found.
+     * @throws IOException If the resource cannot be read.
+     */
+    public static byte[] readResource(String name) throws IOException {
+        String resourceName = name.startsWith("/") ? name.substring(1) : name;
+        try (InputStream in = Utils.currentClassLoader().getResourceAsStream(resourceName)) {
+            if (in == null) {
+                return null;
+            }
+            ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+            byte[] buffer = new byte[8192];
+            int read;
+            while ((read = in.read(buffer)) != -1) {
+                bytes.write(buffer, 0, read);
+            }
+            return bytes.toByteArray();
+        }
+    }
+}
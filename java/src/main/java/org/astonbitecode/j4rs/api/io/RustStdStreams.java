@@ -0,0 +1,104 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport;
+
+import java.io.ByteArrayOutputStream;
+import java.io.OutputStream;
+import java.io.PrintStream;
+import java.io.UnsupportedEncodingException;
+import java.nio.charset.StandardCharsets;
+import java.util.HashMap;
+import java.util.Map;
+import java.util.function.Consumer;
+
+/**
+ * Replaces <code>System.out</code> and <code>System.err</code> with streams that forward
+ * each written line to a Rust channel.
+ * <p>
+ * Each line is sent as a {@link Map} with the name of the stream (<code>out</code> or <code>err</code>)
+ * under the key <code>stream</code> and the line, without the line terminator, under the key <code>line</code>.
+ */
+public class RustStdStreams extends NativeCallbackToRustChannelSupport {
+
+    /**
+     * Installs the redirecting streams.
+     */
+    public void redirect() {
+        System.setOut(newPrintStream("out"));
+        System.setErr(newPrintStream("err"));
+    }
+
+    private PrintStream newPrintStream(String stream) {
+        try {
+            return new PrintStream(new LineForwardingOutputStream(line -> forward(stream, line)), true, "UTF-8");
+        } catch (UnsupportedEncodingException error) {
+            throw new IllegalStateException(error);
+        }
+    }
+
+    private void forward(String stream, String line) {
+        Map<String, String> map = new HashMap<>();
+        map.put("stream", stream);
+        map.put("line", line);
+        doCallback(map);
+    }
+
+    /**
+     * An {@link OutputStream} that buffers the written bytes and passes them to a {@link Consumer}
+     * line by line, decoded as UTF-8.
+     */
+    static class LineForwardingOutputStream extends OutputStream {
+        private final Consumer<String> lineConsumer;
+        private final ByteArrayOutputStream buffer = new ByteArrayOutputStream();
+
+        LineForwardingOutputStream(Consumer<String> lineConsumer) {
+            this.lineConsumer = lineConsumer;
+        }
+
+        @Override
+        public synchronized void write(int b) {
+            if (b == '\n') {
+                forwardBuffer();
+            } else {
+                buffer.write(b);
+            }
+        }
+
+        @Override
+        public synchronized void write(byte[] b, int off, int len) {
+            for (int i = off; i < off + len; i++) {
+                write(b[i]);
+            }
+        }
+
+        @Override
+        public synchronized void close() {
+            if (buffer.size() > 0) {
+                forwardBuffer();
+            }
+        }
+
+        private void forwardBuffer() {
+            String line = new String(buffer.toByteArray(), StandardCharsets.UTF_8);
+            buffer.reset();
+            if (line.endsWith("\r")) {
+                line = line.substring(0, line.length() - 1);
+            }
+            lineConsumer.accept(line);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.kotlin;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.reflect.Field;
+import java.lang.reflect.Modifier;
+import java.util.Arrays;
+
+/**
+ * Helpers for the conventions of the classes that the Kotlin compiler generates.
+ */
+public class KotlinSupport {
+
+    /**
+     * Returns the name of the static field that holds the companion object of a class.
+     * <p>
+     * The field is named after the companion object, which is <code>Companion</code>, unless it is named
+     * explicitly, and its type is a class that is nested in the class.
+     *
+     * @param className The name of the class
+     * @return The name of the field of the companion object
+     * @throws ClassNotFoundException If the class is not found
+     */
+    public static String companionFieldName(String className) throws ClassNotFoundException {
+        Class<?> clazz = Utils.forNameEnhanced(className);
+        return Arrays.stream(clazz.getDeclaredFields())
+                .filter(field -> Modifier.isStatic(field.getModifiers()) && Modifier.isPublic(field.getModifiers()))
+                .filter(field -> field.getType().getDeclaringClass() == clazz)
+                .map(Field::getName)
+                .findFirst()
+                .orElseThrow(() -> new IllegalArgumentException("Class " + className + " has no companion object"));
+    }
+}
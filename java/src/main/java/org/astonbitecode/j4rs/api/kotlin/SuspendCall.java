@@ -0,0 +1,89 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.kotlin;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.reflect.Field;
+import java.lang.reflect.Proxy;
+import java.util.concurrent.CompletableFuture;
+
+/**
+ * A call of a Kotlin <code>suspend</code> function.
+ * <p>
+ * A suspend function gets a <code>kotlin.coroutines.Continuation</code> as an additional, last argument.
+ * It either returns its result, or returns a marker that it is suspended and resumes the continuation
+ * with its result later. Either way, the result completes the future of the call.
+ * <p>
+ * The Kotlin classes are loaded reflectively, so that j4rs does not depend on Kotlin.
+ */
+public class SuspendCall {
+    private final CompletableFuture<Object> future = new CompletableFuture<>();
+    private final Object continuation;
+    private final Object suspendedMarker;
+
+    public SuspendCall() throws ReflectiveOperationException {
+        Class<?> continuationClass = Utils.forNameEnhanced("kotlin.coroutines.Continuation");
+        Object context = Utils.forNameEnhanced("kotlin.coroutines.EmptyCoroutineContext").getField("INSTANCE").get(null);
+        suspendedMarker = Utils.forNameEnhanced("kotlin.coroutines.intrinsics.IntrinsicsKt")
+                .getMethod("getCOROUTINE_SUSPENDED")
+                .invoke(null);
+        // The failures are passed to resumeWith wrapped in a kotlin.Result.Failure
+        Class<?> failureClass = Utils.forNameEnhanced("kotlin.Result$Failure");
+        Field exceptionField = failureClass.getField("exception");
+
+        continuation = Proxy.newProxyInstance(continuationClass.getClassLoader(), new Class<?>[]{continuationClass},
+                (proxy, method, args) -> {
+                    switch (method.getName()) {
+                        case "getContext":
+                            return context;
+                        case "resumeWith":
+                            Object result = args[0];
+                            if (failureClass.isInstance(result)) {
+                                future.completeExceptionally((Throwable) exceptionField.get(result));
+                            } else {
+                                future.complete(result);
+                            }
+                            return null;
+                        case "equals":
+                            return proxy == args[0];
+                        case "hashCode":
+                            return System.identityHashCode(proxy);
+                        default:
+                            return "SuspendCall continuation";
+                    }
+                });
+    }
+
+    /**
+     * @return The continuation to pass to the suspend function
+     */
+    public Object getContinuation() {
+        return continuation;
+    }
+
+    /**
+     * Returns the future of the call, given the value that the suspend function returned.
+     *
+     * @param returned The value that the suspend function returned
+     * @return The future that completes with the result of the suspend function
+     */
+    public CompletableFuture<Object> future(Object returned) {
+        if (returned != suspendedMarker) {
+            future.complete(returned);
+        }
+        return future;
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.logging;
+
+import java.io.PrintWriter;
+import java.io.StringWriter;
+import java.util.logging.Formatter;
+import java.util.logging.Handler;
+import java.util.logging.Level;
+import java.util.logging.LogManager;
+import java.util.logging.LogRecord;
+import java.util.logging.Logger;
+import java.util.logging.SimpleFormatter;
+
+/**
+ * A java.util.logging {@link Handler} that forwards the log records to the <code>log</code> crate of Rust.
+ * <p>
+ * The levels of the <code>log</code> crate are represented by their numeric values:
+ * 1 is error, 2 is warn, 3 is info, 4 is debug and 5 is trace.
+ */
+public class RustLogHandler extends Handler {
+    private static native void lognative(int level, String target, String message);
+
+    private final Formatter messageFormatter = new SimpleFormatter();
+
+    /**
+     * Replaces the handlers of the root logger with a {@link RustLogHandler}.
+     *
+     * @param maxLevel The maximum level of the <code>log</code> crate to forward. 0 turns the logging off.
+     */
+    public static void install(int maxLevel) {
+        Logger rootLogger = LogManager.getLogManager().getLogger("");
+        for (Handler handler : rootLogger.getHandlers()) {
+            rootLogger.removeHandler(handler);
+        }
+        Level level = toJavaLevel(maxLevel);
+        RustLogHandler rustLogHandler = new RustLogHandler();
+        rustLogHandler.setLevel(level);
+        rootLogger.setLevel(level);
+        rootLogger.addHandler(rustLogHandler);
+    }
+
+    @Override
+    public void publish(LogRecord record) {
+        if (!isLoggable(record)) {
+            return;
+        }
+        String message = messageFormatter.formatMessage(record);
+        if (record.getThrown() != null) {
+            StringWriter stackTrace = new StringWriter();
+            record.getThrown().printStackTrace(new PrintWriter(stackTrace));
+            message = message + System.lineSeparator() + stackTrace;
+        }
+        String target = record.getLoggerName() != null ? record.getLoggerName() : "java";
+        lognative(toRustLevel(record.getLevel()), target, message);
+    }
+
+    @Override
+    public void flush() {
+    }
+
+    @Override
+    public void close() {
+    }
+
+    static int toRustLevel(Level level) {
+        int value = level.intValue();
+        if (value >= Level.SEVERE.intValue()) {
+            return 1;
+        } else if (value >= Level.WARNING.intValue()) {
+            return 2;
+        } else if (value >= Level.INFO.intValue()) {
+            return 3;
+        } else if (value >= Level.FINE.intValue()) {
+            return 4;
+        } else {
+            return 5;
+        }
+    }
+
+    static Level toJavaLevel(int rustLevel) {
+        switch (rustLevel) {
+            case 0:
+                return Level.OFF;
+            case 1:
+                return Level.SEVERE;
+            case 2:
+                return Level.WARNING;
+            case 3:
+                return Level.INFO;
+            case 4:
+                return Level.FINE;
+            default:
+                return Level.ALL;
+        }
+    }
+}
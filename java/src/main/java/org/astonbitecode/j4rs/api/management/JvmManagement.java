@@ -0,0 +1,88 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.management;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.lang.management.GarbageCollectorMXBean;
+import java.lang.management.ManagementFactory;
+import java.lang.management.MemoryUsage;
+import java.lang.management.ThreadInfo;
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Retrieves data of the management interface of the JVM, in a form that can be deserialized by Rust.
+ */
+public class JvmManagement {
+
+    public static Map<String, Object> heapUsage() {
+        return describeMemoryUsage(ManagementFactory.getMemoryMXBean().getHeapMemoryUsage());
+    }
+
+    public static Map<String, Object> nonHeapUsage() {
+        return describeMemoryUsage(ManagementFactory.getMemoryMXBean().getNonHeapMemoryUsage());
+    }
+
+    public static List<Map<String, Object>> garbageCollectors() {
+        List<Map<String, Object>> collectors = new ArrayList<>();
+        for (GarbageCollectorMXBean bean : ManagementFactory.getGarbageCollectorMXBeans()) {
+            Map<String, Object> collector = new LinkedHashMap<>();
+            collector.put("name", bean.getName());
+            collector.put("collectionCount", bean.getCollectionCount());
+            collector.put("collectionTimeMillis", bean.getCollectionTime());
+            collectors.add(collector);
+        }
+        return collectors;
+    }
+
+    public static int threadCount() {
+        return ManagementFactory.getThreadMXBean().getThreadCount();
+    }
+
+    public static int daemonThreadCount() {
+        return ManagementFactory.getThreadMXBean().getDaemonThreadCount();
+    }
+
+    public static int loadedClassCount() {
+        return ManagementFactory.getClassLoadingMXBean().getLoadedClassCount();
+    }
+
+    public static List<Map<String, Object>> threadDump() {
+        List<Map<String, Object>> threads = new ArrayList<>();
+        for (ThreadInfo info : ManagementFactory.getThreadMXBean().dumpAllThreads(false, false)) {
+            Map<String, Object> thread = new LinkedHashMap<>();
+            thread.put("id", info.getThreadId());
+            thread.put("name", info.getThreadName());
+            thread.put("state", info.getThreadState().name());
+            thread.put("daemon", info.isDaemon());
+            thread.put("stackTrace", Utils.describeStackTrace(info.getStackTrace()));
+            threads.add(thread);
+        }
+        return threads;
+    }
+
+    private static Map<String, Object> describeMemoryUsage(MemoryUsage usage) {
+        Map<String, Object> description = new LinkedHashMap<>();
+        // -1 means undefined
+        description.put("init", usage.getInit() >= 0 ? usage.getInit() : null);
+        description.put("used", usage.getUsed());
+        description.put("committed", usage.getCommitted());
+        description.put("max", usage.getMax() >= 0 ? usage.getMax() : null);
+        return description;
+    }
+}
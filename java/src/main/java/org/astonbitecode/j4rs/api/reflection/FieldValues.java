@@ -0,0 +1,160 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.reflection;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.invocation.InstanceGenerator;
+
+import java.lang.reflect.AccessibleObject;
+import java.lang.reflect.Array;
+import java.lang.reflect.Field;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Reads the values of the fields of objects using reflection, without serializing them.
+ */
+public class FieldValues {
+    /**
+     * Reads the values of fields of an object.
+     * <p>
+     * Each value is read by the getter of the field (<code>getX</code> or <code>isX</code>), or, if there is no getter,
+     * by the accessor of a record component or a fluent getter (<code>x()</code>), or else, from the field itself.
+     *
+     * @param object The object
+     * @param names  The names of the fields
+     * @return The values of the fields, in the order of their names
+     * @throws NoSuchFieldException If a field cannot be read by any of the above
+     * @throws Exception            If a getter throws
+     */
+    public static Object[] of(Object object, String[] names) throws Exception {
+        Object[] values = new Object[names.length];
+        for (int i = 0; i < names.length; i++) {
+            values[i] = valueOf(object, names[i]);
+        }
+        return values;
+    }
+
+    /**
+     * Like {@link #of(Object, String[])}, but returns the values as j4rs Instances.
+     *
+     * @param object The object
+     * @param names  The names of the fields
+     * @return The Instances of the values of the fields, in the order of their names
+     * @throws Exception If a field cannot be read
+     */
+    public static Instance[] instancesOf(Object object, String[] names) throws Exception {
+        return toInstances(of(object, names));
+    }
+
+    /**
+     * Returns the elements of an array, or of an <code>Iterable</code>.
+     *
+     * @param object The array or <code>Iterable</code>
+     * @return The elements
+     * @throws IllegalArgumentException If the object is neither an array nor an <code>Iterable</code>
+     */
+    public static Object[] elements(Object object) {
+        if (object.getClass().isArray()) {
+            Object[] elements = new Object[Array.getLength(object)];
+            for (int i = 0; i < elements.length; i++) {
+                elements[i] = Array.get(object, i);
+            }
+            return elements;
+        } else if (object instanceof Iterable) {
+            List<Object> elements = new ArrayList<>();
+            ((Iterable<?>) object).forEach(elements::add);
+            return elements.toArray();
+        } else {
+            throw new IllegalArgumentException(object.getClass().getName() + " is neither an array nor an Iterable");
+        }
+    }
+
+    /**
+     * Like {@link #elements(Object)}, but returns the elements as j4rs Instances.
+     *
+     * @param object The array or <code>Iterable</code>
+     * @return The Instances of the elements
+     */
+    public static Instance[] elementInstances(Object object) {
+        return toInstances(elements(object));
+    }
+
+    private static Instance[] toInstances(Object[] values) {
+        Instance[] instances = new Instance[values.length];
+        for (int i = 0; i < values.length; i++) {
+            Object value = values[i];
+            instances[i] = InstanceGenerator.create(value, value != null ? value.getClass() : Object.class);
+        }
+        return instances;
+    }
+
+    private static Object valueOf(Object object, String name) throws Exception {
+        Class<?> clazz = object.getClass();
+        String capitalized = Character.toUpperCase(name.charAt(0)) + name.substring(1);
+        for (String methodName : new String[]{"get" + capitalized, "is" + capitalized, name}) {
+            Method method = findMethod(clazz, methodName);
+            if (method != null) {
+                try {
+                    return accessible(method).invoke(object);
+                } catch (InvocationTargetException error) {
+                    throw error.getCause() instanceof Exception ? (Exception) error.getCause() : error;
+                }
+            }
+        }
+        for (Class<?> c = clazz; c != null; c = c.getSuperclass()) {
+            try {
+                Field field = c.getDeclaredField(name);
+                return accessible(field).get(object);
+            } catch (NoSuchFieldException error) {
+                // Look in the superclass
+            }
+        }
+        throw new NoSuchFieldException("No getter, accessor or field " + name + " in " + clazz.getName());
+    }
+
+    private static Method findMethod(Class<?> clazz, String methodName) {
+        try {
+            Method method = clazz.getMethod(methodName);
+            if (method.getReturnType() != void.class) {
+                return method;
+            }
+        } catch (NoSuchMethodException error) {
+            // Look in the non-public methods
+        }
+        for (Class<?> c = clazz; c != null; c = c.getSuperclass()) {
+            try {
+                Method method = c.getDeclaredMethod(methodName);
+                if (method.getReturnType() != void.class) {
+                    return method;
+                }
+            } catch (NoSuchMethodException error) {
+                // Look in the superclass
+            }
+        }
+        return null;
+    }
+
+    private static <T extends AccessibleObject> T accessible(T member) {
+        try {
+            member.setAccessible(true);
+        } catch (RuntimeException error) {
+            // The member may still be accessible if it is public
+        }
+        return member;
+    }
+}
@@ -0,0 +1,172 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.reflection;
+
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.io.IOException;
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.Comparator;
+import java.util.HashMap;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.jar.JarEntry;
+import java.util.jar.JarFile;
+import java.util.stream.Collectors;
+
+/**
+ * Checks for the existence of classes and methods, in order to allow Rust to adapt to the available classpath.
+ */
+public class Reflection {
+    private static final Map<Class<?>, Class<?>> BOXED = new HashMap<>();
+
+    static {
+        BOXED.put(boolean.class, Boolean.class);
+        BOXED.put(byte.class, Byte.class);
+        BOXED.put(short.class, Short.class);
+        BOXED.put(int.class, Integer.class);
+        BOXED.put(long.class, Long.class);
+        BOXED.put(float.class, Float.class);
+        BOXED.put(double.class, Double.class);
+        BOXED.put(char.class, Character.class);
+    }
+
+    /**
+     * Checks whether a class can be loaded.
+     * <p>
+     * A class that exists, but depends on classes that are not in the classpath, cannot be loaded.
+     *
+     * @param className The fully qualified name of the class
+     * @return true if the class can be loaded
+     */
+    public static boolean classExists(String className) {
+        try {
+            Utils.forNameEnhanced(className);
+            return true;
+        } catch (ClassNotFoundException | LinkageError error) {
+            return false;
+        }
+    }
+
+    /**
+     * Checks whether a class has a public method that can be invoked with arguments of the given classes.
+     *
+     * @param className  The fully qualified name of the class
+     * @param methodName The name of the method
+     * @param argTypes   The fully qualified names of the classes of the arguments
+     * @return true if such a method exists
+     * @throws ClassNotFoundException If the class cannot be loaded
+     */
+    public static boolean methodExists(String className, String methodName, String[] argTypes)
+            throws ClassNotFoundException {
+        Class<?>[] argClasses = new Class<?>[argTypes.length];
+        for (int i = 0; i < argTypes.length; i++) {
+            if (!classExists(argTypes[i])) {
+                return false;
+            }
+            argClasses[i] = Utils.forNameEnhanced(argTypes[i]);
+        }
+        return Arrays.stream(Utils.forNameEnhanced(className).getMethods())
+                .anyMatch(method -> method.getName().equals(methodName) && accepts(method, argClasses));
+    }
+
+    /**
+     * Checks whether a class is annotated with a runtime-visible annotation.
+     *
+     * @param className      The name of the class
+     * @param annotationName The name of the class of the annotation
+     * @return true if the class is annotated
+     * @throws ClassNotFoundException If the class is not found
+     */
+    public static boolean hasAnnotation(String className, String annotationName) throws ClassNotFoundException {
+        return Arrays.stream(Utils.forNameEnhanced(className).getAnnotations())
+                .anyMatch(annotation -> annotation.annotationType().getName().equals(annotationName));
+    }
+
+    /**
+     * Lists the public top-level classes of a jar, which can be loaded by the current classloader.
+     *
+     * @param jarPath The path of the jar
+     * @return The names of the classes, sorted
+     * @throws IOException If the jar cannot be read
+     */
+    public static List<String> listPublicClasses(String jarPath) throws IOException {
+        List<String> classNames;
+        try (JarFile jar = new JarFile(jarPath)) {
+            classNames = jar.stream()
+                    .map(JarEntry::getName)
+                    .filter(name -> name.endsWith(".class") && !name.contains("$") && !name.contains("-"))
+                    .map(name -> name.substring(0, name.length() - ".class".length()).replace('/', '.'))
+                    .sorted()
+                    .collect(Collectors.toList());
+        }
+        List<String> publicClassNames = new ArrayList<>();
+        for (String className : classNames) {
+            try {
+                if (Modifier.isPublic(Class.forName(className, false, Utils.currentClassLoader()).getModifiers())) {
+                    publicClassNames.add(className);
+                }
+            } catch (ClassNotFoundException | LinkageError error) {
+                // Not loadable by the current classloader
+            }
+        }
+        return publicClassNames;
+    }
+
+    /**
+     * Describes the public methods of a class, including the inherited ones, sorted by their names.
+     *
+     * @param className The fully qualified name of the class
+     * @return The descriptions of the methods
+     * @throws ClassNotFoundException If the class cannot be loaded
+     */
+    public static List<Map<String, Object>> listMethods(String className) throws ClassNotFoundException {
+        List<Map<String, Object>> methods = new ArrayList<>();
+        Method[] sorted = Utils.forNameEnhanced(className).getMethods();
+        Arrays.sort(sorted, Comparator.comparing(Method::getName).thenComparing(Method::getParameterCount));
+        for (Method method : sorted) {
+            Map<String, Object> description = new LinkedHashMap<>();
+            description.put("name", method.getName());
+            description.put("parameterTypes",
+                    Arrays.stream(method.getParameterTypes()).map(Class::getName).collect(Collectors.toList()));
+            description.put("returnType", method.getReturnType().getName());
+            description.put("declaringClass", method.getDeclaringClass().getName());
+            description.put("static", Modifier.isStatic(method.getModifiers()));
+            description.put("varArgs", method.isVarArgs());
+            methods.add(description);
+        }
+        return methods;
+    }
+
+    private static boolean accepts(Method method, Class<?>[] argClasses) {
+        Class<?>[] parameterTypes = method.getParameterTypes();
+        if (parameterTypes.length != argClasses.length) {
+            return false;
+        }
+        for (int i = 0; i < parameterTypes.length; i++) {
+            Class<?> parameterType = parameterTypes[i];
+            Class<?> argClass = argClasses[i];
+            boolean boxedMatch = parameterType.isPrimitive() && BOXED.get(parameterType).equals(argClass);
+            if (!parameterType.isAssignableFrom(argClass) && !boxedMatch) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
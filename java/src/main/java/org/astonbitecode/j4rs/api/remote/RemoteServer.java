@@ -0,0 +1,212 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.remote;
+
+import com.fasterxml.jackson.databind.JsonNode;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import com.fasterxml.jackson.databind.node.ObjectNode;
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.api.instantiation.NativeInstantiationImpl;
+import org.astonbitecode.j4rs.utils.Utils;
+
+import java.io.BufferedReader;
+import java.io.IOException;
+import java.io.InputStreamReader;
+import java.io.OutputStreamWriter;
+import java.io.Writer;
+import java.net.Socket;
+import java.nio.charset.StandardCharsets;
+import java.util.HashMap;
+import java.util.Map;
+
+/**
+ * Serves the invocations of a Rust process, when the JVM runs out of process.
+ * <p>
+ * The server connects to the loopback address and the port that are passed as arguments and identifies itself
+ * with the token of the <code>J4RS_REMOTE_TOKEN</code> environment variable. Then, the requests are read from
+ * and the responses are written to the connection, one json object per line. The standard output and error
+ * are not used by the protocol, so anything that Java or native code writes there does not affect the invocations.
+ * The {@link Instance}s that are created are kept under numeric handles, until Rust releases them.
+ */
+public class RemoteServer {
+    private static final String TOKEN_ENV = "J4RS_REMOTE_TOKEN";
+    private static final ObjectMapper Mapper = new ObjectMapper();
+    private final Map<Long, Instance> instances = new HashMap<>();
+    private long nextHandle = 1;
+
+    public static void main(String[] args) throws IOException {
+        if (args.length != 2) {
+            throw new IllegalArgumentException("Usage: RemoteServer <address> <port>");
+        }
+        try (Socket socket = new Socket(args[0], Integer.parseInt(args[1]))) {
+            socket.setTcpNoDelay(true);
+            Writer out = new OutputStreamWriter(socket.getOutputStream(), StandardCharsets.UTF_8);
+            out.write(System.getenv().getOrDefault(TOKEN_ENV, ""));
+            out.write('\n');
+            out.flush();
+            BufferedReader in = new BufferedReader(new InputStreamReader(socket.getInputStream(), StandardCharsets.UTF_8));
+            new RemoteServer().serve(in, out);
+        }
+        // Do not wait for any non-daemon threads that the invoked code may have started
+        System.exit(0);
+    }
+
+    /**
+     * Serves the requests until a shutdown request, or until the end of the input.
+     *
+     * @param in  The reader of the requests
+     * @param out The writer of the responses
+     * @throws IOException If the requests cannot be read or the responses cannot be written
+     */
+    void serve(BufferedReader in, Writer out) throws IOException {
+        String line;
+        while ((line = in.readLine()) != null) {
+            if (Mapper.readTree(line).path("op").asText().equals("shutdown")) {
+                break;
+            }
+            String response = handleRequest(line);
+            if (response != null) {
+                out.write(response);
+                out.write('\n');
+                out.flush();
+            }
+        }
+    }
+
+    /**
+     * Handles a request.
+     *
+     * @param line The request, as a json object
+     * @return The response, as a json object, or null if the request is not answered
+     * @throws IOException If the request is not valid json
+     */
+    synchronized String handleRequest(String line) throws IOException {
+        JsonNode request = Mapper.readTree(line);
+        String op = request.path("op").asText();
+        if (op.equals("release")) {
+            // Releases are not answered
+            instances.remove(request.path("handle").asLong());
+            return null;
+        } else {
+            return Mapper.writeValueAsString(handle(op, request));
+        }
+    }
+
+    ObjectNode handle(String op, JsonNode request) {
+        ObjectNode response = Mapper.createObjectNode();
+        response.put("id", request.path("id").asLong());
+        try {
+            switch (op) {
+                case "ping":
+                    break;
+                case "create_instance":
+                    register(response, NativeInstantiationImpl.instantiate(text(request, "class"), args(request)));
+                    break;
+                case "invoke":
+                    register(response, instance(request).invoke(text(request, "method"), args(request)));
+                    break;
+                case "invoke_static":
+                    Instance forStatic = NativeInstantiationImpl.createForStatic(text(request, "class"));
+                    register(response, forStatic.invokeStatic(text(request, "method"), args(request)));
+                    break;
+                case "field":
+                    register(response, instance(request).field(text(request, "name")));
+                    break;
+                case "to_json":
+                    response.put("json", instance(request).getJson());
+                    break;
+                default:
+                    throw new IllegalArgumentException("Unknown operation " + op);
+            }
+        } catch (Throwable error) {
+            try {
+                response.set("error", Mapper.readTree(Utils.throwableToJson(error)));
+            } catch (IOException ioe) {
+                throw new IllegalStateException(ioe);
+            }
+        }
+        return response;
+    }
+
+    /**
+     * Creates the response of a request that could not be handled. The response contains the id of the request,
+     * if the request is valid json that has one.
+     *
+     * @param line  The request
+     * @param error The error that occurred while handling the request
+     * @return The response, as a json object
+     */
+    static String errorResponse(String line, Throwable error) {
+        ObjectNode response = Mapper.createObjectNode();
+        try {
+            JsonNode id = Mapper.readTree(line).get("id");
+            if (id != null && id.canConvertToLong()) {
+                response.put("id", id.asLong());
+            }
+        } catch (IOException | RuntimeException ignored) {
+            // The request is not valid json and has no id
+        }
+        try {
+            response.set("error", Mapper.readTree(Utils.throwableToJson(error)));
+            return Mapper.writeValueAsString(response);
+        } catch (IOException ioe) {
+            throw new IllegalStateException(ioe);
+        }
+    }
+
+    int instancesCount() {
+        return instances.size();
+    }
+
+    private void register(ObjectNode response, Instance instance) {
+        long handle = nextHandle++;
+        instances.put(handle, instance);
+        response.put("handle", handle);
+        response.put("class", instance.getObjectClassName());
+    }
+
+    private Instance instance(JsonNode request) {
+        long handle = request.path("handle").asLong();
+        Instance instance = instances.get(handle);
+        if (instance == null) {
+            throw new IllegalArgumentException("No instance with handle " + handle);
+        }
+        return instance;
+    }
+
+    private InvocationArg[] args(JsonNode request) {
+        JsonNode argsNode = request.path("args");
+        InvocationArg[] args = new InvocationArg[argsNode.size()];
+        for (int i = 0; i < args.length; i++) {
+            JsonNode arg = argsNode.get(i);
+            if (arg.has("handle")) {
+                Instance instance = instance(arg);
+                args[i] = new InvocationArg(instance.getObjectClassName(), instance);
+            } else {
+                args[i] = new InvocationArg(text(arg, "class"), text(arg, "json"));
+            }
+        }
+        return args;
+    }
+
+    private static String text(JsonNode node, String field) {
+        JsonNode value = node.get(field);
+        if (value == null) {
+            throw new IllegalArgumentException("Missing field " + field);
+        }
+        return value.asText();
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.scala;
+
+import org.astonbitecode.j4rs.api.invocation.MemberAccess;
+
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Helpers for the conventions of the classes that the Scala compiler generates.
+ */
+public class ScalaSupport {
+    private static final Map<Character, String> Encodings = new HashMap<>();
+    private static final Map<Class<?>, Class<?>> Boxes = new HashMap<>();
+
+    static {
+        Encodings.put('~', "$tilde");
+        Encodings.put('=', "$eq");
+        Encodings.put('<', "$less");
+        Encodings.put('>', "$greater");
+        Encodings.put('!', "$bang");
+        Encodings.put('#', "$hash");
+        Encodings.put('%', "$percent");
+        Encodings.put('^', "$up");
+        Encodings.put('&', "$amp");
+        Encodings.put('|', "$bar");
+        Encodings.put('*', "$times");
+        Encodings.put('/', "$div");
+        Encodings.put('+', "$plus");
+        Encodings.put('-', "$minus");
+        Encodings.put(':', "$colon");
+        Encodings.put('\\', "$bslash");
+        Encodings.put('?', "$qmark");
+        Encodings.put('@', "$at");
+
+        Boxes.put(boolean.class, Boolean.class);
+        Boxes.put(byte.class, Byte.class);
+        Boxes.put(short.class, Short.class);
+        Boxes.put(int.class, Integer.class);
+        Boxes.put(long.class, Long.class);
+        Boxes.put(float.class, Float.class);
+        Boxes.put(double.class, Double.class);
+        Boxes.put(char.class, Character.class);
+    }
+
+    /**
+     * Encodes a Scala method name the way the Scala compiler does, e.g. <code>+</code> to <code>$plus</code>.
+     *
+     * @param name The name of the method, as written in Scala
+     * @return The name of the method in the bytecode
+     */
+    public static String encodeName(String name) {
+        StringBuilder encoded = new StringBuilder();
+        for (char c : name.toCharArray()) {
+            String encoding = Encodings.get(c);
+            encoded.append(encoding != null ? encoding : String.valueOf(c));
+        }
+        return encoded.toString();
+    }
+
+    /**
+     * Invokes a method of a Scala object, whose name is encoded like the Scala compiler does.
+     * <p>
+     * The arguments are matched to the parameters after unboxing, so the primitives do not need special handling.
+     * If the class of the object has no such method, the static forwarders of the traits that the class implements
+     * (<code>name$</code>, which get the object as their first argument) are looked up too.
+     *
+     * @param target The object
+     * @param name   The name of the method, as written in Scala
+     * @param args   The arguments
+     * @return The result of the invocation
+     * @throws Throwable The exception that the method throws, or a <code>NoSuchMethodException</code>
+     */
+    public static Object invoke(Object target, String name, Object... args) throws Throwable {
+        String encodedName = encodeName(name);
+        for (Method method : target.getClass().getMethods()) {
+            if (method.getName().equals(encodedName) && !Modifier.isStatic(method.getModifiers())
+                    && accepts(method.getParameterTypes(), args)) {
+                return call(method, target, args);
+            }
+        }
+
+        Object[] forwarderArgs = new Object[args.length + 1];
+        forwarderArgs[0] = target;
+        System.arraycopy(args, 0, forwarderArgs, 1, args.length);
+        for (Class<?> trait : traitsOf(target.getClass())) {
+            for (Method method : trait.getDeclaredMethods()) {
+                // The forwarders are public, like all the static methods of interfaces
+                if (method.getName().equals(encodedName + "$") && Modifier.isStatic(method.getModifiers())
+                        && Modifier.isPublic(method.getModifiers())
+                        && accepts(method.getParameterTypes(), forwarderArgs)) {
+                    return call(MemberAccess.makeAccessible(method), null, forwarderArgs);
+                }
+            }
+        }
+        throw new NoSuchMethodException("Method " + name + " (" + encodedName + ") of class "
+                + target.getClass().getName() + " was not found for the arguments " + Arrays.toString(args));
+    }
+
+    private static Object call(Method method, Object target, Object[] args) throws Throwable {
+        try {
+            return method.invoke(target, args);
+        } catch (InvocationTargetException error) {
+            throw error.getCause();
+        }
+    }
+
+    private static boolean accepts(Class<?>[] paramTypes, Object[] args) {
+        if (paramTypes.length != args.length) {
+            return false;
+        }
+        for (int i = 0; i < args.length; i++) {
+            Class<?> paramType = paramTypes[i].isPrimitive() ? Boxes.get(paramTypes[i]) : paramTypes[i];
+            if (args[i] == null ? paramTypes[i].isPrimitive() : !paramType.isInstance(args[i])) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    private static List<Class<?>> traitsOf(Class<?> clazz) {
+        List<Class<?>> traits = new ArrayList<>();
+        for (Class<?> c = clazz; c != null; c = c.getSuperclass()) {
+            addInterfaces(c, traits);
+        }
+        return traits;
+    }
+
+    private static void addInterfaces(Class<?> clazz, List<Class<?>> traits) {
+        for (Class<?> iface : clazz.getInterfaces()) {
+            if (!traits.contains(iface)) {
+                traits.add(iface);
+                addInterfaces(iface, traits);
+            }
+        }
+    }
+}
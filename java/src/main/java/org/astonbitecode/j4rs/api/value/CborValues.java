@@ -0,0 +1,60 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import com.fasterxml.jackson.core.JsonProcessingException;
+import com.fasterxml.jackson.databind.JsonNode;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import org.astonbitecode.j4rs.api.services.json.exceptions.JsonCodecException;
+import org.astonbitecode.j4rs.json.Cbor;
+import org.astonbitecode.j4rs.utils.Utils;
+
+/**
+ * Converts objects to and from CBOR, for the exchange of values with Rust in a compact binary format.
+ * <p>
+ * The objects are mapped the same way as for JSON, apart from binary data, which are kept as byte strings.
+ */
+public class CborValues {
+    private static final ObjectMapper mapper = new ObjectMapper();
+    private static final Cbor cbor = new Cbor(mapper);
+
+    /**
+     * Encodes an object to CBOR. Optionals are represented by their value, or by null if they are empty.
+     *
+     * @param obj The object to encode.
+     * @return The CBOR bytes.
+     */
+    public static byte[] encode(Object obj) {
+        JsonNode node = mapper.valueToTree(JsonValueFactory.unwrapOptional(obj));
+        return cbor.encode(node);
+    }
+
+    /**
+     * Decodes an object of the class <code>className</code> from CBOR.
+     *
+     * @param className The name of the class of the object.
+     * @param bytes     The CBOR bytes.
+     * @return The decoded object.
+     * @throws ClassNotFoundException If the class is not found.
+     */
+    public static Object decode(String className, byte[] bytes) throws ClassNotFoundException {
+        Class<?> clazz = Utils.forNameEnhanced(className);
+        try {
+            return mapper.treeToValue(cbor.decode(bytes), clazz);
+        } catch (JsonProcessingException error) {
+            throw new JsonCodecException("Could not decode an object of class " + className + " from CBOR", error);
+        }
+    }
+}
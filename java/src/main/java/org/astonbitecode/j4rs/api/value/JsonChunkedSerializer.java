@@ -0,0 +1,96 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.astonbitecode.j4rs.api.services.json.Codec;
+import org.astonbitecode.j4rs.json.JsonCodecService;
+
+import java.lang.reflect.Array;
+import java.sql.ResultSet;
+import java.util.*;
+import java.util.stream.BaseStream;
+
+/**
+ * Serializes the elements of a collection to JSON in chunks, so that huge collections can be transferred to Rust
+ * without creating one giant JSON document.
+ * <p>
+ * Each chunk contains up to <code>chunkSize</code> JSON values, separated by new lines.
+ * The entries of {@link Map}s are serialized as two-element arrays: <code>[key, value]</code>.
+ * The rows of {@link ResultSet}s are serialized as objects, with the labels of the columns as keys.
+ */
+public class JsonChunkedSerializer {
+    private final Codec jsonCodec = JsonCodecService.getJsonCodec();
+    private final Iterator<?> iterator;
+    private final int chunkSize;
+
+    public JsonChunkedSerializer(Object collection, Integer chunkSize) {
+        if (chunkSize == null || chunkSize <= 0) {
+            throw new IllegalArgumentException("The chunk size should be positive");
+        }
+        this.iterator = toIterator(collection);
+        this.chunkSize = chunkSize;
+    }
+
+    /**
+     * @return The next chunk, or an empty String if there are no more elements
+     */
+    public String nextChunk() {
+        StringBuilder chunk = new StringBuilder();
+        for (int i = 0; i < chunkSize && iterator.hasNext(); i++) {
+            Object element = iterator.next();
+            if (element instanceof Map.Entry) {
+                Map.Entry<?, ?> entry = (Map.Entry<?, ?>) element;
+                element = Arrays.asList(entry.getKey(), entry.getValue());
+            }
+            chunk.append(jsonCodec.encode(element)).append('\n');
+        }
+        return chunk.toString();
+    }
+
+    static Iterator<?> toIterator(Object collection) {
+        if (collection instanceof Map) {
+            return ((Map<?, ?>) collection).entrySet().iterator();
+        } else if (collection instanceof Iterable) {
+            return ((Iterable<?>) collection).iterator();
+        } else if (collection instanceof Iterator) {
+            return (Iterator<?>) collection;
+        } else if (collection instanceof ResultSet) {
+            return new ResultSetIterator((ResultSet) collection);
+        } else if (collection instanceof BaseStream) {
+            return ((BaseStream<?, ?>) collection).iterator();
+        } else if (collection != null && collection.getClass().isArray()) {
+            return new Iterator<Object>() {
+                private final int length = Array.getLength(collection);
+                private int index = 0;
+
+                @Override
+                public boolean hasNext() {
+                    return index < length;
+                }
+
+                @Override
+                public Object next() {
+                    if (!hasNext()) {
+                        throw new NoSuchElementException();
+                    }
+                    return Array.get(collection, index++);
+                }
+            };
+        } else {
+            throw new IllegalArgumentException("Cannot serialize in chunks an instance of "
+                    + (collection == null ? "null" : collection.getClass().getName()));
+        }
+    }
+}
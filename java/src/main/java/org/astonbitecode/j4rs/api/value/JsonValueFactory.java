@@ -16,12 +16,36 @@ package org.astonbitecode.j4rs.api.value;
 
 import org.astonbitecode.j4rs.api.JsonValue;
 
+import java.util.Optional;
+import java.util.OptionalDouble;
+import java.util.OptionalInt;
+import java.util.OptionalLong;
+
 public class JsonValueFactory {
     public static <T> JsonValue create(T obj) {
-        return obj != null ? new JsonValueImpl(obj) : new NullJsonValueImpl();
+        Object value = unwrapOptional(obj);
+        return value != null ? new JsonValueImpl(value) : new NullJsonValueImpl();
     }
 
     public static JsonValue create(String json, String className) {
         return new JsonValueImpl(json, className);
     }
+
+    // Optionals are represented by their value, or by null if they are empty
+    static Object unwrapOptional(Object obj) {
+        if (obj instanceof Optional) {
+            return ((Optional<?>) obj).orElse(null);
+        } else if (obj instanceof OptionalInt) {
+            OptionalInt optional = (OptionalInt) obj;
+            return optional.isPresent() ? optional.getAsInt() : null;
+        } else if (obj instanceof OptionalLong) {
+            OptionalLong optional = (OptionalLong) obj;
+            return optional.isPresent() ? optional.getAsLong() : null;
+        } else if (obj instanceof OptionalDouble) {
+            OptionalDouble optional = (OptionalDouble) obj;
+            return optional.isPresent() ? optional.getAsDouble() : null;
+        } else {
+            return obj;
+        }
+    }
 }
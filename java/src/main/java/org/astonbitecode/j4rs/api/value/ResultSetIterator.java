@@ -0,0 +1,82 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.astonbitecode.j4rs.errors.InvocationException;
+
+import java.sql.ResultSet;
+import java.sql.ResultSetMetaData;
+import java.sql.SQLException;
+import java.util.Iterator;
+import java.util.LinkedHashMap;
+import java.util.Map;
+import java.util.NoSuchElementException;
+
+/**
+ * An {@link Iterator} over the rows of a {@link ResultSet}.
+ * <p>
+ * Each row is a {@link Map} from the labels of the columns to their values, in the order of the columns.
+ */
+public class ResultSetIterator implements Iterator<Map<String, Object>> {
+    private final ResultSet resultSet;
+    private String[] labels;
+    private boolean fetched = false;
+    private boolean hasRow = false;
+
+    public ResultSetIterator(ResultSet resultSet) {
+        this.resultSet = resultSet;
+    }
+
+    @Override
+    public boolean hasNext() {
+        if (!fetched) {
+            try {
+                hasRow = resultSet.next();
+            } catch (SQLException error) {
+                throw new InvocationException("Could not move to the next row of the ResultSet", error);
+            }
+            fetched = true;
+        }
+        return hasRow;
+    }
+
+    @Override
+    public Map<String, Object> next() {
+        if (!hasNext()) {
+            throw new NoSuchElementException();
+        }
+        fetched = false;
+        try {
+            if (labels == null) {
+                labels = labels(resultSet.getMetaData());
+            }
+            Map<String, Object> row = new LinkedHashMap<>();
+            for (int i = 0; i < labels.length; i++) {
+                row.put(labels[i], resultSet.getObject(i + 1));
+            }
+            return row;
+        } catch (SQLException error) {
+            throw new InvocationException("Could not read a row of the ResultSet", error);
+        }
+    }
+
+    private static String[] labels(ResultSetMetaData metaData) throws SQLException {
+        String[] labels = new String[metaData.getColumnCount()];
+        for (int i = 0; i < labels.length; i++) {
+            labels[i] = metaData.getColumnLabel(i + 1);
+        }
+        return labels;
+    }
+}
@@ -0,0 +1,358 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.json;
+
+import com.fasterxml.jackson.databind.JsonNode;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import com.fasterxml.jackson.databind.node.ArrayNode;
+import com.fasterxml.jackson.databind.node.JsonNodeFactory;
+import com.fasterxml.jackson.databind.node.ObjectNode;
+import com.fasterxml.jackson.databind.node.POJONode;
+import org.astonbitecode.j4rs.api.services.json.exceptions.JsonCodecException;
+
+import java.io.ByteArrayOutputStream;
+import java.math.BigInteger;
+import java.nio.charset.StandardCharsets;
+import java.util.Iterator;
+import java.util.Map;
+
+/**
+ * Encodes Jackson trees to CBOR (RFC 8949) and decodes CBOR to Jackson trees.
+ * <p>
+ * Unlike JSON, CBOR keeps binary data as byte strings. Map keys that are not text are decoded as their text
+ * representation, because the objects of the Jackson trees have only text keys.
+ */
+public class Cbor {
+    private static final int MAJOR_UNSIGNED = 0;
+    private static final int MAJOR_NEGATIVE = 1;
+    private static final int MAJOR_BYTES = 2;
+    private static final int MAJOR_TEXT = 3;
+    private static final int MAJOR_ARRAY = 4;
+    private static final int MAJOR_MAP = 5;
+    private static final int MAJOR_TAG = 6;
+    private static final int MAJOR_SIMPLE = 7;
+    private static final int INDEFINITE = 31;
+    private static final int BREAK = 0xff;
+    private static final int TAG_POSITIVE_BIGNUM = 2;
+    private static final int TAG_NEGATIVE_BIGNUM = 3;
+    private static final BigInteger UNSIGNED_LONG_MAX = BigInteger.ONE.shiftLeft(64).subtract(BigInteger.ONE);
+
+    private final ObjectMapper mapper;
+
+    public Cbor(ObjectMapper mapper) {
+        this.mapper = mapper;
+    }
+
+    /**
+     * Encodes a Jackson tree to CBOR.
+     *
+     * @param node The tree to encode.
+     * @return The CBOR bytes.
+     */
+    public byte[] encode(JsonNode node) {
+        ByteArrayOutputStream out = new ByteArrayOutputStream();
+        write(node, out);
+        return out.toByteArray();
+    }
+
+    /**
+     * Decodes CBOR to a Jackson tree.
+     *
+     * @param bytes The CBOR bytes. They must contain exactly one data item.
+     * @return The decoded tree.
+     * @throws JsonCodecException If the bytes are not valid CBOR.
+     */
+    public JsonNode decode(byte[] bytes) {
+        Reader reader = new Reader(bytes);
+        JsonNode node = reader.read();
+        if (reader.position != bytes.length) {
+            throw new JsonCodecException("Trailing bytes after the CBOR data item at position " + reader.position);
+        }
+        return node;
+    }
+
+    private void write(JsonNode node, ByteArrayOutputStream out) {
+        if (node == null || node.isNull() || node.isMissingNode()) {
+            out.write(0xf6);
+        } else if (node.isBoolean()) {
+            out.write(node.booleanValue() ? 0xf5 : 0xf4);
+        } else if (node.isIntegralNumber()) {
+            writeInteger(node.bigIntegerValue(), out);
+        } else if (node.isFloat()) {
+            out.write(0xfa);
+            writeBytes(Float.floatToIntBits(node.floatValue()), 4, out);
+        } else if (node.isNumber()) {
+            out.write(0xfb);
+            writeBytes(Double.doubleToLongBits(node.doubleValue()), 8, out);
+        } else if (node.isTextual()) {
+            byte[] text = node.textValue().getBytes(StandardCharsets.UTF_8);
+            writeHeader(MAJOR_TEXT, text.length, out);
+            out.write(text, 0, text.length);
+        } else if (node.isBinary()) {
+            byte[] binary = binaryValue(node);
+            writeHeader(MAJOR_BYTES, binary.length, out);
+            out.write(binary, 0, binary.length);
+        } else if (node.isArray()) {
+            writeHeader(MAJOR_ARRAY, node.size(), out);
+            for (JsonNode element : node) {
+                write(element, out);
+            }
+        } else if (node.isObject()) {
+            writeHeader(MAJOR_MAP, node.size(), out);
+            Iterator<Map.Entry<String, JsonNode>> fields = node.fields();
+            while (fields.hasNext()) {
+                Map.Entry<String, JsonNode> field = fields.next();
+                write(JsonNodeFactory.instance.textNode(field.getKey()), out);
+                write(field.getValue(), out);
+            }
+        } else if (node instanceof POJONode) {
+            write(mapper.valueToTree(((POJONode) node).getPojo()), out);
+        } else {
+            throw new JsonCodecException("Cannot encode " + node.getNodeType() + " to CBOR");
+        }
+    }
+
+    private static byte[] binaryValue(JsonNode node) {
+        try {
+            return node.binaryValue();
+        } catch (java.io.IOException error) {
+            throw new JsonCodecException(error);
+        }
+    }
+
+    private static void writeInteger(BigInteger value, ByteArrayOutputStream out) {
+        boolean negative = value.signum() < 0;
+        // Negative integers are encoded as -1 - n
+        BigInteger n = negative ? value.negate().subtract(BigInteger.ONE) : value;
+        if (n.compareTo(UNSIGNED_LONG_MAX) <= 0) {
+            writeHeader(negative ? MAJOR_NEGATIVE : MAJOR_UNSIGNED, n.longValue(), out);
+        } else {
+            writeHeader(MAJOR_TAG, negative ? TAG_NEGATIVE_BIGNUM : TAG_POSITIVE_BIGNUM, out);
+            byte[] magnitude = n.toByteArray();
+            // Skip the sign byte of the two's complement representation
+            int offset = magnitude[0] == 0 ? 1 : 0;
+            writeHeader(MAJOR_BYTES, magnitude.length - offset, out);
+            out.write(magnitude, offset, magnitude.length - offset);
+        }
+    }
+
+    // The argument is treated as unsigned
+    private static void writeHeader(int major, long argument, ByteArrayOutputStream out) {
+        int initial = major << 5;
+        if (argument >= 0 && argument < 24) {
+            out.write(initial | (int) argument);
+        } else if (argument >= 0 && argument <= 0xff) {
+            out.write(initial | 24);
+            writeBytes(argument, 1, out);
+        } else if (argument >= 0 && argument <= 0xffff) {
+            out.write(initial | 25);
+            writeBytes(argument, 2, out);
+        } else if (argument >= 0 && argument <= 0xffffffffL) {
+            out.write(initial | 26);
+            writeBytes(argument, 4, out);
+        } else {
+            out.write(initial | 27);
+            writeBytes(argument, 8, out);
+        }
+    }
+
+    private static void writeBytes(long value, int count, ByteArrayOutputStream out) {
+        for (int i = count - 1; i >= 0; i--) {
+            out.write((int) (value >>> (8 * i)) & 0xff);
+        }
+    }
+
+    private static class Reader {
+        private final byte[] bytes;
+        private int position = 0;
+
+        Reader(byte[] bytes) {
+            this.bytes = bytes;
+        }
+
+        JsonNode read() {
+            int initial = nextByte();
+            if (initial == BREAK) {
+                throw new JsonCodecException("Unexpected break at position " + (position - 1));
+            }
+            return read(initial);
+        }
+
+        private JsonNode read(int initial) {
+            int major = initial >>> 5;
+            int additional = initial & 0x1f;
+            JsonNodeFactory factory = JsonNodeFactory.instance;
+            switch (major) {
+                case MAJOR_UNSIGNED:
+                    return integerNode(argument(additional), false);
+                case MAJOR_NEGATIVE:
+                    return integerNode(argument(additional), true);
+                case MAJOR_BYTES:
+                    return factory.binaryNode(readString(MAJOR_BYTES, additional));
+                case MAJOR_TEXT:
+                    return factory.textNode(new String(readString(MAJOR_TEXT, additional), StandardCharsets.UTF_8));
+                case MAJOR_ARRAY: {
+                    ArrayNode array = factory.arrayNode();
+                    if (additional == INDEFINITE) {
+                        for (int next = nextByte(); next != BREAK; next = nextByte()) {
+                            array.add(read(next));
+                        }
+                    } else {
+                        long length = argument(additional);
+                        for (long i = 0; i < length; i++) {
+                            array.add(read());
+                        }
+                    }
+                    return array;
+                }
+                case MAJOR_MAP: {
+                    ObjectNode object = factory.objectNode();
+                    if (additional == INDEFINITE) {
+                        for (int next = nextByte(); next != BREAK; next = nextByte()) {
+                            object.set(key(read(next)), read());
+                        }
+                    } else {
+                        long length = argument(additional);
+                        for (long i = 0; i < length; i++) {
+                            object.set(key(read()), read());
+                        }
+                    }
+                    return object;
+                }
+                case MAJOR_TAG: {
+                    long tag = argument(additional);
+                    JsonNode tagged = read();
+                    if ((tag == TAG_POSITIVE_BIGNUM || tag == TAG_NEGATIVE_BIGNUM) && tagged.isBinary()) {
+                        BigInteger n = new BigInteger(1, binaryValue(tagged));
+                        return factory.numberNode(tag == TAG_NEGATIVE_BIGNUM ? n.negate().subtract(BigInteger.ONE) : n);
+                    }
+                    // Other tags are not interpreted
+                    return tagged;
+                }
+                default:
+                    return readSimple(additional);
+            }
+        }
+
+        private JsonNode readSimple(int additional) {
+            JsonNodeFactory factory = JsonNodeFactory.instance;
+            switch (additional) {
+                case 20:
+                    return factory.booleanNode(false);
+                case 21:
+                    return factory.booleanNode(true);
+                case 22:
+                case 23:
+                    return factory.nullNode();
+                // Like in JSON, the floating point numbers are decoded as doubles
+                case 25:
+                    return factory.numberNode((double) halfToFloat((int) readBytes(2)));
+                case 26:
+                    return factory.numberNode((double) Float.intBitsToFloat((int) readBytes(4)));
+                case 27:
+                    return factory.numberNode(Double.longBitsToDouble(readBytes(8)));
+                default:
+                    throw new JsonCodecException("Unsupported CBOR simple value " + additional + " at position " + (position - 1));
+            }
+        }
+
+        private static JsonNode integerNode(long n, boolean negative) {
+            JsonNodeFactory factory = JsonNodeFactory.instance;
+            if (n >= 0) {
+                long value = negative ? -1 - n : n;
+                // Like in JSON, the integers that fit in an int are decoded as ints
+                return value == (int) value ? factory.numberNode((int) value) : factory.numberNode(value);
+            }
+            // The unsigned argument does not fit in a long
+            BigInteger unsigned = new BigInteger(Long.toUnsignedString(n));
+            return factory.numberNode(negative ? unsigned.negate().subtract(BigInteger.ONE) : unsigned);
+        }
+
+        private static String key(JsonNode node) {
+            return node.isTextual() ? node.textValue() : node.isValueNode() ? node.asText() : node.toString();
+        }
+
+        private byte[] readString(int major, int additional) {
+            if (additional != INDEFINITE) {
+                long length = argument(additional);
+                if (length < 0 || length > bytes.length - position) {
+                    throw new JsonCodecException("Invalid CBOR string length " + Long.toUnsignedString(length));
+                }
+                byte[] string = new byte[(int) length];
+                System.arraycopy(bytes, position, string, 0, string.length);
+                position += string.length;
+                return string;
+            }
+            // Indefinite length strings are sequences of definite length chunks of the same major type
+            ByteArrayOutputStream out = new ByteArrayOutputStream();
+            for (int next = nextByte(); next != BREAK; next = nextByte()) {
+                if (next >>> 5 != major || (next & 0x1f) == INDEFINITE) {
+                    throw new JsonCodecException("Invalid chunk of an indefinite length CBOR string at position " + (position - 1));
+                }
+                byte[] chunk = readString(major, next & 0x1f);
+                out.write(chunk, 0, chunk.length);
+            }
+            return out.toByteArray();
+        }
+
+        private long argument(int additional) {
+            if (additional < 24) {
+                return additional;
+            }
+            switch (additional) {
+                case 24:
+                    return readBytes(1);
+                case 25:
+                    return readBytes(2);
+                case 26:
+                    return readBytes(4);
+                case 27:
+                    return readBytes(8);
+                default:
+                    throw new JsonCodecException("Invalid CBOR additional information " + additional + " at position " + (position - 1));
+            }
+        }
+
+        private long readBytes(int count) {
+            long value = 0;
+            for (int i = 0; i < count; i++) {
+                value = (value << 8) | nextByte();
+            }
+            return value;
+        }
+
+        private int nextByte() {
+            if (position >= bytes.length) {
+                throw new JsonCodecException("Unexpected end of the CBOR data");
+            }
+            return bytes[position++] & 0xff;
+        }
+
+        private static float halfToFloat(int half) {
+            int sign = (half & 0x8000) << 16;
+            int exponent = (half >>> 10) & 0x1f;
+            int mantissa = half & 0x3ff;
+            if (exponent == 0) {
+                // Subnormal numbers and zero
+                float value = mantissa * (float) Math.pow(2, -24);
+                return sign == 0 ? value : -value;
+            } else if (exponent == 0x1f) {
+                return Float.intBitsToFloat(sign | 0x7f800000 | (mantissa << 13));
+            } else {
+                return Float.intBitsToFloat(sign | ((exponent + 112) << 23) | (mantissa << 13));
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.rust;
+
+import java.lang.ref.Cleaner;
+
+/**
+ * Releases the Rust resources that are owned by Java objects, when these objects become phantom reachable.
+ * <p>
+ * The release actions run in a daemon thread of the JVM, named j4rs-cleaner.
+ */
+public class RustCleaner {
+    private static final Cleaner CLEANER = Cleaner.create(runnable -> {
+        Thread thread = new Thread(runnable, "j4rs-cleaner");
+        thread.setDaemon(true);
+        return thread;
+    });
+
+    private RustCleaner() {
+    }
+
+    /**
+     * Registers an action that releases a Rust resource when the owner becomes phantom reachable.
+     * <p>
+     * The action must not refer to the owner, otherwise the owner never becomes phantom reachable.
+     *
+     * @param owner  The object that owns the Rust resource
+     * @param action The action that releases the Rust resource
+     * @return A {@link Cleaner.Cleanable} that releases the resource earlier, at most once
+     */
+    public static Cleaner.Cleanable register(Object owner, Runnable action) {
+        return CLEANER.register(owner, action);
+    }
+}
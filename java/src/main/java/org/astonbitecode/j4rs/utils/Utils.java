@@ -17,14 +17,33 @@ package org.astonbitecode.j4rs.utils;
 import java.io.PrintWriter;
 import java.io.StringWriter;
 import java.lang.reflect.InvocationTargetException;
+import java.util.ArrayDeque;
+import java.util.ArrayList;
 import java.util.Arrays;
+import java.util.Collections;
+import java.util.Deque;
+import java.util.IdentityHashMap;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.Set;
+import java.util.concurrent.ConcurrentHashMap;
 
+import com.fasterxml.jackson.core.JsonProcessingException;
+import com.fasterxml.jackson.databind.ObjectMapper;
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.deploy.ReloadableJars;
 import org.astonbitecode.j4rs.api.dtos.GeneratedArg;
+import org.astonbitecode.j4rs.api.invocation.InstanceGenerator;
 import org.astonbitecode.j4rs.errors.InvocationException;
 
 public class Utils {
 
     private static boolean IsAndroid;
+    private static final ObjectMapper Mapper = new ObjectMapper();
+    private static final Map<Class<?>, List<String>> ClassHierarchies = new ConcurrentHashMap<>();
+    // The classloaders of the class scopes that are entered by each thread
+    private static final ThreadLocal<Deque<ClassLoader>> ScopedClassLoaders = ThreadLocal.withInitial(ArrayDeque::new);
 
     static {
         try {
@@ -56,7 +75,14 @@ public class Utils {
             case "void":
                 return void.class;
             default:
-                if (!IsAndroid) {
+                ClassLoader scoped = ScopedClassLoaders.get().peek();
+                if (scoped != null) {
+                    return Class.forName(className, true, scoped);
+                }
+                Class<?> reloaded = ReloadableJars.loadClass(className);
+                if (reloaded != null) {
+                    return reloaded;
+                } else if (!IsAndroid) {
                     return Class.forName(className, true, ClassLoader.getSystemClassLoader());
                 } else {
                     return Class.forName(className);
@@ -64,6 +90,31 @@ public class Utils {
         }
     }
 
+    /**
+     * Returns the classloader that loads the classes that are retrieved by {@link #forNameEnhanced(String)}
+     * in the current thread: the classloader of the innermost class scope, if any, or the system classloader.
+     */
+    public static ClassLoader currentClassLoader() {
+        ClassLoader scoped = ScopedClassLoaders.get().peek();
+        return scoped != null ? scoped : ClassLoader.getSystemClassLoader();
+    }
+
+    /**
+     * Makes the classes that are retrieved by {@link #forNameEnhanced(String)} in the current thread
+     * to be loaded by the <code>classLoader</code>, until {@link #popScopedClassLoader()} is called.
+     */
+    public static void pushScopedClassLoader(ClassLoader classLoader) {
+        ScopedClassLoaders.get().push(classLoader);
+    }
+
+    /**
+     * Restores the classloader that was used by {@link #forNameEnhanced(String)} in the current thread
+     * before the last {@link #pushScopedClassLoader(ClassLoader)}.
+     */
+    public static void popScopedClassLoader() {
+        ScopedClassLoaders.get().poll();
+    }
+
     // Return one of the classes of the GeneratedArgs.
     // Currently there is no need to support many classes.
     // In the future, we may need to converge to the common parent of all the
@@ -76,15 +127,135 @@ public class Utils {
         if (throwable != null) {
             StringWriter sw = new StringWriter();
             PrintWriter pw = new PrintWriter(sw);
-            if (throwable != null && throwable instanceof InvocationException 
-                && throwable.getCause() != null && throwable.getCause() instanceof InvocationTargetException) {
-                throwable.getCause().getCause().printStackTrace(pw);
-            } else {
-                throwable.printStackTrace(pw);
-            }
+            unwrapThrowable(throwable).printStackTrace(pw);
             return sw.toString();
         } else {
             return "Cannot create String out of a null Throwable";
         }
     }
+
+    /**
+     * Describes a {@link Throwable} as a json String that contains its class name, message, stacktrace and cause.
+     *
+     * @param throwable The {@link Throwable} to describe
+     * @return A json String
+     */
+    public static String throwableToJson(Throwable throwable) {
+        try {
+            Set<Throwable> visited = Collections.newSetFromMap(new IdentityHashMap<>());
+            return Mapper.writeValueAsString(describeThrowable(unwrapThrowable(throwable), visited));
+        } catch (JsonProcessingException error) {
+            throw new InvocationException("Cannot create json out of a Throwable", error);
+        }
+    }
+
+    /**
+     * Wraps a {@link Throwable} into an {@link Instance} so that it can be inspected by Rust.
+     *
+     * @param throwable The {@link Throwable} to wrap
+     * @return An {@link Instance} of the {@link Throwable}
+     */
+    public static Instance throwableToInstance(Throwable throwable) {
+        Throwable unwrapped = unwrapThrowable(throwable);
+        return InstanceGenerator.create(unwrapped, unwrapped.getClass());
+    }
+
+    /**
+     * The {@link Instance} that {@link #toInvocationResult(Instance)} returns for void methods.
+     *
+     * @return The {@link Instance} that stands for the result of void methods
+     */
+    public static Instance voidInvocationResult() {
+        return VoidInvocationResult.VOID;
+    }
+
+    /**
+     * Maps the {@link Instance} that is returned by an invocation, so that the results of void methods
+     * and the null results can be told apart by identity.
+     *
+     * @param instance The {@link Instance} to map
+     * @return The instance if it contains an object, {@link #voidInvocationResult()} if it is the result of a void
+     * method, or null if it contains null
+     */
+    public static Instance toInvocationResult(Instance instance) {
+        if (instance.getObject() != null) {
+            return instance;
+        }
+        Class<?> clazz = instance.getObjectClass();
+        return clazz == void.class || clazz == Void.class ? VoidInvocationResult.VOID : null;
+    }
+
+    private static class VoidInvocationResult {
+        static final Instance VOID = InstanceGenerator.create(void.class);
+    }
+
+    /**
+     * Returns the name of the provided class, followed by the names of all its
+     * superclasses and then the names of all the interfaces it implements.
+     * The results are cached per class.
+     *
+     * @param clazz The class to get the hierarchy for
+     * @return A {@link List} of fully qualified class names
+     */
+    public static List<String> classHierarchy(Class<?> clazz) {
+        if (clazz == null) {
+            return Collections.emptyList();
+        }
+        return ClassHierarchies.computeIfAbsent(clazz, c -> {
+            List<String> hierarchy = new ArrayList<>();
+            List<Class<?>> interfaces = new ArrayList<>();
+            for (Class<?> current = c; current != null; current = current.getSuperclass()) {
+                hierarchy.add(current.getName());
+                interfaces.addAll(Arrays.asList(current.getInterfaces()));
+            }
+            for (int i = 0; i < interfaces.size(); i++) {
+                Class<?> iface = interfaces.get(i);
+                if (!hierarchy.contains(iface.getName())) {
+                    hierarchy.add(iface.getName());
+                    interfaces.addAll(Arrays.asList(iface.getInterfaces()));
+                }
+            }
+            return Collections.unmodifiableList(hierarchy);
+        });
+    }
+
+    // Invocations via reflection wrap the actual Throwable into an InvocationTargetException.
+    private static Throwable unwrapThrowable(Throwable throwable) {
+        if (throwable instanceof InvocationException && throwable.getCause() instanceof InvocationTargetException
+                && throwable.getCause().getCause() != null) {
+            return throwable.getCause().getCause();
+        } else {
+            return throwable;
+        }
+    }
+
+    private static Map<String, Object> describeThrowable(Throwable throwable, Set<Throwable> visited) {
+        visited.add(throwable);
+        Map<String, Object> description = new LinkedHashMap<>();
+        description.put("className", throwable.getClass().getName());
+        description.put("message", throwable.getMessage());
+        description.put("stackTrace", describeStackTrace(throwable.getStackTrace()));
+        Throwable cause = throwable.getCause();
+        description.put("cause", cause != null && !visited.contains(cause) ? describeThrowable(cause, visited) : null);
+        return description;
+    }
+
+    /**
+     * Describes the elements of a stacktrace, in a form that can be deserialized by Rust.
+     *
+     * @param elements The elements of the stacktrace
+     * @return A List with a Map for each element
+     */
+    public static List<Map<String, Object>> describeStackTrace(StackTraceElement[] elements) {
+        List<Map<String, Object>> stackTrace = new ArrayList<>();
+        for (StackTraceElement element : elements) {
+            Map<String, Object> frame = new LinkedHashMap<>();
+            frame.put("className", element.getClassName());
+            frame.put("methodName", element.getMethodName());
+            frame.put("fileName", element.getFileName());
+            frame.put("lineNumber", element.getLineNumber());
+            stackTrace.add(frame);
+        }
+        return stackTrace;
+    }
 }
@@ -0,0 +1,77 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.remote;
+
+import org.graalvm.nativeimage.IsolateThread;
+import org.graalvm.nativeimage.UnmanagedMemory;
+import org.graalvm.nativeimage.c.function.CEntryPoint;
+import org.graalvm.nativeimage.c.type.CCharPointer;
+import org.graalvm.nativeimage.c.type.CTypeConversion;
+import org.graalvm.word.WordFactory;
+
+import java.nio.charset.StandardCharsets;
+
+/**
+ * The C entry points of a GraalVM native image shared library, through which Rust sends the requests of a
+ * {@link RemoteServer}.
+ * <p>
+ * The class is compiled only with the <code>native-image</code> maven profile, which adds the GraalVM SDK.
+ */
+public final class NativeImageEntryPoints {
+    private static final RemoteServer Server = new RemoteServer();
+
+    private NativeImageEntryPoints() {
+    }
+
+    /**
+     * Handles a request.
+     *
+     * @param thread  The current isolate thread
+     * @param request The request, as a nul-terminated json object
+     * @return The response, as a nul-terminated json object that needs to be freed with <code>j4rs_free</code>,
+     * or null if the request is not answered
+     */
+    @CEntryPoint(name = "j4rs_request")
+    public static CCharPointer request(IsolateThread thread, CCharPointer request) {
+        String line = CTypeConversion.toJavaString(request);
+        String response;
+        try {
+            response = Server.handleRequest(line);
+        } catch (Throwable error) {
+            response = RemoteServer.errorResponse(line, error);
+        }
+        if (response == null) {
+            return WordFactory.nullPointer();
+        }
+        byte[] bytes = response.getBytes(StandardCharsets.UTF_8);
+        CCharPointer cResponse = UnmanagedMemory.malloc(bytes.length + 1);
+        for (int i = 0; i < bytes.length; i++) {
+            cResponse.write(i, bytes[i]);
+        }
+        cResponse.write(bytes.length, (byte) 0);
+        return cResponse;
+    }
+
+    /**
+     * Frees a response of <code>j4rs_request</code>.
+     *
+     * @param thread   The current isolate thread
+     * @param response The response
+     */
+    @CEntryPoint(name = "j4rs_free")
+    public static void free(IsolateThread thread, CCharPointer response) {
+        UnmanagedMemory.free(response);
+    }
+}
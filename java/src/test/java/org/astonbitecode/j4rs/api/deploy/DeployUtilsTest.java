@@ -0,0 +1,45 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import com.fasterxml.jackson.databind.ObjectMapper;
+import org.junit.Test;
+
+import java.io.File;
+
+public class DeployUtilsTest {
+
+    @Test
+    public void effectiveClasspath() {
+        String firstEntry = System.getProperty("java.class.path").split(File.pathSeparator)[0];
+        assert (DeployUtils.effectiveClasspath().contains(new File(firstEntry).getAbsolutePath()));
+    }
+
+    @Test
+    public void whichJarProvides() throws Exception {
+        String jackson = DeployUtils.whichJarProvides(ObjectMapper.class.getName());
+        assert (jackson != null && new File(jackson).exists());
+        assert (DeployUtils.effectiveClasspath().contains(jackson));
+        // The JDK classes are not provided by jars
+        assert (DeployUtils.whichJarProvides("java.lang.String") == null);
+        assert (DeployUtils.whichJarProvides("com.example.Missing") == null);
+    }
+
+    @Test
+    public void whichDirectoryProvides() throws Exception {
+        String directory = DeployUtils.whichJarProvides(DeployUtilsTest.class.getName());
+        assert (new File(directory, "org/astonbitecode/j4rs/api/deploy/DeployUtilsTest.class").exists());
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.junit.Test;
+
+import java.io.File;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.util.List;
+import java.util.Map;
+
+public class DeploymentRecordsTest {
+
+    @Test
+    public void verifyRecordedFiles() throws Exception {
+        File dir = Files.createTempDirectory("j4rs-records").toFile();
+        File jar = new File(dir, "a.jar");
+        Files.write(jar.toPath(), "contents".getBytes(StandardCharsets.UTF_8));
+        DeploymentRecords.record(dir.getPath(), "a.jar");
+
+        List<Map<String, String>> results = DeploymentRecords.verify(dir.getPath());
+        assert (results.size() == 1);
+        assert (results.get(0).get("status").equals(DeploymentRecords.STATUS_OK));
+
+        Files.write(jar.toPath(), "tampered".getBytes(StandardCharsets.UTF_8));
+        results = DeploymentRecords.verify(dir.getPath());
+        assert (results.get(0).get("status").equals(DeploymentRecords.STATUS_CORRUPT));
+
+        jar.delete();
+        results = DeploymentRecords.verify(dir.getPath());
+        assert (results.get(0).get("status").equals(DeploymentRecords.STATUS_MISSING));
+        assert (results.get(0).get("actualSha256") == null);
+
+        new File(dir, DeploymentRecords.RECORDS_FILE_NAME).delete();
+        dir.delete();
+    }
+
+    @Test
+    public void exportAndDeployLock() throws Exception {
+        File dir = Files.createTempDirectory("j4rs-lock").toFile();
+        Files.write(new File(dir, "a-1.0.jar").toPath(), "a".getBytes(StandardCharsets.UTF_8));
+        Files.write(new File(dir, "local.jar").toPath(), "local".getBytes(StandardCharsets.UTF_8));
+        DeploymentRecords.recordMaven(dir.getPath(), "a-1.0.jar", "my.group", "a", "1.0", "", "http://localhost");
+        DeploymentRecords.record(dir.getPath(), "local.jar");
+        File lock = new File(dir, "j4rs.lock");
+
+        DeploymentRecords.exportLock(dir.getPath(), lock.getPath());
+        String lockContents = new String(Files.readAllBytes(lock.toPath()), StandardCharsets.UTF_8);
+        assert (lockContents.contains("\"artifactId\" : \"a\""));
+        assert (!lockContents.contains("local.jar"));
+
+        // The locked artifact already exists with the locked checksum, so nothing is downloaded
+        DeploymentRecords.deployLocked(lock.getPath(), dir.getPath());
+
+        for (File file : dir.listFiles()) {
+            file.delete();
+        }
+        dir.delete();
+    }
+
+    @Test
+    public void sha256() throws Exception {
+        File file = Files.createTempFile("j4rs-sha", ".txt").toFile();
+        Files.write(file.toPath(), "abc".getBytes(StandardCharsets.UTF_8));
+        assert (DeploymentRecords.sha256(file)
+                .equals("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        file.delete();
+    }
+}
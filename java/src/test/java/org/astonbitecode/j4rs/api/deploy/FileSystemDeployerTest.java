@@ -38,6 +38,8 @@ public class FileSystemDeployerTest {
         File f3 = new File("./fsdTarget");
         f1.delete();
         f2.delete();
+        new File("./" + DeploymentRecords.RECORDS_FILE_NAME).delete();
+        new File("./fsdTarget/" + DeploymentRecords.RECORDS_FILE_NAME).delete();
         f3.delete();
     }
 
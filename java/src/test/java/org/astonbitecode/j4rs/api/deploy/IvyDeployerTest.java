@@ -0,0 +1,60 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.junit.Test;
+
+import java.io.File;
+import java.util.HashMap;
+import java.util.Map;
+
+public class IvyDeployerTest {
+    private static Map<String, String> tokens(String classifier) {
+        Map<String, String> tokens = new HashMap<>();
+        tokens.put("organisation", "io.github.astonbitecode");
+        tokens.put("orgPath", "io/github/astonbitecode");
+        tokens.put("module", "j4rs");
+        tokens.put("revision", "0.5.1");
+        tokens.put("artifact", "j4rs");
+        tokens.put("type", "jar");
+        tokens.put("ext", "jar");
+        tokens.put("classifier", classifier);
+        return tokens;
+    }
+
+    @Test
+    public void expandIvyPattern() {
+        assert (IvyDeployer.expand(IvyDeployer.IVY_PATTERN, tokens(""))
+                .equals("io.github.astonbitecode/j4rs/0.5.1/jars/j4rs-0.5.1.jar"));
+        assert (IvyDeployer.expand(IvyDeployer.IVY_PATTERN, tokens("sources"))
+                .equals("io.github.astonbitecode/j4rs/0.5.1/jars/j4rs-0.5.1-sources.jar"));
+    }
+
+    @Test
+    public void expandGradlePattern() {
+        assert (IvyDeployer.expand(IvyDeployer.GRADLE_PATTERN, tokens(null))
+                .equals("io.github.astonbitecode/j4rs/0.5.1/j4rs-0.5.1.jar"));
+        assert (IvyDeployer.expand("[orgPath]/[module]/[revision]/[artifact]-[revision].[ext]", tokens(null))
+                .equals("io/github/astonbitecode/j4rs/0.5.1/j4rs-0.5.1.jar"));
+    }
+
+    @Test
+    public void defaultPattern() {
+        IvyDeployer deployer = new IvyDeployer("https://my.ivy.repo/", "", "depltarget");
+        assert (deployer.getPattern().equals(IvyDeployer.IVY_PATTERN));
+        assert (deployer.getRepoBase().equals("https://my.ivy.repo"));
+        new File("depltarget").delete();
+    }
+}
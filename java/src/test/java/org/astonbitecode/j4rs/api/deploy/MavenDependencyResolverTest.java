@@ -0,0 +1,146 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.junit.Test;
+
+import java.io.ByteArrayInputStream;
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.nio.charset.StandardCharsets;
+import java.util.*;
+import java.util.stream.Collectors;
+
+public class MavenDependencyResolverTest {
+    private static String pom(String coordinates, String body) {
+        String[] parts = coordinates.split(":");
+        return String.format("<project><groupId>%s</groupId><artifactId>%s</artifactId><version>%s</version>%s</project>",
+                parts[0], parts[1], parts[2], body);
+    }
+
+    private static String dependency(String coordinates, String extra) {
+        String[] parts = coordinates.split(":");
+        String version = parts.length > 2 ? "<version>" + parts[2] + "</version>" : "";
+        return String.format("<dependency><groupId>%s</groupId><artifactId>%s</artifactId>%s%s</dependency>",
+                parts[0], parts[1], version, extra);
+    }
+
+    private static MavenDependencyResolver resolver(Map<String, String> poms) {
+        return new MavenDependencyResolver((groupId, artifactId, version) -> {
+            String pom = poms.get(groupId + ":" + artifactId + ":" + version);
+            if (pom == null) {
+                throw new FileNotFoundException(groupId + ":" + artifactId + ":" + version);
+            }
+            return new ByteArrayInputStream(pom.getBytes(StandardCharsets.UTF_8));
+        });
+    }
+
+    private static List<String> resolve(Map<String, String> poms, String coordinates) throws Exception {
+        String[] parts = coordinates.split(":");
+        return resolver(poms).resolve(parts[0], parts[1], parts[2], "").stream()
+                .map(Object::toString)
+                .collect(Collectors.toList());
+    }
+
+    @Test
+    public void resolveTransitively() throws Exception {
+        Map<String, String> poms = new HashMap<>();
+        poms.put("g:a:1", pom("g:a:1", "<dependencies>"
+                + dependency("g:b:1", "")
+                + dependency("g:test:1", "<scope>test</scope>")
+                + dependency("g:provided:1", "<scope>provided</scope>")
+                + dependency("g:optional:1", "<optional>true</optional>")
+                + "</dependencies>"));
+        poms.put("g:b:1", pom("g:b:1", "<dependencies>"
+                + dependency("g:c:1", "<scope>runtime</scope>")
+                + "</dependencies>"));
+        poms.put("g:c:1", pom("g:c:1", ""));
+
+        assert (resolve(poms, "g:a:1").equals(Arrays.asList("g:a:1", "g:b:1", "g:c:1")));
+    }
+
+    @Test
+    public void nearestWins() throws Exception {
+        Map<String, String> poms = new HashMap<>();
+        poms.put("g:a:1", pom("g:a:1", "<dependencies>"
+                + dependency("g:b:1", "")
+                + dependency("g:c:2", "")
+                + "</dependencies>"));
+        poms.put("g:b:1", pom("g:b:1", "<dependencies>" + dependency("g:c:1", "") + "</dependencies>"));
+        poms.put("g:c:2", pom("g:c:2", ""));
+
+        assert (resolve(poms, "g:a:1").equals(Arrays.asList("g:a:1", "g:b:1", "g:c:2")));
+    }
+
+    @Test
+    public void exclusions() throws Exception {
+        Map<String, String> poms = new HashMap<>();
+        poms.put("g:a:1", pom("g:a:1", "<dependencies>"
+                + dependency("g:b:1", "<exclusions><exclusion><groupId>g</groupId><artifactId>c</artifactId></exclusion></exclusions>")
+                + "</dependencies>"));
+        poms.put("g:b:1", pom("g:b:1", "<dependencies>"
+                + dependency("g:c:1", "")
+                + dependency("g:d:1", "")
+                + "</dependencies>"));
+        poms.put("g:d:1", pom("g:d:1", "<dependencies>" + dependency("g:c:1", "") + "</dependencies>"));
+
+        assert (resolve(poms, "g:a:1").equals(Arrays.asList("g:a:1", "g:b:1", "g:d:1")));
+    }
+
+    @Test
+    public void parentPropertiesAndDependencyManagement() throws Exception {
+        Map<String, String> poms = new HashMap<>();
+        poms.put("g:parent:1", "<project><groupId>g</groupId><artifactId>parent</artifactId><version>1</version>"
+                + "<packaging>pom</packaging>"
+                + "<properties><c.version>3</c.version></properties>"
+                + "<dependencyManagement><dependencies>" + dependency("g:c:${c.version}", "") + "</dependencies></dependencyManagement>"
+                + "</project>");
+        poms.put("g:a:1", "<project><parent><groupId>g</groupId><artifactId>parent</artifactId><version>1</version></parent>"
+                + "<artifactId>a</artifactId>"
+                + "<dependencies>"
+                + dependency("g:b:${project.version}", "")
+                + dependency("g:c", "")
+                + "</dependencies></project>");
+        poms.put("g:b:1", pom("g:b:1", ""));
+        poms.put("g:c:3", pom("g:c:3", ""));
+
+        assert (resolve(poms, "g:a:1").equals(Arrays.asList("g:a:1", "g:b:1", "g:c:3")));
+    }
+
+    @Test(expected = IOException.class)
+    public void externalEntitiesAreNotResolved() throws Exception {
+        Map<String, String> poms = new HashMap<>();
+        poms.put("g:a:1", "<!DOCTYPE project [<!ENTITY xxe SYSTEM \"file:///etc/passwd\">]>"
+                + pom("g:a:1", "<description>&xxe;</description>"));
+
+        resolve(poms, "g:a:1");
+    }
+
+    @Test
+    public void interpolate() {
+        Map<String, String> properties = new HashMap<>();
+        properties.put("a", "${b}");
+        properties.put("b", "value");
+        assert (MavenDependencyResolver.interpolate("x-${a}-${missing}", properties).equals("x-value-${missing}"));
+    }
+
+    @Test
+    public void normalizeVersion() {
+        assert (MavenDependencyResolver.normalizeVersion("1.0").equals("1.0"));
+        assert (MavenDependencyResolver.normalizeVersion("[1.0]").equals("1.0"));
+        assert (MavenDependencyResolver.normalizeVersion("[1.0,2.0)").equals("1.0"));
+        assert (MavenDependencyResolver.normalizeVersion("(,2.0]").equals("2.0"));
+    }
+}
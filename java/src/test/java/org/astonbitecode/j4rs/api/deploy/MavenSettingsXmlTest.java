@@ -0,0 +1,63 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.junit.Test;
+
+import java.io.ByteArrayInputStream;
+import java.io.IOException;
+import java.nio.charset.StandardCharsets;
+
+public class MavenSettingsXmlTest {
+    private static final String SETTINGS = "<settings xmlns=\"http://maven.apache.org/SETTINGS/1.0.0\">"
+            + "<servers>"
+            + "<server><id>myrepo</id><username>user</username><password>${user.name}</password></server>"
+            + "</servers>"
+            + "<proxies>"
+            + "<proxy><id>inactive</id><active>false</active><host>inactive.my.com</host></proxy>"
+            + "<proxy><id>active</id><host>proxy.my.com</host><port>3128</port><nonProxyHosts>localhost</nonProxyHosts></proxy>"
+            + "</proxies>"
+            + "</settings>";
+
+    @Test
+    public void parse() throws Exception {
+        MavenSettingsXml settings = MavenSettingsXml.parse(new ByteArrayInputStream(SETTINGS.getBytes(StandardCharsets.UTF_8)));
+
+        MavenSettingsXml.Server server = settings.getServer("myrepo");
+        assert (server.username.equals("user"));
+        assert (server.password.equals(System.getProperty("user.name")));
+        assert (settings.getServer("other") == null);
+
+        MavenSettingsXml.Proxy proxy = settings.getProxy();
+        assert (proxy.host.equals("proxy.my.com"));
+        assert (proxy.port == 3128);
+        assert (proxy.username == null);
+        assert (proxy.nonProxyHosts.equals("localhost"));
+    }
+
+    @Test(expected = IOException.class)
+    public void externalEntitiesAreNotResolved() throws Exception {
+        String settings = "<!DOCTYPE settings [<!ENTITY xxe SYSTEM \"file:///etc/passwd\">]>"
+                + "<settings><servers><server><id>myrepo</id><username>&xxe;</username></server></servers></settings>";
+        MavenSettingsXml.parse(new ByteArrayInputStream(settings.getBytes(StandardCharsets.UTF_8)));
+    }
+
+    @Test
+    public void loadMissingFile() throws Exception {
+        MavenSettingsXml settings = MavenSettingsXml.load("/non/existing/settings.xml");
+        assert (settings.getServer("myrepo") == null);
+        assert (settings.getProxy() == null);
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.deploy;
+
+import org.astonbitecode.j4rs.utils.Utils;
+import org.junit.Test;
+
+import javax.tools.JavaCompiler;
+import javax.tools.ToolProvider;
+import java.io.FileNotFoundException;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.jar.JarEntry;
+import java.util.jar.JarOutputStream;
+
+public class ReloadableJarsTest {
+
+    @Test
+    public void reloadJar() throws Exception {
+        Path jar = Files.createTempFile("j4rs-reloadable-", ".jar");
+        writeJar(jar, "1");
+        ReloadableJars.reload(jar.toString());
+        Object first = Utils.forNameEnhanced("J4rsReloadable").getConstructor().newInstance();
+        assert (first.toString().equals("1"));
+
+        writeJar(jar, "2");
+        ReloadableJars.reload(jar.toString());
+        Object second = Utils.forNameEnhanced("J4rsReloadable").getConstructor().newInstance();
+        assert (second.toString().equals("2"));
+        assert (first.toString().equals("1"));
+        assert (ReloadableJars.loadClass("java.lang.String") == null);
+        Files.delete(jar);
+    }
+
+    @Test(expected = FileNotFoundException.class)
+    public void reloadMissingJar() throws Exception {
+        ReloadableJars.reload("/non/existing/j4rs.jar");
+    }
+
+    // Writes a jar with a class J4rsReloadable, whose toString returns the version
+    private static void writeJar(Path jar, String version) throws Exception {
+        Path dir = Files.createTempDirectory("j4rs-reloadable-src");
+        Path source = dir.resolve("J4rsReloadable.java");
+        String code = "public class J4rsReloadable { public String toString() { return \"" + version + "\"; } }";
+        Files.write(source, code.getBytes(StandardCharsets.UTF_8));
+        JavaCompiler compiler = ToolProvider.getSystemJavaCompiler();
+        assert (compiler.run(null, null, null, source.toString()) == 0);
+        try (JarOutputStream out = new JarOutputStream(Files.newOutputStream(jar))) {
+            out.putNextEntry(new JarEntry("J4rsReloadable.class"));
+            out.write(Files.readAllBytes(dir.resolve("J4rsReloadable.class")));
+            out.closeEntry();
+        }
+    }
+}
@@ -18,6 +18,7 @@ import org.junit.Test;
 
 import java.io.File;
 import java.io.IOException;
+import java.net.Authenticator;
 
 import static org.mockito.Mockito.*;
 
@@ -74,4 +75,33 @@ public class SimpleMavenDeployerTest {
         f.delete();
     }
 
+    @Test
+    public void nonProxyHosts() {
+        SimpleMavenDeployer md = new SimpleMavenDeployer("https://my.artifactory.com", true, "depltarget")
+                .withProxy("proxy.my.com", 3128, null, null, "localhost|*.my.com");
+        assert (md.isNonProxyHost("localhost"));
+        assert (md.isNonProxyHost("artifactory.my.com"));
+        assert (!md.isNonProxyHost("repo.maven.apache.org"));
+        assert (!new SimpleMavenDeployer("https://my.artifactory.com", true, "depltarget").isNonProxyHost("localhost"));
+
+        File f = new File("depltarget");
+        f.delete();
+    }
+
+    @Test
+    public void proxyCredentialsDoNotReplaceTheDefaultAuthenticator() {
+        Authenticator defaultAuthenticator = Authenticator.getDefault();
+        SimpleMavenDeployer md = new SimpleMavenDeployer("https://my.artifactory.com", true, "depltarget")
+                .withProxy("localhost", 1, "user", "pass", null);
+        try {
+            md.openStream("https://my.artifactory.com/a.jar");
+        } catch (IOException error) {
+            // Nothing listens to the proxy port
+        }
+        assert (Authenticator.getDefault() == defaultAuthenticator);
+
+        File f = new File("depltarget");
+        f.delete();
+    }
+
 }
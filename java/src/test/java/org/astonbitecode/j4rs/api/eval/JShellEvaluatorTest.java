@@ -0,0 +1,46 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.eval;
+
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.junit.Test;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+public class JShellEvaluatorTest {
+
+    @Test
+    public void evalAndBind() {
+        try (JShellEvaluator evaluator = new JShellEvaluator()) {
+            assert (evaluator.eval("int x = 3; x * 2").equals(6));
+            assert (evaluator.eval("x").equals(3));
+            assert (evaluator.eval("System.out.print(\"\")") == null);
+
+            List<String> list = new ArrayList<>(Arrays.asList("a"));
+            evaluator.bind("list", list);
+            evaluator.eval("list.add(\"b\")");
+            assert (list.equals(Arrays.asList("a", "b")));
+        }
+    }
+
+    @Test(expected = InvocationException.class)
+    public void evalInvalidCode() {
+        try (JShellEvaluator evaluator = new JShellEvaluator()) {
+            evaluator.eval("undefined + 1");
+        }
+    }
+}
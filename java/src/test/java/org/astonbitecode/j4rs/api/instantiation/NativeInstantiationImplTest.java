@@ -22,7 +22,10 @@ import org.astonbitecode.j4rs.utils.ClassWithDummyAtConstructor;
 import org.astonbitecode.j4rs.utils.Dummy;
 import org.junit.Test;
 
+import java.util.Arrays;
 import java.util.List;
+import java.util.Map;
+import java.util.stream.Collectors;
 
 public class NativeInstantiationImplTest {
 
@@ -121,4 +124,18 @@ public class NativeInstantiationImplTest {
                 .createInstance(ClassWithDummyAtConstructor.class.getName(), generatedArgs);
         assert (instance.getClazz().equals(ClassWithDummyAtConstructor.class));
     }
+
+    @Test
+    public void createJavaMapFromJson() {
+        String json = "[[{\"i\":1},\"one\"],[{\"i\":2},[1,2]]]";
+        Map<?, ?> typed = NativeInstantiationImpl.createJavaMapFromJson(Dummy.class.getName(), "java.lang.Object",
+                json);
+        List<Dummy> keys = typed.keySet().stream().map(key -> (Dummy) key).collect(Collectors.toList());
+        assert (keys.get(0).getI() == 1 && keys.get(1).getI() == 2);
+        assert (typed.get(keys.get(0)).equals("one"));
+        assert (typed.get(keys.get(1)).equals(Arrays.asList(1, 2)));
+
+        Map<?, ?> untyped = NativeInstantiationImpl.createJavaMapFromJson("java.lang.Object", "java.lang.Object", json);
+        assert (untyped.keySet().iterator().next() instanceof Map);
+    }
 }
@@ -0,0 +1,38 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.junit.Test;
+
+import javax.swing.SwingUtilities;
+
+public class EdtInvocationTest {
+
+    @Test
+    public void invokeOnEdt() {
+        Instance instance = new JsonInvocationImpl(SwingUtilities.class);
+        Instance result = instance.invokeOnEdt("isEventDispatchThread");
+        assert ((Boolean) result.getObject());
+    }
+
+    @Test(expected = InvocationException.class)
+    public void exceptionsArePropagated() {
+        EdtInvocation.run(() -> {
+            throw new InvocationException("Failure in the EDT");
+        });
+    }
+}
@@ -25,6 +25,7 @@ import org.junit.Test;
 
 import java.util.Arrays;
 import java.util.List;
+import java.util.concurrent.atomic.AtomicLong;
 import java.util.concurrent.atomic.AtomicReference;
 
 import static org.mockito.Mockito.*;
@@ -242,6 +243,22 @@ public class JsonInvocationImplTest {
         }
     }
 
+    @Test
+    public void setFieldInstance() {
+        Instance ni = new JsonInvocationImpl(new DummyWithFields(), DummyWithFields.class);
+
+        ni.setField("pubInt", new InvocationArg(new JsonInvocationImpl(22, Integer.class)));
+        Integer i1 = (Integer) ni.field("pubInt").getObject();
+        assert (i1.equals(22));
+
+        try {
+            ni.setField("privateInt", new InvocationArg(new JsonInvocationImpl(22, Integer.class)));
+            assert (false);
+        } catch (InvocationException ie) {
+            assert (true);
+        }
+    }
+
     @Test
     public void invokeMethodWithArgumentOfSubclassType() {
         Instance instance = new JsonInvocationImpl(new ClassWithDummyAtConstructor(new ChildDummy()),
@@ -310,6 +327,27 @@ public class JsonInvocationImplTest {
         assert (iNull1.checkEquals(iNull2));
     }
 
+    @Test
+    public void invokeOnInterface() {
+        java.util.Map<String, Integer> map = java.util.Collections.unmodifiableMap(new java.util.HashMap<>());
+        JsonInvocationImpl instance = new JsonInvocationImpl(map, map.getClass());
+        // Default method of the Map interface, on a private Map implementation
+        Instance res = instance.invokeOnInterface("java.util.Map", "getOrDefault",
+                new InvocationArg(new JsonInvocationImpl("key", String.class)),
+                new InvocationArg(new JsonInvocationImpl(3, Integer.class)));
+        assert (((Integer) res.getObject()) == 3);
+        // Static method of an interface
+        JsonInvocationImpl staticInstance = new JsonInvocationImpl(java.util.Comparator.class);
+        Instance comparator = staticInstance.invokeOnInterface("java.util.Comparator", "naturalOrder");
+        assert (comparator.getObject() instanceof java.util.Comparator);
+    }
+
+    @Test(expected = InvocationException.class)
+    public void invokeOnInterfaceNotImplemented() {
+        JsonInvocationImpl instance = new JsonInvocationImpl("astring", String.class);
+        instance.invokeOnInterface("java.util.Map", "size");
+    }
+
     private class TestCallback extends NativeCallbackToRustFutureSupport {
         private AtomicReference<String> s = new AtomicReference<>(null);
 
@@ -327,4 +365,24 @@ public class JsonInvocationImplTest {
             return this.s.get();
         }
     }
+
+    @Test
+    public void lenientNumericCoercion() {
+        JsonInvocationImpl toTest = new JsonInvocationImpl(new AtomicLong(1), AtomicLong.class);
+        InvocationArg arg = new InvocationArg(new JsonInvocationImpl(2, Integer.class));
+        try {
+            toTest.invoke("addAndGet", arg);
+            assert (false);
+        } catch (InvocationException error) {
+            // Expected: there is no exact match
+        }
+
+        NumericCoercion.setLenient(true);
+        try {
+            Instance invocation = toTest.invoke("addAndGet", arg);
+            assert (((Long) invocation.getObject()) == 3L);
+        } finally {
+            NumericCoercion.setLenient(false);
+        }
+    }
 }
@@ -0,0 +1,91 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.api.instantiation.NativeInstantiationImpl;
+import org.astonbitecode.j4rs.errors.InstantiationException;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.junit.After;
+import org.junit.Test;
+
+public class MemberAccessTest {
+    @After
+    public void disable() {
+        MemberAccess.setEnabled(false);
+    }
+
+    @Test
+    public void nonPublicConstructor() {
+        String className = Secret.class.getName();
+        String[] signature = { "java.lang.String" };
+        InvocationArg arg = new InvocationArg("java.lang.String", "\"value\"");
+        try {
+            NativeInstantiationImpl.instantiateWithSignature(className, signature, arg);
+            assert false;
+        } catch (InstantiationException error) {
+            // The access is not enabled
+        }
+
+        MemberAccess.setEnabled(true);
+        Instance instance = NativeInstantiationImpl.instantiateWithSignature(className, signature, arg);
+        assert ((Secret) instance.getObject()).value.equals("value");
+    }
+
+    @Test
+    public void nonPublicMethod() {
+        Instance instance = new JsonInvocationImpl(new Secret("a"), Secret.class);
+        String[] signature = { "java.lang.String" };
+        InvocationArg arg = new InvocationArg("java.lang.String", "\"b\"");
+        try {
+            instance.invokeWithSignature("concat", signature, arg);
+            assert false;
+        } catch (InvocationException error) {
+            // The access is not enabled
+        }
+
+        MemberAccess.setEnabled(true);
+        assert instance.invokeWithSignature("concat", signature, arg).getObject().equals("ab");
+    }
+
+    @Test
+    public void nonPublicPreparedMethod() throws Exception {
+        Instance instance = new JsonInvocationImpl(new Secret("a"), Secret.class);
+        String[] signature = { "java.lang.String" };
+        try {
+            new PreparedInvocation(instance, "concat", signature);
+            assert false;
+        } catch (NoSuchMethodException error) {
+            // The access is not enabled
+        }
+
+        MemberAccess.setEnabled(true);
+        PreparedInvocation prepared = new PreparedInvocation(instance, "concat", signature);
+        assert prepared.invoke(new InvocationArg("java.lang.String", "\"b\"")).getObject().equals("ab");
+    }
+
+    private static class Secret {
+        private final String value;
+
+        private Secret(String value) {
+            this.value = value;
+        }
+
+        private String concat(String other) {
+            return value + other;
+        }
+    }
+}
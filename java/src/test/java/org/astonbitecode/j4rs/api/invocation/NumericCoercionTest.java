@@ -0,0 +1,44 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.junit.Test;
+
+public class NumericCoercionTest {
+
+    @Test
+    public void costs() {
+        assert (NumericCoercion.cost(Integer.class, Integer.class) == 0);
+        assert (NumericCoercion.cost(Integer.class, Number.class) == 0);
+        assert (NumericCoercion.cost(Integer.class, int.class) == 1);
+        assert (NumericCoercion.cost(Integer.class, long.class) < NumericCoercion.cost(Integer.class, double.class));
+        assert (NumericCoercion.cost(int.class, Long.class) > 0);
+        assert (NumericCoercion.cost(Character.class, int.class) > 0);
+        assert (NumericCoercion.cost(Long.class, int.class) == -1);
+        assert (NumericCoercion.cost(Integer.class, short.class) == -1);
+        assert (NumericCoercion.cost(Boolean.class, int.class) == -1);
+        assert (NumericCoercion.cost(String.class, long.class) == -1);
+    }
+
+    @Test
+    public void coerce() {
+        Object[] args = new Object[]{1, 'a', 2.5f, "a string"};
+        NumericCoercion.coerce(new Class<?>[]{long.class, int.class, Double.class, Object.class}, args);
+        assert (args[0].equals(1L));
+        assert (args[1].equals(97));
+        assert (args[2].equals(2.5));
+        assert (args[3].equals("a string"));
+    }
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.errors.InvocationException;
+import org.junit.Test;
+
+public class PreparedInvocationTest {
+
+    @Test
+    public void invokeRepeatedly() throws Exception {
+        Instance instance = new JsonInvocationImpl(new StringBuilder(), StringBuilder.class);
+        PreparedInvocation toTest = new PreparedInvocation(instance, "append", new String[]{"java.lang.String"});
+
+        toTest.invoke(new InvocationArg(new JsonInvocationImpl("a", String.class)));
+        Instance result = toTest.invoke(new InvocationArg(new JsonInvocationImpl("b", String.class)));
+        assert (result.getObject().toString().equals("ab"));
+    }
+
+    @Test
+    public void invokeStatic() throws Exception {
+        Instance instance = InstanceGenerator.create(Integer.class);
+        PreparedInvocation toTest = new PreparedInvocation(instance, "parseInt", new String[]{"java.lang.String"});
+
+        Instance result = toTest.invoke(new InvocationArg(new JsonInvocationImpl("42", String.class)));
+        assert (result.getObject().equals(42));
+    }
+
+    @Test(expected = NoSuchMethodException.class)
+    public void methodNotFound() throws Exception {
+        Instance instance = new JsonInvocationImpl(new StringBuilder(), StringBuilder.class);
+        new PreparedInvocation(instance, "append", new String[]{"java.util.List"});
+    }
+
+    @Test(expected = InvocationException.class)
+    public void wrongNumberOfArguments() throws Exception {
+        Instance instance = new JsonInvocationImpl(new StringBuilder(), StringBuilder.class);
+        new PreparedInvocation(instance, "append", new String[]{"java.lang.String"}).invoke();
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.junit.Test;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.function.Function;
+import java.util.function.Predicate;
+import java.util.stream.Collectors;
+import java.util.stream.Stream;
+
+public class RustFunctionsTest {
+
+    @Test
+    public void adaptCallbacks() {
+        Function<Object, Object> twice = RustFunctions.function(arg -> (Long) arg * 2);
+        assert twice.andThen(twice).apply(3L).equals(12L);
+
+        Predicate<Object> even = RustFunctions.predicate(arg -> (Long) arg % 2 == 0);
+        List<Object> evens = Stream.of(1L, 2L, 3L, 4L).filter(even).collect(Collectors.toList());
+        assert evens.size() == 2;
+        assert even.negate().test(3L);
+
+        List<Object> consumed = new ArrayList<>();
+        RustFunctions.consumer(arg -> {
+            consumed.add(arg);
+            return null;
+        }).accept("a");
+        assert consumed.get(0).equals("a");
+
+        assert RustFunctions.supplier(arg -> "supplied").get().equals("supplied");
+
+        List<Object> calls = new ArrayList<>();
+        RustFunctions.runnable(arg -> calls.add(arg)).run();
+        assert calls.size() == 1 && calls.get(0) == null;
+    }
+}
@@ -0,0 +1,39 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.invocation;
+
+import org.astonbitecode.j4rs.api.Instance;
+import org.astonbitecode.j4rs.api.dtos.InvocationArg;
+import org.astonbitecode.j4rs.errors.InvocationTimeoutException;
+import org.junit.Test;
+
+import java.util.concurrent.CountDownLatch;
+
+public class TimedInvocationTest {
+
+    @Test
+    public void invokeWithinTimeout() {
+        Instance instance = new JsonInvocationImpl(new StringBuilder(), StringBuilder.class);
+        Instance result = instance.invokeWithTimeout(10000, "append",
+                new InvocationArg(new JsonInvocationImpl("a", String.class)));
+        assert (result.getObject().toString().equals("a"));
+    }
+
+    @Test(expected = InvocationTimeoutException.class)
+    public void invokeTimesOut() {
+        Instance instance = new JsonInvocationImpl(new CountDownLatch(1), CountDownLatch.class);
+        instance.invokeWithTimeout(100, "await");
+    }
+}
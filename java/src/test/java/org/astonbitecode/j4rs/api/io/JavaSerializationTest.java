@@ -0,0 +1,38 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import org.junit.Test;
+
+import java.io.NotSerializableException;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+public class JavaSerializationTest {
+
+    @Test
+    public void serializeAndDeserialize() throws Exception {
+        List<String> list = new ArrayList<>(Arrays.asList("a", "b"));
+        Object deserialized = JavaSerialization.deserialize(JavaSerialization.serialize(list));
+        assert (deserialized instanceof ArrayList);
+        assert (deserialized.equals(list));
+    }
+
+    @Test(expected = NotSerializableException.class)
+    public void serializeNotSerializable() throws Exception {
+        JavaSerialization.serialize(new Object());
+    }
+}
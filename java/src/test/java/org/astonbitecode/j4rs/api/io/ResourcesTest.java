@@ -0,0 +1,28 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import org.junit.Test;
+
+public class ResourcesTest {
+
+    @Test
+    public void readResource() throws Exception {
+        byte[] bytes = Resources.readResource("/java/lang/String.class");
+        assert (bytes.length > 4);
+        assert ((bytes[0] & 0xff) == 0xca && (bytes[1] & 0xff) == 0xfe && (bytes[2] & 0xff) == 0xba && (bytes[3] & 0xff) == 0xbe);
+        assert (Resources.readResource("non/existing/resource.txt") == null);
+    }
+}
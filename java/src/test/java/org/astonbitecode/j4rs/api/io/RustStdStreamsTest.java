@@ -0,0 +1,40 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.io;
+
+import org.junit.Test;
+
+import java.io.PrintStream;
+import java.util.ArrayList;
+import java.util.List;
+
+public class RustStdStreamsTest {
+
+    @Test
+    public void forwardLines() throws Exception {
+        List<String> lines = new ArrayList<>();
+        PrintStream ps = new PrintStream(new RustStdStreams.LineForwardingOutputStream(lines::add), true, "UTF-8");
+        ps.print("first ");
+        ps.println("line");
+        ps.print("second line\r\nκαλημέρα\nincomplete");
+        assert (lines.size() == 3);
+        assert (lines.get(0).equals("first line"));
+        assert (lines.get(1).equals("second line"));
+        assert (lines.get(2).equals("καλημέρα"));
+        ps.close();
+        assert (lines.size() == 4);
+        assert (lines.get(3).equals("incomplete"));
+    }
+}
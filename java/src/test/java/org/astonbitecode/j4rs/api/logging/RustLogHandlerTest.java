@@ -0,0 +1,34 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.logging;
+
+import org.junit.Test;
+
+import java.util.logging.Level;
+
+public class RustLogHandlerTest {
+
+    @Test
+    public void levelMapping() {
+        assert (RustLogHandler.toRustLevel(Level.SEVERE) == 1);
+        assert (RustLogHandler.toRustLevel(Level.WARNING) == 2);
+        assert (RustLogHandler.toRustLevel(Level.INFO) == 3);
+        assert (RustLogHandler.toRustLevel(Level.CONFIG) == 4);
+        assert (RustLogHandler.toRustLevel(Level.FINE) == 4);
+        assert (RustLogHandler.toRustLevel(Level.FINEST) == 5);
+        assert (RustLogHandler.toJavaLevel(0) == Level.OFF);
+        assert (RustLogHandler.toJavaLevel(4) == Level.FINE);
+    }
+}
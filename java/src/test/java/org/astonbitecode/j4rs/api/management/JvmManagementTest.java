@@ -0,0 +1,37 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.management;
+
+import org.junit.Test;
+
+import java.util.List;
+import java.util.Map;
+
+public class JvmManagementTest {
+
+    @Test
+    public void heapUsage() {
+        Map<String, Object> usage = JvmManagement.heapUsage();
+        assert ((Long) usage.get("used") > 0);
+        assert ((Long) usage.get("committed") >= (Long) usage.get("used"));
+    }
+
+    @Test
+    public void threadDump() {
+        List<Map<String, Object>> threads = JvmManagement.threadDump();
+        assert (threads.size() == JvmManagement.threadCount());
+        assert (threads.stream().anyMatch(thread -> thread.get("name").equals(Thread.currentThread().getName())));
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.reflection;
+
+import org.junit.Test;
+
+import java.util.Arrays;
+
+public class FieldValuesTest {
+    public static class Pojo {
+        private final String name = "a name";
+        private final boolean active = true;
+        private final int hidden = 3;
+        private final Pojo self = this;
+
+        public String getName() {
+            return name;
+        }
+
+        public boolean isActive() {
+            return active;
+        }
+
+        public Pojo self() {
+            return self;
+        }
+    }
+
+    @Test
+    public void of() throws Exception {
+        Pojo pojo = new Pojo();
+        Object[] values = FieldValues.of(pojo, new String[]{"name", "active", "hidden", "self"});
+        assert (Arrays.equals(values, new Object[]{"a name", true, 3, pojo}));
+    }
+
+    @Test(expected = NoSuchFieldException.class)
+    public void ofMissingField() throws Exception {
+        FieldValues.of(new Pojo(), new String[]{"missing"});
+    }
+
+    @Test
+    public void elements() {
+        assert (Arrays.equals(FieldValues.elements(new int[]{1, 2}), new Object[]{1, 2}));
+        assert (Arrays.equals(FieldValues.elements(Arrays.asList("a", "b")), new Object[]{"a", "b"}));
+    }
+
+    @Test(expected = IllegalArgumentException.class)
+    public void elementsOfOther() {
+        FieldValues.elements("a");
+    }
+}
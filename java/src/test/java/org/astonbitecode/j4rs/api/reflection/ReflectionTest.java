@@ -0,0 +1,65 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.reflection;
+
+import org.junit.Test;
+
+import java.util.List;
+import java.util.Map;
+
+public class ReflectionTest {
+
+    @Test
+    public void classExists() {
+        assert (Reflection.classExists("java.util.ArrayList"));
+        assert (Reflection.classExists("int"));
+        assert (!Reflection.classExists("com.example.Missing"));
+    }
+
+    @Test
+    public void methodExists() throws Exception {
+        assert (Reflection.methodExists("java.util.ArrayList", "add", new String[]{"java.lang.String"}));
+        assert (Reflection.methodExists("java.util.ArrayList", "get", new String[]{"java.lang.Integer"}));
+        assert (Reflection.methodExists("java.util.ArrayList", "get", new String[]{"int"}));
+        assert (Reflection.methodExists("java.lang.Integer", "valueOf", new String[]{"java.lang.String"}));
+        assert (!Reflection.methodExists("java.util.ArrayList", "get", new String[]{"java.lang.String"}));
+        assert (!Reflection.methodExists("java.util.ArrayList", "missing", new String[]{}));
+        assert (!Reflection.methodExists("java.util.ArrayList", "add", new String[]{"com.example.Missing"}));
+    }
+
+    @Test(expected = ClassNotFoundException.class)
+    public void methodExistsOfMissingClass() throws Exception {
+        Reflection.methodExists("com.example.Missing", "get", new String[]{});
+    }
+
+    @Test
+    public void listMethods() throws Exception {
+        List<Map<String, Object>> methods = Reflection.listMethods("java.lang.Integer");
+        Map<String, Object> parseInt = methods.stream()
+                .filter(m -> m.get("name").equals("parseInt") && ((List<?>) m.get("parameterTypes")).size() == 1)
+                .findFirst()
+                .get();
+        assert (parseInt.get("parameterTypes").equals(java.util.Collections.singletonList("java.lang.String")));
+        assert (parseInt.get("returnType").equals("int"));
+        assert (parseInt.get("static").equals(true));
+        assert (methods.get(0).get("name").toString().compareTo(methods.get(1).get("name").toString()) <= 0);
+    }
+
+    @Test
+    public void hasAnnotation() throws Exception {
+        assert (Reflection.hasAnnotation("java.lang.Runnable", "java.lang.FunctionalInterface"));
+        assert (!Reflection.hasAnnotation("java.util.ArrayList", "java.lang.FunctionalInterface"));
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.remote;
+
+import org.junit.Test;
+
+import java.io.BufferedReader;
+import java.io.StringReader;
+import java.io.StringWriter;
+
+public class RemoteServerTest {
+
+    private String serve(RemoteServer server, String... requests) throws Exception {
+        StringWriter out = new StringWriter();
+        server.serve(new BufferedReader(new StringReader(String.join("\n", requests))), out);
+        return out.toString();
+    }
+
+    @Test
+    public void invocations() throws Exception {
+        RemoteServer server = new RemoteServer();
+        String responses = serve(server,
+                "{\"id\":1,\"op\":\"create_instance\",\"class\":\"java.lang.StringBuilder\",\"args\":[{\"class\":\"java.lang.String\",\"json\":\"\\\"abc\\\"\"}]}",
+                "{\"id\":2,\"op\":\"invoke\",\"handle\":1,\"method\":\"append\",\"args\":[{\"handle\":1}]}",
+                "{\"id\":3,\"op\":\"invoke\",\"handle\":1,\"method\":\"toString\",\"args\":[]}",
+                "{\"id\":4,\"op\":\"to_json\",\"handle\":3}",
+                "{\"op\":\"release\",\"handle\":2}");
+        String[] lines = responses.split("\n");
+        assert (lines.length == 4);
+        assert (lines[0].equals("{\"id\":1,\"handle\":1,\"class\":\"java.lang.StringBuilder\"}"));
+        assert (lines[2].equals("{\"id\":3,\"handle\":3,\"class\":\"java.lang.String\"}"));
+        assert (lines[3].equals("{\"id\":4,\"json\":\"\\\"abcabc\\\"\"}"));
+        assert (server.instancesCount() == 2);
+    }
+
+    @Test
+    public void errors() throws Exception {
+        String responses = serve(new RemoteServer(),
+                "{\"id\":1,\"op\":\"invoke_static\",\"class\":\"java.lang.Integer\",\"method\":\"parseInt\",\"args\":[{\"class\":\"java.lang.String\",\"json\":\"\\\"NaN\\\"\"}]}",
+                "{\"id\":2,\"op\":\"to_json\",\"handle\":7}",
+                "{\"op\":\"shutdown\"}",
+                "{\"id\":3,\"op\":\"ping\"}");
+        String[] lines = responses.split("\n");
+        assert (lines.length == 2);
+        assert (lines[0].startsWith("{\"id\":1,\"error\":{\"className\":\"java.lang.NumberFormatException\""));
+        assert (lines[1].contains("No instance with handle 7"));
+    }
+
+    @Test
+    public void handleRequest() throws Exception {
+        RemoteServer server = new RemoteServer();
+        assert (server.handleRequest("{\"id\":1,\"op\":\"ping\"}").equals("{\"id\":1}"));
+        server.handleRequest("{\"id\":2,\"op\":\"create_instance\",\"class\":\"java.lang.Object\",\"args\":[]}");
+        assert (server.instancesCount() == 1);
+        assert (server.handleRequest("{\"op\":\"release\",\"handle\":1}") == null);
+        assert (server.instancesCount() == 0);
+    }
+
+    @Test
+    public void errorResponse() {
+        Exception error = new IllegalArgumentException("invalid");
+        assert (RemoteServer.errorResponse("{\"id\":3,\"op\":\"ping\"", error).startsWith("{\"error\""));
+        assert (RemoteServer.errorResponse("{\"id\":3,\"op\":\"unknown\"}", error)
+                .startsWith("{\"id\":3,\"error\":{\"className\":\"java.lang.IllegalArgumentException\""));
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.scala;
+
+import org.junit.Test;
+
+public class ScalaSupportTest {
+
+    public interface Named {
+        static String describe$(Named $this, int times) {
+            return "named " + times;
+        }
+    }
+
+    public static class Counter implements Named {
+        public int $plus(int a, int b) {
+            return a + b;
+        }
+
+        public String $colon$colon(String s) {
+            return "::" + s;
+        }
+    }
+
+    @Test
+    public void encodeName() {
+        assert (ScalaSupport.encodeName("+").equals("$plus"));
+        assert (ScalaSupport.encodeName("::").equals("$colon$colon"));
+        assert (ScalaSupport.encodeName("unary_!").equals("unary_$bang"));
+        assert (ScalaSupport.encodeName("apply").equals("apply"));
+    }
+
+    @Test
+    public void invoke() throws Throwable {
+        Counter counter = new Counter();
+        assert (ScalaSupport.invoke(counter, "+", new Object[]{1, 2}).equals(3));
+        assert (ScalaSupport.invoke(counter, "::", new Object[]{"a"}).equals("::a"));
+        assert (ScalaSupport.invoke(counter, "describe", new Object[]{2}).equals("named 2"));
+    }
+
+    @Test(expected = NoSuchMethodException.class)
+    public void invokeMissing() throws Throwable {
+        ScalaSupport.invoke(new Counter(), "+", new Object[]{"a", 2});
+    }
+}
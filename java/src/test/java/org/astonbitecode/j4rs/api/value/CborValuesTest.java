@@ -0,0 +1,72 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.astonbitecode.j4rs.api.services.json.exceptions.JsonCodecException;
+import org.junit.Test;
+
+import java.math.BigInteger;
+import java.util.Arrays;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.Optional;
+
+public class CborValuesTest {
+
+    @Test
+    public void encodeValues() {
+        assert (Arrays.equals(CborValues.encode(10), new byte[]{0x0a}));
+        assert (Arrays.equals(CborValues.encode(-500), new byte[]{0x39, 0x01, (byte) 0xf3}));
+        assert (Arrays.equals(CborValues.encode("a"), new byte[]{0x61, 0x61}));
+        assert (Arrays.equals(CborValues.encode(new byte[]{1, 2}), new byte[]{0x42, 0x01, 0x02}));
+        assert (Arrays.equals(CborValues.encode(Optional.empty()), new byte[]{(byte) 0xf6}));
+        assert (Arrays.equals(CborValues.encode(Arrays.asList(true, 1.5)),
+                new byte[]{(byte) 0x82, (byte) 0xf5, (byte) 0xfb, 0x3f, (byte) 0xf8, 0, 0, 0, 0, 0, 0}));
+    }
+
+    @Test
+    public void roundTrip() throws Exception {
+        Map<String, Object> map = new HashMap<>();
+        map.put("list", Arrays.asList(1, 2, 3));
+        map.put("text", "\u00fc");
+        map.put("big", new BigInteger("123456789012345678901234567890"));
+        map.put("negative", Long.MIN_VALUE);
+        Map<?, ?> decoded = (Map<?, ?>) CborValues.decode("java.util.HashMap", CborValues.encode(map));
+        assert (decoded.get("list").equals(Arrays.asList(1, 2, 3)));
+        assert (decoded.get("text").equals("\u00fc"));
+        assert (decoded.get("big").equals(new BigInteger("123456789012345678901234567890")));
+        assert (decoded.get("negative").equals(Long.MIN_VALUE));
+
+        byte[] bytes = (byte[]) CborValues.decode("[B", CborValues.encode(new byte[]{1, 2, 3}));
+        assert (Arrays.equals(bytes, new byte[]{1, 2, 3}));
+    }
+
+    @Test
+    public void decodeIndefiniteLengthsAndHalfFloats() throws Exception {
+        // [_ 1, 1.5 (half)] and {_ 1: "a"}
+        byte[] array = {(byte) 0x9f, 0x01, (byte) 0xf9, 0x3e, 0x00, (byte) 0xff};
+        List<?> list = (List<?>) CborValues.decode("java.util.List", array);
+        assert (list.get(0).equals(1));
+        assert (list.get(1).equals(1.5));
+        byte[] map = {(byte) 0xbf, 0x01, 0x61, 0x61, (byte) 0xff};
+        assert (((Map<?, ?>) CborValues.decode("java.util.Map", map)).get("1").equals("a"));
+    }
+
+    @Test(expected = JsonCodecException.class)
+    public void decodeTruncated() throws Exception {
+        CborValues.decode("java.util.List", new byte[]{(byte) 0x82, 0x01});
+    }
+}
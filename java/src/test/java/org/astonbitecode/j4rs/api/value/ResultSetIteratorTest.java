@@ -0,0 +1,81 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.api.value;
+
+import org.junit.Test;
+
+import java.lang.reflect.Proxy;
+import java.sql.ResultSet;
+import java.sql.ResultSetMetaData;
+import java.util.Iterator;
+import java.util.Map;
+
+public class ResultSetIteratorTest {
+
+    @Test
+    public void iterateRows() {
+        Object[][] rows = {{1, "a"}, {2, "b"}};
+        Iterator<Map<String, Object>> iterator = new ResultSetIterator(resultSet(new String[]{"id", "name"}, rows));
+        assert (iterator.hasNext());
+        assert (iterator.hasNext());
+        Map<String, Object> first = iterator.next();
+        assert (first.get("id").equals(1));
+        assert (first.get("name").equals("a"));
+        assert (first.keySet().iterator().next().equals("id"));
+        assert (iterator.next().get("name").equals("b"));
+        assert (!iterator.hasNext());
+    }
+
+    @Test
+    public void serializeRowsInChunks() {
+        Object[][] rows = {{1, "a"}, {2, "b"}, {3, "c"}};
+        JsonChunkedSerializer serializer = new JsonChunkedSerializer(resultSet(new String[]{"id", "name"}, rows), 2);
+        assert (serializer.nextChunk().equals("{\"id\":1,\"name\":\"a\"}\n{\"id\":2,\"name\":\"b\"}\n"));
+        assert (serializer.nextChunk().equals("{\"id\":3,\"name\":\"c\"}\n"));
+        assert (serializer.nextChunk().isEmpty());
+    }
+
+    private static ResultSet resultSet(String[] labels, Object[][] rows) {
+        ResultSetMetaData metaData = (ResultSetMetaData) Proxy.newProxyInstance(
+                ResultSetIteratorTest.class.getClassLoader(),
+                new Class[]{ResultSetMetaData.class},
+                (proxy, method, args) -> {
+                    switch (method.getName()) {
+                        case "getColumnCount":
+                            return labels.length;
+                        case "getColumnLabel":
+                            return labels[(Integer) args[0] - 1];
+                        default:
+                            throw new UnsupportedOperationException(method.getName());
+                    }
+                });
+        int[] current = {-1};
+        return (ResultSet) Proxy.newProxyInstance(
+                ResultSetIteratorTest.class.getClassLoader(),
+                new Class[]{ResultSet.class},
+                (proxy, method, args) -> {
+                    switch (method.getName()) {
+                        case "next":
+                            return ++current[0] < rows.length;
+                        case "getMetaData":
+                            return metaData;
+                        case "getObject":
+                            return rows[current[0]][(Integer) args[0] - 1];
+                        default:
+                            throw new UnsupportedOperationException(method.getName());
+                    }
+                });
+    }
+}
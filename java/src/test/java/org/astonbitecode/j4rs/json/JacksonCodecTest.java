@@ -14,6 +14,8 @@
  */
 package org.astonbitecode.j4rs.json;
 
+import org.astonbitecode.j4rs.api.dtos.Tuple2;
+import org.astonbitecode.j4rs.api.dtos.Tuple3;
 import org.astonbitecode.j4rs.api.services.json.exceptions.JsonCodecException;
 import org.astonbitecode.j4rs.utils.Dummy;
 import org.astonbitecode.j4rs.utils.OtherDummy;
@@ -69,4 +71,17 @@ public class JacksonCodecTest {
         String json = "[{\"i\":3,\"j\":33}, {\"i\":333,\"j\":3333}]";
         jacksonCodec.decodeArrayContents(json);
     }
+
+    @Test
+    public void encodeDecodeTuples() {
+        Tuple2<Integer, String> pair = new Tuple2<>(1, "one");
+        String json = jacksonCodec.encode(pair);
+        assert (json.equals("[1,\"one\"]"));
+        assert (jacksonCodec.decode(json, Tuple2.class.getName()).equals(pair));
+
+        Tuple3<Integer, String, Boolean> triple = new Tuple3<>(1, "one", true);
+        json = jacksonCodec.encode(triple);
+        assert (json.equals("[1,\"one\",true]"));
+        assert (jacksonCodec.decode(json, Tuple3.class.getName()).equals(triple));
+    }
 }
@@ -19,6 +19,10 @@ import org.astonbitecode.j4rs.api.value.JsonValueFactory;
 import org.astonbitecode.j4rs.utils.Dummy;
 import org.junit.Test;
 
+import java.util.Optional;
+import java.util.OptionalInt;
+import java.util.OptionalLong;
+
 public class JsonValueImplTest {
 
     @Test
@@ -56,4 +60,18 @@ public class JsonValueImplTest {
         assert json.equals("null");
         assert obj == null;
     }
+
+    @Test
+    public void fromOptional() {
+        JsonValue present = JsonValueFactory.create(Optional.of(new Dummy(3)));
+        assert present.getJson().equals("{\"i\":3}");
+        assert ((Dummy) present.getObject()).getI() == 3;
+
+        JsonValue empty = JsonValueFactory.create(Optional.empty());
+        assert empty.getJson().equals("null");
+        assert empty.getObject() == null;
+
+        assert JsonValueFactory.create(OptionalInt.of(3)).getJson().equals("3");
+        assert JsonValueFactory.create(OptionalLong.empty()).getJson().equals("null");
+    }
 }
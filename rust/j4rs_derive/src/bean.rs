@@ -0,0 +1,250 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, LitStr, Result, Type};
+
+// The Rust types that are passed to Java as primitives, unless the field is marked as `boxed`
+const PRIMITIVES: &[&str] = &["bool", "i8", "i16", "i32", "i64", "f32", "f64", "char"];
+
+/// The `#[j4rs(...)]` attributes of the struct.
+struct BeanAttributes {
+    class: LitStr,
+    constructor: bool,
+}
+
+/// A field of the struct, along with its `#[j4rs(...)]` attributes.
+struct BeanField {
+    ident: Ident,
+    ty: Type,
+    java_name: String,
+    boxed: bool,
+    bean: bool,
+}
+
+impl BeanField {
+    fn is_primitive(&self) -> bool {
+        match &self.ty {
+            Type::Path(type_path) => type_path
+                .path
+                .get_ident()
+                .map(|ident| PRIMITIVES.contains(&ident.to_string().as_str()))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    fn setter(&self) -> String {
+        format!("set{}", capitalize(&self.java_name))
+    }
+
+    fn getter(&self) -> String {
+        match &self.ty {
+            Type::Path(type_path) if type_path.path.is_ident("bool") && !self.boxed => {
+                format!("is{}", capitalize(&self.java_name))
+            }
+            _ => format!("get{}", capitalize(&self.java_name)),
+        }
+    }
+
+    /// The expression that creates the `InvocationArg` for the field.
+    fn invocation_arg(&self) -> TokenStream {
+        let ident = &self.ident;
+        if self.bean {
+            quote! {
+                ::j4rs::InvocationArg::from(::j4rs::IntoJava::to_java(&self.#ident, jvm)?)
+            }
+        } else if self.is_primitive() && !self.boxed {
+            quote! {
+                <::j4rs::InvocationArg as ::std::convert::TryFrom<_>>::try_from(&self.#ident)?.into_primitive()?
+            }
+        } else {
+            quote! {
+                <::j4rs::InvocationArg as ::std::convert::TryFrom<_>>::try_from(&self.#ident)?
+            }
+        }
+    }
+}
+
+pub(crate) fn impl_into_java(input: &DeriveInput) -> Result<TokenStream> {
+    let attributes = parse_bean_attributes(input)?;
+    let fields = parse_bean_fields(input)?;
+    let class = &attributes.class;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = if attributes.constructor {
+        let args: Vec<TokenStream> = fields.iter().map(BeanField::invocation_arg).collect();
+        quote! {
+            jvm.create_instance(#class, &[#(#args),*])
+        }
+    } else {
+        let setters: Vec<TokenStream> = fields
+            .iter()
+            .map(|field| {
+                let setter = field.setter();
+                let arg = field.invocation_arg();
+                quote! {
+                    jvm.invoke(&instance, #setter, &[#arg])?;
+                }
+            })
+            .collect();
+        quote! {
+            let instance = jvm.create_instance(#class, ::j4rs::InvocationArg::empty())?;
+            #(#setters)*
+            Ok(instance)
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::j4rs::IntoJava for #name #ty_generics #where_clause {
+            fn to_java(&self, jvm: &::j4rs::Jvm) -> ::j4rs::errors::Result<::j4rs::Instance> {
+                #body
+            }
+        }
+    })
+}
+
+pub(crate) fn impl_from_java(input: &DeriveInput) -> Result<TokenStream> {
+    // Validate the struct attributes, even if the class is not needed for retrieving the values
+    let _ = parse_bean_attributes(input)?;
+    let fields = parse_bean_fields(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let values: Vec<TokenStream> = fields
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let getter = field.getter();
+            if field.bean {
+                quote! {
+                    #ident: <#ty as ::j4rs::FromJava>::from_java(
+                        jvm,
+                        &jvm.invoke(instance, #getter, ::j4rs::InvocationArg::empty())?,
+                    )?
+                }
+            } else {
+                quote! {
+                    #ident: jvm.to_rust::<#ty>(jvm.invoke(instance, #getter, ::j4rs::InvocationArg::empty())?)?
+                }
+            }
+        })
+        .collect();
+
+    Ok(quote! {
+        impl #impl_generics ::j4rs::FromJava for #name #ty_generics #where_clause {
+            fn from_java(jvm: &::j4rs::Jvm, instance: &::j4rs::Instance) -> ::j4rs::errors::Result<Self> {
+                Ok(#name {
+                    #(#values),*
+                })
+            }
+        }
+    })
+}
+
+fn parse_bean_attributes(input: &DeriveInput) -> Result<BeanAttributes> {
+    let mut class = None;
+    let mut constructor = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("j4rs")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("class") {
+                class = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("constructor") {
+                constructor = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported j4rs attribute. Expected `class` or `constructor`"))
+            }
+        })?;
+    }
+    match class {
+        Some(class) => Ok(BeanAttributes { class, constructor }),
+        None => Err(Error::new_spanned(
+            &input.ident,
+            "the Java class should be defined with #[j4rs(class = \"...\")]",
+        )),
+    }
+}
+
+fn parse_bean_fields(input: &DeriveInput) -> Result<Vec<BeanField>> {
+    let named = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => return Err(Error::new_spanned(&input.ident, "only structs with named fields are supported")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "only structs are supported")),
+    };
+
+    named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("named fields have identifiers");
+            let mut java_name = to_camel_case(&ident.to_string());
+            let mut boxed = false;
+            let mut bean = false;
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("j4rs")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        java_name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("boxed") {
+                        boxed = true;
+                        Ok(())
+                    } else if meta.path.is_ident("bean") {
+                        bean = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported j4rs field attribute. Expected `name`, `boxed` or `bean`"))
+                    }
+                })?;
+            }
+            Ok(BeanField {
+                ident,
+                ty: field.ty.clone(),
+                java_name,
+                boxed,
+                bean,
+            })
+        })
+        .collect()
+}
+
+// some_field -> someField
+fn to_camel_case(s: &str) -> String {
+    let mut camel = String::with_capacity(s.len());
+    let mut upper_next = false;
+    for c in s.trim_start_matches("r#").chars() {
+        if c == '_' {
+            upper_next = !camel.is_empty();
+        } else if upper_next {
+            camel.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+// someField -> SomeField
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
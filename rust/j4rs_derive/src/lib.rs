@@ -17,10 +17,13 @@ extern crate proc_macro2;
 use proc_macro::TokenStream;
 
 use proc_macro2::{Ident, Span};
-use syn::{parse_macro_input, Expr, FnArg, ItemFn, ReturnType, LitStr};
+use syn::{parse_macro_input, DeriveInput, Error, Expr, FnArg, ItemFn, LitStr, Pat, PatType, ReturnType, Type};
 
 use quote::quote;
 
+mod bean;
+mod natives;
+
 #[proc_macro_attribute]
 pub fn call_from_java(macro_args: TokenStream, user_function: TokenStream) -> TokenStream {
     let cloned_user_function = user_function.clone();
@@ -42,18 +45,37 @@ fn impl_call_from_java_macro(user_function: &ItemFn, macro_arg: LitStr) -> Token
     let user_function_name = &user_function_signature.ident;
     // Arguments
     let user_function_args = &user_function_signature.inputs;
-    // The argument names as defined by the user
-    let user_function_arg_names: Vec<String> = user_function_args
-        .iter()
-        .map(|arg| {
-            let a = arg.clone();
-            let q = quote!(#a).to_string();
-            let v: Vec<&str> = q.split(' ').collect();
-            v.get(0)
-                .expect(&format!("Could not locate the argument name for: {}", q))
-                .to_string()
-        })
-        .collect();
+    // The argument names and types as defined by the user
+    let mut user_function_arg_names: Vec<Ident> = Vec::new();
+    let mut user_function_arg_types: Vec<&Type> = Vec::new();
+    for arg in user_function_args.iter() {
+        match arg {
+            FnArg::Typed(PatType { pat, ty, .. }) => match pat.as_ref() {
+                Pat::Ident(pat_ident) => {
+                    user_function_arg_names.push(pat_ident.ident.clone());
+                    user_function_arg_types.push(ty.as_ref());
+                }
+                _ => return Error::new_spanned(pat, "call_from_java supports only simple argument names")
+                    .to_compile_error()
+                    .into(),
+            },
+            FnArg::Receiver(_) => return Error::new_spanned(arg, "call_from_java cannot be used for methods")
+                .to_compile_error()
+                .into(),
+        }
+    }
+    // An async function returns a java.util.concurrent.CompletableFuture to Java
+    let is_async = user_function_signature.asyncness.is_some();
+    // The descriptor of the Java native method, if requested
+    if let Err(message) = natives::write_descriptor(
+        &macro_arg.value(),
+        &user_function_arg_names,
+        &user_function_arg_types,
+        &user_function_signature.output,
+        is_async,
+    ) {
+        return Error::new_spanned(&macro_arg, message).to_compile_error().into();
+    }
     // The arguments of the jni function
     let jni_function_args: Vec<FnArg> = user_function_arg_names
         .iter()
@@ -64,18 +86,43 @@ fn impl_call_from_java_macro(user_function: &ItemFn, macro_arg: LitStr) -> Token
         .collect();
     // The jni function return type
     let ref jni_function_output = match &user_function_signature.output {
-        ReturnType::Default => ReturnType::Default,
+        ReturnType::Default if !is_async => ReturnType::Default,
         _ => {
             let ret_type: ReturnType = syn::parse_str("-> jobject").unwrap();
             ret_type
         }
     };
-    // The jni return value. This may be void or jobject
-    let return_value = match &user_function_signature.output {
-        ReturnType::Default => {
-            let ret_value: Expr = syn::parse_str("()").unwrap();
-            ret_value
+    // The call of the user function and the jni return value. This may be void or jobject
+    let call_and_return_value = match &user_function_signature.output {
+        _ if is_async => {
+            // The future is spawned and the CompletableFuture is returned immediately
+            let future = match &user_function_signature.output {
+                ReturnType::Default => quote! {
+                    async move {
+                        #user_function_name(#(#user_function_arg_names),*).await;
+                        Ok::<Option<Instance>, String>(None)
+                    }
+                },
+                _ => quote! {
+                    async move {
+                        #user_function_name(#(#user_function_arg_names),*).await.map(Some)
+                    }
+                },
+            };
+            quote! {
+                match jvm.spawn_completable_future(#future).and_then(|i| jvm.instance_into_raw_object(i)) {
+                    Ok(completable_future) => completable_future,
+                    Err(error) => {
+                        let message = format!("{}", error);
+                        let _ = jvm.throw_invocation_exception(&message);
+                        ptr::null_mut()
+                    },
+                }
+            }
         }
+        ReturnType::Default => quote! {
+            #user_function_name(#(#user_function_arg_names),*);
+        },
         _ => {
             let ret_value: Expr = syn::parse_str(
                 r#"match instance_to_return {
@@ -90,14 +137,39 @@ fn impl_call_from_java_macro(user_function: &ItemFn, macro_arg: LitStr) -> Token
                     },
                 }"#,
             ).unwrap();
-            ret_value
+            quote! {
+                let instance_to_return = #user_function_name(#(#user_function_arg_names),*);
+                #ret_value
+            }
         }
     };
+    // The jni return statement, in case that the arguments cannot be converted
+    let error_return = match &user_function_signature.output {
+        ReturnType::Default if !is_async => quote!(return;),
+        _ => quote!(return ptr::null_mut();),
+    };
 
-    let instance_args_to_pass_to_user_function: Vec<Expr> = user_function_arg_names.iter()
-        .map(|jobj_arg_name| {
-            let expression: Expr = syn::parse_str(&format!("Instance::from_jobject_with_global_ref({}).expect(\"Could not create Instance from jobject\")", jobj_arg_name)).unwrap();
-            expression
+    // The jobject arguments are converted to the types that the user function expects.
+    // Instance arguments are passed as they are, while any other type is deserialized with Jvm::to_rust.
+    // If any conversion fails, a Java InvocationException is thrown.
+    let args_to_pass_to_user_function: Vec<proc_macro2::TokenStream> = user_function_arg_names.iter()
+        .zip(user_function_arg_types.iter())
+        .map(|(jobj_arg_name, arg_type)| {
+            let conversion = if is_instance_type(arg_type) {
+                quote!(Instance::from_jobject_with_global_ref(#jobj_arg_name))
+            } else {
+                quote!(Instance::from_jobject_with_global_ref(#jobj_arg_name).and_then(|i| jvm.to_rust::<#arg_type>(i)))
+            };
+            quote! {
+                let #jobj_arg_name: #arg_type = match #conversion {
+                    Ok(arg) => arg,
+                    Err(error) => {
+                        let message = format!("Could not convert the argument {} to {}: {}", stringify!(#jobj_arg_name), stringify!(#arg_type), error);
+                        let _ = jvm.throw_invocation_exception(&message);
+                        #error_return
+                    }
+                };
+            }
         })
         .collect();
 
@@ -108,8 +180,8 @@ fn impl_call_from_java_macro(user_function: &ItemFn, macro_arg: LitStr) -> Token
                 Ok(mut jvm) => {
                     jvm.detach_thread_on_drop(false);
                     // println!("Called {}. Calling now  {}", stringify!(#jni_ident), stringify!(#user_function_name));
-                    let instance_to_return = #user_function_name(#(#instance_args_to_pass_to_user_function),*);
-                    #return_value
+                    #(#args_to_pass_to_user_function)*
+                    #call_and_return_value
                 },
                 Err(error) => {
                     let message = format!("Could not attach to the JVM thread: {}", error);
@@ -121,3 +193,47 @@ fn impl_call_from_java_macro(user_function: &ItemFn, macro_arg: LitStr) -> Token
     };
     gen.into()
 }
+
+// Whether the type of an argument is a j4rs Instance, which does not need any conversion
+fn is_instance_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Instance" && segment.arguments.is_empty())
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Implements `j4rs::IntoJava` for a struct with named fields.
+///
+/// The Java class is defined with `#[j4rs(class = "...")]`. Its no-arg constructor is used to create the Java instance
+/// and the fields are set with the setters of the class (e.g. `some_field` is set with `setSomeField`).
+/// If `#[j4rs(constructor)]` is added, the constructor that accepts all the fields, in the order they are defined,
+/// is used instead.
+///
+/// The fields may be annotated with:
+/// * `#[j4rs(name = "...")]` to define the name of the Java property.
+/// * `#[j4rs(boxed)]` to pass a Rust primitive as its Java wrapper class (e.g. `Integer` instead of `int`).
+/// * `#[j4rs(bean)]` to convert a field that implements `j4rs::IntoJava` itself.
+#[proc_macro_derive(IntoJava, attributes(j4rs))]
+pub fn derive_into_java(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    bean::impl_into_java(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// Implements `j4rs::FromJava` for a struct with named fields.
+///
+/// The values of the fields are retrieved with the getters of the Java class (e.g. `some_field` is retrieved with
+/// `getSomeField`, or `isSomeField` for `bool`s). The attributes are the same as the ones of `IntoJava`.
+#[proc_macro_derive(FromJava, attributes(j4rs))]
+pub fn derive_from_java(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    bean::impl_from_java(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
@@ -0,0 +1,107 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use proc_macro2::Ident;
+use syn::{ReturnType, Type};
+
+/// The environment variable that defines the directory where the descriptors of the Java native methods are written.
+pub(crate) const NATIVES_DIR_ENV_VAR: &str = "J4RS_NATIVES_DIR";
+
+/// Writes the descriptor of the Java native method that corresponds to a `call_from_java` function,
+/// if the `J4RS_NATIVES_DIR` environment variable is set during the build.
+///
+/// The descriptor file is named after the fully qualified name of the native method and contains two lines:
+/// the fully qualified name of the Java class and the declaration of the native method.
+pub(crate) fn write_descriptor(
+    java_path: &str,
+    arg_names: &[Ident],
+    arg_types: &[&Type],
+    output: &ReturnType,
+    is_async: bool,
+) -> Result<(), String> {
+    let natives_dir = match env::var(NATIVES_DIR_ENV_VAR) {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => return Ok(()),
+    };
+    let (class_name, method_name) = java_class_and_method(java_path)?;
+    let return_type = match output {
+        _ if is_async => "java.util.concurrent.CompletableFuture<Object>",
+        ReturnType::Default => "void",
+        _ => "Instance",
+    };
+    let params: Vec<String> = arg_names
+        .iter()
+        .zip(arg_types.iter())
+        .map(|(name, ty)| format!("{} {}", java_instance_type(ty), name))
+        .collect();
+    let declaration = format!(
+        "public static native {} {}({});",
+        return_type,
+        method_name,
+        params.join(", ")
+    );
+
+    fs::create_dir_all(&natives_dir).map_err(|error| format!("Could not create {:?}: {}", natives_dir, error))?;
+    let descriptor_path = natives_dir.join(format!("{}.{}.native", class_name, method_name));
+    fs::write(&descriptor_path, format!("{}\n{}\n", class_name, declaration))
+        .map_err(|error| format!("Could not write {:?}: {}", descriptor_path, error))
+}
+
+// Splits the JNI path of a call_from_java function to the Java class name and the method name,
+// unescaping the underscores (`_1` -> `_`)
+fn java_class_and_method(java_path: &str) -> Result<(String, String), String> {
+    let unescaped = java_path.replace("_1", "_");
+    match unescaped.rfind('.') {
+        Some(index) if index > 0 && index < unescaped.len() - 1 => Ok((
+            unescaped[..index].to_string(),
+            unescaped[index + 1..].to_string(),
+        )),
+        _ => Err(format!(
+            "Invalid call_from_java definition {}. Expected the fully qualified name of a Java method",
+            java_path
+        )),
+    }
+}
+
+// The Java type of the j4rs Instance that is passed for an argument of the Rust function.
+// The generic type is defined for the Rust types that correspond to Java classes.
+fn java_instance_type(ty: &Type) -> String {
+    match java_class_of(ty) {
+        Some(class_name) => format!("Instance<{}>", class_name),
+        None => "Instance".to_string(),
+    }
+}
+
+fn java_class_of(ty: &Type) -> Option<String> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+    let class_name = match segment.ident.to_string().as_str() {
+        "String" => "String",
+        "bool" => "Boolean",
+        "i8" => "Byte",
+        "i16" => "Short",
+        "char" | "u16" => "Character",
+        "i32" => "Integer",
+        "i64" => "Long",
+        "f32" => "Float",
+        "f64" => "Double",
+        _ => return None,
+    };
+    Some(class_name.to_string())
+}
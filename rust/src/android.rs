@@ -0,0 +1,81 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Mutex;
+
+use crate::api::shared::SharedJvm;
+use crate::errors::J4RsError;
+use crate::logger::{debug, error};
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+lazy_static! {
+    static ref CONTEXT: Mutex<Option<Instance>> = Mutex::new(None);
+}
+
+/// Stores the `android.content.Context` of the application, replacing any previously stored one.
+///
+/// The application context (`Context.getApplicationContext`) should be preferred over an `Activity`,
+/// because an `Activity` is destroyed and recreated during its lifecycle.
+pub fn set_context(context: Instance) -> errors::Result<()> {
+    debug("Setting the Android Context");
+    *CONTEXT.lock()? = Some(context);
+    Ok(())
+}
+
+/// Removes the stored `android.content.Context`, e.g. when the `Activity` that was stored is destroyed.
+pub fn clear_context() -> errors::Result<()> {
+    debug("Clearing the Android Context");
+    let context = CONTEXT.lock()?.take();
+    SharedJvm::new().with(move |_| {
+        drop(context);
+        Ok(())
+    })
+}
+
+/// Returns the `android.content.Context` that was stored with `set_context`.
+///
+/// It can be called from any Rust thread; the thread is attached to the JavaVM if needed.
+pub fn context() -> errors::Result<Instance> {
+    let g = CONTEXT.lock()?;
+    let context = g.as_ref().ok_or_else(|| {
+        J4RsError::GeneralError("The Android Context is not set. Please call android::set_context first".to_string())
+    })?;
+    SharedJvm::new().with(|jvm| jvm.clone_instance(context))
+}
+
+/// Posts the closure `f` to be executed in the main (UI) thread of the application and returns without waiting for it.
+///
+/// The `Jvm` that is passed to the closure is attached to the UI thread. Any errors returned by the closure are logged.
+pub fn run_on_ui_thread<F>(f: F) -> errors::Result<()>
+where
+    F: FnOnce(&Jvm) -> errors::Result<()> + Send + 'static,
+{
+    let f = Mutex::new(Some(f));
+    SharedJvm::new().with(move |jvm| {
+        let runnable = jvm.new_proxy("java.lang.Runnable", move |jvm, method_name, _args| {
+            if method_name == "run" {
+                if let Some(f) = f.lock()?.take() {
+                    if let Err(e) = f(jvm) {
+                        error(&format!("Error while running on the Android UI thread: {}", e));
+                    }
+                }
+            }
+            Ok(None)
+        })?;
+        let main_looper = jvm.invoke_static("android.os.Looper", "getMainLooper", InvocationArg::empty())?;
+        let handler = jvm.create_instance("android.os.Handler", &[InvocationArg::from(main_looper)])?;
+        jvm.invoke(&handler, "post", &[InvocationArg::from(runnable)])?;
+        Ok(())
+    })
+}
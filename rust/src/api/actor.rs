@@ -0,0 +1,312 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::convert::TryInto;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use futures::channel::oneshot;
+use serde::de::DeserializeOwned;
+
+use crate::errors::J4RsError;
+use crate::logger::{debug, error};
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+// A request that is executed by the worker thread of a JvmActor
+type Command = Box<dyn FnOnce(&Jvm) + Send>;
+
+/// A handle to a dedicated worker thread that is attached to the Java VM and executes requests one by one.
+///
+/// The handle is `Send`, `Sync` and cheap to clone; its methods mirror the ones of `Jvm`, but are asynchronous
+/// and do not require the calling thread to be attached to the Java VM. This makes it suitable for sharing one JVM
+/// across an async application.
+///
+/// The Java objects are held by `ActorInstance`s and the arguments of the invocations are `ActorArg`s,
+/// which are turned to Java objects by the worker.
+///
+/// The worker thread ends when all the `JvmActor` and `ActorInstance` handles are dropped.
+///
+/// It is created by calling `Jvm::actor`.
+#[derive(Clone)]
+pub struct JvmActor {
+    tx: Sender<Command>,
+}
+
+impl JvmActor {
+    pub(crate) fn new() -> errors::Result<JvmActor> {
+        let (tx, rx) = channel::<Command>();
+        thread::Builder::new()
+            .name("j4rs-actor".to_string())
+            .spawn(move || {
+                let jvm = match Jvm::attach_thread_as_daemon() {
+                    Ok(jvm) => jvm,
+                    Err(e) => {
+                        error(&format!("Could not attach the JvmActor thread to the JVM: {}", e));
+                        return;
+                    }
+                };
+                for command in rx {
+                    if panic::catch_unwind(AssertUnwindSafe(|| command(&jvm))).is_err() {
+                        error("A request of the JvmActor panicked");
+                    }
+                }
+                debug("The JvmActor thread ends");
+            })?;
+        Ok(JvmActor { tx })
+    }
+
+    /// Calls `f` with the `Jvm` of the worker thread and returns its result.
+    ///
+    /// The Java objects of `ActorInstance`s that are moved in `f` can be accessed with `ActorInstance::instance`.
+    pub async fn run<R, F>(&self, f: F) -> errors::Result<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        send_command(&self.tx, Box::new(move |jvm| {
+            let _ = tx.send(f(jvm));
+        }))?;
+        rx.await?
+    }
+
+    /// Creates an `ActorInstance` of the class `class_name`. See `Jvm::create_instance`.
+    pub async fn create_instance(&self, class_name: &str, inv_args: Vec<ActorArg>) -> errors::Result<ActorInstance> {
+        let class_name = class_name.to_string();
+        let tx = self.tx.clone();
+        self.run(move |jvm| {
+            let inv_args = ActorArg::into_invocation_args(inv_args, jvm)?;
+            let instance = jvm.create_instance(&class_name, &inv_args)?;
+            Ok(ActorInstance::new(instance, tx))
+        })
+        .await
+    }
+
+    /// Invokes the method `method_name` of an `ActorInstance`. See `Jvm::invoke`.
+    pub async fn invoke(
+        &self,
+        instance: &ActorInstance,
+        method_name: &str,
+        inv_args: Vec<ActorArg>,
+    ) -> errors::Result<ActorInstance> {
+        let instance = instance.clone();
+        let method_name = method_name.to_string();
+        let tx = self.tx.clone();
+        self.run(move |jvm| {
+            let inv_args = ActorArg::into_invocation_args(inv_args, jvm)?;
+            let result = jvm.invoke(instance.instance(), &method_name, &inv_args)?;
+            Ok(ActorInstance::new(result, tx))
+        })
+        .await
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name`. See `Jvm::invoke_static`.
+    pub async fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: Vec<ActorArg>,
+    ) -> errors::Result<ActorInstance> {
+        let class_name = class_name.to_string();
+        let method_name = method_name.to_string();
+        let tx = self.tx.clone();
+        self.run(move |jvm| {
+            let inv_args = ActorArg::into_invocation_args(inv_args, jvm)?;
+            let result = jvm.invoke_static(&class_name, &method_name, &inv_args)?;
+            Ok(ActorInstance::new(result, tx))
+        })
+        .await
+    }
+
+    /// Invokes the method `method_name` of an `ActorInstance` and returns the Rust representation of the result.
+    /// See `Jvm::invoke_to_rust`.
+    pub async fn invoke_to_rust<T>(
+        &self,
+        instance: &ActorInstance,
+        method_name: &str,
+        inv_args: Vec<ActorArg>,
+    ) -> errors::Result<T>
+    where
+        T: DeserializeOwned + Any + Send,
+    {
+        let instance = instance.clone();
+        let method_name = method_name.to_string();
+        self.run(move |jvm| {
+            let inv_args = ActorArg::into_invocation_args(inv_args, jvm)?;
+            jvm.invoke_to_rust(instance.instance(), &method_name, &inv_args)
+        })
+        .await
+    }
+
+    /// Retrieves the field `field_name` of an `ActorInstance`. See `Jvm::field`.
+    pub async fn field(&self, instance: &ActorInstance, field_name: &str) -> errors::Result<ActorInstance> {
+        let instance = instance.clone();
+        let field_name = field_name.to_string();
+        let tx = self.tx.clone();
+        self.run(move |jvm| {
+            let result = jvm.field(instance.instance(), &field_name)?;
+            Ok(ActorInstance::new(result, tx))
+        })
+        .await
+    }
+
+    /// Returns the Rust representation of an `ActorInstance`. See `Jvm::to_rust`.
+    pub async fn to_rust<T>(&self, instance: &ActorInstance) -> errors::Result<T>
+    where
+        T: DeserializeOwned + Any + Send,
+    {
+        let instance = instance.clone();
+        self.run(move |jvm| jvm.to_rust(jvm.clone_instance(instance.instance())?))
+            .await
+    }
+}
+
+fn send_command(tx: &Sender<Command>, command: Command) -> errors::Result<()> {
+    tx.send(command)
+        .map_err(|_| J4RsError::GeneralError("The worker thread of the JvmActor is not running".to_string()))
+}
+
+/// A Java object that is created by a `JvmActor`.
+///
+/// Clones refer to the same Java object. When the last clone is dropped, the Java object is released by the worker thread
+/// of the `JvmActor`, so `ActorInstance`s can be dropped by threads that are not attached to the Java VM.
+#[derive(Clone)]
+pub struct ActorInstance {
+    inner: Arc<ActorInstanceInner>,
+}
+
+impl ActorInstance {
+    fn new(instance: Instance, tx: Sender<Command>) -> ActorInstance {
+        ActorInstance {
+            inner: Arc::new(ActorInstanceInner {
+                instance: Some(instance),
+                tx,
+            }),
+        }
+    }
+
+    /// Returns the name of the class of the Java object.
+    pub fn class_name(&self) -> &str {
+        self.instance().class_name()
+    }
+
+    /// Returns the `Instance` of the Java object.
+    ///
+    /// It is meant to be used in the closures that are passed to `JvmActor::run`.
+    pub fn instance(&self) -> &Instance {
+        // The instance is taken only when the inner is dropped
+        self.inner.instance.as_ref().unwrap()
+    }
+}
+
+struct ActorInstanceInner {
+    instance: Option<Instance>,
+    tx: Sender<Command>,
+}
+
+// The Instance contains a global Java reference, which may be used by any thread.
+unsafe impl Sync for ActorInstanceInner {}
+
+impl Drop for ActorInstanceInner {
+    fn drop(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            let _ = send_command(&self.tx, Box::new(move |_| drop(instance)));
+        }
+    }
+}
+
+/// An argument of the invocations of a `JvmActor`.
+///
+/// It is turned to an `InvocationArg` by the worker thread of the `JvmActor`, so it can be created by threads
+/// that are not attached to the Java VM.
+pub struct ActorArg(ActorArgKind);
+
+enum ActorArgKind {
+    Instance(ActorInstance),
+    Value(Box<dyn FnOnce() -> errors::Result<InvocationArg> + Send>),
+}
+
+impl ActorArg {
+    /// Creates an `ActorArg` out of any value that can be turned to an `InvocationArg`.
+    pub fn new<T>(value: T) -> ActorArg
+    where
+        T: TryInto<InvocationArg> + Send + 'static,
+        J4RsError: From<T::Error>,
+    {
+        ActorArg(ActorArgKind::Value(Box::new(move || Ok(value.try_into()?))))
+    }
+
+    fn into_invocation_arg(self, jvm: &Jvm) -> errors::Result<InvocationArg> {
+        match self.0 {
+            ActorArgKind::Instance(instance) => Ok(InvocationArg::from(jvm.clone_instance(instance.instance())?)),
+            ActorArgKind::Value(f) => f(),
+        }
+    }
+
+    fn into_invocation_args(inv_args: Vec<ActorArg>, jvm: &Jvm) -> errors::Result<Vec<InvocationArg>> {
+        inv_args.into_iter().map(|arg| arg.into_invocation_arg(jvm)).collect()
+    }
+}
+
+impl From<&ActorInstance> for ActorArg {
+    fn from(instance: &ActorInstance) -> ActorArg {
+        ActorArg(ActorArgKind::Instance(instance.clone()))
+    }
+}
+
+#[cfg(test)]
+mod actor_unit_tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn actor_invocations() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let actor = jvm.actor()?;
+
+        let handle = thread::spawn(move || {
+            block_on(async {
+                let string = actor.create_instance("java.lang.String", vec![ActorArg::new("a string")]).await?;
+                assert_eq!(string.class_name(), "java.lang.String");
+                let upper: String = actor.invoke_to_rust(&string, "toUpperCase", Vec::new()).await?;
+                assert_eq!(upper, "A STRING");
+
+                let list = actor.create_instance("java.util.ArrayList", Vec::new()).await?;
+                actor.invoke(&list, "add", vec![ActorArg::from(&string)]).await?;
+                actor.invoke(&list, "add", vec![ActorArg::new("another string")]).await?;
+                let strings: Vec<String> = actor.to_rust(&list).await?;
+                assert_eq!(strings, vec!["a string", "another string"]);
+
+                let size = actor
+                    .run(move |jvm| jvm.invoke_to_rust::<i32>(list.instance(), "size", InvocationArg::empty()))
+                    .await?;
+                assert_eq!(size, 2);
+
+                let integer = actor.invoke_static("java.lang.Integer", "valueOf", vec![ActorArg::new("42")]).await?;
+                let integer: i32 = actor.to_rust(&integer).await?;
+                assert_eq!(integer, 42);
+
+                let res = actor.invoke_static("java.lang.Integer", "parseInt", vec![ActorArg::new("abc")]).await;
+                assert!(res.is_err());
+                Ok::<_, J4RsError>(())
+            })
+        });
+        handle.join().unwrap()
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{errors, Instance, Jvm};
+
+/// A Rust type that can be converted to an `Instance` of a Java class, by calling its constructor or setters.
+///
+/// It is usually implemented with `#[derive(IntoJava)]` of the `j4rs_derive` crate.
+pub trait IntoJava {
+    /// Creates a Java `Instance` that holds the values of `self`.
+    fn to_java(&self, jvm: &Jvm) -> errors::Result<Instance>;
+}
+
+/// A Rust type that can be created from an `Instance` of a Java class, by calling its getters.
+///
+/// It is usually implemented with `#[derive(FromJava)]` of the `j4rs_derive` crate.
+pub trait FromJava: Sized {
+    /// Creates a value of this type from the Java `instance`.
+    fn from_java(jvm: &Jvm, instance: &Instance) -> errors::Result<Self>;
+}
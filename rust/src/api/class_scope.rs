@@ -0,0 +1,81 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+pub(crate) const CLASS_SCOPE_CLASS: &str = "org.astonbitecode.j4rs.api.deploy.ClassScope";
+
+/// An isolated scope of Java classes, loaded out of its own jars and directories.
+///
+/// The classes of a scope are loaded by a child-first classloader: they are looked up in the entries of the scope
+/// before the classpath of the JVM. This way, libraries that depend on conflicting versions of the same classes
+/// can be used in the same JVM, each one in its own scope.
+///
+/// It is created by calling `Jvm::new_class_scope`.
+pub struct ClassScope<'a> {
+    jvm: &'a Jvm,
+    scope: Instance,
+}
+
+impl<'a> ClassScope<'a> {
+    pub(crate) fn new(jvm: &'a Jvm, scope: Instance) -> ClassScope<'a> {
+        ClassScope { jvm, scope }
+    }
+
+    /// Returns the `Jvm` of the scope.
+    pub fn jvm(&self) -> &Jvm {
+        self.jvm
+    }
+
+    /// Creates an instance of the class `class_name`, which is loaded by the scope. See `Jvm::create_instance`.
+    pub fn create_instance(
+        &self,
+        class_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        self.within(|jvm| jvm.create_instance(class_name, inv_args))
+    }
+
+    /// Retrieves the static class `class_name`, which is loaded by the scope. See `Jvm::static_class`.
+    pub fn static_class(&self, class_name: &str) -> errors::Result<Instance> {
+        self.within(|jvm| jvm.static_class(class_name))
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name`, which is loaded by the scope.
+    /// See `Jvm::invoke_static`.
+    pub fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        self.within(|jvm| jvm.invoke_static(class_name, method_name, inv_args))
+    }
+
+    /// Calls `f` with the scope entered: any class that is referenced by name in the `Jvm` calls of `f`
+    /// (e.g. in `create_instance` or in `InvocationArg`s) is loaded by the scope.
+    ///
+    /// Only the calls of the current thread are affected.
+    pub fn within<T, F>(&self, f: F) -> errors::Result<T>
+    where
+        F: FnOnce(&Jvm) -> errors::Result<T>,
+    {
+        self.jvm.invoke(&self.scope, "enter", InvocationArg::empty())?;
+        let result = f(self.jvm);
+        self.jvm.invoke(&self.scope, "exit", InvocationArg::empty())?;
+        result
+    }
+}
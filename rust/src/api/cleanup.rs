@@ -0,0 +1,138 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use jni_sys::{jobject, JNIEnv};
+
+use crate::jni_utils;
+use crate::logger::debug;
+
+// The global references of the Instances that were dropped in threads without a JNIEnv.
+// Global references are valid in all the threads, so they are deleted by the next Jvm that is available.
+static DEFERRED_DELETIONS: DeferredDeletions = DeferredDeletions::new();
+
+/// A queue of global references that wait to be deleted.
+struct DeferredDeletions {
+    // The number of the global references that wait to be deleted, checked before locking
+    count: AtomicUsize,
+    pending: Mutex<Vec<usize>>,
+}
+
+impl DeferredDeletions {
+    const fn new() -> DeferredDeletions {
+        DeferredDeletions {
+            count: AtomicUsize::new(0),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn defer(&self, jinstance: jobject) {
+        if jinstance.is_null() {
+            return;
+        }
+        debug("Deferring the deletion of a global reference");
+        let mut pending = self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        pending.push(jinstance as usize);
+        self.count.store(pending.len(), Ordering::SeqCst);
+    }
+
+    fn delete_pending(&self, jni_env: *mut JNIEnv) {
+        if self.count.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let pending: Vec<usize> = {
+            let mut pending = self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.count.store(0, Ordering::SeqCst);
+            pending.drain(..).collect()
+        };
+        debug(&format!("Deleting {} deferred global references", pending.len()));
+        for jinstance in pending {
+            jni_utils::delete_java_ref(jni_env, jinstance as jobject);
+        }
+    }
+
+    fn clear(&self) {
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+        self.count.store(0, Ordering::SeqCst);
+    }
+}
+
+/// Defers the deletion of a global reference, until a `Jvm` is available.
+pub(crate) fn defer_deletion(jinstance: jobject) {
+    DEFERRED_DELETIONS.defer(jinstance);
+}
+
+/// Deletes the global references whose deletion was deferred, using the `jni_env` of the current thread.
+pub(crate) fn delete_pending(jni_env: *mut JNIEnv) {
+    DEFERRED_DELETIONS.delete_pending(jni_env);
+}
+
+/// Forgets the deferred deletions. Called when the JavaVM is destroyed and the references are not valid anymore.
+pub(crate) fn clear() {
+    DEFERRED_DELETIONS.clear();
+}
+
+#[cfg(test)]
+mod cleanup_unit_tests {
+    use std::thread;
+
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::{errors, Instance, InvocationArg};
+
+    use super::*;
+
+    fn is_pending(deletions: &DeferredDeletions, jinstance: jobject) -> bool {
+        deletions.pending.lock().unwrap().contains(&(jinstance as usize))
+    }
+
+    #[test]
+    fn deferred_deletions() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let deletions = DeferredDeletions::new();
+        let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("deferred")?])?;
+        let jinstance = instance.java_object();
+
+        deletions.defer(jinstance);
+        deletions.defer(std::ptr::null_mut());
+        assert!(is_pending(&deletions, jinstance));
+        assert_eq!(deletions.count.load(Ordering::SeqCst), 1);
+
+        deletions.delete_pending(jvm.jni_env);
+        assert!(!is_pending(&deletions, jinstance));
+        assert_eq!(deletions.count.load(Ordering::SeqCst), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn instances_outliving_their_jvm() -> errors::Result<()> {
+        let (instance, jinstance) = thread::spawn(|| -> errors::Result<(Instance, usize)> {
+            let jvm = create_tests_jvm()?;
+            let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("outliving")?])?;
+            let jinstance = instance.raw_jobject() as usize;
+            Ok((instance, jinstance))
+        })
+        .join()
+        .unwrap()?;
+
+        // Dropped in a thread without a Jvm. Other tests may create a Jvm and delete it at any time after this.
+        thread::spawn(move || drop(instance)).join().unwrap();
+
+        // Deleted at the latest by the next Jvm
+        let _jvm = create_tests_jvm()?;
+        assert!(!is_pending(&DEFERRED_DELETIONS, jinstance as jobject));
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::api::remote::{RemoteCommand, RemoteJvm};
+use crate::errors::J4RsError;
+use crate::logger::info;
+use crate::{errors, JvmBuilder};
+
+/// A set of JVM workers, each one running in a process of its own.
+///
+/// Only one JavaVM can be created in a process, so the workers are `RemoteJvm`s (see `JvmBuilder::out_of_process`),
+/// which may have different classpaths and Java options. The invocations can be routed to the workers
+/// in round-robin (`next_worker`), or by key (`worker_for`), in order to keep the objects of a key, e.g. a tenant,
+/// isolated in one JVM.
+pub struct JvmCluster {
+    workers: Vec<Worker>,
+    next: AtomicUsize,
+}
+
+struct Worker {
+    command: RemoteCommand,
+    jvm: RemoteJvm,
+}
+
+impl JvmCluster {
+    /// Creates a cluster with one worker for each of the `builders`.
+    pub fn new(builders: &[&JvmBuilder]) -> errors::Result<JvmCluster> {
+        let commands = builders
+            .iter()
+            .map(|builder| builder.remote_command())
+            .collect::<errors::Result<Vec<_>>>()?;
+        JvmCluster::spawn(commands)
+    }
+
+    /// Creates a cluster with `count` identical workers, created by the `builder`.
+    pub fn with_workers(builder: &JvmBuilder, count: usize) -> errors::Result<JvmCluster> {
+        JvmCluster::spawn(vec![builder.remote_command()?; count])
+    }
+
+    fn spawn(commands: Vec<RemoteCommand>) -> errors::Result<JvmCluster> {
+        if commands.is_empty() {
+            return Err(J4RsError::GeneralError(
+                "A JvmCluster needs at least one worker".to_string(),
+            ));
+        }
+        let workers = commands
+            .into_iter()
+            .map(|command| Ok(Worker { jvm: command.spawn()?, command }))
+            .collect::<errors::Result<Vec<_>>>()?;
+        Ok(JvmCluster {
+            workers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the number of workers.
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Returns true if the cluster has no workers, which never happens.
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    /// Returns the worker at `index`, in the order that the workers were defined.
+    pub fn worker(&self, index: usize) -> Option<&RemoteJvm> {
+        self.workers.get(index).map(|worker| &worker.jvm)
+    }
+
+    /// Returns the workers, in round-robin. The workers whose process is not running are skipped, if possible.
+    pub fn next_worker(&self) -> &RemoteJvm {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..self.workers.len())
+            .map(|offset| &self.workers[(start + offset) % self.workers.len()].jvm)
+            .find(|jvm| jvm.is_alive())
+            .unwrap_or(&self.workers[start % self.workers.len()].jvm)
+    }
+
+    /// Returns the worker for a `key`. The same key is always routed to the same worker.
+    pub fn worker_for<K: Hash + ?Sized>(&self, key: &K) -> &RemoteJvm {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.workers[(hasher.finish() % self.workers.len() as u64) as usize].jvm
+    }
+
+    /// Spawns again the workers whose process is not running and returns their number.
+    ///
+    /// The `RemoteInstance`s of the replaced workers become invalid.
+    pub fn restart_dead_workers(&mut self) -> errors::Result<usize> {
+        let mut restarted = 0;
+        for (index, worker) in self.workers.iter_mut().enumerate() {
+            if !worker.jvm.is_alive() {
+                info(&format!("Restarting the JVM worker {}", index));
+                worker.jvm = worker.command.spawn()?;
+                restarted += 1;
+            }
+        }
+        Ok(restarted)
+    }
+}
+
+#[cfg(test)]
+mod cluster_unit_tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+    use crate::{JvmOptions, RemoteArg};
+
+    fn worker_name(jvm: &RemoteJvm) -> errors::Result<String> {
+        let name = jvm.invoke_static("java.lang.System", "getProperty", &[RemoteArg::try_from("worker.name")?])?;
+        jvm.to_rust(name)
+    }
+
+    #[test]
+    fn cluster_routing() -> errors::Result<()> {
+        let mut builder_a = JvmBuilder::new();
+        let builder_a = builder_a.with_jvm_options(JvmOptions::new().system_property("worker.name", "a"));
+        let mut builder_b = JvmBuilder::new();
+        let builder_b = builder_b.with_jvm_options(JvmOptions::new().system_property("worker.name", "b"));
+        let cluster = JvmCluster::new(&[builder_a, builder_b])?;
+        assert_eq!(cluster.len(), 2);
+        assert_eq!(worker_name(cluster.worker(0).unwrap())?, "a");
+        assert_eq!(worker_name(cluster.worker(1).unwrap())?, "b");
+        assert!(cluster.worker(2).is_none());
+
+        let mut names = vec![worker_name(cluster.next_worker())?, worker_name(cluster.next_worker())?];
+        names.sort();
+        assert_eq!(names, vec!["a", "b"]);
+
+        let tenant_worker = worker_name(cluster.worker_for("tenant"))?;
+        assert_eq!(worker_name(cluster.worker_for("tenant"))?, tenant_worker);
+        Ok(())
+    }
+
+    #[test]
+    fn cluster_restart() -> errors::Result<()> {
+        let mut cluster = JvmCluster::with_workers(&JvmBuilder::new(), 2)?;
+        let crashed = cluster.worker(0).unwrap();
+        let runtime = crashed.invoke_static("java.lang.Runtime", "getRuntime", &[])?;
+        assert!(crashed
+            .invoke(&runtime, "halt", &[RemoteArg::try_from(1)?.into_primitive()?])
+            .is_err());
+        drop(runtime);
+
+        // The dead worker is skipped
+        assert!(cluster.next_worker().is_alive());
+        assert!(cluster.next_worker().is_alive());
+
+        assert_eq!(cluster.restart_dead_workers()?, 1);
+        assert!(cluster.worker(0).unwrap().is_alive());
+        assert!(JvmCluster::with_workers(&JvmBuilder::new(), 0).is_err());
+        Ok(())
+    }
+}
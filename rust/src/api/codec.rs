@@ -0,0 +1,185 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(feature = "cbor")]
+use jni_sys::jobject;
+#[cfg(feature = "cbor")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "cbor")]
+use serde::Serialize;
+
+#[cfg(feature = "cbor")]
+use crate::logger::warn;
+use crate::Jvm;
+#[cfg(feature = "cbor")]
+use crate::api::{
+    JavaClass, PRIMITIVE_BOOLEAN, PRIMITIVE_BYTE, PRIMITIVE_CHAR, PRIMITIVE_DOUBLE, PRIMITIVE_FLOAT, PRIMITIVE_INT,
+    PRIMITIVE_LONG, PRIMITIVE_SHORT,
+};
+#[cfg(feature = "cbor")]
+use crate::{cache, errors, Instance, InvocationArg};
+
+#[cfg(feature = "cbor")]
+const CLASS_CBOR_VALUES: &str = "org.astonbitecode.j4rs.api.value.CborValues";
+
+// The negotiated format, as the discriminant of the SerializationFormat
+static FORMAT: AtomicU8 = AtomicU8::new(SerializationFormat::Json as u8);
+
+/// The format of the values that are exchanged between Rust and Java by `Jvm::to_rust` and `InvocationArg::new`.
+///
+/// The format is set with `JvmBuilder::serialization_format` and applies to all the `Jvm`s of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerializationFormat {
+    /// JSON. This is the default and is always supported.
+    #[default]
+    Json,
+    /// CBOR, a compact binary format that keeps binary data as byte strings. Needs the `cbor` feature.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl Jvm {
+    /// Returns the format of the values that are exchanged with Java by `Jvm::to_rust` and `InvocationArg::new`.
+    pub fn serialization_format(&self) -> SerializationFormat {
+        format()
+    }
+
+    /// Deserializes the Java object of the `jinstance` from CBOR. The `jinstance` is not consumed.
+    #[cfg(feature = "cbor")]
+    pub(crate) fn cbor_to_rust<T: DeserializeOwned>(&self, jinstance: jobject) -> errors::Result<T> {
+        // The jobject is owned by the caller
+        let instance = Instance {
+            jinstance,
+            class_name: cache::UNKNOWN_FOR_RUST.to_string(),
+            skip_deleting_jobject: true,
+        };
+        let bytes = self.invoke_static(
+            CLASS_CBOR_VALUES,
+            "encode",
+            // The Instance may be of a primitive class, or of a null object
+            &[InvocationArg::from(self.cast(&instance, "java.lang.Object")?)],
+        )?;
+        let bytes: Vec<u8> = self.to_rust(bytes)?;
+        Ok(ciborium::from_reader(bytes.as_slice())?)
+    }
+}
+
+pub(crate) fn format() -> SerializationFormat {
+    match FORMAT.load(Ordering::SeqCst) {
+        #[cfg(feature = "cbor")]
+        f if f == SerializationFormat::Cbor as u8 => SerializationFormat::Cbor,
+        _ => SerializationFormat::Json,
+    }
+}
+
+/// Sets the `format` if the Java side supports it, falling back to JSON otherwise.
+#[cfg_attr(not(feature = "cbor"), allow(unused_variables))]
+pub(crate) fn negotiate(jvm: &Jvm, format: SerializationFormat) -> SerializationFormat {
+    let negotiated = match format {
+        SerializationFormat::Json => format,
+        // Older j4rs jars do not contain the CBOR support
+        #[cfg(feature = "cbor")]
+        SerializationFormat::Cbor => match jvm.static_class(CLASS_CBOR_VALUES) {
+            Ok(_) => format,
+            Err(error) => {
+                warn(&format!("CBOR is not supported by the Java side ({}). Falling back to JSON.", error));
+                SerializationFormat::Json
+            }
+        },
+    };
+    FORMAT.store(negotiated as u8, Ordering::SeqCst);
+    negotiated
+}
+
+/// Returns true if the values of the class `class_name` are passed to Java as CBOR.
+///
+/// Single values that may be converted to primitives, like `bool`s, are always passed as JSON.
+#[cfg(feature = "cbor")]
+pub(crate) fn is_passed_as_cbor(class_name: &str) -> bool {
+    let primitives = [
+        PRIMITIVE_BOOLEAN,
+        PRIMITIVE_BYTE,
+        PRIMITIVE_SHORT,
+        PRIMITIVE_INT,
+        PRIMITIVE_LONG,
+        PRIMITIVE_FLOAT,
+        PRIMITIVE_DOUBLE,
+        PRIMITIVE_CHAR,
+    ];
+    format() == SerializationFormat::Cbor
+        && matches!(JavaClass::from(class_name), JavaClass::List | JavaClass::Of(_))
+        && !primitives.contains(&class_name)
+}
+
+/// Creates an InvocationArg of the class `class_name` by passing `arg` to Java as CBOR.
+#[cfg(feature = "cbor")]
+pub(crate) fn cbor_invocation_arg<T: Serialize>(arg: &T, class_name: &str) -> errors::Result<InvocationArg> {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(arg, &mut bytes)?;
+    let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+    let decoded = jvm.invoke_static(
+        CLASS_CBOR_VALUES,
+        "decode",
+        &[
+            InvocationArg::try_from(class_name)?,
+            InvocationArg::try_from(bytes.as_slice())?,
+        ],
+    )?;
+    // The decoded object is returned as a java.lang.Object
+    let instance = jvm.cast(&decoded, class_name)?;
+    Ok(InvocationArg::Java {
+        instance,
+        class_name: class_name.to_string(),
+        serialized: true,
+    })
+}
+
+#[cfg(all(test, feature = "cbor"))]
+mod codec_unit_tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    // The format is not changed, in order not to affect the tests that run in parallel
+    #[test]
+    fn cbor_values() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let ia = cbor_invocation_arg(&vec![1_i64, 2, 3], "java.util.List")?;
+        let list = jvm.create_instance("java.util.ArrayList", &[ia])?;
+        let size: i32 = jvm.to_rust(jvm.invoke(&list, "size", InvocationArg::empty())?)?;
+        assert_eq!(size, 3);
+        let list: Vec<i64> = jvm.cbor_to_rust(list.jinstance)?;
+        assert_eq!(list, vec![1, 2, 3]);
+
+        let map = HashMap::from([("key".to_string(), 1_u64 << 40)]);
+        let ia = cbor_invocation_arg(&map, "java.util.Map")?;
+        let instance = jvm.create_instance("java.util.HashMap", &[ia])?;
+        assert_eq!(jvm.cbor_to_rust::<HashMap<String, u64>>(instance.jinstance)?, map);
+
+        // Byte arrays are exchanged as byte strings
+        let decoder = jvm.invoke_static("java.util.Base64", "getDecoder", InvocationArg::empty())?;
+        let bytes = jvm.invoke(&decoder, "decode", &[InvocationArg::try_from("AQID")?])?;
+        let encoded = jvm.invoke_static(CLASS_CBOR_VALUES, "encode", &[InvocationArg::from(jvm.clone_instance(&bytes)?)])?;
+        assert_eq!(jvm.to_rust::<Vec<u8>>(encoded)?, vec![0x43, 1, 2, 3]);
+        assert_eq!(jvm.cbor_to_rust::<Vec<u8>>(bytes.jinstance)?, vec![1, 2, 3]);
+
+        let optional = jvm.invoke_static("java.util.Optional", "empty", InvocationArg::empty())?;
+        assert_eq!(jvm.cbor_to_rust::<Option<String>>(optional.jinstance)?, None);
+        Ok(())
+    }
+}
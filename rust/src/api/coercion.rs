@@ -0,0 +1,64 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::logger::debug;
+use crate::{errors, InvocationArg, Jvm};
+
+const CLASS_NUMERIC_COERCION: &str = "org.astonbitecode.j4rs.api.invocation.NumericCoercion";
+
+impl Jvm {
+    /// Enables or disables the lenient coercion of the invocation arguments.
+    ///
+    /// By default, a method is invoked only if its parameter types match the types of the arguments exactly,
+    /// so, for example, an `i32` argument needs `InvocationArg::into_primitive` for an `int` parameter
+    /// and cannot satisfy a `long` parameter.
+    /// With the lenient coercion, when no method matches exactly, the arguments are unboxed and widened
+    /// like in Java source code, e.g. an `i32` satisfies `int`, `long` and `double` parameters.
+    /// If more methods match, the one that needs the narrowest coercion is invoked.
+    ///
+    /// The setting applies to all the invocations of all the `Jvm`s of the process.
+    pub fn set_lenient_coercion(&self, lenient: bool) -> errors::Result<()> {
+        debug(&format!("Setting the lenient coercion of the invocation arguments to {}", lenient));
+        self.invoke_static(
+            CLASS_NUMERIC_COERCION,
+            "setLenient",
+            &[InvocationArg::try_from(lenient)?.into_primitive()?],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod coercion_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn lenient_coercion() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let atomic_long = jvm.create_instance("java.util.concurrent.atomic.AtomicLong", InvocationArg::empty())?;
+        assert!(jvm.invoke(&atomic_long, "addAndGet", &[InvocationArg::try_from(2_i32)?]).is_err());
+
+        jvm.set_lenient_coercion(true)?;
+        let sum = jvm.invoke(&atomic_long, "addAndGet", &[InvocationArg::try_from(2_i32)?]);
+        let root = jvm.invoke_static("java.lang.Math", "sqrt", &[InvocationArg::try_from(16_i32)?]);
+        let max = jvm.invoke_static("java.lang.Math", "max", &[InvocationArg::try_from(3_i32)?, InvocationArg::try_from(7_i64)?]);
+        jvm.set_lenient_coercion(false)?;
+
+        assert_eq!(jvm.to_rust::<i64>(sum?)?, 2);
+        assert_eq!(jvm.to_rust::<f64>(root?)?, 4.0);
+        assert_eq!(jvm.to_rust::<i64>(max?)?, 7);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::logger::debug;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const CLASS_MDC: &str = "org.slf4j.MDC";
+
+impl Jvm {
+    /// Calls `f` while the `thread_local`, which is an `Instance` of a `java.lang.ThreadLocal`,
+    /// is set to `value` for the current thread.
+    ///
+    /// The invocations that `f` makes with this `Jvm` run in the current thread, so they see the `value`.
+    /// The previous value of the `thread_local` is restored after `f` returns, even if `f` returns an error.
+    pub fn with_thread_local<R, F>(&self, thread_local: &Instance, value: InvocationArg, f: F) -> errors::Result<R>
+    where
+        F: FnOnce() -> errors::Result<R>,
+    {
+        let previous = self.invoke(thread_local, "get", InvocationArg::empty())?;
+        self.invoke(thread_local, "set", &[value])?;
+        let result = f();
+        let restored = if previous.is_null(self)? {
+            self.invoke(thread_local, "remove", InvocationArg::empty())
+        } else {
+            self.invoke(thread_local, "set", &[InvocationArg::from(previous)])
+        };
+        restore_after(result, restored)
+    }
+
+    /// Calls `f` while the SLF4J `MDC` of the current thread contains the `entries`,
+    /// so that the Java code that `f` invokes logs them, e.g. trace IDs.
+    ///
+    /// The previous values of the keys are restored after `f` returns, even if `f` returns an error.
+    /// Other keys of the `MDC` are not touched. SLF4J needs to be in the classpath.
+    pub fn with_mdc<R, F>(&self, entries: &[(&str, &str)], f: F) -> errors::Result<R>
+    where
+        F: FnOnce() -> errors::Result<R>,
+    {
+        debug(&format!("Setting {} MDC entries", entries.len()));
+        let mut previous_values = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            let previous = self.invoke_static(CLASS_MDC, "get", &[InvocationArg::try_from(*key)?])?;
+            previous_values.push((*key, self.to_rust::<Option<String>>(previous)?));
+            self.invoke_static(CLASS_MDC, "put", &[InvocationArg::try_from(*key)?, InvocationArg::try_from(*value)?])?;
+        }
+        let result = f();
+        let restored = previous_values
+            .into_iter()
+            .rev()
+            .try_for_each(|(key, previous)| {
+                match previous {
+                    Some(value) => self.invoke_static(
+                        CLASS_MDC,
+                        "put",
+                        &[InvocationArg::try_from(key)?, InvocationArg::try_from(value)?],
+                    ),
+                    None => self.invoke_static(CLASS_MDC, "remove", &[InvocationArg::try_from(key)?]),
+                }
+                .map(|_| ())
+            });
+        restore_after(result, restored)
+    }
+}
+
+// The error of the call takes precedence over the error of restoring the context
+fn restore_after<R, T>(result: errors::Result<R>, restored: errors::Result<T>) -> errors::Result<R> {
+    let value = result?;
+    restored?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod context_unit_tests {
+    use super::*;
+    use crate::errors::J4RsError;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn thread_local_is_set_and_restored() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let thread_local = jvm.create_instance("java.lang.ThreadLocal", InvocationArg::empty())?;
+        let get = |jvm: &Jvm| -> errors::Result<Option<String>> {
+            jvm.to_rust(jvm.invoke(&thread_local, "get", InvocationArg::empty())?)
+        };
+
+        let seen = jvm.with_thread_local(&thread_local, InvocationArg::try_from("outer")?, || {
+            let inner = jvm.with_thread_local(&thread_local, InvocationArg::try_from("inner")?, || get(&jvm))?;
+            assert_eq!(inner.as_deref(), Some("inner"));
+            get(&jvm)
+        })?;
+        assert_eq!(seen.as_deref(), Some("outer"));
+        assert_eq!(get(&jvm)?, None);
+
+        let res: errors::Result<()> = jvm.with_thread_local(&thread_local, InvocationArg::try_from("failing")?, || {
+            Err(J4RsError::GeneralError("Boom!".to_string()))
+        });
+        assert!(res.is_err());
+        assert_eq!(get(&jvm)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn mdc_without_slf4j() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let res = jvm.with_mdc(&[("traceId", "1234")], || Ok(()));
+        assert!(res.is_err());
+        Ok(())
+    }
+}
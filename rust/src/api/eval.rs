@@ -0,0 +1,100 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+pub(crate) const CLASS_JSHELL_EVALUATOR: &str = "org.astonbitecode.j4rs.api.eval.JShellEvaluator";
+
+/// A persistent context for evaluating Java code with JShell.
+///
+/// The code is executed in the JVM of j4rs, so the results are the actual Java objects and `Instance`s can be bound
+/// to variables of the context. The declarations of each evaluation are available to the evaluations that follow.
+///
+/// It is created by calling `Jvm::eval_context`. JShell requires Java 11 or above.
+pub struct EvalContext<'a> {
+    jvm: &'a Jvm,
+    evaluator: Instance,
+}
+
+impl<'a> EvalContext<'a> {
+    pub(crate) fn new(jvm: &'a Jvm) -> errors::Result<EvalContext<'a>> {
+        let evaluator = jvm.create_instance(CLASS_JSHELL_EVALUATOR, InvocationArg::empty())?;
+        Ok(EvalContext { jvm, evaluator })
+    }
+
+    /// Evaluates Java `code`, which may contain statements, declarations and expressions.
+    ///
+    /// Returns the value of the last expression or variable declaration of the code, or a Java `null` if there is none.
+    pub fn eval(&self, code: &str) -> errors::Result<Instance> {
+        self.jvm.invoke(&self.evaluator, "eval", &[InvocationArg::try_from(code)?])
+    }
+
+    /// Binds a `value`, e.g. an `Instance`, to the variable `name` of the context.
+    ///
+    /// The variable is declared with the public class of the value that is closest to its actual class, if this class
+    /// is found in the classpath, or as an `Object` otherwise.
+    pub fn bind<R>(&self, name: &str, value: R) -> errors::Result<()>
+    where
+        R: TryInto<InvocationArg>,
+        J4RsError: From<R::Error>,
+    {
+        self.jvm.invoke(
+            &self.evaluator,
+            "bind",
+            &[InvocationArg::try_from(name)?, value.try_into()?],
+        )?;
+        Ok(())
+    }
+}
+
+impl<'a> Drop for EvalContext<'a> {
+    fn drop(&mut self) {
+        let _ = self.jvm.invoke(&self.evaluator, "close", InvocationArg::empty());
+    }
+}
+
+#[cfg(test)]
+mod eval_unit_tests {
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::{errors, InvocationArg};
+
+    #[test]
+    fn evaluate_code() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let random: i32 = jvm.to_rust(jvm.eval("new java.util.Random().nextInt(10)")?)?;
+        assert!((0..10).contains(&random));
+
+        let ctx = jvm.eval_context()?;
+        let x: i32 = jvm.to_rust(ctx.eval("int x = 3; x * 2")?)?;
+        assert_eq!(x, 6);
+        let x: i32 = jvm.to_rust(ctx.eval("x")?)?;
+        assert_eq!(x, 3);
+
+        let list = jvm.java_list("java.lang.String", vec!["a"])?;
+        let list = jvm.create_instance("java.util.ArrayList", &[InvocationArg::from(list)])?;
+        ctx.bind("list", jvm.clone_instance(&list)?)?;
+        ctx.bind("y", 2)?;
+        ctx.eval("list.add(\"b\".repeat(y))")?;
+        let strings: Vec<String> = jvm.to_rust(list)?;
+        assert_eq!(strings, vec!["a", "bb"]);
+
+        assert!(ctx.eval("System.out.print(\"\")")?.is_null(&jvm)?);
+        assert!(ctx.eval("1 / 0").is_err());
+        assert!(ctx.eval("undefined + 1").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, RwLock};
+
+use crate::errors::{J4RsError, JavaException, MappedException};
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const CLASS_UTILS: &str = "org.astonbitecode.j4rs.utils.Utils";
+
+type ExceptionMapping = Arc<dyn Fn(&JavaException) -> Arc<dyn Error + Send + Sync> + Send + Sync>;
+
+lazy_static! {
+    // The registered mappings of Java exceptions to Rust errors, keyed by the class name of the exceptions
+    static ref MAPPINGS: RwLock<HashMap<String, ExceptionMapping>> = RwLock::new(HashMap::new());
+}
+
+impl Jvm {
+    /// Registers a mapping of the Java exceptions of the class `class_name` to Rust errors.
+    ///
+    /// When an invocation throws an exception of exactly this class, the returned error is a
+    /// `J4RsError::MappedException`, which contains the result of `f`, along with the `JavaException`.
+    /// Exceptions of subclasses are not mapped. A new mapping for the same class replaces the previous one.
+    ///
+    /// The mappings apply to all the `Jvm`s of the process.
+    pub fn map_exception<E, F>(&self, class_name: &str, f: F)
+    where
+        E: Error + Send + Sync + 'static,
+        F: Fn(&JavaException) -> E + Send + Sync + 'static,
+    {
+        let mapping: ExceptionMapping = Arc::new(move |exception| Arc::new(f(exception)));
+        MAPPINGS.write().unwrap().insert(class_name.to_string(), mapping);
+    }
+
+    /// Removes the mapping of the Java exceptions of the class `class_name`, if any.
+    pub fn unmap_exception(&self, class_name: &str) {
+        MAPPINGS.write().unwrap().remove(class_name);
+    }
+}
+
+/// Creates the error for a Java exception, applying the registered mapping of its class, if any.
+pub(crate) fn to_error(java_exception: JavaException) -> J4RsError {
+    let mapping = MAPPINGS.read().unwrap().get(java_exception.class_name()).cloned();
+    match mapping {
+        Some(mapping) => {
+            let error = mapping(&java_exception);
+            J4RsError::MappedException(Box::new(MappedException::new(error, java_exception)))
+        }
+        None => J4RsError::JavaException(Box::new(java_exception)),
+    }
+}
+
+/// Creates the error for a Java Throwable that is passed to Rust as an `Instance`, instead of being thrown.
+pub(crate) fn throwable_to_error(jvm: &Jvm, mut throwable: Instance) -> errors::Result<J4RsError> {
+    let json = jvm.invoke_static(
+        CLASS_UTILS,
+        "throwableToJson",
+        &[InvocationArg::from(jvm.clone_instance(&throwable)?)],
+    )?;
+    let mut java_exception: JavaException = serde_json::from_str(&jvm.to_rust::<String>(json)?)?;
+    throwable.class_name = java_exception.class_name().to_string();
+    java_exception.set_throwable(throwable);
+    Ok(to_error(java_exception))
+}
+
+#[cfg(test)]
+mod exceptions_unit_tests {
+    use std::fmt;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::{errors, InvocationArg};
+
+    #[derive(Debug)]
+    enum MyError {
+        UnknownZone(String),
+    }
+
+    impl fmt::Display for MyError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                MyError::UnknownZone(message) => write!(f, "Unknown zone: {}", message),
+            }
+        }
+    }
+
+    impl Error for MyError {}
+
+    #[test]
+    fn mapped_exceptions() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let zone_rules_exception = "java.time.zone.ZoneRulesException";
+        jvm.map_exception(zone_rules_exception, |e| {
+            MyError::UnknownZone(e.message().unwrap_or_default().to_string())
+        });
+        let res = jvm.invoke_static("java.time.ZoneId", "of", &[InvocationArg::try_from("Not/AZone")?]);
+        match &res {
+            Err(J4RsError::MappedException(mapped)) => {
+                assert!(matches!(mapped.downcast_ref::<MyError>(), Some(MyError::UnknownZone(_))));
+                assert_eq!(mapped.java_exception().class_name(), zone_rules_exception);
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("The exception was not thrown"),
+        }
+        let java_exception = res.err().and_then(|error| error.java_exception().cloned());
+        assert_eq!(java_exception.map(|e| e.class_name().to_string()).as_deref(), Some(zone_rules_exception));
+
+        jvm.unmap_exception(zone_rules_exception);
+        let res = jvm.invoke_static("java.time.ZoneId", "of", &[InvocationArg::try_from("Not/AZone")?]);
+        assert!(matches!(res, Err(J4RsError::JavaException(_))));
+        assert!(res.err().is_some_and(|error| error.java_exception().is_some()));
+        assert!(J4RsError::GeneralError("Not from Java".to_string()).java_exception().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::errors::J4RsError;
+use crate::{errors, jni_utils, Instance, InvocationArg, Jvm};
+
+const CLASS_FIELD_VALUES: &str = "org.astonbitecode.j4rs.api.reflection.FieldValues";
+
+impl Jvm {
+    /// Reads the values of the fields `names` of the `instance`, using reflection.
+    ///
+    /// Each value is read by the getter of the field (`getX` or `isX`), or, if there is no getter,
+    /// by the accessor of a record component (`x()`), or else, from the field itself, even if it is not public.
+    pub fn field_values(&self, instance: &Instance, names: &[&str]) -> errors::Result<Vec<Instance>> {
+        let names = names
+            .iter()
+            .map(|name| InvocationArg::try_from(*name))
+            .collect::<errors::Result<Vec<InvocationArg>>>()?;
+        let names = self.create_java_array("java.lang.String", &names)?;
+        let values = self.invoke_static(
+            CLASS_FIELD_VALUES,
+            "instancesOf",
+            &[InvocationArg::from(self.clone_instance(instance)?), InvocationArg::from(names)],
+        )?;
+        self.instances_of_array(&values)
+    }
+
+    /// Creates a `T` from the `instance`, reading its fields with `field_values`.
+    ///
+    /// Unlike `to_rust`, the `instance` is not serialized to JSON. Only the fields that `T` needs are read,
+    /// so this works for objects that cannot be serialized, e.g. because they contain cyclic references.
+    /// The nested structs are read the same way, the sequences are read from Java arrays and `Iterable`s
+    /// and any other values are transformed like in `to_rust`.
+    pub fn to_struct<T: DeserializeOwned>(&self, instance: &Instance) -> errors::Result<T> {
+        let deserializer = InstanceDeserializer {
+            jvm: self,
+            instance: self.clone_instance(instance)?,
+        };
+        Ok(T::deserialize(deserializer)?)
+    }
+
+    // Returns the Instances of a Java `Instance[]`
+    fn instances_of_array(&self, array_instance: &Instance) -> errors::Result<Vec<Instance>> {
+        let array = self.local_java_object(array_instance.jinstance)?;
+        let instances = unsafe { jni_utils::instances_from_jobject_array(self.jni_env, array) };
+        jni_utils::delete_java_local_ref(self.jni_env, array);
+        instances
+    }
+}
+
+// Deserializes a Java object reading its fields, or elements, one by one
+struct InstanceDeserializer<'a> {
+    jvm: &'a Jvm,
+    instance: Instance,
+}
+
+impl InstanceDeserializer<'_> {
+    fn into_rust<T: DeserializeOwned + 'static>(self) -> Result<T, serde_json::Error> {
+        self.jvm.to_rust(self.instance).map_err(to_de_error)
+    }
+
+    fn into_json(self) -> Result<serde_json::Value, serde_json::Error> {
+        self.into_rust()
+    }
+}
+
+fn to_de_error(error: J4RsError) -> serde_json::Error {
+    de::Error::custom(error)
+}
+
+macro_rules! deserialize_with_to_rust {
+    ($($method:ident => $visit:ident: $t:ty),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.into_rust::<$t>()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for InstanceDeserializer<'_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_any(self.into_json()?, visitor)
+    }
+
+    deserialize_with_to_rust!(
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_str => visit_string: String,
+        deserialize_string => visit_string: String,
+        deserialize_bytes => visit_byte_buf: Vec<u8>,
+        deserialize_byte_buf => visit_byte_buf: Vec<u8>
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.jvm.is_java_null(self.instance.jinstance).map_err(to_de_error)? {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let elements = self
+            .jvm
+            .invoke_static(CLASS_FIELD_VALUES, "elementInstances", &[InvocationArg::from(self.instance)])
+            .and_then(|elements| self.jvm.instances_of_array(&elements))
+            .map_err(to_de_error)?;
+        visitor.visit_seq(InstancesAccess {
+            jvm: self.jvm,
+            instances: elements.into_iter(),
+            fields: [].iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let values = self.jvm.field_values(&self.instance, fields).map_err(to_de_error)?;
+        visitor.visit_map(InstancesAccess {
+            jvm: self.jvm,
+            instances: values.into_iter(),
+            fields: fields.iter(),
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_enum(self.into_json()?, name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        char map identifier ignored_any
+    }
+}
+
+// Accesses the elements of a sequence, or the fields of a struct, along with their names
+struct InstancesAccess<'a> {
+    jvm: &'a Jvm,
+    instances: std::vec::IntoIter<Instance>,
+    fields: std::slice::Iter<'static, &'static str>,
+}
+
+impl<'a> InstancesAccess<'a> {
+    fn next_deserializer(&mut self) -> Result<InstanceDeserializer<'a>, serde_json::Error> {
+        match self.instances.next() {
+            Some(instance) => Ok(InstanceDeserializer { jvm: self.jvm, instance }),
+            None => Err(de::Error::custom("No more values")),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for InstancesAccess<'_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        if self.instances.len() == 0 {
+            return Ok(None);
+        }
+        seed.deserialize(self.next_deserializer()?).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.instances.len())
+    }
+}
+
+impl<'de> MapAccess<'de> for InstancesAccess<'_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        match self.fields.next() {
+            Some(field) => seed.deserialize(field.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Self::Error> {
+        seed.deserialize(self.next_deserializer()?)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+#[cfg(test)]
+mod fields_unit_tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::Null;
+
+    const CLASS_SIMPLE_ENTRY: &str = "java.util.AbstractMap$SimpleEntry";
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Entry<V> {
+        key: String,
+        value: V,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Key {
+        key: String,
+    }
+
+    #[test]
+    fn field_values() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let entry = jvm.create_instance(
+            CLASS_SIMPLE_ENTRY,
+            &[InvocationArg::try_from("a")?, InvocationArg::try_from(1_i32)?],
+        )?;
+        let values = jvm.field_values(&entry, &["key", "value"])?;
+        assert_eq!(jvm.to_rust::<String>(jvm.clone_instance(&values[0])?)?, "a");
+        assert_eq!(jvm.to_rust::<i32>(jvm.clone_instance(&values[1])?)?, 1);
+        assert!(jvm.field_values(&entry, &["missing"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn to_struct() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let list = jvm.java_list("java.lang.Integer", vec![1_i32, 2])?;
+        let inner = jvm.create_instance(
+            CLASS_SIMPLE_ENTRY,
+            &[InvocationArg::try_from("inner")?, InvocationArg::from(list)],
+        )?;
+        let outer = jvm.create_instance(
+            CLASS_SIMPLE_ENTRY,
+            &[InvocationArg::try_from("outer")?, InvocationArg::from(inner)],
+        )?;
+        let entry: Entry<Entry<Vec<i32>>> = jvm.to_struct(&outer)?;
+        assert_eq!(
+            entry,
+            Entry {
+                key: "outer".to_string(),
+                value: Entry { key: "inner".to_string(), value: vec![1, 2] }
+            }
+        );
+
+        // An entry that contains itself cannot be serialized, but its fields can be read
+        let cyclic = jvm.create_instance(
+            CLASS_SIMPLE_ENTRY,
+            &[InvocationArg::try_from("cyclic")?, InvocationArg::try_from(Null::Of("java.lang.Object"))?],
+        )?;
+        jvm.invoke(&cyclic, "setValue", &[InvocationArg::from(jvm.clone_instance(&cyclic)?)])?;
+        let key: Key = jvm.to_struct(&cyclic)?;
+        assert_eq!(key.key, "cyclic");
+        let entry: Entry<Key> = jvm.to_struct(&cyclic)?;
+        assert_eq!(entry.value.key, "cyclic");
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use jni_sys::jobject;
+use serde::de::DeserializeOwned;
+
+use crate::errors::opt_to_res;
+use crate::{cache, errors, jni_utils, Instance, InvocationArg, Jvm};
+
+/// A JNI local frame, in which `LocalInstance`s can be created without allocating JNI global references.
+///
+/// All the local references that are created in the frame are released at once, when the frame is popped.
+/// It is created by calling `Jvm::with_local_frame`.
+pub struct LocalFrame<'a> {
+    jvm: &'a Jvm,
+}
+
+impl<'a> LocalFrame<'a> {
+    pub(crate) fn new(jvm: &'a Jvm) -> LocalFrame<'a> {
+        LocalFrame { jvm }
+    }
+
+    /// Returns the `Jvm` of the frame.
+    pub fn jvm(&self) -> &Jvm {
+        self.jvm
+    }
+
+    /// Creates a `LocalInstance` of the class `class_name`. See `Jvm::create_instance`.
+    pub fn create_instance(
+        &self,
+        class_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<LocalInstance<'_>> {
+        unsafe {
+            let jinstance = self.jvm.do_create_instance(class_name, inv_args)?;
+            Ok(LocalInstance::new(jinstance, class_name))
+        }
+    }
+
+    /// Invokes the method `method_name` of an `Instance`, or `LocalInstance`, and returns the result
+    /// as a `LocalInstance`. See `Jvm::invoke`.
+    pub fn invoke(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<LocalInstance<'_>> {
+        unsafe {
+            let jinstance = self.jvm.do_invoke(instance, method_name, inv_args)?;
+            Ok(LocalInstance::new(jinstance, cache::UNKNOWN_FOR_RUST))
+        }
+    }
+
+    /// Returns the Rust representation of a `LocalInstance`.
+    pub fn to_rust<T>(&self, instance: &LocalInstance) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.jvm.jobject_to_rust_boxed(instance.instance.jinstance).map(|v| *v)
+    }
+
+    /// Creates an `Instance` that references the same Java object as the `LocalInstance`,
+    /// in order to use it after the frame is popped.
+    pub fn to_global(&self, instance: &LocalInstance) -> errors::Result<Instance> {
+        unsafe {
+            let global = (opt_to_res(cache::get_jni_new_global_ref())?)(self.jvm.jni_env, instance.instance.jinstance);
+            Jvm::do_return(self.jvm.jni_env, ())?;
+            Instance::new(global, instance.instance.class_name())
+        }
+    }
+}
+
+/// An `Instance` that is backed by a JNI local reference. It is valid only within its `LocalFrame`.
+///
+/// A `LocalInstance` can be used wherever an `&Instance` is expected, for as long as the frame is active.
+/// Its local reference is deleted when it is dropped, or, at the latest, when the frame is popped.
+pub struct LocalInstance<'f> {
+    instance: Instance,
+    // LocalInstances may not leave the frame, nor the thread
+    _marker: PhantomData<(&'f (), *const ())>,
+}
+
+impl<'f> LocalInstance<'f> {
+    fn new(jinstance: jobject, class_name: &str) -> LocalInstance<'f> {
+        LocalInstance {
+            instance: Instance {
+                jinstance,
+                class_name: class_name.to_string(),
+                // The local reference is deleted by the LocalInstance
+                skip_deleting_jobject: true,
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'f> Deref for LocalInstance<'f> {
+    type Target = Instance;
+
+    fn deref(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl<'f> Drop for LocalInstance<'f> {
+    fn drop(&mut self) {
+        if let Some(j_env) = cache::get_thread_local_env_opt() {
+            jni_utils::delete_java_local_ref(j_env, self.instance.jinstance);
+        }
+    }
+}
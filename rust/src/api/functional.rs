@@ -0,0 +1,150 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::any::Any;
+use std::convert::{TryFrom, TryInto};
+
+use serde::de::DeserializeOwned;
+
+use crate::api::{Jvm, CLASS_RUST_FUNCTIONS, CLASS_RUST_FUNCTIONS_CALLBACK};
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg};
+
+impl InvocationArg {
+    /// Creates an InvocationArg that contains a `java.util.function.Function` which calls the closure `f`.
+    ///
+    /// The argument of the `Function` is transformed to `A` like with `Jvm::to_rust`
+    /// and the result of `f` is transformed to an `InvocationArg`.
+    /// The closure is dropped when the `Function` is garbage collected in the Java world.
+    pub fn from_fn<A, R, F>(f: F) -> errors::Result<InvocationArg>
+    where
+        A: DeserializeOwned + Any,
+        R: TryInto<InvocationArg, Error = J4RsError>,
+        F: Fn(A) -> R + Send + Sync + 'static,
+    {
+        functional_arg("function", move |jvm, arg| {
+            let arg = jvm.to_rust(arg)?;
+            f(arg).try_into().map(Some)
+        })
+    }
+
+    /// Creates an InvocationArg that contains a `java.util.function.Consumer` which calls the closure `f`.
+    ///
+    /// See `from_fn`.
+    pub fn from_consumer<A, F>(f: F) -> errors::Result<InvocationArg>
+    where
+        A: DeserializeOwned + Any,
+        F: Fn(A) + Send + Sync + 'static,
+    {
+        functional_arg("consumer", move |jvm, arg| {
+            f(jvm.to_rust(arg)?);
+            Ok(None)
+        })
+    }
+
+    /// Creates an InvocationArg that contains a `java.util.function.Supplier` which calls the closure `f`.
+    ///
+    /// See `from_fn`.
+    pub fn from_supplier<R, F>(f: F) -> errors::Result<InvocationArg>
+    where
+        R: TryInto<InvocationArg, Error = J4RsError>,
+        F: Fn() -> R + Send + Sync + 'static,
+    {
+        functional_arg("supplier", move |_, _| f().try_into().map(Some))
+    }
+
+    /// Creates an InvocationArg that contains a `java.util.function.Predicate` which calls the closure `f`.
+    ///
+    /// See `from_fn`.
+    pub fn from_predicate<A, F>(f: F) -> errors::Result<InvocationArg>
+    where
+        A: DeserializeOwned + Any,
+        F: Fn(A) -> bool + Send + Sync + 'static,
+    {
+        functional_arg("predicate", move |jvm, arg| {
+            let arg = jvm.to_rust(arg)?;
+            InvocationArg::try_from(f(arg)).map(Some)
+        })
+    }
+
+    /// Creates an InvocationArg that contains a `java.lang.Runnable` which calls the closure `f`.
+    ///
+    /// See `from_fn`.
+    pub fn from_runnable<F>(f: F) -> errors::Result<InvocationArg>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        functional_arg("runnable", move |_, _| {
+            f();
+            Ok(None)
+        })
+    }
+}
+
+/// Creates a `RustFunctions.Callback` proxy that calls the `callback`
+/// and adapts it to a functional interface, using the factory method `adapter` of the `RustFunctions`.
+fn functional_arg<F>(adapter: &str, callback: F) -> errors::Result<InvocationArg>
+where
+    F: Fn(&Jvm, Instance) -> errors::Result<Option<InvocationArg>> + Send + Sync + 'static,
+{
+    let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+    let proxy = jvm.new_proxy(CLASS_RUST_FUNCTIONS_CALLBACK, move |jvm, _, mut args| {
+        let arg = args.pop().ok_or_else(|| {
+            J4RsError::GeneralError("The callback was called without an argument".to_string())
+        })?;
+        callback(jvm, arg)
+    })?;
+    let adapted = jvm.invoke_static(CLASS_RUST_FUNCTIONS, adapter, &[InvocationArg::from(proxy)])?;
+    Ok(InvocationArg::from(adapted))
+}
+
+#[cfg(test)]
+mod functional_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn closures_as_functional_interfaces() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let list = jvm.java_list("java.lang.Long", vec![1_i64, 2, 3, 4])?;
+        let stream = jvm.invoke(&list, "stream", InvocationArg::empty())?;
+        let stream = jvm.invoke(&stream, "filter", &[InvocationArg::from_predicate(|x: i64| x % 2 == 0)?])?;
+        let stream = jvm.invoke(&stream, "map", &[InvocationArg::from_fn(|x: i64| x * 10)?])?;
+        let collector = jvm.invoke_static("java.util.stream.Collectors", "toList", InvocationArg::empty())?;
+        let result = jvm.invoke(&stream, "collect", &[InvocationArg::from(collector)])?;
+        let result: Vec<i64> = jvm.to_rust(result)?;
+        assert_eq!(result, vec![20, 40]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let tx = std::sync::Mutex::new(tx);
+        let consumer = InvocationArg::from_consumer(move |s: String| tx.lock().unwrap().send(s).unwrap())?;
+        let optional = jvm.invoke_static("java.util.Optional", "of", &[InvocationArg::try_from("value")?])?;
+        jvm.invoke(&optional, "ifPresent", &[consumer])?;
+        assert_eq!(rx.try_recv().unwrap(), "value");
+
+        let empty = jvm.invoke_static("java.util.Optional", "empty", InvocationArg::empty())?;
+        let supplied = jvm.invoke(&empty, "orElseGet", &[InvocationArg::from_supplier(|| "supplied".to_string())?])?;
+        assert_eq!(jvm.to_rust::<String>(supplied)?, "supplied");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let tx = std::sync::Mutex::new(tx);
+        let runnable = InvocationArg::from_runnable(move || tx.lock().unwrap().send(()).unwrap())?;
+        let thread = jvm.create_instance("java.lang.Thread", &[runnable])?;
+        jvm.invoke(&thread, "run", InvocationArg::empty())?;
+        assert!(rx.try_recv().is_ok());
+
+        Ok(())
+    }
+}
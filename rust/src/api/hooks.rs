@@ -0,0 +1,193 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::{errors, InvocationArg, Jvm};
+
+/// A hook that is called before and after every `Jvm::create_instance`, `Jvm::invoke` and `Jvm::invoke_static`.
+///
+/// This allows tracing the calls to Java (e.g. with OpenTelemetry spans) without wrapping every call site.
+/// The invocations that are made by a hook itself do not call the hook again.
+pub trait InvocationHook: Send + Sync {
+    /// Called before the invocation
+    fn before(&self, _invocation: &InvocationInfo) {}
+
+    /// Called after the invocation, with the time it took and the error, if it failed
+    fn after(&self, _invocation: &InvocationInfo, _elapsed: Duration, _error: Option<&errors::J4RsError>) {}
+}
+
+/// The kind of an invocation that is passed to an `InvocationHook`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvocationKind {
+    /// A `Jvm::create_instance`
+    CreateInstance,
+    /// A `Jvm::invoke`
+    Invoke,
+    /// A `Jvm::invoke_static`
+    InvokeStatic,
+}
+
+/// Describes an invocation that is passed to an `InvocationHook`
+#[derive(Debug)]
+pub struct InvocationInfo<'a> {
+    kind: InvocationKind,
+    class_name: &'a str,
+    method_name: &'a str,
+    arg_class_names: Vec<&'a str>,
+}
+
+impl<'a> InvocationInfo<'a> {
+    /// Returns the kind of the invocation
+    pub fn kind(&self) -> InvocationKind {
+        self.kind
+    }
+
+    /// Returns the name of the class, as known to Rust.
+    ///
+    /// For `Jvm::invoke`, this is the class name of the `Instance`, which may be unknown.
+    pub fn class_name(&self) -> &str {
+        self.class_name
+    }
+
+    /// Returns the name of the invoked method, or `<init>` for `Jvm::create_instance`
+    pub fn method_name(&self) -> &str {
+        self.method_name
+    }
+
+    /// Returns the class names of the arguments
+    pub fn arg_class_names(&self) -> &[&'a str] {
+        &self.arg_class_names
+    }
+}
+
+lazy_static! {
+    // The hook that is called around the invocations
+    static ref HOOK: RwLock<Option<Arc<dyn InvocationHook>>> = RwLock::new(None);
+}
+
+thread_local! {
+    // Set while a hook is running, in order not to call hooks for the invocations of the hooks
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+impl Jvm {
+    /// Sets the hook that is called before and after every `create_instance`, `invoke` and `invoke_static`.
+    ///
+    /// The hook applies to all the `Jvm`s of the process and replaces any previously set hook.
+    pub fn set_invocation_hook(hook: Box<dyn InvocationHook>) {
+        *HOOK.write().unwrap() = Some(Arc::from(hook));
+    }
+
+    /// Removes the hook that was set with `set_invocation_hook`.
+    pub fn clear_invocation_hook() {
+        *HOOK.write().unwrap() = None;
+    }
+}
+
+/// Calls `f`, which performs an invocation, between the calls of the hook, if a hook is set.
+pub(crate) fn with_hook<T>(
+    kind: InvocationKind,
+    class_name: &str,
+    method_name: &str,
+    inv_args: &[impl Borrow<InvocationArg>],
+    f: impl FnOnce() -> errors::Result<T>,
+) -> errors::Result<T> {
+    let hook = match HOOK.read().unwrap().clone() {
+        Some(hook) if !IN_HOOK.with(Cell::get) => hook,
+        _ => return f(),
+    };
+    let info = InvocationInfo {
+        kind,
+        class_name,
+        method_name,
+        arg_class_names: inv_args.iter().map(|arg| arg.borrow().class_name()).collect(),
+    };
+    run_hook(|| hook.before(&info));
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    run_hook(|| hook.after(&info, elapsed, result.as_ref().err()));
+    result
+}
+
+fn run_hook(f: impl FnOnce()) {
+    IN_HOOK.with(|in_hook| in_hook.set(true));
+    f();
+    IN_HOOK.with(|in_hook| in_hook.set(false));
+}
+
+#[cfg(test)]
+mod hooks_unit_tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    // Records the invocations of a single thread, as the hook applies to the tests that run in parallel too
+    struct RecordingHook {
+        thread: std::thread::ThreadId,
+        records: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl InvocationHook for RecordingHook {
+        fn before(&self, invocation: &InvocationInfo) {
+            if std::thread::current().id() == self.thread {
+                // Invocations of hooks are not hooked
+                let jvm = Jvm::attach_thread_with_no_detach_on_drop().unwrap();
+                let _ = jvm.create_instance("java.lang.StringBuilder", InvocationArg::empty()).unwrap();
+                self.records.lock().unwrap().push(format!(
+                    "before {:?} {} {:?}",
+                    invocation.kind(),
+                    invocation.method_name(),
+                    invocation.arg_class_names()
+                ));
+            }
+        }
+
+        fn after(&self, invocation: &InvocationInfo, _elapsed: Duration, error: Option<&errors::J4RsError>) {
+            if std::thread::current().id() == self.thread {
+                self.records.lock().unwrap().push(format!("after {} {}", invocation.method_name(), error.is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn invocation_hooks() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let records = Arc::new(Mutex::new(Vec::new()));
+        Jvm::set_invocation_hook(Box::new(RecordingHook {
+            thread: std::thread::current().id(),
+            records: records.clone(),
+        }));
+        let builder = jvm.create_instance("java.lang.StringBuilder", &[InvocationArg::try_from("abc")?]);
+        let reversed = jvm.invoke(&builder?, "reverse", InvocationArg::empty());
+        Jvm::clear_invocation_hook();
+        reversed?;
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            vec![
+                "before CreateInstance <init> [\"java.lang.String\"]",
+                "after <init> false",
+                "before Invoke reverse []",
+                "after reverse false",
+            ]
+        );
+        Ok(())
+    }
+}
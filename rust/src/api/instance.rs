@@ -12,14 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::api::cleanup;
+use crate::api::metrics;
+use crate::api::tracking;
 use crate::logger::debug;
 use crate::{cache, errors, jni_utils, InvocationArg, Jvm};
 use jni_sys::jobject;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use futures::task::AtomicWaker;
+use futures::Stream;
 use std::any::Any;
+use std::borrow::Borrow;
 use std::convert::TryFrom;
-use std::sync::mpsc::{Receiver, Sender};
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::thread;
 
 /// A Java instance
 /// Instances contain global Java references and can be sent to other threads
@@ -40,6 +51,7 @@ impl Instance {
     /// Creates a new Instance, leaving the passed jobject as is.
     /// In most cases, the jobject is already transformed to a global reference.
     pub(crate) fn new(obj: jobject, classname: &str) -> errors::Result<Instance> {
+        tracking::register(obj, classname);
         Ok(Instance {
             jinstance: obj,
             class_name: classname.to_string(),
@@ -52,12 +64,54 @@ impl Instance {
         self.class_name.as_ref()
     }
 
+    /// Returns true if the Java object of this Instance is `null`
+    pub fn is_null(&self, jvm: &Jvm) -> errors::Result<bool> {
+        jvm.is_java_null(self.jinstance)
+    }
+
+    /// Returns the JNI global reference of this Instance, without consuming it.
+    ///
+    /// The reference is owned by the Instance: it is valid for as long as the Instance is alive
+    /// and it must not be deleted by the caller. It references a Java `org.astonbitecode.j4rs.api.Instance`,
+    /// which wraps the actual Java object. `Jvm::instance_into_raw_object` returns the wrapped object instead.
+    pub fn raw_jobject(&self) -> jobject {
+        self.jinstance
+    }
+
+    /// Creates an Instance that takes the ownership of a JNI global reference,
+    /// like the ones returned by `java_object`. The reference is deleted when the Instance is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The `obj` must be a valid global reference to a Java `org.astonbitecode.j4rs.api.Instance`,
+    /// which is not owned by anything else.
+    pub unsafe fn from_raw_global(obj: jobject) -> errors::Result<Instance> {
+        Instance::new(obj, cache::UNKNOWN_FOR_RUST)
+    }
+
     /// Consumes the Instance and returns its jobject
     pub fn java_object(mut self) -> jobject {
+        tracking::deregister(self.jinstance);
         self.skip_deleting_jobject = true;
         self.jinstance
     }
 
+    /// Consumes the Instance and releases its Java reference immediately.
+    ///
+    /// This is what happens when an Instance is dropped as well; calling `close` makes the release explicit,
+    /// without relying on the scope of the Instance.
+    pub fn close(self) {
+        debug(&format!("Closing an instance of {}", self.class_name));
+    }
+
+    /// Consumes the Instance without releasing its Java reference.
+    ///
+    /// The referenced Java object will never be garbage collected. This is useful for objects that should
+    /// live as long as the program runs. A forgotten Instance is not counted by `Jvm::instance_stats`.
+    pub fn forget(self) {
+        let _ = self.java_object();
+    }
+
     #[deprecated(
         since = "0.12.0",
         note = "Please use Instance::from_jobject or Instance::from_jobject_with_global_ref instead"
@@ -67,21 +121,13 @@ impl Instance {
 
         let global =
             jni_utils::create_global_ref_from_local_ref(obj, cache::get_thread_local_env()?)?;
-        Ok(Instance {
-            jinstance: global,
-            class_name: cache::UNKNOWN_FOR_RUST.to_string(),
-            skip_deleting_jobject: false,
-        })
+        Instance::new(global, cache::UNKNOWN_FOR_RUST)
     }
 
     pub fn from_jobject(obj: jobject) -> errors::Result<Instance> {
         let _jvm = cache::get_thread_local_env().map_err(|_| Jvm::attach_thread());
 
-        Ok(Instance {
-            jinstance: obj,
-            class_name: cache::UNKNOWN_FOR_RUST.to_string(),
-            skip_deleting_jobject: false,
-        })
+        Instance::new(obj, cache::UNKNOWN_FOR_RUST)
     }
 
     pub fn from_jobject_with_global_ref(obj: jobject) -> errors::Result<Instance> {
@@ -89,11 +135,7 @@ impl Instance {
 
         let global =
             jni_utils::create_global_ref_from_local_ref(obj, cache::get_thread_local_env()?)?;
-        Ok(Instance {
-            jinstance: global,
-            class_name: cache::UNKNOWN_FOR_RUST.to_string(),
-            skip_deleting_jobject: false,
-        })
+        Instance::new(global, cache::UNKNOWN_FOR_RUST)
     }
 
     /// Creates a weak reference of this Instance.
@@ -128,8 +170,17 @@ impl Drop for Instance {
     fn drop(&mut self) {
         debug(&format!("Dropping an instance of {}", self.class_name));
         if !self.skip_deleting_jobject {
-            if let Some(j_env) = cache::get_thread_local_env_opt() {
-                jni_utils::delete_java_ref(j_env, self.jinstance);
+            tracking::deregister(self.jinstance);
+            if cache::is_jvm_destroyed() {
+                return;
+            }
+            match cache::get_thread_local_env_opt() {
+                Some(j_env) => {
+                    jni_utils::delete_java_ref(j_env, self.jinstance);
+                    cleanup::delete_pending(j_env);
+                }
+                // The Jvm of the thread is dropped, or the thread was never attached
+                None => cleanup::defer_deletion(self.jinstance),
             }
         }
     }
@@ -138,40 +189,340 @@ impl Drop for Instance {
 /// Instances contain global Java references and can be sent to other threads
 unsafe impl Send for Instance {}
 
+/// An Instance that can be used as a key of Rust `HashMap`s and `HashSet`s, using the identity of its Java object.
+///
+/// Two keys are equal if their Instances refer to the very same Java object (see `Jvm::same_object`).
+/// This is useful for deduplicating Java objects that are held by Rust.
+pub struct InstanceKey {
+    instance: Instance,
+    identity_hash: i32,
+}
+
+impl InstanceKey {
+    /// Creates a new key for the `instance`.
+    pub fn new(jvm: &Jvm, instance: Instance) -> errors::Result<InstanceKey> {
+        let identity_hash = jvm.identity_hash_code(&instance)?;
+        Ok(InstanceKey { instance, identity_hash })
+    }
+
+    /// Returns the Instance of this key
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    /// Consumes the key and returns its Instance
+    pub fn into_instance(self) -> Instance {
+        self.instance
+    }
+}
+
+impl PartialEq for InstanceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity_hash == other.identity_hash
+            && Jvm::attach_thread_with_no_detach_on_drop()
+                .and_then(|jvm| jvm.same_object(&self.instance, &other.instance))
+                .unwrap_or(false)
+    }
+}
+
+impl Eq for InstanceKey {}
+
+impl Hash for InstanceKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity_hash.hash(state);
+    }
+}
+
+/// The sending side, the receiving side and the waker of a channel for Instances.
+pub(crate) type InstanceChannel = (InstanceSender, Receiver<Instance>, Arc<AtomicWaker>);
+
+/// Creates a channel for Instances that are sent by Java callbacks.
+pub(crate) fn instance_channel() -> InstanceChannel {
+    let (tx, rx) = channel();
+    new_instance_channel(ChannelSender::Unbounded(tx), rx)
+}
+
+/// Creates a channel for Instances that are sent by Java callbacks, which holds up to `capacity` Instances.
+pub(crate) fn bounded_instance_channel(
+    capacity: usize,
+    overflow: ChannelOverflow,
+) -> InstanceChannel {
+    let (tx, rx) = sync_channel(capacity);
+    new_instance_channel(ChannelSender::Bounded(tx, overflow), rx)
+}
+
+fn new_instance_channel(
+    tx: ChannelSender,
+    rx: Receiver<Instance>,
+) -> InstanceChannel {
+    let waker = Arc::new(AtomicWaker::new());
+    let sender = InstanceSender {
+        tx: Arc::new(Mutex::new(Some(tx))),
+        waker: waker.clone(),
+        record_sent: metrics::callback_instance_sent,
+    };
+    (sender, rx, waker)
+}
+
+/// Defines what happens when Java sends an Instance to a bounded channel that is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelOverflow {
+    /// The Java thread that sends the Instance blocks until there is space in the channel, or the channel is closed.
+    Block,
+    /// The Instance is dropped.
+    Drop,
+}
+
+/// The result of sending an Instance via an `InstanceSender`.
+///
+/// The values are the ones that `NativeCallbackToRustChannelSupport` expects from the native callbacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SendStatus {
+    /// The Instance was sent, or dropped because the channel was full.
+    Sent = 0,
+    /// The channel is closed. Java should stop sending.
+    Closed = 1,
+    /// The channel is full and Java should retry.
+    Full = 2,
+}
+
+enum ChannelSender {
+    Unbounded(Sender<Instance>),
+    Bounded(SyncSender<Instance>, ChannelOverflow),
+}
+
+/// The sending side of the channel of an `InstanceReceiver`.
+///
+/// Apart from sending, it wakes up any task that asynchronously waits for Instances.
+/// It is owned by the Java `NativeCallbackToRustChannelSupport` that sends to the channel,
+/// which releases it when the channel is closed, or when it is garbage collected.
+pub(crate) struct InstanceSender {
+    // None if the channel is closed
+    tx: Arc<Mutex<Option<ChannelSender>>>,
+    waker: Arc<AtomicWaker>,
+    // Records the sent Instances in the metrics. Java calls the sender via the native library that it loaded,
+    // which may be a different copy of j4rs, so the function of the copy that created the channel is kept.
+    record_sent: fn(),
+}
+
+impl InstanceSender {
+    pub(crate) fn send(&self, instance: Instance) -> SendStatus {
+        let status = match self.tx.lock().unwrap().as_ref() {
+            Some(ChannelSender::Unbounded(tx)) => match tx.send(instance) {
+                Ok(_) => SendStatus::Sent,
+                Err(_) => SendStatus::Closed,
+            },
+            Some(ChannelSender::Bounded(tx, overflow)) => match tx.try_send(instance) {
+                Ok(_) => SendStatus::Sent,
+                Err(TrySendError::Full(_)) if *overflow == ChannelOverflow::Drop => {
+                    debug("Dropping an Instance because the callback channel is full");
+                    SendStatus::Sent
+                }
+                Err(TrySendError::Full(_)) => SendStatus::Full,
+                Err(TrySendError::Disconnected(_)) => SendStatus::Closed,
+            },
+            None => SendStatus::Closed,
+        };
+        if status == SendStatus::Sent {
+            (self.record_sent)();
+            self.waker.wake();
+        }
+        status
+    }
+
+    /// Returns a `ChannelCloser` that can close the channel, even after this `InstanceSender` is released.
+    ///
+    /// The `java_sender` is the Java `NativeCallbackToRustChannelSupport` that sends to the channel.
+    pub(crate) fn closer(&self, java_sender: Option<Instance>) -> ChannelCloser {
+        ChannelCloser {
+            tx: self.tx.clone(),
+            waker: self.waker.clone(),
+            java_sender: Arc::new(Mutex::new(java_sender)),
+        }
+    }
+}
+
+/// Closes the channel of an `InstanceSender`.
+#[derive(Clone)]
+pub(crate) struct ChannelCloser {
+    tx: Arc<Mutex<Option<ChannelSender>>>,
+    waker: Arc<AtomicWaker>,
+    // Told once that the channel is closed
+    java_sender: Arc<Mutex<Option<Instance>>>,
+}
+
+impl ChannelCloser {
+    /// Drops the sending side of the channel, so that the receiver gets disconnected
+    /// once it has received the Instances that are already sent.
+    ///
+    /// With a `jvm`, Java is told that the channel is closed and releases the `InstanceSender` immediately.
+    /// Otherwise, Java finds out with its next callback.
+    fn close(&self, jvm: Option<&Jvm>) {
+        self.tx.lock().unwrap().take();
+        self.waker.wake();
+        if let Some(jvm) = jvm {
+            if let Some(java_sender) = self.java_sender.lock().unwrap().take() {
+                if let Err(error) = jvm.close_callback_channel(&java_sender) {
+                    debug(&format!("Could not close the callback channel in Java: {}", error));
+                }
+            }
+        }
+    }
+}
+
+// The Jvm of the current thread, if the thread is already attached
+fn current_jvm() -> Option<Jvm> {
+    if cache::is_jvm_destroyed() || cache::get_thread_local_env().is_err() {
+        None
+    } else {
+        Jvm::attach_thread_with_no_detach_on_drop().ok()
+    }
+}
+
 /// A receiver for Java Instances.
 ///
-/// It keeps a channel Receiver to get callback Instances from the Java world
-/// and the address of a `Box<Sender<Instance>>` Box in the heap. This Box is used by Java to communicate
-/// asynchronously Instances to Rust.
+/// It keeps a channel Receiver to get callback Instances from the Java world.
+/// The sending side of the channel is owned by the Java object that sends the Instances.
 ///
-/// On Drop, the InstanceReceiver removes the Box from the heap.
+/// On Drop, the channel is closed.
 pub struct InstanceReceiver {
     pub(crate) rx: Box<Receiver<Instance>>,
-    tx_address: u64,
+    closer: Option<ChannelCloser>,
+    waker: Arc<AtomicWaker>,
 }
 
 impl InstanceReceiver {
-    pub(crate) fn new(rx: Receiver<Instance>, tx_address: u64) -> InstanceReceiver {
+    pub(crate) fn new(rx: Receiver<Instance>, closer: Option<ChannelCloser>, waker: Arc<AtomicWaker>) -> InstanceReceiver {
         InstanceReceiver {
             rx: Box::new(rx),
-            tx_address,
+            closer,
+            waker,
         }
     }
 
     pub fn rx(&self) -> &Receiver<Instance> {
         &self.rx
     }
+
+    /// Closes the channel, signaling Java to stop sending Instances.
+    ///
+    /// The Instances that are already sent can still be received. After that, the `Receiver` is disconnected.
+    /// `NativeCallbackToRustChannelSupport::isChannelClosed` returns true in Java from now on, so that producers can stop,
+    /// and any further Instances that Java sends are dropped.
+    pub fn close(&self) {
+        if let Some(closer) = &self.closer {
+            debug("Closing an InstanceReceiver");
+            closer.close(Jvm::attach_thread_with_no_detach_on_drop().ok().as_ref());
+        }
+    }
+
+    /// Transforms this `InstanceReceiver` to an `AsyncInstanceReceiver`,
+    /// which is a `Stream` of the Instances that are sent by Java.
+    ///
+    /// The `Stream` does not need a dedicated thread; the Java callbacks wake up the task that polls it.
+    pub fn into_async(self) -> AsyncInstanceReceiver {
+        AsyncInstanceReceiver { instance_receiver: self }
+    }
+
+    /// Transforms this `InstanceReceiver` to a `TypedInstanceReceiver`,
+    /// which receives the Instances that are sent by Java, transformed to Rust values of type `T`.
+    ///
+    /// The transformation happens in a dedicated thread, so the consumer gets ready Rust values.
+    pub fn into_typed<T>(self) -> errors::Result<TypedInstanceReceiver<T>>
+    where
+        T: DeserializeOwned + Any + Send + 'static,
+    {
+        let closer = self.closer.clone();
+        let (tx, rx) = channel();
+        thread::Builder::new()
+            .name("j4rs-typed-channel".to_string())
+            .spawn(move || {
+                let jvm = match Jvm::attach_thread_as_daemon() {
+                    Ok(jvm) => jvm,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                };
+                for instance in self.rx() {
+                    if tx.send(jvm.to_rust::<T>(instance)).is_err() {
+                        // The TypedInstanceReceiver is dropped
+                        break;
+                    }
+                }
+            })?;
+        Ok(TypedInstanceReceiver { rx, closer })
+    }
 }
 
 impl Drop for InstanceReceiver {
     fn drop(&mut self) {
-        if self.tx_address > 0 {
+        if let Some(closer) = &self.closer {
             debug("Dropping an InstanceReceiver");
-            let p = self.tx_address as *mut Sender<Instance>;
-            unsafe {
-                let tx = Box::from_raw(p);
-                drop(tx);
-            }
+            // Threads that are not attached are not attached just for this
+            closer.close(current_jvm().as_ref());
+        }
+    }
+}
+
+/// An asynchronous receiver for Java Instances. It is created by `InstanceReceiver::into_async`.
+///
+/// It is a `Stream` of the Instances that are sent by Java callbacks.
+pub struct AsyncInstanceReceiver {
+    instance_receiver: InstanceReceiver,
+}
+
+impl AsyncInstanceReceiver {
+    fn try_next(&self) -> Option<Poll<Option<errors::Result<Instance>>>> {
+        match self.instance_receiver.rx.try_recv() {
+            Ok(instance) => Some(Poll::Ready(Some(Ok(instance)))),
+            Err(TryRecvError::Disconnected) => Some(Poll::Ready(None)),
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+}
+
+impl Stream for AsyncInstanceReceiver {
+    type Item = errors::Result<Instance>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(poll) = self.try_next() {
+            return poll;
+        }
+        self.instance_receiver.waker.register(cx.waker());
+        // Check again, in case an Instance was sent before registering the waker
+        self.try_next().unwrap_or(Poll::Pending)
+    }
+}
+
+/// A receiver for Rust values that are transformed from Java Instances. It is created by `InstanceReceiver::into_typed`.
+///
+/// If the transformation of an Instance fails, the error is received in the place of the value.
+///
+/// On Drop, the channel is closed.
+pub struct TypedInstanceReceiver<T> {
+    rx: Receiver<errors::Result<T>>,
+    closer: Option<ChannelCloser>,
+}
+
+impl<T> TypedInstanceReceiver<T> {
+    pub fn rx(&self) -> &Receiver<errors::Result<T>> {
+        &self.rx
+    }
+
+    /// Closes the channel, signaling Java to stop sending Instances. See `InstanceReceiver::close`.
+    pub fn close(&self) {
+        if let Some(closer) = &self.closer {
+            debug("Closing a TypedInstanceReceiver");
+            closer.close(Jvm::attach_thread_with_no_detach_on_drop().ok().as_ref());
+        }
+    }
+}
+
+impl<T> Drop for TypedInstanceReceiver<T> {
+    fn drop(&mut self) {
+        if let Some(closer) = &self.closer {
+            closer.close(current_jvm().as_ref());
         }
     }
 }
@@ -230,6 +581,36 @@ impl<'a> ChainableInstance<'a> {
         Ok(ChainableInstance::new(instance, self.jvm))
     }
 
+    /// Sets the value of the field `field_name` of the `Instance` and continues the chain with the same `Instance`.
+    pub fn set_field(self, field_name: &str, value: impl Borrow<InvocationArg>) -> errors::Result<ChainableInstance<'a>> {
+        self.jvm.set_field(&self.instance, field_name, value)?;
+        Ok(self)
+    }
+
+    /// Casts the `Instance` to the class `to_class`, after checking that the Java object is an instance of it.
+    ///
+    /// Unlike `cast`, a `null` Java object is not casted, but results in an error.
+    pub fn cast_checked(&self, to_class: &str) -> errors::Result<ChainableInstance<'a>> {
+        if !self.jvm.is_instance_of(&self.instance, to_class)? {
+            return Err(errors::J4RsError::GeneralError(format!(
+                "Cannot cast to {}: the object is not an instance of it",
+                to_class
+            )));
+        }
+        let instance = self.jvm.cast(&self.instance, to_class)?;
+        Ok(ChainableInstance::new(instance, self.jvm))
+    }
+
+    /// Invokes the method `method_name` of the `Instance` asynchronously. See `Jvm::invoke_async`.
+    pub async fn invoke_async(
+        &self,
+        method_name: &str,
+        inv_args: &[InvocationArg],
+    ) -> errors::Result<ChainableInstance<'a>> {
+        let instance = self.jvm.invoke_async(&self.instance, method_name, inv_args).await?;
+        Ok(ChainableInstance::new(instance, self.jvm))
+    }
+
     /// Returns the Rust representation of the provided instance
     pub fn to_rust<T: Any>(self) -> errors::Result<T>
     where
@@ -238,6 +619,18 @@ impl<'a> ChainableInstance<'a> {
         self.jvm.to_rust(self.instance)
     }
 
+    /// Returns the Rust representation of the provided instance, or `None` if the Java object is `null`
+    pub fn to_rust_opt<T>(&self) -> errors::Result<Option<T>>
+    where
+        T: DeserializeOwned + Any,
+    {
+        if self.instance.is_null(self.jvm)? {
+            Ok(None)
+        } else {
+            self.jvm.jobject_to_rust_boxed(self.instance.jinstance).map(|v| Some(*v))
+        }
+    }
+
     /// Returns the Rust representation of the provided instance, boxed
     pub fn to_rust_boxed<T: Any>(self) -> errors::Result<Box<T>>
     where
@@ -249,6 +642,7 @@ impl<'a> ChainableInstance<'a> {
 
 #[cfg(test)]
 mod instance_unit_tests {
+    use super::{bounded_instance_channel, instance_channel, SendStatus};
     use crate::*;
     use crate::lib_unit_tests::create_tests_jvm;
 
@@ -269,6 +663,139 @@ mod instance_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn chain_fields_casts_and_options() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+        let i: i32 = jvm
+            .into_chain(test_instance)
+            .set_field("publicInteger", InvocationArg::try_from(5)?)?
+            .field("publicInteger")?
+            .to_rust()?;
+        assert_eq!(i, 5);
+
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+        let chain = jvm.chain(&test_instance)?;
+        let null_integer: Option<i32> = chain.invoke("getNullInteger", InvocationArg::empty())?.to_rust_opt()?;
+        assert_eq!(null_integer, None);
+        let integer: Option<i32> = chain.field("publicInteger")?.to_rust_opt()?;
+        assert_eq!(integer, Some(0));
+
+        let size: i32 = jvm
+            .into_chain(jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?)
+            .cast_checked("java.util.List")?
+            .invoke("size", InvocationArg::empty())?
+            .to_rust()?;
+        assert_eq!(size, 0);
+        assert!(chain.cast_checked("java.util.List").is_err());
+        assert!(chain.invoke("getNullInteger", InvocationArg::empty())?.cast_checked("java.lang.Integer").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn chain_static_fields() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let max: i32 = jvm.field_static("java.lang.Integer", "MAX_VALUE")?.to_rust()?;
+        assert_eq!(max, i32::MAX);
+        let language: String = jvm
+            .field_static("java.util.Locale", "ROOT")?
+            .invoke("getLanguage", InvocationArg::empty())?
+            .to_rust()?;
+        assert_eq!(language, "");
+        assert!(jvm.field_static("java.lang.Integer", "NOT_A_FIELD").is_err());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn chain_invoke_async() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+        let length: i32 = jvm
+            .chain(&test_instance)?
+            .invoke_async("getStringWithFuture", &[InvocationArg::try_from("j4rs")?])
+            .await?
+            .invoke("length", InvocationArg::empty())?
+            .to_rust()?;
+        assert_eq!(length, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn raw_handles() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?])?;
+        assert_eq!(instance.raw_jobject(), instance.jinstance);
+        let raw = instance.java_object();
+        let instance = unsafe { Instance::from_raw_global(raw)? };
+        let upper: String = jvm.to_rust(jvm.invoke(&instance, "toUpperCase", InvocationArg::empty())?)?;
+        assert_eq!(upper, "A STRING");
+        assert_eq!(jvm.raw_env(), jvm.jni_env);
+        Ok(())
+    }
+
+    #[test]
+    fn bounded_channels() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let new_string = || jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?]);
+
+        let (sender, rx, waker) = bounded_instance_channel(1, ChannelOverflow::Block);
+        let receiver = InstanceReceiver::new(rx, Some(sender.closer(None)), waker);
+        assert_eq!(sender.send(new_string()?), SendStatus::Sent);
+        assert_eq!(sender.send(new_string()?), SendStatus::Full);
+        assert!(receiver.rx().try_recv().is_ok());
+        assert_eq!(sender.send(new_string()?), SendStatus::Sent);
+        receiver.close();
+        assert_eq!(sender.send(new_string()?), SendStatus::Closed);
+        assert!(receiver.rx().recv().is_ok());
+        assert!(receiver.rx().recv().is_err());
+
+        let (sender, rx, _) = bounded_instance_channel(1, ChannelOverflow::Drop);
+        assert_eq!(sender.send(new_string()?), SendStatus::Sent);
+        assert_eq!(sender.send(new_string()?), SendStatus::Sent);
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn typed_channels() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let (sender, rx, waker) = instance_channel();
+        let typed_receiver = InstanceReceiver::new(rx, Some(sender.closer(None)), waker).into_typed::<String>()?;
+
+        sender.send(jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?])?);
+        sender.send(jvm.create_instance("java.lang.Object", InvocationArg::empty())?);
+        assert_eq!(typed_receiver.rx().recv()?.unwrap(), "a string");
+        assert!(typed_receiver.rx().recv()?.is_err());
+
+        typed_receiver.close();
+        assert!(typed_receiver.rx().recv().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn closing_channels_is_pushed_to_java() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let is_channel_closed = |instance: &Instance| -> errors::Result<bool> {
+            jvm.to_rust(jvm.invoke(instance, "isChannelClosed", InvocationArg::empty())?)
+        };
+
+        let instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MySecondTest", InvocationArg::empty())?;
+        let receiver = jvm.init_callback_channel(&instance)?;
+        assert!(!is_channel_closed(&instance)?);
+        receiver.close();
+        assert!(is_channel_closed(&instance)?);
+        // Callbacks to a closed channel are dropped
+        jvm.invoke(&instance, "performTenCallbacks", InvocationArg::empty())?;
+        assert!(receiver.rx().recv().is_err());
+
+        let instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MySecondTest", InvocationArg::empty())?;
+        let receiver = jvm.init_bounded_callback_channel(&instance, 1, ChannelOverflow::Block)?;
+        drop(receiver);
+        assert!(is_channel_closed(&instance)?);
+        Ok(())
+    }
+
     #[test]
     fn try_from_jobject() -> errors::Result<()> {
         let c = std::ptr::null_mut();
@@ -13,16 +13,24 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::ptr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use jni_sys::{jobject, JNIEnv};
 use serde::Serialize;
 
+#[cfg(feature = "cbor")]
+use crate::api::codec;
 use crate::api::instance::Instance;
-use crate::api::{JavaClass, Jvm, Null};
+use crate::api::{JavaClass, Jvm, Null, CLASS_DURATION, CLASS_INSTANT, PRIMITIVE_BYTE_ARRAY};
 use crate::{cache, errors, jni_utils, utils};
 
+const CLASS_OBJECT: &str = "java.lang.Object";
+const CLASS_OPTIONAL: &str = "java.util.Optional";
+const CLASS_MAP: &str = "java.util.Map";
+
 /// Struct that carries an argument that is used for method invocations in Java.
 #[derive(Serialize)]
 pub enum InvocationArg {
@@ -150,6 +158,10 @@ impl InvocationArg {
                 serialized: false,
             })
         } else {
+            #[cfg(feature = "cbor")]
+            if codec::is_passed_as_cbor(class_name) {
+                return codec::cbor_invocation_arg(arg, class_name);
+            }
             let json = serde_json::to_string(arg)?;
             Ok(InvocationArg::Rust {
                 json,
@@ -159,6 +171,33 @@ impl InvocationArg {
         }
     }
 
+    /// Creates an InvocationArg of a `java.math.BigDecimal` or `java.math.BigInteger` from its `String` representation.
+    ///
+    /// The number is passed as a JSON String and is parsed by the Java constructor of the big number class.
+    pub(crate) fn new_big_number(number: &str, java_class: JavaClass) -> errors::Result<InvocationArg> {
+        Ok(InvocationArg::Rust {
+            json: serde_json::to_string(number)?,
+            class_name: java_class.get_class_str().to_string(),
+            serialized: true,
+        })
+    }
+
+    // Creates an InvocationArg by calling a static factory method of a java.time class,
+    // that accepts seconds and a nanosecond adjustment
+    fn new_java_time(class_name: &str, method_name: &str, seconds: i64, nanos: i64) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        let mut instance = jvm.invoke_static(
+            class_name,
+            method_name,
+            &[
+                InvocationArg::try_from(seconds)?.into_primitive()?,
+                InvocationArg::try_from(nanos)?.into_primitive()?,
+            ],
+        )?;
+        instance.class_name = class_name.to_string();
+        Ok(InvocationArg::from(instance))
+    }
+
     fn make_primitive(&mut self) -> errors::Result<()> {
         match utils::primitive_of(self) {
             Some(primitive_repr) => {
@@ -257,6 +296,28 @@ impl InvocationArg {
         }
     }
 
+    /// Creates an InvocationArg of the class `class_name` out of a JSON `value`, without defining a Rust type for it.
+    ///
+    /// The value is always passed to Java as JSON and is deserialized to an instance of `class_name`.
+    pub fn from_json(value: serde_json::Value, class_name: &str) -> errors::Result<InvocationArg> {
+        Ok(InvocationArg::Rust {
+            json: serde_json::to_string(&value)?,
+            class_name: class_name.to_string(),
+            serialized: true,
+        })
+    }
+
+    /// Creates an InvocationArg that contains the value of `arg`, or `null` of the class `class_name` if `arg` is `None`.
+    pub fn new_nullable<T>(arg: Option<T>, class_name: &str) -> errors::Result<InvocationArg>
+        where
+            InvocationArg: TryFrom<T, Error = errors::J4RsError>,
+    {
+        match arg {
+            Some(value) => InvocationArg::try_from(value),
+            None => InvocationArg::create_null(Null::Of(class_name)),
+        }
+    }
+
     /// Creates an InvocationArg that contains null
     pub fn create_null(null: Null) -> errors::Result<InvocationArg> {
         let class_name: &str = match null {
@@ -281,6 +342,115 @@ impl InvocationArg {
     }
 }
 
+/// Creates an InvocationArg that contains a `java.util.Optional`: `Optional.empty()` for `None`
+/// and `Optional.of` the value for `Some`.
+///
+/// `InvocationArg::new_nullable` can be used instead, for passing `null` for `None`.
+impl<T> TryFrom<Option<T>> for InvocationArg
+    where
+        InvocationArg: TryFrom<T, Error = errors::J4RsError>,
+{
+    type Error = errors::J4RsError;
+    fn try_from(arg: Option<T>) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        let optional = match arg {
+            Some(value) => jvm.invoke_static(CLASS_OPTIONAL, "of", &[InvocationArg::try_from(value)?])?,
+            None => jvm.invoke_static(CLASS_OPTIONAL, "empty", InvocationArg::empty())?,
+        };
+        Ok(InvocationArg::from(optional))
+    }
+}
+
+/// Creates an InvocationArg out of a JSON value, choosing the Java class by the kind of the value:
+///
+/// * `null` is passed as a `null` `java.lang.Object`
+/// * booleans and strings as `java.lang.Boolean` and `java.lang.String`
+/// * numbers as `java.lang.Integer`, `java.lang.Long`, `java.math.BigInteger` or `java.lang.Double`, whichever fits
+/// * arrays as `java.util.List`s and objects as `java.util.Map`s
+///
+/// `InvocationArg::from_json` can be used for passing the value as an instance of a specific class.
+impl TryFrom<serde_json::Value> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(value: serde_json::Value) -> errors::Result<InvocationArg> {
+        match value {
+            serde_json::Value::Null => InvocationArg::create_null(Null::Of(CLASS_OBJECT)),
+            serde_json::Value::Bool(b) => InvocationArg::try_from(b),
+            serde_json::Value::String(s) => InvocationArg::try_from(s),
+            serde_json::Value::Number(ref n) => match (n.as_i64(), n.as_u64()) {
+                (Some(i), _) => match i32::try_from(i) {
+                    Ok(i) => InvocationArg::try_from(i),
+                    Err(_) => InvocationArg::try_from(i),
+                },
+                (None, Some(_)) => InvocationArg::from_json(value, JavaClass::BigInteger.into()),
+                (None, None) => InvocationArg::from_json(value, JavaClass::Double.into()),
+            },
+            serde_json::Value::Array(_) => InvocationArg::from_json(value, JavaClass::List.into()),
+            serde_json::Value::Object(_) => InvocationArg::from_json(value, CLASS_MAP),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a serde_json::Value> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(value: &'a serde_json::Value) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(value.clone())
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.util.Map` with the entries of a `HashMap`.
+///
+/// The keys and values are transferred to Java as JSON. See `Jvm::java_typed_map` for creating Maps of specific classes.
+impl<K, V, S> TryFrom<HashMap<K, V, S>> for InvocationArg
+    where
+        K: Serialize,
+        V: Serialize,
+{
+    type Error = errors::J4RsError;
+    fn try_from(map: HashMap<K, V, S>) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        Ok(InvocationArg::from(jvm.java_typed_map(CLASS_OBJECT, CLASS_OBJECT, map)?))
+    }
+}
+
+impl<'a, K, V, S> TryFrom<&'a HashMap<K, V, S>> for InvocationArg
+    where
+        K: Serialize,
+        V: Serialize,
+{
+    type Error = errors::J4RsError;
+    fn try_from(map: &'a HashMap<K, V, S>) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        Ok(InvocationArg::from(jvm.java_typed_map(CLASS_OBJECT, CLASS_OBJECT, map)?))
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.util.Map` with the entries of a `BTreeMap`, in the same order.
+///
+/// The keys and values are transferred to Java as JSON. See `Jvm::java_typed_map` for creating Maps of specific classes.
+impl<K, V> TryFrom<BTreeMap<K, V>> for InvocationArg
+    where
+        K: Serialize,
+        V: Serialize,
+{
+    type Error = errors::J4RsError;
+    fn try_from(map: BTreeMap<K, V>) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        Ok(InvocationArg::from(jvm.java_typed_map(CLASS_OBJECT, CLASS_OBJECT, map)?))
+    }
+}
+
+impl<'a, K, V> TryFrom<&'a BTreeMap<K, V>> for InvocationArg
+    where
+        K: Serialize,
+        V: Serialize,
+{
+    type Error = errors::J4RsError;
+    fn try_from(map: &'a BTreeMap<K, V>) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        Ok(InvocationArg::from(jvm.java_typed_map(CLASS_OBJECT, CLASS_OBJECT, map)?))
+    }
+}
+
 impl From<Instance> for InvocationArg {
     fn from(instance: Instance) -> InvocationArg {
         let class_name = instance.class_name.to_owned();
@@ -402,6 +572,25 @@ impl<'a> TryFrom<&'a [i8]> for InvocationArg {
     }
 }
 
+/// Creates an InvocationArg that contains a Java `byte[]`.
+///
+/// The bytes are copied to the Java array in one go, without being converted one by one.
+/// Values greater than 127 are seen by Java as the respective negative `byte`s.
+impl<'a> TryFrom<&'a [u8]> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(bytes: &'a [u8]) -> errors::Result<InvocationArg> {
+        let jni_env = cache::get_thread_local_env()?;
+        Ok(InvocationArg::RustBasic {
+            instance: Instance::new(
+                jni_utils::global_jobject_from_u8_slice(bytes, jni_env)?,
+                PRIMITIVE_BYTE_ARRAY,
+            )?,
+            class_name: PRIMITIVE_BYTE_ARRAY.to_string(),
+            serialized: false,
+        })
+    }
+}
+
 impl TryFrom<char> for InvocationArg {
     type Error = errors::J4RsError;
     fn try_from(arg: char) -> errors::Result<InvocationArg> {
@@ -663,6 +852,240 @@ impl<'a> TryFrom<&'a f64> for InvocationArg {
     }
 }
 
+impl TryFrom<u8> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u8) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(i16::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a u8> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u8) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl TryFrom<u32> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u32) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(i64::from(arg))
+    }
+}
+
+impl<'a> TryFrom<&'a u32> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u32) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl TryFrom<u64> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u64) -> errors::Result<InvocationArg> {
+        let long = i64::try_from(arg).map_err(|_| {
+            errors::J4RsError::ParseError(format!("{} is too big to fit in a java.lang.Long", arg))
+        })?;
+        InvocationArg::try_from(long)
+    }
+}
+
+impl<'a> TryFrom<&'a u64> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u64) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl TryFrom<usize> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: usize) -> errors::Result<InvocationArg> {
+        let long = i64::try_from(arg).map_err(|_| {
+            errors::J4RsError::ParseError(format!("{} is too big to fit in a java.lang.Long", arg))
+        })?;
+        InvocationArg::try_from(long)
+    }
+}
+
+impl<'a> TryFrom<&'a usize> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a usize) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl TryFrom<i128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: i128) -> errors::Result<InvocationArg> {
+        InvocationArg::new_big_number(&arg.to_string(), JavaClass::BigInteger)
+    }
+}
+
+impl<'a> TryFrom<&'a i128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a i128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+impl TryFrom<u128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: u128) -> errors::Result<InvocationArg> {
+        InvocationArg::new_big_number(&arg.to_string(), JavaClass::BigInteger)
+    }
+}
+
+impl<'a> TryFrom<&'a u128> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a u128) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(*arg)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<rust_decimal::Decimal> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: rust_decimal::Decimal) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<'a> TryFrom<&'a rust_decimal::Decimal> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a rust_decimal::Decimal) -> errors::Result<InvocationArg> {
+        InvocationArg::new_big_number(&arg.to_string(), JavaClass::BigDecimal)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl TryFrom<num_bigint::BigInt> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: num_bigint::BigInt) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl<'a> TryFrom<&'a num_bigint::BigInt> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a num_bigint::BigInt) -> errors::Result<InvocationArg> {
+        InvocationArg::new_big_number(&arg.to_string(), JavaClass::BigInteger)
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.time.Instant`.
+impl TryFrom<SystemTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: SystemTime) -> errors::Result<InvocationArg> {
+        let (seconds, nanos) = match arg.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => (seconds_to_i64(since_epoch.as_secs())?, i64::from(since_epoch.subsec_nanos())),
+            Err(error) => {
+                let before_epoch = error.duration();
+                (-seconds_to_i64(before_epoch.as_secs())?, -i64::from(before_epoch.subsec_nanos()))
+            }
+        };
+        InvocationArg::new_java_time(CLASS_INSTANT, "ofEpochSecond", seconds, nanos)
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.time.Duration`.
+impl TryFrom<Duration> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: Duration) -> errors::Result<InvocationArg> {
+        InvocationArg::new_java_time(
+            CLASS_DURATION,
+            "ofSeconds",
+            seconds_to_i64(arg.as_secs())?,
+            i64::from(arg.subsec_nanos()),
+        )
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.time.Instant`.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: chrono::DateTime<chrono::Utc>) -> errors::Result<InvocationArg> {
+        InvocationArg::new_java_time(
+            CLASS_INSTANT,
+            "ofEpochSecond",
+            arg.timestamp(),
+            i64::from(arg.timestamp_subsec_nanos()),
+        )
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.time.LocalDateTime`.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDateTime> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: chrono::NaiveDateTime) -> errors::Result<InvocationArg> {
+        use crate::api::{CLASS_LOCAL_DATE_TIME, CLASS_ZONE_OFFSET};
+
+        let utc = arg.and_utc();
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        let mut instance = jvm.invoke_static(
+            CLASS_LOCAL_DATE_TIME,
+            "ofEpochSecond",
+            &[
+                InvocationArg::try_from(utc.timestamp())?.into_primitive()?,
+                InvocationArg::try_from(utc.timestamp_subsec_nanos() as i32)?.into_primitive()?,
+                InvocationArg::from(jvm.static_class_field(CLASS_ZONE_OFFSET, "UTC")?),
+            ],
+        )?;
+        instance.class_name = CLASS_LOCAL_DATE_TIME.to_string();
+        Ok(InvocationArg::from(instance))
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.time.Duration`.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::TimeDelta> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: chrono::TimeDelta) -> errors::Result<InvocationArg> {
+        InvocationArg::new_java_time(
+            CLASS_DURATION,
+            "ofSeconds",
+            arg.num_seconds(),
+            i64::from(arg.subsec_nanos()),
+        )
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.util.UUID`.
+#[cfg(feature = "uuid")]
+impl TryFrom<uuid::Uuid> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: uuid::Uuid) -> errors::Result<InvocationArg> {
+        InvocationArg::try_from(&arg)
+    }
+}
+
+/// Creates an InvocationArg that contains a `java.util.UUID`.
+#[cfg(feature = "uuid")]
+impl<'a> TryFrom<&'a uuid::Uuid> for InvocationArg {
+    type Error = errors::J4RsError;
+    fn try_from(arg: &'a uuid::Uuid) -> errors::Result<InvocationArg> {
+        let (most, least) = arg.as_u64_pair();
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        let instance = jvm.create_instance(
+            "java.util.UUID",
+            &[
+                InvocationArg::try_from(most as i64)?.into_primitive()?,
+                InvocationArg::try_from(least as i64)?.into_primitive()?,
+            ],
+        )?;
+        Ok(InvocationArg::from(instance))
+    }
+}
+
+fn seconds_to_i64(seconds: u64) -> errors::Result<i64> {
+    i64::try_from(seconds).map_err(|_| {
+        errors::J4RsError::ParseError(format!("{} seconds do not fit in a java.lang.Long", seconds))
+    })
+}
+
 impl<'a, T: 'static> TryFrom<(&'a [T], &'a str)> for InvocationArg
     where
         T: Serialize,
@@ -751,6 +1174,34 @@ mod inv_arg_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn invocation_arg_try_from_unsigned_types() -> errors::Result<()> {
+        let _jvm = create_tests_jvm()?;
+        validate_type(InvocationArg::try_from(1_u8)?, "java.lang.Short");
+        validate_type(InvocationArg::try_from(&1_u8)?, "java.lang.Short");
+        validate_type(InvocationArg::try_from(1_u32)?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(&1_u32)?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(1_u64)?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(&1_u64)?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(1_usize)?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(&1_usize)?, "java.lang.Long");
+        assert!(InvocationArg::try_from(u64::MAX).is_err());
+        assert!(InvocationArg::try_from(usize::MAX).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invocation_arg_try_from_big_numbers() -> errors::Result<()> {
+        let _jvm = create_tests_jvm()?;
+        validate_type(InvocationArg::try_from(1_i128)?, "java.math.BigInteger");
+        validate_type(InvocationArg::try_from(&1_i128)?, "java.math.BigInteger");
+        validate_type(InvocationArg::try_from(1_u128)?, "java.math.BigInteger");
+        validate_type(InvocationArg::try_from(&1_u128)?, "java.math.BigInteger");
+
+        Ok(())
+    }
+
     #[test]
     fn invocation_into_primitive() -> errors::Result<()> {
         let _jvm: Jvm = create_tests_jvm()?;
@@ -808,6 +1259,35 @@ mod inv_arg_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn invocation_arg_from_json() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let value = serde_json::json!({"name": "j4rs", "versions": [1, 2.5, null]});
+        let map = jvm.create_instance(
+            "java.util.HashMap",
+            &[InvocationArg::from_json(value.clone(), "java.util.Map")?],
+        )?;
+        let value_from_java: serde_json::Value = jvm.to_rust(map)?;
+        assert_eq!(value_from_java, value);
+
+        validate_type(InvocationArg::try_from(serde_json::json!(true))?, "java.lang.Boolean");
+        validate_type(InvocationArg::try_from(serde_json::json!("str"))?, "java.lang.String");
+        validate_type(InvocationArg::try_from(serde_json::json!(1))?, "java.lang.Integer");
+        validate_type(InvocationArg::try_from(serde_json::json!(i64::MAX))?, "java.lang.Long");
+        validate_type(InvocationArg::try_from(serde_json::json!(u64::MAX))?, "java.math.BigInteger");
+        validate_type(InvocationArg::try_from(serde_json::json!(1.5))?, "java.lang.Double");
+        validate_type(InvocationArg::try_from(serde_json::json!(null))?, "java.lang.Object");
+
+        let list = jvm.create_instance(
+            "java.util.ArrayList",
+            &[InvocationArg::try_from(serde_json::json!([{"a": 1}, "b"]))?],
+        )?;
+        let first = jvm.invoke(&list, "get", &[InvocationArg::try_from(0)?.into_primitive()?])?;
+        let first: serde_json::Value = jvm.to_rust(first)?;
+        assert_eq!(first, serde_json::json!({"a": 1}));
+        Ok(())
+    }
+
     #[derive(Serialize, Deserialize, Debug)]
     #[allow(non_snake_case)]
     struct MyBean {
@@ -0,0 +1,186 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::borrow::Borrow;
+
+use jni_sys::{jobject, JNI_TRUE};
+
+use crate::api::hooks::{self, InvocationKind};
+use crate::api::instance::Instance;
+use crate::api::invocation_arg::InvocationArg;
+use crate::api::metrics::{self, Operation};
+use crate::errors::opt_to_res;
+use crate::logger::debug;
+use crate::{cache, errors, jni_utils, Jvm};
+
+/// The result of an invocation, as returned by `Jvm::invoke2` and `Jvm::invoke_static2`.
+///
+/// Distinguishes methods that returned an object from void methods and methods that returned `null`.
+pub enum InvocationResult {
+    /// The invoked method returned an object.
+    Value(Instance),
+    /// The invoked method is void.
+    Void,
+    /// The invoked method returned `null`.
+    Null,
+}
+
+impl InvocationResult {
+    /// Returns the `Instance` if the invoked method returned an object.
+    pub fn value(self) -> Option<Instance> {
+        match self {
+            InvocationResult::Value(instance) => Some(instance),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the invoked method is void.
+    pub fn is_void(&self) -> bool {
+        matches!(self, InvocationResult::Void)
+    }
+
+    /// Returns true if the invoked method returned `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, InvocationResult::Null)
+    }
+}
+
+impl Jvm {
+    /// Invokes the method `method_name` of a created `Instance`, like `invoke` does,
+    /// and returns an `InvocationResult` that tells whether the method returned a value, `null`, or is void.
+    pub fn invoke2(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<InvocationResult> {
+        debug(&format!(
+            "Invoking method {} of class {} using {} arguments, for an InvocationResult",
+            method_name,
+            instance.class_name,
+            inv_args.len()
+        ));
+        metrics::timed(Operation::Invoke, || {
+            hooks::with_hook(InvocationKind::Invoke, &instance.class_name, method_name, inv_args, || {
+                self.do_invoke_for_result(instance, method_name, inv_args)
+            })
+        })
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name`, like `invoke_static` does,
+    /// and returns an `InvocationResult` that tells whether the method returned a value, `null`, or is void.
+    pub fn invoke_static2(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<InvocationResult> {
+        debug(&format!(
+            "Invoking static method {} of class {} using {} arguments, for an InvocationResult",
+            method_name,
+            class_name,
+            inv_args.len()
+        ));
+        metrics::timed(Operation::InvokeStatic, || {
+            hooks::with_hook(InvocationKind::InvokeStatic, class_name, method_name, inv_args, || {
+                let static_instance = self.static_class(class_name)?;
+                self.do_invoke_for_result(&static_instance, method_name, inv_args)
+            })
+        })
+    }
+
+    // The kind of the result is returned along with the result by `Instance.invokeForResult`:
+    // `null` for null results and the void invocation result for void methods.
+    fn do_invoke_for_result(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<InvocationResult> {
+        unsafe {
+            let method_name_jstring = jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
+
+            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_invoke_for_result_method()?,
+                method_name_jstring,
+                array_ptr,
+            );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_instance);
+
+            // Prevent memory leaks from the created references
+            self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+
+            self.to_invocation_result(result?)
+        }
+    }
+
+    unsafe fn to_invocation_result(&self, java_instance: jobject) -> errors::Result<InvocationResult> {
+        if java_instance.is_null() {
+            return Ok(InvocationResult::Null);
+        }
+        let is_void = (opt_to_res(cache::get_is_same_object())?)(
+            self.jni_env,
+            java_instance,
+            cache::get_void_invocation_result()?,
+        ) == JNI_TRUE;
+        if is_void {
+            jni_utils::delete_java_local_ref(self.jni_env, java_instance);
+            Ok(InvocationResult::Void)
+        } else {
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
+            Ok(InvocationResult::Value(Instance::new(
+                java_instance_global_instance,
+                cache::UNKNOWN_FOR_RUST,
+            )?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod invocation_result_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn distinguish_value_void_and_null() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let map = jvm.create_instance("java.util.HashMap", InvocationArg::empty())?;
+
+        let res = jvm.invoke2(&map, "put", &[InvocationArg::try_from("a")?, InvocationArg::try_from("b")?])?;
+        assert!(res.is_null());
+        let res = jvm.invoke2(&map, "get", &[InvocationArg::try_from("a")?])?;
+        let value: String = jvm.to_rust(res.value().unwrap())?;
+        assert_eq!(value, "b");
+        let res = jvm.invoke2(&map, "clear", InvocationArg::empty())?;
+        assert!(res.is_void());
+        let res = jvm.invoke2(&map, "get", &[InvocationArg::try_from("a")?])?;
+        assert!(res.is_null());
+
+        let res = jvm.invoke_static2("java.lang.System", "gc", InvocationArg::empty())?;
+        assert!(res.is_void());
+        let res = jvm.invoke_static2("java.lang.System", "getProperty", &[InvocationArg::try_from("no.such.property")?])?;
+        assert!(res.is_null());
+        let res = jvm.invoke_static2("java.lang.Integer", "valueOf", &[InvocationArg::try_from("1")?])?;
+        let value: i32 = jvm.to_rust(res.value().unwrap())?;
+        assert_eq!(value, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+/// A Rust `Iterator` over the elements of a Java `java.util.Iterator`.
+///
+/// The elements are fetched lazily, one by one, as the iteration proceeds.
+/// It is created by calling `Jvm::iterate`.
+pub struct JavaIterator<'a> {
+    jvm: &'a Jvm,
+    iterator: Instance,
+    done: bool,
+}
+
+impl<'a> JavaIterator<'a> {
+    pub(crate) fn new(jvm: &'a Jvm, iterator: Instance) -> JavaIterator<'a> {
+        JavaIterator {
+            jvm,
+            iterator,
+            done: false,
+        }
+    }
+
+    /// Calls `hasNext` of the Java iterator. Returns `None` if the iteration is over.
+    fn has_next(&mut self) -> Option<errors::Result<()>> {
+        if self.done {
+            return None;
+        }
+        match self
+            .jvm
+            .invoke_to_rust::<bool>(&self.iterator, "hasNext", InvocationArg::empty())
+        {
+            Ok(true) => Some(Ok(())),
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn next_with<T>(&mut self, f: impl FnOnce(&Jvm, &Instance) -> errors::Result<T>) -> Option<errors::Result<T>> {
+        match self.has_next()? {
+            Ok(()) => {
+                let next = f(self.jvm, &self.iterator);
+                if next.is_err() {
+                    self.done = true;
+                }
+                Some(next)
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+impl<'a> Iterator for JavaIterator<'a> {
+    type Item = errors::Result<Instance>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with(|jvm, iterator| jvm.invoke(iterator, "next", InvocationArg::empty()))
+    }
+}
+
+/// A Rust `Iterator` over the elements of a Java `java.util.Iterator`, which transforms each element to Rust.
+///
+/// It is created by calling `Jvm::iterate_to_rust`.
+pub struct JavaToRustIterator<'a, T> {
+    inner: JavaIterator<'a>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> JavaToRustIterator<'a, T> {
+    pub(crate) fn new(inner: JavaIterator<'a>) -> JavaToRustIterator<'a, T> {
+        JavaToRustIterator {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for JavaToRustIterator<'a, T>
+    where
+        T: DeserializeOwned + Any,
+{
+    type Item = errors::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_with(|jvm, iterator| jvm.invoke_to_rust(iterator, "next", InvocationArg::empty()))
+    }
+}
+
+/// A Rust `Iterator` over the elements of a Java collection, which are transferred to Rust in chunks of JSON values.
+///
+/// Only one chunk at a time is kept in memory, both in Java and in Rust.
+/// It is created by calling `Jvm::to_rust_stream`.
+pub struct ChunkedToRustIterator<'a, T> {
+    jvm: &'a Jvm,
+    serializer: Instance,
+    buffer: VecDeque<T>,
+    done: bool,
+}
+
+impl<'a, T> ChunkedToRustIterator<'a, T>
+    where
+        T: DeserializeOwned + Any,
+{
+    pub(crate) fn new(jvm: &'a Jvm, serializer: Instance) -> ChunkedToRustIterator<'a, T> {
+        ChunkedToRustIterator {
+            jvm,
+            serializer,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Retrieves the next chunk of JSON values from Java and deserializes it in the buffer.
+    fn fill_buffer(&mut self) -> errors::Result<()> {
+        let chunk: String = self
+            .jvm
+            .invoke_to_rust(&self.serializer, "nextChunk", InvocationArg::empty())?;
+        if chunk.is_empty() {
+            self.done = true;
+        }
+        for value in serde_json::Deserializer::from_str(&chunk).into_iter::<T>() {
+            self.buffer.push_back(value?);
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> Iterator for ChunkedToRustIterator<'a, T>
+    where
+        T: DeserializeOwned + Any,
+{
+    type Item = errors::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() && !self.done {
+            if let Err(error) = self.fill_buffer() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+        self.buffer.pop_front().map(Ok)
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::logger::debug;
+use crate::{errors, utils, InvocationArg, Jvm};
+
+const CLASS_DEPLOYMENT_RECORDS: &str = "org.astonbitecode.j4rs.api.deploy.DeploymentRecords";
+
+/// The integrity status of a deployed jar.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JarStatus {
+    /// The jar exists and its checksum is the one recorded when it was deployed.
+    Ok,
+    /// The jar does not exist.
+    Missing,
+    /// The checksum of the jar is different than the one recorded when it was deployed.
+    Corrupt,
+}
+
+/// The result of the verification of a deployed jar.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JarVerification {
+    file: String,
+    status: JarStatus,
+    expected_sha256: String,
+    actual_sha256: Option<String>,
+}
+
+impl JarVerification {
+    /// The file name of the jar, relative to the jassets directory.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The integrity status of the jar.
+    pub fn status(&self) -> JarStatus {
+        self.status
+    }
+
+    /// The SHA-256 checksum that was recorded when the jar was deployed.
+    pub fn expected_sha256(&self) -> &str {
+        &self.expected_sha256
+    }
+
+    /// The SHA-256 checksum of the jar. `None` if the jar is missing.
+    pub fn actual_sha256(&self) -> Option<&str> {
+        self.actual_sha256.as_deref()
+    }
+}
+
+impl Jvm {
+    /// Verifies the jars that were deployed in the jassets directory with `deploy_artifact`,
+    /// against the SHA-256 checksums that were recorded when they were deployed.
+    ///
+    /// Files that were not deployed with `deploy_artifact` are not verified.
+    pub fn verify_jassets(&self) -> errors::Result<Vec<JarVerification>> {
+        debug("Verifying the jassets");
+        self.call_deployment_records("verify")
+    }
+
+    /// Downloads again the Maven artifacts of the jassets directory that are missing or corrupt
+    /// and returns the verification of the jars after the repair.
+    ///
+    /// Jars that were not deployed from a Maven repository cannot be repaired.
+    pub fn repair_jassets(&self) -> errors::Result<Vec<JarVerification>> {
+        debug("Repairing the jassets");
+        self.call_deployment_records("repair")
+    }
+
+    /// Writes a lock file with the Maven artifacts that were deployed in the jassets directory with `deploy_artifact`,
+    /// along with their SHA-256 checksums.
+    ///
+    /// The lock file can be used with `JvmBuilder::with_artifact_lock` in order to deploy exactly the same artifacts.
+    pub fn export_artifact_lock<P: AsRef<Path>>(&self, path: P) -> errors::Result<()> {
+        debug(&format!("Exporting the artifact lock to {}", path.as_ref().display()));
+        let jassets_path = utils::jassets_path()?;
+        self.invoke_static(
+            CLASS_DEPLOYMENT_RECORDS,
+            "exportLock",
+            &[
+                InvocationArg::try_from(jassets_path.to_string_lossy().as_ref())?,
+                InvocationArg::try_from(path.as_ref().to_string_lossy().as_ref())?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Deploys the Maven artifacts of a lock file that is written by `export_artifact_lock`.
+    /// An error is returned if the checksum of any artifact is not the locked one.
+    pub(crate) fn deploy_artifact_lock(&self, path: &Path) -> errors::Result<()> {
+        debug(&format!("Deploying the artifacts of the lock {}", path.display()));
+        let jassets_path = utils::jassets_path()?;
+        self.invoke_static(
+            CLASS_DEPLOYMENT_RECORDS,
+            "deployLocked",
+            &[
+                InvocationArg::try_from(path.to_string_lossy().as_ref())?,
+                InvocationArg::try_from(jassets_path.to_string_lossy().as_ref())?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn call_deployment_records(&self, method_name: &str) -> errors::Result<Vec<JarVerification>> {
+        let jassets_path = utils::jassets_path()?;
+        let results = self.invoke_static(
+            CLASS_DEPLOYMENT_RECORDS,
+            method_name,
+            &[InvocationArg::try_from(jassets_path.to_string_lossy().as_ref())?],
+        )?;
+        self.to_rust(results)
+    }
+}
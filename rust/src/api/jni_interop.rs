@@ -0,0 +1,71 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jni::objects::JObject;
+
+use crate::{errors, Instance, Jvm};
+
+impl Jvm {
+    /// Creates a `Jvm` from a `JNIEnv` of the `jni` crate.
+    pub fn from_jni_env(env: &jni::JNIEnv) -> errors::Result<Jvm> {
+        // The JNIEnv of the jni crate is always valid for the current thread
+        unsafe { Jvm::try_from(env.get_raw().cast()) }
+    }
+
+    /// Returns a `JNIEnv` of the `jni` crate, for the thread of this `Jvm`.
+    pub fn jni_env(&self) -> errors::Result<jni::JNIEnv<'_>> {
+        Ok(unsafe { jni::JNIEnv::from_raw(self.jni_env.cast())? })
+    }
+
+    /// Creates an `Instance` of a `JObject` of the `jni` crate. The `JObject` is not consumed.
+    pub fn instance_from_jni_object(&self, object: &JObject) -> errors::Result<Instance> {
+        // The JObject of the jni crate is a valid reference, or null
+        unsafe { self.instance_from_raw_object(object.as_raw().cast()) }
+    }
+
+    /// Consumes an `Instance` and returns its Java object as a `JObject` of the `jni` crate.
+    ///
+    /// The returned `JObject` is a JNI local reference.
+    pub fn instance_into_jni_object(&self, instance: Instance) -> errors::Result<JObject<'_>> {
+        let object = self.instance_into_raw_object(instance)?;
+        Ok(unsafe { JObject::from_raw(object.cast()) })
+    }
+}
+
+#[cfg(test)]
+mod jni_interop_unit_tests {
+    use jni::objects::JString;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::InvocationArg;
+
+    #[test]
+    fn jni_objects_to_instances_and_back() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let mut env = jvm.jni_env()?;
+        let string = env.new_string("a string")?;
+        let instance = jvm.instance_from_jni_object(&string)?;
+        let upper = jvm.invoke(&instance, "toUpperCase", InvocationArg::empty())?;
+
+        let object = jvm.instance_into_jni_object(upper)?;
+        let upper: String = env.get_string(&JString::from(object))?.into();
+        assert_eq!(upper, "A STRING");
+
+        let same_jvm = Jvm::from_jni_env(&env)?;
+        let length: i32 = same_jvm.to_rust(same_jvm.invoke(&instance, "length", InvocationArg::empty())?)?;
+        assert_eq!(length, 8);
+        Ok(())
+    }
+}
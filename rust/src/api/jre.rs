@@ -0,0 +1,448 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::env::consts::{ARCH, OS};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+
+use crate::api::jvm_discovery::jvm_dyn_lib_file_name;
+use crate::api_tweaks as tweaks;
+use crate::errors::J4RsError;
+use crate::logger::{debug, info};
+use crate::{errors, utils};
+
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3/binary";
+
+/// Defines a JRE (or JDK) that is downloaded and unpacked, in order to load the jvm dynamic library from it
+/// when no Java installation is found.
+///
+/// The JREs are unpacked once, in a cache directory, and reused by later executions.
+/// The archives are verified against a SHA-256 checksum before they are unpacked.
+#[derive(Debug, Clone)]
+pub struct JreProvisioning {
+    source: JreSource,
+    sha256: Option<String>,
+    cache_dir: Option<PathBuf>,
+    prefer_provisioned: bool,
+}
+
+#[derive(Debug, Clone)]
+enum JreSource {
+    Temurin { version: String, latest: bool },
+    Url(String),
+    Archive(PathBuf),
+}
+
+impl JreProvisioning {
+    /// Provisions the latest Eclipse Temurin JRE of a Java feature version (e.g. `21`), using the Adoptium API.
+    ///
+    /// The downloaded archive is verified against the checksum that is published along with it, unless one is pinned with `sha256`.
+    ///
+    /// Once unpacked, the JRE is reused, even if a newer release of the feature version becomes available.
+    pub fn temurin(feature_version: u32) -> JreProvisioning {
+        Self::with_source(JreSource::Temurin {
+            version: feature_version.to_string(),
+            latest: true,
+        })
+    }
+
+    /// Provisions a specific Eclipse Temurin JRE release (e.g. `jdk-21.0.4+7`), using the Adoptium API.
+    ///
+    /// The downloaded archive is verified against the checksum that is published along with it, unless one is pinned with `sha256`.
+    pub fn temurin_release(release_name: &str) -> JreProvisioning {
+        Self::with_source(JreSource::Temurin {
+            version: release_name.to_string(),
+            latest: false,
+        })
+    }
+
+    /// Provisions a JRE from a `.tar.gz` or `.zip` archive that is downloaded from `url`.
+    ///
+    /// The downloaded archive must match the hex encoded SHA-256 checksum `sha256`.
+    pub fn from_url(url: &str, sha256: &str) -> JreProvisioning {
+        Self::with_source(JreSource::Url(url.to_string())).sha256(sha256)
+    }
+
+    /// Provisions a JRE from a local `.tar.gz` or `.zip` archive, e.g. one that is shipped along with the application.
+    ///
+    /// The archive is verified only if a checksum is set with `sha256`.
+    pub fn from_archive(path: impl AsRef<Path>) -> JreProvisioning {
+        Self::with_source(JreSource::Archive(path.as_ref().to_path_buf()))
+    }
+
+    fn with_source(source: JreSource) -> JreProvisioning {
+        JreProvisioning {
+            source,
+            sha256: None,
+            cache_dir: None,
+            prefer_provisioned: false,
+        }
+    }
+
+    /// Sets the directory where the JREs are unpacked.
+    ///
+    /// Defaults to a `jre` directory next to the `jassets` directory.
+    pub fn cache_dir(mut self, dir: impl AsRef<Path>) -> JreProvisioning {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Pins the hex encoded SHA-256 checksum that the archive of the JRE must match.
+    pub fn sha256(mut self, sha256: &str) -> JreProvisioning {
+        self.sha256 = Some(sha256.trim().to_lowercase());
+        self
+    }
+
+    /// Uses the provisioned JRE even if a Java installation is found.
+    pub fn prefer_provisioned(mut self) -> JreProvisioning {
+        self.prefer_provisioned = true;
+        self
+    }
+
+    /// Downloads and unpacks the JRE, unless it is already unpacked in the cache directory,
+    /// and returns the path of its jvm dynamic library.
+    pub fn provision(&self) -> errors::Result<PathBuf> {
+        let cache_dir = match &self.cache_dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let mut pb = utils::jassets_path()?;
+                pb.pop();
+                pb.push("jre");
+                pb
+            }
+        };
+        let jre_dir = cache_dir.join(self.dir_name()?);
+        if !jre_dir.exists() {
+            fs::create_dir_all(&cache_dir)?;
+            self.unpack_to(&jre_dir)?;
+        }
+        find_jvm_dyn_lib(&jre_dir)
+    }
+
+    // The name of the directory in the cache that contains the JRE
+    fn dir_name(&self) -> errors::Result<String> {
+        Ok(match &self.source {
+            JreSource::Temurin { version, .. } => {
+                let (os, arch) = adoptium_platform()?;
+                sanitized(&format!("temurin-{}-{}-{}", version, os, arch))
+            }
+            JreSource::Url(url) => format!("jre-{:016x}", fnv1a(url)),
+            JreSource::Archive(path) => format!("jre-{:016x}", fnv1a(&path.to_string_lossy())),
+        })
+    }
+
+    fn url(&self) -> errors::Result<String> {
+        match &self.source {
+            JreSource::Temurin { version, latest } => {
+                let (os, arch) = adoptium_platform()?;
+                Ok(if *latest {
+                    format!("{}/latest/{}/ga/{}/{}/jre/hotspot/normal/eclipse", ADOPTIUM_API, version, os, arch)
+                } else {
+                    format!(
+                        "{}/version/{}/{}/{}/jre/hotspot/normal/eclipse",
+                        ADOPTIUM_API,
+                        version.replace('+', "%2B"),
+                        os,
+                        arch
+                    )
+                })
+            }
+            JreSource::Url(url) => Ok(url.clone()),
+            JreSource::Archive(path) => Err(J4RsError::GeneralError(format!("The JRE archive {:?} is local", path))),
+        }
+    }
+
+    // Unpacks the archive to a temporary directory first, so that interrupted provisionings are not reused
+    fn unpack_to(&self, jre_dir: &Path) -> errors::Result<()> {
+        let partial_dir = jre_dir.with_extension("partial");
+        if partial_dir.exists() {
+            fs::remove_dir_all(&partial_dir)?;
+        }
+        let download = jre_dir.with_extension("download");
+        let result = self.download(&download).and_then(|(archive, expected_sha256)| {
+            match expected_sha256 {
+                Some(expected) => verify_sha256(&archive, &expected)?,
+                None => debug(&format!("No checksum is set for the JRE archive {:?}", archive)),
+            }
+            info(&format!("Unpacking the JRE {:?} to {:?}", archive, jre_dir));
+            unpack(&archive, &partial_dir)
+        });
+        if download.exists() {
+            fs::remove_file(&download)?;
+        }
+        result?;
+        if fs::rename(&partial_dir, jre_dir).is_err() && jre_dir.exists() {
+            // Another process provisioned the same JRE in the meantime
+            fs::remove_dir_all(&partial_dir)?;
+        }
+        Ok(())
+    }
+
+    // Returns the archive to unpack, downloading it to `download` if needed, along with its expected checksum
+    fn download(&self, download: &Path) -> errors::Result<(PathBuf, Option<String>)> {
+        if let JreSource::Archive(path) = &self.source {
+            return Ok((path.clone(), self.sha256.clone()));
+        }
+        let url = self.url()?;
+        info(&format!("Downloading a JRE from {}", url));
+        let response = ureq::get(&url).call()?;
+        // The binaries of the Adoptium API redirect to the actual archive, which is published along with its checksum
+        let checksum_url = format!("{}.sha256.txt", response.get_url());
+        io::copy(&mut response.into_reader(), &mut File::create(download)?)?;
+        let expected_sha256 = match (&self.sha256, &self.source) {
+            (Some(sha256), _) => sha256.clone(),
+            (None, JreSource::Temurin { .. }) => published_sha256(&checksum_url)?,
+            (None, _) => {
+                return Err(J4RsError::GeneralError(format!("No checksum is set for the JRE archive of {}", url)))
+            }
+        };
+        Ok((download.to_path_buf(), Some(expected_sha256)))
+    }
+}
+
+/// Provisions the JRE and instructs j4rs to load the jvm dynamic library from it, if needed.
+pub(crate) fn apply(provisioning: &JreProvisioning) -> errors::Result<()> {
+    if tweaks::jvm_dyn_lib_loaded() {
+        debug("The jvm dynamic library is already loaded. Skipping the JRE provisioning.");
+        return Ok(());
+    }
+    if !provisioning.prefer_provisioned && tweaks::jvm_dyn_lib_located() {
+        debug("A Java installation is found. Skipping the JRE provisioning.");
+        return Ok(());
+    }
+    let jvm_dyn_lib = provisioning.provision()?;
+    info(&format!("Using the provisioned jvm dynamic library {:?}", jvm_dyn_lib));
+    tweaks::set_jvm_dyn_lib_path(jvm_dyn_lib)
+}
+
+fn unpack(archive: &Path, dir: &Path) -> errors::Result<()> {
+    let mut file = File::open(archive)?;
+    let mut magic = [0u8; 2];
+    file.read_exact(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+    match magic {
+        [0x1f, 0x8b] => tar::Archive::new(GzDecoder::new(BufReader::new(file))).unpack(dir)?,
+        [b'P', b'K'] => zip::ZipArchive::new(BufReader::new(file))?.extract(dir)?,
+        _ => {
+            return Err(J4RsError::GeneralError(format!(
+                "{:?} is neither a .tar.gz nor a .zip archive",
+                archive
+            )))
+        }
+    }
+    Ok(())
+}
+
+// Fetches a checksum file, which contains the hex encoded SHA-256 checksum, optionally followed by the file name
+fn published_sha256(url: &str) -> errors::Result<String> {
+    debug(&format!("Fetching the checksum of the JRE archive from {}", url));
+    let content = ureq::get(url).call()?.into_string()?;
+    content
+        .split_whitespace()
+        .next()
+        .map(|sha256| sha256.to_lowercase())
+        .ok_or_else(|| J4RsError::GeneralError(format!("The checksum file {} is empty", url)))
+}
+
+fn verify_sha256(archive: &Path, expected: &str) -> errors::Result<()> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(archive)?, &mut hasher)?;
+    let actual: String = hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(J4RsError::GeneralError(format!(
+            "The SHA-256 checksum of the JRE archive {:?} is {}, but {} was expected",
+            archive, actual, expected
+        )))
+    }
+}
+
+/// Finds the jvm dynamic library in a directory, preferring the one of the server VM.
+fn find_jvm_dyn_lib(dir: &Path) -> errors::Result<PathBuf> {
+    let mut found = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.file_name().map(|name| name == jvm_dyn_lib_file_name()).unwrap_or(false) {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    let server = found
+        .iter()
+        .position(|path| path.parent().and_then(Path::file_name).map(|name| name == "server").unwrap_or(false));
+    match server {
+        Some(index) => Ok(found.swap_remove(index)),
+        None => found.into_iter().next().ok_or_else(|| {
+            J4RsError::GeneralError(format!("Could not find {} in {:?}", jvm_dyn_lib_file_name(), dir))
+        }),
+    }
+}
+
+/// Returns the names of the operating system and the architecture, as known to the Adoptium API.
+fn adoptium_platform() -> errors::Result<(&'static str, &'static str)> {
+    let os = match OS {
+        "linux" => "linux",
+        "macos" => "mac",
+        "windows" => "windows",
+        "aix" => "aix",
+        "solaris" => "solaris",
+        other => return Err(J4RsError::GeneralError(format!("Temurin JREs are not available for {}", other))),
+    };
+    let arch = match ARCH {
+        "x86_64" => "x64",
+        "x86" => "x32",
+        "aarch64" => "aarch64",
+        "arm" => "arm",
+        "powerpc64" => "ppc64le",
+        "s390x" => "s390x",
+        "riscv64" => "riscv64",
+        other => return Err(J4RsError::GeneralError(format!("Temurin JREs are not available for {}", other))),
+    };
+    Ok((os, arch))
+}
+
+fn sanitized(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect()
+}
+
+// A hash that is stable between executions, for naming the cache directories of URLs and archives
+fn fnv1a(s: &str) -> u64 {
+    s.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod jre_unit_tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    // Unique per test process, so that concurrent test runs do not interfere
+    fn test_dir(name: &str) -> errors::Result<PathBuf> {
+        let dir = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn provision_from_tar_gz() -> errors::Result<()> {
+        let dir = test_dir("j4rs_jre_tar_gz_test")?;
+        let archive = dir.join("jre.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(File::create(&archive)?, Compression::fast()));
+        for path in ["jdk-21-jre/lib/client", "jdk-21-jre/lib/server"] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(3);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, format!("{}/{}", path, jvm_dyn_lib_file_name()), &b"jvm"[..])?;
+        }
+        builder.into_inner()?.finish()?;
+
+        let provisioning = JreProvisioning::from_archive(&archive).cache_dir(dir.join("cache"));
+        let lib = provisioning.provision()?;
+        assert!(lib.ends_with(Path::new("jdk-21-jre/lib/server").join(jvm_dyn_lib_file_name())));
+        // The unpacked JRE is reused
+        fs::remove_file(&archive)?;
+        assert_eq!(provisioning.provision()?, lib);
+        Ok(())
+    }
+
+    #[test]
+    fn provision_from_zip() -> errors::Result<()> {
+        let dir = test_dir("j4rs_jre_zip_test")?;
+        let archive = dir.join("jre.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive)?);
+        writer.start_file(
+            format!("jdk-21-jre/bin/server/{}", jvm_dyn_lib_file_name()),
+            zip::write::SimpleFileOptions::default(),
+        )?;
+        writer.write_all(b"jvm")?;
+        writer.finish()?;
+
+        let lib = JreProvisioning::from_archive(&archive).cache_dir(dir.join("cache")).provision()?;
+        assert!(lib.ends_with(Path::new("jdk-21-jre/bin/server").join(jvm_dyn_lib_file_name())));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_archive() -> errors::Result<()> {
+        let dir = test_dir("j4rs_jre_invalid_test")?;
+        let archive = dir.join("jre.tar.gz");
+        fs::write(&archive, b"not an archive")?;
+        let provisioning = JreProvisioning::from_archive(&archive).cache_dir(dir.join("cache"));
+        assert!(provisioning.provision().is_err());
+        // Nothing is left in the cache
+        assert_eq!(fs::read_dir(dir.join("cache"))?.count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn verify_checksums() -> errors::Result<()> {
+        let dir = test_dir("j4rs_jre_checksum_test")?;
+        let archive = dir.join("jre.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive)?);
+        writer.start_file(
+            format!("jdk-21-jre/lib/server/{}", jvm_dyn_lib_file_name()),
+            zip::write::SimpleFileOptions::default(),
+        )?;
+        writer.write_all(b"jvm")?;
+        writer.finish()?;
+        let mut hasher = Sha256::new();
+        hasher.update(fs::read(&archive)?);
+        let sha256: String = hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        let mismatching = JreProvisioning::from_archive(&archive)
+            .sha256(&"0".repeat(64))
+            .cache_dir(dir.join("cache"));
+        assert!(mismatching.provision().is_err());
+        // Nothing is unpacked
+        assert_eq!(fs::read_dir(dir.join("cache"))?.count(), 0);
+
+        let matching = JreProvisioning::from_archive(&archive)
+            .sha256(&sha256.to_uppercase())
+            .cache_dir(dir.join("cache"));
+        assert!(matching.provision()?.ends_with(Path::new("jdk-21-jre/lib/server").join(jvm_dyn_lib_file_name())));
+        Ok(())
+    }
+
+    #[test]
+    fn temurin_urls() -> errors::Result<()> {
+        let latest = JreProvisioning::temurin(21).url()?;
+        assert!(latest.starts_with("https://api.adoptium.net/v3/binary/latest/21/ga/"));
+        assert!(latest.ends_with("/jre/hotspot/normal/eclipse"));
+        let release = JreProvisioning::temurin_release("jdk-21.0.4+7").url()?;
+        assert!(release.starts_with("https://api.adoptium.net/v3/binary/version/jdk-21.0.4%2B7/"));
+        assert!(JreProvisioning::temurin_release("jdk-21.0.4+7").dir_name()?.starts_with("temurin-jdk-21.0.4_7-"));
+        Ok(())
+    }
+}
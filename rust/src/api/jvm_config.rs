@@ -0,0 +1,219 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::errors;
+use crate::errors::J4RsError;
+use crate::provisioning::{MavenArtifactRepo, MavenSettings};
+use crate::api::{ClasspathEntry, JavaOpt, JvmBuilder};
+
+/// The contents of a configuration file of `JvmBuilder::from_config`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct JvmConfig {
+    classpath: Vec<String>,
+    java_opts: Vec<String>,
+    maven_repositories: Vec<MavenRepositoryConfig>,
+    javafx: bool,
+    base_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MavenRepositoryConfig {
+    id: String,
+    uri: String,
+    username: Option<String>,
+    password: Option<String>,
+    token: Option<String>,
+}
+
+impl JvmBuilder<'static> {
+    /// Creates a new JvmBuilder that is configured by the file at `path`.
+    ///
+    /// The file is JSON, or TOML if its extension is `.toml` and the `toml` feature is enabled. It may define:
+    ///
+    /// * `classpath`: a list of classpath entries
+    /// * `java_opts`: a list of Java options
+    /// * `maven_repositories`: a list of Maven repositories, each with an `id`, a `uri` and optionally
+    ///   a `username` and `password`, or a `token`
+    /// * `javafx`: whether to add JavaFX support
+    /// * `base_path`: the location of the jassets and deps directories
+    ///
+    /// The environment variables that are referenced as `${NAME}` in the values are expanded.
+    /// It is an error if a referenced variable is not defined, or if the file defines anything else.
+    pub fn from_config<P: AsRef<Path>>(path: P) -> errors::Result<JvmBuilder<'static>> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let config = parse_config(path, &contents)?;
+
+        let mut builder = JvmBuilder::new();
+        for entry in config.classpath {
+            builder.classpath_entries.push(ClasspathEntry(Cow::Owned(expand_env_vars(&entry)?)));
+        }
+        for opt in config.java_opts {
+            builder.java_opts.push(JavaOpt(Cow::Owned(expand_env_vars(&opt)?)));
+        }
+        if !config.maven_repositories.is_empty() {
+            let repos = config
+                .maven_repositories
+                .iter()
+                .map(to_maven_artifact_repo)
+                .collect::<errors::Result<Vec<MavenArtifactRepo>>>()?;
+            builder.maven_settings = MavenSettings::new(repos);
+        }
+        builder.javafx = config.javafx;
+        if let Some(base_path) = config.base_path {
+            builder.base_path = Some(expand_env_vars(&base_path)?);
+        }
+        Ok(builder)
+    }
+}
+
+fn parse_config(path: &Path, contents: &str) -> errors::Result<JvmConfig> {
+    let is_toml = path.extension().map(|ext| ext == "toml").unwrap_or(false);
+    if is_toml {
+        parse_toml(contents)
+    } else {
+        Ok(serde_json::from_str(contents)?)
+    }
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(contents: &str) -> errors::Result<JvmConfig> {
+    Ok(toml::from_str(contents)?)
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_: &str) -> errors::Result<JvmConfig> {
+    Err(J4RsError::GeneralError(
+        "TOML configuration files need the toml feature of j4rs".to_string(),
+    ))
+}
+
+fn to_maven_artifact_repo(config: &MavenRepositoryConfig) -> errors::Result<MavenArtifactRepo> {
+    let id = expand_env_vars(&config.id)?;
+    let uri = expand_env_vars(&config.uri)?;
+    let mut repo = MavenArtifactRepo::from(&[id.as_str(), uri.as_str()][..]);
+    match (&config.username, &config.password, &config.token) {
+        (Some(username), Some(password), None) => {
+            repo = repo.with_credentials(&expand_env_vars(username)?, &expand_env_vars(password)?);
+        }
+        (None, None, Some(token)) => {
+            repo = repo.with_token(&expand_env_vars(token)?);
+        }
+        (None, None, None) => {}
+        _ => {
+            return Err(J4RsError::GeneralError(format!(
+                "Invalid configuration of the Maven repository {}: define either a username and a password, or a token",
+                id
+            )));
+        }
+    }
+    Ok(repo)
+}
+
+/// Replaces the references to environment variables, formed like `${NAME}`, with their values.
+pub(crate) fn expand_env_vars(s: &str) -> errors::Result<String> {
+    let mut expanded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..].find('}').ok_or_else(|| {
+            J4RsError::GeneralError(format!("Unterminated environment variable reference in '{}'", s))
+        })?;
+        let name = &rest[start + 2..start + end];
+        let value = env::var(name).map_err(|_| {
+            J4RsError::GeneralError(format!("The environment variable {} that is referenced in '{}' is not defined", name, s))
+        })?;
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod jvm_config_unit_tests {
+    use super::*;
+
+    #[test]
+    fn expand_environment_variables() -> errors::Result<()> {
+        env::set_var("J4RS_CONFIG_TEST_HOME", "/opt/app");
+        assert_eq!(expand_env_vars("${J4RS_CONFIG_TEST_HOME}/lib/a.jar")?, "/opt/app/lib/a.jar");
+        assert_eq!(expand_env_vars("-Dhome=${J4RS_CONFIG_TEST_HOME}")?, "-Dhome=/opt/app");
+        assert_eq!(expand_env_vars("-Xmx1g")?, "-Xmx1g");
+        assert!(expand_env_vars("${J4RS_CONFIG_TEST_UNDEFINED}").is_err());
+        assert!(expand_env_vars("${J4RS_CONFIG_TEST_HOME").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn builder_from_json_config() -> errors::Result<()> {
+        env::set_var("J4RS_CONFIG_TEST_HEAP", "512m");
+        let path = env::temp_dir().join("j4rs_builder_from_json_config.json");
+        fs::write(
+            &path,
+            r#"{
+                "classpath": ["/opt/app/lib/a.jar"],
+                "java_opts": ["-Xmx${J4RS_CONFIG_TEST_HEAP}"],
+                "maven_repositories": [{"id": "internal", "uri": "https://repo.example.com/maven2", "token": "abc"}],
+                "javafx": true
+            }"#,
+        )?;
+        let builder = JvmBuilder::from_config(&path)?;
+        fs::remove_file(&path)?;
+
+        assert_eq!(builder.classpath_entries[0].to_string(), "/opt/app/lib/a.jar");
+        assert_eq!(builder.java_opts[0].to_string(), "-Xmx512m");
+        assert_eq!(builder.maven_settings.repos[0].id, "internal");
+        assert_eq!(builder.maven_settings.repos[0].token.as_deref(), Some("abc"));
+        assert!(builder.javafx);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_config() {
+        let path = Path::new("config.json");
+        assert!(parse_config(path, r#"{"classpaths": []}"#).is_err());
+        assert!(parse_config(path, r#"{"javafx": "yes"}"#).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_config() -> errors::Result<()> {
+        let config = parse_config(
+            Path::new("config.toml"),
+            r#"
+            classpath = ["/opt/app/lib/a.jar"]
+            java_opts = ["-Xss2m"]
+
+            [[maven_repositories]]
+            id = "internal"
+            uri = "https://repo.example.com/maven2"
+            "#,
+        )?;
+        assert_eq!(config.classpath, vec!["/opt/app/lib/a.jar"]);
+        assert_eq!(config.java_opts, vec!["-Xss2m"]);
+        assert_eq!(config.maven_repositories[0].uri, "https://repo.example.com/maven2");
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::J4RsError;
+use crate::{errors, Jvm};
+
+// The locations of the jvm dynamic library, relative to the home directory of a Java installation
+const JVM_DYN_LIB_DIRS: &[&str] = &[
+    "lib/server",
+    "jre/lib/server",
+    "lib/amd64/server",
+    "jre/lib/amd64/server",
+    "lib/aarch64/server",
+    "jre/lib/aarch64/server",
+    "lib/i386/server",
+    "jre/lib/i386/server",
+    "bin/server",
+    "jre/bin/server",
+    "lib/client",
+    "jre/lib/client",
+    "bin/client",
+    "jre/bin/client",
+];
+
+/// A Java installation that contains a jvm dynamic library, as found by `Jvm::discover_jvms`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmInstallation {
+    home: PathBuf,
+    jvm_dyn_lib: PathBuf,
+    version: Option<String>,
+    arch: Option<String>,
+}
+
+impl JvmInstallation {
+    /// Creates a `JvmInstallation` for the Java installation in the directory `home`
+    /// (the directory that `JAVA_HOME` would point to).
+    pub fn from_home(home: impl AsRef<Path>) -> errors::Result<JvmInstallation> {
+        let home = home.as_ref();
+        let jvm_dyn_lib = JVM_DYN_LIB_DIRS
+            .iter()
+            .map(|dir| home.join(dir).join(jvm_dyn_lib_file_name()))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                J4RsError::GeneralError(format!("Could not find {} in {:?}", jvm_dyn_lib_file_name(), home))
+            })?;
+        let release = fs::read_to_string(home.join("release")).unwrap_or_default();
+        Ok(JvmInstallation {
+            home: home.to_path_buf(),
+            jvm_dyn_lib,
+            version: release_property(&release, "JAVA_VERSION"),
+            arch: release_property(&release, "OS_ARCH"),
+        })
+    }
+
+    /// Returns the home directory of the installation
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// Returns the path of the jvm dynamic library of the installation
+    pub fn jvm_dyn_lib(&self) -> &Path {
+        &self.jvm_dyn_lib
+    }
+
+    /// Returns the Java version (e.g. `21.0.4` or `1.8.0_392`), if it is defined in the `release` file of the installation
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the major Java version (e.g. `21`, or `8` for `1.8.0_392`), if the version is known
+    pub fn major_version(&self) -> Option<u32> {
+        let version = self.version.as_deref()?;
+        let mut parts = version.split(|c: char| !c.is_ascii_digit());
+        match parts.next()? {
+            "1" => parts.next()?.parse().ok(),
+            major => major.parse().ok(),
+        }
+    }
+
+    /// Returns the architecture (e.g. `x86_64` or `aarch64`), if it is defined in the `release` file of the installation
+    pub fn arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+}
+
+impl Jvm {
+    /// Discovers the Java installations of the system.
+    ///
+    /// The installations of `JAVA_HOME` and of the `java` that is found in the `PATH` come first,
+    /// followed by the ones in the standard installation directories of the operating system and of tools like SDKMAN!.
+    /// An installation can be selected with `JvmBuilder::with_jvm_installation`.
+    pub fn discover_jvms() -> Vec<JvmInstallation> {
+        let mut seen = HashSet::new();
+        candidate_homes()
+            .into_iter()
+            .filter_map(|home| JvmInstallation::from_home(home).ok())
+            .filter(|installation| {
+                let lib = fs::canonicalize(&installation.jvm_dyn_lib).unwrap_or_else(|_| installation.jvm_dyn_lib.clone());
+                seen.insert(lib)
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn jvm_dyn_lib_file_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "jvm.dll"
+    } else if cfg!(target_os = "macos") {
+        "libjvm.dylib"
+    } else {
+        "libjvm.so"
+    }
+}
+
+fn candidate_homes() -> Vec<PathBuf> {
+    let mut homes = Vec::new();
+    if let Some(java_home) = env::var_os("JAVA_HOME") {
+        homes.push(PathBuf::from(java_home));
+    }
+    let java = if cfg!(target_os = "windows") { "java.exe" } else { "java" };
+    if let Some(path) = env::var_os("PATH") {
+        for dir in env::split_paths(&path) {
+            if let Ok(java_path) = fs::canonicalize(dir.join(java)) {
+                // The home is the parent of the bin directory
+                if let Some(home) = java_path.parent().and_then(Path::parent) {
+                    homes.push(home.to_path_buf());
+                }
+            }
+        }
+    }
+    let mut install_dirs: Vec<PathBuf> = if cfg!(target_os = "windows") {
+        ["ProgramFiles", "ProgramFiles(x86)"]
+            .iter()
+            .filter_map(env::var_os)
+            .flat_map(|program_files| {
+                let program_files = PathBuf::from(program_files);
+                ["Java", "Eclipse Adoptium", "Eclipse Foundation", "Microsoft", "Zulu", "Amazon Corretto"]
+                    .map(|vendor| program_files.join(vendor))
+            })
+            .collect()
+    } else if cfg!(target_os = "macos") {
+        vec![PathBuf::from("/Library/Java/JavaVirtualMachines")]
+    } else {
+        vec![
+            PathBuf::from("/usr/lib/jvm"),
+            PathBuf::from("/usr/lib64/jvm"),
+            PathBuf::from("/usr/java"),
+            PathBuf::from("/opt/java"),
+        ]
+    };
+    if let Some(user_home) = env::var_os(if cfg!(target_os = "windows") { "USERPROFILE" } else { "HOME" }) {
+        let user_home = PathBuf::from(user_home);
+        install_dirs.push(user_home.join(".sdkman").join("candidates").join("java"));
+        install_dirs.push(user_home.join(".jdks"));
+        if cfg!(target_os = "macos") {
+            install_dirs.push(user_home.join("Library/Java/JavaVirtualMachines"));
+        }
+    }
+    for install_dir in install_dirs {
+        if let Ok(entries) = fs::read_dir(&install_dir) {
+            let mut dirs: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+            dirs.sort();
+            for dir in dirs {
+                // The macOS bundles contain the home in Contents/Home
+                let bundle_home = dir.join("Contents").join("Home");
+                homes.push(if bundle_home.is_dir() { bundle_home } else { dir });
+            }
+        }
+    }
+    homes
+}
+
+// Reads a property of the `release` file of a Java installation, e.g. `JAVA_VERSION="21.0.4"`
+fn release_property(release: &str, name: &str) -> Option<String> {
+    release.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == name).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+#[cfg(test)]
+mod jvm_discovery_unit_tests {
+    use super::*;
+
+    #[test]
+    fn installation_from_home() -> errors::Result<()> {
+        let home = env::temp_dir().join("j4rs_jvm_discovery_test");
+        let lib_dir = home.join("jre").join("lib").join("amd64").join("server");
+        fs::create_dir_all(&lib_dir)?;
+        fs::write(lib_dir.join(jvm_dyn_lib_file_name()), b"jvm")?;
+        fs::write(home.join("release"), "JAVA_VERSION=\"1.8.0_392\"\nOS_ARCH=\"amd64\"\n")?;
+
+        let installation = JvmInstallation::from_home(&home)?;
+        assert_eq!(installation.jvm_dyn_lib(), lib_dir.join(jvm_dyn_lib_file_name()));
+        assert_eq!(installation.version(), Some("1.8.0_392"));
+        assert_eq!(installation.major_version(), Some(8));
+        assert_eq!(installation.arch(), Some("amd64"));
+
+        assert!(JvmInstallation::from_home(home.join("jre").join("lib")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn discover_jvms() {
+        let installations = Jvm::discover_jvms();
+        for installation in &installations {
+            assert!(installation.jvm_dyn_lib().is_file());
+        }
+        let libs: HashSet<PathBuf> = installations
+            .iter()
+            .map(|installation| fs::canonicalize(installation.jvm_dyn_lib()).unwrap())
+            .collect();
+        assert_eq!(libs.len(), installations.len());
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::errors;
+use crate::errors::J4RsError;
+
+/// Typed options for the JVM that is created by the `JvmBuilder`.
+///
+/// The options are validated and translated to the respective Java options when the `Jvm` is built.
+/// Invalid values and options that are defined more than once result in an error during the build.
+#[derive(Debug, Clone, Default)]
+pub struct JvmOptions {
+    heap_min: Option<String>,
+    heap_max: Option<String>,
+    stack_size: Option<String>,
+    module_path: Option<String>,
+    modules: Vec<String>,
+    system_properties: Vec<(String, String)>,
+    errors: Vec<String>,
+}
+
+impl JvmOptions {
+    /// Creates new, empty `JvmOptions`.
+    pub fn new() -> JvmOptions {
+        JvmOptions::default()
+    }
+
+    /// Sets the initial heap size (`-Xms`). The size is a number, optionally followed by `k`, `m` or `g`.
+    pub fn heap_min(mut self, size: &str) -> JvmOptions {
+        self.heap_min = self.validated_size("heap_min", size, self.heap_min.is_some());
+        self
+    }
+
+    /// Sets the maximum heap size (`-Xmx`). The size is a number, optionally followed by `k`, `m` or `g`.
+    pub fn heap_max(mut self, size: &str) -> JvmOptions {
+        self.heap_max = self.validated_size("heap_max", size, self.heap_max.is_some());
+        self
+    }
+
+    /// Sets the thread stack size (`-Xss`). The size is a number, optionally followed by `k`, `m` or `g`.
+    pub fn stack_size(mut self, size: &str) -> JvmOptions {
+        self.stack_size = self.validated_size("stack_size", size, self.stack_size.is_some());
+        self
+    }
+
+    /// Sets the module path (`--module-path`).
+    pub fn module_path(mut self, path: &str) -> JvmOptions {
+        if self.module_path.is_some() {
+            self.errors.push("module_path is defined more than once".to_string());
+        } else if path.trim().is_empty() {
+            self.errors.push("module_path cannot be empty".to_string());
+        } else {
+            self.module_path = Some(path.to_string());
+        }
+        self
+    }
+
+    /// Adds modules to the root modules (`--add-modules`).
+    pub fn add_modules(mut self, modules: &[&str]) -> JvmOptions {
+        for module in modules {
+            if !is_valid_module_name(module) {
+                self.errors.push(format!("'{}' is not a valid module name", module));
+            } else if self.modules.iter().any(|m| m == module) {
+                self.errors.push(format!("Module {} is added more than once", module));
+            } else {
+                self.modules.push(module.to_string());
+            }
+        }
+        self
+    }
+
+    /// Sets a system property (`-Dkey=value`).
+    pub fn system_property(mut self, key: &str, value: &str) -> JvmOptions {
+        if key.is_empty() || key.contains('=') || key.contains(char::is_whitespace) {
+            self.errors.push(format!("'{}' is not a valid system property name", key));
+        } else if self.system_properties.iter().any(|(k, _)| k == key) {
+            self.errors.push(format!("System property {} is defined more than once", key));
+        } else {
+            self.system_properties.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    fn validated_size(&mut self, name: &str, size: &str, already_defined: bool) -> Option<String> {
+        if already_defined {
+            self.errors.push(format!("{} is defined more than once", name));
+            None
+        } else if size_in_bytes(size).is_none() {
+            self.errors.push(format!("'{}' is not a valid size for {}", size, name));
+            None
+        } else {
+            Some(size.to_string())
+        }
+    }
+
+    /// Validates the options and translates them to Java options.
+    pub(crate) fn to_java_opts(&self) -> errors::Result<Vec<String>> {
+        if !self.errors.is_empty() {
+            return Err(J4RsError::GeneralError(format!(
+                "Invalid JVM options: {}",
+                self.errors.join(", ")
+            )));
+        }
+        if let (Some(min), Some(max)) = (&self.heap_min, &self.heap_max) {
+            if size_in_bytes(min) > size_in_bytes(max) {
+                return Err(J4RsError::GeneralError(format!(
+                    "Invalid JVM options: heap_min {} is greater than heap_max {}",
+                    min, max
+                )));
+            }
+        }
+
+        let mut opts = Vec::new();
+        if let Some(size) = &self.heap_min {
+            opts.push(format!("-Xms{}", size));
+        }
+        if let Some(size) = &self.heap_max {
+            opts.push(format!("-Xmx{}", size));
+        }
+        if let Some(size) = &self.stack_size {
+            opts.push(format!("-Xss{}", size));
+        }
+        if let Some(path) = &self.module_path {
+            opts.push(format!("--module-path={}", path));
+        }
+        if !self.modules.is_empty() {
+            opts.push(format!("--add-modules={}", self.modules.join(",")));
+        }
+        for (key, value) in &self.system_properties {
+            opts.push(format!("-D{}={}", key, value));
+        }
+        Ok(opts)
+    }
+}
+
+/// Returns an error if any of the `typed_opts` defines the same setting as another one of the `other_opts`.
+pub(crate) fn check_conflicts(typed_opts: &[String], other_opts: &[String]) -> errors::Result<()> {
+    let typed_keys: HashSet<String> = typed_opts.iter().map(|opt| option_key(opt)).collect();
+    match other_opts.iter().find(|opt| typed_keys.contains(&option_key(opt))) {
+        Some(conflicting) => Err(J4RsError::GeneralError(format!(
+            "Invalid JVM options: {} conflicts with the JvmOptions",
+            conflicting
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Returns true if a Java option looks like a long option whose value is separated with a space instead of `=`.
+///
+/// Such options are not understood when passed while creating the JVM.
+pub(crate) fn is_space_separated(opt: &str) -> bool {
+    opt.starts_with("--") && opt.split('=').next().unwrap_or(opt).contains(char::is_whitespace)
+}
+
+// The part of a Java option that identifies the setting it defines
+fn option_key(opt: &str) -> String {
+    for prefix in ["-Xms", "-Xmx", "-Xss"] {
+        if opt.starts_with(prefix) {
+            return prefix.to_string();
+        }
+    }
+    opt.split(|c: char| c == '=' || c.is_whitespace())
+        .next()
+        .unwrap_or(opt)
+        .to_string()
+}
+
+fn is_valid_module_name(module: &str) -> bool {
+    !module.is_empty()
+        && module
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$'))
+        || module == "ALL-DEFAULT"
+        || module == "ALL-SYSTEM"
+        || module == "ALL-MODULE-PATH"
+}
+
+fn size_in_bytes(size: &str) -> Option<u64> {
+    let (number, multiplier) = match size.chars().last()? {
+        'k' | 'K' => (&size[..size.len() - 1], 1024),
+        'm' | 'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'g' | 'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    number.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod jvm_options_unit_tests {
+    use super::*;
+
+    #[test]
+    fn translate_options() -> errors::Result<()> {
+        let opts = JvmOptions::new()
+            .heap_min("512m")
+            .heap_max("2g")
+            .module_path("/my/modules")
+            .add_modules(&["javafx.base", "javafx.controls"])
+            .system_property("my.prop", "a value")
+            .to_java_opts()?;
+        assert_eq!(
+            opts,
+            vec![
+                "-Xms512m",
+                "-Xmx2g",
+                "--module-path=/my/modules",
+                "--add-modules=javafx.base,javafx.controls",
+                "-Dmy.prop=a value",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_options() {
+        assert!(JvmOptions::new().heap_max("2 g").to_java_opts().is_err());
+        assert!(JvmOptions::new().heap_max("2g").heap_max("1g").to_java_opts().is_err());
+        assert!(JvmOptions::new().heap_min("2g").heap_max("1g").to_java_opts().is_err());
+        assert!(JvmOptions::new().module_path("").to_java_opts().is_err());
+        assert!(JvmOptions::new().add_modules(&["a b"]).to_java_opts().is_err());
+        assert!(JvmOptions::new().add_modules(&["a", "a"]).to_java_opts().is_err());
+        assert!(JvmOptions::new().system_property("a", "1").system_property("a", "2").to_java_opts().is_err());
+        assert!(JvmOptions::new().system_property("a=b", "1").to_java_opts().is_err());
+    }
+
+    #[test]
+    fn conflicting_options() {
+        let typed = JvmOptions::new().heap_max("1g").system_property("a", "b").to_java_opts().unwrap();
+        assert!(check_conflicts(&typed, &["-Xmx2g".to_string()]).is_err());
+        assert!(check_conflicts(&typed, &["-Da=c".to_string()]).is_err());
+        assert!(check_conflicts(&typed, &["-Xms1g".to_string(), "-Db=c".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn space_separated_options() {
+        assert!(is_space_separated("--module-path x"));
+        assert!(!is_space_separated("--module-path=x y"));
+        assert!(!is_space_separated("-Dkey=a value"));
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::iter;
+
+use crate::logger::debug;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const KOTLIN_SUPPORT_CLASS: &str = "org.astonbitecode.j4rs.api.kotlin.KotlinSupport";
+const SUSPEND_CALL_CLASS: &str = "org.astonbitecode.j4rs.api.kotlin.SuspendCall";
+
+impl Jvm {
+    /// Returns the `Instance` of the Kotlin `object` singleton `class_name`.
+    pub fn kotlin_object(&self, class_name: &str) -> errors::Result<Instance> {
+        self.static_class_field(class_name, "INSTANCE")
+    }
+
+    /// Returns the `Instance` of the companion object of the Kotlin class `class_name`.
+    pub fn kotlin_companion(&self, class_name: &str) -> errors::Result<Instance> {
+        let field_name: String = self.to_rust(self.invoke_static(
+            KOTLIN_SUPPORT_CLASS,
+            "companionFieldName",
+            &[InvocationArg::try_from(class_name)?],
+        )?)?;
+        self.static_class_field(class_name, &field_name)
+    }
+
+    /// Invokes the function `method_name` of the companion object of the Kotlin class `class_name`,
+    /// passing an array of `InvocationArg`s.
+    pub fn invoke_companion(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        let companion = self.kotlin_companion(class_name)?;
+        self.invoke(&companion, method_name, inv_args)
+    }
+
+    /// Invokes the Kotlin `suspend` function `method_name` of a created `Instance`, passing an array of `InvocationArg`s,
+    /// and awaits its result.
+    ///
+    /// The `Continuation` of the suspend function is created by j4rs and has an empty coroutine context.
+    /// If the suspend function fails, the returned error contains the respective Java exception.
+    pub async fn invoke_suspend(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking suspend function {} of class {} using {} arguments",
+            method_name,
+            instance.class_name(),
+            inv_args.len()
+        ));
+        let call = self.create_instance(SUSPEND_CALL_CLASS, InvocationArg::empty())?;
+        let continuation = self.invoke(&call, "getContinuation", InvocationArg::empty())?;
+        let continuation = InvocationArg::from(self.cast(&continuation, "kotlin.coroutines.Continuation")?);
+        let args: Vec<&InvocationArg> = inv_args
+            .iter()
+            .map(Borrow::borrow)
+            .chain(iter::once(&continuation))
+            .collect();
+        let returned = self.invoke(instance, method_name, &args)?;
+        self.invoke_async(&call, "future", &[InvocationArg::from(returned)]).await
+    }
+}
+
+#[cfg(test)]
+mod kotlin_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn kotlin_objects() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let object = jvm.kotlin_object("org.astonbitecode.j4rs.tests.MyKotlinObject")?;
+        let greeting: String = jvm.to_rust(jvm.invoke(&object, "greet", &[InvocationArg::try_from("j4rs")?])?)?;
+        assert_eq!(greeting, "Hello j4rs");
+
+        let created = jvm.invoke_companion(
+            "org.astonbitecode.j4rs.tests.MyKotlinClass",
+            "create",
+            &[InvocationArg::try_from("j4rs")?],
+        )?;
+        let name: String = jvm.to_rust(jvm.invoke(&created, "getName", InvocationArg::empty())?)?;
+        assert_eq!(name, "j4rs");
+        assert!(jvm.kotlin_companion("java.lang.String").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::errors::JavaStackFrame;
+use crate::{errors, InvocationArg, Jvm};
+
+pub(crate) const CLASS_JVM_MANAGEMENT: &str = "org.astonbitecode.j4rs.api.management.JvmManagement";
+
+/// Typed access to the management interface (the platform MXBeans) of the JVM.
+///
+/// It is created by calling `Jvm::management`.
+pub struct Management<'a> {
+    jvm: &'a Jvm,
+}
+
+impl<'a> Management<'a> {
+    pub(crate) fn new(jvm: &'a Jvm) -> Management<'a> {
+        Management { jvm }
+    }
+
+    /// Returns the memory usage of the heap.
+    pub fn heap_usage(&self) -> errors::Result<MemoryUsage> {
+        self.call("heapUsage")
+    }
+
+    /// Returns the memory usage of the non-heap memory.
+    pub fn non_heap_usage(&self) -> errors::Result<MemoryUsage> {
+        self.call("nonHeapUsage")
+    }
+
+    /// Returns the statistics of the garbage collectors of the JVM.
+    pub fn garbage_collectors(&self) -> errors::Result<Vec<GarbageCollectorStats>> {
+        self.call("garbageCollectors")
+    }
+
+    /// Returns the number of the live threads, including both daemon and non-daemon threads.
+    pub fn thread_count(&self) -> errors::Result<i32> {
+        self.call("threadCount")
+    }
+
+    /// Returns the number of the live daemon threads.
+    pub fn daemon_thread_count(&self) -> errors::Result<i32> {
+        self.call("daemonThreadCount")
+    }
+
+    /// Returns the number of the classes that are currently loaded.
+    pub fn loaded_class_count(&self) -> errors::Result<i32> {
+        self.call("loadedClassCount")
+    }
+
+    /// Returns the info and the stacktraces of all the live threads.
+    pub fn thread_dump(&self) -> errors::Result<Vec<JavaThreadInfo>> {
+        self.call("threadDump")
+    }
+
+    fn call<T: DeserializeOwned + 'static>(&self, method_name: &str) -> errors::Result<T> {
+        let instance = self
+            .jvm
+            .invoke_static(CLASS_JVM_MANAGEMENT, method_name, InvocationArg::empty())?;
+        self.jvm.to_rust(instance)
+    }
+}
+
+/// A snapshot of the memory usage of a memory area of the JVM, in bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct MemoryUsage {
+    init: Option<i64>,
+    used: i64,
+    committed: i64,
+    max: Option<i64>,
+}
+
+impl MemoryUsage {
+    /// Returns the amount of memory that the JVM initially requested from the operating system, if defined
+    pub fn init(&self) -> Option<i64> {
+        self.init
+    }
+
+    /// Returns the amount of used memory
+    pub fn used(&self) -> i64 {
+        self.used
+    }
+
+    /// Returns the amount of memory that is committed for the JVM to use
+    pub fn committed(&self) -> i64 {
+        self.committed
+    }
+
+    /// Returns the maximum amount of memory that can be used, if defined
+    pub fn max(&self) -> Option<i64> {
+        self.max
+    }
+}
+
+/// The statistics of a garbage collector of the JVM.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GarbageCollectorStats {
+    name: String,
+    collection_count: i64,
+    collection_time_millis: i64,
+}
+
+impl GarbageCollectorStats {
+    /// Returns the name of the garbage collector
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of the collections that have occurred, or -1 if undefined
+    pub fn collection_count(&self) -> i64 {
+        self.collection_count
+    }
+
+    /// Returns the accumulated collection time in milliseconds, or -1 if undefined
+    pub fn collection_time_millis(&self) -> i64 {
+        self.collection_time_millis
+    }
+}
+
+/// The info of a Java thread, as contained in a thread dump.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaThreadInfo {
+    id: i64,
+    name: String,
+    state: String,
+    daemon: bool,
+    stack_trace: Vec<JavaStackFrame>,
+}
+
+impl JavaThreadInfo {
+    /// Returns the ID of the thread
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns the name of the thread
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the state of the thread (e.g. `RUNNABLE`, `WAITING`)
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Returns true if the thread is a daemon thread
+    pub fn is_daemon(&self) -> bool {
+        self.daemon
+    }
+
+    /// Returns the stacktrace of the thread
+    pub fn stack_trace(&self) -> &[JavaStackFrame] {
+        &self.stack_trace
+    }
+}
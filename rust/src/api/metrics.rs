@@ -0,0 +1,313 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::{errors, Jvm};
+
+// Opt-in metrics of the calls to Java, for getting visibility into the JNI overhead
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CREATE_INSTANCE: OperationCounters = OperationCounters::new();
+static INVOKE: OperationCounters = OperationCounters::new();
+static INVOKE_STATIC: OperationCounters = OperationCounters::new();
+static TO_RUST: OperationCounters = OperationCounters::new();
+static GLOBAL_REFS_CREATED: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_REFS_DELETED: AtomicU64 = AtomicU64::new(0);
+static CALLBACK_INSTANCES: AtomicU64 = AtomicU64::new(0);
+
+/// The operations whose counts and latencies are recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operation {
+    CreateInstance,
+    Invoke,
+    InvokeStatic,
+    ToRust,
+}
+
+impl Operation {
+    fn counters(&self) -> &'static OperationCounters {
+        match self {
+            Operation::CreateInstance => &CREATE_INSTANCE,
+            Operation::Invoke => &INVOKE,
+            Operation::InvokeStatic => &INVOKE_STATIC,
+            Operation::ToRust => &TO_RUST,
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::CreateInstance => "create_instance",
+            Operation::Invoke => "invoke",
+            Operation::InvokeStatic => "invoke_static",
+            Operation::ToRust => "to_rust",
+        }
+    }
+}
+
+struct OperationCounters {
+    count: AtomicU64,
+    errors: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl OperationCounters {
+    const fn new() -> OperationCounters {
+        OperationCounters {
+            count: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, elapsed: Duration, failed: bool) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        if failed {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> OperationMetrics {
+        OperationMetrics {
+            count: self.count.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    fn reset(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.errors.store(0, Ordering::Relaxed);
+        self.total_nanos.store(0, Ordering::Relaxed);
+        self.max_nanos.store(0, Ordering::Relaxed);
+    }
+}
+
+/// The count and the latencies of the calls of an operation, as recorded by the metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OperationMetrics {
+    count: u64,
+    errors: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl OperationMetrics {
+    /// Returns the number of calls
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the number of calls that failed
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Returns the total time that the calls took
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the time that the slowest call took
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the mean time that the calls took, or zero if there were no calls
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+        }
+    }
+}
+
+/// A snapshot of the metrics of the calls to Java, as returned by `Jvm::metrics_snapshot`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetricsSnapshot {
+    create_instance: OperationMetrics,
+    invoke: OperationMetrics,
+    invoke_static: OperationMetrics,
+    to_rust: OperationMetrics,
+    global_refs_created: u64,
+    global_refs_deleted: u64,
+    callback_instances: u64,
+}
+
+impl MetricsSnapshot {
+    /// Returns the metrics of `Jvm::create_instance`
+    pub fn create_instance(&self) -> &OperationMetrics {
+        &self.create_instance
+    }
+
+    /// Returns the metrics of `Jvm::invoke`
+    pub fn invoke(&self) -> &OperationMetrics {
+        &self.invoke
+    }
+
+    /// Returns the metrics of `Jvm::invoke_static`
+    pub fn invoke_static(&self) -> &OperationMetrics {
+        &self.invoke_static
+    }
+
+    /// Returns the metrics of the transformations of Instances to Rust values (`Jvm::to_rust` and the like)
+    pub fn to_rust(&self) -> &OperationMetrics {
+        &self.to_rust
+    }
+
+    /// Returns the number of JNI global references that were created by j4rs
+    pub fn global_refs_created(&self) -> u64 {
+        self.global_refs_created
+    }
+
+    /// Returns the number of JNI global references that were deleted by j4rs
+    pub fn global_refs_deleted(&self) -> u64 {
+        self.global_refs_deleted
+    }
+
+    /// Returns the number of Instances that Java sent to Rust channels via callbacks
+    pub fn callback_instances(&self) -> u64 {
+        self.callback_instances
+    }
+}
+
+impl Jvm {
+    /// Enables or disables the metrics of the calls to Java.
+    ///
+    /// When enabled, the counts and latencies of `create_instance`, `invoke`, `invoke_static` and `to_rust`,
+    /// the JNI global references that are created and deleted and the Instances that are sent via callback channels
+    /// are recorded and can be retrieved with `metrics_snapshot`.
+    /// With the `metrics` feature, they are also reported to the recorder that is installed for the `metrics` crate.
+    ///
+    /// The metrics apply to all the `Jvm`s of the process. They add some overhead and are disabled by default.
+    pub fn set_metrics_enabled(&self, enabled: bool) {
+        ENABLED.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Returns the metrics that are recorded since they were enabled, or since the last `reset_metrics`.
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            create_instance: CREATE_INSTANCE.snapshot(),
+            invoke: INVOKE.snapshot(),
+            invoke_static: INVOKE_STATIC.snapshot(),
+            to_rust: TO_RUST.snapshot(),
+            global_refs_created: GLOBAL_REFS_CREATED.load(Ordering::Relaxed),
+            global_refs_deleted: GLOBAL_REFS_DELETED.load(Ordering::Relaxed),
+            callback_instances: CALLBACK_INSTANCES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets the recorded metrics to zero.
+    pub fn reset_metrics(&self) {
+        for counters in [&CREATE_INSTANCE, &INVOKE, &INVOKE_STATIC, &TO_RUST] {
+            counters.reset();
+        }
+        GLOBAL_REFS_CREATED.store(0, Ordering::Relaxed);
+        GLOBAL_REFS_DELETED.store(0, Ordering::Relaxed);
+        CALLBACK_INSTANCES.store(0, Ordering::Relaxed);
+    }
+}
+
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Calls `f`, which performs an operation, recording its latency and whether it failed, if the metrics are enabled.
+pub(crate) fn timed<T>(operation: Operation, f: impl FnOnce() -> errors::Result<T>) -> errors::Result<T> {
+    if !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    operation.counters().record(elapsed, result.is_err());
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!("j4rs_calls_total", "operation" => operation.name()).increment(1);
+        if result.is_err() {
+            ::metrics::counter!("j4rs_call_errors_total", "operation" => operation.name()).increment(1);
+        }
+        ::metrics::histogram!("j4rs_call_duration_seconds", "operation" => operation.name())
+            .record(elapsed.as_secs_f64());
+    }
+    result
+}
+
+pub(crate) fn global_ref_created() {
+    if is_enabled() {
+        GLOBAL_REFS_CREATED.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("j4rs_global_refs_created_total").increment(1);
+    }
+}
+
+pub(crate) fn global_ref_deleted() {
+    if is_enabled() {
+        GLOBAL_REFS_DELETED.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("j4rs_global_refs_deleted_total").increment(1);
+    }
+}
+
+pub(crate) fn callback_instance_sent() {
+    if is_enabled() {
+        CALLBACK_INSTANCES.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("j4rs_callback_instances_total").increment(1);
+    }
+}
+
+#[cfg(test)]
+mod metrics_unit_tests {
+    use super::*;
+    use crate::api::instance::instance_channel;
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::InvocationArg;
+
+    #[test]
+    fn record_metrics() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        jvm.set_metrics_enabled(true);
+        // Other tests that run in parallel may be recorded too, so only lower bounds are checked
+        let before = jvm.metrics_snapshot();
+        let builder = jvm.create_instance("java.lang.StringBuilder", &[InvocationArg::try_from("abc")?])?;
+        let reversed = jvm.invoke(&builder, "toString", InvocationArg::empty())?;
+        let _: String = jvm.to_rust(reversed)?;
+        let failed = jvm.invoke_static("java.lang.Integer", "parseInt", &[InvocationArg::try_from("NaN")?]);
+        assert!(failed.is_err());
+        let after = jvm.metrics_snapshot();
+
+        assert!(after.create_instance().count() > before.create_instance().count());
+        assert!(after.invoke().count() > before.invoke().count());
+        assert!(after.invoke_static().errors() > before.invoke_static().errors());
+        assert!(after.to_rust().count() > before.to_rust().count());
+        assert!(after.global_refs_created() > before.global_refs_created());
+        assert!(after.invoke().max() > Duration::ZERO);
+
+        let (sender, rx, _) = instance_channel();
+        sender.send(jvm.create_instance("java.lang.Object", InvocationArg::empty())?);
+        let _ = rx.recv();
+        assert!(jvm.metrics_snapshot().callback_instances() > after.callback_instances());
+        Ok(())
+    }
+}
@@ -16,29 +16,48 @@ use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::env;
+use std::marker::PhantomData;
 use std::ops::Drop;
 use std::os::raw::c_void;
 use std::path::{Path, PathBuf};
 use std::ptr;
-use std::sync::mpsc::channel;
 use std::{fs, thread, time};
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 
 use jni_sys::{
-    self, jint, jobject, jsize, jstring, JNIEnv, JavaVM, JavaVMInitArgs, JavaVMOption,
+    self, jint, jlong, jmethodID, jobject, jsize, jstring, jvalue, JNIEnv, JavaVM, JavaVMInitArgs, JavaVMOption,
     JNI_EDETACHED, JNI_EEXIST, JNI_EINVAL, JNI_ENOMEM, JNI_ERR, JNI_EVERSION, JNI_OK, JNI_TRUE,
     JNI_VERSION_1_6,
 };
 use libc::c_char;
 use serde::de::DeserializeOwned;
-
-use instance::{ChainableInstance, Instance, InstanceReceiver};
+use serde::Serialize;
+
+use actor::JvmActor;
+use class_scope::{ClassScope, CLASS_SCOPE_CLASS};
+use codec::SerializationFormat;
+use eval::EvalContext;
+use frame::LocalFrame;
+use hooks::InvocationKind;
+use instance::{
+    AsyncInstanceReceiver, ChainableInstance, ChannelOverflow, Instance, InstanceChannel, InstanceReceiver,
+    TypedInstanceReceiver,
+};
+use iterator::{ChunkedToRustIterator, JavaIterator, JavaToRustIterator};
+use jvm_discovery::JvmInstallation;
+use jvm_options::JvmOptions;
+use management::{JavaThreadInfo, Management};
+use metrics::Operation;
+use prepared::PreparedInvocation;
+use primitives::{JavaPrimitive, PrimitiveArg};
+use remote::{RemoteCommand, RemoteJvm};
+use shared::{SendableJvm, SharedJvm};
 
 use crate::{errors, set_java_vm};
-use crate::errors::{opt_to_res, J4RsError};
+use crate::errors::{opt_to_res, J4RsError, JavaException};
 use crate::jni_utils;
 use crate::provisioning;
-use crate::provisioning::{get_maven_settings, JavaArtifact, LocalJarArtifact, MavenArtifact};
+use crate::provisioning::{get_maven_settings, IvyArtifact, JavaArtifact, LocalJarArtifact, MavenArtifact, SettingsXml};
 use crate::utils;
 use crate::{api_tweaks as tweaks, cache, InvocationArg, MavenSettings};
 
@@ -46,12 +65,58 @@ use self::tweaks::cache_classloader_of;
 
 use super::logger::{debug, error, info, warn};
 
+pub(crate) mod actor;
+pub(crate) mod bean;
+pub(crate) mod class_scope;
+pub(crate) mod cleanup;
+pub(crate) mod cluster;
+pub(crate) mod codec;
+pub(crate) mod coercion;
+pub(crate) mod context;
+pub(crate) mod frame;
+pub(crate) mod eval;
+pub(crate) mod exceptions;
+pub(crate) mod fields;
+pub(crate) mod functional;
+pub(crate) mod hooks;
 pub(crate) mod instance;
 pub(crate) mod invocation_arg;
+pub(crate) mod invocation_result;
+pub(crate) mod iterator;
+pub(crate) mod jassets;
+#[cfg(feature = "jni-interop")]
+pub(crate) mod jni_interop;
+#[cfg(feature = "provision-jre")]
+pub(crate) mod jre;
+pub(crate) mod jvm_config;
+pub(crate) mod jvm_discovery;
+pub(crate) mod jvm_options;
+pub(crate) mod kotlin;
+pub(crate) mod management;
+pub(crate) mod metrics;
+#[cfg(feature = "native-image")]
+pub(crate) mod native_image;
+pub(crate) mod prepared;
+pub(crate) mod primitives;
+pub(crate) mod proxy;
+pub(crate) mod publisher;
+pub(crate) mod reflection;
+pub(crate) mod remote;
+pub(crate) mod scala;
+pub(crate) mod shared;
+pub(crate) mod shutdown;
+pub(crate) mod std_streams;
+pub(crate) mod stream;
+pub(crate) mod threads;
+pub(crate) mod tracking;
+pub(crate) mod tuple;
+pub(crate) mod typed_instance;
 
 // Initialize the environment
 include!(concat!(env!("OUT_DIR"), "/j4rs_init.rs"));
 
+// The default number of elements of the chunks that are transferred by `Jvm::to_rust_stream`
+const DEFAULT_CHUNK_SIZE: usize = 1000;
 const CLASS_STRING: &str = "java.lang.String";
 const CLASS_BOOLEAN: &str = "java.lang.Boolean";
 const CLASS_BYTE: &str = "java.lang.Byte";
@@ -62,6 +127,20 @@ const CLASS_LONG: &str = "java.lang.Long";
 const CLASS_FLOAT: &str = "java.lang.Float";
 const CLASS_DOUBLE: &str = "java.lang.Double";
 const CLASS_LIST: &str = "java.util.List";
+const CLASS_MAP: &str = "java.util.Map";
+const CLASS_BIG_DECIMAL: &str = "java.math.BigDecimal";
+const CLASS_BIG_INTEGER: &str = "java.math.BigInteger";
+const CLASS_BYTE_BUFFER: &str = "java.nio.ByteBuffer";
+const CLASS_RUST_LOG_HANDLER: &str = "org.astonbitecode.j4rs.api.logging.RustLogHandler";
+const CLASS_INVOCATION_TIMEOUT_EXCEPTION: &str = "org.astonbitecode.j4rs.errors.InvocationTimeoutException";
+const CLASS_EDT_INVOCATION: &str = "org.astonbitecode.j4rs.api.invocation.EdtInvocation";
+const CLASS_MEMBER_ACCESS: &str = "org.astonbitecode.j4rs.api.invocation.MemberAccess";
+const CLASS_INSTANT: &str = "java.time.Instant";
+const CLASS_DURATION: &str = "java.time.Duration";
+const CLASS_LOCAL_DATE_TIME: &str = "java.time.LocalDateTime";
+const CLASS_ZONE_OFFSET: &str = "java.time.ZoneOffset";
+#[cfg(feature = "uuid")]
+const CLASS_UUID: &str = "java.util.UUID";
 pub(crate) const PRIMITIVE_BOOLEAN: &str = "boolean";
 pub(crate) const PRIMITIVE_BYTE: &str = "byte";
 pub(crate) const PRIMITIVE_SHORT: &str = "short";
@@ -84,28 +163,60 @@ pub(crate) const CLASS_NATIVE_CALLBACK_TO_RUST_CHANNEL_SUPPORT: &str =
     "org.astonbitecode.j4rs.api.invocation.NativeCallbackToRustChannelSupport";
 pub(crate) const CLASS_J4RS_EVENT_HANDLER: &str =
     "org.astonbitecode.j4rs.api.jfx.handlers.J4rsEventHandler";
+pub(crate) const CLASS_J4RS_CHANGE_LISTENER: &str =
+    "org.astonbitecode.j4rs.api.jfx.handlers.J4rsChangeListener";
 pub(crate) const CLASS_J4RS_FXML_LOADER: &str =
     "org.astonbitecode.j4rs.api.jfx.J4rsFxmlLoader";
+pub(crate) const CLASS_NATIVE_INVOCATION_HANDLER: &str =
+    "org.astonbitecode.j4rs.api.invocation.NativeInvocationHandler";
+pub(crate) const CLASS_NATIVE_INSTANTIATION: &str =
+    "org.astonbitecode.j4rs.api.instantiation.NativeInstantiationImpl";
+pub(crate) const CLASS_JAVA_SERIALIZATION: &str = "org.astonbitecode.j4rs.api.io.JavaSerialization";
+pub(crate) const CLASS_RESOURCES: &str = "org.astonbitecode.j4rs.api.io.Resources";
+pub(crate) const CLASS_RUST_FUNCTIONS: &str = "org.astonbitecode.j4rs.api.invocation.RustFunctions";
+pub(crate) const CLASS_RUST_FUNCTIONS_CALLBACK: &str =
+    "org.astonbitecode.j4rs.api.invocation.RustFunctions$Callback";
+pub(crate) const CLASS_TUPLE2: &str = "org.astonbitecode.j4rs.api.dtos.Tuple2";
+pub(crate) const CLASS_TUPLE3: &str = "org.astonbitecode.j4rs.api.dtos.Tuple3";
 pub const _JNI_VERSION_10: jint = 0x000a0000;
 
 pub type Callback = fn(Jvm, Instance) -> ();
 
 /// Holds the assets for the JVM
-#[derive(Clone)]
+///
+/// A `Jvm` is bound to the thread that created it and it is neither `Send` nor `Sync`.
+/// `Jvm::to_sendable` returns a token that can be moved to another thread and attached there:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<j4rs::Jvm>();
+/// ```
 pub struct Jvm {
     pub(crate) jni_env: *mut JNIEnv,
     detach_thread_on_drop: bool,
+    // Keeps the Jvm !Send and !Sync, regardless of the types of the other fields
+    _not_send: PhantomData<*mut ()>,
 }
 
 impl Jvm {
     /// Creates a new Jvm.
     pub fn new(jvm_options: &[String], lib_name_to_load: Option<String>) -> errors::Result<Jvm> {
-        Self::create_jvm(jvm_options, lib_name_to_load)
+        Self::create_jvm(jvm_options, lib_name_to_load, false)
     }
 
     /// Attaches the current thread to an active JavaVM
     pub fn attach_thread() -> errors::Result<Jvm> {
-        Self::create_jvm(&[], None)
+        Self::create_jvm(&[], None, false)
+    }
+
+    /// Attaches the current thread to an active JavaVM as a daemon thread.
+    ///
+    /// Unlike the threads that are attached with `attach_thread`, daemon threads do not prevent the JavaVM from shutting down.
+    /// This is useful for worker threads of Rust thread pools.
+    ///
+    /// If the current thread is already attached, it remains attached the way it was.
+    pub fn attach_thread_as_daemon() -> errors::Result<Jvm> {
+        Self::create_jvm(&[], None, true)
     }
 
     /// Attaches the current thread to an active JavaVM and instructs that the Jvm will detach the Java JVM
@@ -128,9 +239,56 @@ impl Jvm {
         self.detach_thread_on_drop = detach;
     }
 
+    /// Destroys the JavaVM, calling the JNI `DestroyJavaVM`.
+    ///
+    /// The call blocks until all the non-daemon Java threads have terminated.
+    /// After the JavaVM is destroyed, the cached classes and methods are invalidated
+    /// and any attempt to create or attach a `Jvm` returns an error.
+    /// Please note that a destroyed JavaVM cannot be created again in the same process.
+    ///
+    /// While the JavaVM is being destroyed, the shutdown hooks and the remaining threads may still attach `Jvm`s.
+    pub fn destroy(mut self) -> errors::Result<()> {
+        debug("Destroying the JavaVM");
+        let java_vm = {
+            let _g = cache::MUTEX.lock()?;
+            if cache::is_jvm_destroyed() {
+                return Err(Self::destroyed_error());
+            }
+            let java_vm = Self::created_java_vm().ok_or_else(|| {
+                J4RsError::JniError("Could not destroy the JVM: No created JavaVM was found".to_string())
+            })?;
+            if !cache::set_jvm_destroying() {
+                return Err(J4RsError::GeneralError("The JVM is already being destroyed".to_string()));
+            }
+            java_vm
+        };
+        // The thread does not need detaching after the JavaVM is destroyed
+        self.detach_thread_on_drop(false);
+        // DestroyJavaVM waits for the shutdown hooks and the non-daemon threads, which may need to
+        // create Jvms. Therefore, it is called without holding the lock.
+        let result = unsafe { ((**java_vm).v1_4.DestroyJavaVM)(java_vm) };
+        let _g = cache::MUTEX.lock()?;
+        cache::unset_jvm_destroying();
+        if result != JNI_OK {
+            return Err(J4RsError::JniError(format!(
+                "Could not destroy the JVM: JNI error {}",
+                result
+            )));
+        }
+        cache::set_jvm_destroyed();
+        cleanup::clear();
+        cache::invalidate_thread_local_cache();
+        Ok(())
+    }
+
+    fn destroyed_error() -> J4RsError {
+        J4RsError::GeneralError("The JVM has been destroyed and cannot be used anymore".to_string())
+    }
+
     /// Creates a new Jvm.
-    /// If a JavaVM is already created by the current process, it attempts to attach the current thread to it.
-    fn create_jvm(jvm_options: &[String], lib_name_to_load: Option<String>) -> errors::Result<Jvm> {
+    /// If a JavaVM is already created by the current process, it attempts to attach the current thread to it,
+    /// as a daemon thread if `as_daemon` is true.
+    fn create_jvm(jvm_options: &[String], lib_name_to_load: Option<String>, as_daemon: bool) -> errors::Result<Jvm> {
         debug("Creating a Jvm");
         let mut jvm: *mut JavaVM = ptr::null_mut();
         let mut jni_environment: *mut JNIEnv = ptr::null_mut();
@@ -138,13 +296,17 @@ impl Jvm {
         // Create the Jvm atomically
         let _g = cache::MUTEX.lock()?;
 
+        if cache::is_jvm_destroyed() {
+            return Err(Self::destroyed_error());
+        }
+
         let result = if let Some(env) = cache::get_thread_local_env_opt() {
             debug("A JVM is already created for this thread. Retrieving it...");
             jni_environment = env;
 
             JNI_OK
         } else {
-            let created_vm = Self::get_created_vm();
+            let created_vm = Self::get_created_vm(as_daemon);
 
             let res_int = if created_vm.is_some() {
                 debug("A JVM is already created by another thread. Retrieving it...");
@@ -225,6 +387,9 @@ impl Jvm {
     }
 
     pub unsafe fn try_from(jni_environment: *mut JNIEnv) -> errors::Result<Jvm> {
+        if cache::is_jvm_destroyed() {
+            return Err(Self::destroyed_error());
+        }
         if cache::get_thread_local_env_opt().is_none() {
             // Create and set the environment in Thread Local
             let _ = cache::get_jni_get_method_id().or_else(|| {
@@ -285,6 +450,11 @@ impl Jvm {
                     (**jni_environment).v1_6.CallStaticObjectMethod,
                 ))
             });
+            let _ = cache::get_jni_call_static_int_method().or_else(|| {
+                cache::set_jni_call_static_int_method(Some(
+                    (**jni_environment).v1_6.CallStaticIntMethod,
+                ))
+            });
             let _ = cache::get_jni_get_array_length().or_else(|| {
                 cache::set_jni_get_array_length(Some(
                     (**jni_environment).v1_6.GetArrayLength,
@@ -403,6 +573,24 @@ impl Jvm {
                 .or_else(|| cache::set_jni_throw_new(Some((**jni_environment).v1_6.ThrowNew)));
             let _ = cache::get_is_same_object()
                 .or_else(|| cache::set_is_same_object(Some((**jni_environment).v1_6.IsSameObject)));
+            let _ = cache::get_jni_new_byte_array().or_else(|| {
+                cache::set_jni_new_byte_array(Some((**jni_environment).v1_6.NewByteArray))
+            });
+            let _ = cache::get_jni_set_byte_array_region().or_else(|| {
+                cache::set_jni_set_byte_array_region(Some((**jni_environment).v1_6.SetByteArrayRegion))
+            });
+            let _ = cache::get_jni_get_byte_array_region().or_else(|| {
+                cache::set_jni_get_byte_array_region(Some((**jni_environment).v1_6.GetByteArrayRegion))
+            });
+            let _ = cache::get_jni_new_direct_byte_buffer().or_else(|| {
+                cache::set_jni_new_direct_byte_buffer(Some((**jni_environment).v1_6.NewDirectByteBuffer))
+            });
+            let _ = cache::get_jni_get_direct_buffer_address().or_else(|| {
+                cache::set_jni_get_direct_buffer_address(Some((**jni_environment).v1_6.GetDirectBufferAddress))
+            });
+            let _ = cache::get_jni_get_direct_buffer_capacity().or_else(|| {
+                cache::set_jni_get_direct_buffer_capacity(Some((**jni_environment).v1_6.GetDirectBufferCapacity))
+            });
 
             match (ec, ed, exclear) {
                 (Some(ec), Some(ed), Some(exclear)) => {
@@ -416,10 +604,12 @@ impl Jvm {
                         let jvm = Jvm {
                             jni_env: jni_environment,
                             detach_thread_on_drop: true,
+                            _not_send: PhantomData,
                         };
 
                         cache::set_thread_local_env(Some(jni_environment));
                         cache::add_active_jvm();
+                        cleanup::delete_pending(jni_environment);
 
                         Ok(jvm)
                     }
@@ -431,10 +621,12 @@ impl Jvm {
             let jvm = Jvm {
                 jni_env: jni_environment,
                 detach_thread_on_drop: true,
+                _not_send: PhantomData,
             };
 
             cache::set_thread_local_env(Some(jni_environment));
             cache::add_active_jvm();
+            cleanup::delete_pending(jni_environment);
 
             Ok(jvm)
         }
@@ -451,72 +643,113 @@ impl Jvm {
             class_name,
             inv_args.len()
         ));
+        metrics::timed(Operation::CreateInstance, || hooks::with_hook(InvocationKind::CreateInstance, class_name, "<init>", inv_args, || unsafe {
+            let java_instance = self.do_create_instance(class_name, inv_args)?;
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
+
+            // Create and return the Instance
+            Self::do_return(
+                self.jni_env,
+                Instance::new(java_instance_global_instance, class_name)?,
+            )
+        }))
+    }
+
+    /// Creates an instance of the class `class_name` and returns the local reference of it.
+    pub(crate) unsafe fn do_create_instance(
+        &self,
+        class_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<jobject> {
+        // Factory invocation - first argument: create a jstring to pass as argument for the class_name
+        let class_name_jstring: jstring =
+            jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+
+        // Factory invocation - rest of the arguments: Create a new objectarray of class InvocationArg
+        let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
+
+        // Call the method of the factory that instantiates a new class of `class_name`.
+        // This returns a Instance that acts like a proxy to the Java world.
+        let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            self.jni_env,
+            cache::get_factory_class()?,
+            cache::get_factory_instantiate_method()?,
+            class_name_jstring,
+            array_ptr,
+        );
+        // Check for exceptions before deleting any refs
+        let result = Self::do_return(self.jni_env, java_instance);
+
+        // Prevent memory leaks from the created references
+        self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+        jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+
+        result
+    }
+
+    /// Creates an instance of the class `class_name`, using the constructor with the declared parameter types `param_types`.
+    ///
+    /// The parameter types are fully qualified class names, or names of primitives (e.g. `int`).
+    /// Non-public constructors can be used as well, if the access to them is enabled with `set_accessible`.
+    pub fn invoke_constructor_with_signature(
+        &self,
+        class_name: &str,
+        param_types: &[&str],
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Instantiating class {} with signature {:?}",
+            class_name, param_types
+        ));
         unsafe {
-            // Factory invocation - first argument: create a jstring to pass as argument for the class_name
             let class_name_jstring: jstring =
                 jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+            let param_types_array = jni_utils::global_jobject_array_from_strs(param_types, self.jni_env)?;
+            let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
 
-            // Factory invocation - rest of the arguments: Create a new objectarray of class InvocationArg
-            let size = inv_args.len() as i32;
-            let array_ptr = {
-                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
-                    self.jni_env,
-                    size,
-                    cache::get_invocation_arg_class()?,
-                    ptr::null_mut(),
-                );
-                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
-            };
-            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
-
-            // Factory invocation - rest of the arguments: populate the array
-            for i in 0..size {
-                // Create an InvocationArg Java Object
-                let inv_arg_java =
-                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
-                // Set it in the array
-                (opt_to_res(cache::get_jni_set_object_array_element())?)(
-                    self.jni_env,
-                    array_ptr,
-                    i,
-                    inv_arg_java,
-                );
-                inv_arg_jobjects.push(inv_arg_java);
-            }
-            // Call the method of the factory that instantiates a new class of `class_name`.
-            // This returns a Instance that acts like a proxy to the Java world.
             let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
                 self.jni_env,
                 cache::get_factory_class()?,
-                cache::get_factory_instantiate_method()?,
+                cache::get_factory_instantiate_with_signature_method()?,
                 class_name_jstring,
+                param_types_array,
                 array_ptr,
             );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_instance);
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
-
-            let java_instance_global_instance =
-                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
-            // Prevent memory leaks from the created local references
-            jni_utils::delete_java_ref(self.jni_env, array_ptr);
+            // Prevent memory leaks from the created references
+            self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+            jni_utils::delete_java_ref(self.jni_env, param_types_array);
             jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
-            for inv_arg_jobject in inv_arg_jobjects {
-                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
-            }
 
-            // Create and return the Instance
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(result?, self.jni_env)?;
             Self::do_return(
                 self.jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: class_name.to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, class_name)?,
             )
         }
     }
 
+    /// Enables or disables the invocation of non-public constructors and methods.
+    ///
+    /// The access is disabled by default. When enabled, `invoke_constructor_with_signature`, `invoke`,
+    /// `invoke_with_signature` and their static variants can use private, protected and package-private members,
+    /// which are made accessible with `setAccessible` on the Java side.
+    /// The members of the JDK modules that are not opened to the classpath remain inaccessible.
+    ///
+    /// The setting applies to the whole JVM and therefore to all the `Jvm`s of the process.
+    pub fn set_accessible(&self, accessible: bool) -> errors::Result<()> {
+        self.invoke_static(
+            CLASS_MEMBER_ACCESS,
+            "setEnabled",
+            &[InvocationArg::try_from(accessible)?.into_primitive()?],
+        )?;
+        Ok(())
+    }
+
     /// Retrieves the static class `class_name`.
     pub fn static_class(&self, class_name: &str) -> errors::Result<Instance> {
         debug(&format!("Retrieving static class {}", class_name));
@@ -534,7 +767,10 @@ impl Jvm {
                 class_name_jstring,
             );
 
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, ());
             jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+            result?;
 
             // Create and return the Instance.
             Self::do_return(
@@ -558,6 +794,40 @@ impl Jvm {
             inv_args.len()
         ));
         unsafe {
+            self.do_create_java_array(class_name, inv_args, cache::get_factory_create_java_array_method()?)
+        }
+    }
+
+    /// Creates a new two-dimensional Java Array with elements of the class `class_name`.
+    ///
+    /// Each one of the `rows` becomes an array of `class_name`, like in `create_java_array`.
+    /// For example, a `double[][]` is created by passing `double` as `class_name` and rows of primitive `double`s.
+    /// The rows may have different lengths.
+    pub fn create_java_2d_array(
+        &self,
+        class_name: &str,
+        rows: &[&[InvocationArg]],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Creating a two-dimensional java array of class {} with {} rows",
+            class_name,
+            rows.len()
+        ));
+        let row_arrays = rows
+            .iter()
+            .map(|row| self.create_java_array(class_name, row).map(InvocationArg::from))
+            .collect::<errors::Result<Vec<InvocationArg>>>()?;
+        self.create_java_array(&array_class_name(class_name), &row_arrays)
+    }
+
+    /// Creates a new Java Array with elements of the class `class_name`, using the factory method `create_method`.
+    unsafe fn do_create_java_array(
+        &self,
+        class_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+        create_method: jmethodID,
+    ) -> errors::Result<Instance> {
+        {
             // Factory invocation - first argument: create a jstring to pass as argument for the class_name
             let class_name_jstring: jstring =
                 jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
@@ -594,7 +864,7 @@ impl Jvm {
             let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
                 self.jni_env,
                 cache::get_factory_class()?,
-                cache::get_factory_create_java_array_method()?,
+                create_method,
                 class_name_jstring,
                 array_ptr,
             );
@@ -614,11 +884,7 @@ impl Jvm {
             // Create and return the Instance
             Self::do_return(
                 self.jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: class_name.to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, class_name)?,
             )
         }
     }
@@ -712,11 +978,7 @@ impl Jvm {
             // Create and return the Instance
             Self::do_return(
                 jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: class_name.to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, class_name)?,
             )
         }
     }
@@ -754,6 +1016,38 @@ impl Jvm {
         )
     }
 
+    /// Creates a new Java Map from the `entries`, which are transferred to Java as JSON.
+    ///
+    /// Unlike `java_map`, the keys and values may be of any type that implements `Serialize`, like structs or maps.
+    /// They are deserialized to the classes `key_class_name` and `value_class_name` in the Java world.
+    /// If the class is `java.lang.Object`, they are kept as they are deserialized from JSON
+    /// (e.g. `String`s, `Number`s, `List`s and `Map`s).
+    /// The order of the `entries` is preserved.
+    pub fn java_typed_map<K, V>(
+        &self,
+        key_class_name: &str,
+        value_class_name: &str,
+        entries: impl IntoIterator<Item = (K, V)>,
+    ) -> errors::Result<Instance>
+        where
+            K: Serialize,
+            V: Serialize,
+    {
+        let entries: Vec<(K, V)> = entries.into_iter().collect();
+        let json = serde_json::to_string(&entries)?;
+        let mut map = self.invoke_static(
+            CLASS_NATIVE_INSTANTIATION,
+            "createJavaMapFromJson",
+            &[
+                InvocationArg::try_from(key_class_name)?,
+                InvocationArg::try_from(value_class_name)?,
+                InvocationArg::try_from(json)?,
+            ],
+        )?;
+        map.class_name = CLASS_MAP.to_string();
+        Ok(map)
+    }
+
     fn do_create_java_map(
         jni_env: *mut JNIEnv,
         key_class_name: &str,
@@ -828,11 +1122,7 @@ impl Jvm {
             // Create and return the Instance
             Self::do_return(
                 jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: "".to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, "")?,
             )
         }
     }
@@ -850,1590 +1140,4859 @@ impl Jvm {
             instance.class_name,
             inv_args.len()
         ));
+        metrics::timed(Operation::Invoke, || hooks::with_hook(InvocationKind::Invoke, &instance.class_name, method_name, inv_args, || unsafe {
+            let java_instance = self.do_invoke(instance, method_name, inv_args)?;
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
+
+            // Create and return the Instance
+            Self::do_return(
+                self.jni_env,
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
+            )
+        }))
+    }
+
+    /// Invokes the method `method_name` of a created `Instance`, forcing the declared parameter types of the method.
+    ///
+    /// This is useful when the method is overloaded and the method selection by the types of the `InvocationArg`s
+    /// is ambiguous (e.g. `foo(Object)` vs `foo(String)`).
+    ///
+    /// The `param_types` are the fully qualified class names of the parameters (e.g. `java.lang.String`),
+    /// or the names of the primitives (e.g. `int`).
+    pub fn invoke_with_signature(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        param_types: &[&str],
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking method {} of class {} with signature {:?}",
+            method_name,
+            instance.class_name,
+            param_types
+        ));
         unsafe {
-            // First argument: create a jstring to pass as argument for the method_name
-            let method_name_jstring: jstring =
-                jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let java_instance =
+                self.do_invoke_with_param_types(instance, method_name, Some(param_types), inv_args)?;
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
 
-            // Rest of the arguments: Create a new objectarray of class InvocationArg
-            let size = inv_args.len() as i32;
-            let array_ptr = {
-                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
-                    self.jni_env,
-                    size,
-                    cache::get_invocation_arg_class()?,
-                    ptr::null_mut(),
-                );
-                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
-            };
-            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
+            // Create and return the Instance
+            Self::do_return(
+                self.jni_env,
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
+            )
+        }
+    }
 
-            // Rest of the arguments: populate the array
-            for i in 0..size {
-                // Create an InvocationArg Java Object
-                let inv_arg_java =
-                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
-                // Set it in the array
-                (opt_to_res(cache::get_jni_set_object_array_element())?)(
-                    self.jni_env,
-                    array_ptr,
-                    i,
-                    inv_arg_java,
-                );
-                inv_arg_jobjects.push(inv_arg_java);
-            }
+    /// Invokes the static method `method_name` of the class `class_name`, forcing the declared parameter types of the method.
+    ///
+    /// See `invoke_with_signature`.
+    pub fn invoke_static_with_signature(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        param_types: &[&str],
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        let static_instance = self.static_class(class_name)?;
+        self.invoke_with_signature(&static_instance, method_name, param_types, inv_args)
+    }
+
+    /// Invokes the method `method_name` that is declared in the interface `interface_name`, or in its superinterfaces.
+    ///
+    /// The method is resolved using the interface instead of the class of the `Instance`. This allows invoking
+    /// default interface methods, as well as methods of `Instance`s whose classes are not accessible
+    /// (e.g. private classes that implement a public interface, like the ones returned by `Collections.unmodifiableMap`).
+    ///
+    /// Static interface methods can be invoked by passing an `Instance` that is retrieved with `static_class`.
+    pub fn invoke_on_interface(
+        &self,
+        instance: &Instance,
+        interface_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking method {} of interface {} on class {}",
+            method_name, interface_name, instance.class_name
+        ));
+        unsafe {
+            let interface_name_jstring = jni_utils::global_jobject_from_str(interface_name, self.jni_env)?;
+            let method_name_jstring = jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
 
-            // Call the method of the instance
             let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_invoke_method()?,
+                cache::get_invoke_on_interface_method()?,
+                interface_name_jstring,
                 method_name_jstring,
                 array_ptr,
             );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_instance);
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
-
-            let java_instance_global_instance =
-                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
-            // Prevent memory leaks from the created local references
-            for inv_arg_jobject in inv_arg_jobjects {
-                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
-            }
-            jni_utils::delete_java_ref(self.jni_env, array_ptr);
+            // Prevent memory leaks from the created references
+            self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
             jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+            jni_utils::delete_java_ref(self.jni_env, interface_name_jstring);
 
-            // Create and return the Instance
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(result?, self.jni_env)?;
             Self::do_return(
                 self.jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: cache::UNKNOWN_FOR_RUST.to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
             )
         }
     }
 
-    /// Retrieves the field `field_name` of a created `Instance`.
-    pub fn field(&self, instance: &Instance, field_name: &str) -> errors::Result<Instance> {
+    /// Invokes the method `method_name` of a created `Instance`, waiting for at most `timeout` for the invocation to complete.
+    ///
+    /// The method is executed by a thread pool in the Java world. If it does not complete in time,
+    /// the Java thread that executes it is interrupted and `J4RsError::Timeout` is returned.
+    /// Please note that the interruption is cooperative: Java code that ignores interrupts may keep running in the background.
+    pub fn invoke_with_timeout(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+        timeout: time::Duration,
+    ) -> errors::Result<Instance> {
         debug(&format!(
-            "Retrieving field {} of class {}",
-            field_name, instance.class_name
+            "Invoking method {} of class {} with timeout {:?}",
+            method_name, instance.class_name, timeout
         ));
         unsafe {
-            // First argument: create a jstring to pass as argument for the field_name
-            let field_name_jstring: jstring =
-                jni_utils::global_jobject_from_str(field_name, self.jni_env)?;
+            let method_name_jstring = jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
+            let timeout_millis = jlong::try_from(timeout.as_millis()).unwrap_or(jlong::MAX);
 
-            // Call the method of the instance
             let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_field_method()?,
-                field_name_jstring,
+                cache::get_invoke_with_timeout_method()?,
+                timeout_millis,
+                method_name_jstring,
+                array_ptr,
             );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_instance);
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
+            // Prevent memory leaks from the created references
+            self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
 
+            let java_instance = match result {
+                Err(J4RsError::JavaException(exception))
+                    if exception.class_name() == CLASS_INVOCATION_TIMEOUT_EXCEPTION =>
+                {
+                    return Err(J4RsError::Timeout)
+                }
+                other => other?,
+            };
             let java_instance_global_instance =
                 jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
-            // Prevent memory leaks from the created local references
-            jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
-
-            // Create and return the Instance
             Self::do_return(
                 self.jni_env,
-                Instance {
-                    jinstance: java_instance_global_instance,
-                    class_name: cache::UNKNOWN_FOR_RUST.to_string(),
-                    skip_deleting_jobject: false,
-                },
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
             )
         }
     }
 
-    /// Retrieves the field `field_name` of a static class.
-    pub fn static_class_field(
+    /// Invokes the static method `method_name` of the class `class_name`, waiting for at most `timeout` for the invocation to complete.
+    ///
+    /// See `invoke_with_timeout`.
+    pub fn invoke_static_with_timeout(
         &self,
         class_name: &str,
-        field_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+        timeout: time::Duration,
     ) -> errors::Result<Instance> {
-        debug(&format!(
-            "Retrieving field {} of static class {}",
-            field_name, class_name
-        ));
-        let i = self.static_class(class_name)?;
-        self.field(&i, field_name)
+        let static_instance = self.static_class(class_name)?;
+        self.invoke_with_timeout(&static_instance, method_name, inv_args, timeout)
     }
 
-    /// Invokes the method `method_name` of a created `Instance`, passing an array of `InvocationArg`s.
-    /// It returns a Result of `InstanceReceiver` that may be used to get an underlying `Receiver<Instance>`. The result of the invocation will come via this Receiver.
-    pub fn invoke_to_channel(
+    /// Invokes the method `method_name` of a created `Instance` in the AWT event dispatching thread (EDT)
+    /// and waits for the invocation to complete.
+    ///
+    /// Swing components should be created and accessed only from the EDT.
+    pub fn invoke_on_edt(
         &self,
         instance: &Instance,
         method_name: &str,
         inv_args: &[impl Borrow<InvocationArg>],
-    ) -> errors::Result<InstanceReceiver> {
-        debug(&format!("Invoking method {} of class {} using {} arguments. The result of the invocation will come via an InstanceReceiver", method_name, instance.class_name, inv_args.len()));
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking method {} of class {} on the event dispatching thread",
+            method_name, instance.class_name
+        ));
         unsafe {
-            // Create the channel
-            let (sender, rx) = channel();
-            let tx = Box::new(sender);
-            // First argument: the address of the channel Sender
-            let raw_ptr = Box::into_raw(tx);
-            // Find the address of tx
-            let address_string = format!("{:p}", raw_ptr);
-            let address = u64::from_str_radix(&address_string[2..], 16).unwrap();
-
-            // Second argument: create a jstring to pass as argument for the method_name
-            let method_name_jstring: jstring =
-                jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
-
-            // Rest of the arguments: Create a new objectarray of class InvocationArg
-            let size = inv_args.len() as i32;
-            let array_ptr = {
-                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
-                    self.jni_env,
-                    size,
-                    cache::get_invocation_arg_class()?,
-                    ptr::null_mut(),
-                );
-                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
-            };
-            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
+            let method_name_jstring = jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
 
-            // Rest of the arguments: populate the array
-            for i in 0..size {
-                // Create an InvocationArg Java Object
-                let inv_arg_java =
-                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
-                // Set it in the array
-                (opt_to_res(cache::get_jni_set_object_array_element())?)(
-                    self.jni_env,
-                    array_ptr,
-                    i,
-                    inv_arg_java,
-                );
-                inv_arg_jobjects.push(inv_arg_java);
-            }
-
-            // Call the method of the instance
-            (opt_to_res(cache::get_jni_call_void_method())?)(
+            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_invoke_to_channel_method()?,
-                address,
+                cache::get_invoke_on_edt_method()?,
                 method_name_jstring,
                 array_ptr,
             );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_instance);
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
-
-            // Prevent memory leaks from the created local references
-            for inv_arg_jobject in inv_arg_jobjects {
-                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
-            }
-            jni_utils::delete_java_ref(self.jni_env, array_ptr);
+            // Prevent memory leaks from the created references
+            self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
             jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
 
-            // Create and return the Instance
-            Self::do_return(self.jni_env, InstanceReceiver::new(rx, address))
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(result?, self.jni_env)?;
+            Self::do_return(
+                self.jni_env,
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
+            )
         }
     }
 
-    /// Initializes a callback channel via a Java Instance that is a `NativeCallbackToRustChannelSupport`.
-    /// It returns a Result of `InstanceReceiver` that may be used to get an underlying `Receiver<Instance>`.
-    /// The `NativeCallbackToRustChannelSupport` Instance which is passed as argument, will be sending `Instance`s via this Receiver.
-    pub fn init_callback_channel(&self, instance: &Instance) -> errors::Result<InstanceReceiver> {
-        debug("Initializing callback channel");
+    /// Invokes the static method `method_name` of the class `class_name` in the AWT event dispatching thread (EDT)
+    /// and waits for the invocation to complete.
+    pub fn invoke_static_on_edt(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        let static_instance = self.static_class(class_name)?;
+        self.invoke_on_edt(&static_instance, method_name, inv_args)
+    }
+
+    /// Executes the closure `f` in the AWT event dispatching thread (EDT), waits for it to complete and returns its result.
+    ///
+    /// The `Jvm` that is passed to the closure is attached to the EDT, so all the invocations that are made with it
+    /// are executed in the EDT. This is useful for building Swing UIs with several invocations at once.
+    pub fn run_on_edt<F, R>(&self, f: F) -> errors::Result<R>
+    where
+        F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let f = std::sync::Mutex::new(Some(f));
+        let runnable = self.new_proxy("java.lang.Runnable", move |jvm, method_name, _args| {
+            if method_name == "run" {
+                if let Some(f) = f.lock()?.take() {
+                    let _ = tx.send(f(jvm));
+                }
+            }
+            Ok(None)
+        })?;
+        self.invoke_static(CLASS_EDT_INVOCATION, "run", &[InvocationArg::from(runnable)])?;
+        rx.try_recv().map_err(|_| {
+            J4RsError::GeneralError("The closure was not executed in the event dispatching thread".to_string())
+        })?
+    }
+
+    /// Resolves the method `method_name` of a created `Instance` once, so that it can be called repeatedly
+    /// with less overhead than `invoke`.
+    ///
+    /// The method is selected by the declared types of its parameters, like in `invoke_with_signature`.
+    pub fn prepare_invocation(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        param_types: &[&str],
+    ) -> errors::Result<PreparedInvocation<'_>> {
+        debug(&format!(
+            "Preparing the invocation of method {} of class {} with signature {:?}",
+            method_name, instance.class_name, param_types
+        ));
         unsafe {
-            // Create the channel
-            let (sender, rx) = channel();
-            let tx = Box::new(sender);
-            // First argument: the address of the channel Sender
-            let raw_ptr = Box::into_raw(tx);
-            // Find the address of tx
-            let address_string = format!("{:p}", raw_ptr);
-            let address = u64::from_str_radix(&address_string[2..], 16).unwrap();
+            let method_name_jstring = jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let param_types_array = jni_utils::global_jobject_array_from_strs(param_types, self.jni_env)?;
 
-            // Call the method of the instance
-            (opt_to_res(cache::get_jni_call_void_method())?)(
+            let jinvocation = (opt_to_res(cache::get_jni_new_object())?)(
                 self.jni_env,
+                cache::get_prepared_invocation_class()?,
+                cache::get_prepared_invocation_constructor_method()?,
                 instance.jinstance,
-                cache::get_init_callback_channel_method()?,
-                address,
+                method_name_jstring,
+                param_types_array,
             );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, ());
+            jni_utils::delete_java_ref(self.jni_env, param_types_array);
+            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+            result?;
 
-            // Create and return the Instance
-            Self::do_return(self.jni_env, InstanceReceiver::new(rx, address))
+            let jinvocation = jni_utils::create_global_ref_from_local_ref(jinvocation, self.jni_env)?;
+            Ok(PreparedInvocation::new(self, method_name, jinvocation))
         }
     }
 
-    /// Invokes the static method `method_name` of the class `class_name`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
-    pub fn invoke_static(
+    /// Resolves the static method `method_name` of the class `class_name` once, so that it can be called repeatedly.
+    ///
+    /// See `prepare_invocation`.
+    pub fn prepare_static_invocation(
         &self,
         class_name: &str,
         method_name: &str,
-        inv_args: &[impl Borrow<InvocationArg>],
+        param_types: &[&str],
+    ) -> errors::Result<PreparedInvocation<'_>> {
+        let static_instance = self.static_class(class_name)?;
+        self.prepare_invocation(&static_instance, method_name, param_types)
+    }
+
+    /// Invokes the variadic method `method_name` of a created `Instance`.
+    ///
+    /// The `fixed_args` are the arguments that precede the variadic one. The `variadic_args` are collected
+    /// in a Java array of class `variadic_class`, which is passed as the last argument of the method.
+    /// The `variadic_args` may be of any class that is assignable to `variadic_class`.
+    /// For arrays of primitives (e.g. `long`), the arguments are unboxed and widened as needed.
+    ///
+    /// E.g. for invoking `String.format("%s-%s", "a", "b")`, the `fixed_args` contain the format `"%s-%s"`,
+    /// the `variadic_args` contain `"a"` and `"b"` and the `variadic_class` is `java.lang.Object`.
+    pub fn invoke_varargs(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        fixed_args: &[impl Borrow<InvocationArg>],
+        variadic_args: &[impl Borrow<InvocationArg>],
+        variadic_class: &str,
+    ) -> errors::Result<Instance> {
+        let array_arg = self.varargs_array(variadic_args, variadic_class)?;
+        let mut args: Vec<&InvocationArg> = fixed_args.iter().map(|arg| arg.borrow()).collect();
+        args.push(&array_arg);
+        self.invoke(instance, method_name, &args)
+    }
+
+    /// Invokes the static variadic method `method_name` of the class `class_name`.
+    ///
+    /// See `invoke_varargs`.
+    pub fn invoke_static_varargs(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        fixed_args: &[impl Borrow<InvocationArg>],
+        variadic_args: &[impl Borrow<InvocationArg>],
+        variadic_class: &str,
     ) -> errors::Result<Instance> {
+        let array_arg = self.varargs_array(variadic_args, variadic_class)?;
+        let mut args: Vec<&InvocationArg> = fixed_args.iter().map(|arg| arg.borrow()).collect();
+        args.push(&array_arg);
+        self.invoke_static(class_name, method_name, &args)
+    }
+
+    /// Creates the Java array that is passed as the variadic argument of an invocation.
+    fn varargs_array(
+        &self,
+        variadic_args: &[impl Borrow<InvocationArg>],
+        variadic_class: &str,
+    ) -> errors::Result<InvocationArg> {
+        let array = unsafe {
+            self.do_create_java_array(
+                variadic_class,
+                variadic_args,
+                cache::get_factory_create_java_varargs_array_method()?,
+            )?
+        };
+        Ok(InvocationArg::from(array))
+    }
+
+    /// Invokes the method `method_name` of a created `Instance`, passing an array of `InvocationArg`s and
+    /// returns the Rust representation of the result of the invocation.
+    ///
+    /// This is equivalent to calling `invoke` and then `to_rust`, but without creating an intermediate `Instance`.
+    pub fn invoke_to_rust<T>(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
         debug(&format!(
-            "Invoking static method {} of class {} using {} arguments",
+            "Invoking method {} of class {} using {} arguments and transforming the result to Rust",
             method_name,
-            class_name,
+            instance.class_name,
             inv_args.len()
         ));
         unsafe {
-            // Factory invocation - first argument: create a jstring to pass as argument for the class_name
-            let class_name_jstring: jstring =
-                jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
-            // Call the method of the factory that creates a Instance for static calls to methods of class `class_name`.
-            // This returns a Instance that acts like a proxy to the Java world.
-            let tmp_java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            let java_instance = self.do_invoke(instance, method_name, inv_args)?;
+            let result = self.jobject_to_rust_boxed(java_instance).map(|v| *v);
+            // Prevent memory leaks from the created local reference
+            jni_utils::delete_java_local_ref(self.jni_env, java_instance);
+            result
+        }
+    }
+
+    /// Invokes the method `method_name` of a created `Instance` that returns a Java primitive.
+    ///
+    /// This is a fast path for hot loops: the method is called directly via JNI, without any reflection in Java and
+    /// without creating Java objects for the arguments or the result.
+    /// The `args` are Java primitives as well and the method is selected by the exact JNI signature
+    /// that is derived by the `args` and the return type `T` (e.g. `i32` selects methods returning `int`).
+    /// The resolved method IDs are cached, so repeated invocations do not look the method up again.
+    pub fn invoke_primitive<T: JavaPrimitive>(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        args: &[PrimitiveArg],
+    ) -> errors::Result<T> {
+        debug(&format!(
+            "Invoking method {} of class {} using {} primitive arguments",
+            method_name,
+            instance.class_name,
+            args.len()
+        ));
+        let signature = primitives::method_signature::<T>(args);
+        let jvalues: Vec<jvalue> = args.iter().map(|arg| arg.as_jvalue()).collect();
+        unsafe {
+            // Call the getObject method. This returns a localref
+            let object = (opt_to_res(cache::get_jni_call_object_method())?)(
                 self.jni_env,
-                cache::get_factory_class()?,
-                cache::get_factory_create_for_static_method()?,
-                class_name_jstring,
+                instance.jinstance,
+                cache::get_get_object_method()?,
             );
+            Self::do_return(self.jni_env, ())?;
+            if object.is_null() {
+                return Err(Self::null_object_error(method_name));
+            }
 
-            // First argument: create a jstring to pass as argument for the method_name
-            let method_name_jstring: jstring =
-                jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+            let result = self
+                .primitive_method_id(object, method_name, &signature)
+                .and_then(|method_id| {
+                    let value = T::call_method(self.jni_env, object, method_id, jvalues.as_ptr());
+                    Self::do_return(self.jni_env, value)
+                });
+            jni_utils::delete_java_local_ref(self.jni_env, object);
+            result
+        }
+    }
 
-            // Rest of the arguments: Create a new objectarray of class InvocationArg
-            let size = inv_args.len() as i32;
-            let array_ptr = {
-                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
+    /// Returns the method ID of the method `method_name` with the JNI `signature`, for the class of `object`.
+    unsafe fn primitive_method_id(
+        &self,
+        object: jobject,
+        method_name: &str,
+        signature: &str,
+    ) -> errors::Result<jmethodID> {
+        // GetObjectClass aborts the JVM for null objects
+        if object.is_null() {
+            return Err(Self::null_object_error(method_name));
+        }
+        let key = format!("{}{}", method_name, signature);
+        let class = ((**self.jni_env).v1_6.GetObjectClass)(self.jni_env, object);
+        let result = match cache::get_primitive_method_id(&key, class, self.jni_env) {
+            Ok(Some(method_id)) => Ok(method_id),
+            Ok(None) => {
+                let cstr1 = utils::to_c_string(method_name);
+                let cstr2 = utils::to_c_string(signature);
+                let method_id = (opt_to_res(cache::get_jni_get_method_id())?)(self.jni_env, class, cstr1, cstr2);
+                utils::drop_c_string(cstr1);
+                utils::drop_c_string(cstr2);
+                Self::do_return(self.jni_env, method_id).and_then(|method_id| {
+                    let global_class = (opt_to_res(cache::get_jni_new_global_ref())?)(self.jni_env, class);
+                    cache::add_primitive_method_id(key, global_class, method_id);
+                    Ok(method_id)
+                })
+            }
+            Err(error) => Err(error),
+        };
+        jni_utils::delete_java_local_ref(self.jni_env, class);
+        result
+    }
+
+    fn null_object_error(method_name: &str) -> J4RsError {
+        J4RsError::GeneralError(format!(
+            "Cannot invoke the primitive method {}: the Instance holds no Java object (null or a static class)",
+            method_name
+        ))
+    }
+
+    /// Invokes the method `method_name` of a created `Instance` and returns the local reference of the result.
+    pub(crate) unsafe fn do_invoke(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<jobject> {
+        self.do_invoke_with_param_types(instance, method_name, None, inv_args)
+    }
+
+    /// Invokes the method `method_name` of a created `Instance` and returns the local reference of the result.
+    ///
+    /// If `param_types` is defined, the method to invoke is the one with exactly these parameter types.
+    /// Otherwise, the method is selected by the types of the `inv_args`.
+    unsafe fn do_invoke_with_param_types(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        param_types: Option<&[&str]>,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<jobject> {
+        // First argument: create a jstring to pass as argument for the method_name
+        let method_name_jstring: jstring =
+            jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+
+        // Rest of the arguments: Create a new objectarray of class InvocationArg
+        let (array_ptr, inv_arg_jobjects) = self.invocation_args_array(inv_args)?;
+
+        // Call the method of the instance
+        let java_instance = match param_types {
+            Some(param_types) => {
+                let param_types_array = jni_utils::global_jobject_array_from_strs(param_types, self.jni_env)?;
+                let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                     self.jni_env,
-                    size,
-                    cache::get_invocation_arg_class()?,
-                    ptr::null_mut(),
+                    instance.jinstance,
+                    cache::get_invoke_with_signature_method()?,
+                    method_name_jstring,
+                    param_types_array,
+                    array_ptr,
                 );
-                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
-            };
-            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
-            // Rest of the arguments: populate the array
-            for i in 0..size {
-                // Create an InvocationArg Java Object
-                let inv_arg_java =
-                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
-                // Set it in the array
-                (opt_to_res(cache::get_jni_set_object_array_element())?)(
+                // Check for exceptions before deleting any refs
+                let result = Self::do_return(self.jni_env, ());
+                jni_utils::delete_java_ref(self.jni_env, param_types_array);
+                result.map(|_| java_instance)
+            }
+            None => {
+                let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                     self.jni_env,
+                    instance.jinstance,
+                    cache::get_invoke_method()?,
+                    method_name_jstring,
                     array_ptr,
-                    i,
-                    inv_arg_java,
                 );
-                inv_arg_jobjects.push(inv_arg_java);
+                // Check for exceptions before deleting any refs
+                Self::do_return(self.jni_env, java_instance)
             }
-            // Call the method of the instance
-            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+        };
+
+        // Prevent memory leaks from the created references
+        self.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+        jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+
+        java_instance
+    }
+
+    /// Creates a Java array of `org.astonbitecode.j4rs.api.dtos.InvocationArg`s.
+    ///
+    /// Returns the global refs of the array and of its elements,
+    /// which should be deleted by calling `delete_invocation_args_array`.
+    pub(crate) unsafe fn invocation_args_array(
+        &self,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<(jobject, Vec<jobject>)> {
+        let size = inv_args.len() as i32;
+        let array_ptr = {
+            let j = (opt_to_res(cache::get_jni_new_object_array())?)(
+                self.jni_env,
+                size,
+                cache::get_invocation_arg_class()?,
+                ptr::null_mut(),
+            );
+            jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
+        };
+        let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
+
+        // Populate the array
+        for i in 0..size {
+            // Create an InvocationArg Java Object
+            let inv_arg_java =
+                inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
+            // Set it in the array
+            (opt_to_res(cache::get_jni_set_object_array_element())?)(
                 self.jni_env,
-                tmp_java_instance,
-                cache::get_invoke_static_method()?,
-                method_name_jstring,
                 array_ptr,
+                i,
+                inv_arg_java,
             );
-            // Delete temp ref
-            jni_utils::delete_java_local_ref(self.jni_env, tmp_java_instance);
-            jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
+            inv_arg_jobjects.push(inv_arg_java);
+        }
 
-            // Prevent memory leaks from the created local references
-            for inv_arg_jobject in inv_arg_jobjects {
-                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
-            }
-            jni_utils::delete_java_ref(self.jni_env, array_ptr);
-            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+        Ok((array_ptr, inv_arg_jobjects))
+    }
 
-            // Create and return the Instance.
-            Self::do_return(
-                self.jni_env,
-                Instance::from_jobject_with_global_ref(java_instance)?,
-            )
+    /// Deletes the references that were created by `invocation_args_array`.
+    pub(crate) fn delete_invocation_args_array(&self, array_ptr: jobject, inv_arg_jobjects: Vec<jobject>) {
+        for inv_arg_jobject in inv_arg_jobjects {
+            jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
         }
+        jni_utils::delete_java_ref(self.jni_env, array_ptr);
     }
 
-    /// Creates a clone of the provided Instance
-    pub fn clone_instance(&self, instance: &Instance) -> errors::Result<Instance> {
+    /// Retrieves the field `field_name` of a created `Instance`.
+    pub fn field(&self, instance: &Instance, field_name: &str) -> errors::Result<Instance> {
+        debug(&format!(
+            "Retrieving field {} of class {}",
+            field_name, instance.class_name
+        ));
         unsafe {
-            // Call the clone method
-            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            // First argument: create a jstring to pass as argument for the field_name
+            let field_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(field_name, self.jni_env)?;
+
+            // Call the method of the instance
+            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
                 self.jni_env,
-                cache::get_class_to_invoke_clone_and_cast()?,
-                cache::get_clone_static_method()?,
                 instance.jinstance,
+                cache::get_field_method()?,
+                field_name_jstring,
             );
 
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jni_env)?;
+            // Prevent memory leaks from the created local references
+            jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
+
             // Create and return the Instance
             Self::do_return(
                 self.jni_env,
-                Instance::from_jobject_with_global_ref(java_instance)?,
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
             )
         }
     }
 
-    /// Invokes the static method `method_name` of the class `class_name`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
-    pub fn cast(&self, from_instance: &Instance, to_class: &str) -> errors::Result<Instance> {
-        debug(&format!("Casting to class {}", to_class));
+    /// Retrieves the field `field_name` of a static class.
+    pub fn static_class_field(
+        &self,
+        class_name: &str,
+        field_name: &str,
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Retrieving field {} of static class {}",
+            field_name, class_name
+        ));
+        let i = self.static_class(class_name)?;
+        self.field(&i, field_name)
+    }
+
+    /// Sets the value of the field `field_name` of a created `Instance`.
+    pub fn set_field(
+        &self,
+        instance: &Instance,
+        field_name: &str,
+        value: impl Borrow<InvocationArg>,
+    ) -> errors::Result<()> {
+        debug(&format!(
+            "Setting field {} of class {}",
+            field_name, instance.class_name
+        ));
         unsafe {
-            // First argument is the jobject that is inside the from_instance
-            // Second argument: create a jstring to pass as argument for the to_class
-            let to_class_jstring: jstring =
-                jni_utils::global_jobject_from_str(to_class, self.jni_env)?;
+            // First argument: create a jstring to pass as argument for the field_name
+            let field_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(field_name, self.jni_env)?;
+            // Second argument: the InvocationArg that holds the value
+            let inv_arg_java = value.borrow().as_java_ptr_with_global_ref(self.jni_env)?;
 
-            // Call the cast method
-            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            // Call the method of the instance
+            (opt_to_res(cache::get_jni_call_void_method())?)(
                 self.jni_env,
-                cache::get_class_to_invoke_clone_and_cast()?,
-                cache::get_cast_static_method()?,
-                from_instance.jinstance,
-                to_class_jstring,
+                instance.jinstance,
+                cache::get_set_field_method()?,
+                field_name_jstring,
+                inv_arg_java,
             );
 
-            // Check for exceptions before creating the globalref
-            Self::do_return(self.jni_env, ())?;
+            // Check for exceptions before deleting the references
+            let result = Self::do_return(self.jni_env, ());
 
             // Prevent memory leaks from the created local references
-            jni_utils::delete_java_ref(self.jni_env, to_class_jstring);
+            jni_utils::delete_java_ref(self.jni_env, inv_arg_java);
+            jni_utils::delete_java_ref(self.jni_env, field_name_jstring);
 
-            // Create and return the Instance
-            Self::do_return(
-                self.jni_env,
-                Instance::from_jobject_with_global_ref(java_instance)?,
-            )
+            result
         }
     }
 
-    /// Checks whether an Instance a is equal to some InvocationArg. 
-    /// 
-    /// The check is actually against the Java `Object.equals`, taking into consideration the possibility of null.
-    /// `NullPointerException` will not be thrown, even if one of the inputs is null.
-    pub fn check_equals(&self, instance: impl Borrow<Instance>, inv_arg: impl Borrow<InvocationArg>) -> errors::Result<bool> {
-        debug(&format!("Checking equality between instances of {} and {}", instance.borrow().class_name(), inv_arg.borrow().class_name()));
-        unsafe {
-            // Create InvocationArg Java Objects
-            let inv_arg_java_b = inv_arg.borrow().as_java_ptr_with_global_ref(self.jni_env)?;
-            // Call the checkEquals method
-            let java_boolean = (opt_to_res(cache::get_jni_call_boolean_method())?)(
-                self.jni_env,
-                instance.borrow().jinstance,
-                cache::get_check_equals_method()?,
-                inv_arg_java_b,
-            );
-
-            // Create and return the boolean
-            Self::do_return(
-                self.jni_env,
-                java_boolean,
-            )
-        }
+    /// Sets the value of the static field `field_name` of the class `class_name`.
+    pub fn set_static_field(
+        &self,
+        class_name: &str,
+        field_name: &str,
+        value: impl Borrow<InvocationArg>,
+    ) -> errors::Result<()> {
+        debug(&format!(
+            "Setting field {} of static class {}",
+            field_name, class_name
+        ));
+        let i = self.static_class(class_name)?;
+        self.set_field(&i, field_name, value)
     }
 
-    /// Consumes an `Instance` and returns its jobject. The returned jobject is a JNI local reference.
-    pub fn instance_into_raw_object(&self, instance: Instance) -> errors::Result<jobject> {
-        debug(&format!("Getting the raw jobject from instance of class {}", instance.borrow().class_name()));
-        // Call the getObjectMethod. This returns a localref
-        let object_instance = unsafe {
-            (opt_to_res(cache::get_jni_call_object_method())?)(
-                self.jni_env,
-                instance.jinstance,
-                cache::get_get_object_method()?,
-        )};
+    /// Invokes the method `method_name` of a created `Instance`, passing an array of `InvocationArg`s.
+    /// It returns a Result of `InstanceReceiver` that may be used to get an underlying `Receiver<Instance>`. The result of the invocation will come via this Receiver.
+    pub fn invoke_to_channel(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<InstanceReceiver> {
+        self.do_invoke_to_channel(instance, method_name, inv_args, instance::instance_channel())
+    }
 
-        Self::do_return(
-            self.jni_env,
-            object_instance,
-        )
+    /// Like `invoke_to_channel`, but the Instances that come via the channel are returned as a `futures::Stream`.
+    ///
+    /// No thread blocks waiting for the Instances; the Java callbacks wake up the task that polls the `Stream`.
+    /// See `InstanceReceiver::into_async`.
+    pub fn invoke_to_stream(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<AsyncInstanceReceiver> {
+        Ok(self.invoke_to_channel(instance, method_name, inv_args)?.into_async())
     }
 
-    /// Consumes the `Jvm` and returns its `JNIEnv`
-    pub fn into_raw(self) -> *mut JNIEnv {
-        debug("Getting the raw JNIEnv from the Jvm");
+    /// Like `invoke_to_channel`, but the Instances that come via the channel are transformed to Rust values of type `T`.
+    ///
+    /// The transformation happens in a dedicated thread. See `InstanceReceiver::into_typed`.
+    pub fn invoke_to_channel_typed<T>(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<TypedInstanceReceiver<T>>
+    where
+        T: DeserializeOwned + Any + Send + 'static,
+    {
+        self.invoke_to_channel(instance, method_name, inv_args)?.into_typed()
+    }
 
-        self.jni_env
+    /// Like `invoke_to_channel`, but the channel holds up to `capacity` Instances.
+    ///
+    /// When the channel is full, the `overflow` defines whether Java blocks until the Rust side receives, or drops the Instances.
+    /// This avoids unbounded memory growth when Java produces faster than Rust consumes.
+    pub fn invoke_to_bounded_channel(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+        capacity: usize,
+        overflow: ChannelOverflow,
+    ) -> errors::Result<InstanceReceiver> {
+        let channel = instance::bounded_instance_channel(capacity, overflow);
+        self.do_invoke_to_channel(instance, method_name, inv_args, channel)
     }
 
-    /// Returns the Rust representation of the provided instance, boxed
-    pub fn to_rust_boxed<T>(&self, instance: Instance) -> errors::Result<Box<T>>
-        where
-            T: DeserializeOwned + Any,
-    {
-        // Define the macro inside the function in order to have access to &self
-        macro_rules! rust_box_from_java_object {
-            ($jni_transformation:path) => {{
-                // Call the getObjectMethod. This returns a localref
-                let object_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+    fn do_invoke_to_channel(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+        channel: InstanceChannel,
+    ) -> errors::Result<InstanceReceiver> {
+        debug(&format!("Invoking method {} of class {} using {} arguments. The result of the invocation will come via an InstanceReceiver", method_name, instance.class_name, inv_args.len()));
+        unsafe {
+            let (sender, rx, waker) = channel;
+            let closer = sender.closer(Some(self.clone_instance(instance)?));
+
+            // Second argument: create a jstring to pass as argument for the method_name
+            let method_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+
+            // Rest of the arguments: Create a new objectarray of class InvocationArg
+            let size = inv_args.len() as i32;
+            let array_ptr = {
+                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
                     self.jni_env,
-                    instance.jinstance,
-                    cache::get_get_object_method()?,
+                    size,
+                    cache::get_invocation_arg_class()?,
+                    ptr::null_mut(),
                 );
-                let object_instance =
-                    jni_utils::create_global_ref_from_local_ref(object_instance, self.jni_env)?;
-                let v = Box::new($jni_transformation(object_instance, self.jni_env)?);
-                let v_any = v as Box<dyn Any>;
-
-                jni_utils::delete_java_ref(self.jni_env, object_instance);
-
-                match v_any.downcast::<T>() {
-                    Ok(v) => Ok(v),
-                    Err(error) => Err(errors::J4RsError::RustError(format!(
-                        "Could not downcast to Rust type: {:?}",
-                        error
-                    ))),
-                }
-            }};
-        }
+                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
+            };
+            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
 
-        let t_type = TypeId::of::<T>();
-        
+            // Rest of the arguments: populate the array
+            for i in 0..size {
+                // Create an InvocationArg Java Object
+                let inv_arg_java =
+                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
+                // Set it in the array
+                (opt_to_res(cache::get_jni_set_object_array_element())?)(
+                    self.jni_env,
+                    array_ptr,
+                    i,
+                    inv_arg_java,
+                );
+                inv_arg_jobjects.push(inv_arg_java);
+            }
 
-        unsafe {
-            // Call the getClassName method. This returns a localref
-            let object_class_name_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+            let call_void_method = opt_to_res(cache::get_jni_call_void_method())?;
+            let invoke_to_channel_method = cache::get_invoke_to_channel_method()?;
+            // First argument: the address of the channel Sender.
+            // From now on, the Sender is owned by Java, which releases it.
+            let address = Box::into_raw(Box::new(sender)) as u64;
+
+            // Call the method of the instance
+            (call_void_method)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_get_object_class_name_method()?,
+                invoke_to_channel_method,
+                address,
+                method_name_jstring,
+                array_ptr,
             );
-            let object_class_name_instance = jni_utils::create_global_ref_from_local_ref(
-                object_class_name_instance,
-                self.jni_env,
-            )?;
-            let class_name = &(jni_utils::string_from_jobject(object_class_name_instance, self.jni_env)?);
-            jni_utils::delete_java_ref(self.jni_env, object_class_name_instance);
-            if t_type == TypeId::of::<String>() && JavaClass::String.get_class_str() == class_name {
-                rust_box_from_java_object!(jni_utils::string_from_jobject)
-            } else if t_type == TypeId::of::<i32>()
-                && (JavaClass::Integer.get_class_str() == class_name || PRIMITIVE_INT == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::i32_from_jobject)
-            } else if t_type == TypeId::of::<i8>()
-                && (JavaClass::Byte.get_class_str() == class_name || PRIMITIVE_BYTE == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::i8_from_jobject)
-            } else if t_type == TypeId::of::<i16>()
-                && (JavaClass::Short.get_class_str() == class_name || PRIMITIVE_SHORT == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::i16_from_jobject)
-            }  else if t_type == TypeId::of::<u16>()
-                && (JavaClass::Character.get_class_str() == class_name || PRIMITIVE_CHAR == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::u16_from_jobject)
-            } else if t_type == TypeId::of::<i64>()
-                && (JavaClass::Long.get_class_str() == class_name || PRIMITIVE_LONG == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::i64_from_jobject)
-            } else if t_type == TypeId::of::<f32>()
-                && (JavaClass::Float.get_class_str() == class_name || PRIMITIVE_FLOAT == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::f32_from_jobject)
-            } else if t_type == TypeId::of::<f64>()
-                && (JavaClass::Double.get_class_str() == class_name
-                || PRIMITIVE_DOUBLE == class_name)
-            {
-                rust_box_from_java_object!(jni_utils::f64_from_jobject)
-            } else if t_type == TypeId::of::<Vec<i8>>()
-                && PRIMITIVE_BYTE_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::i8_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<i16>>()
-                && PRIMITIVE_SHORT_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::i16_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<u16>>()
-                && PRIMITIVE_CHAR_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::u16_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<i32>>()
-                && PRIMITIVE_INT_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::i32_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<i64>>()
-                && PRIMITIVE_LONG_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::i64_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<f32>>()
-                && PRIMITIVE_FLOAT_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::f32_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<f64>>()
-                && PRIMITIVE_DOUBLE_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::f64_array_from_jobject)
-            } else if t_type == TypeId::of::<Vec<bool>>()
-                && PRIMITIVE_BOOLEAN_ARRAY == class_name
-            {
-                rust_box_from_java_object!(jni_utils::boolean_array_from_jobject)
-            } else {
-                Ok(Box::new(self.to_rust_deserialized(instance)?))
+
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+
+            // Prevent memory leaks from the created local references
+            for inv_arg_jobject in inv_arg_jobjects {
+                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
             }
+            jni_utils::delete_java_ref(self.jni_env, array_ptr);
+            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+
+            // Create and return the Instance
+            Self::do_return(self.jni_env, InstanceReceiver::new(rx, Some(closer), waker))
         }
     }
 
-    /// Returns the Rust representation of the provided instance
-    pub fn to_rust<T>(&self, instance: Instance) -> errors::Result<T>
-        where
-            T: DeserializeOwned + Any,
-    {
-        self.to_rust_boxed(instance).map(|v| *v)
+    /// Initializes a callback channel via a Java Instance that is a `NativeCallbackToRustChannelSupport`.
+    /// It returns a Result of `InstanceReceiver` that may be used to get an underlying `Receiver<Instance>`.
+    /// The `NativeCallbackToRustChannelSupport` Instance which is passed as argument, will be sending `Instance`s via this Receiver.
+    pub fn init_callback_channel(&self, instance: &Instance) -> errors::Result<InstanceReceiver> {
+        self.do_init_callback_channel(instance, instance::instance_channel())
     }
 
-    pub fn to_rust_deserialized<T>(&self, instance: Instance) -> errors::Result<T>
-        where
-            T: DeserializeOwned + Any,
-    {
+    /// Like `init_callback_channel`, but the channel holds up to `capacity` Instances.
+    ///
+    /// When the channel is full, the `overflow` defines whether Java blocks until the Rust side receives, or drops the Instances.
+    pub fn init_bounded_callback_channel(
+        &self,
+        instance: &Instance,
+        capacity: usize,
+        overflow: ChannelOverflow,
+    ) -> errors::Result<InstanceReceiver> {
+        let channel = instance::bounded_instance_channel(capacity, overflow);
+        self.do_init_callback_channel(instance, channel)
+    }
+
+    fn do_init_callback_channel(
+        &self,
+        instance: &Instance,
+        channel: InstanceChannel,
+    ) -> errors::Result<InstanceReceiver> {
+        debug("Initializing callback channel");
         unsafe {
-            debug("Invoking the getJson method");
-            // Call the getJson method. This returns a localref
-            let json_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+            let (sender, rx, waker) = channel;
+            let closer = sender.closer(Some(self.clone_instance(instance)?));
+            let call_void_method = opt_to_res(cache::get_jni_call_void_method())?;
+            let init_callback_channel_method = cache::get_init_callback_channel_method()?;
+            // First argument: the address of the channel Sender.
+            // From now on, the Sender is owned by Java, which releases it.
+            let address = Box::into_raw(Box::new(sender)) as u64;
+
+            // Call the method of the instance
+            (call_void_method)(
                 self.jni_env,
                 instance.jinstance,
-                cache::get_get_json_method()?,
+                init_callback_channel_method,
+                address,
             );
-            let _ = Self::do_return(self.jni_env, "")?;
-            debug("Transforming jstring to rust String");
-            let global_json_instance =
-                jni_utils::create_global_ref_from_local_ref(json_instance, self.jni_env)?;
-            let json = jni_utils::jstring_to_rust_string(self, global_json_instance as jstring)?;
-            jni_utils::delete_java_ref(self.jni_env, global_json_instance);
-            Self::do_return(self.jni_env, serde_json::from_str(&json)?)
+
+            // Create and return the Instance
+            Self::do_return(self.jni_env, InstanceReceiver::new(rx, Some(closer), waker))
         }
     }
 
-    /// Deploys an artifact in the default j4rs jars location.
-    ///
-    /// This is useful for build scripts that need jars for the runtime that can be downloaded from e.g. Maven.
-    ///
-    /// The function deploys __only__ the specified artifact, not its transitive dependencies.
-    pub fn deploy_artifact<T: Any + JavaArtifact>(&self, artifact: &T) -> errors::Result<()> {
-        let artifact = artifact as &dyn Any;
-        if let Some(maven_artifact) = artifact.downcast_ref::<MavenArtifact>() {
-            for repo in get_maven_settings().repos.into_iter() {
-                let instance = self.create_instance(
-                    "org.astonbitecode.j4rs.api.deploy.SimpleMavenDeployer",
-                    &[InvocationArg::try_from(repo.uri)?,
-                        InvocationArg::try_from(&maven_artifact.base)?],
-                )?;
+    /// Tells the Java `NativeCallbackToRustChannelSupport` `instance` that the receiving side of its channel is closed.
+    pub(crate) fn close_callback_channel(&self, instance: &Instance) -> errors::Result<()> {
+        unsafe {
+            (opt_to_res(cache::get_jni_call_void_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_close_callback_channel_method()?,
+            );
+        }
+        Self::do_return(self.jni_env, ())
+    }
 
-                let res = self.invoke(
-                    &instance,
-                    "deploy",
-                    &vec![
-                        InvocationArg::try_from(&maven_artifact.group)?,
-                        InvocationArg::try_from(&maven_artifact.id)?,
-                        InvocationArg::try_from(&maven_artifact.version)?,
-                        InvocationArg::try_from(&maven_artifact.qualifier)?,
-                    ],
-                );
+    /// Invokes the static method `method_name` of the class `class_name`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
+    pub fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking static method {} of class {} using {} arguments",
+            method_name,
+            class_name,
+            inv_args.len()
+        ));
+        metrics::timed(Operation::InvokeStatic, || hooks::with_hook(InvocationKind::InvokeStatic, class_name, method_name, inv_args, || unsafe {
+            // Factory invocation - first argument: create a jstring to pass as argument for the class_name
+            let class_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+            // Call the method of the factory that creates a Instance for static calls to methods of class `class_name`.
+            // This returns a Instance that acts like a proxy to the Java world.
+            let tmp_java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+                self.jni_env,
+                cache::get_factory_class()?,
+                cache::get_factory_create_for_static_method()?,
+                class_name_jstring,
+            );
 
-                if res.is_ok() {
-                    break;
-                }
+            // First argument: create a jstring to pass as argument for the method_name
+            let method_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(method_name, self.jni_env)?;
+
+            // Rest of the arguments: Create a new objectarray of class InvocationArg
+            let size = inv_args.len() as i32;
+            let array_ptr = {
+                let j = (opt_to_res(cache::get_jni_new_object_array())?)(
+                    self.jni_env,
+                    size,
+                    cache::get_invocation_arg_class()?,
+                    ptr::null_mut(),
+                );
+                jni_utils::create_global_ref_from_local_ref(j, self.jni_env)?
+            };
+            let mut inv_arg_jobjects: Vec<jobject> = Vec::with_capacity(size as usize);
+            // Rest of the arguments: populate the array
+            for i in 0..size {
+                // Create an InvocationArg Java Object
+                let inv_arg_java =
+                    inv_args[i as usize].borrow().as_java_ptr_with_global_ref(self.jni_env)?;
+                // Set it in the array
+                (opt_to_res(cache::get_jni_set_object_array_element())?)(
+                    self.jni_env,
+                    array_ptr,
+                    i,
+                    inv_arg_java,
+                );
+                inv_arg_jobjects.push(inv_arg_java);
             }
+            // Call the method of the instance
+            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                tmp_java_instance,
+                cache::get_invoke_static_method()?,
+                method_name_jstring,
+                array_ptr,
+            );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, ());
 
-            Ok(())
-        } else if let Some(local_jar_artifact) = artifact.downcast_ref::<LocalJarArtifact>() {
-            let instance = self.create_instance(
-                "org.astonbitecode.j4rs.api.deploy.FileSystemDeployer",
-                &[InvocationArg::try_from(&local_jar_artifact.base)?],
-            )?;
+            // Delete temp ref
+            jni_utils::delete_java_local_ref(self.jni_env, tmp_java_instance);
+            jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+            // Prevent memory leaks from the created local references
+            for inv_arg_jobject in inv_arg_jobjects {
+                jni_utils::delete_java_ref(self.jni_env, inv_arg_jobject);
+            }
+            jni_utils::delete_java_ref(self.jni_env, array_ptr);
+            jni_utils::delete_java_ref(self.jni_env, method_name_jstring);
+            result?;
 
-            let _ = self.invoke(
-                &instance,
-                "deploy",
-                &[InvocationArg::try_from(&local_jar_artifact.path)?],
-            )?;
-            Ok(())
-        } else {
-            Err(J4RsError::GeneralError(format!(
-                "Don't know how to deploy artifacts of {:?}",
-                artifact.type_id()
-            )))
-        }
+            // Create and return the Instance.
+            Self::do_return(
+                self.jni_env,
+                Instance::from_jobject_with_global_ref(java_instance)?,
+            )
+        }))
     }
 
-    /// Copies the jassets default directory and the j4rs dynamic library under the specified
-    /// location.
-    /// This is useful for cases when `with_base_path` method is used when building a Jvm with
-    /// the JvmBuilder.
-    /// Build scripts should use this method.
-    pub fn copy_j4rs_libs_under(path: &str) -> errors::Result<()> {
-        let mut pb = PathBuf::from(path);
-        pb.push("deps");
-        fs::create_dir_all(&pb)?;
+    /// Creates a clone of the provided Instance
+    pub fn clone_instance(&self, instance: &Instance) -> errors::Result<Instance> {
+        unsafe {
+            // Call the clone method
+            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+                self.jni_env,
+                cache::get_class_to_invoke_clone_and_cast()?,
+                cache::get_clone_static_method()?,
+                instance.jinstance,
+            );
 
-        let default_jassets_path_buf = utils::default_jassets_path()?;
-        let default_jassets_path_string = default_jassets_path_buf.to_str().unwrap().to_owned();
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
 
-        // Copy the jassets
-        let options = &mut fs_extra::dir::CopyOptions::new();
-        options.overwrite = true;
-        let _ = fs_extra::copy_items(vec![default_jassets_path_string].as_ref(), path, options)?;
+            // Create and return the Instance
+            Self::do_return(
+                self.jni_env,
+                Instance::from_jobject_with_global_ref(java_instance)?,
+            )
+        }
+    }
 
-        // Copy the dynamic libraries
-        let dynlibs: Vec<String> = {
-            let mut dynlibs = vec![];
-            // We try every 1 second for 10 iterations because on most systems, cargo will
-            // parallelize the build and the dynlib might not be created yet.
-            for _i in 0..10 {
-                dynlibs = utils::find_j4rs_dynamic_libraries_paths()?;
-                if dynlibs.is_empty() {
-                    thread::sleep(time::Duration::from_millis(1000));
-                } else {
-                    break;
-                }
-            }
-            dynlibs
-        };
-        if dynlibs.is_empty() {
-            let message = format!(
-                "No j4rs dynamic libraries found for target triple {}. \
-                                  The host triple during build is {}.",
-                env::var("TARGET").unwrap_or("".to_string()),
-                env::var("HOST").unwrap_or("UNKNOWN".to_string())
+    /// Invokes the static method `method_name` of the class `class_name`, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
+    pub fn cast(&self, from_instance: &Instance, to_class: &str) -> errors::Result<Instance> {
+        debug(&format!("Casting to class {}", to_class));
+        unsafe {
+            // First argument is the jobject that is inside the from_instance
+            // Second argument: create a jstring to pass as argument for the to_class
+            let to_class_jstring: jstring =
+                jni_utils::global_jobject_from_str(to_class, self.jni_env)?;
+
+            // Call the cast method
+            let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+                self.jni_env,
+                cache::get_class_to_invoke_clone_and_cast()?,
+                cache::get_cast_static_method()?,
+                from_instance.jinstance,
+                to_class_jstring,
             );
-            println!("cargo:warning={}", message);
-        }
 
-        let _ = fs_extra::copy_items(&dynlibs, &pb, options)?;
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
 
-        Ok(())
-    }
+            // Prevent memory leaks from the created local references
+            jni_utils::delete_java_ref(self.jni_env, to_class_jstring);
 
-    /// Initiates a chain of operations on Instances.
-    pub fn chain(&self, instance: &Instance) -> errors::Result<ChainableInstance> {
-        ChainableInstance::new_with_instance_ref(instance, self)
+            // Create and return the Instance
+            Self::do_return(
+                self.jni_env,
+                Instance::from_jobject_with_global_ref(java_instance)?,
+            )
+        }
     }
 
-    /// Initiates a chain of operations on Instances.
-    pub fn into_chain(&self, instance: Instance) -> ChainableInstance {
-        ChainableInstance::new(instance, self)
+    /// Retrieves the name of the runtime class of the Java object that is contained in an `Instance`.
+    ///
+    /// Unlike `Instance::class_name`, which may be unknown for `Instance`s that are returned by invocations,
+    /// this queries Java for the actual class of the object.
+    /// If the object is null, the class that is known to Java for the `Instance` is returned.
+    pub fn get_class_name(&self, instance: &Instance) -> errors::Result<String> {
+        debug(&format!("Retrieving the runtime class name of an instance of {}", instance.class_name()));
+        unsafe {
+            // Call the getRuntimeClassName method. This returns a localref
+            let java_string = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_get_runtime_class_name_method()?,
+            );
+            Self::do_return(self.jni_env, ())?;
+            let class_name = jni_utils::jstring_to_rust_string(self, java_string);
+            jni_utils::delete_java_local_ref(self.jni_env, java_string);
+            class_name
+        }
     }
 
-    /// Throws an exception in the Java World
-    pub fn throw_invocation_exception(&self, message: &str) -> errors::Result<()> {
+    /// Checks whether the Java object that is contained in an `Instance` is an instance of the class or interface `class_name`.
+    ///
+    /// Returns false if the object is null.
+    pub fn is_instance_of(&self, instance: &Instance, class_name: &str) -> errors::Result<bool> {
+        debug(&format!("Checking whether an instance of {} is an instance of {}", instance.class_name(), class_name));
         unsafe {
-            let _ = jni_utils::throw_exception(message, self.jni_env)?;
+            let class_name_jstring: jstring =
+                jni_utils::global_jobject_from_str(class_name, self.jni_env)?;
+            // Call the isInstanceOf method
+            let java_boolean = (opt_to_res(cache::get_jni_call_boolean_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_is_instance_of_method()?,
+                class_name_jstring,
+            );
+            // Check for exceptions before deleting any refs
+            let result = Self::do_return(self.jni_env, java_boolean);
+            jni_utils::delete_java_ref(self.jni_env, class_name_jstring);
+            result
         }
-        Ok(())
     }
 
-    pub(crate) fn do_return<T>(jni_env: *mut JNIEnv, to_return: T) -> errors::Result<T> {
+    /// Retrieves the names of the runtime class of the Java object that is contained in an `Instance`,
+    /// of all its superclasses and of all the interfaces that it implements.
+    ///
+    /// The runtime class comes first, followed by its superclasses up to `java.lang.Object` and then the interfaces.
+    pub fn get_class_hierarchy(&self, instance: &Instance) -> errors::Result<Vec<String>> {
+        debug(&format!("Retrieving the class hierarchy of an instance of {}", instance.class_name()));
         unsafe {
-            if (opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE {
-                let throwable = (opt_to_res(cache::get_jni_exception_occured())?)(jni_env);
-                let throwable_string = Self::get_throwable_string(throwable, jni_env)?;
-                (opt_to_res(cache::get_jni_exception_clear())?)(jni_env);
-                Err(J4RsError::JavaError(throwable_string))
-            } else {
-                Ok(to_return)
-            }
+            // Call the getClassHierarchy method. This returns a localref
+            let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_get_class_hierarchy_method()?,
+            );
+            // Check for exceptions before creating the globalref
+            Self::do_return(self.jni_env, ())?;
+            self.to_rust(Instance::from_jobject_with_global_ref(java_instance)?)
         }
     }
 
-    unsafe fn get_throwable_string(throwable: jobject, jni_env: *mut JNIEnv) -> errors::Result<String> {
-        let java_string = (opt_to_res(cache::get_jni_call_static_object_method())?)(
-            jni_env,
-            cache::get_utils_class()?,
-            cache::get_utils_exception_to_string_method()?,
-            throwable,
-        );
-        let to_ret = jni_utils::string_from_jobject(java_string, jni_env);
-        jni_utils::delete_java_local_ref(jni_env, java_string);
-        to_ret
+    /// Creates a Rust `Iterator` over the elements of a Java `Iterable` (e.g. a `java.util.List`), `Iterator` or `Stream`.
+    ///
+    /// The elements are retrieved lazily, one by one, instead of transforming the whole collection at once.
+    pub fn iterate(&self, instance: &Instance) -> errors::Result<JavaIterator<'_>> {
+        let iterator = if self.is_instance_of(instance, "java.util.Iterator")? {
+            self.cast(instance, "java.util.Iterator")?
+        } else if self.is_instance_of(instance, "java.lang.Iterable")? {
+            let iterable = self.cast(instance, "java.lang.Iterable")?;
+            self.invoke(&iterable, "iterator", InvocationArg::empty())?
+        } else if self.is_instance_of(instance, "java.util.stream.BaseStream")? {
+            let stream = self.cast(instance, "java.util.stream.BaseStream")?;
+            self.invoke(&stream, "iterator", InvocationArg::empty())?
+        } else {
+            return Err(J4RsError::GeneralError(format!(
+                "Cannot iterate over an instance of {}",
+                self.get_class_name(instance)?
+            )));
+        };
+        Ok(JavaIterator::new(self, iterator))
     }
 
-    // Retrieves a JNIEnv in the case that a JVM is already created even from another thread.
-    fn get_created_vm() -> Option<*mut JNIEnv> {
-        unsafe {
-            // Get the number of the already created VMs. This is most probably 1, but we retrieve the number just in case...
-            let mut created_vms_size: jsize = 0;
-            tweaks::get_created_java_vms(
-                &mut Vec::with_capacity(created_vms_size as usize),
-                0,
-                &mut created_vms_size,
-            );
-
-            if created_vms_size == 0 {
-                None
-            } else {
-                debug(&format!(
-                    "Retrieving the first of {} created JVMs",
-                    created_vms_size
-                ));
-                // Get the created VM (use 2 just in case... :) )
-                let mut buffer: Vec<*mut JavaVM> = Vec::with_capacity(2);
-                for _ in 0..created_vms_size {
-                    buffer.push(ptr::null_mut());
-                }
+    /// Like `iterate`, but transforms each element to its Rust representation.
+    pub fn iterate_to_rust<T>(&self, instance: &Instance) -> errors::Result<JavaToRustIterator<'_, T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        Ok(JavaToRustIterator::new(self.iterate(instance)?))
+    }
 
-                let retjint = tweaks::get_created_java_vms(
-                    &mut buffer,
-                    created_vms_size,
-                    &mut created_vms_size,
-                );
-                if retjint == JNI_OK {
-                    let act = (**buffer[0]).v1_4.AttachCurrentThread;
-                    let mut jni_environment: *mut JNIEnv = ptr::null_mut();
-                    (act)(
-                        buffer[0],
-                        (&mut jni_environment as *mut *mut JNIEnv) as *mut *mut c_void,
-                        ptr::null_mut(),
-                    );
-                    Some(jni_environment)
-                } else {
-                    error(&format!(
-                        "Error while retrieving the created JVMs: {}",
-                        retjint
-                    ));
-                    None
-                }
-            }
-        }
+    /// Transforms the elements of a Java collection to Rust incrementally, with bounded memory.
+    ///
+    /// Instead of serializing the whole collection to one JSON document (like `to_rust` does),
+    /// the elements are serialized and transferred in chunks of 1000 elements.
+    ///
+    /// The collection may be a Java `Iterable`, `Iterator`, `Stream`, array, `Map` or `java.sql.ResultSet`.
+    /// The entries of `Map`s are transformed to `(key, value)` tuples and the rows of `ResultSet`s
+    /// are transformed like `Map`s of the labels of the columns to their values.
+    pub fn to_rust_stream<T>(&self, instance: &Instance) -> errors::Result<ChunkedToRustIterator<'_, T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.to_rust_stream_with_chunk_size(instance, DEFAULT_CHUNK_SIZE)
     }
 
-    fn detach_current_thread(&self) {
+    /// Like `to_rust_stream`, defining the number of elements of each chunk.
+    pub fn to_rust_stream_with_chunk_size<T>(
+        &self,
+        instance: &Instance,
+        chunk_size: usize,
+    ) -> errors::Result<ChunkedToRustIterator<'_, T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        let serializer = self.create_instance(
+            "org.astonbitecode.j4rs.api.value.JsonChunkedSerializer",
+            &[
+                InvocationArg::from(self.clone_instance(instance)?),
+                InvocationArg::try_from(i32::try_from(chunk_size).unwrap_or(i32::MAX))?,
+            ],
+        )?;
+        Ok(ChunkedToRustIterator::new(self, serializer))
+    }
+
+    /// Transforms the rows of a `java.sql.ResultSet`, or of any tabular Java collection, to Rust, in batches.
+    ///
+    /// Each row is transformed like a `Map` of the labels of the columns to their values, so `T` is typically
+    /// a struct with fields named after the columns. The rows of a `ResultSet` are walked by Java and transferred
+    /// in chunks of 1000 rows, so that the JNI crossings are few. See `to_rust_stream`.
+    pub fn rows<T>(&self, instance: &Instance) -> errors::Result<ChunkedToRustIterator<'_, T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.to_rust_stream(instance)
+    }
+
+    /// Checks whether an Instance a is equal to some InvocationArg. 
+    /// 
+    /// The check is actually against the Java `Object.equals`, taking into consideration the possibility of null.
+    /// `NullPointerException` will not be thrown, even if one of the inputs is null.
+    pub fn check_equals(&self, instance: impl Borrow<Instance>, inv_arg: impl Borrow<InvocationArg>) -> errors::Result<bool> {
+        debug(&format!("Checking equality between instances of {} and {}", instance.borrow().class_name(), inv_arg.borrow().class_name()));
         unsafe {
-            // Get the number of the already created VMs. This is most probably 1, but we retrieve the number just in case...
-            let mut created_vms_size: jsize = 0;
-            tweaks::get_created_java_vms(
-                &mut Vec::with_capacity(created_vms_size as usize),
-                0,
-                &mut created_vms_size,
+            // Create InvocationArg Java Objects
+            let inv_arg_java_b = inv_arg.borrow().as_java_ptr_with_global_ref(self.jni_env)?;
+            // Call the checkEquals method
+            let java_boolean = (opt_to_res(cache::get_jni_call_boolean_method())?)(
+                self.jni_env,
+                instance.borrow().jinstance,
+                cache::get_check_equals_method()?,
+                inv_arg_java_b,
             );
 
-            if created_vms_size > 0 {
-                // Get the created VM
-                let mut buffer: Vec<*mut JavaVM> = Vec::with_capacity(created_vms_size as usize);
-                for _ in 0..created_vms_size {
-                    buffer.push(ptr::null_mut());
-                }
+            // Create and return the boolean
+            Self::do_return(
+                self.jni_env,
+                java_boolean,
+            )
+        }
+    }
 
-                let retjint = tweaks::get_created_java_vms(
-                    &mut buffer,
-                    created_vms_size,
-                    &mut created_vms_size,
+    /// Checks whether the Java objects of two Instances are equal, using the Java `Object.equals`.
+    ///
+    /// Two `null`s are equal and `NullPointerException` is never thrown.
+    pub fn equals(&self, a: impl Borrow<Instance>, b: impl Borrow<Instance>) -> errors::Result<bool> {
+        self.with_local_java_objects(a.borrow(), b.borrow(), |object_a, object_b| {
+            if object_a.is_null() {
+                return Ok(object_b.is_null());
+            }
+            unsafe {
+                let equal = (opt_to_res(cache::get_jni_call_boolean_method())?)(
+                    self.jni_env,
+                    object_a,
+                    cache::get_object_equals_method()?,
+                    object_b,
                 );
-                if retjint == JNI_OK {
-                    let dct = (**buffer[0]).v1_4.DetachCurrentThread;
-                    (dct)(buffer[0]);
-                } else {
-                    warn(&format!(
-                        "Error while retrieving the created JVMs: {}",
-                        retjint
-                    ));
-                }
+                Self::do_return(self.jni_env, equal)
             }
+        })
+    }
+
+    /// Checks whether two Instances refer to the very same Java object (like `==` in Java).
+    pub fn same_object(&self, a: impl Borrow<Instance>, b: impl Borrow<Instance>) -> errors::Result<bool> {
+        self.with_local_java_objects(a.borrow(), b.borrow(), |object_a, object_b| unsafe {
+            Ok((opt_to_res(cache::get_is_same_object())?)(self.jni_env, object_a, object_b))
+        })
+    }
+
+    /// Returns the Java `Object.hashCode` of the Java object of an Instance, or 0 if the object is `null`.
+    pub fn hash_code(&self, instance: impl Borrow<Instance>) -> errors::Result<i32> {
+        self.call_int_on_java_object(instance.borrow(), |object| unsafe {
+            Ok((opt_to_res(cache::get_jni_call_int_method())?)(
+                self.jni_env,
+                object,
+                cache::get_object_hash_code_method()?,
+            ))
+        })
+    }
+
+    /// Returns the `System.identityHashCode` of the Java object of an Instance, or 0 if the object is `null`.
+    pub fn identity_hash_code(&self, instance: impl Borrow<Instance>) -> errors::Result<i32> {
+        self.call_int_on_java_object(instance.borrow(), |object| unsafe {
+            Ok((opt_to_res(cache::get_jni_call_static_int_method())?)(
+                self.jni_env,
+                cache::get_system_class()?,
+                cache::get_system_identity_hash_code_method()?,
+                object,
+            ))
+        })
+    }
+
+    fn call_int_on_java_object(
+        &self,
+        instance: &Instance,
+        f: impl FnOnce(jobject) -> errors::Result<jint>,
+    ) -> errors::Result<i32> {
+        let object = self.local_java_object(instance.jinstance)?;
+        if object.is_null() {
+            return Ok(0);
         }
+        let result = f(object).and_then(|i| Self::do_return(self.jni_env, i));
+        jni_utils::delete_java_local_ref(self.jni_env, object);
+        result
     }
 
-    /// Returns the first `Instance` that is available from the passed `InstanceReceiver`s,
-    /// along with the index of the receiver that was selected and actually returned the instance.
+    /// Consumes an `Instance` and returns its jobject. The returned jobject is a JNI local reference.
+    pub fn instance_into_raw_object(&self, instance: Instance) -> errors::Result<jobject> {
+        debug(&format!("Getting the raw jobject from instance of class {}", instance.borrow().class_name()));
+        // Call the getObjectMethod. This returns a localref
+        let object_instance = unsafe {
+            (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                instance.jinstance,
+                cache::get_get_object_method()?,
+        )};
+
+        Self::do_return(
+            self.jni_env,
+            object_instance,
+        )
+    }
+
+    /// Creates an `Instance` of the Java object that is referenced by a JNI reference. The reference is not consumed.
     ///
-    /// This is a mostly naive implementation of select, because of [absence for selecting among mpsc channels](https://github.com/rust-lang/rust/issues/27800).
-    pub fn select(instance_receivers: &[&InstanceReceiver]) -> errors::Result<(usize, Instance)> {
-        loop {
-            for (index, ir) in instance_receivers.iter().enumerate() {
-                let res = ir.rx.try_recv();
-                if res.is_ok() {
-                    return Ok((index, res.unwrap()));
-                }
-            }
-            thread::yield_now();
+    /// This is the counterpart of `instance_into_raw_object`, for objects that are obtained using JNI directly.
+    ///
+    /// # Safety
+    ///
+    /// The `object` must be null, or a valid local or global JNI reference that is usable in the thread of this `Jvm`.
+    pub unsafe fn instance_from_raw_object(&self, object: jobject) -> errors::Result<Instance> {
+        debug("Creating an Instance from a raw jobject");
+        let class = if object.is_null() {
+            cache::get_object_class()?
+        } else {
+            ((**self.jni_env).v1_6.GetObjectClass)(self.jni_env, object)
+        };
+        // Call the InstanceGenerator.create method. This returns a localref
+        let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            self.jni_env,
+            cache::get_instance_generator_class()?,
+            cache::get_instance_generator_create_method()?,
+            object,
+            class,
+        );
+        if !object.is_null() {
+            jni_utils::delete_java_local_ref(self.jni_env, class);
         }
+        let java_instance = Self::do_return(self.jni_env, java_instance)?;
+        Self::do_return(
+            self.jni_env,
+            Instance::from_jobject_with_global_ref(java_instance)?,
+        )
     }
 
-    /// Returns the first `Instance` that is available from the passed `InstanceReceiver`s,
-    /// along with the index of the receiver that was selected and actually returned the instance.
+    /// Creates a direct `java.nio.ByteBuffer` that is backed by the memory of `bytes`, without copying it.
     ///
-    /// If there are no instances returned for the duration defined in timeout argument, an error is returned.
+    /// The returned buffer is read-only for Java.
     ///
-    /// This is a mostly naive implementation of select, because of [absence for selecting among mpsc channels](https://github.com/rust-lang/rust/issues/27800).
-    pub fn select_timeout(
-        instance_receivers: &[&InstanceReceiver],
-        timeout: &time::Duration,
-    ) -> errors::Result<(usize, Instance)> {
-        let start = time::Instant::now();
-        loop {
-            for (index, ir) in instance_receivers.iter().enumerate() {
-                let res = ir.rx.try_recv();
-                if res.is_ok() {
-                    return Ok((index, res.unwrap()));
-                }
-            }
-            if &start.elapsed() > timeout {
-                return Err(J4RsError::Timeout);
-            }
-            thread::yield_now();
+    /// # Safety
+    ///
+    /// Java accesses the memory of `bytes` directly. The caller must make sure that `bytes` is not dropped nor modified
+    /// for as long as the returned `Instance`, or any other Java reference to the buffer, may be used.
+    pub unsafe fn byte_buffer_from_slice(&self, bytes: &[u8]) -> errors::Result<Instance> {
+        debug(&format!("Creating a direct ByteBuffer of {} bytes", bytes.len()));
+        let byte_buffer = (opt_to_res(cache::get_jni_new_direct_byte_buffer())?)(
+            self.jni_env,
+            bytes.as_ptr() as *mut c_void,
+            bytes.len() as jlong,
+        );
+        Self::do_return(self.jni_env, ())?;
+        if byte_buffer.is_null() {
+            return Err(J4RsError::JniError("The JVM does not support direct access to buffers".to_string()));
         }
+        let byte_buffer = jni_utils::create_global_ref_from_local_ref(byte_buffer, self.jni_env)?;
+        let inv_arg = InvocationArg::RustBasic {
+            instance: Instance::new(byte_buffer, CLASS_BYTE_BUFFER)?,
+            class_name: CLASS_BYTE_BUFFER.to_string(),
+            serialized: false,
+        };
+        let writable = Instance::new(inv_arg.as_java_ptr_with_global_ref(self.jni_env)?, CLASS_BYTE_BUFFER)?;
+        let mut read_only = self.invoke(&writable, "asReadOnlyBuffer", InvocationArg::empty())?;
+        read_only.class_name = CLASS_BYTE_BUFFER.to_string();
+        Ok(read_only)
     }
-}
 
-impl Drop for Jvm {
-    fn drop(&mut self) {
-        if cache::remove_active_jvm() <= 0 {
-            if self.detach_thread_on_drop {
-                self.detach_current_thread();
+    /// Returns the memory of a direct `java.nio.ByteBuffer` as a slice, without copying it.
+    ///
+    /// The slice contains the whole capacity of the buffer, regardless of its position and limit.
+    /// It borrows the `Instance`, which keeps the buffer alive.
+    ///
+    /// An error is returned if the `Instance` is not a direct `java.nio.ByteBuffer`.
+    ///
+    /// # Safety
+    ///
+    /// The slice points to memory that is not owned by Rust. The caller must make sure that neither Java nor anything else
+    /// modifies or frees the memory of the buffer while the slice is in use. For buffers created with `byte_buffer_from_slice`,
+    /// this means that the original bytes must outlive the slice as well.
+    pub unsafe fn slice_from_byte_buffer<'a>(&self, instance: &'a Instance) -> errors::Result<&'a [u8]> {
+        debug(&format!("Getting the memory of a ByteBuffer of class {}", instance.class_name()));
+        // Call the getObject method. This returns a localref
+        let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+            self.jni_env,
+            instance.jinstance,
+            cache::get_get_object_method()?,
+        );
+        Self::do_return(self.jni_env, ())?;
+        let address = (opt_to_res(cache::get_jni_get_direct_buffer_address())?)(self.jni_env, object);
+        let capacity = (opt_to_res(cache::get_jni_get_direct_buffer_capacity())?)(self.jni_env, object);
+        jni_utils::delete_java_local_ref(self.jni_env, object);
+        Self::do_return(self.jni_env, ())?;
+
+        if address.is_null() || capacity < 0 {
+            Err(J4RsError::RustError(format!(
+                "The Instance of class {} is not a direct {}",
+                instance.class_name(),
+                CLASS_BYTE_BUFFER
+            )))
+        } else {
+            Ok(std::slice::from_raw_parts(address as *const u8, capacity as usize))
+        }
+    }
+
+    /// Calls `f` within a new JNI local frame that can hold at least `capacity` local references.
+    ///
+    /// The `LocalInstance`s that are created by the `LocalFrame` do not allocate JNI global references, like the `Instance`s do.
+    /// They are all released when `f` returns. This reduces the pressure to the Java GC when creating many short-lived objects,
+    /// e.g. in long loops.
+    pub fn with_local_frame<R, F>(&self, capacity: i32, f: F) -> errors::Result<R>
+        where
+            F: for<'f> FnOnce(&'f LocalFrame<'f>) -> errors::Result<R>,
+    {
+        debug(&format!("Pushing a local frame with capacity {}", capacity));
+        unsafe {
+            let push_result = ((**self.jni_env).v1_6.PushLocalFrame)(self.jni_env, capacity);
+            Self::do_return(self.jni_env, ())?;
+            if push_result != 0 {
+                return Err(J4RsError::JniError(format!(
+                    "Could not push a local frame with capacity {}",
+                    capacity
+                )));
             }
-            cache::set_thread_local_env(None);
+
+            let frame = LocalFrame::new(self);
+            let result = f(&frame);
+
+            debug("Popping the local frame");
+            ((**self.jni_env).v1_6.PopLocalFrame)(self.jni_env, ptr::null_mut());
+            result
         }
     }
-}
 
-/// A builder for Jvm
-pub struct JvmBuilder<'a> {
-    classpath_entries: Vec<ClasspathEntry<'a>>,
-    java_opts: Vec<JavaOpt<'a>>,
-    no_implicit_classpath: bool,
-    detach_thread_on_drop: bool,
-    lib_name_opt: Option<String>,
-    skip_setting_native_lib: bool,
-    base_path: Option<String>,
-    maven_settings: MavenSettings,
-    javafx: bool,
-    default_classloader: bool,
-    java_vm_opt: Option<*mut JavaVM>,
-    jobject_within_valid_classloader_opt: Option<jobject>,
-}
+    /// Enables or disables the tracking of the `Instance`s that hold Java references.
+    ///
+    /// When enabled, each created `Instance` is registered until it is dropped, closed or forgotten,
+    /// so that leaks can be diagnosed with `instance_stats`. The tracking adds overhead and is disabled by default.
+    pub fn set_instance_tracking(&self, enabled: bool) {
+        tracking::set_enabled(enabled);
+    }
+
+    /// Returns the number of the tracked `Instance`s that are alive, per Java class name.
+    ///
+    /// Only the `Instance`s that were created while the tracking was enabled are counted. See `set_instance_tracking`.
+    pub fn instance_stats(&self) -> HashMap<String, usize> {
+        tracking::stats()
+    }
+
+    /// Routes the `java.util.logging` output to the `log` crate.
+    ///
+    /// The handlers of the root Java logger are replaced by a handler that forwards the log records
+    /// of level up to `max_level`, using the logger names as targets.
+    /// The Java levels are mapped as follows: `SEVERE` to `Error`, `WARNING` to `Warn`, `INFO` to `Info`,
+    /// `CONFIG` and `FINE` to `Debug` and `FINER` and `FINEST` to `Trace`.
+    ///
+    /// SLF4J output can be routed as well, by using the `slf4j-jdk14` binding.
+    ///
+    /// The j4rs native library needs to be loaded by Java, like for the callbacks.
+    pub fn redirect_java_logging(&self, max_level: log::LevelFilter) -> errors::Result<()> {
+        self.invoke_static(
+            CLASS_RUST_LOG_HANDLER,
+            "install",
+            &[InvocationArg::try_from(max_level as i32)?.into_primitive()?],
+        )?;
+        Ok(())
+    }
+
+    /// Resolves the commonly used classes and methods that j4rs uses internally and caches them process-wide.
+    ///
+    /// The resolution normally happens lazily, the first time each of them is needed.
+    /// Calling `warm_up` once, after creating the `Jvm`, reduces the latency of the first calls in the threads
+    /// that get attached afterwards, as they use the already resolved classes and methods.
+    pub fn warm_up(&self) -> errors::Result<()> {
+        cache::warm_up()
+    }
+
+    /// Returns typed access to the management interface of the JVM,
+    /// like the memory usage, the garbage collection statistics and the number of threads and loaded classes.
+    pub fn management(&self) -> Management<'_> {
+        Management::new(self)
+    }
+
+    /// Returns the info and the stacktraces of all the live Java threads.
+    pub fn thread_dump(&self) -> errors::Result<Vec<JavaThreadInfo>> {
+        self.management().thread_dump()
+    }
+
+    /// Returns a `SharedJvm`, a handle to the Java VM that can be shared between threads.
+    ///
+    /// The threads that use the `SharedJvm` get attached to the Java VM automatically.
+    pub fn shared(&self) -> SharedJvm {
+        SharedJvm::new()
+    }
+
+    /// Returns a `SendableJvm`, a token that can be moved to another thread, in order to attach it to the Java VM
+    /// and get a `Jvm` for it.
+    pub fn to_sendable(&self) -> SendableJvm {
+        SendableJvm::new()
+    }
+
+    /// Returns a `JvmActor`, an asynchronous handle to a dedicated worker thread that executes Java invocations.
+    ///
+    /// The handle can be shared between threads that are not attached to the Java VM.
+    pub fn actor(&self) -> errors::Result<JvmActor> {
+        JvmActor::new()
+    }
+
+    /// Returns the `JNIEnv` of this `Jvm`, without consuming it.
+    ///
+    /// The `JNIEnv` is owned by the `Jvm`: it is valid only for the current thread and as long as the `Jvm` is alive.
+    pub fn raw_env(&self) -> *mut JNIEnv {
+        self.jni_env
+    }
+
+    /// Consumes the `Jvm` and returns its `JNIEnv`
+    pub fn into_raw(self) -> *mut JNIEnv {
+        debug("Getting the raw JNIEnv from the Jvm");
+
+        self.jni_env
+    }
+
+    /// Returns the Rust representation of the provided instance, boxed
+    pub fn to_rust_boxed<T>(&self, instance: Instance) -> errors::Result<Box<T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        metrics::timed(Operation::ToRust, || self.jobject_to_rust_boxed(instance.jinstance))
+    }
+
+    pub(crate) fn jobject_to_rust_boxed<T>(&self, jinstance: jobject) -> errors::Result<Box<T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        let t_type = TypeId::of::<T>();
+
+        // A Java null can only be transformed to types that accept a JSON null, like Option
+        if self.is_java_null(jinstance)? {
+            return serde_json::from_str::<T>("null").map(Box::new).map_err(|_| {
+                J4RsError::RustError(format!(
+                    "The Java object is null and cannot be transformed to {}",
+                    std::any::type_name::<T>()
+                ))
+            });
+        }
+
+        // The strategies depend only on the Rust type and the Java class, so they are classified once.
+        // The Java class is not even retrieved for the Rust types that the objects of any class are deserialized to.
+        let deserialized_for_any_class = cache::is_to_rust_deserialized_for_any_class(t_type).unwrap_or_else(|| {
+            let deserialized = !Self::to_rust_strategy_table().iter().any(|(_, t, _)| *t == t_type);
+            cache::set_to_rust_deserialized_for_any_class(t_type, deserialized);
+            deserialized
+        });
+        if deserialized_for_any_class {
+            return Ok(Box::new(self.jobject_to_rust_deserialized(jinstance)?));
+        }
+
+        unsafe {
+            // Call the getClassName method. This returns a localref
+            let object_class_name_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                jinstance,
+                cache::get_get_object_class_name_method()?,
+            );
+            let object_class_name_instance = jni_utils::create_global_ref_from_local_ref(
+                object_class_name_instance,
+                self.jni_env,
+            )?;
+            let class_name = &(jni_utils::string_from_jobject(object_class_name_instance, self.jni_env)?);
+            jni_utils::delete_java_ref(self.jni_env, object_class_name_instance);
+
+            let strategy = cache::get_to_rust_strategy(t_type, class_name).unwrap_or_else(|| {
+                let strategy = Self::classify_to_rust_strategy(t_type, class_name);
+                cache::set_to_rust_strategy(t_type, class_name, strategy);
+                strategy
+            });
+            match strategy {
+                cache::ToRustStrategy::Direct(transformation) => {
+                    // Call the getObjectMethod. This returns a localref
+                    let object_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                        self.jni_env,
+                        jinstance,
+                        cache::get_get_object_method()?,
+                    );
+                    let object_instance =
+                        jni_utils::create_global_ref_from_local_ref(object_instance, self.jni_env)?;
+                    let v_any = transformation(object_instance, self.jni_env);
+                    jni_utils::delete_java_ref(self.jni_env, object_instance);
+
+                    match v_any?.downcast::<T>() {
+                        Ok(v) => Ok(v),
+                        Err(error) => Err(errors::J4RsError::RustError(format!(
+                            "Could not downcast to Rust type: {:?}",
+                            error
+                        ))),
+                    }
+                }
+                cache::ToRustStrategy::Special(special) => {
+                    match self.special_from_jobject::<T>(special, jinstance, class_name)? {
+                        Some(v) => Ok(v),
+                        None => Ok(Box::new(self.jobject_to_rust_deserialized(jinstance)?)),
+                    }
+                }
+                cache::ToRustStrategy::Deserialized => Ok(Box::new(self.jobject_to_rust_deserialized(jinstance)?)),
+            }
+        }
+    }
+
+    /// Classifies how the Java objects of the class `class_name` are transformed to the Rust type `t_type`.
+    fn classify_to_rust_strategy(t_type: TypeId, class_name: &str) -> cache::ToRustStrategy {
+        Self::to_rust_strategy_table()
+            .into_iter()
+            .find(|(c, t, _)| *c == class_name && *t == t_type)
+            .map(|(_, _, strategy)| strategy)
+            .unwrap_or(cache::ToRustStrategy::Deserialized)
+    }
+
+    /// The Java classes and Rust types that are not transformed by deserialization, along with their strategies.
+    fn to_rust_strategy_table() -> Vec<(&'static str, TypeId, cache::ToRustStrategy)> {
+        use cache::SpecialToRust::*;
+        use cache::ToRustStrategy::*;
+
+        macro_rules! direct {
+            ($jni_transformation:path) => {
+                Direct(|obj, jni_env| unsafe { Ok(Box::new($jni_transformation(obj, jni_env)?) as Box<dyn Any>) })
+            };
+        }
+        macro_rules! nested {
+            ($jni_transformation:path) => {
+                Direct(|obj, jni_env| unsafe { jni_utils::nested_array_from_jobject(obj, jni_env, $jni_transformation) })
+            };
+        }
+
+        let mut table = vec![
+            (CLASS_STRING, TypeId::of::<String>(), direct!(jni_utils::string_from_jobject)),
+            (CLASS_INTEGER, TypeId::of::<i32>(), direct!(jni_utils::i32_from_jobject)),
+            (PRIMITIVE_INT, TypeId::of::<i32>(), direct!(jni_utils::i32_from_jobject)),
+            (CLASS_BYTE, TypeId::of::<i8>(), direct!(jni_utils::i8_from_jobject)),
+            (PRIMITIVE_BYTE, TypeId::of::<i8>(), direct!(jni_utils::i8_from_jobject)),
+            (CLASS_SHORT, TypeId::of::<i16>(), direct!(jni_utils::i16_from_jobject)),
+            (PRIMITIVE_SHORT, TypeId::of::<i16>(), direct!(jni_utils::i16_from_jobject)),
+            (CLASS_CHARACTER, TypeId::of::<u16>(), direct!(jni_utils::u16_from_jobject)),
+            (PRIMITIVE_CHAR, TypeId::of::<u16>(), direct!(jni_utils::u16_from_jobject)),
+            (CLASS_LONG, TypeId::of::<i64>(), direct!(jni_utils::i64_from_jobject)),
+            (PRIMITIVE_LONG, TypeId::of::<i64>(), direct!(jni_utils::i64_from_jobject)),
+            (CLASS_FLOAT, TypeId::of::<f32>(), direct!(jni_utils::f32_from_jobject)),
+            (PRIMITIVE_FLOAT, TypeId::of::<f32>(), direct!(jni_utils::f32_from_jobject)),
+            (CLASS_DOUBLE, TypeId::of::<f64>(), direct!(jni_utils::f64_from_jobject)),
+            (PRIMITIVE_DOUBLE, TypeId::of::<f64>(), direct!(jni_utils::f64_from_jobject)),
+            (PRIMITIVE_BYTE_ARRAY, TypeId::of::<Vec<i8>>(), direct!(jni_utils::i8_array_from_jobject)),
+            (PRIMITIVE_BYTE_ARRAY, TypeId::of::<Vec<u8>>(), direct!(jni_utils::u8_array_from_jobject)),
+            (PRIMITIVE_SHORT_ARRAY, TypeId::of::<Vec<i16>>(), direct!(jni_utils::i16_array_from_jobject)),
+            (PRIMITIVE_CHAR_ARRAY, TypeId::of::<Vec<u16>>(), direct!(jni_utils::u16_array_from_jobject)),
+            (PRIMITIVE_INT_ARRAY, TypeId::of::<Vec<i32>>(), direct!(jni_utils::i32_array_from_jobject)),
+            (PRIMITIVE_LONG_ARRAY, TypeId::of::<Vec<i64>>(), direct!(jni_utils::i64_array_from_jobject)),
+            (PRIMITIVE_FLOAT_ARRAY, TypeId::of::<Vec<f32>>(), direct!(jni_utils::f32_array_from_jobject)),
+            (PRIMITIVE_DOUBLE_ARRAY, TypeId::of::<Vec<f64>>(), direct!(jni_utils::f64_array_from_jobject)),
+            (PRIMITIVE_BOOLEAN_ARRAY, TypeId::of::<Vec<bool>>(), direct!(jni_utils::boolean_array_from_jobject)),
+            // The two-dimensional arrays of primitives
+            ("[[Z", TypeId::of::<Vec<Vec<bool>>>(), nested!(jni_utils::boolean_array_from_jobject)),
+            ("[[B", TypeId::of::<Vec<Vec<i8>>>(), nested!(jni_utils::i8_array_from_jobject)),
+            ("[[B", TypeId::of::<Vec<Vec<u8>>>(), nested!(jni_utils::u8_array_from_jobject)),
+            ("[[S", TypeId::of::<Vec<Vec<i16>>>(), nested!(jni_utils::i16_array_from_jobject)),
+            ("[[C", TypeId::of::<Vec<Vec<u16>>>(), nested!(jni_utils::u16_array_from_jobject)),
+            ("[[I", TypeId::of::<Vec<Vec<i32>>>(), nested!(jni_utils::i32_array_from_jobject)),
+            ("[[J", TypeId::of::<Vec<Vec<i64>>>(), nested!(jni_utils::i64_array_from_jobject)),
+            ("[[F", TypeId::of::<Vec<Vec<f32>>>(), nested!(jni_utils::f32_array_from_jobject)),
+            ("[[D", TypeId::of::<Vec<Vec<f64>>>(), nested!(jni_utils::f64_array_from_jobject)),
+            (CLASS_INSTANT, TypeId::of::<time::SystemTime>(), Special(Time)),
+            (CLASS_DURATION, TypeId::of::<time::Duration>(), Special(Time)),
+        ];
+
+        for class_name in [CLASS_BIG_DECIMAL, CLASS_BIG_INTEGER] {
+            let big_number_types = [TypeId::of::<String>(), TypeId::of::<i128>(), TypeId::of::<u128>()];
+            table.extend(big_number_types.map(|t| (class_name, t, Special(BigNumber))));
+            #[cfg(feature = "rust_decimal")]
+            table.push((class_name, TypeId::of::<rust_decimal::Decimal>(), Special(BigNumber)));
+            #[cfg(feature = "num-bigint")]
+            table.push((class_name, TypeId::of::<num_bigint::BigInt>(), Special(BigNumber)));
+        }
+
+        let unsigned_types = [TypeId::of::<u8>(), TypeId::of::<u32>(), TypeId::of::<u64>(), TypeId::of::<usize>()];
+        for class_name in [
+            CLASS_BYTE,
+            PRIMITIVE_BYTE,
+            CLASS_SHORT,
+            PRIMITIVE_SHORT,
+            CLASS_INTEGER,
+            PRIMITIVE_INT,
+            CLASS_LONG,
+            PRIMITIVE_LONG,
+        ] {
+            table.extend(unsigned_types.iter().map(|t| (class_name, *t, Special(Unsigned))));
+        }
+
+        #[cfg(feature = "chrono")]
+        table.extend([
+            (CLASS_INSTANT, TypeId::of::<chrono::DateTime<chrono::Utc>>(), Special(Time)),
+            (CLASS_DURATION, TypeId::of::<chrono::TimeDelta>(), Special(Time)),
+            (CLASS_LOCAL_DATE_TIME, TypeId::of::<chrono::NaiveDateTime>(), Special(Time)),
+        ]);
+        #[cfg(feature = "uuid")]
+        table.push((CLASS_UUID, TypeId::of::<uuid::Uuid>(), Special(Uuid)));
+        table
+    }
+
+    /// Applies a transformation of big numbers, unsigned numbers, times or UUIDs to `T`.
+    unsafe fn special_from_jobject<T: Any>(
+        &self,
+        special: cache::SpecialToRust,
+        jinstance: jobject,
+        class_name: &str,
+    ) -> errors::Result<Option<Box<T>>> {
+        match special {
+            cache::SpecialToRust::BigNumber => self.big_number_from_jobject::<T>(jinstance, class_name),
+            cache::SpecialToRust::Unsigned => self.unsigned_from_jobject::<T>(jinstance, class_name),
+            cache::SpecialToRust::Time => self.time_from_jobject::<T>(jinstance, class_name),
+            #[cfg(feature = "uuid")]
+            cache::SpecialToRust::Uuid => self.uuid_from_jobject::<T>(jinstance, class_name),
+        }
+    }
+
+    /// Returns true if the Java object that is wrapped by the `jinstance` is `null`.
+    pub(crate) fn is_java_null(&self, jinstance: jobject) -> errors::Result<bool> {
+        let object = self.local_java_object(jinstance)?;
+        let is_null = object.is_null();
+        if !is_null {
+            jni_utils::delete_java_local_ref(self.jni_env, object);
+        }
+        Ok(is_null)
+    }
+
+    /// Returns a local reference to the Java object that is wrapped by the `jinstance`, or null.
+    fn local_java_object(&self, jinstance: jobject) -> errors::Result<jobject> {
+        unsafe {
+            // Call the getObject method. This returns a localref
+            let object = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                jinstance,
+                cache::get_get_object_method()?,
+            );
+            Self::do_return(self.jni_env, object)
+        }
+    }
+
+    /// Calls `f` with local references to the Java objects of the Instances `a` and `b` and deletes the references afterwards.
+    fn with_local_java_objects<T>(
+        &self,
+        a: &Instance,
+        b: &Instance,
+        f: impl FnOnce(jobject, jobject) -> errors::Result<T>,
+    ) -> errors::Result<T> {
+        let object_a = self.local_java_object(a.jinstance)?;
+        let object_b = self.local_java_object(b.jinstance);
+        let result = object_b.and_then(|object_b| {
+            let result = f(object_a, object_b);
+            if !object_b.is_null() {
+                jni_utils::delete_java_local_ref(self.jni_env, object_b);
+            }
+            result
+        });
+        if !object_a.is_null() {
+            jni_utils::delete_java_local_ref(self.jni_env, object_a);
+        }
+        result
+    }
+
+    /// Transforms a `java.math.BigDecimal` or a `java.math.BigInteger` to `T`,
+    /// if `T` is a `String`, an `i128`, a `u128`, or a big number type of an enabled feature.
+    fn big_number_from_jobject<T: Any>(
+        &self,
+        jinstance: jobject,
+        class_name: &str,
+    ) -> errors::Result<Option<Box<T>>> {
+        let t_type = TypeId::of::<T>();
+        let method_name = if CLASS_BIG_DECIMAL == class_name {
+            "toPlainString"
+        } else if CLASS_BIG_INTEGER == class_name {
+            "toString"
+        } else {
+            return Ok(None);
+        };
+
+        // The jobject is owned by the caller
+        let big_instance = Instance {
+            jinstance,
+            class_name: class_name.to_string(),
+            skip_deleting_jobject: true,
+        };
+        let number: String = self.invoke_to_rust(&big_instance, method_name, InvocationArg::empty())?;
+        let parse_error = |error: &dyn std::fmt::Display| {
+            J4RsError::ParseError(format!(
+                "{} of class {} cannot be transformed to {}: {}",
+                number,
+                class_name,
+                std::any::type_name::<T>(),
+                error
+            ))
+        };
+
+        let big: Box<dyn Any> = if t_type == TypeId::of::<i128>() {
+            Box::new(number.parse::<i128>().map_err(|error| parse_error(&error))?)
+        } else if t_type == TypeId::of::<u128>() {
+            Box::new(number.parse::<u128>().map_err(|error| parse_error(&error))?)
+        } else {
+            #[cfg(feature = "rust_decimal")]
+            if t_type == TypeId::of::<rust_decimal::Decimal>() {
+                let decimal = number.parse::<rust_decimal::Decimal>().map_err(|error| parse_error(&error))?;
+                return Ok((Box::new(decimal) as Box<dyn Any>).downcast::<T>().ok());
+            }
+            #[cfg(feature = "num-bigint")]
+            if t_type == TypeId::of::<num_bigint::BigInt>() {
+                let big_int = number.parse::<num_bigint::BigInt>().map_err(|error| parse_error(&error))?;
+                return Ok((Box::new(big_int) as Box<dyn Any>).downcast::<T>().ok());
+            }
+            Box::new(number.clone())
+        };
+        Ok(big.downcast::<T>().ok())
+    }
+
+    /// Transforms a `java.time.Instant`, `java.time.Duration` or `java.time.LocalDateTime` to `T`,
+    /// if `T` is a `std::time::SystemTime`, a `std::time::Duration`, or a date-time type of the `chrono` feature.
+    fn time_from_jobject<T: Any>(
+        &self,
+        jinstance: jobject,
+        class_name: &str,
+    ) -> errors::Result<Option<Box<T>>> {
+        let t_type = TypeId::of::<T>();
+        // The jobject is owned by the caller
+        let time_instance = Instance {
+            jinstance,
+            class_name: class_name.to_string(),
+            skip_deleting_jobject: true,
+        };
+        let seconds: i64 = match class_name {
+            CLASS_INSTANT => self.invoke_to_rust(&time_instance, "getEpochSecond", InvocationArg::empty())?,
+            CLASS_DURATION => self.invoke_to_rust(&time_instance, "getSeconds", InvocationArg::empty())?,
+            CLASS_LOCAL_DATE_TIME => {
+                let utc = self.static_class_field(CLASS_ZONE_OFFSET, "UTC")?;
+                self.invoke_to_rust(&time_instance, "toEpochSecond", &[InvocationArg::from(utc)])?
+            }
+            _ => return Ok(None),
+        };
+        let nanos: i32 = self.invoke_to_rust(&time_instance, "getNano", InvocationArg::empty())?;
+        let range_error = || {
+            J4RsError::ParseError(format!(
+                "{}s and {}ns of class {} cannot be transformed to {}",
+                seconds,
+                nanos,
+                class_name,
+                std::any::type_name::<T>(),
+            ))
+        };
+
+        // The nanos of all the java.time classes are in the range 0 to 999,999,999
+        let since_epoch = time::Duration::new(seconds.unsigned_abs(), nanos as u32);
+        let time: Box<dyn Any> = if t_type == TypeId::of::<time::SystemTime>() {
+            let system_time = if seconds >= 0 {
+                time::UNIX_EPOCH.checked_add(since_epoch)
+            } else {
+                time::UNIX_EPOCH
+                    .checked_sub(time::Duration::from_secs(seconds.unsigned_abs()))
+                    .and_then(|t| t.checked_add(time::Duration::from_nanos(nanos as u64)))
+            };
+            Box::new(system_time.ok_or_else(range_error)?)
+        } else if t_type == TypeId::of::<time::Duration>() {
+            if seconds < 0 {
+                return Err(range_error());
+            }
+            Box::new(since_epoch)
+        } else {
+            #[cfg(feature = "chrono")]
+            if t_type == TypeId::of::<chrono::DateTime<chrono::Utc>>() {
+                let date_time = chrono::DateTime::from_timestamp(seconds, nanos as u32).ok_or_else(range_error)?;
+                return Ok((Box::new(date_time) as Box<dyn Any>).downcast::<T>().ok());
+            } else if t_type == TypeId::of::<chrono::NaiveDateTime>() {
+                let date_time = chrono::DateTime::from_timestamp(seconds, nanos as u32).ok_or_else(range_error)?;
+                return Ok((Box::new(date_time.naive_utc()) as Box<dyn Any>).downcast::<T>().ok());
+            } else if t_type == TypeId::of::<chrono::TimeDelta>() {
+                let delta = chrono::TimeDelta::new(seconds, nanos as u32).ok_or_else(range_error)?;
+                return Ok((Box::new(delta) as Box<dyn Any>).downcast::<T>().ok());
+            }
+            return Ok(None);
+        };
+        Ok(time.downcast::<T>().ok())
+    }
+
+    /// Transforms a `java.util.UUID` to `T`, if `T` is a `uuid::Uuid`.
+    ///
+    /// The most and least significant bits are retrieved directly, without any string parsing.
+    #[cfg(feature = "uuid")]
+    fn uuid_from_jobject<T: Any>(
+        &self,
+        jinstance: jobject,
+        class_name: &str,
+    ) -> errors::Result<Option<Box<T>>> {
+        // The jobject is owned by the caller
+        let uuid_instance = Instance {
+            jinstance,
+            class_name: class_name.to_string(),
+            skip_deleting_jobject: true,
+        };
+        let most: i64 = self.invoke_to_rust(&uuid_instance, "getMostSignificantBits", InvocationArg::empty())?;
+        let least: i64 = self.invoke_to_rust(&uuid_instance, "getLeastSignificantBits", InvocationArg::empty())?;
+        let uuid: Box<dyn Any> = Box::new(uuid::Uuid::from_u64_pair(most as u64, least as u64));
+        Ok(uuid.downcast::<T>().ok())
+    }
+
+    /// Transforms a Java integral number to `T`, if `T` is one of `u8`, `u32`, `u64` or `usize`.
+    ///
+    /// Returns an error if the number is negative or does not fit in `T`.
+    unsafe fn unsigned_from_jobject<T: Any>(
+        &self,
+        jinstance: jobject,
+        class_name: &str,
+    ) -> errors::Result<Option<Box<T>>> {
+        let t_type = TypeId::of::<T>();
+        let transformation: unsafe fn(jobject, *mut JNIEnv) -> errors::Result<i64> =
+            if JavaClass::Byte.get_class_str() == class_name || PRIMITIVE_BYTE == class_name {
+                |obj, jni_env| jni_utils::i8_from_jobject(obj, jni_env).map(i64::from)
+            } else if JavaClass::Short.get_class_str() == class_name || PRIMITIVE_SHORT == class_name {
+                |obj, jni_env| jni_utils::i16_from_jobject(obj, jni_env).map(i64::from)
+            } else if JavaClass::Integer.get_class_str() == class_name || PRIMITIVE_INT == class_name {
+                |obj, jni_env| jni_utils::i32_from_jobject(obj, jni_env).map(i64::from)
+            } else if JavaClass::Long.get_class_str() == class_name || PRIMITIVE_LONG == class_name {
+                jni_utils::i64_from_jobject
+            } else {
+                return Ok(None);
+            };
+
+        // Call the getObjectMethod. This returns a localref
+        let object_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+            self.jni_env,
+            jinstance,
+            cache::get_get_object_method()?,
+        );
+        let object_instance =
+            jni_utils::create_global_ref_from_local_ref(object_instance, self.jni_env)?;
+        let value = transformation(object_instance, self.jni_env);
+        jni_utils::delete_java_ref(self.jni_env, object_instance);
+        let value = value?;
+
+        let overflow_error = || {
+            J4RsError::ParseError(format!(
+                "{} of class {} cannot be transformed to {}",
+                value,
+                class_name,
+                std::any::type_name::<T>()
+            ))
+        };
+        let unsigned: Box<dyn Any> = if t_type == TypeId::of::<u8>() {
+            Box::new(u8::try_from(value).map_err(|_| overflow_error())?)
+        } else if t_type == TypeId::of::<u32>() {
+            Box::new(u32::try_from(value).map_err(|_| overflow_error())?)
+        } else if t_type == TypeId::of::<u64>() {
+            Box::new(u64::try_from(value).map_err(|_| overflow_error())?)
+        } else {
+            Box::new(usize::try_from(value).map_err(|_| overflow_error())?)
+        };
+        Ok(unsigned.downcast::<T>().ok())
+    }
+
+    /// Returns the Rust representation of the provided instance
+    pub fn to_rust<T>(&self, instance: Instance) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.to_rust_boxed(instance).map(|v| *v)
+    }
+
+    pub fn to_rust_deserialized<T>(&self, instance: Instance) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        metrics::timed(Operation::ToRust, || self.jobject_to_rust_deserialized(instance.jinstance))
+    }
+
+    fn jobject_to_rust_deserialized<T>(&self, jinstance: jobject) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        // serde_json::Values cannot contain byte strings and are always transformed from JSON
+        #[cfg(feature = "cbor")]
+        if codec::format() == SerializationFormat::Cbor && TypeId::of::<T>() != TypeId::of::<serde_json::Value>() {
+            return match self.cbor_to_rust(jinstance) {
+                Ok(value) => Ok(value),
+                // Like in JSON, the keys of maps are text. Maps with other keys are retried using their entries.
+                Err(error) => match self.map_entries_to_rust(jinstance)? {
+                    Some(value) => Ok(value),
+                    None => Err(error),
+                },
+            };
+        }
+        unsafe {
+            debug("Invoking the getJson method");
+            // Call the getJson method. This returns a localref
+            let json_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+                self.jni_env,
+                jinstance,
+                cache::get_get_json_method()?,
+            );
+            let _ = Self::do_return(self.jni_env, "")?;
+            debug("Transforming jstring to rust String");
+            let global_json_instance =
+                jni_utils::create_global_ref_from_local_ref(json_instance, self.jni_env)?;
+            let json = jni_utils::jstring_to_rust_string(self, global_json_instance as jstring)?;
+            jni_utils::delete_java_ref(self.jni_env, global_json_instance);
+            match serde_json::from_str(&json) {
+                Ok(value) => Self::do_return(self.jni_env, value),
+                // JSON objects have only String keys. Maps with other keys are retried using their entries.
+                Err(error) => match self.map_entries_to_rust(jinstance)? {
+                    Some(value) => Ok(value),
+                    None => Err(error.into()),
+                },
+            }
+        }
+    }
+
+    /// Deserializes the entries of a Java `Map` to `T`, if the `jinstance` is a `Map`.
+    ///
+    /// Unlike the JSON objects, the keys of the entries do not need to be `String`s.
+    fn map_entries_to_rust<T>(&self, jinstance: jobject) -> errors::Result<Option<T>>
+        where
+            T: DeserializeOwned + Any,
+    {
+        // The jobject is owned by the caller
+        let map_instance = Instance {
+            jinstance,
+            class_name: cache::UNKNOWN_FOR_RUST.to_string(),
+            skip_deleting_jobject: true,
+        };
+        if !self.is_instance_of(&map_instance, CLASS_MAP)? {
+            return Ok(None);
+        }
+        let entries: Vec<(serde_json::Value, serde_json::Value)> = self
+            .to_rust_stream_with_chunk_size(&map_instance, i32::MAX as usize)?
+            .collect::<errors::Result<_>>()?;
+        let deserializer =
+            serde::de::value::MapDeserializer::<_, serde_json::Error>::new(entries.into_iter());
+        Ok(Some(T::deserialize(deserializer)?))
+    }
+
+    /// Deploys an artifact in the default j4rs jars location.
+    ///
+    /// This is useful for build scripts that need jars for the runtime that can be downloaded from e.g. Maven.
+    ///
+    /// By default, the function deploys __only__ the specified artifact, not its transitive dependencies.
+    /// For Maven artifacts, the transitive dependencies can be deployed as well by using `MavenArtifact::with_transitive(true)`.
+    pub fn deploy_artifact<T: Any + JavaArtifact>(&self, artifact: &T) -> errors::Result<()> {
+        let artifact = artifact as &dyn Any;
+        if let Some(maven_artifact) = artifact.downcast_ref::<MavenArtifact>() {
+            let deployers = self.maven_deployers(&maven_artifact.base)?;
+            let coordinates = || -> errors::Result<Vec<InvocationArg>> {
+                Ok(vec![
+                    InvocationArg::try_from(&maven_artifact.group)?,
+                    InvocationArg::try_from(&maven_artifact.id)?,
+                    InvocationArg::try_from(&maven_artifact.version)?,
+                    InvocationArg::try_from(&maven_artifact.qualifier)?,
+                ])
+            };
+
+            if maven_artifact.transitive {
+                let deployers: Vec<InvocationArg> =
+                    deployers.into_iter().map(InvocationArg::from).collect();
+                let deployers_list = Self::do_create_java_list(
+                    self.jni_env,
+                    "org.astonbitecode.j4rs.api.deploy.SimpleMavenDeployer",
+                    &deployers,
+                )?;
+                let instance = self.create_instance(
+                    "org.astonbitecode.j4rs.api.deploy.MavenDependencyResolver",
+                    &[InvocationArg::from(deployers_list)],
+                )?;
+                let _ = self.invoke(&instance, "deploy", &coordinates()?)?;
+            } else {
+                for instance in deployers {
+                    if self.invoke(&instance, "deploy", &coordinates()?).is_ok() {
+                        break;
+                    }
+                }
+            }
+
+            Ok(())
+        } else if let Some(ivy_artifact) = artifact.downcast_ref::<IvyArtifact>() {
+            let instance = self.create_instance(
+                "org.astonbitecode.j4rs.api.deploy.IvyDeployer",
+                &[
+                    InvocationArg::try_from(&ivy_artifact.repo)?,
+                    InvocationArg::try_from(&ivy_artifact.pattern)?,
+                    InvocationArg::try_from(&ivy_artifact.base)?,
+                ],
+            )?;
+
+            let _ = self.invoke(
+                &instance,
+                "deploy",
+                &[
+                    InvocationArg::try_from(&ivy_artifact.organisation)?,
+                    InvocationArg::try_from(&ivy_artifact.module)?,
+                    InvocationArg::try_from(&ivy_artifact.revision)?,
+                    InvocationArg::try_from(&ivy_artifact.artifact)?,
+                    InvocationArg::try_from(&ivy_artifact.artifact_type)?,
+                    InvocationArg::try_from(&ivy_artifact.ext)?,
+                    InvocationArg::try_from(&ivy_artifact.classifier)?,
+                ],
+            )?;
+            Ok(())
+        } else if let Some(local_jar_artifact) = artifact.downcast_ref::<LocalJarArtifact>() {
+            let instance = self.create_instance(
+                "org.astonbitecode.j4rs.api.deploy.FileSystemDeployer",
+                &[InvocationArg::try_from(&local_jar_artifact.base)?],
+            )?;
+
+            let _ = self.invoke(
+                &instance,
+                "deploy",
+                &[InvocationArg::try_from(&local_jar_artifact.path)?],
+            )?;
+            Ok(())
+        } else {
+            Err(J4RsError::GeneralError(format!(
+                "Don't know how to deploy artifacts of {:?}",
+                artifact.type_id()
+            )))
+        }
+    }
+
+    /// Adds a jar or a directory to the classpath of the running JVM.
+    ///
+    /// The classes and resources of the entry are available to any invocation that follows.
+    /// This requires the j4rs classloader, so it fails if the `Jvm` was created with `with_default_classloader`.
+    pub fn add_classpath_entry(&self, path: &str) -> errors::Result<()> {
+        let _ = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.DeployUtils",
+            "addClasspathEntry",
+            &[InvocationArg::try_from(path)?],
+        )?;
+        Ok(())
+    }
+
+    /// Loads the current version of the jar at `path`, replacing any previously loaded version of it.
+    ///
+    /// Each version of the jar is loaded in isolation by its own classloader, which prefers the classes of the jar
+    /// over the ones of the classpath. After the reload, the classes that are referenced by name
+    /// (e.g. in `create_instance` or `invoke_static`) are resolved from the latest version of the jar.
+    /// The `Instance`s that are already created keep using the classes of the version they were created with.
+    ///
+    /// This is meant for iterating on Java code during development, without restarting the Rust process.
+    pub fn reload_jar(&self, path: &str) -> errors::Result<()> {
+        let _ = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.ReloadableJars",
+            "reload",
+            &[InvocationArg::try_from(path)?],
+        )?;
+        Ok(())
+    }
+
+    /// Lists the paths of the jars and directories of the classpath of the running JVM.
+    ///
+    /// These are the entries of the `java.class.path` system property, followed by the ones that are added
+    /// to the j4rs classloader (e.g. with `add_classpath_entry` or by deploying artifacts).
+    pub fn effective_classpath(&self) -> errors::Result<Vec<String>> {
+        let classpath = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.DeployUtils",
+            "effectiveClasspath",
+            InvocationArg::empty(),
+        )?;
+        self.to_rust(classpath)
+    }
+
+    /// Finds the jar, or the directory, that provides the class `class_name`, as seen by the classloader
+    /// that loads the classes of the invocations.
+    ///
+    /// Returns `None` if the class is not found, or if it is not provided by a jar or directory (like the classes of the JDK).
+    pub fn which_jar_provides(&self, class_name: &str) -> errors::Result<Option<PathBuf>> {
+        let path = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.DeployUtils",
+            "whichJarProvides",
+            &[InvocationArg::try_from(class_name)?],
+        )?;
+        let path: Option<String> = self.to_rust(path)?;
+        Ok(path.map(PathBuf::from))
+    }
+
+    /// Returns the class name of the classloader that loads the classes of the invocations.
+    ///
+    /// This is the `J4rsClassLoader`, unless the `Jvm` was created with `with_default_classloader`, or a `ClassScope` is entered.
+    pub fn class_loader_name(&self) -> errors::Result<String> {
+        let name = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.DeployUtils",
+            "currentClassLoaderName",
+            InvocationArg::empty(),
+        )?;
+        self.to_rust(name)
+    }
+
+    /// Reads the bytes of the classpath resource `name`, like `config/defaults.yaml`, which may be inside a jar.
+    ///
+    /// The resource is loaded by the classloader that loads the classes of the invocations.
+    /// An error is returned if the resource is not found.
+    pub fn read_resource(&self, name: &str) -> errors::Result<Vec<u8>> {
+        let bytes = self.invoke_static(CLASS_RESOURCES, "readResource", &[InvocationArg::try_from(name)?])?;
+        if bytes.is_null(self)? {
+            Err(J4RsError::GeneralError(format!("Resource {} was not found in the classpath", name)))
+        } else {
+            self.to_rust(bytes)
+        }
+    }
+
+    /// Defines a Java class out of its `bytecode`, as found in a `.class` file.
+    ///
+    /// The class is defined in the j4rs classloader and can be used by name after this call.
+    /// This requires the j4rs classloader, so it fails if the `Jvm` was created with `with_default_classloader`.
+    pub fn define_class(&self, name: &str, bytecode: &[u8]) -> errors::Result<()> {
+        // The buffer is used only during the invocation, while the bytecode is borrowed
+        let buffer = unsafe { self.byte_buffer_from_slice(bytecode)? };
+        let _ = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.DeployUtils",
+            "defineClass",
+            &[InvocationArg::try_from(name)?, InvocationArg::from(buffer)],
+        )?;
+        Ok(())
+    }
+
+    /// Compiles Java sources in memory and defines the resulting classes, like with `define_class`.
+    ///
+    /// Each source is given along with the fully qualified name of its top-level class, e.g. `("com.foo.Gen", source)`.
+    /// The sources are compiled together, against the classpath of the `Jvm`, so they may refer to each other.
+    /// If the compilation fails, the error contains the diagnostics of the compiler.
+    ///
+    /// This requires a JDK, which provides the compiler, and the j4rs classloader.
+    pub fn compile_and_load(&self, sources: &[(&str, &str)]) -> errors::Result<()> {
+        let class_names = sources
+            .iter()
+            .map(|(class_name, _)| InvocationArg::try_from(*class_name))
+            .collect::<errors::Result<Vec<InvocationArg>>>()?;
+        let codes = sources
+            .iter()
+            .map(|(_, code)| InvocationArg::try_from(*code))
+            .collect::<errors::Result<Vec<InvocationArg>>>()?;
+        let _ = self.invoke_static(
+            "org.astonbitecode.j4rs.api.deploy.JavaSourceCompiler",
+            "compileAndLoad",
+            &[
+                InvocationArg::from(self.create_java_array(CLASS_STRING, &class_names)?),
+                InvocationArg::from(self.create_java_array(CLASS_STRING, &codes)?),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Serializes the Java object of an `Instance` using the Java serialization (`ObjectOutputStream`).
+    ///
+    /// The object must be `java.io.Serializable`. The returned bytes may be persisted and restored with `deserialize_instance`,
+    /// even after a restart of the application.
+    pub fn serialize_instance(&self, instance: &Instance) -> errors::Result<Vec<u8>> {
+        let bytes = self.invoke_static(
+            CLASS_JAVA_SERIALIZATION,
+            "serialize",
+            &[InvocationArg::from(self.clone_instance(instance)?)],
+        )?;
+        self.to_rust(bytes)
+    }
+
+    /// Deserializes an `Instance` out of `bytes` that are created by `serialize_instance`, using the Java serialization (`ObjectInputStream`).
+    ///
+    /// The classes of the serialized object need to be available in the classpath.
+    /// The returned `Instance` is of class `java.lang.Object` and may be cast using `Jvm::cast`.
+    ///
+    /// # Security
+    ///
+    /// Deserializing untrusted bytes with the Java serialization may execute arbitrary code,
+    /// via the classes that are available in the classpath. Only the JVM-wide filter (`jdk.serialFilter`) applies, if any.
+    /// Bytes that do not come from a trusted source should be deserialized with `deserialize_instance_with_filter`.
+    pub fn deserialize_instance(&self, bytes: &[u8]) -> errors::Result<Instance> {
+        self.invoke_static(
+            CLASS_JAVA_SERIALIZATION,
+            "deserialize",
+            &[InvocationArg::try_from(bytes)?],
+        )
+    }
+
+    /// Like `deserialize_instance`, but only the classes and limits that the `filter_pattern` allows are accepted.
+    ///
+    /// The pattern is defined by the Java `ObjectInputFilter.Config.createFilter`.
+    /// E.g. `java.util.ArrayList;java.lang.Object;java.lang.String;!*` allows only `ArrayList`s of `String`s
+    /// (an `ArrayList` is backed by an `Object` array).
+    /// An error is returned if the serialized object is rejected by the filter.
+    pub fn deserialize_instance_with_filter(&self, bytes: &[u8], filter_pattern: &str) -> errors::Result<Instance> {
+        self.invoke_static(
+            CLASS_JAVA_SERIALIZATION,
+            "deserialize",
+            &[InvocationArg::try_from(bytes)?, InvocationArg::try_from(filter_pattern)?],
+        )
+    }
+
+    /// Creates a persistent `EvalContext` for evaluating Java code with JShell.
+    pub fn eval_context(&self) -> errors::Result<EvalContext<'_>> {
+        EvalContext::new(self)
+    }
+
+    /// Evaluates Java `code` with JShell, in a new `EvalContext`, and returns the value of its last expression.
+    ///
+    /// Creating a context is expensive; `eval_context` should be preferred for repeated evaluations.
+    pub fn eval(&self, code: &str) -> errors::Result<Instance> {
+        self.eval_context()?.eval(code)
+    }
+
+    /// Creates a `ClassScope` out of the jars and directories of `paths`.
+    ///
+    /// The classes of the scope are loaded in isolation from the rest of the classpath, preferring the classes of
+    /// `paths` over the ones of the JVM classpath. The classes of the JDK and of j4rs are always shared.
+    pub fn new_class_scope(&self, paths: &[&str]) -> errors::Result<ClassScope<'_>> {
+        let paths: Vec<InvocationArg> = paths
+            .iter()
+            .map(|path| InvocationArg::try_from(*path))
+            .collect::<errors::Result<_>>()?;
+        let paths = self.create_java_array("java.lang.String", &paths)?;
+        let scope = self.create_instance(CLASS_SCOPE_CLASS, &[InvocationArg::from(paths)])?;
+        Ok(ClassScope::new(self, scope))
+    }
+
+    /// Creates one `SimpleMavenDeployer` per configured repository, applying the credentials and proxy settings.
+    fn maven_deployers(&self, base: &str) -> errors::Result<Vec<Instance>> {
+        let maven_settings = get_maven_settings();
+        let mut deployers = Vec::with_capacity(maven_settings.repos.len());
+
+        for repo in maven_settings.repos.iter() {
+            let instance = self.create_instance(
+                "org.astonbitecode.j4rs.api.deploy.SimpleMavenDeployer",
+                &[InvocationArg::try_from(&repo.uri)?, InvocationArg::try_from(base)?],
+            )?;
+
+            if let Some(token) = &repo.token {
+                let _ = self.invoke(&instance, "withToken", &[InvocationArg::try_from(token)?])?;
+            } else if let (Some(username), Some(password)) = (&repo.username, &repo.password) {
+                let _ = self.invoke(
+                    &instance,
+                    "withCredentials",
+                    &[InvocationArg::try_from(username)?, InvocationArg::try_from(password)?],
+                )?;
+            }
+
+            if let Some(proxy) = &maven_settings.proxy {
+                let optional_arg = |value: &Option<String>| match value {
+                    Some(s) => InvocationArg::try_from(s),
+                    None => InvocationArg::try_from(Null::String),
+                };
+                let _ = self.invoke(
+                    &instance,
+                    "withProxy",
+                    &[
+                        InvocationArg::try_from(&proxy.host)?,
+                        InvocationArg::try_from(proxy.port as i32)?.into_primitive()?,
+                        optional_arg(&proxy.username)?,
+                        optional_arg(&proxy.password)?,
+                        optional_arg(&proxy.non_proxy_hosts)?,
+                    ],
+                )?;
+            }
+
+            if let Some(settings_xml) = &maven_settings.settings_xml {
+                // A null path means the default location of the user
+                let path = match settings_xml {
+                    SettingsXml::Path(path) => InvocationArg::try_from(path)?,
+                    SettingsXml::User => InvocationArg::try_from(Null::String)?,
+                };
+                let _ = self.invoke(
+                    &instance,
+                    "withSettingsXml",
+                    &[InvocationArg::try_from(&repo.id)?, path],
+                )?;
+            }
+
+            deployers.push(instance);
+        }
+
+        Ok(deployers)
+    }
+
+    /// Copies the jassets default directory and the j4rs dynamic library under the specified
+    /// location.
+    /// This is useful for cases when `with_base_path` method is used when building a Jvm with
+    /// the JvmBuilder.
+    /// Build scripts should use this method.
+    pub fn copy_j4rs_libs_under(path: &str) -> errors::Result<()> {
+        let mut pb = PathBuf::from(path);
+        pb.push("deps");
+        fs::create_dir_all(&pb)?;
+
+        let default_jassets_path_buf = utils::default_jassets_path()?;
+        let default_jassets_path_string = default_jassets_path_buf.to_str().unwrap().to_owned();
+
+        // Copy the jassets
+        let options = &mut fs_extra::dir::CopyOptions::new();
+        options.overwrite = true;
+        let _ = fs_extra::copy_items(vec![default_jassets_path_string].as_ref(), path, options)?;
+
+        // Copy the dynamic libraries
+        let dynlibs: Vec<String> = {
+            let mut dynlibs = vec![];
+            // We try every 1 second for 10 iterations because on most systems, cargo will
+            // parallelize the build and the dynlib might not be created yet.
+            for _i in 0..10 {
+                dynlibs = utils::find_j4rs_dynamic_libraries_paths()?;
+                if dynlibs.is_empty() {
+                    thread::sleep(time::Duration::from_millis(1000));
+                } else {
+                    break;
+                }
+            }
+            dynlibs
+        };
+        if dynlibs.is_empty() {
+            let message = format!(
+                "No j4rs dynamic libraries found for target triple {}. \
+                                  The host triple during build is {}.",
+                env::var("TARGET").unwrap_or("".to_string()),
+                env::var("HOST").unwrap_or("UNKNOWN".to_string())
+            );
+            println!("cargo:warning={}", message);
+        }
+
+        let _ = fs_extra::copy_items(&dynlibs, &pb, options)?;
+
+        Ok(())
+    }
+
+    /// Initiates a chain of operations on Instances.
+    pub fn chain(&self, instance: &Instance) -> errors::Result<ChainableInstance> {
+        ChainableInstance::new_with_instance_ref(instance, self)
+    }
+
+    /// Initiates a chain of operations on Instances.
+    pub fn into_chain(&self, instance: Instance) -> ChainableInstance {
+        ChainableInstance::new(instance, self)
+    }
+
+    /// Initiates a chain of operations on the value of the static field `field_name` of the class `class_name`.
+    pub fn field_static(&self, class_name: &str, field_name: &str) -> errors::Result<ChainableInstance<'_>> {
+        Ok(ChainableInstance::new(self.static_class_field(class_name, field_name)?, self))
+    }
+
+    /// Throws an exception in the Java World
+    pub fn throw_invocation_exception(&self, message: &str) -> errors::Result<()> {
+        unsafe {
+            let _ = jni_utils::throw_exception(message, self.jni_env)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn do_return<T>(jni_env: *mut JNIEnv, to_return: T) -> errors::Result<T> {
+        unsafe {
+            if (opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE {
+                let throwable = (opt_to_res(cache::get_jni_exception_occured())?)(jni_env);
+                // Clear the exception in order to be able to call Java for describing the throwable
+                (opt_to_res(cache::get_jni_exception_clear())?)(jni_env);
+                let error = match Self::get_java_exception(throwable, jni_env) {
+                    Ok(java_exception) => exceptions::to_error(java_exception),
+                    Err(_) => J4RsError::JavaError(Self::get_throwable_string(throwable, jni_env)?),
+                };
+                jni_utils::delete_java_local_ref(jni_env, throwable);
+                Err(error)
+            } else {
+                Ok(to_return)
+            }
+        }
+    }
+
+    unsafe fn get_java_exception(throwable: jobject, jni_env: *mut JNIEnv) -> errors::Result<JavaException> {
+        let java_string = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jni_env,
+            cache::get_utils_class()?,
+            cache::get_utils_throwable_to_json_method()?,
+            throwable,
+        );
+        let json = Self::clear_exception_and_return(jni_env, java_string)
+            .and_then(|java_string| {
+                let json = jni_utils::string_from_jobject(java_string, jni_env);
+                jni_utils::delete_java_local_ref(jni_env, java_string);
+                json
+            })?;
+        let mut java_exception: JavaException = serde_json::from_str(&json)?;
+
+        let java_instance = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jni_env,
+            cache::get_utils_class()?,
+            cache::get_utils_throwable_to_instance_method()?,
+            throwable,
+        );
+        if let Ok(java_instance) = Self::clear_exception_and_return(jni_env, java_instance) {
+            if let Ok(mut instance) = Instance::from_jobject_with_global_ref(java_instance) {
+                instance.class_name = java_exception.class_name().to_string();
+                java_exception.set_throwable(instance);
+            }
+        }
+        Ok(java_exception)
+    }
+
+    // Like do_return, but does not try to describe the exception. Used while already handling an exception.
+    unsafe fn clear_exception_and_return(jni_env: *mut JNIEnv, to_return: jobject) -> errors::Result<jobject> {
+        if (opt_to_res(cache::get_jni_exception_check())?)(jni_env) == JNI_TRUE {
+            (opt_to_res(cache::get_jni_exception_clear())?)(jni_env);
+            Err(J4RsError::JavaError("An exception was thrown while describing a Java exception".to_string()))
+        } else if to_return.is_null() {
+            Err(J4RsError::JavaError("Could not describe a Java exception".to_string()))
+        } else {
+            Ok(to_return)
+        }
+    }
+
+    unsafe fn get_throwable_string(throwable: jobject, jni_env: *mut JNIEnv) -> errors::Result<String> {
+        let java_string = (opt_to_res(cache::get_jni_call_static_object_method())?)(
+            jni_env,
+            cache::get_utils_class()?,
+            cache::get_utils_exception_to_string_method()?,
+            throwable,
+        );
+        let to_ret = jni_utils::string_from_jobject(java_string, jni_env);
+        jni_utils::delete_java_local_ref(jni_env, java_string);
+        to_ret
+    }
+
+    // Retrieves a JNIEnv in the case that a JVM is already created even from another thread.
+    // The current thread is attached to the JVM as a daemon if `as_daemon` is true.
+    fn get_created_vm(as_daemon: bool) -> Option<*mut JNIEnv> {
+        unsafe {
+            // Get the number of the already created VMs. This is most probably 1, but we retrieve the number just in case...
+            let mut created_vms_size: jsize = 0;
+            tweaks::get_created_java_vms(
+                &mut Vec::with_capacity(created_vms_size as usize),
+                0,
+                &mut created_vms_size,
+            );
+
+            if created_vms_size == 0 {
+                None
+            } else {
+                debug(&format!(
+                    "Retrieving the first of {} created JVMs",
+                    created_vms_size
+                ));
+                // Get the created VM (use 2 just in case... :) )
+                let mut buffer: Vec<*mut JavaVM> = Vec::with_capacity(2);
+                for _ in 0..created_vms_size {
+                    buffer.push(ptr::null_mut());
+                }
+
+                let retjint = tweaks::get_created_java_vms(
+                    &mut buffer,
+                    created_vms_size,
+                    &mut created_vms_size,
+                );
+                if retjint == JNI_OK {
+                    let act = if as_daemon {
+                        (**buffer[0]).v1_4.AttachCurrentThreadAsDaemon
+                    } else {
+                        (**buffer[0]).v1_4.AttachCurrentThread
+                    };
+                    let mut jni_environment: *mut JNIEnv = ptr::null_mut();
+                    (act)(
+                        buffer[0],
+                        (&mut jni_environment as *mut *mut JNIEnv) as *mut *mut c_void,
+                        ptr::null_mut(),
+                    );
+                    Some(jni_environment)
+                } else {
+                    error(&format!(
+                        "Error while retrieving the created JVMs: {}",
+                        retjint
+                    ));
+                    None
+                }
+            }
+        }
+    }
+
+    // Returns the first of the JavaVMs that are created by the current process
+    fn created_java_vm() -> Option<*mut JavaVM> {
+        // Get the number of the already created VMs. This is most probably 1, but we retrieve the number just in case...
+        let mut created_vms_size: jsize = 0;
+        tweaks::get_created_java_vms(
+            &mut Vec::with_capacity(created_vms_size as usize),
+            0,
+            &mut created_vms_size,
+        );
+
+        if created_vms_size > 0 {
+            // Get the created VM
+            let mut buffer: Vec<*mut JavaVM> = Vec::with_capacity(created_vms_size as usize);
+            for _ in 0..created_vms_size {
+                buffer.push(ptr::null_mut());
+            }
+
+            let retjint = tweaks::get_created_java_vms(
+                &mut buffer,
+                created_vms_size,
+                &mut created_vms_size,
+            );
+            if retjint == JNI_OK {
+                Some(buffer[0])
+            } else {
+                warn(&format!(
+                    "Error while retrieving the created JVMs: {}",
+                    retjint
+                ));
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    fn detach_current_thread(&self) {
+        if let Some(java_vm) = Self::created_java_vm() {
+            unsafe {
+                let dct = (**java_vm).v1_4.DetachCurrentThread;
+                (dct)(java_vm);
+            }
+        }
+    }
+
+    /// Returns the first `Instance` that is available from the passed `InstanceReceiver`s,
+    /// along with the index of the receiver that was selected and actually returned the instance.
+    ///
+    /// This is a mostly naive implementation of select, because of [absence for selecting among mpsc channels](https://github.com/rust-lang/rust/issues/27800).
+    pub fn select(instance_receivers: &[&InstanceReceiver]) -> errors::Result<(usize, Instance)> {
+        loop {
+            for (index, ir) in instance_receivers.iter().enumerate() {
+                let res = ir.rx.try_recv();
+                if res.is_ok() {
+                    return Ok((index, res.unwrap()));
+                }
+            }
+            thread::yield_now();
+        }
+    }
+
+    /// Returns the first `Instance` that is available from the passed `InstanceReceiver`s,
+    /// along with the index of the receiver that was selected and actually returned the instance.
+    ///
+    /// If there are no instances returned for the duration defined in timeout argument, an error is returned.
+    ///
+    /// This is a mostly naive implementation of select, because of [absence for selecting among mpsc channels](https://github.com/rust-lang/rust/issues/27800).
+    pub fn select_timeout(
+        instance_receivers: &[&InstanceReceiver],
+        timeout: &time::Duration,
+    ) -> errors::Result<(usize, Instance)> {
+        let start = time::Instant::now();
+        loop {
+            for (index, ir) in instance_receivers.iter().enumerate() {
+                let res = ir.rx.try_recv();
+                if res.is_ok() {
+                    return Ok((index, res.unwrap()));
+                }
+            }
+            if &start.elapsed() > timeout {
+                return Err(J4RsError::Timeout);
+            }
+            thread::yield_now();
+        }
+    }
+}
+
+impl Clone for Jvm {
+    // The clone is one more active Jvm of the current thread, so that the thread is not detached while any of them is alive
+    fn clone(&self) -> Jvm {
+        cache::add_active_jvm();
+        Jvm {
+            jni_env: self.jni_env,
+            detach_thread_on_drop: self.detach_thread_on_drop,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for Jvm {
+    fn drop(&mut self) {
+        if cache::remove_active_jvm() <= 0 {
+            if !cache::is_jvm_destroyed() {
+                cleanup::delete_pending(self.jni_env);
+            }
+            if self.detach_thread_on_drop {
+                self.detach_current_thread();
+            }
+            cache::set_thread_local_env(None);
+        }
+    }
+}
+
+/// A builder for Jvm
+pub struct JvmBuilder<'a> {
+    classpath_entries: Vec<ClasspathEntry<'a>>,
+    java_opts: Vec<JavaOpt<'a>>,
+    jvm_options: JvmOptions,
+    no_implicit_classpath: bool,
+    detach_thread_on_drop: bool,
+    lib_name_opt: Option<String>,
+    skip_setting_native_lib: bool,
+    base_path: Option<String>,
+    maven_settings: MavenSettings,
+    javafx: bool,
+    default_classloader: bool,
+    java_vm_opt: Option<*mut JavaVM>,
+    jobject_within_valid_classloader_opt: Option<jobject>,
+    artifact_lock: Option<PathBuf>,
+    serialization_format: Option<SerializationFormat>,
+    env_var_expansion: bool,
+    jvm_installation: Option<JvmInstallation>,
+    #[cfg(feature = "provision-jre")]
+    jre_provisioning: Option<jre::JreProvisioning>,
+}
+
+impl<'a> JvmBuilder<'a> {
+    /// Creates a new JvmBuilder.
+    pub fn new<'b>() -> JvmBuilder<'b> {
+        JvmBuilder {
+            classpath_entries: Vec::new(),
+            java_opts: Vec::new(),
+            jvm_options: JvmOptions::new(),
+            no_implicit_classpath: false,
+            detach_thread_on_drop: true,
+            lib_name_opt: None,
+            skip_setting_native_lib: false,
+            base_path: None,
+            maven_settings: MavenSettings::default(),
+            javafx: false,
+            default_classloader: false,
+            java_vm_opt: None,
+            jobject_within_valid_classloader_opt: None,
+            artifact_lock: None,
+            serialization_format: None,
+            env_var_expansion: false,
+            jvm_installation: None,
+            #[cfg(feature = "provision-jre")]
+            jre_provisioning: None,
+        }
+    }
+
+    /// Adds a classpath entry.
+    pub fn classpath_entry(&'a mut self, cp_entry: ClasspathEntry<'a>) -> &'a mut JvmBuilder<'a> {
+        self.classpath_entries.push(cp_entry);
+        self
+    }
+
+    /// Adds classpath entries.
+    pub fn classpath_entries(
+        &'a mut self,
+        cp_entries: Vec<ClasspathEntry<'a>>,
+    ) -> &'a mut JvmBuilder<'a> {
+        for cp_entry in cp_entries {
+            self.classpath_entries.push(cp_entry);
+        }
+        self
+    }
+
+    /// Adds a Java option.
+    pub fn java_opt(&'a mut self, opt: JavaOpt<'a>) -> &'a mut JvmBuilder<'a> {
+        self.java_opts.push(opt);
+        self
+    }
+
+    /// Adds Java options.
+    pub fn java_opts(&'a mut self, opts: Vec<JavaOpt<'a>>) -> &'a mut JvmBuilder<'a> {
+        for opt in opts {
+            self.java_opts.push(opt);
+        }
+        self
+    }
+
+    /// Defines typed options for the JVM.
+    ///
+    /// The options are validated when `build` is called.
+    /// It is an error if they define a setting that is also defined by a `JavaOpt`.
+    pub fn with_jvm_options(&'a mut self, jvm_options: JvmOptions) -> &'a mut JvmBuilder<'a> {
+        self.jvm_options = jvm_options;
+        self
+    }
+
+    /// Deploys the Maven artifacts of a lock file that is written by `Jvm::export_artifact_lock`, after the `Jvm` is created.
+    ///
+    /// `build` fails if any of the artifacts cannot be deployed, or if its checksum is not the locked one.
+    pub fn with_artifact_lock<P: AsRef<Path>>(&'a mut self, path: P) -> &'a mut JvmBuilder<'a> {
+        self.artifact_lock = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the format of the values that are exchanged with Java by `Jvm::to_rust` and `InvocationArg::new`.
+    ///
+    /// The format is negotiated with the Java side when the `Jvm` is built; if it is not supported, JSON is used.
+    /// It applies to all the `Jvm`s of the process.
+    pub fn serialization_format(&'a mut self, format: SerializationFormat) -> &'a mut JvmBuilder<'a> {
+        self.serialization_format = Some(format);
+        self
+    }
+
+    /// Expands the environment variables that are referenced as `${NAME}` in the classpath entries and the Java options.
+    ///
+    /// `build` fails if a referenced variable is not defined.
+    pub fn with_env_var_expansion(&'a mut self) -> &'a mut JvmBuilder<'a> {
+        self.env_var_expansion = true;
+        self
+    }
+
+    /// By default, the created `Jvm`s include an implicit classpath entry that includes the j4rs jar.
+    /// When `with_no_implicit_classpath()` is called, this classpath will not be added to the Jvm.
+    pub fn with_no_implicit_classpath(&'a mut self) -> &'a mut JvmBuilder<'a> {
+        self.no_implicit_classpath = true;
+        self
+    }
+
+    /// When a Jvm goes out of scope and is being dropped, its current thread is being detached from the Java VM.
+    /// A Jvm that is created with `detach_thread_on_drop(false)` will not detach the thread when being dropped.
+    ///
+    /// This is useful when in the Java world a native method is called and in the native code someone needs to create a j4rs Jvm.
+    /// If that Jvm detaches its current thread when being dropped, there will be problems for the Java world code to continue executing.
+    pub fn detach_thread_on_drop(&'a mut self, detach_thread_on_drop: bool) -> &'a mut JvmBuilder<'a> {
+        self.detach_thread_on_drop = detach_thread_on_drop;
+        self
+    }
+
+    /// In the case that the j4rs is statically linked to some other library, the Java world (j4rs.jar) needs to load that
+    /// library instead of the default one.
+    ///
+    /// This function defines the native library name to load.
+    pub fn with_native_lib_name(&'a mut self, lib_name: &str) -> &'a mut JvmBuilder<'a> {
+        self.lib_name_opt = Some(lib_name.to_string());
+        self
+    }
+
+    /// Configures the builder not to instruct the Java world j4rs code to load the native library.
+    /// (most probably because it is already loaded)
+    pub fn skip_setting_native_lib(&'a mut self) -> &'a mut JvmBuilder<'a> {
+        self.skip_setting_native_lib = true;
+        self
+    }
+
+    /// Defines the location of the jassets and deps directory.
+    /// The jassets contains the j4rs jar and the deps the j4rs dynamic library.
+    pub fn with_base_path(&'a mut self, base_path: &str) -> &'a mut JvmBuilder<'a> {
+        self.base_path = Some(base_path.to_string());
+        self
+    }
+
+    /// Defines the maven settings to use for provisioning maven artifacts.
+    pub fn with_maven_settings(&'a mut self, maven_settings: MavenSettings) -> &'a mut JvmBuilder<'a> {
+        self.maven_settings = maven_settings;
+        self
+    }
+
+    /// Adds JavaFX support to the created JVM
+    pub fn with_javafx_support(&'a mut self) -> &'a mut JvmBuilder<'a> {
+        self.javafx = true;
+        self
+    }
+
+    /// Loads the jvm dynamic library of the provided Java installation, instead of locating it via `JAVA_HOME`.
+    ///
+    /// The installations of the system can be found with `Jvm::discover_jvms`.
+    /// Only one jvm dynamic library can be loaded in a process, so building fails if another one is already loaded.
+    /// This is not supported with the `no-runtime-libloading` feature, where the jvm dynamic library is linked.
+    pub fn with_jvm_installation(&'a mut self, installation: &JvmInstallation) -> &'a mut JvmBuilder<'a> {
+        self.jvm_installation = Some(installation.clone());
+        self
+    }
+
+    /// Provisions a JRE to load the jvm dynamic library from, if no Java installation is found.
+    ///
+    /// The JRE is downloaded and unpacked during the build, unless it is already unpacked by a previous execution.
+    /// This has no effect with the `no-runtime-libloading` feature, or when a JVM is already loaded in the process.
+    #[cfg(feature = "provision-jre")]
+    pub fn with_jre_provisioning(&'a mut self, jre_provisioning: jre::JreProvisioning) -> &'a mut JvmBuilder<'a> {
+        self.jre_provisioning = Some(jre_provisioning);
+        self
+    }
+
+    /// Create the j4rs `Jvm` using an already created jni `JavaVM`.
+    /// 
+    /// Useful for Android apps, where the JVM is automatically created.
+    pub fn with_java_vm(&'a mut self, java_vm: *mut JavaVM) -> &'a mut JvmBuilder<'a> {
+        self.java_vm_opt = Some(java_vm);
+        self
+    }
+
+    /// Instructs j4rs to use the classloader associated to the specified `jobject` when searching for classes.
+    /// 
+    /// Useful for pure native Android apps.
+    /// 
+    /// As described in Android documentation, the native libraries that spawn new threads cannot locate and use Java classes correctly.
+    ///
+    /// From the [docs]((https://developer.android.com/training/articles/perf-jni#faq:-why-didnt-findclass-find-my-class)):
+    /// 
+    /// > You can get into trouble if you create a thread yourself (perhaps by calling pthread_create and then attaching it with AttachCurrentThread). 
+    /// > Now there are no stack frames from your application. If you call FindClass from this thread, the JavaVM will start in the "system" class loader 
+    /// > instead of the one associated with your application, so attempts to find app-specific classes will fail.
+    /// 
+    /// Even if it is proposed to load and cache classes during `JNI_OnLoad` (j4rs documentation about it [here](https://github.com/astonbitecode/j4rs?tab=readme-ov-file#j4rs-in-android))
+    /// `JNI_OnLoad` is not called on pure native apps. One solution to find the app-specific classes in this case, is to use the classloader 
+    /// of the `Activity` to find and use classes. `j4rs` will use the `jobject` passed here (the `jobject` of the `Activity`) to get the 
+    /// proper classloader and use it when needed.
+    /// #[cfg(target_os = "android")]
+    pub fn with_classloader_of_activity(&'a mut self, jobject_within_valid_classloader: jobject) -> &'a mut JvmBuilder<'a> {
+        self.jobject_within_valid_classloader_opt = Some(jobject_within_valid_classloader);
+        // If the `jobject_within_valid_classloader_opt` is provided, it means that the object's classloader
+        // should be used to load classes in case the traditional `FindClass` invocations fail.
+        // Apply here the needed configuration for it to happen.
+        // Do not detach the thread on drop. This would make the Activity to fail.
+        let tmp = self.detach_thread_on_drop(false);
+        let tmp = tmp.with_no_implicit_classpath();
+        tmp
+    }
+
+    /// `j4rs` uses a custom ClassLoader (namely the `J4rsClassLoader`),
+    /// that allows adding jars to the classpath during runtime, after the underlying `JVM` is initialized.
+    ///
+    /// This function instructs the builder not to use this custom classloader, but use the default one.
+    ///
+    /// Please note that the `J4rsClassLoader` needs Java 9 or higher. If you use an older Java version,
+    /// you __must__ call this function in order for `j4rs` to work.
+    ///
+    /// If not, you will get exceptions like the following:
+    ///
+    /// ```text
+    /// java.lang.NoSuchMethodError: java.net.URLClassLoader.`<init>`(Ljava/lang/String;[Ljava/net/URL;Ljava/lang/ClassLoader;)V
+    ///         at org.astonbitecode.j4rs.api.deploy.J4rsClassLoader.`<init>`(J4rsClassLoader.java:22)
+    ///         at sun.reflect.NativeConstructorAccessorImpl.newInstance0(Native Method)
+    ///         at sun.reflect.NativeConstructorAccessorImpl.newInstance(NativeConstructorAccessorImpl.java:62)
+    ///         at sun.reflect.DelegatingConstructorAccessorImpl.newInstance(DelegatingConstructorAccessorImpl.java:45)
+    ///         at java.lang.reflect.Constructor.newInstance(Constructor.java:423)
+    ///         at java.lang.SystemClassLoaderAction.run(ClassLoader.java:2204)
+    ///         at java.lang.SystemClassLoaderAction.run(ClassLoader.java:2188)
+    ///         at java.security.AccessController.doPrivileged(Native Method)
+    ///         at java.lang.ClassLoader.initSystemClassLoader(ClassLoader.java:1449)
+    ///         at java.lang.ClassLoader.getSystemClassLoader(ClassLoader.java:1429)
+    /// ```
+    pub fn with_default_classloader(&'a mut self) -> &'a mut JvmBuilder<'a> {
+        self.default_classloader = true;
+        self
+    }
+
+    /// Creates a Jvm
+    pub fn build(&mut self) -> errors::Result<Jvm> {
+        if self.env_var_expansion {
+            for entry in self.classpath_entries.iter_mut() {
+                entry.0 = Cow::Owned(jvm_config::expand_env_vars(&entry.0)?);
+            }
+            for opt in self.java_opts.iter_mut() {
+                opt.0 = Cow::Owned(jvm_config::expand_env_vars(&opt.0)?);
+            }
+        }
+        if !self.default_classloader {
+            // Define the system classloader
+            self.java_opts.push(JavaOpt::new(
+                "-Djava.system.class.loader=org.astonbitecode.j4rs.api.deploy.J4rsClassLoader",
+            ));
+            self.java_opts.push(JavaOpt::new("-Xshare:off"));
+            self.java_opts.push(JavaOpt::new(
+                "-Djdk.net.URLClassPath.showIgnoredClassPathEntries=true",
+            ));
+        }
+
+        let classpath = if self.no_implicit_classpath {
+            self.classpath_entries
+                .iter()
+                .fold(".".to_string(), |all, elem| {
+                    format!("{}{}{}", all, utils::classpath_sep(), elem.to_string())
+                })
+        } else {
+            let default_class_path = format!("-Djava.class.path={}", self.default_classpath()?);
+
+            self.classpath_entries
+                .iter()
+                .fold(default_class_path, |all, elem| {
+                    format!("{}{}{}", all, utils::classpath_sep(), elem.to_string())
+                })
+        };
+        info(&format!("Setting classpath to {}", classpath));
+
+        // Populate the JVM Options
+        let mut jvm_options = if self.no_implicit_classpath {
+            vec![classpath]
+        } else {
+            let default_library_path = utils::java_library_path()?;
+            info(&format!("Setting library path to {}", default_library_path));
+            vec![classpath, default_library_path]
+        };
+
+        if self.javafx {
+            let jassets_path = self.get_jassets_path()?;
+            let jassets_path_string = jassets_path.to_str().unwrap_or(".");
+            let modules_path = format!("--module-path {}", jassets_path_string);
+            jvm_options.push(modules_path);
+            jvm_options.push(
+                "--add-modules javafx.base,javafx.controls,javafx.graphics,javafx.fxml".to_string(),
+            );
+        }
+        self.java_opts
+            .clone()
+            .into_iter()
+            .for_each(|opt| {
+                let opt = opt.to_string();
+                if jvm_options::is_space_separated(&opt) {
+                    warn(&format!(
+                        "The Java option '{}' separates its value with a space. Please use '=' instead.",
+                        opt
+                    ));
+                }
+                jvm_options.push(opt)
+            });
+        let typed_options = self.jvm_options.to_java_opts()?;
+        jvm_options::check_conflicts(&typed_options, &jvm_options)?;
+        jvm_options.extend(typed_options);
+
+        // Pass to the Java world the name of the j4rs library.
+        let lib_name_opt = if self.lib_name_opt.is_none() && !self.skip_setting_native_lib && cfg!(not(target_os = "android")) {
+            let deps_dir = utils::deps_dir()?;
+            let found_libs: Vec<String> = if Path::new(&deps_dir).exists() {
+                utils::find_j4rs_dynamic_libraries_names()?
+            } else {
+                // If deps dir is not found, fallback to default naming in order for the library to be searched in the default
+                // library locations of the system.
+                let default_lib_name = if cfg!(windows) {
+                    "l4rs.dll".to_string()
+                } else {
+                    "libj4rs.so".to_string()
+                };
+                info(&format!(
+                    "Deps directory not found. Setting the library name to search to default: {}",
+                    default_lib_name
+                ));
+                vec![default_lib_name]
+            };
+
+            let lib_name_opt = if !found_libs.is_empty() {
+                let a_lib = found_libs[0].clone().replace("lib", "");
+
+                let dot_splitted: Vec<&str> = a_lib.split('.').collect();
+                let name = dot_splitted[0].to_string();
+                info(&format!(
+                    "Passing to the Java world the name of the library to load: {}",
+                    name
+                ));
+                Some(name)
+            } else {
+                None
+            };
+            lib_name_opt
+        } else if self.lib_name_opt.is_some() && !self.skip_setting_native_lib {
+            let name = self.lib_name_opt.clone();
+            info(&format!(
+                "Passing to the Java world the name of the library to load: {}",
+                name.as_ref().unwrap()
+            ));
+            name
+        } else {
+            None
+        };
+
+        provisioning::set_maven_settings(&self.maven_settings);
+
+        if self.java_vm_opt.is_none() {
+            if let Some(installation) = &self.jvm_installation {
+                tweaks::set_jvm_dyn_lib_path(installation.jvm_dyn_lib().to_path_buf())?;
+            } else {
+                #[cfg(feature = "provision-jre")]
+                if let Some(jre_provisioning) = &self.jre_provisioning {
+                    jre::apply(jre_provisioning)?;
+                }
+            }
+        }
+
+        let jvm_res = if self.java_vm_opt.is_some() {
+            // If the `java_vm` is already created and provided, just attach the current thread.
+            set_java_vm(self.java_vm_opt.unwrap());
+            Jvm::attach_thread()
+        } else {
+            Jvm::new(&jvm_options, lib_name_opt)
+        };
+
+        jvm_res.and_then(|mut jvm| {
+            if !self.detach_thread_on_drop {
+                jvm.detach_thread_on_drop(false);
+            }
+            if self.jobject_within_valid_classloader_opt.is_some() {
+                cache_classloader_of(jvm.jni_env, self.jobject_within_valid_classloader_opt.unwrap())?;
+            }
+            if let Some(artifact_lock) = &self.artifact_lock {
+                jvm.deploy_artifact_lock(artifact_lock)?;
+            }
+            if let Some(format) = self.serialization_format {
+                codec::negotiate(&jvm, format);
+            }
+            Ok(jvm)
+        })
+    }
+
+    /// Creates a `RemoteJvm`, which runs the JVM in a child process, instead of in the current process.
+    ///
+    /// The classpath, the Java options and the Java installation of the builder are applied.
+    /// Crashes of the JVM do not affect the current process and any number of `RemoteJvm`s can be created,
+    /// each one with a lifetime of its own.
+    pub fn out_of_process(&self) -> errors::Result<RemoteJvm> {
+        self.remote_command()?.spawn()
+    }
+
+    pub(crate) fn remote_command(&self) -> errors::Result<RemoteCommand> {
+        let expand = |s: String| {
+            if self.env_var_expansion {
+                jvm_config::expand_env_vars(&s)
+            } else {
+                Ok(s)
+            }
+        };
+        let mut classpath = Vec::new();
+        if !self.no_implicit_classpath {
+            classpath.push(self.default_classpath()?);
+        }
+        for entry in &self.classpath_entries {
+            classpath.push(expand(entry.to_string())?);
+        }
+        let mut java_args = Vec::new();
+        for opt in &self.java_opts {
+            java_args.push(expand(opt.to_string())?);
+        }
+        java_args.extend(self.jvm_options.to_java_opts()?);
+        java_args.push("-cp".to_string());
+        java_args.push(classpath.join(utils::classpath_sep()));
+
+        let java_executable = if cfg!(windows) { "java.exe" } else { "java" };
+        let java = match (&self.jvm_installation, env::var_os("JAVA_HOME")) {
+            (Some(installation), _) => installation.home().join("bin").join(java_executable),
+            (None, Some(java_home)) => PathBuf::from(java_home).join("bin").join(java_executable),
+            (None, None) => PathBuf::from(java_executable),
+        };
+        Ok(RemoteCommand::new(java, java_args))
+    }
+
+    /// Creates a Jvm, similar with an already created j4rs Jvm.
+    ///
+    /// _Note: The already created Jvm is a j4rs Jvm, not a Java VM._
+    pub fn already_initialized() -> errors::Result<Jvm> {
+        Jvm::new(&[], None)
+    }
+
+    /// Returns the default classpath, which contains all the jars in the jassets directory.
+    fn default_classpath(&self) -> errors::Result<String> {
+        let jassets_path = self.get_jassets_path()?;
+        // This is the j4rs jar that should be included in the classpath
+        let j4rs_jar_to_use = format!("j4rs-{}-jar-with-dependencies.jar", j4rs_version());
+        let j4rs_testing_jar_to_use = format!("j4rs-testing-{}.jar", j4rs_version());
+        let j4rs_javafx_jar_to_use = format!("j4rs-javafx-{}.jar", j4rs_version());
+        // Filter out possible incorrect jars of j4rs
+        let mut cp_string = String::new();
+        for entry in std::fs::read_dir(jassets_path)? {
+            let path = entry?.path();
+            let (Some(file_name), Some(path_str)) = (opt_to_res(path.file_name())?.to_str(), path.to_str()) else {
+                warn(&format!("Ignoring {:?} of the jassets directory, as its path is not valid unicode", path));
+                continue;
+            };
+            if !file_name.contains("j4rs-") || file_name.ends_with(&j4rs_jar_to_use) || file_name.ends_with(&j4rs_testing_jar_to_use)  || file_name.ends_with(&j4rs_javafx_jar_to_use) {
+                if !cp_string.is_empty() {
+                    cp_string.push_str(utils::classpath_sep());
+                }
+                cp_string.push_str(&utils::java_path(path_str));
+            }
+        }
+        Ok(cp_string)
+    }
+
+    fn get_jassets_path(&self) -> errors::Result<PathBuf> {
+        match &self.base_path {
+            Some(base_path_string) => {
+                let mut pb = PathBuf::from(utils::java_path(base_path_string).as_ref());
+                pb.push("jassets");
+                let mut global_jassets_path_opt = cache::JASSETS_PATH.lock()?;
+                *global_jassets_path_opt = Some(pb.clone());
+                Ok(pb)
+            }
+            None => utils::default_jassets_path(),
+        }
+    }
+}
+
+/// Represents default, known Classes in Java. Can be used as class argument in `Jvm#java_list`, etc.
+pub enum JavaClass<'a> {
+    Void,
+    String,
+    Boolean,
+    Byte,
+    Character,
+    Short,
+    Integer,
+    Long,
+    Float,
+    Double,
+    List,
+    BigDecimal,
+    BigInteger,
+    Of(&'a str),
+}
+
+impl<'a> JavaClass<'a> {
+    pub fn get_class_str(&self) -> &'a str {
+        match self {
+            Self::Void => "void",
+            Self::String => CLASS_STRING,
+            Self::Boolean => CLASS_BOOLEAN,
+            Self::Byte => CLASS_BYTE,
+            Self::Character => CLASS_CHARACTER,
+            Self::Short => CLASS_SHORT,
+            Self::Integer => CLASS_INTEGER,
+            Self::Long => CLASS_LONG,
+            Self::Float => CLASS_FLOAT,
+            Self::Double => CLASS_DOUBLE,
+            Self::List => CLASS_LIST,
+            Self::BigDecimal => CLASS_BIG_DECIMAL,
+            Self::BigInteger => CLASS_BIG_INTEGER,
+            Self::Of(str) => str,
+        }
+    }
+}
+
+impl<'a> From<JavaClass<'a>> for &'a str {
+    fn from(java_class: JavaClass<'a>) -> &'a str {
+        java_class.get_class_str()
+    }
+}
+
+impl<'a> From<&'a str> for JavaClass<'a> {
+    fn from(java_class: &'a str) -> JavaClass<'a> {
+        match java_class {
+            "void" => Self::Void,
+            CLASS_STRING => Self::String,
+            CLASS_BOOLEAN => Self::Boolean,
+            CLASS_BYTE => Self::Byte,
+            CLASS_CHARACTER => Self::Character,
+            CLASS_SHORT => Self::Short,
+            CLASS_INTEGER => Self::Integer,
+            CLASS_LONG => Self::Long,
+            CLASS_FLOAT => Self::Float,
+            CLASS_DOUBLE => Self::Double,
+            CLASS_LIST => Self::List,
+            CLASS_BIG_DECIMAL => Self::BigDecimal,
+            CLASS_BIG_INTEGER => Self::BigInteger,
+            str => Self::Of(str),
+        }
+    }
+}
+
+/// Represents Java's null. Use this to create null Objects. E.g.:
+///
+/// let null_integer = InvocationArg::from(Null::Integer);
+/// let null_object = InvocationArg::from(Null::Of("some.class.Name"));
+pub enum Null<'a> {
+    String,
+    Boolean,
+    Byte,
+    Character,
+    Short,
+    Integer,
+    Long,
+    Float,
+    Double,
+    List,
+    Of(&'a str),
+}
+
+/// A classpath entry.
+#[derive(Debug, Clone)]
+pub struct ClasspathEntry<'a>(Cow<'a, str>);
+
+impl<'a> ClasspathEntry<'a> {
+    pub fn new(classpath_entry: &str) -> ClasspathEntry {
+        ClasspathEntry(Cow::Borrowed(classpath_entry))
+    }
+}
+
+impl<'a> ToString for ClasspathEntry<'a> {
+    fn to_string(&self) -> String {
+        utils::java_path(&self.0).into_owned()
+    }
+}
+
+/// A Java Option.
+#[derive(Debug, Clone)]
+pub struct JavaOpt<'a>(Cow<'a, str>);
+
+impl<'a> JavaOpt<'a> {
+    pub fn new(java_opt: &str) -> JavaOpt {
+        JavaOpt(Cow::Borrowed(java_opt))
+    }
+}
+
+impl<'a> ToString for JavaOpt<'a> {
+    fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// Returns the binary name of the class of the arrays with elements of the class `class_name`.
+fn array_class_name(class_name: &str) -> String {
+    match class_name {
+        PRIMITIVE_BOOLEAN => PRIMITIVE_BOOLEAN_ARRAY.to_string(),
+        PRIMITIVE_BYTE => PRIMITIVE_BYTE_ARRAY.to_string(),
+        PRIMITIVE_SHORT => PRIMITIVE_SHORT_ARRAY.to_string(),
+        PRIMITIVE_INT => PRIMITIVE_INT_ARRAY.to_string(),
+        PRIMITIVE_LONG => PRIMITIVE_LONG_ARRAY.to_string(),
+        PRIMITIVE_FLOAT => PRIMITIVE_FLOAT_ARRAY.to_string(),
+        PRIMITIVE_DOUBLE => PRIMITIVE_DOUBLE_ARRAY.to_string(),
+        PRIMITIVE_CHAR => PRIMITIVE_CHAR_ARRAY.to_string(),
+        _ if class_name.starts_with('[') => format!("[{}", class_name),
+        _ => format!("[L{};", class_name),
+    }
+}
+
+#[cfg(test)]
+mod api_unit_tests {
+    use crate::lib_unit_tests::create_tests_jvm;
+    use crate::{FromJava, InstanceKey, IntoJava, JarStatus, StdStream};
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn jvm_builder() -> errors::Result<()> {
+        let res = create_tests_jvm();
+        assert!(res.is_ok());
+        let one_more_res = JvmBuilder::already_initialized();
+        assert!(one_more_res.is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_j4rs_libs_under() -> errors::Result<()> {
+        let newdir = "./newdir";
+        Jvm::copy_j4rs_libs_under(newdir)?;
+
+        let _ = std::fs::remove_dir_all(newdir);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() -> errors::Result<()> {
+        let (tx1, rx1, waker1) = instance::instance_channel();
+        let ir1 = InstanceReceiver::new(rx1, None, waker1);
+        let (_tx2, rx2, waker2) = instance::instance_channel();
+        let ir2 = InstanceReceiver::new(rx2, None, waker2);
+        let (tx3, rx3, waker3) = instance::instance_channel();
+        let ir3 = InstanceReceiver::new(rx3, None, waker3);
+
+        thread::spawn(move || {
+            let _ = tx3.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
+            // Block the thread as sending does not block the current thread
+            thread::sleep(time::Duration::from_millis(10));
+            let _ = tx1.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
+            thread::sleep(time::Duration::from_millis(10));
+            let _ = tx3.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
+        });
+
+        let (index1, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
+        let (index2, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
+        let (index3, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
+        assert_eq!(index1, 2);
+        assert_eq!(index2, 0);
+        assert_eq!(index3, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_timeout() -> errors::Result<()> {
+        let (tx1, rx1, waker1) = instance::instance_channel();
+        let ir1 = InstanceReceiver::new(rx1, None, waker1);
+        let (tx2, rx2, waker2) = instance::instance_channel();
+        let ir2 = InstanceReceiver::new(rx2, None, waker2);
+
+        thread::spawn(move || {
+            let _ = tx1.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
+            // Block the thread as sending does not block the current thread
+            thread::sleep(time::Duration::from_millis(10));
+            let _ = tx2.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
+        });
+
+        let d = time::Duration::from_millis(500);
+        let (index1, _) = Jvm::select_timeout(&[&ir1, &ir2], &d)?;
+        let (index2, _) = Jvm::select_timeout(&[&ir1, &ir2], &d)?;
+        assert!(Jvm::select_timeout(&[&ir1, &ir2], &d).is_err());
+        assert_eq!(index1, 0);
+        assert_eq!(index2, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_java_class_creation() -> errors::Result<()> {
+        assert_eq!(JavaClass::Void.get_class_str(), "void");
+        assert_eq!(JavaClass::String.get_class_str(), CLASS_STRING);
+        assert_eq!(JavaClass::Boolean.get_class_str(), CLASS_BOOLEAN);
+        assert_eq!(JavaClass::Byte.get_class_str(), CLASS_BYTE);
+        assert_eq!(JavaClass::Character.get_class_str(), CLASS_CHARACTER);
+        assert_eq!(JavaClass::Short.get_class_str(), CLASS_SHORT);
+        assert_eq!(JavaClass::Integer.get_class_str(), CLASS_INTEGER);
+        assert_eq!(JavaClass::Long.get_class_str(), CLASS_LONG);
+        assert_eq!(JavaClass::Float.get_class_str(), CLASS_FLOAT);
+        assert_eq!(JavaClass::Double.get_class_str(), CLASS_DOUBLE);
+        assert_eq!(JavaClass::List.get_class_str(), CLASS_LIST);
+        assert_eq!(
+            JavaClass::Of("a.java.Class").get_class_str(),
+            "a.java.Class"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<i8> = vec![-3_i8, 7_i8, 8_i8];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_BYTE, &ia)?;
+        let rust_value_from_java: Vec<i8> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_u8_slice_to_java_and_back() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let bytes = "αβγ".as_bytes();
+        let s = jvm.create_instance(CLASS_STRING, &[InvocationArg::try_from(bytes)?, InvocationArg::try_from("UTF-8")?])?;
+        let text: String = jvm.to_rust(jvm.clone_instance(&s)?)?;
+        assert_eq!(text, "αβγ");
+        let rust_value_from_java: Vec<u8> = jvm.to_rust(jvm.invoke(&s, "getBytes", &[InvocationArg::try_from("UTF-8")?])?)?;
+        assert_eq!(rust_value_from_java, bytes);
+
+        let empty: Vec<u8> = jvm.to_rust(jvm.create_java_array(PRIMITIVE_BYTE, InvocationArg::empty())?)?;
+        assert!(empty.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_2d_arrays() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let row1 = [InvocationArg::try_from(1.5_f64)?.into_primitive()?, InvocationArg::try_from(2.0_f64)?.into_primitive()?];
+        let row2 = [InvocationArg::try_from(3.0_f64)?.into_primitive()?];
+        let matrix = jvm.create_java_2d_array(PRIMITIVE_DOUBLE, &[&row1, &row2, &[]])?;
+        let text: String = jvm.to_rust(jvm.invoke_static(
+            "java.util.Arrays",
+            "deepToString",
+            &[InvocationArg::from(jvm.clone_instance(&matrix)?)],
+        )?)?;
+        assert_eq!(text, "[[1.5, 2.0], [3.0], []]");
+        let rust_value_from_java: Vec<Vec<f64>> = jvm.to_rust(matrix)?;
+        assert_eq!(rust_value_from_java, vec![vec![1.5, 2.0], vec![3.0], vec![]]);
+
+        let strings = jvm.create_java_2d_array(CLASS_STRING, &[&[InvocationArg::try_from("a")?], &[InvocationArg::try_from("b")?]])?;
+        let rust_value_from_java: Vec<Vec<String>> = jvm.to_rust(strings)?;
+        assert_eq!(rust_value_from_java, vec![vec!["a".to_string()], vec!["b".to_string()]]);
+
+        let bytes = jvm.create_java_2d_array(PRIMITIVE_BYTE, &[&[InvocationArg::try_from(-1_i8)?.into_primitive()?]])?;
+        let rust_value_from_java: Vec<Vec<u8>> = jvm.to_rust(bytes)?;
+        assert_eq!(rust_value_from_java, vec![vec![255]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_short_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<i16> = vec![-3_i16, 7_i16, 10000_i16];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_SHORT, &ia)?;
+        let rust_value_from_java: Vec<i16> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_char_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<u16> = vec![3_u16, 7_u16, 10000_u16];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_CHAR, &ia)?;
+        let rust_value_from_java: Vec<u16> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_int_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<i32> = vec![-100_000, -1_000_000, 1_000_000];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_INT, &ia)?;
+        let rust_value_from_java: Vec<i32> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_long_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<i64> = vec![-100_000, -1_000_000, 1_000_000];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_LONG, &ia)?;
+        let rust_value_from_java: Vec<i64> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<f32> = vec![3_f32, 7.5_f32, -1000.5_f32];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_FLOAT, &ia)?;
+        let rust_value_from_java: Vec<f32> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<f64> = vec![3_f64, 7.5_f64, -1000.5_f64];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_DOUBLE, &ia)?;
+        let rust_value_from_java: Vec<f64> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_boolean_array_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: Vec<bool> = vec![false, true, false];
+        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
+        let java_instance = jvm.create_java_array(PRIMITIVE_BOOLEAN, &ia)?;
+        let rust_value_from_java: Vec<bool> = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_int_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: i32 = 3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_INTEGER, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "intValue", InvocationArg::empty())?;
+        let rust_value_from_java: i32 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: i32 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
 
-impl<'a> JvmBuilder<'a> {
-    /// Creates a new JvmBuilder.
-    pub fn new<'b>() -> JvmBuilder<'b> {
-        JvmBuilder {
-            classpath_entries: Vec::new(),
-            java_opts: Vec::new(),
-            no_implicit_classpath: false,
-            detach_thread_on_drop: true,
-            lib_name_opt: None,
-            skip_setting_native_lib: false,
-            base_path: None,
-            maven_settings: MavenSettings::default(),
-            javafx: false,
-            default_classloader: false,
-            java_vm_opt: None,
-            jobject_within_valid_classloader_opt: None
-        }
+        Ok(())
     }
 
-    /// Adds a classpath entry.
-    pub fn classpath_entry(&'a mut self, cp_entry: ClasspathEntry<'a>) -> &'a mut JvmBuilder<'a> {
-        self.classpath_entries.push(cp_entry);
-        self
+    #[test]
+    fn test_byte_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: i8 = 3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_BYTE, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "byteValue", InvocationArg::empty())?;
+        let rust_value_from_java: i8 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: i8 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// Adds classpath entries.
-    pub fn classpath_entries(
-        &'a mut self,
-        cp_entries: Vec<ClasspathEntry<'a>>,
-    ) -> &'a mut JvmBuilder<'a> {
-        for cp_entry in cp_entries {
-            self.classpath_entries.push(cp_entry);
-        }
-        self
+    #[test]
+    fn test_short_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: i16 = 3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_SHORT, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "shortValue", InvocationArg::empty())?;
+        let rust_value_from_java: i16 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: i16 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// Adds a Java option.
-    pub fn java_opt(&'a mut self, opt: JavaOpt<'a>) -> &'a mut JvmBuilder<'a> {
-        self.java_opts.push(opt);
-        self
+    #[test]
+    fn test_char_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: u16 = 3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_CHARACTER, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "charValue", InvocationArg::empty())?;
+        let rust_value_from_java: u16 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: u16 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// Adds Java options.
-    pub fn java_opts(&'a mut self, opts: Vec<JavaOpt<'a>>) -> &'a mut JvmBuilder<'a> {
-        for opt in opts {
-            self.java_opts.push(opt);
-        }
-        self
+    #[test]
+    fn test_long_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: i64 = 3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_LONG, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "longValue", InvocationArg::empty())?;
+        let rust_value_from_java: i64 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: i64 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// By default, the created `Jvm`s include an implicit classpath entry that includes the j4rs jar.
-    /// When `with_no_implicit_classpath()` is called, this classpath will not be added to the Jvm.
-    pub fn with_no_implicit_classpath(&'a mut self) -> &'a mut JvmBuilder<'a> {
-        self.no_implicit_classpath = true;
-        self
+    #[test]
+    fn test_float_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: f32 = 3.3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_FLOAT, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "floatValue", InvocationArg::empty())?;
+        let rust_value_from_java: f32 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: f32 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// When a Jvm goes out of scope and is being dropped, its current thread is being detached from the Java VM.
-    /// A Jvm that is created with `detach_thread_on_drop(false)` will not detach the thread when being dropped.
-    ///
-    /// This is useful when in the Java world a native method is called and in the native code someone needs to create a j4rs Jvm.
-    /// If that Jvm detaches its current thread when being dropped, there will be problems for the Java world code to continue executing.
-    pub fn detach_thread_on_drop(&'a mut self, detach_thread_on_drop: bool) -> &'a mut JvmBuilder<'a> {
-        self.detach_thread_on_drop = detach_thread_on_drop;
-        self
+    #[test]
+    fn test_double_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let rust_value: f64 = 3.3;
+        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
+        let java_instance = jvm.create_instance(CLASS_DOUBLE, &[ia])?;
+        let java_primitive_instance = jvm.invoke(&java_instance, "doubleValue", InvocationArg::empty())?;
+        let rust_value_from_java: f64 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+        let rust_value_from_java: f64 = jvm.to_rust(java_primitive_instance)?;
+        assert_eq!(rust_value_from_java, rust_value);
+
+        Ok(())
     }
 
-    /// In the case that the j4rs is statically linked to some other library, the Java world (j4rs.jar) needs to load that
-    /// library instead of the default one.
-    ///
-    /// This function defines the native library name to load.
-    pub fn with_native_lib_name(&'a mut self, lib_name: &str) -> &'a mut JvmBuilder<'a> {
-        self.lib_name_opt = Some(lib_name.to_string());
-        self
+    #[test]
+    fn test_invoke_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let java_instance = jvm.create_instance(CLASS_INTEGER, &[InvocationArg::try_from(3)?.into_primitive()?])?;
+        // Primitive
+        let i: i32 = jvm.invoke_to_rust(&java_instance, "intValue", InvocationArg::empty())?;
+        assert_eq!(i, 3);
+        // String
+        let s: String = jvm.invoke_to_rust(&java_instance, "toString", InvocationArg::empty())?;
+        assert_eq!(s, "3");
+        // Deserialized
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+        let v: Vec<i32> = jvm.invoke_to_rust(&test_instance, "getNumbersUntil", &[InvocationArg::try_from(3)?])?;
+        assert_eq!(v, vec![0, 1, 2]);
+        // Exception
+        let res: errors::Result<i32> = jvm.invoke_to_rust(&java_instance, "nonExisting", InvocationArg::empty());
+        assert!(res.is_err());
+
+        Ok(())
     }
 
-    /// Configures the builder not to instruct the Java world j4rs code to load the native library.
-    /// (most probably because it is already loaded)
-    pub fn skip_setting_native_lib(&'a mut self) -> &'a mut JvmBuilder<'a> {
-        self.skip_setting_native_lib = true;
-        self
+    #[test]
+    fn test_unsigned_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let java_instance = jvm.create_instance(CLASS_SHORT, &[InvocationArg::try_from(3_u8)?.into_primitive()?])?;
+        let rust_value_from_java: u8 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, 3);
+        let java_instance = jvm.create_instance(CLASS_LONG, &[InvocationArg::try_from(3_u32)?.into_primitive()?])?;
+        let rust_value_from_java: u32 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, 3);
+        let java_instance = jvm.create_instance(CLASS_LONG, &[InvocationArg::try_from(3_u64)?.into_primitive()?])?;
+        let rust_value_from_java: u64 = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, 3);
+        let java_instance = jvm.create_instance(CLASS_INTEGER, &[InvocationArg::try_from(3_i32)?.into_primitive()?])?;
+        let rust_value_from_java: usize = jvm.to_rust(java_instance)?;
+        assert_eq!(rust_value_from_java, 3);
+
+        let java_instance = jvm.create_instance(CLASS_INTEGER, &[InvocationArg::try_from(-3_i32)?.into_primitive()?])?;
+        let res: errors::Result<u32> = jvm.to_rust(java_instance);
+        assert!(res.is_err());
+        let java_instance = jvm.create_instance(CLASS_INTEGER, &[InvocationArg::try_from(256_i32)?.into_primitive()?])?;
+        let res: errors::Result<u8> = jvm.to_rust(java_instance);
+        assert!(res.is_err());
+
+        Ok(())
     }
 
-    /// Defines the location of the jassets and deps directory.
-    /// The jassets contains the j4rs jar and the deps the j4rs dynamic library.
-    pub fn with_base_path(&'a mut self, base_path: &str) -> &'a mut JvmBuilder<'a> {
-        self.base_path = Some(base_path.to_string());
-        self
+    #[test]
+    fn test_big_numbers_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let big_integer = jvm.create_instance(CLASS_BIG_INTEGER, &[InvocationArg::try_from("170141183460469231731687303715884105727")?])?;
+        let s: String = jvm.to_rust(jvm.clone_instance(&big_integer)?)?;
+        assert_eq!(s, "170141183460469231731687303715884105727");
+        let i: i128 = jvm.to_rust(jvm.clone_instance(&big_integer)?)?;
+        assert_eq!(i, i128::MAX);
+        let u: u128 = jvm.to_rust(big_integer)?;
+        assert_eq!(u, i128::MAX as u128);
+
+        let big_decimal = jvm.create_instance(CLASS_BIG_DECIMAL, &[InvocationArg::try_from("1E+3")?])?;
+        let s: String = jvm.to_rust(jvm.clone_instance(&big_decimal)?)?;
+        assert_eq!(s, "1000");
+        let big_decimal = jvm.create_instance(CLASS_BIG_DECIMAL, &[InvocationArg::try_from("1.5")?])?;
+        let res: errors::Result<i128> = jvm.to_rust(big_decimal);
+        assert!(res.is_err());
+
+        let big_integer = jvm.create_instance(CLASS_BIG_INTEGER, &[InvocationArg::try_from("1")?])?;
+        let big_integer = jvm.invoke(&big_integer, "add", &[InvocationArg::try_from(-3_i128)?])?;
+        let i: i128 = jvm.to_rust(big_integer)?;
+        assert_eq!(i, -2);
+
+        Ok(())
     }
 
-    /// Defines the maven settings to use for provisioning maven artifacts.
-    pub fn with_maven_settings(&'a mut self, maven_settings: MavenSettings) -> &'a mut JvmBuilder<'a> {
-        self.maven_settings = maven_settings;
-        self
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn test_rust_decimal() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let decimal: rust_decimal::Decimal = "12.345".parse().unwrap();
+        let big_decimal = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(&decimal)?])?;
+        let big_decimal = jvm.cast(&big_decimal, CLASS_BIG_DECIMAL)?;
+        let d: rust_decimal::Decimal = jvm.to_rust(big_decimal)?;
+        assert_eq!(d, decimal);
+        Ok(())
     }
 
-    /// Adds JavaFX support to the created JVM
-    pub fn with_javafx_support(&'a mut self) -> &'a mut JvmBuilder<'a> {
-        self.javafx = true;
-        self
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_num_bigint() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let big_int: num_bigint::BigInt = "-1234567890123456789012345678901234567890".parse().unwrap();
+        let big_integer = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::try_from(&big_int)?])?;
+        let big_integer = jvm.cast(&big_integer, CLASS_BIG_INTEGER)?;
+        let b: num_bigint::BigInt = jvm.to_rust(big_integer)?;
+        assert_eq!(b, big_int);
+        Ok(())
     }
 
-    /// Create the j4rs `Jvm` using an already created jni `JavaVM`.
-    /// 
-    /// Useful for Android apps, where the JVM is automatically created.
-    pub fn with_java_vm(&'a mut self, java_vm: *mut JavaVM) -> &'a mut JvmBuilder<'a> {
-        self.java_vm_opt = Some(java_vm);
-        self
+    #[test]
+    fn test_java_exception() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let res = jvm.invoke_static("java.lang.Integer", "parseInt", &[InvocationArg::try_from("abc")?]);
+        let java_exception = match res {
+            Err(J4RsError::JavaException(java_exception)) => java_exception,
+            other => panic!("Expected a JavaException, found {:?}", other.map(|_| ())),
+        };
+        assert_eq!(java_exception.class_name(), "java.lang.NumberFormatException");
+        assert_eq!(java_exception.message(), Some("For input string: \"abc\""));
+        assert!(java_exception.stack_trace().iter().any(|frame| frame.method_name() == "parseInt"));
+        assert!(java_exception.cause().is_none());
+        assert!(java_exception.to_string().starts_with("java.lang.NumberFormatException: For input string"));
+
+        let throwable = java_exception.throwable(&jvm)?.unwrap();
+        assert_eq!(throwable.class_name(), "java.lang.NumberFormatException");
+        let message: String = jvm.to_rust(jvm.invoke(&throwable, "getMessage", InvocationArg::empty())?)?;
+        assert_eq!(Some(message.as_str()), java_exception.message());
+
+        Ok(())
     }
 
-    /// Instructs j4rs to use the classloader associated to the specified `jobject` when searching for classes.
-    /// 
-    /// Useful for pure native Android apps.
-    /// 
-    /// As described in Android documentation, the native libraries that spawn new threads cannot locate and use Java classes correctly.
-    ///
-    /// From the [docs]((https://developer.android.com/training/articles/perf-jni#faq:-why-didnt-findclass-find-my-class)):
-    /// 
-    /// > You can get into trouble if you create a thread yourself (perhaps by calling pthread_create and then attaching it with AttachCurrentThread). 
-    /// > Now there are no stack frames from your application. If you call FindClass from this thread, the JavaVM will start in the "system" class loader 
-    /// > instead of the one associated with your application, so attempts to find app-specific classes will fail.
-    /// 
-    /// Even if it is proposed to load and cache classes during `JNI_OnLoad` (j4rs documentation about it [here](https://github.com/astonbitecode/j4rs?tab=readme-ov-file#j4rs-in-android))
-    /// `JNI_OnLoad` is not called on pure native apps. One solution to find the app-specific classes in this case, is to use the classloader 
-    /// of the `Activity` to find and use classes. `j4rs` will use the `jobject` passed here (the `jobject` of the `Activity`) to get the 
-    /// proper classloader and use it when needed.
-    /// #[cfg(target_os = "android")]
-    pub fn with_classloader_of_activity(&'a mut self, jobject_within_valid_classloader: jobject) -> &'a mut JvmBuilder<'a> {
-        self.jobject_within_valid_classloader_opt = Some(jobject_within_valid_classloader);
-        // If the `jobject_within_valid_classloader_opt` is provided, it means that the object's classloader
-        // should be used to load classes in case the traditional `FindClass` invocations fail.
-        // Apply here the needed configuration for it to happen.
-        // Do not detach the thread on drop. This would make the Activity to fail.
-        let tmp = self.detach_thread_on_drop(false);
-        let tmp = tmp.with_no_implicit_classpath();
-        tmp
+    #[test]
+    fn test_invoke_with_signature() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+
+        let res: String = jvm.to_rust(jvm.invoke_with_signature(
+            &test_instance, "overloaded", &["java.lang.Object"], &[InvocationArg::try_from("a")?])?)?;
+        assert_eq!(res, "Object");
+        let res: String = jvm.to_rust(jvm.invoke_with_signature(
+            &test_instance, "overloaded", &["java.lang.String"], &[InvocationArg::try_from("a")?])?)?;
+        assert_eq!(res, "String");
+        let res: String = jvm.to_rust(jvm.invoke_with_signature(
+            &test_instance, "overloaded", &["int"], &[InvocationArg::try_from(1)?])?)?;
+        assert_eq!(res, "int");
+
+        let res: String = jvm.to_rust(jvm.invoke_static_with_signature(
+            "org.astonbitecode.j4rs.tests.MyTest", "overloadedStatic", &["java.lang.Object"], &[InvocationArg::try_from("a")?])?)?;
+        assert_eq!(res, "Object");
+        let res: String = jvm.to_rust(jvm.invoke_static_with_signature(
+            "org.astonbitecode.j4rs.tests.MyTest", "overloadedStatic", &["java.lang.String"], &[InvocationArg::try_from("a")?])?)?;
+        assert_eq!(res, "String");
+
+        assert!(jvm.invoke_with_signature(
+            &test_instance, "overloaded", &["java.lang.Long"], &[InvocationArg::try_from(1_i64)?]).is_err());
+        assert!(jvm.invoke_with_signature(
+            &test_instance, "overloaded", &["java.lang.String"], InvocationArg::empty()).is_err());
+
+        Ok(())
     }
 
-    /// `j4rs` uses a custom ClassLoader (namely the `J4rsClassLoader`),
-    /// that allows adding jars to the classpath during runtime, after the underlying `JVM` is initialized.
-    ///
-    /// This function instructs the builder not to use this custom classloader, but use the default one.
-    ///
-    /// Please note that the `J4rsClassLoader` needs Java 9 or higher. If you use an older Java version,
-    /// you __must__ call this function in order for `j4rs` to work.
-    ///
-    /// If not, you will get exceptions like the following:
-    ///
-    /// ```text
-    /// java.lang.NoSuchMethodError: java.net.URLClassLoader.`<init>`(Ljava/lang/String;[Ljava/net/URL;Ljava/lang/ClassLoader;)V
-    ///         at org.astonbitecode.j4rs.api.deploy.J4rsClassLoader.`<init>`(J4rsClassLoader.java:22)
-    ///         at sun.reflect.NativeConstructorAccessorImpl.newInstance0(Native Method)
-    ///         at sun.reflect.NativeConstructorAccessorImpl.newInstance(NativeConstructorAccessorImpl.java:62)
-    ///         at sun.reflect.DelegatingConstructorAccessorImpl.newInstance(DelegatingConstructorAccessorImpl.java:45)
-    ///         at java.lang.reflect.Constructor.newInstance(Constructor.java:423)
-    ///         at java.lang.SystemClassLoaderAction.run(ClassLoader.java:2204)
-    ///         at java.lang.SystemClassLoaderAction.run(ClassLoader.java:2188)
-    ///         at java.security.AccessController.doPrivileged(Native Method)
-    ///         at java.lang.ClassLoader.initSystemClassLoader(ClassLoader.java:1449)
-    ///         at java.lang.ClassLoader.getSystemClassLoader(ClassLoader.java:1429)
-    /// ```
-    pub fn with_default_classloader(&'a mut self) -> &'a mut JvmBuilder<'a> {
-        self.default_classloader = true;
-        self
+    #[test]
+    fn test_invoke_on_interface() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let map = jvm.create_instance("java.util.HashMap", InvocationArg::empty())?;
+        jvm.invoke(&map, "put", &[InvocationArg::try_from("one")?, InvocationArg::try_from(1)?])?;
+        let unmodifiable = jvm.invoke_static(
+            "java.util.Collections", "unmodifiableMap", &[InvocationArg::from(map)])?;
+
+        // A default method of the Map interface, invoked on a private implementation class
+        let res: i32 = jvm.to_rust(jvm.invoke_on_interface(
+            &unmodifiable, "java.util.Map", "getOrDefault", &[InvocationArg::try_from("one")?, InvocationArg::try_from(0)?])?)?;
+        assert_eq!(res, 1);
+        let res: i32 = jvm.to_rust(jvm.invoke_on_interface(
+            &unmodifiable, "java.util.Map", "getOrDefault", &[InvocationArg::try_from("two")?, InvocationArg::try_from(2)?])?)?;
+        assert_eq!(res, 2);
+
+        // A static method of an interface
+        let comparator_class = jvm.static_class("java.util.Comparator")?;
+        let comparator = jvm.invoke_on_interface(
+            &comparator_class, "java.util.Comparator", "naturalOrder", InvocationArg::empty())?;
+        let res: i32 = jvm.to_rust(jvm.invoke_on_interface(
+            &comparator, "java.util.Comparator", "compare", &[InvocationArg::try_from("a")?, InvocationArg::try_from("b")?])?)?;
+        assert!(res < 0);
+
+        let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a")?])?;
+        assert!(jvm.invoke_on_interface(&string, "java.util.Map", "size", InvocationArg::empty()).is_err());
+        assert!(jvm.invoke_on_interface(&string, "java.lang.String", "length", InvocationArg::empty()).is_err());
+
+        Ok(())
     }
 
-    /// Creates a Jvm
-    pub fn build(&mut self) -> errors::Result<Jvm> {
-        if !self.default_classloader {
-            // Define the system classloader
-            self.java_opts.push(JavaOpt::new(
-                "-Djava.system.class.loader=org.astonbitecode.j4rs.api.deploy.J4rsClassLoader",
-            ));
-            self.java_opts.push(JavaOpt::new("-Xshare:off"));
-            self.java_opts.push(JavaOpt::new(
-                "-Djdk.net.URLClassPath.showIgnoredClassPathEntries=true",
-            ));
+    #[test]
+    fn test_derive_java_bean() -> errors::Result<()> {
+        #[derive(j4rs_derive::IntoJava, j4rs_derive::FromJava, Debug, PartialEq)]
+        #[j4rs(class = "org.astonbitecode.j4rs.tests.MyBean")]
+        struct Bean {
+            some_string: String,
+            #[j4rs(boxed)]
+            some_integer: i32,
         }
 
-        let classpath = if self.no_implicit_classpath {
-            self.classpath_entries
-                .iter()
-                .fold(".".to_string(), |all, elem| {
-                    format!("{}{}{}", all, utils::classpath_sep(), elem.to_string())
-                })
-        } else {
-            // The default classpath contains all the jars in the jassets directory
-            let jassets_path = self.get_jassets_path()?;
-            // This is the j4rs jar that should be included in the classpath
-            let j4rs_jar_to_use = format!("j4rs-{}-jar-with-dependencies.jar", j4rs_version());
-            let j4rs_testing_jar_to_use = format!("j4rs-testing-{}.jar", j4rs_version());
-            let j4rs_javafx_jar_to_use = format!("j4rs-javafx-{}.jar", j4rs_version());
-            // Filter out possible incorrect jars of j4rs
-            let mut cp_string = String::new();
-            for entry in std::fs::read_dir(jassets_path)? {
-                let path = entry?.path();
-                if let Some(file_name) = opt_to_res(path.file_name())?.to_str() {
-                    if !file_name.contains("j4rs-") || file_name.ends_with(&j4rs_jar_to_use) || file_name.ends_with(&j4rs_testing_jar_to_use)  || file_name.ends_with(&j4rs_javafx_jar_to_use) {
-                        if !cp_string.is_empty() {
-                            cp_string.push_str(utils::classpath_sep());
-                        }
-                        if let Some(path) = path.to_str() {
-                            cp_string.push_str(path);
-                        }
-                    }
-                }
-            }
+        #[derive(j4rs_derive::IntoJava, j4rs_derive::FromJava, Debug, PartialEq)]
+        #[j4rs(class = "org.astonbitecode.j4rs.tests.MyBean", constructor)]
+        struct ConstructedBean {
+            #[j4rs(name = "someString")]
+            string: String,
+            #[j4rs(name = "someInteger", boxed)]
+            integer: i32,
+        }
 
-            let default_class_path = format!("-Djava.class.path={}", cp_string);
+        let jvm = create_tests_jvm()?;
+        let bean = Bean { some_string: "a string".to_string(), some_integer: 33 };
+        let instance = bean.to_java(&jvm)?;
+        let some_string: String = jvm.to_rust(jvm.invoke(&instance, "getSomeString", InvocationArg::empty())?)?;
+        assert_eq!(some_string, "a string");
+        assert_eq!(Bean::from_java(&jvm, &instance)?, bean);
+
+        let constructed = ConstructedBean { string: "another string".to_string(), integer: 11 };
+        let instance = constructed.to_java(&jvm)?;
+        assert_eq!(ConstructedBean::from_java(&jvm, &instance)?, constructed);
+        assert_eq!(
+            Bean::from_java(&jvm, &instance)?,
+            Bean { some_string: "another string".to_string(), some_integer: 11 });
 
-            self.classpath_entries
-                .iter()
-                .fold(default_class_path, |all, elem| {
-                    format!("{}{}{}", all, utils::classpath_sep(), elem.to_string())
-                })
-        };
-        info(&format!("Setting classpath to {}", classpath));
+        Ok(())
+    }
 
-        // Populate the JVM Options
-        let mut jvm_options = if self.no_implicit_classpath {
-            vec![classpath]
-        } else {
-            let default_library_path = utils::java_library_path()?;
-            info(&format!("Setting library path to {}", default_library_path));
-            vec![classpath, default_library_path]
-        };
+    #[test]
+    fn test_invoke_primitive() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("hello")?])?;
 
-        if self.javafx {
-            let jassets_path = self.get_jassets_path()?;
-            let jassets_path_string = jassets_path.to_str().unwrap_or(".");
-            let modules_path = format!("--module-path {}", jassets_path_string);
-            jvm_options.push(modules_path);
-            jvm_options.push(
-                "--add-modules javafx.base,javafx.controls,javafx.graphics,javafx.fxml".to_string(),
-            );
+        for _ in 0..3 {
+            assert_eq!(jvm.invoke_primitive::<i32>(&string, "length", &[])?, 5);
         }
-        self.java_opts
-            .clone()
-            .into_iter()
-            .for_each(|opt| jvm_options.push(opt.to_string()));
+        assert!(!jvm.invoke_primitive::<bool>(&string, "isEmpty", &[])?);
+        assert_eq!(jvm.invoke_primitive::<u16>(&string, "charAt", &[1.into()])?, 'e' as u16);
+        assert_eq!(jvm.invoke_primitive::<i32>(&string, "indexOf", &[('l' as i32).into(), 3.into()])?, 3);
 
-        // Pass to the Java world the name of the j4rs library.
-        let lib_name_opt = if self.lib_name_opt.is_none() && !self.skip_setting_native_lib && cfg!(not(target_os = "android")) {
-            let deps_dir = utils::deps_dir()?;
-            let found_libs: Vec<String> = if Path::new(&deps_dir).exists() {
-                utils::find_j4rs_dynamic_libraries_names()?
-            } else {
-                // If deps dir is not found, fallback to default naming in order for the library to be searched in the default
-                // library locations of the system.
-                let default_lib_name = if cfg!(windows) {
-                    "l4rs.dll".to_string()
-                } else {
-                    "libj4rs.so".to_string()
-                };
-                info(&format!(
-                    "Deps directory not found. Setting the library name to search to default: {}",
-                    default_lib_name
-                ));
-                vec![default_lib_name]
-            };
+        // Same method name and signature in a different class
+        let builder = jvm.create_instance("java.lang.StringBuilder", &[InvocationArg::try_from("abc")?])?;
+        assert_eq!(jvm.invoke_primitive::<i32>(&builder, "length", &[])?, 3);
 
-            let lib_name_opt = if !found_libs.is_empty() {
-                let a_lib = found_libs[0].clone().replace("lib", "");
+        let atomic = jvm.create_instance("java.util.concurrent.atomic.AtomicLong", InvocationArg::empty())?;
+        assert_eq!(jvm.invoke_primitive::<i64>(&atomic, "addAndGet", &[5_i64.into()])?, 5);
+        assert_eq!(jvm.invoke_primitive::<i64>(&atomic, "addAndGet", &[5_i64.into()])?, 10);
 
-                let dot_splitted: Vec<&str> = a_lib.split('.').collect();
-                let name = dot_splitted[0].to_string();
-                info(&format!(
-                    "Passing to the Java world the name of the library to load: {}",
-                    name
-                ));
-                Some(name)
-            } else {
-                None
-            };
-            lib_name_opt
-        } else if self.lib_name_opt.is_some() && !self.skip_setting_native_lib {
-            let name = self.lib_name_opt.clone();
-            info(&format!(
-                "Passing to the Java world the name of the library to load: {}",
-                name.as_ref().unwrap()
-            ));
-            name
-        } else {
-            None
-        };
+        let integer = jvm.create_instance("java.lang.Integer", &[InvocationArg::try_from(7)?.into_primitive()?])?;
+        assert_eq!(jvm.invoke_primitive::<f64>(&integer, "doubleValue", &[])?, 7.0);
 
-        provisioning::set_maven_settings(&self.maven_settings);
+        // Exceptions and wrong signatures result to errors
+        assert!(jvm.invoke_primitive::<u16>(&string, "charAt", &[10.into()]).is_err());
+        assert!(jvm.invoke_primitive::<i64>(&string, "length", &[]).is_err());
+        assert_eq!(jvm.invoke_primitive::<i32>(&string, "length", &[])?, 5);
 
-        let jvm_res = if self.java_vm_opt.is_some() {
-            // If the `java_vm` is already created and provided, just attach the current thread.
-            set_java_vm(self.java_vm_opt.unwrap());
-            Jvm::attach_thread()
-        } else {
-            Jvm::new(&jvm_options, lib_name_opt)
-        };
+        // Instances without a Java object result to errors
+        let static_class = jvm.static_class("java.lang.Integer")?;
+        assert!(jvm.invoke_primitive::<i32>(&static_class, "intValue", &[]).is_err());
+        let null = jvm.invoke_static("java.lang.System", "getProperty", &[InvocationArg::try_from("j4rs.non.existing")?])?;
+        assert!(null.is_null(&jvm)?);
+        assert!(jvm.invoke_primitive::<i32>(&null, "length", &[]).is_err());
 
-        jvm_res.and_then(|mut jvm| {
-            if !self.detach_thread_on_drop {
-                jvm.detach_thread_on_drop(false);
-            }
-            if self.jobject_within_valid_classloader_opt.is_some() {
-                cache_classloader_of(jvm.jni_env, self.jobject_within_valid_classloader_opt.unwrap())?;
-            }
-            Ok(jvm)
-        })
+        Ok(())
     }
 
-    /// Creates a Jvm, similar with an already created j4rs Jvm.
-    ///
-    /// _Note: The already created Jvm is a j4rs Jvm, not a Java VM._
-    pub fn already_initialized() -> errors::Result<Jvm> {
-        Jvm::new(&[], None)
-    }
+    #[test]
+    fn test_prepare_invocation() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
 
-    fn get_jassets_path(&self) -> errors::Result<PathBuf> {
-        match &self.base_path {
-            Some(base_path_string) => {
-                let mut pb = PathBuf::from(base_path_string);
-                pb.push("jassets");
-                let mut global_jassets_path_opt = cache::JASSETS_PATH.lock()?;
-                *global_jassets_path_opt = Some(pb.clone());
-                Ok(pb)
-            }
-            None => utils::default_jassets_path(),
+        let prepared = jvm.prepare_invocation(&test_instance, "appendToMyString", &["java.lang.String"])?;
+        for _ in 0..3 {
+            let _ = prepared.call(&[InvocationArg::try_from("a")?])?;
         }
+        let res: String = prepared.call_to_rust(&[InvocationArg::try_from("b")?])?;
+        assert!(res.ends_with("aaab"));
+
+        let prepared = jvm.prepare_invocation(&test_instance, "overloaded", &["java.lang.Object"])?;
+        let res: String = jvm.to_rust(prepared.call(&[InvocationArg::try_from("a")?])?)?;
+        assert_eq!(res, "Object");
+
+        let prepared = jvm.prepare_static_invocation("java.lang.Integer", "parseInt", &["java.lang.String"])?;
+        let res: i32 = prepared.call_to_rust(&[InvocationArg::try_from("42")?])?;
+        assert_eq!(res, 42);
+        assert!(prepared.call(&[InvocationArg::try_from("not a number")?]).is_err());
+        assert!(prepared.call(InvocationArg::empty()).is_err());
+
+        assert!(jvm.prepare_invocation(&test_instance, "appendToMyString", &["java.lang.Long"]).is_err());
+
+        Ok(())
     }
-}
 
-/// Represents default, known Classes in Java. Can be used as class argument in `Jvm#java_list`, etc.
-pub enum JavaClass<'a> {
-    Void,
-    String,
-    Boolean,
-    Byte,
-    Character,
-    Short,
-    Integer,
-    Long,
-    Float,
-    Double,
-    List,
-    Of(&'a str),
-}
+    #[test]
+    fn test_shared_jvm() -> errors::Result<()> {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
 
-impl<'a> JavaClass<'a> {
-    pub fn get_class_str(&self) -> &'a str {
-        match self {
-            Self::Void => "void",
-            Self::String => CLASS_STRING,
-            Self::Boolean => CLASS_BOOLEAN,
-            Self::Byte => CLASS_BYTE,
-            Self::Character => CLASS_CHARACTER,
-            Self::Short => CLASS_SHORT,
-            Self::Integer => CLASS_INTEGER,
-            Self::Long => CLASS_LONG,
-            Self::Float => CLASS_FLOAT,
-            Self::Double => CLASS_DOUBLE,
-            Self::List => CLASS_LIST,
-            Self::Of(str) => str,
+        let jvm = create_tests_jvm()?;
+        let shared = jvm.shared();
+        assert_send_sync(&shared);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let shared = shared.clone();
+                thread::spawn(move || -> errors::Result<String> {
+                    let string = shared.with(|jvm| {
+                        jvm.create_instance("java.lang.String", &[InvocationArg::try_from(format!("thread-{}", i))?])
+                    })?;
+                    let _ = shared.invoke(&string, "toUpperCase", InvocationArg::empty())?;
+                    shared.invoke_to_rust(&string, "toString", InvocationArg::empty())
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap()?, format!("thread-{}", i));
         }
-    }
-}
 
-impl<'a> From<JavaClass<'a>> for &'a str {
-    fn from(java_class: JavaClass<'a>) -> &'a str {
-        java_class.get_class_str()
+        let res: i32 = shared.to_rust(shared.invoke_static("java.lang.Integer", "parseInt", &[InvocationArg::try_from("7")?])?)?;
+        assert_eq!(res, 7);
+
+        Ok(())
     }
-}
 
-impl<'a> From<&'a str> for JavaClass<'a> {
-    fn from(java_class: &'a str) -> JavaClass<'a> {
-        match java_class {
-            "void" => Self::Void,
-            CLASS_STRING => Self::String,
-            CLASS_BOOLEAN => Self::Boolean,
-            CLASS_BYTE => Self::Byte,
-            CLASS_CHARACTER => Self::Character,
-            CLASS_SHORT => Self::Short,
-            CLASS_INTEGER => Self::Integer,
-            CLASS_LONG => Self::Long,
-            CLASS_FLOAT => Self::Float,
-            CLASS_DOUBLE => Self::Double,
-            CLASS_LIST => Self::List,
-            str => Self::Of(str),
+    #[test]
+    fn test_to_rust_strategies_are_cached() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        for _ in 0..2 {
+            let i: i32 = jvm.to_rust(jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from("3")?])?)?;
+            assert_eq!(i, 3);
+            let list: Vec<i32> = jvm.to_rust(jvm.java_list(JavaClass::Integer, vec![1_i32, 2])?)?;
+            assert_eq!(list, vec![1, 2]);
         }
+        assert!(matches!(
+            cache::get_to_rust_strategy(TypeId::of::<i32>(), "java.lang.Integer"),
+            Some(cache::ToRustStrategy::Direct(_))
+        ));
+        assert!(matches!(
+            cache::get_to_rust_strategy(TypeId::of::<Vec<i32>>(), "java.util.Arrays$ArrayList"),
+            Some(cache::ToRustStrategy::Deserialized)
+        ));
+        assert_eq!(cache::is_to_rust_deserialized_for_any_class(TypeId::of::<Vec<i32>>()), Some(false));
+        Ok(())
     }
-}
-
-/// Represents Java's null. Use this to create null Objects. E.g.:
-///
-/// let null_integer = InvocationArg::from(Null::Integer);
-/// let null_object = InvocationArg::from(Null::Of("some.class.Name"));
-pub enum Null<'a> {
-    String,
-    Boolean,
-    Byte,
-    Character,
-    Short,
-    Integer,
-    Long,
-    Float,
-    Double,
-    List,
-    Of(&'a str),
-}
 
-/// A classpath entry.
-#[derive(Debug, Clone)]
-pub struct ClasspathEntry<'a>(&'a str);
-
-impl<'a> ClasspathEntry<'a> {
-    pub fn new(classpath_entry: &str) -> ClasspathEntry {
-        ClasspathEntry(classpath_entry)
+    #[test]
+    fn test_to_rust_strategies_per_rust_type() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        // The objects of any class are deserialized to a Vec<String>, so the class is not retrieved for each conversion
+        for _ in 0..2 {
+            let list: Vec<String> = jvm.to_rust(jvm.java_list(JavaClass::String, vec!["a", "b"])?)?;
+            assert_eq!(list, vec!["a".to_string(), "b".to_string()]);
+        }
+        assert_eq!(cache::is_to_rust_deserialized_for_any_class(TypeId::of::<Vec<String>>()), Some(true));
+
+        // The memoized special transformation is applied directly
+        for _ in 0..2 {
+            let big = jvm.create_instance("java.math.BigDecimal", &[InvocationArg::try_from("1.50")?])?;
+            assert_eq!(jvm.to_rust::<String>(big)?, "1.50");
+            let unsigned = jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from("7")?])?;
+            assert_eq!(jvm.to_rust::<u32>(unsigned)?, 7);
+            let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("7")?])?;
+            assert_eq!(jvm.to_rust::<String>(string)?, "7");
+        }
+        assert!(matches!(
+            cache::get_to_rust_strategy(TypeId::of::<String>(), "java.math.BigDecimal"),
+            Some(cache::ToRustStrategy::Special(cache::SpecialToRust::BigNumber))
+        ));
+        assert!(matches!(
+            cache::get_to_rust_strategy(TypeId::of::<u32>(), "java.lang.Integer"),
+            Some(cache::ToRustStrategy::Special(cache::SpecialToRust::Unsigned))
+        ));
+        assert!(matches!(
+            cache::get_to_rust_strategy(TypeId::of::<String>(), "java.lang.String"),
+            Some(cache::ToRustStrategy::Direct(_))
+        ));
+        Ok(())
     }
-}
 
-impl<'a> ToString for ClasspathEntry<'a> {
-    fn to_string(&self) -> String {
-        self.0.to_string()
+    #[test]
+    fn test_sendable_jvm() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let sendable = jvm.to_sendable();
+        let res = thread::spawn(move || -> errors::Result<i32> {
+            let jvm = sendable.attach()?;
+            // Dropping a clone does not detach the thread, while the original Jvm is alive
+            drop(jvm.clone());
+            let integer = jvm.invoke_static("java.lang.Integer", "parseInt", &[InvocationArg::try_from("7")?])?;
+            jvm.to_rust(integer)
+        })
+        .join()
+        .unwrap()?;
+        assert_eq!(res, 7);
+        Ok(())
     }
-}
 
-/// A Java Option.
-#[derive(Debug, Clone)]
-pub struct JavaOpt<'a>(&'a str);
+    #[test]
+    fn test_invoke_with_timeout() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let timeout = time::Duration::from_secs(10);
+        let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a")?])?;
+        let upper: String = jvm.to_rust(jvm.invoke_with_timeout(&string, "toUpperCase", InvocationArg::empty(), timeout)?)?;
+        assert_eq!(upper, "A");
+        let parsed: i32 = jvm.to_rust(jvm.invoke_static_with_timeout(
+            "java.lang.Integer",
+            "parseInt",
+            &[InvocationArg::try_from("42")?],
+            timeout,
+        )?)?;
+        assert_eq!(parsed, 42);
+
+        let latch = jvm.create_instance(
+            "java.util.concurrent.CountDownLatch",
+            &[InvocationArg::try_from(1)?.into_primitive()?],
+        )?;
+        let res = jvm.invoke_with_timeout(&latch, "await", InvocationArg::empty(), time::Duration::from_millis(100));
+        assert_eq!(res.err(), Some(J4RsError::Timeout));
+        let res = jvm.invoke_static_with_timeout(
+            "java.lang.Thread",
+            "sleep",
+            &[InvocationArg::try_from(10_000_i64)?.into_primitive()?],
+            time::Duration::from_millis(100),
+        );
+        assert_eq!(res.err(), Some(J4RsError::Timeout));
 
-impl<'a> JavaOpt<'a> {
-    pub fn new(java_opt: &str) -> JavaOpt {
-        JavaOpt(java_opt)
+        // Other errors are returned as usual
+        let res = jvm.invoke_static_with_timeout("java.lang.Integer", "parseInt", &[InvocationArg::try_from("a")?], timeout);
+        assert!(matches!(res, Err(J4RsError::JavaException(_))));
+
+        Ok(())
     }
-}
 
-impl<'a> ToString for JavaOpt<'a> {
-    fn to_string(&self) -> String {
-        self.0.to_string()
+    #[test]
+    fn test_management() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let management = jvm.management();
+
+        let heap = management.heap_usage()?;
+        assert!(heap.used() > 0);
+        assert!(heap.committed() >= heap.used());
+        assert!(management.non_heap_usage()?.used() > 0);
+        assert!(!management.garbage_collectors()?.is_empty());
+        assert!(management.thread_count()? > 0);
+        assert!(management.daemon_thread_count()? <= management.thread_count()?);
+        assert!(management.loaded_class_count()? > 0);
+
+        let dump = jvm.thread_dump()?;
+        let current = dump.iter().find(|thread| {
+            thread.stack_trace().iter().any(|frame| frame.method_name() == "threadDump")
+        });
+        assert!(current.is_some());
+        assert_eq!(current.unwrap().state(), "RUNNABLE");
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod api_unit_tests {
-    use crate::lib_unit_tests::create_tests_jvm;
-    use super::*;
+    #[test]
+    fn test_redirect_java_logging() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        jvm.redirect_java_logging(log::LevelFilter::Debug)?;
+        let logger = jvm.invoke_static("java.util.logging.Logger", "getLogger", &[InvocationArg::try_from("j4rs.tests")?])?;
+        jvm.invoke(&logger, "warning", &[InvocationArg::try_from("A warning from Java")?])?;
+        jvm.invoke(&logger, "fine", &[InvocationArg::try_from("A debug message from Java")?])?;
+        let enabled: bool = jvm.invoke_to_rust(
+            &logger,
+            "isLoggable",
+            &[InvocationArg::from(jvm.static_class_field("java.util.logging.Level", "FINEST")?)],
+        )?;
+        assert!(!enabled);
+        Ok(())
+    }
 
     #[test]
-    fn jvm_builder() -> errors::Result<()> {
-        let res = create_tests_jvm();
-        assert!(res.is_ok());
-        let one_more_res = JvmBuilder::already_initialized();
-        assert!(one_more_res.is_ok());
+    fn test_invoke_on_edt() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let on_edt: bool = jvm.to_rust(jvm.invoke_static_on_edt(
+            "javax.swing.SwingUtilities",
+            "isEventDispatchThread",
+            InvocationArg::empty(),
+        )?)?;
+        assert!(on_edt);
+
+        let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("edt")?])?;
+        let upper: String = jvm.to_rust(jvm.invoke_on_edt(&string, "toUpperCase", InvocationArg::empty())?)?;
+        assert_eq!(upper, "EDT");
+
+        let on_edt = jvm.run_on_edt(|jvm| {
+            let instance = jvm.invoke_static("javax.swing.SwingUtilities", "isEventDispatchThread", InvocationArg::empty())?;
+            jvm.to_rust::<bool>(instance)
+        })?;
+        assert!(on_edt);
+        Ok(())
+    }
 
+    #[test]
+    fn test_warm_up() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        jvm.warm_up()?;
+        let handle = thread::spawn(move || -> errors::Result<String> {
+            let jvm = Jvm::attach_thread()?;
+            let instance = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("warm")?])?;
+            jvm.invoke_to_rust(&instance, "toUpperCase", InvocationArg::empty())
+        });
+        assert_eq!(handle.join().unwrap()?, "WARM");
         Ok(())
     }
 
     #[test]
-    fn test_copy_j4rs_libs_under() -> errors::Result<()> {
-        let newdir = "./newdir";
-        Jvm::copy_j4rs_libs_under(newdir)?;
+    fn test_verify_jassets() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let source = env::temp_dir().join("j4rs-verify-jassets-test.jar");
+        fs::write(&source, b"j4rs")?;
+        jvm.deploy_artifact(&LocalJarArtifact::new(source.to_str().unwrap()))?;
+        let deployed = utils::jassets_path()?.join("j4rs-verify-jassets-test.jar");
+        let status = |jvm: &Jvm| -> errors::Result<Option<JarStatus>> {
+            Ok(jvm
+                .verify_jassets()?
+                .into_iter()
+                .find(|verification| verification.file() == "j4rs-verify-jassets-test.jar")
+                .map(|verification| verification.status()))
+        };
 
-        let _ = std::fs::remove_dir_all(newdir);
+        assert_eq!(status(&jvm)?, Some(JarStatus::Ok));
+        fs::write(&deployed, b"tampered")?;
+        assert_eq!(status(&jvm)?, Some(JarStatus::Corrupt));
+        fs::remove_file(&deployed)?;
+        assert_eq!(status(&jvm)?, Some(JarStatus::Missing));
 
+        fs::remove_file(&source)?;
         Ok(())
     }
 
     #[test]
-    fn test_select() -> errors::Result<()> {
-        let (tx1, rx1) = channel();
-        let ir1 = InstanceReceiver::new(rx1, 0);
-        let (_tx2, rx2) = channel();
-        let ir2 = InstanceReceiver::new(rx2, 0);
-        let (tx3, rx3) = channel();
-        let ir3 = InstanceReceiver::new(rx3, 0);
+    fn test_artifact_lock() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let lock_path = env::temp_dir().join("j4rs-test-artifact.lock");
+        jvm.export_artifact_lock(&lock_path)?;
+        assert!(fs::read_to_string(&lock_path)?.trim_start().starts_with('['));
+        let _jvm = JvmBuilder::new().with_artifact_lock(&lock_path).build()?;
+
+        fs::write(
+            &lock_path,
+            r#"[{"groupId":"my.group","artifactId":"j4rs-not-deployed","version":"1.0","qualifier":"","repoBase":"http://localhost:1","sha256":"00"}]"#,
+        )?;
+        assert!(JvmBuilder::new().with_artifact_lock(&lock_path).build().is_err());
+
+        fs::remove_file(&lock_path)?;
+        Ok(())
+    }
 
-        thread::spawn(move || {
-            let _ = tx3.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
-            // Block the thread as sending does not block the current thread
-            thread::sleep(time::Duration::from_millis(10));
-            let _ = tx1.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
-            thread::sleep(time::Duration::from_millis(10));
-            let _ = tx3.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
-        });
+    #[test]
+    fn test_non_public_member_access() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let class_name = "org.astonbitecode.j4rs.json.JsonCodecService";
+        let deployer = "org.astonbitecode.j4rs.api.deploy.SimpleMavenDeployer";
+        let signature = ["java.lang.String", "java.lang.String"];
+        let auth_args = [InvocationArg::try_from("user")?, InvocationArg::try_from("pass")?];
+
+        // The public constructors can always be used
+        let s = jvm.invoke_constructor_with_signature(
+            "java.lang.String",
+            &["java.lang.String"],
+            &[InvocationArg::try_from("a")?],
+        )?;
+        assert_eq!(jvm.to_rust::<String>(s)?, "a");
+
+        assert!(jvm.invoke_constructor_with_signature(class_name, &[], InvocationArg::empty()).is_err());
+        assert!(jvm.invoke_static_with_signature(deployer, "basicAuthorization", &signature, &auth_args).is_err());
+
+        jvm.set_accessible(true)?;
+        let instance = jvm.invoke_constructor_with_signature(class_name, &[], InvocationArg::empty());
+        let auth = jvm.invoke_static_with_signature(deployer, "basicAuthorization", &signature, &auth_args);
+        jvm.set_accessible(false)?;
+
+        assert_eq!(instance?.class_name(), class_name);
+        assert_eq!(jvm.to_rust::<String>(auth?)?, "Basic dXNlcjpwYXNz");
+        Ok(())
+    }
 
-        let (index1, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
-        let (index2, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
-        let (index3, _) = Jvm::select(&[&ir1, &ir2, &ir3]).unwrap();
-        assert_eq!(index1, 2);
-        assert_eq!(index2, 0);
-        assert_eq!(index3, 2);
+    #[test]
+    fn test_maps_of_serde_types() -> errors::Result<()> {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
 
+        let jvm = create_tests_jvm()?;
+        let numbers: HashMap<i32, String> = HashMap::from([(1, "one".to_string()), (2, "two".to_string())]);
+        let map = InvocationArg::try_from(&numbers)?.instance()?;
+        let rust_value_from_java: HashMap<i32, String> = jvm.to_rust(map)?;
+        assert_eq!(rust_value_from_java, numbers);
+
+        let points: BTreeMap<Point, Vec<String>> = BTreeMap::from([
+            (Point { x: 1, y: 2 }, vec!["a".to_string()]),
+            (Point { x: 0, y: 5 }, vec!["b".to_string(), "c".to_string()]),
+        ]);
+        let map = InvocationArg::try_from(&points)?.instance()?;
+        let rust_value_from_java: BTreeMap<Point, Vec<String>> = jvm.to_rust(map)?;
+        assert_eq!(rust_value_from_java, points);
+
+        let nested: BTreeMap<String, BTreeMap<i64, bool>> =
+            BTreeMap::from([("a".to_string(), BTreeMap::from([(10, true), (20, false)]))]);
+        let map = InvocationArg::try_from(nested.clone())?.instance()?;
+        let rust_value_from_java: BTreeMap<String, BTreeMap<i64, bool>> = jvm.to_rust(map)?;
+        assert_eq!(rust_value_from_java, nested);
+
+        let typed = jvm.java_typed_map(CLASS_LONG, CLASS_STRING, vec![(1_i64, "one"), (2_i64, "two")])?;
+        let value: String = jvm.to_rust(jvm.invoke(&typed, "get", &[InvocationArg::try_from(2_i64)?])?)?;
+        assert_eq!(value, "two");
         Ok(())
     }
 
     #[test]
-    fn test_select_timeout() -> errors::Result<()> {
-        let (tx1, rx1) = channel();
-        let ir1 = InstanceReceiver::new(rx1, 0);
-        let (tx2, rx2) = channel();
-        let ir2 = InstanceReceiver::new(rx2, 0);
+    fn test_null_to_rust() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let null_instance = || jvm.invoke_static("java.lang.System", "getProperty", &[InvocationArg::try_from("j4rs.no.such.property")?]);
+        assert!(null_instance()?.is_null(&jvm)?);
+        assert_eq!(jvm.to_rust::<Option<String>>(null_instance()?)?, None);
+        assert_eq!(jvm.to_rust::<Option<i32>>(null_instance()?)?, None);
+        let error = jvm.to_rust::<String>(null_instance()?).unwrap_err();
+        assert!(error.to_string().contains("null"));
+
+        let version = jvm.invoke_static("java.lang.System", "getProperty", &[InvocationArg::try_from("java.version")?])?;
+        assert!(!version.is_null(&jvm)?);
+        assert!(jvm.to_rust::<Option<String>>(version)?.is_some());
+        Ok(())
+    }
 
-        thread::spawn(move || {
-            let _ = tx1.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
-            // Block the thread as sending does not block the current thread
-            thread::sleep(time::Duration::from_millis(10));
-            let _ = tx2.send(Instance::new(ptr::null_mut(), CLASS_STRING).unwrap());
-        });
+    #[test]
+    fn test_equality_and_hashing() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let a = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?])?;
+        let b = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?])?;
+        let a_clone = jvm.clone_instance(&a)?;
+        let null = jvm.invoke_static("java.lang.System", "getProperty", &[InvocationArg::try_from("j4rs.no.such.property")?])?;
+
+        assert!(jvm.equals(&a, &b)?);
+        assert!(!jvm.same_object(&a, &b)?);
+        assert!(jvm.same_object(&a, &a_clone)?);
+        assert!(!jvm.equals(&a, &null)?);
+        assert!(!jvm.equals(&null, &a)?);
+        assert!(jvm.equals(&null, &null)?);
+        assert_eq!(jvm.hash_code(&a)?, jvm.hash_code(&b)?);
+        assert_eq!(jvm.hash_code(&null)?, 0);
+        let expected_hash: i32 = jvm.to_rust(jvm.invoke(&a, "hashCode", InvocationArg::empty())?)?;
+        assert_eq!(jvm.hash_code(&a)?, expected_hash);
+
+        let mut keys = std::collections::HashSet::new();
+        assert!(keys.insert(InstanceKey::new(&jvm, a)?));
+        assert!(keys.insert(InstanceKey::new(&jvm, b)?));
+        assert!(!keys.insert(InstanceKey::new(&jvm, a_clone)?));
+        assert_eq!(keys.len(), 2);
+        Ok(())
+    }
 
-        let d = time::Duration::from_millis(500);
-        let (index1, _) = Jvm::select_timeout(&[&ir1, &ir2], &d)?;
-        let (index2, _) = Jvm::select_timeout(&[&ir1, &ir2], &d)?;
-        assert!(Jvm::select_timeout(&[&ir1, &ir2], &d).is_err());
-        assert_eq!(index1, 0);
-        assert_eq!(index2, 1);
+    #[test]
+    fn test_instance_from_raw_object() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let string = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a string")?])?;
+        let raw = jvm.instance_into_raw_object(string)?;
+        let instance = unsafe { jvm.instance_from_raw_object(raw)? };
+        let upper: String = jvm.to_rust(jvm.invoke(&instance, "toUpperCase", InvocationArg::empty())?)?;
+        assert_eq!(upper, "A STRING");
+
+        let null = unsafe { jvm.instance_from_raw_object(ptr::null_mut())? };
+        assert!(null.is_null(&jvm)?);
+        Ok(())
+    }
 
+    #[test]
+    fn test_java_serialization() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let list = jvm.java_list("java.lang.String", vec!["a", "b"])?;
+        let list = jvm.create_instance("java.util.ArrayList", &[InvocationArg::from(list)])?;
+        let bytes = jvm.serialize_instance(&list)?;
+        let deserialized = jvm.deserialize_instance(&bytes)?;
+        assert!(jvm.equals(&list, &deserialized)?);
+        let strings: Vec<String> = jvm.to_rust(deserialized)?;
+        assert_eq!(strings, vec!["a", "b"]);
+
+        let filtered = jvm.deserialize_instance_with_filter(&bytes, "java.util.ArrayList;java.lang.Object;java.lang.String;!*")?;
+        assert!(jvm.equals(&list, &filtered)?);
+        assert!(jvm.deserialize_instance_with_filter(&bytes, "java.lang.String;!*").is_err());
+
+        let object = jvm.create_instance("java.lang.Object", InvocationArg::empty())?;
+        assert!(jvm.serialize_instance(&object).is_err());
+        assert!(jvm.deserialize_instance(&[1, 2, 3]).is_err());
         Ok(())
     }
 
     #[test]
-    fn test_java_class_creation() -> errors::Result<()> {
-        assert_eq!(JavaClass::Void.get_class_str(), "void");
-        assert_eq!(JavaClass::String.get_class_str(), CLASS_STRING);
-        assert_eq!(JavaClass::Boolean.get_class_str(), CLASS_BOOLEAN);
-        assert_eq!(JavaClass::Byte.get_class_str(), CLASS_BYTE);
-        assert_eq!(JavaClass::Character.get_class_str(), CLASS_CHARACTER);
-        assert_eq!(JavaClass::Short.get_class_str(), CLASS_SHORT);
-        assert_eq!(JavaClass::Integer.get_class_str(), CLASS_INTEGER);
-        assert_eq!(JavaClass::Long.get_class_str(), CLASS_LONG);
-        assert_eq!(JavaClass::Float.get_class_str(), CLASS_FLOAT);
-        assert_eq!(JavaClass::Double.get_class_str(), CLASS_DOUBLE);
-        assert_eq!(JavaClass::List.get_class_str(), CLASS_LIST);
+    fn test_rows() -> errors::Result<()> {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Row {
+            id: String,
+            name: String,
+        }
+
+        let jvm = create_tests_jvm()?;
+        let table = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+        for (id, name) in [("1", "a"), ("2", "b")] {
+            let row = jvm.java_map(JavaClass::String, JavaClass::String, HashMap::from([("id", id), ("name", name)]))?;
+            jvm.invoke(&table, "add", &[InvocationArg::from(row)])?;
+        }
+        let rows: Vec<Row> = jvm.rows(&table)?.collect::<errors::Result<_>>()?;
         assert_eq!(
-            JavaClass::Of("a.java.Class").get_class_str(),
-            "a.java.Class"
+            rows,
+            vec![
+                Row { id: "1".to_string(), name: "a".to_string() },
+                Row { id: "2".to_string(), name: "b".to_string() },
+            ]
         );
-
         Ok(())
     }
 
     #[test]
-    fn test_byte_array_to_rust() -> errors::Result<()> {
+    fn test_classpath_introspection() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<i8> = vec![-3_i8, 7_i8, 8_i8];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_BYTE, &ia)?;
-        let rust_value_from_java: Vec<i8> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let classpath = jvm.effective_classpath()?;
+        let j4rs_jar = jvm
+            .which_jar_provides("org.astonbitecode.j4rs.api.Instance")?
+            .expect("The j4rs jar is not found");
+        assert!(classpath.contains(&j4rs_jar.to_string_lossy().to_string()));
+        assert_eq!(jvm.which_jar_provides("java.lang.String")?, None);
+        assert_eq!(jvm.which_jar_provides("com.example.Missing")?, None);
+        assert!(!jvm.class_loader_name()?.is_empty());
+        Ok(())
+    }
 
+    #[test]
+    fn test_reload_jar() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        // Writes a jar with the J4rsDefinedClass, whose method and returned String are renamed to the five letters `version`.
+        // Both are the same constant of the bytecode.
+        let write_jar = |path: &str, version: &str| -> errors::Result<()> {
+            let mut bytecode = DEFINED_CLASS_BYTECODE.to_vec();
+            let at = bytecode.windows(5).position(|w| w == b"hello").unwrap();
+            bytecode[at..at + 5].copy_from_slice(version.as_bytes());
+            let out = jvm.create_instance("java.io.FileOutputStream", &[InvocationArg::try_from(path)?])?;
+            let jar = jvm.create_instance("java.util.jar.JarOutputStream", &[InvocationArg::from(out)])?;
+            let entry = jvm.create_instance("java.util.jar.JarEntry", &[InvocationArg::try_from("J4rsDefinedClass.class")?])?;
+            jvm.invoke(&jar, "putNextEntry", &[InvocationArg::from(entry)])?;
+            jvm.invoke(&jar, "write", &[InvocationArg::try_from(bytecode.as_slice())?])?;
+            jvm.invoke(&jar, "close", InvocationArg::empty())?;
+            Ok(())
+        };
+        let jar = std::env::temp_dir().join("j4rs_reload_jar_test.jar");
+        let jar = jar.to_str().unwrap();
+
+        write_jar(jar, "first")?;
+        jvm.reload_jar(jar)?;
+        let first = jvm.create_instance("J4rsDefinedClass", InvocationArg::empty())?;
+        let s: String = jvm.to_rust(jvm.invoke(&first, "first", InvocationArg::empty())?)?;
+        assert_eq!(s, "first");
+
+        write_jar(jar, "other")?;
+        jvm.reload_jar(jar)?;
+        let other = jvm.create_instance("J4rsDefinedClass", InvocationArg::empty())?;
+        let s: String = jvm.to_rust(jvm.invoke(&other, "other", InvocationArg::empty())?)?;
+        assert_eq!(s, "other");
+        // The existing instances are not affected
+        assert!(jvm.invoke(&first, "first", InvocationArg::empty()).is_ok());
+
+        let _ = std::fs::remove_file(jar);
+        assert!(jvm.reload_jar("/non/existing/j4rs.jar").is_err());
         Ok(())
     }
 
     #[test]
-    fn test_short_array_to_rust() -> errors::Result<()> {
+    fn test_redirect_std_streams() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<i16> = vec![-3_i16, 7_i16, 10000_i16];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_SHORT, &ia)?;
-        let rust_value_from_java: Vec<i16> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let (tx, rx) = std::sync::mpsc::channel();
+        jvm.redirect_std_streams(move |stream, line| {
+            let _ = tx.send((stream, line));
+        })?;
+        let out = jvm.static_class_field("java.lang.System", "out")?;
+        jvm.invoke(&out, "println", &[InvocationArg::try_from("j4rs std streams test")?])?;
+        let err = jvm.static_class_field("java.lang.System", "err")?;
+        jvm.invoke(&err, "print", &[InvocationArg::try_from("an error\n")?])?;
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let (stream, line) = rx.recv_timeout(time::Duration::from_secs(10)).unwrap();
+            if line == "j4rs std streams test" || line == "an error" {
+                received.push((stream, line));
+            }
+        }
+        assert!(received.contains(&(StdStream::Out, "j4rs std streams test".to_string())));
+        assert!(received.contains(&(StdStream::Err, "an error".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_attach_thread_as_daemon() -> errors::Result<()> {
+        let _jvm = create_tests_jvm()?;
+        let is_daemon = |jvm: &Jvm| -> errors::Result<bool> {
+            let current = jvm.invoke_static("java.lang.Thread", "currentThread", InvocationArg::empty())?;
+            jvm.invoke_to_rust(&current, "isDaemon", InvocationArg::empty())
+        };
+
+        let daemon = thread::spawn(move || -> errors::Result<bool> {
+            let jvm = Jvm::attach_thread_as_daemon()?;
+            is_daemon(&jvm)
+        });
+        assert!(daemon.join().unwrap()?);
+
+        let non_daemon = thread::spawn(move || -> errors::Result<bool> {
+            let jvm = Jvm::attach_thread()?;
+            is_daemon(&jvm)
+        });
+        assert!(!non_daemon.join().unwrap()?);
 
         Ok(())
     }
 
     #[test]
-    fn test_char_array_to_rust() -> errors::Result<()> {
+    fn test_with_local_frame() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<u16> = vec![3_u16, 7_u16, 10000_u16];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_CHAR, &ia)?;
-        let rust_value_from_java: Vec<u16> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let list = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+
+        let size: i32 = jvm.with_local_frame(16, |frame| {
+            for i in 0..1000 {
+                let integer = frame.create_instance("java.lang.Integer", &[InvocationArg::try_from(i)?.into_primitive()?])?;
+                let string = frame.invoke(&integer, "toString", InvocationArg::empty())?;
+                let _ = frame.invoke(&list, "add", &[InvocationArg::from(frame.to_global(&string)?)])?;
+            }
+            let size = frame.invoke(&list, "size", InvocationArg::empty())?;
+            frame.to_rust(&size)
+        })?;
+        assert_eq!(size, 1000);
+
+        let global = jvm.with_local_frame(4, |frame| {
+            let first = frame.invoke(&list, "get", &[InvocationArg::try_from(0)?.into_primitive()?])?;
+            // LocalInstances may be used as Instances by the Jvm
+            let _ = frame.jvm().invoke(&first, "hashCode", InvocationArg::empty())?;
+            frame.to_global(&first)
+        })?;
+        let first: String = jvm.to_rust(global)?;
+        assert_eq!(first, "0");
+
+        let res: errors::Result<()> = jvm.with_local_frame(4, |frame| {
+            frame.create_instance("no.such.Class", InvocationArg::empty()).map(|_| ())
+        });
+        assert!(res.is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_int_array_to_rust() -> errors::Result<()> {
+    fn test_instance_tracking() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<i32> = vec![-100_000, -1_000_000, 1_000_000];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_INT, &ia)?;
-        let rust_value_from_java: Vec<i32> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let class_name = "java.util.concurrent.atomic.AtomicBoolean";
+        let count = |jvm: &Jvm| jvm.instance_stats().get(class_name).cloned().unwrap_or(0);
+
+        jvm.set_instance_tracking(true);
+        let first = jvm.create_instance(class_name, InvocationArg::empty())?;
+        let second = jvm.create_instance(class_name, InvocationArg::empty())?;
+        // The class of Instances that are returned by invocations is retrieved from Java
+        let third = jvm.invoke(&first, "getClass", InvocationArg::empty())?;
+        assert_eq!(count(&jvm), 2);
+        assert_eq!(jvm.instance_stats().get("java.lang.Class"), Some(&1));
+
+        first.close();
+        assert_eq!(count(&jvm), 1);
+        second.forget();
+        assert_eq!(count(&jvm), 0);
+        drop(third);
+        assert_eq!(jvm.instance_stats().get("java.lang.Class"), None);
+
+        jvm.set_instance_tracking(false);
+        let _untracked = jvm.create_instance(class_name, InvocationArg::empty())?;
+        assert!(jvm.instance_stats().is_empty());
 
         Ok(())
     }
 
     #[test]
-    fn test_long_array_to_rust() -> errors::Result<()> {
+    fn test_iterate() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<i64> = vec![-100_000, -1_000_000, 1_000_000];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_LONG, &ia)?;
-        let rust_value_from_java: Vec<i64> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let list = jvm.java_list(JavaClass::String, vec!["a", "b", "c"])?;
+
+        let elements: Vec<String> = jvm.iterate(&list)?
+            .map(|element| element.and_then(|instance| jvm.to_rust(instance)))
+            .collect::<errors::Result<_>>()?;
+        assert_eq!(elements, vec!["a", "b", "c"]);
+
+        let elements: Vec<String> = jvm.iterate_to_rust(&list)?.collect::<errors::Result<_>>()?;
+        assert_eq!(elements, vec!["a", "b", "c"]);
+
+        // Iterator and Stream instances, declared as Object
+        let list = jvm.cast(&list, "java.util.List")?;
+        let iterator = jvm.invoke_static("java.util.Objects", "requireNonNull", &[
+            InvocationArg::from(jvm.invoke(&list, "iterator", InvocationArg::empty())?)])?;
+        assert_eq!(jvm.iterate_to_rust::<String>(&iterator)?.count(), 3);
+        let stream = jvm.invoke(&list, "stream", InvocationArg::empty())?;
+        let elements: Vec<String> = jvm.iterate_to_rust(&stream)?.collect::<errors::Result<_>>()?;
+        assert_eq!(elements, vec!["a", "b", "c"]);
+
+        let ints = jvm.java_list(JavaClass::Integer, vec![1, 2, 3])?;
+        let sum: i32 = jvm.iterate_to_rust::<i32>(&ints)?.map(|i| i.unwrap()).sum();
+        assert_eq!(sum, 6);
+
+        let empty = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+        assert!(jvm.iterate(&empty)?.next().is_none());
+
+        let string = InvocationArg::try_from("a")?;
+        let not_iterable = jvm.create_instance("java.lang.String", &[string])?;
+        assert!(jvm.iterate(&not_iterable).is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_float_array_to_rust() -> errors::Result<()> {
+    fn test_to_rust_stream() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<f32> = vec![3_f32, 7.5_f32, -1000.5_f32];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_FLOAT, &ia)?;
-        let rust_value_from_java: Vec<f32> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let values: Vec<i32> = (0..2500).collect();
+        let list = jvm.java_list(JavaClass::Integer, values.clone())?;
+
+        let streamed: Vec<i32> = jvm.to_rust_stream(&list)?.collect::<errors::Result<_>>()?;
+        assert_eq!(streamed, values);
+        let streamed: Vec<i32> = jvm.to_rust_stream_with_chunk_size(&list, 7)?.collect::<errors::Result<_>>()?;
+        assert_eq!(streamed, values);
+
+        let array = jvm.create_java_array(
+            "int",
+            &[InvocationArg::try_from(1)?.into_primitive()?, InvocationArg::try_from(2)?.into_primitive()?])?;
+        let streamed: Vec<i32> = jvm.to_rust_stream(&array)?.collect::<errors::Result<_>>()?;
+        assert_eq!(streamed, vec![1, 2]);
+
+        let map = jvm.java_map(JavaClass::String, JavaClass::Integer, HashMap::from([("a", 1), ("b", 2)]))?;
+        let mut entries: Vec<(String, i32)> = jvm.to_rust_stream(&map)?.collect::<errors::Result<_>>()?;
+        entries.sort();
+        assert_eq!(entries, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+
+        let empty = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+        assert!(jvm.to_rust_stream::<i32>(&empty)?.next().is_none());
+
+        let mut wrong_type = jvm.to_rust_stream::<String>(&list)?;
+        assert!(wrong_type.next().unwrap().is_err());
+        assert!(wrong_type.next().is_none());
+
+        let string = InvocationArg::try_from("a")?;
+        let not_a_collection = jvm.create_instance("java.lang.String", &[string])?;
+        assert!(jvm.to_rust_stream::<String>(&not_a_collection).is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_double_array_to_rust() -> errors::Result<()> {
+    fn test_runtime_reflection() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<f64> = vec![3_f64, 7.5_f64, -1000.5_f64];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_DOUBLE, &ia)?;
-        let rust_value_from_java: Vec<f64> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let list = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+        let object = jvm.invoke_static("java.util.Objects", "requireNonNull", &[InvocationArg::from(list)])?;
+        assert_eq!(object.class_name(), cache::UNKNOWN_FOR_RUST);
+        let class_name = jvm.get_class_name(&object)?;
+        assert_eq!(class_name, "java.util.ArrayList");
+
+        assert!(jvm.is_instance_of(&object, "java.util.List")?);
+        assert!(jvm.is_instance_of(&object, "java.lang.Object")?);
+        assert!(!jvm.is_instance_of(&object, "java.lang.String")?);
+        assert!(jvm.is_instance_of(&object, "non.existing.Class").is_err());
+
+        let hierarchy = jvm.get_class_hierarchy(&object)?;
+        assert_eq!(hierarchy[0], "java.util.ArrayList");
+        assert!(hierarchy.contains(&"java.util.AbstractList".to_string()));
+        assert!(hierarchy.contains(&"java.lang.Object".to_string()));
+        assert!(hierarchy.contains(&"java.util.Collection".to_string()));
+        assert!(hierarchy.contains(&"java.lang.Iterable".to_string()));
+
+        let null = jvm.invoke_static("java.lang.System", "getProperty", &[InvocationArg::try_from("j4rs.non.existing")?])?;
+        assert!(!jvm.is_instance_of(&null, "java.lang.String")?);
+        assert_eq!(jvm.get_class_name(&null)?, "java.lang.String");
 
         Ok(())
     }
 
     #[test]
-    fn test_boolean_array_to_rust() -> errors::Result<()> {
+    fn test_direct_byte_buffers() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: Vec<bool> = vec![false, true, false];
-        let ia: Vec<_> = rust_value.iter().map(|x| InvocationArg::try_from(x).unwrap().into_primitive().unwrap()).collect();
-        let java_instance = jvm.create_java_array(PRIMITIVE_BOOLEAN, &ia)?;
-        let rust_value_from_java: Vec<bool> = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let bytes: Vec<u8> = vec![1, 2, 3, 250];
+        let byte_buffer = unsafe { jvm.byte_buffer_from_slice(&bytes)? };
+        let read_only: bool = jvm.to_rust(jvm.invoke(&byte_buffer, "isReadOnly", InvocationArg::empty())?)?;
+        assert!(read_only);
+        let b: i8 = jvm.to_rust(jvm.invoke(&byte_buffer, "get", &[InvocationArg::try_from(3)?.into_primitive()?])?)?;
+        assert_eq!(b as u8, 250);
+
+        let slice = unsafe { jvm.slice_from_byte_buffer(&byte_buffer)? };
+        assert_eq!(slice, bytes.as_slice());
+        assert_eq!(slice.as_ptr(), bytes.as_ptr());
+        drop(byte_buffer);
+
+        let direct = jvm.invoke_static("java.nio.ByteBuffer", "allocateDirect", &[InvocationArg::try_from(3)?.into_primitive()?])?;
+        let _ = jvm.invoke(&direct, "put", &[InvocationArg::try_from(7_i8)?.into_primitive()?])?;
+        assert_eq!(unsafe { jvm.slice_from_byte_buffer(&direct)? }, &[7, 0, 0]);
+
+        let heap = jvm.invoke_static("java.nio.ByteBuffer", "allocate", &[InvocationArg::try_from(3)?.into_primitive()?])?;
+        assert!(unsafe { jvm.slice_from_byte_buffer(&heap) }.is_err());
 
         Ok(())
     }
 
+    // The bytecode of `public class J4rsDefinedClass { public String hello() { return "hello"; } }`
+    const DEFINED_CLASS_BYTECODE: &[u8] = &[
+        0xca, 0xfe, 0xba, 0xbe, 0x00, 0x00, 0x00, 0x34, 0x00, 0x0d, 0x0a, 0x00, 0x02, 0x00, 0x03, 0x07,
+        0x00, 0x04, 0x0c, 0x00, 0x05, 0x00, 0x06, 0x01, 0x00, 0x10, 0x6a, 0x61, 0x76, 0x61, 0x2f, 0x6c,
+        0x61, 0x6e, 0x67, 0x2f, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x01, 0x00, 0x06, 0x3c, 0x69, 0x6e,
+        0x69, 0x74, 0x3e, 0x01, 0x00, 0x03, 0x28, 0x29, 0x56, 0x08, 0x00, 0x08, 0x01, 0x00, 0x05, 0x68,
+        0x65, 0x6c, 0x6c, 0x6f, 0x07, 0x00, 0x0a, 0x01, 0x00, 0x10, 0x4a, 0x34, 0x72, 0x73, 0x44, 0x65,
+        0x66, 0x69, 0x6e, 0x65, 0x64, 0x43, 0x6c, 0x61, 0x73, 0x73, 0x01, 0x00, 0x04, 0x43, 0x6f, 0x64,
+        0x65, 0x01, 0x00, 0x14, 0x28, 0x29, 0x4c, 0x6a, 0x61, 0x76, 0x61, 0x2f, 0x6c, 0x61, 0x6e, 0x67,
+        0x2f, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x3b, 0x00, 0x21, 0x00, 0x09, 0x00, 0x02, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x00, 0x05, 0x00, 0x06, 0x00, 0x01, 0x00, 0x0b, 0x00, 0x00,
+        0x00, 0x11, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x2a, 0xb7, 0x00, 0x01, 0xb1, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08, 0x00, 0x0c, 0x00, 0x01, 0x00, 0x0b, 0x00, 0x00, 0x00,
+        0x0f, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x12, 0x07, 0xb0, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+
     #[test]
-    fn test_int_to_rust() -> errors::Result<()> {
+    fn test_read_resource() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: i32 = 3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_INTEGER, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "intValue", InvocationArg::empty())?;
-        let rust_value_from_java: i32 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: i32 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let bytes = jvm.read_resource("java/lang/String.class")?;
+        assert_eq!(bytes[..4], [0xca, 0xfe, 0xba, 0xbe]);
+        assert!(jvm.read_resource("/non/existing/resource.txt").is_err());
+        Ok(())
+    }
 
+    #[test]
+    fn test_compile_and_load() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let base = "package j4rs.gen; public class Base { public String name() { return \"base\"; } }";
+        let greeter = r#"
+            package j4rs.gen;
+            import java.util.function.Supplier;
+            public class Greeter extends Base {
+                public String greet(String who) {
+                    Supplier<String> greeting = () -> "Hello " + who + " from " + name();
+                    return greeting.get();
+                }
+            }"#;
+        jvm.compile_and_load(&[("j4rs.gen.Greeter", greeter), ("j4rs.gen.Base", base)])?;
+        let instance = jvm.create_instance("j4rs.gen.Greeter", InvocationArg::empty())?;
+        let greeting: String = jvm.to_rust(jvm.invoke(&instance, "greet", &[InvocationArg::try_from("j4rs")?])?)?;
+        assert_eq!(greeting, "Hello j4rs from base");
+
+        let invalid = "package j4rs.gen; public class Invalid { int x = \"not an int\"; }";
+        match jvm.compile_and_load(&[("j4rs.gen.Invalid", invalid)]) {
+            Err(J4RsError::JavaException(exception)) => {
+                assert!(exception.message().unwrap_or_default().contains("Invalid.java:1"));
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("The invalid source was compiled"),
+        }
         Ok(())
     }
 
     #[test]
-    fn test_byte_to_rust() -> errors::Result<()> {
+    fn test_define_class_and_add_classpath_entry() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: i8 = 3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_BYTE, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "byteValue", InvocationArg::empty())?;
-        let rust_value_from_java: i8 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: i8 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        jvm.define_class("J4rsDefinedClass", DEFINED_CLASS_BYTECODE)?;
+        let instance = jvm.create_instance("J4rsDefinedClass", InvocationArg::empty())?;
+        let hello: String = jvm.to_rust(jvm.invoke(&instance, "hello", InvocationArg::empty())?)?;
+        assert_eq!(hello, "hello");
+
+        let dir = std::env::temp_dir().join("j4rs_classpath_entry_test");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("j4rs_classpath_entry_test.txt"), "test")?;
+        jvm.add_classpath_entry(dir.to_str().unwrap())?;
+        let resource = jvm.invoke_static(
+            "java.lang.ClassLoader",
+            "getSystemResource",
+            &[InvocationArg::try_from("j4rs_classpath_entry_test.txt")?],
+        )?;
+        let found: bool = jvm.to_rust(jvm.invoke_static(
+            "java.util.Objects",
+            "nonNull",
+            &[InvocationArg::from(resource)],
+        )?)?;
+        assert!(found);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(jvm.add_classpath_entry("/non/existing/j4rs/path").is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_short_to_rust() -> errors::Result<()> {
+    fn test_optional() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: i16 = 3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_SHORT, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "shortValue", InvocationArg::empty())?;
-        let rust_value_from_java: i16 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: i16 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let present = jvm.invoke_static("java.util.Optional", "ofNullable", &[InvocationArg::try_from("value")?])?;
+        let present: Option<String> = jvm.to_rust(present)?;
+        assert_eq!(present, Some("value".to_string()));
+        let empty = jvm.invoke_static("java.util.Optional", "empty", InvocationArg::empty())?;
+        let empty: Option<String> = jvm.to_rust(empty)?;
+        assert_eq!(empty, None);
+        let int = jvm.invoke_static("java.util.OptionalInt", "of", &[InvocationArg::try_from(3)?.into_primitive()?])?;
+        let int: Option<i32> = jvm.to_rust(int)?;
+        assert_eq!(int, Some(3));
+
+        let optional = jvm.create_instance(
+            "java.util.concurrent.atomic.AtomicReference",
+            &[InvocationArg::try_from(Some(33))?],
+        )?;
+        let value: Option<i32> = jvm.to_rust(jvm.invoke(&optional, "get", InvocationArg::empty())?)?;
+        assert_eq!(value, Some(33));
+        let optional = jvm.create_instance(
+            "java.util.concurrent.atomic.AtomicReference",
+            &[InvocationArg::try_from(None::<&str>)?],
+        )?;
+        let value = jvm.cast(&jvm.invoke(&optional, "get", InvocationArg::empty())?, "java.util.Optional")?;
+        let is_present: bool = jvm.to_rust(jvm.invoke(&value, "isPresent", InvocationArg::empty())?)?;
+        assert!(!is_present);
+
+        let nullable = InvocationArg::new_nullable(None::<String>, "java.lang.String")?;
+        let is_null: bool = jvm.to_rust(jvm.invoke_static("java.util.Objects", "isNull", &[nullable])?)?;
+        assert!(is_null);
+        let nullable = InvocationArg::new_nullable(Some("value"), "java.lang.String")?;
+        let is_null: bool = jvm.to_rust(jvm.invoke_static("java.util.Objects", "isNull", &[nullable])?)?;
+        assert!(!is_null);
 
         Ok(())
     }
 
     #[test]
-    fn test_char_to_rust() -> errors::Result<()> {
+    fn test_java_time() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: u16 = 3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_CHARACTER, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "charValue", InvocationArg::empty())?;
-        let rust_value_from_java: u16 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: u16 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let system_time = time::UNIX_EPOCH + time::Duration::new(1_700_000_000, 123_456_789);
+        let instant = jvm.invoke_static(
+            "java.time.Instant",
+            "from",
+            &[InvocationArg::try_from(system_time)?],
+        )?;
+        let instant_string: String = jvm.to_rust(jvm.invoke(&instant, "toString", InvocationArg::empty())?)?;
+        assert_eq!(instant_string, "2023-11-14T22:13:20.123456789Z");
+        let back: time::SystemTime = jvm.to_rust(instant)?;
+        assert_eq!(back, system_time);
+
+        let before_epoch = time::UNIX_EPOCH - time::Duration::from_millis(1500);
+        let instant = jvm.invoke_static("java.time.Instant", "from", &[InvocationArg::try_from(before_epoch)?])?;
+        let millis: i64 = jvm.to_rust(jvm.invoke(&instant, "toEpochMilli", InvocationArg::empty())?)?;
+        assert_eq!(millis, -1500);
+        let back: time::SystemTime = jvm.to_rust(instant)?;
+        assert_eq!(back, before_epoch);
+
+        let duration = time::Duration::new(90, 5);
+        let java_duration = jvm.invoke_static(
+            "java.time.Duration",
+            "from",
+            &[InvocationArg::try_from(duration)?],
+        )?;
+        let duration_string: String = jvm.to_rust(jvm.invoke(&java_duration, "toString", InvocationArg::empty())?)?;
+        assert_eq!(duration_string, "PT1M30.000000005S");
+        let back: time::Duration = jvm.to_rust(java_duration)?;
+        assert_eq!(back, duration);
+
+        let negative = jvm.invoke_static(
+            "java.time.Duration",
+            "ofSeconds",
+            &[InvocationArg::try_from(-1_i64)?.into_primitive()?],
+        )?;
+        assert!(jvm.to_rust::<time::Duration>(negative).is_err());
 
         Ok(())
     }
 
+    #[cfg(feature = "chrono")]
     #[test]
-    fn test_long_to_rust() -> errors::Result<()> {
+    fn test_java_time_chrono() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: i64 = 3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_LONG, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "longValue", InvocationArg::empty())?;
-        let rust_value_from_java: i64 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: i64 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let date_time = chrono::DateTime::from_timestamp(1_700_000_000, 123_456_789).unwrap();
+        let instant = jvm.invoke_static("java.time.Instant", "from", &[InvocationArg::try_from(date_time)?])?;
+        let back: chrono::DateTime<chrono::Utc> = jvm.to_rust(instant)?;
+        assert_eq!(back, date_time);
+
+        let naive = date_time.naive_utc();
+        let local_date_time = InvocationArg::try_from(naive)?.instance()?;
+        let local_string: String = jvm.to_rust(jvm.invoke(&local_date_time, "toString", InvocationArg::empty())?)?;
+        assert_eq!(local_string, "2023-11-14T22:13:20.123456789");
+        let back: chrono::NaiveDateTime = jvm.to_rust(local_date_time)?;
+        assert_eq!(back, naive);
+
+        let delta = chrono::TimeDelta::milliseconds(-1500);
+        let java_duration = jvm.invoke_static("java.time.Duration", "from", &[InvocationArg::try_from(delta)?])?;
+        let back: chrono::TimeDelta = jvm.to_rust(java_duration)?;
+        assert_eq!(back, delta);
 
         Ok(())
     }
 
+    #[cfg(feature = "uuid")]
     #[test]
-    fn test_float_to_rust() -> errors::Result<()> {
+    fn test_uuid() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: f32 = 3.3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_FLOAT, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "floatValue", InvocationArg::empty())?;
-        let rust_value_from_java: f32 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: f32 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        let uuid = uuid::Uuid::parse_str("f81d4fae-7dec-11d0-a765-00a0c91e6bf6").unwrap();
+        let java_uuid = InvocationArg::try_from(uuid)?.instance()?;
+        let uuid_string: String = jvm.to_rust(jvm.invoke(&java_uuid, "toString", InvocationArg::empty())?)?;
+        assert_eq!(uuid_string, "f81d4fae-7dec-11d0-a765-00a0c91e6bf6");
+        let back: uuid::Uuid = jvm.to_rust(java_uuid)?;
+        assert_eq!(back, uuid);
+
+        let random = jvm.invoke_static(CLASS_UUID, "randomUUID", InvocationArg::empty())?;
+        let random_string: String = jvm.to_rust(jvm.invoke(&random, "toString", InvocationArg::empty())?)?;
+        let back: uuid::Uuid = jvm.to_rust(random)?;
+        assert_eq!(back.to_string(), random_string);
 
         Ok(())
     }
 
     #[test]
-    fn test_double_to_rust() -> errors::Result<()> {
+    fn test_class_scopes() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
-        let rust_value: f64 = 3.3;
-        let ia = InvocationArg::try_from(rust_value)?.into_primitive()?;
-        let java_instance = jvm.create_instance(CLASS_DOUBLE, &[ia])?;
-        let java_primitive_instance = jvm.invoke(&java_instance, "doubleValue", InvocationArg::empty())?;
-        let rust_value_from_java: f64 = jvm.to_rust(java_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
-        let rust_value_from_java: f64 = jvm.to_rust(java_primitive_instance)?;
-        assert_eq!(rust_value_from_java, rust_value);
+        // The bytecode of `public class J4rsScopedClass { public String hello() { return "scoped"; } }`
+        let bytecode: &[u8] = &[
+            0xca, 0xfe, 0xba, 0xbe, 0x00, 0x00, 0x00, 0x34, 0x00, 0x0e, 0x0a, 0x00, 0x02, 0x00, 0x03, 0x07,
+            0x00, 0x04, 0x0c, 0x00, 0x05, 0x00, 0x06, 0x01, 0x00, 0x10, 0x6a, 0x61, 0x76, 0x61, 0x2f, 0x6c,
+            0x61, 0x6e, 0x67, 0x2f, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x01, 0x00, 0x06, 0x3c, 0x69, 0x6e,
+            0x69, 0x74, 0x3e, 0x01, 0x00, 0x03, 0x28, 0x29, 0x56, 0x08, 0x00, 0x08, 0x01, 0x00, 0x06, 0x73,
+            0x63, 0x6f, 0x70, 0x65, 0x64, 0x07, 0x00, 0x0a, 0x01, 0x00, 0x0f, 0x4a, 0x34, 0x72, 0x73, 0x53,
+            0x63, 0x6f, 0x70, 0x65, 0x64, 0x43, 0x6c, 0x61, 0x73, 0x73, 0x01, 0x00, 0x04, 0x43, 0x6f, 0x64,
+            0x65, 0x01, 0x00, 0x05, 0x68, 0x65, 0x6c, 0x6c, 0x6f, 0x01, 0x00, 0x14, 0x28, 0x29, 0x4c, 0x6a,
+            0x61, 0x76, 0x61, 0x2f, 0x6c, 0x61, 0x6e, 0x67, 0x2f, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x3b,
+            0x00, 0x21, 0x00, 0x09, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x00, 0x05,
+            0x00, 0x06, 0x00, 0x01, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x11, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x05, 0x2a, 0xb7, 0x00, 0x01, 0xb1, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0c, 0x00,
+            0x0d, 0x00, 0x01, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x0f, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x03, 0x12, 0x07, 0xb0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let dir1 = std::env::temp_dir().join("j4rs_class_scope_test_1");
+        let dir2 = std::env::temp_dir().join("j4rs_class_scope_test_2");
+        for dir in [&dir1, &dir2] {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(dir.join("J4rsScopedClass.class"), bytecode)?;
+        }
 
+        let scope1 = jvm.new_class_scope(&[dir1.to_str().unwrap()])?;
+        let scope2 = jvm.new_class_scope(&[dir2.to_str().unwrap()])?;
+        let instance1 = scope1.create_instance("J4rsScopedClass", InvocationArg::empty())?;
+        let instance2 = scope2.create_instance("J4rsScopedClass", InvocationArg::empty())?;
+        let hello: String = jvm.to_rust(jvm.invoke(&instance1, "hello", InvocationArg::empty())?)?;
+        assert_eq!(hello, "scoped");
+
+        // Each scope loads its own class
+        let class1 = jvm.invoke(&instance1, "getClass", InvocationArg::empty())?;
+        let class2 = jvm.invoke(&instance2, "getClass", InvocationArg::empty())?;
+        assert!(!jvm.check_equals(&class1, InvocationArg::from(class2))?);
+        // The classes of j4rs and the JDK are shared
+        let s = scope1.create_instance("java.lang.String", &[InvocationArg::try_from("shared")?])?;
+        let s: String = jvm.to_rust(s)?;
+        assert_eq!(s, "shared");
+        let _ = scope1.static_class("org.astonbitecode.j4rs.tests.MyTest")?;
+
+        // The scoped classes are not available outside the scopes
+        assert!(jvm.create_instance("J4rsScopedClass", InvocationArg::empty()).is_err());
+        assert!(jvm.new_class_scope(&["/non/existing/j4rs/path"]).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir1);
+        let _ = std::fs::remove_dir_all(&dir2);
         Ok(())
     }
 
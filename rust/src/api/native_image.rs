@@ -0,0 +1,170 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::Path;
+use std::ptr;
+
+use libloading::Library;
+
+use crate::api::remote::{RemoteJvm, Transport};
+use crate::errors;
+use crate::errors::J4RsError;
+use crate::logger::{debug, warn};
+
+type GraalCreateIsolate = unsafe extern "C" fn(*mut c_void, *mut *mut c_void, *mut *mut c_void) -> c_int;
+type GraalGetCurrentThread = unsafe extern "C" fn(*mut c_void) -> *mut c_void;
+type GraalAttachThread = unsafe extern "C" fn(*mut c_void, *mut *mut c_void) -> c_int;
+type GraalDetachThread = unsafe extern "C" fn(*mut c_void) -> c_int;
+type GraalTearDownIsolate = unsafe extern "C" fn(*mut c_void) -> c_int;
+type J4rsRequest = unsafe extern "C" fn(*mut c_void, *const c_char) -> *mut c_char;
+type J4rsFree = unsafe extern "C" fn(*mut c_void, *mut c_char);
+
+impl RemoteJvm {
+    /// Creates a `RemoteJvm` that runs in an isolate of a GraalVM native image shared library, instead of a JVM.
+    ///
+    /// The library needs to be built with the `native-image` maven profile of j4rs, which adds the C entry points
+    /// `j4rs_request` and `j4rs_free`, along with the reflection configuration of the classes that are used.
+    /// The isolate is torn down when the `RemoteJvm` and all its `RemoteInstance`s are dropped.
+    pub fn native_image<P: AsRef<Path>>(library_path: P) -> errors::Result<RemoteJvm> {
+        let library_path = library_path.as_ref();
+        debug(&format!("Loading the native image {:?}", library_path));
+        let library = unsafe { Library::new(library_path) }.map_err(|error| {
+            J4RsError::GeneralError(format!("Could not load the native image {:?}: {}", library_path, error))
+        })?;
+        let transport = NativeImageTransport::new(library)?;
+        RemoteJvm::with_transport(Box::new(transport))
+    }
+}
+
+// An isolate of a native image, to which the requests are passed through its C entry points
+struct NativeImageTransport {
+    isolate: *mut c_void,
+    get_current_thread: GraalGetCurrentThread,
+    attach_thread: GraalAttachThread,
+    detach_thread: GraalDetachThread,
+    tear_down_isolate: GraalTearDownIsolate,
+    request: J4rsRequest,
+    free: J4rsFree,
+    // Declared last, so that it is dropped after the isolate is torn down
+    _library: Library,
+}
+
+// The isolate can be used by any thread that is attached to it
+unsafe impl Send for NativeImageTransport {}
+
+impl NativeImageTransport {
+    fn new(library: Library) -> errors::Result<NativeImageTransport> {
+        unsafe {
+            let create_isolate: GraalCreateIsolate = symbol(&library, b"graal_create_isolate\0")?;
+            let mut isolate = ptr::null_mut();
+            let mut thread = ptr::null_mut();
+            if create_isolate(ptr::null_mut(), &mut isolate, &mut thread) != 0 {
+                return Err(J4RsError::GeneralError("Could not create the native image isolate".to_string()));
+            }
+            let transport = NativeImageTransport {
+                isolate,
+                get_current_thread: symbol(&library, b"graal_get_current_thread\0")?,
+                attach_thread: symbol(&library, b"graal_attach_thread\0")?,
+                detach_thread: symbol(&library, b"graal_detach_thread\0")?,
+                tear_down_isolate: symbol(&library, b"graal_tear_down_isolate\0")?,
+                request: symbol(&library, b"j4rs_request\0")?,
+                free: symbol(&library, b"j4rs_free\0")?,
+                _library: library,
+            };
+            // The creating thread is attached to the isolate; the invocations attach their threads as needed
+            (transport.detach_thread)(thread);
+            Ok(transport)
+        }
+    }
+
+    // Calls `f` with the isolate thread of the current thread, attaching it to the isolate if needed
+    fn with_thread<T>(&self, f: impl FnOnce(*mut c_void) -> T) -> errors::Result<T> {
+        unsafe {
+            let current = (self.get_current_thread)(self.isolate);
+            if !current.is_null() {
+                return Ok(f(current));
+            }
+            let mut thread = ptr::null_mut();
+            if (self.attach_thread)(self.isolate, &mut thread) != 0 {
+                return Err(J4RsError::GeneralError(
+                    "Could not attach the current thread to the native image isolate".to_string(),
+                ));
+            }
+            let result = f(thread);
+            (self.detach_thread)(thread);
+            Ok(result)
+        }
+    }
+}
+
+impl Transport for NativeImageTransport {
+    fn exchange(&mut self, request: &str, _answered: bool) -> errors::Result<Option<String>> {
+        let request = CString::new(request)?;
+        self.with_thread(|thread| unsafe {
+            let response = (self.request)(thread, request.as_ptr());
+            if response.is_null() {
+                None
+            } else {
+                let response_string = CStr::from_ptr(response).to_string_lossy().into_owned();
+                (self.free)(thread, response);
+                Some(response_string)
+            }
+        })
+    }
+
+    fn is_alive(&mut self) -> bool {
+        true
+    }
+}
+
+impl Drop for NativeImageTransport {
+    fn drop(&mut self) {
+        debug("Tearing down the native image isolate");
+        let torn_down = unsafe {
+            let mut thread = (self.get_current_thread)(self.isolate);
+            if thread.is_null() && (self.attach_thread)(self.isolate, &mut thread) != 0 {
+                -1
+            } else {
+                // Tearing down detaches the current thread as well
+                (self.tear_down_isolate)(thread)
+            }
+        };
+        if torn_down != 0 {
+            warn("Could not tear down the native image isolate");
+        }
+    }
+}
+
+unsafe fn symbol<T: Copy>(library: &Library, name: &[u8]) -> errors::Result<T> {
+    library.get::<T>(name).map(|symbol| *symbol).map_err(|error| {
+        J4RsError::GeneralError(format!(
+            "The native image does not export {}: {}",
+            String::from_utf8_lossy(&name[..name.len() - 1]),
+            error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod native_image_unit_tests {
+    use super::*;
+
+    #[test]
+    fn missing_native_image() {
+        assert!(RemoteJvm::native_image("/not/a/native/image.so").is_err());
+    }
+}
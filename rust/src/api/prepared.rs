@@ -0,0 +1,106 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::borrow::Borrow;
+
+use jni_sys::jobject;
+use serde::de::DeserializeOwned;
+
+use crate::errors::opt_to_res;
+use crate::logger::debug;
+use crate::{cache, errors, jni_utils, Instance, InvocationArg, Jvm};
+
+/// A method invocation that is resolved once in Java and can be called repeatedly.
+///
+/// Calling a `PreparedInvocation` avoids searching for the method to invoke by its name and the types of the arguments,
+/// which `Jvm::invoke` does on every call.
+/// It is created by calling `Jvm::prepare_invocation` or `Jvm::prepare_static_invocation`.
+pub struct PreparedInvocation<'a> {
+    jvm: &'a Jvm,
+    method_name: String,
+    /// A global reference of the `org.astonbitecode.j4rs.api.invocation.PreparedInvocation` Java object
+    jinvocation: jobject,
+}
+
+impl<'a> PreparedInvocation<'a> {
+    pub(crate) fn new(jvm: &'a Jvm, method_name: &str, jinvocation: jobject) -> PreparedInvocation<'a> {
+        PreparedInvocation {
+            jvm,
+            method_name: method_name.to_string(),
+            jinvocation,
+        }
+    }
+
+    /// Invokes the prepared method, passing an array of `InvocationArg`s. It returns an `Instance` as the result of the invocation.
+    pub fn call(&self, inv_args: &[impl Borrow<InvocationArg>]) -> errors::Result<Instance> {
+        unsafe {
+            let java_instance = self.do_call(inv_args)?;
+            let java_instance_global_instance =
+                jni_utils::create_global_ref_from_local_ref(java_instance, self.jvm.jni_env)?;
+            Jvm::do_return(
+                self.jvm.jni_env,
+                Instance::new(java_instance_global_instance, cache::UNKNOWN_FOR_RUST)?,
+            )
+        }
+    }
+
+    /// Invokes the prepared method, passing an array of `InvocationArg`s and
+    /// returns the Rust representation of the result of the invocation.
+    pub fn call_to_rust<T>(&self, inv_args: &[impl Borrow<InvocationArg>]) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        unsafe {
+            let java_instance = self.do_call(inv_args)?;
+            let result = self.jvm.jobject_to_rust_boxed(java_instance).map(|v| *v);
+            // Prevent memory leaks from the created local reference
+            jni_utils::delete_java_local_ref(self.jvm.jni_env, java_instance);
+            result
+        }
+    }
+
+    /// Invokes the prepared method and returns the local reference of the result.
+    unsafe fn do_call(&self, inv_args: &[impl Borrow<InvocationArg>]) -> errors::Result<jobject> {
+        debug(&format!(
+            "Calling the prepared method {} using {} arguments",
+            self.method_name,
+            inv_args.len()
+        ));
+        let (array_ptr, inv_arg_jobjects) = self.jvm.invocation_args_array(inv_args)?;
+
+        let java_instance = (opt_to_res(cache::get_jni_call_object_method())?)(
+            self.jvm.jni_env,
+            self.jinvocation,
+            cache::get_prepared_invocation_invoke_method()?,
+            array_ptr,
+        );
+        // Check for exceptions before deleting any refs
+        let result = Jvm::do_return(self.jvm.jni_env, java_instance);
+
+        // Prevent memory leaks from the created references
+        self.jvm.delete_invocation_args_array(array_ptr, inv_arg_jobjects);
+
+        result
+    }
+}
+
+impl<'a> Drop for PreparedInvocation<'a> {
+    fn drop(&mut self) {
+        debug(&format!("Dropping the prepared invocation of {}", self.method_name));
+        if let Some(j_env) = cache::get_thread_local_env_opt() {
+            jni_utils::delete_java_ref(j_env, self.jinvocation);
+        }
+    }
+}
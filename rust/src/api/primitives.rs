@@ -0,0 +1,145 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jni_sys::{jboolean, jmethodID, jobject, jvalue, JNIEnv, JNI_FALSE, JNI_TRUE};
+
+/// A Rust type that corresponds to a Java primitive type and can be returned by `Jvm::invoke_primitive`.
+pub trait JavaPrimitive: Sized {
+    /// The JNI type signature of the Java primitive (e.g. `I` for `int`).
+    #[doc(hidden)]
+    const SIGNATURE: &'static str;
+
+    /// Calls the JNI `Call<Type>MethodA` function that returns this primitive.
+    ///
+    /// # Safety
+    ///
+    /// The `method_id` should belong to the class of `object` and return this primitive.
+    #[doc(hidden)]
+    unsafe fn call_method(
+        jni_env: *mut JNIEnv,
+        object: jobject,
+        method_id: jmethodID,
+        args: *const jvalue,
+    ) -> Self;
+}
+
+macro_rules! java_primitive {
+    ($rust_type:ty, $signature:expr, $jni_function:ident, $to_rust:expr) => {
+        impl JavaPrimitive for $rust_type {
+            const SIGNATURE: &'static str = $signature;
+
+            unsafe fn call_method(
+                jni_env: *mut JNIEnv,
+                object: jobject,
+                method_id: jmethodID,
+                args: *const jvalue,
+            ) -> Self {
+                let result = ((**jni_env).v1_6.$jni_function)(jni_env, object, method_id, args);
+                $to_rust(result)
+            }
+        }
+    };
+}
+
+java_primitive!(bool, "Z", CallBooleanMethodA, |b: jboolean| b == JNI_TRUE);
+java_primitive!(i8, "B", CallByteMethodA, |b| b);
+java_primitive!(u16, "C", CallCharMethodA, |c| c);
+java_primitive!(i16, "S", CallShortMethodA, |s| s);
+java_primitive!(i32, "I", CallIntMethodA, |i| i);
+java_primitive!(i64, "J", CallLongMethodA, |j| j);
+java_primitive!(f32, "F", CallFloatMethodA, |f| f);
+java_primitive!(f64, "D", CallDoubleMethodA, |d| d);
+
+/// An argument of a method that is called with `Jvm::invoke_primitive`.
+///
+/// The arguments are passed to Java as they are, without creating any Java objects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimitiveArg {
+    Boolean(bool),
+    Byte(i8),
+    Char(u16),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+}
+
+impl PrimitiveArg {
+    /// The JNI type signature of the argument.
+    pub(crate) fn signature(&self) -> &'static str {
+        match self {
+            PrimitiveArg::Boolean(_) => bool::SIGNATURE,
+            PrimitiveArg::Byte(_) => i8::SIGNATURE,
+            PrimitiveArg::Char(_) => u16::SIGNATURE,
+            PrimitiveArg::Short(_) => i16::SIGNATURE,
+            PrimitiveArg::Int(_) => i32::SIGNATURE,
+            PrimitiveArg::Long(_) => i64::SIGNATURE,
+            PrimitiveArg::Float(_) => f32::SIGNATURE,
+            PrimitiveArg::Double(_) => f64::SIGNATURE,
+        }
+    }
+
+    pub(crate) fn as_jvalue(&self) -> jvalue {
+        match *self {
+            PrimitiveArg::Boolean(z) => jvalue { z: if z { JNI_TRUE } else { JNI_FALSE } },
+            PrimitiveArg::Byte(b) => jvalue { b },
+            PrimitiveArg::Char(c) => jvalue { c },
+            PrimitiveArg::Short(s) => jvalue { s },
+            PrimitiveArg::Int(i) => jvalue { i },
+            PrimitiveArg::Long(j) => jvalue { j },
+            PrimitiveArg::Float(f) => jvalue { f },
+            PrimitiveArg::Double(d) => jvalue { d },
+        }
+    }
+}
+
+/// Creates the JNI method signature for the provided arguments and return type.
+pub(crate) fn method_signature<T: JavaPrimitive>(args: &[PrimitiveArg]) -> String {
+    let params: String = args.iter().map(|arg| arg.signature()).collect();
+    format!("({}){}", params, T::SIGNATURE)
+}
+
+macro_rules! primitive_arg_from {
+    ($rust_type:ty, $variant:ident) => {
+        impl From<$rust_type> for PrimitiveArg {
+            fn from(value: $rust_type) -> Self {
+                PrimitiveArg::$variant(value)
+            }
+        }
+    };
+}
+
+primitive_arg_from!(bool, Boolean);
+primitive_arg_from!(i8, Byte);
+primitive_arg_from!(u16, Char);
+primitive_arg_from!(i16, Short);
+primitive_arg_from!(i32, Int);
+primitive_arg_from!(i64, Long);
+primitive_arg_from!(f32, Float);
+primitive_arg_from!(f64, Double);
+
+#[cfg(test)]
+mod primitives_unit_tests {
+    use super::*;
+
+    #[test]
+    fn method_signature_of_args() {
+        assert_eq!(method_signature::<i32>(&[]), "()I");
+        assert_eq!(
+            method_signature::<bool>(&[1_i64.into(), 2.0_f64.into(), true.into(), ('a' as u16).into()]),
+            "(JDZC)Z"
+        );
+    }
+}
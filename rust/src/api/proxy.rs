@@ -0,0 +1,210 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use jni_sys::{jlong, jobject, jobjectArray, jstring, JNIEnv};
+
+use crate::api::{Jvm, CLASS_NATIVE_INVOCATION_HANDLER};
+use crate::logger::{debug, error};
+use crate::{errors, jni_utils, Instance, InvocationArg};
+
+/// The type-erased handler that is called by the `NativeInvocationHandler` of a Java proxy.
+///
+/// The handler is created by the code that calls `Jvm::new_proxy`, so the invocation is always handled
+/// by that code, no matter which copy of the j4rs library receives the native call from Java.
+pub(crate) type ProxyHandler =
+    Box<dyn Fn(*mut JNIEnv, jstring, jobjectArray) -> jobject + Send + Sync>;
+
+impl Jvm {
+    /// Creates an `Instance` of a `java.lang.reflect.Proxy` that implements the Java interface `interface_name`.
+    ///
+    /// Each call of a method of the interface is routed to the `handler`, along with the method name and the invocation arguments.
+    /// The `InvocationArg` returned by the `handler` is the result of the call. `None` is translated to a `null` result.
+    ///
+    /// The `handler` is dropped when the proxy is garbage collected in the Java world. The drop happens in a thread of the JVM
+    /// (`j4rs-cleaner`), so the `handler` should not capture anything that needs to be dropped in a specific thread.
+    /// The Java references of any captured `Instance`s may be released later, by another thread that uses j4rs.
+    pub fn new_proxy<F>(&self, interface_name: &str, handler: F) -> errors::Result<Instance>
+    where
+        F: Fn(&Jvm, &str, Vec<Instance>) -> errors::Result<Option<InvocationArg>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        debug(&format!("Creating proxy for interface {}", interface_name));
+        let proxy_handler: ProxyHandler = Box::new(move |jni_env, method_name, args| unsafe {
+            handle_proxy_invocation(jni_env, method_name, args, &handler)
+        });
+        let address = Box::into_raw(Box::new(proxy_handler)) as jlong;
+
+        let proxy = self.invoke_static(
+            CLASS_NATIVE_INVOCATION_HANDLER,
+            "newProxy",
+            &[
+                InvocationArg::try_from(interface_name)?,
+                InvocationArg::try_from(address)?.into_primitive()?,
+            ],
+        )?;
+        self.cast(&proxy, interface_name)
+    }
+}
+
+unsafe fn handle_proxy_invocation<F>(
+    jni_env: *mut JNIEnv,
+    method_name: jstring,
+    args: jobjectArray,
+    handler: &F,
+) -> jobject
+where
+    F: Fn(&Jvm, &str, Vec<Instance>) -> errors::Result<Option<InvocationArg>>,
+{
+    // The Jvm is dropped after any exception is thrown, because dropping the last Jvm of a thread
+    // resets the environment of the thread, which is needed for throwing
+    let jvm = Jvm::try_from(jni_env).map(|mut jvm| {
+        jvm.detach_thread_on_drop(false);
+        jvm
+    });
+    // Panics must not unwind into the JVM, which called this function
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| match &jvm {
+        Ok(jvm) => invoke_handler(jvm, jni_env, method_name, args, handler),
+        Err(error) => Err(error.clone()),
+    }));
+    let message = match outcome {
+        Ok(Ok(obj)) => return obj,
+        Ok(Err(error)) => format!("{}", error),
+        Err(panic) => {
+            let reason = panic
+                .downcast_ref::<&str>()
+                .map(|reason| reason.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            format!("The Rust handler of the proxy panicked: {}", reason)
+        }
+    };
+    if let Err(e) = jni_utils::throw_exception(&message, jni_env) {
+        error(&format!("Could not throw an exception with message {}: {}", message, e));
+    }
+    ptr::null_mut()
+}
+
+unsafe fn invoke_handler<F>(
+    jvm: &Jvm,
+    jni_env: *mut JNIEnv,
+    method_name: jstring,
+    args: jobjectArray,
+    handler: &F,
+) -> errors::Result<jobject>
+where
+    F: Fn(&Jvm, &str, Vec<Instance>) -> errors::Result<Option<InvocationArg>>,
+{
+    let method_name = jni_utils::jstring_to_rust_string(jvm, method_name)?;
+    let instances = jni_utils::instances_from_jobject_array(jni_env, args)?;
+    match handler(jvm, &method_name, instances)? {
+        Some(inv_arg) => inv_arg.as_java_ptr_with_local_ref(jni_env),
+        None => Ok(ptr::null_mut()),
+    }
+}
+
+#[cfg(test)]
+mod proxy_unit_tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn proxy_function() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let function = jvm.new_proxy("java.util.function.Function", |jvm, method_name, args| {
+            assert_eq!(method_name, "apply");
+            let s: String = jvm.to_rust(jvm.clone_instance(&args[0])?)?;
+            Ok(Some(InvocationArg::try_from(format!("{} from Rust", s))?))
+        })?;
+
+        let result = jvm.invoke(&function, "apply", &[InvocationArg::try_from("Hello")?])?;
+        let s: String = jvm.to_rust(result)?;
+        assert_eq!(s, "Hello from Rust");
+
+        let optional = jvm.invoke_static(
+            "java.util.Optional",
+            "of",
+            &[InvocationArg::try_from("Hi")?],
+        )?;
+        let mapped = jvm.invoke(&optional, "map", &[InvocationArg::from(function)])?;
+        let s: String = jvm.to_rust(jvm.invoke(&mapped, "get", InvocationArg::empty())?)?;
+        assert_eq!(s, "Hi from Rust");
+
+        Ok(())
+    }
+
+    #[test]
+    fn proxy_errors() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let runnable = jvm.new_proxy("java.lang.Runnable", |_, _, _| {
+            Err(errors::J4RsError::GeneralError("Failed in Rust".to_string()))
+        })?;
+        let res = jvm.invoke(&runnable, "run", InvocationArg::empty());
+        assert!(res.is_err());
+
+        let res = jvm.new_proxy("java.lang.String", |_, _, _| Ok(None));
+        assert!(res.is_err());
+
+        let panicking = jvm.new_proxy("java.lang.Runnable", |_, _, _| panic!("Panicked in Rust"))?;
+        match jvm.invoke(&panicking, "run", InvocationArg::empty()) {
+            Err(error) => assert!(format!("{:?}", error).contains("Panicked in Rust")),
+            Ok(_) => panic!("The panic of the handler was not propagated as an error"),
+        }
+        // The JVM is still usable
+        let res = jvm.invoke(&runnable, "run", InvocationArg::empty());
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn proxy_handler_is_dropped_after_garbage_collection() -> errors::Result<()> {
+        struct DropGuard(Arc<AtomicBool>);
+        impl Drop for DropGuard {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let jvm = create_tests_jvm()?;
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = DropGuard(dropped.clone());
+        let runnable = jvm.new_proxy("java.lang.Runnable", move |_, _, _| {
+            let _ = &guard;
+            Ok(None)
+        })?;
+        jvm.invoke(&runnable, "run", InvocationArg::empty())?;
+        drop(runnable);
+
+        for _ in 0..100 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+            jvm.invoke_static("java.lang.System", "gc", InvocationArg::empty())?;
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+
+use crate::api::exceptions;
+use crate::errors::J4RsError;
+use crate::logger::{debug, error};
+use crate::{errors, AsyncInstanceReceiver, Instance, InvocationArg, Jvm};
+
+const CLASS_RUST_PUBLISHER_SUBSCRIBER: &str = "org.astonbitecode.j4rs.api.async.RustPublisherSubscriber";
+
+impl Jvm {
+    /// Subscribes to a `java.util.concurrent.Flow.Publisher`, or a Reactive Streams `org.reactivestreams.Publisher`,
+    /// and returns the published items as a `futures::Stream`.
+    ///
+    /// The backpressure is demand-based: `demand` items are requested when subscribing and more items are requested
+    /// with `Subscription.request` while the `Stream` is consumed. This way, at most `demand` items are buffered.
+    ///
+    /// If the publisher fails, the `Stream` returns the error and ends. The error is a `J4RsError::JavaException`,
+    /// or a `J4RsError::MappedException` if the class of the Java error is mapped with `Jvm::map_exception`.
+    /// Dropping the `Stream` cancels the subscription.
+    pub fn subscribe_to_publisher(&self, publisher: &Instance, demand: usize) -> errors::Result<PublisherStream> {
+        if demand == 0 {
+            return Err(J4RsError::GeneralError("The demand of a publisher subscription must be positive".to_string()));
+        }
+        debug(&format!("Subscribing to a publisher of class {} with demand {}", publisher.class_name(), demand));
+        let subscriber = self.create_instance(CLASS_RUST_PUBLISHER_SUBSCRIBER, InvocationArg::empty())?;
+        let terminal = self.invoke(&subscriber, "terminal", InvocationArg::empty())?;
+        let receiver = self.init_callback_channel(&subscriber)?.into_async();
+        self.invoke(&subscriber, "subscribe", &[InvocationArg::from(self.clone_instance(publisher)?)])?;
+        self.invoke(&subscriber, "request", &[InvocationArg::try_from(demand as i64)?.into_primitive()?])?;
+        Ok(PublisherStream {
+            receiver,
+            subscriber,
+            terminal,
+            demand,
+            received: 0,
+            done: false,
+        })
+    }
+}
+
+/// A `Stream` of the items of a Java publisher. It is created by `Jvm::subscribe_to_publisher`.
+///
+/// On Drop, the subscription is cancelled, unless the publisher has already completed or failed.
+pub struct PublisherStream {
+    receiver: AsyncInstanceReceiver,
+    subscriber: Instance,
+    terminal: Instance,
+    demand: usize,
+    // The items that are received since the last request to the publisher
+    received: usize,
+    done: bool,
+}
+
+impl PublisherStream {
+    // Returns the item to return for an Instance that is received from Java, or None if the publisher completed
+    fn on_received(&mut self, jvm: &Jvm, instance: Instance) -> errors::Result<Option<Instance>> {
+        if jvm.same_object(&instance, &self.terminal)? {
+            self.done = true;
+            let error = jvm.invoke(&self.subscriber, "error", InvocationArg::empty())?;
+            return if error.is_null(jvm)? {
+                Ok(None)
+            } else {
+                Err(exceptions::throwable_to_error(jvm, error)?)
+            };
+        }
+        self.received += 1;
+        // Request more items once half of the demand is consumed, so that the publisher does not wait idle
+        if self.received >= self.demand.div_ceil(2) {
+            let n = self.received as i64;
+            self.received = 0;
+            jvm.invoke(&self.subscriber, "request", &[InvocationArg::try_from(n)?.into_primitive()?])?;
+        }
+        Ok(Some(instance))
+    }
+}
+
+impl Stream for PublisherStream {
+    type Item = errors::Result<Instance>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        match Pin::new(&mut this.receiver).poll_next(cx) {
+            Poll::Ready(Some(Ok(instance))) => {
+                // The thread stays attached, so that the next items are handled without attaching again
+                let item =
+                    Jvm::attach_thread_with_no_detach_on_drop().and_then(|jvm| this.on_received(&jvm, instance));
+                if item.is_err() {
+                    this.done = true;
+                }
+                Poll::Ready(item.transpose())
+            }
+            Poll::Ready(other) => {
+                this.done = true;
+                Poll::Ready(other)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for PublisherStream {
+    fn drop(&mut self) {
+        if !self.done {
+            debug("Cancelling a publisher subscription");
+            let cancelled = Jvm::attach_thread_with_no_detach_on_drop()
+                .and_then(|jvm| jvm.invoke(&self.subscriber, "cancel", InvocationArg::empty()));
+            if let Err(e) = cancelled {
+                error(&format!("Could not cancel a publisher subscription: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod publisher_unit_tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    fn submission_publisher(jvm: &Jvm) -> errors::Result<Instance> {
+        jvm.create_instance("java.util.concurrent.SubmissionPublisher", InvocationArg::empty())
+    }
+
+    fn submit(jvm: &Jvm, publisher: &Instance, values: std::ops::Range<i32>) -> errors::Result<()> {
+        for i in values {
+            let value = jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from(i.to_string())?])?;
+            jvm.invoke(publisher, "submit", &[InvocationArg::from(value)])?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn subscribe_to_flow_publisher() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let publisher = submission_publisher(&jvm)?;
+        let stream = jvm.subscribe_to_publisher(&publisher, 4)?;
+        // More items than the demand
+        submit(&jvm, &publisher, 0..10)?;
+        jvm.invoke(&publisher, "close", InvocationArg::empty())?;
+
+        let values: Vec<i32> = futures::executor::block_on(stream.collect::<Vec<_>>())
+            .into_iter()
+            .map(|instance| jvm.to_rust(instance?))
+            .collect::<errors::Result<_>>()?;
+        assert_eq!(values, (0..10).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn demand_limits_the_requested_items() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let publisher = submission_publisher(&jvm)?;
+        let mut stream = jvm.subscribe_to_publisher(&publisher, 2)?;
+        submit(&jvm, &publisher, 0..5)?;
+        // Nothing is consumed yet, so the publisher buffers the items that are not requested
+        let lag: i32 = jvm.to_rust(jvm.invoke(&publisher, "estimateMaximumLag", InvocationArg::empty())?)?;
+        assert!(lag >= 3);
+
+        let first: i32 = jvm.to_rust(futures::executor::block_on(stream.next()).unwrap()?)?;
+        assert_eq!(first, 0);
+        drop(stream);
+        Ok(())
+    }
+
+    #[test]
+    fn failed_publisher() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let publisher = submission_publisher(&jvm)?;
+        let mut stream = jvm.subscribe_to_publisher(&publisher, 4)?;
+        let exception = jvm.create_instance("java.lang.IllegalStateException", &[InvocationArg::try_from("Boom!")?])?;
+        jvm.invoke(&publisher, "closeExceptionally", &[InvocationArg::from(exception)])?;
+
+        match futures::executor::block_on(stream.next()).unwrap() {
+            Err(J4RsError::JavaException(exception)) => {
+                assert_eq!(exception.class_name(), "java.lang.IllegalStateException");
+                assert_eq!(exception.message(), Some("Boom!"));
+                let throwable = exception.throwable(&jvm)?.unwrap();
+                assert_eq!(throwable.class_name(), "java.lang.IllegalStateException");
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("The error of the publisher was not returned"),
+        }
+        assert!(futures::executor::block_on(stream.next()).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn not_a_publisher() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let not_a_publisher = jvm.create_instance("java.lang.Object", InvocationArg::empty())?;
+        assert!(jvm.subscribe_to_publisher(&not_a_publisher, 4).is_err());
+        assert!(jvm.subscribe_to_publisher(&not_a_publisher, 0).is_err());
+        Ok(())
+    }
+}
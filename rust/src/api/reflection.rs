@@ -0,0 +1,158 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{errors, utils, Instance, InvocationArg, Jvm};
+
+pub(crate) const CLASS_REFLECTION: &str = "org.astonbitecode.j4rs.api.reflection.Reflection";
+
+impl Jvm {
+    /// Checks whether the class `class_name` can be loaded.
+    ///
+    /// This allows adapting to optional jars that may be absent from the classpath, instead of failing when invoking.
+    /// A class that exists, but depends on classes that are absent, cannot be loaded either.
+    pub fn class_exists(&self, class_name: &str) -> errors::Result<bool> {
+        let exists = self.invoke_static(CLASS_REFLECTION, "classExists", &[InvocationArg::try_from(class_name)?])?;
+        self.to_rust(exists)
+    }
+
+    /// Checks whether the runtime class of the Java object of the `instance` has a public method `method_name`
+    /// that can be invoked with arguments of the classes `arg_types`.
+    ///
+    /// Static methods are included, so the `Instance`s of `Jvm::static_class` can be checked as well.
+    /// Returns false if any of the `arg_types` cannot be loaded.
+    pub fn method_exists(&self, instance: &Instance, method_name: &str, arg_types: &[&str]) -> errors::Result<bool> {
+        let arg_types: Vec<InvocationArg> = arg_types
+            .iter()
+            .map(|arg_type| InvocationArg::try_from(*arg_type))
+            .collect::<errors::Result<_>>()?;
+        let arg_types = self.create_java_array("java.lang.String", &arg_types)?;
+        let exists = self.invoke_static(
+            CLASS_REFLECTION,
+            "methodExists",
+            &[
+                InvocationArg::try_from(self.get_class_name(instance)?)?,
+                InvocationArg::try_from(method_name)?,
+                InvocationArg::from(arg_types),
+            ],
+        )?;
+        self.to_rust(exists)
+    }
+
+    /// Lists the public top-level classes of the jar `jar_path` that can be loaded, sorted by their names.
+    ///
+    /// The jar needs to be in the classpath for its classes to be loaded.
+    pub fn list_jar_classes<P: AsRef<Path>>(&self, jar_path: P) -> errors::Result<Vec<String>> {
+        let classes = self.invoke_static(
+            CLASS_REFLECTION,
+            "listPublicClasses",
+            &[InvocationArg::try_from(utils::java_path(&jar_path.as_ref().to_string_lossy()).as_ref())?],
+        )?;
+        self.to_rust(classes)
+    }
+
+    /// Lists the public methods, including the inherited ones, of the runtime class of the Java object of the `instance`.
+    ///
+    /// The methods are sorted by their names.
+    pub fn list_methods(&self, instance: &Instance) -> errors::Result<Vec<JavaMethod>> {
+        let methods = self.invoke_static(
+            CLASS_REFLECTION,
+            "listMethods",
+            &[InvocationArg::try_from(self.get_class_name(instance)?)?],
+        )?;
+        self.to_rust(methods)
+    }
+}
+
+/// The description of a public Java method, as returned by `Jvm::list_methods`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaMethod {
+    name: String,
+    parameter_types: Vec<String>,
+    return_type: String,
+    declaring_class: String,
+    #[serde(rename = "static")]
+    is_static: bool,
+    var_args: bool,
+}
+
+impl JavaMethod {
+    /// Returns the name of the method
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the names of the classes of the parameters
+    pub fn parameter_types(&self) -> &[String] {
+        &self.parameter_types
+    }
+
+    /// Returns the name of the class of the returned value, or `void`
+    pub fn return_type(&self) -> &str {
+        &self.return_type
+    }
+
+    /// Returns the name of the class that declares the method
+    pub fn declaring_class(&self) -> &str {
+        &self.declaring_class
+    }
+
+    /// Returns true if the method is static
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Returns true if the last parameter of the method is variadic
+    pub fn is_var_args(&self) -> bool {
+        self.var_args
+    }
+}
+
+#[cfg(test)]
+mod reflection_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn class_and_method_existence() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        assert!(jvm.class_exists("java.util.ArrayList")?);
+        assert!(!jvm.class_exists("com.example.Missing")?);
+
+        let list = jvm.create_instance("java.util.ArrayList", InvocationArg::empty())?;
+        assert!(jvm.method_exists(&list, "add", &["java.lang.String"])?);
+        assert!(jvm.method_exists(&list, "get", &["int"])?);
+        assert!(!jvm.method_exists(&list, "get", &["java.lang.String"])?);
+        assert!(!jvm.method_exists(&list, "add", &["com.example.Missing"])?);
+        let integer_class = jvm.static_class("java.lang.Integer")?;
+        assert!(jvm.method_exists(&integer_class, "parseInt", &["java.lang.String"])?);
+
+        let methods = jvm.list_methods(&integer_class)?;
+        let parse_int = methods
+            .iter()
+            .find(|m| m.name() == "parseInt" && m.parameter_types().len() == 1)
+            .expect("parseInt is not listed");
+        assert_eq!(parse_int.parameter_types(), ["java.lang.String"]);
+        assert_eq!(parse_int.return_type(), "int");
+        assert_eq!(parse_int.declaring_class(), "java.lang.Integer");
+        assert!(parse_int.is_static());
+        assert!(!parse_int.is_var_args());
+        Ok(())
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::collections::hash_map::RandomState;
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::api::exceptions;
+use crate::errors::{J4RsError, JavaException};
+use crate::logger::{debug, warn};
+use crate::{errors, utils, JavaClass};
+
+const REMOTE_SERVER_CLASS: &str = "org.astonbitecode.j4rs.api.remote.RemoteServer";
+const REMOTE_TOKEN_ENV: &str = "J4RS_REMOTE_TOKEN";
+// The time that a spawned JVM process has to connect
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A JVM that runs in a child process, as created by `JvmBuilder::out_of_process`, or in a GraalVM native image
+/// (see `RemoteJvm::native_image`).
+///
+/// The `RemoteJvm` offers the basic invocations of the `Jvm`. The Java objects stay in the child process
+/// and are referenced by `RemoteInstance`s, while the arguments and the results that are transformed to Rust
+/// values are exchanged as JSON, like with the `Jvm`.
+///
+/// Only `create_instance`, `invoke`, `invoke_static`, `field` and `to_rust` are supported. The rest of the `Jvm` API,
+/// like callbacks, async invocations, arrays, casts, static fields and chained invocations, is not available.
+///
+/// A crash of the JVM process does not affect the current process: the invocations just return errors afterwards.
+/// The JVM ends when the `RemoteJvm` and all its `RemoteInstance`s are dropped.
+pub struct RemoteJvm {
+    connection: Arc<Connection>,
+}
+
+impl RemoteJvm {
+    /// Creates an instance of the class `class_name`, passing the `args` to its constructor.
+    pub fn create_instance(&self, class_name: &str, args: &[RemoteArg]) -> errors::Result<RemoteInstance> {
+        self.connection
+            .instance_request(json!({"op": "create_instance", "class": class_name, "args": args_json(args)}))
+    }
+
+    /// Invokes the method `method_name` of an instance, passing the `args`.
+    pub fn invoke(
+        &self,
+        instance: &RemoteInstance,
+        method_name: &str,
+        args: &[RemoteArg],
+    ) -> errors::Result<RemoteInstance> {
+        self.connection.instance_request(json!({
+            "op": "invoke",
+            "handle": instance.handle,
+            "method": method_name,
+            "args": args_json(args),
+        }))
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name`, passing the `args`.
+    pub fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        args: &[RemoteArg],
+    ) -> errors::Result<RemoteInstance> {
+        self.connection.instance_request(json!({
+            "op": "invoke_static",
+            "class": class_name,
+            "method": method_name,
+            "args": args_json(args),
+        }))
+    }
+
+    /// Retrieves the public field `field_name` of an instance.
+    pub fn field(&self, instance: &RemoteInstance, field_name: &str) -> errors::Result<RemoteInstance> {
+        self.connection
+            .instance_request(json!({"op": "field", "handle": instance.handle, "name": field_name}))
+    }
+
+    /// Returns the Rust representation of an instance.
+    pub fn to_rust<T: DeserializeOwned>(&self, instance: RemoteInstance) -> errors::Result<T> {
+        let response = self.connection.request(json!({"op": "to_json", "handle": instance.handle}))?;
+        let json = response["json"]
+            .as_str()
+            .ok_or_else(|| J4RsError::ParseError(format!("Invalid response of the JVM process: {}", response)))?;
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Returns true if the JVM is running.
+    pub fn is_alive(&self) -> bool {
+        self.connection.is_alive()
+    }
+
+    pub(crate) fn with_transport(transport: Box<dyn Transport>) -> errors::Result<RemoteJvm> {
+        let connection = Arc::new(Connection {
+            transport: Mutex::new(transport),
+            next_id: AtomicU64::new(1),
+        });
+        // Make sure that the JVM is ready
+        connection.request(json!({"op": "ping"}))?;
+        Ok(RemoteJvm { connection })
+    }
+}
+
+/// A Java object that lives in the JVM process of a `RemoteJvm`.
+///
+/// The object is released in the JVM process when the `RemoteInstance` is dropped.
+pub struct RemoteInstance {
+    handle: u64,
+    class_name: String,
+    connection: Arc<Connection>,
+}
+
+impl RemoteInstance {
+    /// Returns the name of the class of the instance, as known to Java.
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+}
+
+impl Drop for RemoteInstance {
+    fn drop(&mut self) {
+        self.connection.send(&json!({"op": "release", "handle": self.handle}));
+    }
+}
+
+/// An argument of the invocations of a `RemoteJvm`.
+pub struct RemoteArg(Value);
+
+impl RemoteArg {
+    /// Creates an argument of the class `class_name` from a Rust value, which is serialized to JSON,
+    /// like with `InvocationArg::new`.
+    pub fn new<T: Serialize>(arg: &T, class_name: &str) -> errors::Result<RemoteArg> {
+        Ok(RemoteArg(json!({"class": class_name, "json": serde_json::to_string(arg)?})))
+    }
+
+    /// Transforms the argument to an argument that contains a Java primitive, like `InvocationArg::into_primitive`.
+    pub fn into_primitive(mut self) -> errors::Result<RemoteArg> {
+        match self.0["class"].as_str().and_then(utils::primitive_of_class) {
+            Some(primitive_repr) => {
+                self.0["class"] = json!(primitive_repr);
+                Ok(self)
+            }
+            None => Err(J4RsError::JavaError(format!("Cannot transform to primitive: {}", self.0))),
+        }
+    }
+}
+
+impl From<&RemoteInstance> for RemoteArg {
+    fn from(instance: &RemoteInstance) -> RemoteArg {
+        RemoteArg(json!({"handle": instance.handle}))
+    }
+}
+
+macro_rules! remote_arg_from {
+    ($($t:ty => $class:expr),* $(,)?) => {
+        $(
+            impl TryFrom<$t> for RemoteArg {
+                type Error = J4RsError;
+                fn try_from(arg: $t) -> errors::Result<RemoteArg> {
+                    RemoteArg::new(&arg, $class.into())
+                }
+            }
+        )*
+    };
+}
+
+remote_arg_from!(
+    &str => JavaClass::String,
+    String => JavaClass::String,
+    bool => JavaClass::Boolean,
+    char => JavaClass::Character,
+    i8 => JavaClass::Byte,
+    i16 => JavaClass::Short,
+    i32 => JavaClass::Integer,
+    i64 => JavaClass::Long,
+    f32 => JavaClass::Float,
+    f64 => JavaClass::Double,
+);
+
+fn args_json(args: &[RemoteArg]) -> Vec<&Value> {
+    args.iter().map(|arg| &arg.0).collect()
+}
+
+/// Exchanges the requests and the responses of a `RemoteJvm` with the JVM, as JSON objects.
+pub(crate) trait Transport: Send {
+    /// Sends a request and returns its response, or None if the request is not `answered`.
+    fn exchange(&mut self, request: &str, answered: bool) -> errors::Result<Option<String>>;
+
+    /// Returns true if the JVM is running.
+    fn is_alive(&mut self) -> bool;
+}
+
+struct Connection {
+    transport: Mutex<Box<dyn Transport>>,
+    next_id: AtomicU64,
+}
+
+impl Connection {
+    fn instance_request(self: &Arc<Self>, request: Value) -> errors::Result<RemoteInstance> {
+        let response = self.request(request)?;
+        match (response["handle"].as_u64(), response["class"].as_str()) {
+            (Some(handle), Some(class_name)) => Ok(RemoteInstance {
+                handle,
+                class_name: class_name.to_string(),
+                connection: self.clone(),
+            }),
+            _ => Err(J4RsError::ParseError(format!("Invalid response of the JVM: {}", response))),
+        }
+    }
+
+    fn request(&self, mut request: Value) -> errors::Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        request["id"] = json!(id);
+        let line = self
+            .transport
+            .lock()?
+            .exchange(&request.to_string(), true)?
+            .ok_or_else(|| J4RsError::ParseError(format!("The JVM did not answer {}", request)))?;
+        let response: Value = serde_json::from_str(&line)?;
+        // Requests that cannot be parsed are answered with errors that have no id
+        match response["id"].as_u64() {
+            Some(response_id) if response_id == id => {}
+            None if response.get("error").is_some() => {}
+            _ => return Err(J4RsError::ParseError(format!("Unexpected response of the JVM: {}", line))),
+        }
+        if let Some(error) = response.get("error") {
+            let java_exception: JavaException = serde_json::from_value(error.clone())?;
+            return Err(exceptions::to_error(java_exception));
+        }
+        Ok(response)
+    }
+
+    // Sends a request that is not answered
+    fn send(&self, request: &Value) {
+        if let Ok(mut transport) = self.transport.lock() {
+            let _ = transport.exchange(&request.to_string(), false);
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        self.transport.lock().map(|mut transport| transport.is_alive()).unwrap_or(false)
+    }
+}
+
+// A child JVM process, with which the requests and the responses are exchanged over a loopback TCP connection,
+// one JSON object per line. The standard output and error of the process are inherited and not used by the protocol.
+struct ProcessTransport {
+    child: Child,
+    writer: Option<TcpStream>,
+    reader: BufReader<TcpStream>,
+}
+
+impl ProcessTransport {
+    // The JVM process exits at the end of its input
+    fn close(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.shutdown(Shutdown::Write);
+        }
+    }
+}
+
+impl Transport for ProcessTransport {
+    fn exchange(&mut self, request: &str, answered: bool) -> errors::Result<Option<String>> {
+        let mut line = String::new();
+        let exchanged = match self.writer.as_mut() {
+            Some(writer) => writeln!(writer, "{}", request).and_then(|_| writer.flush()).and_then(|_| {
+                if answered {
+                    self.reader.read_line(&mut line)
+                } else {
+                    Ok(1)
+                }
+            }),
+            None => Ok(0),
+        };
+        if matches!(exchanged, Ok(n) if n > 0) {
+            Ok(if answered { Some(line) } else { None })
+        } else {
+            self.close();
+            let status = match self.child.wait() {
+                Ok(status) => status.to_string(),
+                Err(_) => "unknown status".to_string(),
+            };
+            Err(J4RsError::GeneralError(format!("The JVM process is not running ({})", status)))
+        }
+    }
+
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for ProcessTransport {
+    fn drop(&mut self) {
+        debug("Stopping the JVM process");
+        self.close();
+        if let Err(error) = self.child.wait() {
+            warn(&format!("Could not wait for the JVM process: {}", error));
+        }
+    }
+}
+
+/// The command that spawns a JVM process that serves the invocations of a `RemoteJvm`.
+#[derive(Debug, Clone)]
+pub(crate) struct RemoteCommand {
+    java: PathBuf,
+    java_args: Vec<String>,
+}
+
+impl RemoteCommand {
+    pub(crate) fn new(java: PathBuf, java_args: Vec<String>) -> RemoteCommand {
+        RemoteCommand { java, java_args }
+    }
+
+    pub(crate) fn spawn(&self) -> errors::Result<RemoteJvm> {
+        debug(&format!("Spawning the JVM process {:?} {:?}", self.java, self.java_args));
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let address = listener.local_addr()?;
+        let token = connection_token();
+        let mut child = Command::new(&self.java)
+            .args(&self.java_args)
+            .arg(REMOTE_SERVER_CLASS)
+            .arg(address.ip().to_string())
+            .arg(address.port().to_string())
+            .env(REMOTE_TOKEN_ENV, &token)
+            .stdin(Stdio::null())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| J4RsError::GeneralError(format!("Could not spawn {:?}: {}", self.java, error)))?;
+        let stream = match accept_connection(&listener, &mut child, &token) {
+            Ok(stream) => stream,
+            Err(error) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(error);
+            }
+        };
+        let transport = ProcessTransport {
+            writer: Some(stream.try_clone()?),
+            reader: BufReader::new(stream),
+            child,
+        };
+        RemoteJvm::with_transport(Box::new(transport))
+    }
+}
+
+// Accepts the connection of the spawned JVM process, which identifies itself with the token.
+// Connections that do not present the token are dropped.
+fn accept_connection(listener: &TcpListener, child: &mut Child, token: &str) -> errors::Result<TcpStream> {
+    let deadline = Instant::now() + CONNECTION_TIMEOUT;
+    listener.set_nonblocking(true)?;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                stream.set_read_timeout(Some(remaining.max(Duration::from_millis(1))))?;
+                let mut line = String::new();
+                let presented = BufReader::new(&stream).read_line(&mut line).is_ok() && line.trim_end() == token;
+                if presented {
+                    stream.set_read_timeout(None)?;
+                    stream.set_nodelay(true)?;
+                    return Ok(stream);
+                }
+                warn("Dropping a connection that did not present the token of the JVM process");
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                if let Some(status) = child.try_wait()? {
+                    return Err(J4RsError::GeneralError(format!("The JVM process exited ({})", status)));
+                }
+                if Instant::now() >= deadline {
+                    return Err(J4RsError::GeneralError("The JVM process did not connect in time".to_string()));
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+// A random token, with which the spawned JVM process identifies itself
+fn connection_token() -> String {
+    let state = RandomState::new();
+    (0..2u64)
+        .map(|i| {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(i);
+            hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos());
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod remote_unit_tests {
+    use super::*;
+    use crate::JvmBuilder;
+
+    // Answers the requests in the current process and records the released handles
+    struct FakeTransport {
+        released: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl Transport for FakeTransport {
+        fn exchange(&mut self, request: &str, answered: bool) -> errors::Result<Option<String>> {
+            let request: Value = serde_json::from_str(request)?;
+            let response = match request["op"].as_str() {
+                Some("ping") => json!({"id": request["id"]}),
+                Some("create_instance") => json!({"id": request["id"], "handle": 7, "class": request["class"]}),
+                Some("to_json") => json!({"id": request["id"], "json": "\"a string\""}),
+                Some("release") => {
+                    self.released.lock()?.push(request["handle"].as_u64().unwrap());
+                    json!(null)
+                }
+                _ => json!({"error": {
+                    "className": "java.lang.IllegalArgumentException",
+                    "message": "Unknown operation",
+                    "stackTrace": [],
+                }}),
+            };
+            assert_eq!(answered, !response.is_null());
+            Ok(if answered { Some(response.to_string()) } else { None })
+        }
+
+        fn is_alive(&mut self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn remote_transport_requests_and_releases() -> errors::Result<()> {
+        let released = Arc::new(Mutex::new(Vec::new()));
+        let jvm = RemoteJvm::with_transport(Box::new(FakeTransport { released: released.clone() }))?;
+        let instance = jvm.create_instance("java.lang.String", &[])?;
+        assert_eq!(instance.class_name(), "java.lang.String");
+        // The instance is released when it is consumed
+        assert_eq!(jvm.to_rust::<String>(instance)?, "a string");
+        assert_eq!(*released.lock()?, vec![7]);
+        let other = jvm.create_instance("java.lang.Object", &[])?;
+        match jvm.field(&other, "field") {
+            Err(J4RsError::JavaException(exception)) => {
+                assert_eq!(exception.class_name(), "java.lang.IllegalArgumentException")
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("The error response was not returned as an error"),
+        }
+        drop(other);
+        assert_eq!(*released.lock()?, vec![7, 7]);
+        Ok(())
+    }
+
+    #[test]
+    fn remote_invocations() -> errors::Result<()> {
+        let jvm = JvmBuilder::new().out_of_process()?;
+        let builder = jvm.create_instance("java.lang.StringBuilder", &[RemoteArg::try_from("abc")?])?;
+        assert_eq!(builder.class_name(), "java.lang.StringBuilder");
+        let _ = jvm.invoke(&builder, "append", &[RemoteArg::from(&builder)])?;
+        let string = jvm.invoke(&builder, "toString", &[])?;
+        assert_eq!(jvm.to_rust::<String>(string)?, "abcabc");
+
+        let list = jvm.create_instance("java.util.ArrayList", &[])?;
+        let _ = jvm.invoke(&list, "add", &[RemoteArg::new(&vec![1, 2], "java.util.List")?])?;
+        let size = jvm.invoke(&list, "size", &[])?;
+        assert_eq!(jvm.to_rust::<i32>(size)?, 1);
+        let first = jvm.invoke(&list, "get", &[RemoteArg::try_from(0)?.into_primitive()?])?;
+        assert_eq!(jvm.to_rust::<Vec<i32>>(first)?, vec![1, 2]);
+
+        let point = jvm.create_instance(
+            "java.awt.Point",
+            &[RemoteArg::try_from(3)?.into_primitive()?, RemoteArg::try_from(4)?.into_primitive()?],
+        )?;
+        let y = jvm.field(&point, "y")?;
+        assert_eq!(jvm.to_rust::<i32>(y)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn remote_exceptions() -> errors::Result<()> {
+        let jvm = JvmBuilder::new().out_of_process()?;
+        match jvm.invoke_static("java.lang.Integer", "parseInt", &[RemoteArg::try_from("NaN")?]) {
+            Err(J4RsError::JavaException(exception)) => {
+                assert_eq!(exception.class_name(), "java.lang.NumberFormatException")
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+            Ok(_) => panic!("The exception was not thrown"),
+        }
+        let parsed = jvm.invoke_static("java.lang.Integer", "parseInt", &[RemoteArg::try_from("33")?])?;
+        assert_eq!(jvm.to_rust::<i32>(parsed)?, 33);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn remote_output_does_not_interfere() -> errors::Result<()> {
+        let jvm = JvmBuilder::new().out_of_process()?;
+        // Writes to the file descriptor of the standard output, like native code would
+        let stdout = jvm.create_instance("java.io.PrintStream", &[RemoteArg::try_from("/dev/stdout")?])?;
+        let _ = jvm.invoke(&stdout, "println", &[RemoteArg::try_from("{\"id\":1}")?])?;
+        let _ = jvm.invoke(&stdout, "flush", &[])?;
+        let parsed = jvm.invoke_static("java.lang.Integer", "parseInt", &[RemoteArg::try_from("33")?])?;
+        assert_eq!(jvm.to_rust::<i32>(parsed)?, 33);
+        Ok(())
+    }
+
+    #[test]
+    fn remote_jvm_crash() -> errors::Result<()> {
+        let jvm = JvmBuilder::new().out_of_process()?;
+        assert!(jvm.is_alive());
+        let runtime = jvm.invoke_static("java.lang.Runtime", "getRuntime", &[])?;
+        assert!(jvm.invoke(&runtime, "halt", &[RemoteArg::try_from(1)?.into_primitive()?]).is_err());
+        assert!(!jvm.is_alive());
+        assert!(jvm.invoke_static("java.lang.Integer", "valueOf", &[RemoteArg::try_from(1)?]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+
+use crate::logger::debug;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const SCALA_SUPPORT_CLASS: &str = "org.astonbitecode.j4rs.api.scala.ScalaSupport";
+
+impl Jvm {
+    /// Returns the `Instance` of the Scala `object` `object_name`.
+    ///
+    /// The name may be given either as in Scala (`com.foo.Bar`), or as the class that the Scala compiler generates
+    /// for the object (`com.foo.Bar$`).
+    pub fn scala_object(&self, object_name: &str) -> errors::Result<Instance> {
+        let class_name = if object_name.ends_with('$') {
+            object_name.to_string()
+        } else {
+            format!("{}$", object_name)
+        };
+        self.static_class_field(&class_name, "MODULE$")
+    }
+
+    /// Invokes the Scala method `method_name` of a created `Instance`, passing an array of `InvocationArg`s.
+    ///
+    /// The method name is given as in Scala and is encoded like the Scala compiler does (e.g. `+` becomes `$plus`).
+    /// The arguments are matched to the parameters after unboxing, so arguments like `InvocationArg::try_from(1)`
+    /// match `Int` parameters without calling `into_primitive`. Methods that are implemented only in traits
+    /// are invoked via the static forwarders of the traits.
+    pub fn invoke_scala(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Invoking Scala method {} of class {} using {} arguments",
+            method_name,
+            instance.class_name(),
+            inv_args.len()
+        ));
+        self.invoke_static_varargs(
+            SCALA_SUPPORT_CLASS,
+            "invoke",
+            &[
+                InvocationArg::from(self.clone_instance(instance)?),
+                InvocationArg::try_from(method_name)?,
+            ],
+            inv_args,
+            "java.lang.Object",
+        )
+    }
+
+    /// Invokes the method `method_name` of the Scala `object` `object_name`. See `Jvm::invoke_scala`.
+    pub fn invoke_scala_object(
+        &self,
+        object_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        let object = self.scala_object(object_name)?;
+        self.invoke_scala(&object, method_name, inv_args)
+    }
+}
+
+#[cfg(test)]
+mod scala_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn scala_objects() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let object = jvm.scala_object("org.astonbitecode.j4rs.tests.MyScalaObject")?;
+        let same_object = jvm.scala_object("org.astonbitecode.j4rs.tests.MyScalaObject$")?;
+        assert!(jvm.equals(&object, &same_object)?);
+
+        let sum = jvm.invoke_scala(&object, "+", &[InvocationArg::try_from(1)?, InvocationArg::try_from(2)?])?;
+        assert_eq!(jvm.to_rust::<i32>(sum)?, 3);
+        let described = jvm.invoke_scala_object(
+            "org.astonbitecode.j4rs.tests.MyScalaObject",
+            "describe",
+            &[InvocationArg::try_from(2_i64)?],
+        )?;
+        assert_eq!(jvm.to_rust::<String>(described)?, "described 2 times");
+        assert!(jvm.invoke_scala(&object, "-", InvocationArg::empty()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::borrow::Borrow;
+use std::cell::RefCell;
+
+use serde::de::DeserializeOwned;
+
+use crate::logger::debug;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+thread_local! {
+    // The Jvm that is lazily attached to the current thread by a SharedJvm.
+    // It is dropped, and the thread gets detached, when the thread ends.
+    static THREAD_JVM: RefCell<Option<Jvm>> = const { RefCell::new(None) };
+}
+
+/// A handle to the Java VM that is `Send` and `Sync` and can be shared between threads.
+///
+/// Each thread that uses the handle is attached lazily to the Java VM, the first time it is needed.
+/// The attached thread is detached when it ends.
+///
+/// Creating `InvocationArg`s requires the thread to be attached as well; this is why they should be created
+/// inside the closure that is passed to `SharedJvm::with`, or after calling any other method of the `SharedJvm`
+/// in the same thread.
+///
+/// It is created by calling `Jvm::shared`, which guarantees that the Java VM is already created.
+#[derive(Debug, Clone)]
+pub struct SharedJvm {
+    _private: (),
+}
+
+impl SharedJvm {
+    pub(crate) fn new() -> SharedJvm {
+        SharedJvm { _private: () }
+    }
+
+    /// Calls `f` with the `Jvm` of the current thread, attaching the thread to the Java VM if needed.
+    pub fn with<R>(&self, f: impl FnOnce(&Jvm) -> errors::Result<R>) -> errors::Result<R> {
+        THREAD_JVM.with(|opt| {
+            if opt.borrow().is_none() {
+                debug("Attaching the current thread for a SharedJvm");
+                let jvm = Jvm::attach_thread()?;
+                *opt.borrow_mut() = Some(jvm);
+            }
+            let jvm_ref = opt.borrow();
+            match jvm_ref.as_ref() {
+                Some(jvm) => f(jvm),
+                None => Err(errors::J4RsError::RustError(
+                    "Could not attach the current thread to the Java VM".to_string(),
+                )),
+            }
+        })
+    }
+
+    /// Creates an `Instance` of the class `class_name`. See `Jvm::create_instance`.
+    pub fn create_instance(
+        &self,
+        class_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.create_instance(class_name, inv_args))
+    }
+
+    /// Retrieves the static class `class_name`. See `Jvm::static_class`.
+    pub fn static_class(&self, class_name: &str) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.static_class(class_name))
+    }
+
+    /// Invokes the method `method_name` of a created `Instance`. See `Jvm::invoke`.
+    pub fn invoke(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.invoke(instance, method_name, inv_args))
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name`. See `Jvm::invoke_static`.
+    pub fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.invoke_static(class_name, method_name, inv_args))
+    }
+
+    /// Invokes the method `method_name` of a created `Instance` and returns the Rust representation of the result.
+    /// See `Jvm::invoke_to_rust`.
+    pub fn invoke_to_rust<T>(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.with(|jvm| jvm.invoke_to_rust(instance, method_name, inv_args))
+    }
+
+    /// Retrieves the field `field_name` of a created `Instance`. See `Jvm::field`.
+    pub fn field(&self, instance: &Instance, field_name: &str) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.field(instance, field_name))
+    }
+
+    /// Casts an `Instance` to the class `to_class`. See `Jvm::cast`.
+    pub fn cast(&self, from_instance: &Instance, to_class: &str) -> errors::Result<Instance> {
+        self.with(|jvm| jvm.cast(from_instance, to_class))
+    }
+
+    /// Returns the Rust representation of the provided instance. See `Jvm::to_rust`.
+    pub fn to_rust<T>(&self, instance: Instance) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any,
+    {
+        self.with(|jvm| jvm.to_rust(instance))
+    }
+}
+
+/// A token of the Java VM that can be moved to another thread, in order to get a `Jvm` for that thread.
+///
+/// Unlike a `Jvm`, which is bound to the thread that created it, the token is `Send` and `Sync`.
+/// Calling `attach` in the destination thread attaches it to the Java VM; the thread is detached
+/// when the returned `Jvm`, and any other `Jvm` of the thread, is dropped.
+///
+/// It is created by calling `Jvm::to_sendable`.
+#[derive(Debug, Clone)]
+pub struct SendableJvm {
+    _private: (),
+}
+
+impl SendableJvm {
+    pub(crate) fn new() -> SendableJvm {
+        SendableJvm { _private: () }
+    }
+
+    /// Attaches the current thread to the Java VM and returns its `Jvm`.
+    pub fn attach(self) -> errors::Result<Jvm> {
+        debug("Attaching the current thread for a SendableJvm");
+        Jvm::attach_thread()
+    }
+}
+
@@ -0,0 +1,109 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::logger::error;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const CLASS_RUST_SHUTDOWN_HOOK: &str = "org.astonbitecode.j4rs.api.invocation.RustShutdownHook";
+
+type ShutdownHooks = Arc<Mutex<Vec<Box<dyn FnOnce(&Jvm) + Send>>>>;
+
+lazy_static! {
+    // The functions of `on_shutdown`. They are called by a single listener thread, which is created along with the first one.
+    static ref SHUTDOWN_HOOKS: Mutex<Option<ShutdownHooks>> = Mutex::new(None);
+}
+
+impl Jvm {
+    /// Registers `f` to be called when the JVM shuts down, e.g. because `System.exit` is called,
+    /// the process receives a termination signal, or `Jvm::destroy` is called.
+    ///
+    /// All the registered functions are called one after the other, in the order of their registration,
+    /// in a dedicated thread, which is attached to the JVM as a daemon, with the `Jvm` of that thread.
+    /// The JVM waits for the functions to return before halting, for up to 30 seconds. Like any other shutdown hook,
+    /// `f` should finish quickly. Functions that are registered while the JVM shuts down are not called.
+    pub fn on_shutdown<F>(&self, f: F) -> errors::Result<()>
+    where
+        F: FnOnce(&Jvm) + Send + 'static,
+    {
+        let mut shutdown_hooks = SHUTDOWN_HOOKS.lock()?;
+        let hooks = match shutdown_hooks.as_ref() {
+            Some(hooks) => hooks.clone(),
+            None => {
+                let hooks = ShutdownHooks::default();
+                let hook = self.create_shutdown_hook(hooks.clone())?;
+                self.invoke(&hook, "register", InvocationArg::empty())?;
+                *shutdown_hooks = Some(hooks.clone());
+                hooks
+            }
+        };
+        hooks.lock()?.push(Box::new(f));
+        Ok(())
+    }
+
+    // Creates a RustShutdownHook that calls the `hooks` when it runs, without registering it
+    fn create_shutdown_hook(&self, hooks: ShutdownHooks) -> errors::Result<Instance> {
+        let hook = self.create_instance(CLASS_RUST_SHUTDOWN_HOOK, InvocationArg::empty())?;
+        let receiver = self.init_callback_channel(&hook)?;
+        let listener_hook = self.clone_instance(&hook)?;
+        self.spawn_attached_as_daemon("j4rs-shutdown-listener", move |jvm| {
+            // The hook calls back when it runs and waits until it is completed.
+            // If the channel is closed, the hook does not wait.
+            if receiver.rx().recv().is_ok() {
+                let hooks = mem::take(&mut *hooks.lock().unwrap_or_else(PoisonError::into_inner));
+                for f in hooks {
+                    if panic::catch_unwind(AssertUnwindSafe(|| f(jvm))).is_err() {
+                        error("A shutdown hook panicked");
+                    }
+                }
+                if let Err(err) = jvm.invoke(&listener_hook, "complete", InvocationArg::empty()) {
+                    error(&format!("Could not complete the shutdown hook: {}", err));
+                }
+            }
+            Ok(())
+        })?;
+        Ok(hook)
+    }
+}
+
+#[cfg(test)]
+mod shutdown_unit_tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn shutdown_hook() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let called = Arc::new(AtomicBool::new(false));
+        let called_by_hook = called.clone();
+        let hooks = ShutdownHooks::default();
+        let hook = jvm.create_shutdown_hook(hooks.clone())?;
+        hooks.lock()?.push(Box::new(|_| panic!("Panicked in a shutdown hook")));
+        hooks.lock()?.push(Box::new(move |jvm| {
+            let res = jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from("1").unwrap()]);
+            called_by_hook.store(res.is_ok(), Ordering::SeqCst);
+        }));
+        // Run the hook like the JVM does during the shutdown. It returns after the functions are called, even if one panics.
+        jvm.invoke(&hook, "run", InvocationArg::empty())?;
+        assert!(called.load(Ordering::SeqCst));
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::thread;
+
+use serde::Deserialize;
+
+use crate::logger::{debug, error};
+use crate::{errors, InvocationArg, Jvm};
+
+const CLASS_RUST_STD_STREAMS: &str = "org.astonbitecode.j4rs.api.io.RustStdStreams";
+
+/// A standard stream of the Java world.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StdStream {
+    /// `System.out`
+    Out,
+    /// `System.err`
+    Err,
+}
+
+#[derive(Deserialize)]
+struct StdStreamLine {
+    stream: StdStream,
+    line: String,
+}
+
+impl Jvm {
+    /// Replaces `System.out` and `System.err` of the Java world with streams that forward each written line to the `handler`,
+    /// instead of writing it to the stdout and the stderr of the process.
+    ///
+    /// The lines are sent over a callback channel and the `handler` is called by a dedicated thread,
+    /// in the order that the lines were written. The line terminators are not included in the lines.
+    ///
+    /// The j4rs native library needs to be loaded by Java, like for the callbacks.
+    pub fn redirect_std_streams<F>(&self, handler: F) -> errors::Result<()>
+    where
+        F: Fn(StdStream, String) + Send + 'static,
+    {
+        debug("Redirecting the Java standard streams");
+        let redirector = self.create_instance(CLASS_RUST_STD_STREAMS, InvocationArg::empty())?;
+        let instance_receiver = self.init_callback_channel(&redirector)?;
+        self.invoke(&redirector, "redirect", InvocationArg::empty())?;
+
+        thread::Builder::new()
+            .name("j4rs-std-streams".to_string())
+            .spawn(move || {
+                let jvm = match Jvm::attach_thread_as_daemon() {
+                    Ok(jvm) => jvm,
+                    Err(e) => {
+                        error(&format!("Could not attach the std streams thread to the JVM: {}", e));
+                        return;
+                    }
+                };
+                for instance in instance_receiver.rx() {
+                    match jvm.to_rust::<StdStreamLine>(instance) {
+                        Ok(std_stream_line) => handler(std_stream_line.stream, std_stream_line.line),
+                        Err(e) => error(&format!("Could not receive a line of a Java std stream: {}", e)),
+                    }
+                }
+            })?;
+        Ok(())
+    }
+}
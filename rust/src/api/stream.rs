@@ -0,0 +1,176 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::any::Any;
+use std::convert::{TryFrom, TryInto};
+
+use serde::de::DeserializeOwned;
+
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+const CLASS_STREAM: &str = "java.util.stream.Stream";
+const CLASS_COLLECTION: &str = "java.util.Collection";
+
+/// A Java `java.util.stream.Stream` pipeline that is composed from Rust.
+///
+/// Like in Java, the intermediate operations (`map`, `filter` etc.) are lazy.
+/// The pipeline is executed by a terminal operation (`collect_to_vec`, `count`), which transfers its result to Rust at once,
+/// instead of transferring the elements one by one.
+/// It is created by calling `Jvm::java_stream`.
+pub struct JavaStream<'a> {
+    jvm: &'a Jvm,
+    stream: Instance,
+}
+
+impl<'a> JavaStream<'a> {
+    /// Maps the elements of the stream using the closure `f`.
+    ///
+    /// The elements are transformed to `A` and the results of `f` to `InvocationArg`s, like with `InvocationArg::from_fn`.
+    pub fn map<A, R, F>(self, f: F) -> errors::Result<JavaStream<'a>>
+    where
+        A: DeserializeOwned + Any,
+        F: Fn(A) -> R + Send + Sync + 'static,
+        R: TryInto<InvocationArg, Error = J4RsError>,
+    {
+        self.map_with(InvocationArg::from_fn::<A, R, F>(f)?)
+    }
+
+    /// Maps the elements of the stream using a Java `java.util.function.Function`.
+    ///
+    /// Unlike `map`, the elements do not need to be transferred to Rust.
+    pub fn map_with(self, function: InvocationArg) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("map", &[function])
+    }
+
+    /// Keeps the elements of the stream for which the closure `f` returns `true`.
+    ///
+    /// The elements are transformed to `A`, like with `InvocationArg::from_predicate`.
+    pub fn filter<A, F>(self, f: F) -> errors::Result<JavaStream<'a>>
+    where
+        A: DeserializeOwned + Any,
+        F: Fn(A) -> bool + Send + Sync + 'static,
+    {
+        self.filter_with(InvocationArg::from_predicate::<A, F>(f)?)
+    }
+
+    /// Keeps the elements of the stream that match a Java `java.util.function.Predicate`.
+    ///
+    /// Unlike `filter`, the elements do not need to be transferred to Rust.
+    pub fn filter_with(self, predicate: InvocationArg) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("filter", &[predicate])
+    }
+
+    /// Keeps at most `max_size` elements of the stream.
+    pub fn limit(self, max_size: i64) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("limit", &[InvocationArg::try_from(max_size)?.into_primitive()?])
+    }
+
+    /// Discards the first `n` elements of the stream.
+    pub fn skip(self, n: i64) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("skip", &[InvocationArg::try_from(n)?.into_primitive()?])
+    }
+
+    /// Removes the duplicate elements of the stream, according to `Object.equals`.
+    pub fn distinct(self) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("distinct", InvocationArg::empty())
+    }
+
+    /// Sorts the elements of the stream according to their natural order.
+    pub fn sorted(self) -> errors::Result<JavaStream<'a>> {
+        self.intermediate("sorted", InvocationArg::empty())
+    }
+
+    /// Executes the pipeline and transforms its elements to a Rust `Vec`.
+    pub fn collect_to_vec<T>(self) -> errors::Result<Vec<T>>
+    where
+        T: DeserializeOwned + Any,
+    {
+        self.jvm.invoke_to_rust(&self.stream, "toArray", InvocationArg::empty())
+    }
+
+    /// Executes the pipeline and returns the number of its elements.
+    pub fn count(self) -> errors::Result<i64> {
+        self.jvm.invoke_to_rust(&self.stream, "count", InvocationArg::empty())
+    }
+
+    /// Returns the `Instance` of the Java `Stream`, for applying operations that are not provided by the `JavaStream`.
+    pub fn into_instance(self) -> Instance {
+        self.stream
+    }
+
+    fn intermediate(self, method_name: &str, inv_args: &[InvocationArg]) -> errors::Result<JavaStream<'a>> {
+        let stream = self.jvm.invoke(&self.stream, method_name, inv_args)?;
+        Ok(JavaStream {
+            jvm: self.jvm,
+            stream: self.jvm.cast(&stream, CLASS_STREAM)?,
+        })
+    }
+}
+
+impl Jvm {
+    /// Creates a `JavaStream` from a Java `java.util.stream.Stream` or `java.util.Collection`.
+    pub fn java_stream(&self, instance: &Instance) -> errors::Result<JavaStream<'_>> {
+        let stream = if self.is_instance_of(instance, CLASS_STREAM)? {
+            self.cast(instance, CLASS_STREAM)?
+        } else if self.is_instance_of(instance, CLASS_COLLECTION)? {
+            let collection = self.cast(instance, CLASS_COLLECTION)?;
+            let stream = self.invoke(&collection, "stream", InvocationArg::empty())?;
+            self.cast(&stream, CLASS_STREAM)?
+        } else {
+            return Err(J4RsError::GeneralError(format!(
+                "Cannot create a stream from an instance of {}",
+                self.get_class_name(instance)?
+            )));
+        };
+        Ok(JavaStream { jvm: self, stream })
+    }
+}
+
+#[cfg(test)]
+mod stream_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn stream_pipeline() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let list = jvm.java_list("java.lang.Long", vec![5_i64, 1, 4, 2, 3, 4])?;
+
+        let result: Vec<i64> = jvm
+            .java_stream(&list)?
+            .filter(|x: i64| x % 2 == 0)?
+            .map(|x: i64| x * 10)?
+            .distinct()?
+            .sorted()?
+            .collect_to_vec()?;
+        assert_eq!(result, vec![20, 40]);
+
+        let count = jvm.java_stream(&list)?.skip(1)?.limit(3)?.count()?;
+        assert_eq!(count, 3);
+
+        let strings = jvm.java_list("java.lang.String", vec!["a", "b"])?;
+        let identity = jvm.invoke_static("java.util.function.Function", "identity", InvocationArg::empty())?;
+        let result: Vec<String> = jvm
+            .java_stream(&strings)?
+            .map_with(InvocationArg::from(identity))?
+            .collect_to_vec()?;
+        assert_eq!(result, vec!["a", "b"]);
+
+        let object = jvm.create_instance("java.lang.Object", InvocationArg::empty())?;
+        assert!(jvm.java_stream(&object).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::cell::RefCell;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use jni_sys::{JNIEnv, JNI_OK, JNI_VERSION_1_6};
+
+use crate::errors;
+use crate::logger::{debug, error};
+use crate::{InvocationArg, Jvm};
+
+thread_local! {
+    // The Jvm that keeps a thread of a pool attached, between the start and the exit handlers of a JavaThreadFactory.
+    static POOL_THREAD_JVM: RefCell<Option<Jvm>> = const { RefCell::new(None) };
+}
+
+/// Creates Rust threads that are attached to the Java VM for as long as they run.
+///
+/// The threads can be spawned directly, or be created by a thread pool that calls the
+/// `start_handler` and the `exit_handler` of the factory in each of its threads,
+/// like the `rayon::ThreadPoolBuilder` does.
+#[derive(Debug, Clone)]
+pub struct JavaThreadFactory {
+    name_prefix: String,
+    daemon: bool,
+    counter: Arc<AtomicUsize>,
+}
+
+impl JavaThreadFactory {
+    /// Creates a factory of threads that are named `name_prefix` followed by their index, e.g. `worker-0`, `worker-1` etc.
+    pub fn new(name_prefix: &str) -> JavaThreadFactory {
+        JavaThreadFactory {
+            name_prefix: name_prefix.to_string(),
+            daemon: false,
+            counter: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Defines whether the threads are attached as daemon threads, which do not prevent the Java VM from shutting down.
+    pub fn daemon(mut self, daemon: bool) -> JavaThreadFactory {
+        self.daemon = daemon;
+        self
+    }
+
+    /// Spawns a thread that is attached to the Java VM and calls `f` with its `Jvm`.
+    ///
+    /// The thread is detached when `f` returns. The result of `f`, or the error of attaching the thread,
+    /// is returned by the join of the returned handle.
+    pub fn spawn<R, F>(&self, f: F) -> errors::Result<thread::JoinHandle<errors::Result<R>>>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+    {
+        let index = self.counter.fetch_add(1, Ordering::SeqCst);
+        spawn_attached(format!("{}-{}", self.name_prefix, index), self.daemon, f)
+    }
+
+    /// Returns a handler that attaches the thread that calls it to the Java VM, until the `exit_handler` is called.
+    ///
+    /// Threads that are already attached, e.g. because they are created by Java, are left as they are
+    /// and are not detached by the `exit_handler`.
+    pub fn start_handler(&self) -> impl Fn(usize) + Send + Sync + 'static {
+        let daemon = self.daemon;
+        move |index| {
+            let attached = if is_current_thread_attached() {
+                Jvm::attach_thread_with_no_detach_on_drop()
+            } else if daemon {
+                Jvm::attach_thread_as_daemon()
+            } else {
+                Jvm::attach_thread()
+            };
+            match attached {
+                Ok(jvm) => {
+                    if let Some(name) = thread::current().name() {
+                        if let Err(e) = set_java_thread_name(&jvm, name) {
+                            error(&format!("Could not set the name of the pool thread {}: {}", index, e));
+                        }
+                    }
+                    POOL_THREAD_JVM.with(|opt| *opt.borrow_mut() = Some(jvm))
+                }
+                Err(e) => error(&format!("Could not attach the pool thread {} to the JVM: {}", index, e)),
+            }
+        }
+    }
+
+    /// Returns a handler that detaches the thread that calls it, if it was attached by the `start_handler`.
+    pub fn exit_handler(&self) -> impl Fn(usize) + Send + Sync + 'static {
+        move |index| {
+            debug(&format!("Detaching the pool thread {}", index));
+            POOL_THREAD_JVM.with(|opt| drop(opt.borrow_mut().take()));
+        }
+    }
+}
+
+impl Jvm {
+    /// Spawns a thread named `name` that is attached to the Java VM and calls `f` with its `Jvm`.
+    ///
+    /// The thread is detached when `f` returns. The result of `f`, or the error of attaching the thread,
+    /// is returned by the join of the returned handle.
+    pub fn spawn_attached<R, F>(&self, name: &str, f: F) -> errors::Result<thread::JoinHandle<errors::Result<R>>>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+    {
+        spawn_attached(name.to_string(), false, f)
+    }
+
+    /// Like `spawn_attached`, but the thread is attached as a daemon thread, which does not prevent the Java VM from shutting down.
+    pub fn spawn_attached_as_daemon<R, F>(&self, name: &str, f: F) -> errors::Result<thread::JoinHandle<errors::Result<R>>>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+    {
+        spawn_attached(name.to_string(), true, f)
+    }
+}
+
+fn spawn_attached<R, F>(name: String, daemon: bool, f: F) -> errors::Result<thread::JoinHandle<errors::Result<R>>>
+where
+    R: Send + 'static,
+    F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+{
+    let handle = thread::Builder::new().name(name.clone()).spawn(move || {
+        let jvm = if daemon {
+            Jvm::attach_thread_as_daemon()?
+        } else {
+            Jvm::attach_thread()?
+        };
+        set_java_thread_name(&jvm, &name)?;
+        // The Jvm is dropped, and the thread is detached, after f returns
+        f(&jvm)
+    })?;
+    Ok(handle)
+}
+
+// Gives the Java thread of the current thread the same name as the Rust one
+fn set_java_thread_name(jvm: &Jvm, name: &str) -> errors::Result<()> {
+    let thread = jvm.invoke_static("java.lang.Thread", "currentThread", InvocationArg::empty())?;
+    jvm.invoke(&thread, "setName", &[InvocationArg::try_from(name)?])?;
+    Ok(())
+}
+
+// Returns true if the current thread is attached to the Java VM, by j4rs or by anyone else
+fn is_current_thread_attached() -> bool {
+    match Jvm::created_java_vm() {
+        Some(java_vm) => {
+            let mut jni_env: *mut JNIEnv = ptr::null_mut();
+            let result = unsafe {
+                ((**java_vm).v1_4.GetEnv)(
+                    java_vm,
+                    (&mut jni_env as *mut *mut JNIEnv) as *mut *mut c_void,
+                    JNI_VERSION_1_6,
+                )
+            };
+            result == JNI_OK
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod threads_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn spawn_attached_threads() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let handle = jvm.spawn_attached("j4rs-test-attached", |jvm| {
+            let thread = jvm.invoke_static("java.lang.Thread", "currentThread", InvocationArg::empty())?;
+            let name = jvm.invoke(&thread, "getName", InvocationArg::empty())?;
+            let is_daemon = jvm.invoke(&thread, "isDaemon", InvocationArg::empty())?;
+            Ok((jvm.to_rust::<String>(name)?, jvm.to_rust::<bool>(is_daemon)?))
+        })?;
+        assert_eq!(handle.join().unwrap()?, ("j4rs-test-attached".to_string(), false));
+
+        let factory = JavaThreadFactory::new("j4rs-test-worker").daemon(true);
+        let handles = (0..2)
+            .map(|_| {
+                factory.spawn(|jvm| {
+                    let thread = jvm.invoke_static("java.lang.Thread", "currentThread", InvocationArg::empty())?;
+                    let is_daemon = jvm.invoke(&thread, "isDaemon", InvocationArg::empty())?;
+                    jvm.to_rust::<bool>(is_daemon)
+                })
+            })
+            .collect::<errors::Result<Vec<_>>>()?;
+        for handle in handles {
+            assert!(handle.join().unwrap()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn pool_handlers() -> errors::Result<()> {
+        let _jvm = create_tests_jvm()?;
+        let factory = JavaThreadFactory::new("j4rs-test-pool");
+        let start_handler = factory.start_handler();
+        let exit_handler = factory.exit_handler();
+        let attached = thread::spawn(move || {
+            start_handler(0);
+            let attached_during = is_current_thread_attached();
+            // A Jvm that is created and dropped in a task does not detach the thread
+            drop(Jvm::attach_thread());
+            let attached_after_task = is_current_thread_attached();
+            exit_handler(0);
+            (attached_during, attached_after_task, is_current_thread_attached())
+        })
+        .join()
+        .unwrap();
+        assert_eq!(attached, (true, true, false));
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use jni_sys::{jobject, JNI_TRUE};
+
+use crate::errors::opt_to_res;
+use crate::{cache, errors, jni_utils, utils};
+
+// Opt-in tracking of the Instances that hold JNI global references, for diagnosing memory leaks
+static TRACKING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    // The tracked jobjects of the Instances that are alive, along with their class names
+    static ref TRACKED_INSTANCES: Mutex<HashMap<usize, String>> = Mutex::new(HashMap::new());
+}
+
+pub(crate) fn set_enabled(enabled: bool) {
+    TRACKING_ENABLED.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        TRACKED_INSTANCES.lock().unwrap().clear();
+    }
+}
+
+pub(crate) fn is_enabled() -> bool {
+    TRACKING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts tracking the global reference of an `Instance`.
+pub(crate) fn register(jinstance: jobject, class_name: &str) {
+    if is_enabled() && !jinstance.is_null() {
+        let class_name = if class_name == cache::UNKNOWN_FOR_RUST {
+            java_class_name(jinstance).unwrap_or_else(|_| class_name.to_string())
+        } else {
+            class_name.to_string()
+        };
+        TRACKED_INSTANCES.lock().unwrap().insert(jinstance as usize, class_name);
+    }
+}
+
+/// Stops tracking the global reference of an `Instance`.
+pub(crate) fn deregister(jinstance: jobject) {
+    if is_enabled() {
+        TRACKED_INSTANCES.lock().unwrap().remove(&(jinstance as usize));
+    }
+}
+
+/// Returns the number of tracked `Instance`s per class name.
+pub(crate) fn stats() -> HashMap<String, usize> {
+    let mut stats = HashMap::new();
+    for class_name in TRACKED_INSTANCES.lock().unwrap().values() {
+        *stats.entry(class_name.clone()).or_insert(0) += 1;
+    }
+    stats
+}
+
+/// Retrieves the class name of the Java object that is wrapped by a `org.astonbitecode.j4rs.api.Instance`.
+fn java_class_name(jinstance: jobject) -> errors::Result<String> {
+    unsafe {
+        let jni_env = cache::get_thread_local_env()?;
+        // Make sure that the object is an Instance before calling its methods
+        let is_instance = ((**jni_env).v1_6.IsInstanceOf)(jni_env, jinstance, cache::get_java_instance_class()?);
+        if is_instance != JNI_TRUE {
+            return Err(errors::J4RsError::RustError("Not a j4rs Instance".to_string()));
+        }
+        let class_name_jstring = (opt_to_res(cache::get_jni_call_object_method())?)(
+            jni_env,
+            jinstance,
+            cache::get_get_object_class_name_method()?,
+        );
+        crate::Jvm::do_return(jni_env, ())?;
+        let chars = (opt_to_res(cache::get_jni_get_string_utf_chars())?)(jni_env, class_name_jstring, ptr::null_mut());
+        let class_name = utils::to_rust_string(chars);
+        (opt_to_res(cache::get_jni_release_string_utf_chars())?)(jni_env, class_name_jstring, chars);
+        jni_utils::delete_java_local_ref(jni_env, class_name_jstring);
+        class_name
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use crate::api::{Jvm, CLASS_TUPLE2, CLASS_TUPLE3};
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg};
+
+/// Rust tuples that can be transformed to the `org.astonbitecode.j4rs.api.dtos.Tuple2` and `Tuple3` Java classes.
+///
+/// The elements can be anything that can be transformed to an `InvocationArg`, including `Instance`s.
+/// Java tuples are serialized as JSON arrays, so they can be transformed back to Rust tuples with `Jvm::to_rust`.
+pub trait JavaTuple {
+    /// Creates an `Instance` of the respective Java tuple class that contains the elements of this tuple.
+    fn into_java_tuple(self, jvm: &Jvm) -> errors::Result<Instance>;
+}
+
+impl<A, B> JavaTuple for (A, B)
+where
+    A: TryInto<InvocationArg>,
+    J4RsError: From<A::Error>,
+    B: TryInto<InvocationArg>,
+    J4RsError: From<B::Error>,
+{
+    fn into_java_tuple(self, jvm: &Jvm) -> errors::Result<Instance> {
+        jvm.create_instance(CLASS_TUPLE2, &[self.0.try_into()?, self.1.try_into()?])
+    }
+}
+
+impl<A, B, C> JavaTuple for (A, B, C)
+where
+    A: TryInto<InvocationArg>,
+    J4RsError: From<A::Error>,
+    B: TryInto<InvocationArg>,
+    J4RsError: From<B::Error>,
+    C: TryInto<InvocationArg>,
+    J4RsError: From<C::Error>,
+{
+    fn into_java_tuple(self, jvm: &Jvm) -> errors::Result<Instance> {
+        jvm.create_instance(
+            CLASS_TUPLE3,
+            &[self.0.try_into()?, self.1.try_into()?, self.2.try_into()?],
+        )
+    }
+}
+
+impl InvocationArg {
+    /// Creates an InvocationArg that contains a `Tuple2` or a `Tuple3` Java object with the elements of the `tuple`.
+    ///
+    /// `TryFrom` cannot be implemented for all the tuples, as it is already implemented for `(&[T], &str)`.
+    pub fn from_tuple<T: JavaTuple>(tuple: T) -> errors::Result<InvocationArg> {
+        let jvm = Jvm::attach_thread_with_no_detach_on_drop()?;
+        Ok(InvocationArg::from(tuple.into_java_tuple(&jvm)?))
+    }
+}
+
+#[cfg(test)]
+mod tuple_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn tuples_to_java_and_back() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let pair = InvocationArg::from_tuple((1_i32, "one"))?.instance()?;
+        let first: i32 = jvm.to_rust(jvm.invoke(&pair, "getFirst", InvocationArg::empty())?)?;
+        assert_eq!(first, 1);
+        let pair: (i32, String) = jvm.to_rust(pair)?;
+        assert_eq!(pair, (1, "one".to_string()));
+
+        let list = jvm.java_list("java.lang.Long", vec![1_i64, 2])?;
+        let triple = InvocationArg::from_tuple((true, list, 2.5_f64))?.instance()?;
+        let triple: (bool, Vec<i64>, f64) = jvm.to_rust(triple)?;
+        assert_eq!(triple, (true, vec![1, 2], 2.5));
+        Ok(())
+    }
+}
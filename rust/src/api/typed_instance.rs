@@ -0,0 +1,196 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+/// A marker type for a Java class, to be used as the type parameter of `TypedInstance`.
+///
+/// Marker types are generated by the `BindingGenerator`, or declared by the user:
+///
+/// ```ignore
+/// struct ArrayList;
+///
+/// impl ClassMarker for ArrayList {
+///     const CLASS_NAME: &'static str = "java.util.ArrayList";
+/// }
+/// ```
+pub trait ClassMarker {
+    /// The fully qualified name of the Java class.
+    const CLASS_NAME: &'static str;
+}
+
+/// An `Instance` that is known to be of the Java class that is marked by `T`.
+///
+/// It dereferences to `Instance`, so it can be used wherever an `&Instance` is expected.
+pub struct TypedInstance<T: ClassMarker> {
+    instance: Instance,
+    marker: PhantomData<T>,
+}
+
+impl<T: ClassMarker> TypedInstance<T> {
+    fn new(instance: Instance) -> TypedInstance<T> {
+        TypedInstance {
+            instance,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the name of the Java class that is marked by `T`.
+    pub fn class_name(&self) -> &'static str {
+        T::CLASS_NAME
+    }
+
+    /// Returns the `Instance`, dropping the type information.
+    pub fn into_instance(self) -> Instance {
+        self.instance
+    }
+}
+
+impl<T: ClassMarker> Deref for TypedInstance<T> {
+    type Target = Instance;
+
+    fn deref(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl<T: ClassMarker> AsRef<Instance> for TypedInstance<T> {
+    fn as_ref(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl<T: ClassMarker> From<TypedInstance<T>> for Instance {
+    fn from(typed: TypedInstance<T>) -> Instance {
+        typed.instance
+    }
+}
+
+/// The argument is passed as an instance of the class that is marked by `T`, like a casted `Instance`.
+impl<T: ClassMarker> From<TypedInstance<T>> for InvocationArg {
+    fn from(typed: TypedInstance<T>) -> InvocationArg {
+        InvocationArg::Java {
+            instance: typed.instance,
+            class_name: T::CLASS_NAME.to_string(),
+            serialized: false,
+        }
+    }
+}
+
+/// Succeeds only if the `Instance` was created or cast as the class that is marked by `T`,
+/// or if the runtime class of its Java object is exactly that class.
+/// Use `Jvm::cast_typed` for subclasses and implementations of interfaces.
+impl<T: ClassMarker> TryFrom<Instance> for TypedInstance<T> {
+    type Error = J4RsError;
+
+    fn try_from(mut instance: Instance) -> errors::Result<TypedInstance<T>> {
+        if instance.class_name() == T::CLASS_NAME {
+            return Ok(TypedInstance::new(instance));
+        }
+        // The Instances that are returned by invocations do not know their class, so ask Java
+        let runtime_class_name = Jvm::attach_thread_with_no_detach_on_drop()?.get_class_name(&instance)?;
+        if runtime_class_name == T::CLASS_NAME {
+            instance.class_name = T::CLASS_NAME.to_string();
+            Ok(TypedInstance::new(instance))
+        } else {
+            Err(J4RsError::GeneralError(format!(
+                "Cannot use an instance of {} as {}",
+                runtime_class_name,
+                T::CLASS_NAME
+            )))
+        }
+    }
+}
+
+impl Jvm {
+    /// Creates an instance of the Java class that is marked by `T`.
+    pub fn create_typed_instance<T: ClassMarker>(
+        &self,
+        inv_args: &[impl Borrow<InvocationArg>],
+    ) -> errors::Result<TypedInstance<T>> {
+        Ok(TypedInstance::new(self.create_instance(T::CLASS_NAME, inv_args)?))
+    }
+
+    /// Casts the `from_instance` to the Java class that is marked by `T`.
+    ///
+    /// Fails if the Java object is not an instance of that class.
+    pub fn cast_typed<T: ClassMarker>(&self, from_instance: &Instance) -> errors::Result<TypedInstance<T>> {
+        Ok(TypedInstance::new(self.cast(from_instance, T::CLASS_NAME)?))
+    }
+}
+
+#[cfg(test)]
+mod typed_instance_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    struct ArrayList;
+
+    impl ClassMarker for ArrayList {
+        const CLASS_NAME: &'static str = "java.util.ArrayList";
+    }
+
+    struct List;
+
+    impl ClassMarker for List {
+        const CLASS_NAME: &'static str = "java.util.List";
+    }
+
+    fn size(jvm: &Jvm, list: &TypedInstance<List>) -> errors::Result<i32> {
+        jvm.to_rust(jvm.invoke(list, "size", InvocationArg::empty())?)
+    }
+
+    #[test]
+    fn typed_instances() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let array_list = jvm.create_typed_instance::<ArrayList>(InvocationArg::empty())?;
+        assert_eq!(array_list.class_name(), "java.util.ArrayList");
+        jvm.invoke(&array_list, "add", &[InvocationArg::try_from("one")?])?;
+
+        let list = jvm.cast_typed::<List>(&array_list)?;
+        assert_eq!(size(&jvm, &list)?, 1);
+        let copy = jvm.create_instance("java.util.ArrayList", &[InvocationArg::from(list)])?;
+        assert_eq!(jvm.to_rust::<Vec<String>>(copy)?, vec!["one".to_string()]);
+
+        let string = jvm.create_instance("java.lang.String", InvocationArg::empty())?;
+        assert!(jvm.cast_typed::<List>(&string).is_err());
+        assert!(TypedInstance::<ArrayList>::try_from(string).is_err());
+        let array_list: TypedInstance<ArrayList> = TypedInstance::try_from(array_list.into_instance())?;
+        let instance: Instance = array_list.into();
+        assert!(TypedInstance::<List>::try_from(instance).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn typed_instances_from_invocations() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let array_list = jvm.create_typed_instance::<ArrayList>(InvocationArg::empty())?;
+        jvm.invoke(&array_list, "add", &[InvocationArg::try_from("one")?])?;
+
+        let cloned = jvm.invoke(&array_list, "clone", InvocationArg::empty())?;
+        let cloned: TypedInstance<ArrayList> = TypedInstance::try_from(cloned)?;
+        assert_eq!(cloned.instance.class_name(), "java.util.ArrayList");
+        assert_eq!(jvm.to_rust::<Vec<String>>(jvm.clone_instance(&cloned)?)?, vec!["one".to_string()]);
+
+        let cloned = jvm.invoke(&array_list, "clone", InvocationArg::empty())?;
+        assert!(TypedInstance::<List>::try_from(cloned).is_err());
+        Ok(())
+    }
+}
@@ -11,8 +11,11 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::fs;
 use std::os::raw::c_void;
-use std::path::MAIN_SEPARATOR;
+use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 use java_locator::{get_jvm_dyn_lib_file_name, locate_jvm_dyn_library};
 use jni_sys::{jclass, jint, jsize, JNIEnv, JavaVM};
@@ -28,14 +31,25 @@ type JNICreateJavaVM = unsafe extern "system" fn(
     args: *mut c_void,
 ) -> jint;
 
+// Set when the jvm dynamic library gets loaded
+static JVM_LIB_LOADED: AtomicBool = AtomicBool::new(false);
+
 lazy_static! {
+    // The path of the jvm dynamic library to load instead of the located one, or the path of the loaded library
+    static ref JVM_LIB_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
     static ref JVM_LIB: libloading::Library = {
-        let full_path = format!(
-            "{}{}{}",
-            locate_jvm_dyn_library().expect("Could not find the jvm dynamic library"),
-            MAIN_SEPARATOR,
-            get_jvm_dyn_lib_file_name()
-        );
+        let mut jvm_lib_path = JVM_LIB_PATH.lock().unwrap();
+        let full_path = match jvm_lib_path.clone() {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => format!(
+                "{}{}{}",
+                locate_jvm_dyn_library().expect("Could not find the jvm dynamic library"),
+                MAIN_SEPARATOR,
+                get_jvm_dyn_lib_file_name()
+            ),
+        };
+        *jvm_lib_path = Some(PathBuf::from(&full_path));
+        JVM_LIB_LOADED.store(true, Ordering::SeqCst);
         unsafe {
             libloading::Library::new(full_path).expect("Could not load the jvm dynamic library")
         }
@@ -52,6 +66,35 @@ lazy_static! {
     };
 }
 
+#[cfg(feature = "provision-jre")]
+pub(crate) fn jvm_dyn_lib_loaded() -> bool {
+    JVM_LIB_LOADED.load(Ordering::SeqCst)
+}
+
+#[cfg(feature = "provision-jre")]
+pub(crate) fn jvm_dyn_lib_located() -> bool {
+    locate_jvm_dyn_library().is_ok()
+}
+
+/// Sets the path of the jvm dynamic library to load, instead of locating it.
+///
+/// Fails if another jvm dynamic library is already loaded.
+pub(crate) fn set_jvm_dyn_lib_path(path: PathBuf) -> errors::Result<()> {
+    let mut jvm_lib_path = JVM_LIB_PATH.lock()?;
+    if JVM_LIB_LOADED.load(Ordering::SeqCst) {
+        let loaded = jvm_lib_path.clone().unwrap_or_default();
+        if fs::canonicalize(&loaded).ok() != fs::canonicalize(&path).ok() {
+            return Err(errors::J4RsError::GeneralError(format!(
+                "Cannot load {:?}, because the jvm dynamic library {:?} is already loaded",
+                path, loaded
+            )));
+        }
+    } else {
+        *jvm_lib_path = Some(path);
+    }
+    Ok(())
+}
+
 pub(crate) fn get_created_java_vms(
     vm_buf: &mut Vec<*mut JavaVM>,
     buf_len: jsize,
@@ -43,6 +43,21 @@ pub fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jclass> {
 
 #[cfg(all(not(feature = "no-runtime-libloading"), not(target_os = "android")))]
 pub fn cache_classloader_of(_env: *mut JNIEnv, _obj: jobject) -> errors::Result<()> {Ok(())}
+
+#[cfg(all(feature = "provision-jre", not(feature = "no-runtime-libloading"), not(target_os = "android")))]
+pub(crate) fn jvm_dyn_lib_loaded() -> bool {
+    generic::jvm_dyn_lib_loaded()
+}
+
+#[cfg(all(feature = "provision-jre", not(feature = "no-runtime-libloading"), not(target_os = "android")))]
+pub(crate) fn jvm_dyn_lib_located() -> bool {
+    generic::jvm_dyn_lib_located()
+}
+
+#[cfg(all(not(feature = "no-runtime-libloading"), not(target_os = "android")))]
+pub(crate) fn set_jvm_dyn_lib_path(path: std::path::PathBuf) -> errors::Result<()> {
+    generic::set_jvm_dyn_lib_path(path)
+}
 // ++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ //
 
 #[cfg(all(feature = "no-runtime-libloading", not(target_os = "android")))]
@@ -72,6 +87,24 @@ pub fn find_class(env: *mut JNIEnv, classname: &str) -> errors::Result<jclass> {
 
 #[cfg(all(feature = "no-runtime-libloading", not(target_os = "android")))]
 pub fn cache_classloader_of(_env: *mut JNIEnv, _obj: jobject) -> errors::Result<()> {Ok(())}
+
+// The jvm dynamic library is linked, so it cannot be loaded from a provisioned JRE
+#[cfg(all(feature = "provision-jre", any(feature = "no-runtime-libloading", target_os = "android")))]
+pub(crate) fn jvm_dyn_lib_loaded() -> bool {
+    true
+}
+
+#[cfg(all(feature = "provision-jre", any(feature = "no-runtime-libloading", target_os = "android")))]
+pub(crate) fn jvm_dyn_lib_located() -> bool {
+    true
+}
+
+#[cfg(any(feature = "no-runtime-libloading", target_os = "android"))]
+pub(crate) fn set_jvm_dyn_lib_path(_path: std::path::PathBuf) -> errors::Result<()> {
+    Err(errors::J4RsError::GeneralError(
+        "The jvm dynamic library is linked and cannot be loaded from another Java installation".to_string(),
+    ))
+}
 // ++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ //
 
 #[cfg(target_os = "android")]
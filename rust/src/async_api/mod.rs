@@ -12,18 +12,200 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::ptr;
+use std::any::Any;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex, RwLock};
+use std::{ptr, thread};
 
 use jni_sys::{jobject, jstring};
 
 use futures::channel::oneshot;
+use futures::future::BoxFuture;
+use serde::de::DeserializeOwned;
 
 use crate::errors::opt_to_res;
-use crate::{cache, errors, jni_utils, Instance, InvocationArg, Jvm};
+use crate::{cache, errors, jni_utils, Instance, InvocationArg, Jvm, Null};
 
-use super::logger::debug;
+use super::logger::{debug, error};
+
+type Spawner = Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>;
+type Executor = Arc<dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync>;
+
+lazy_static! {
+    // The user-defined function that runs the futures of Jvm::spawn_completable_future
+    static ref SPAWNER: RwLock<Option<Spawner>> = RwLock::new(None);
+    // The user-defined function that runs the completions of the futures of Jvm::invoke_async
+    static ref EXECUTOR: RwLock<Option<Executor>> = RwLock::new(None);
+}
+
+/// Sets the function that runs the futures that are spawned by `Jvm::spawn_completable_future`,
+/// including the ones of the `async` functions that are annotated with `call_from_java`.
+///
+/// This way, the futures may run on the runtime of the application. For example, with `tokio`:
+///
+/// ```ignore
+/// let handle = tokio::runtime::Handle::current();
+/// j4rs::async_api::set_spawner(move |future| {
+///     handle.spawn(future);
+/// });
+/// ```
+///
+/// If no spawner is set, each future runs to completion in a new thread.
+pub fn set_spawner(spawner: impl Fn(BoxFuture<'static, ()>) + Send + Sync + 'static) {
+    *SPAWNER.write().unwrap() = Some(Arc::new(spawner));
+}
+
+fn spawn(future: BoxFuture<'static, ()>) {
+    let spawner = SPAWNER.read().unwrap().clone();
+    match spawner {
+        Some(spawner) => spawner(future),
+        None => {
+            thread::spawn(move || futures::executor::block_on(future));
+        }
+    }
+}
+
+/// Sets the function that runs the completions of the futures that are returned by `Jvm::invoke_async`
+/// and its variants, as well as the conversions of `Jvm::invoke_async_to_rust`.
+///
+/// By default, the futures are completed in the Java thread that completes the respective Java `Future`.
+/// With an executor, the Java thread only hands over the completion, so that it is not stalled
+/// by the wake-ups of the awaiting tasks or by converting big results to Rust values. For example, with `tokio`:
+///
+/// ```ignore
+/// let handle = tokio::runtime::Handle::current();
+/// j4rs::async_api::set_executor(move |task| {
+///     handle.spawn_blocking(task);
+/// });
+/// ```
+///
+/// The executor applies to the invocations that are made after it is set, until it is cleared with `clear_executor`.
+pub fn set_executor(executor: impl Fn(Box<dyn FnOnce() + Send>) + Send + Sync + 'static) {
+    *EXECUTOR.write().unwrap() = Some(Arc::new(executor));
+}
+
+/// Clears the executor that is set with `set_executor`.
+///
+/// The futures of the invocations that are made afterwards are completed in the Java threads again,
+/// while the ones of the invocations that were already made keep using the executor.
+pub fn clear_executor() {
+    *EXECUTOR.write().unwrap() = None;
+}
+
+fn execute(executor: Option<&Executor>, task: Box<dyn FnOnce() + Send>) {
+    match executor {
+        Some(executor) => executor(task),
+        None => task(),
+    }
+}
+
+/// The completion of a future of `Jvm::invoke_async`, whose address is passed to Java.
+///
+/// The executor is captured when the invocation is made, because the callbacks from Java
+/// may run in a different copy of the library, which does not share the statics.
+pub(crate) struct FutureCompletion {
+    sender: oneshot::Sender<errors::Result<Instance>>,
+    executor: Option<Executor>,
+}
+
+impl FutureCompletion {
+    fn new(sender: oneshot::Sender<errors::Result<Instance>>) -> FutureCompletion {
+        FutureCompletion {
+            sender,
+            executor: EXECUTOR.read().unwrap().clone(),
+        }
+    }
+
+    /// Completes the future with the `result`, using the executor, if any.
+    pub(crate) fn complete(self, result: errors::Result<Instance>) {
+        let FutureCompletion { sender, executor } = self;
+        execute(executor.as_ref(), Box::new(move || {
+            if sender.send(result).is_err() {
+                error("Could not send to the defined callback channel to complete the future");
+            }
+        }));
+    }
+}
 
 impl Jvm {
+    /// Spawns a Rust `future` and returns an `Instance` of a `java.util.concurrent.CompletableFuture`,
+    /// which completes with the result of the `future`, or completes exceptionally if the `future` returns an error.
+    ///
+    /// A `future` that returns `None` completes the `CompletableFuture` with `null`.
+    /// The `future` runs with the spawner that is defined with `async_api::set_spawner`.
+    pub fn spawn_completable_future<F, E>(&self, future: F) -> errors::Result<Instance>
+        where
+            F: Future<Output = Result<Option<Instance>, E>> + Send + 'static,
+            E: Display,
+    {
+        self.spawn_completing(future, |instance_opt| match instance_opt {
+            Some(instance) => Ok(InvocationArg::from(instance)),
+            None => InvocationArg::try_from(Null::Of("java.lang.Object")),
+        })
+    }
+
+    /// Converts a Rust `future` to an `Instance` of a `java.util.concurrent.CompletableFuture`, which Java code can await.
+    ///
+    /// The `CompletableFuture` completes with the value of the `future`, converted to a Java object like an `InvocationArg`,
+    /// or completes exceptionally if the `future` returns an error, or if the conversion fails.
+    /// The `future` runs with the spawner that is defined with `async_api::set_spawner`.
+    ///
+    /// For futures that return `Instance`s, see `spawn_completable_future`.
+    pub fn future_to_java<F, T, E>(&self, future: F) -> errors::Result<Instance>
+        where
+            F: Future<Output = Result<T, E>> + Send + 'static,
+            T: Send + 'static,
+            InvocationArg: TryFrom<T, Error = errors::J4RsError>,
+            E: Display,
+    {
+        self.spawn_completing(future, InvocationArg::try_from)
+    }
+
+    fn spawn_completing<F, T, E, C>(&self, future: F, to_inv_arg: C) -> errors::Result<Instance>
+        where
+            F: Future<Output = Result<T, E>> + Send + 'static,
+            E: Display,
+            C: FnOnce(T) -> errors::Result<InvocationArg> + Send + 'static,
+    {
+        debug("Spawning a future that completes a CompletableFuture");
+        let completable_future =
+            self.create_instance("java.util.concurrent.CompletableFuture", InvocationArg::empty())?;
+        let to_complete = self.clone_instance(&completable_future)?;
+        spawn(Box::pin(async move {
+            let result = future.await;
+            // The future may complete in any thread. The Jvm should outlive the Instances that are used
+            // for completing the CompletableFuture
+            let completion = Jvm::attach_thread()
+                .and_then(|jvm| jvm.complete_future(to_complete, result.map_err(|error| error.to_string()), to_inv_arg));
+            if let Err(e) = completion {
+                error(&format!("Could not complete the CompletableFuture: {}", e));
+            }
+        }));
+        Ok(completable_future)
+    }
+
+    fn complete_future<T>(
+        &self,
+        completable_future: Instance,
+        result: Result<T, String>,
+        to_inv_arg: impl FnOnce(T) -> errors::Result<InvocationArg>,
+    ) -> errors::Result<()> {
+        match result.and_then(|value| to_inv_arg(value).map_err(|error| error.to_string())) {
+            Ok(inv_arg) => {
+                self.invoke(&completable_future, "complete", &[inv_arg])?;
+            }
+            Err(error) => {
+                let exception = self.create_instance(
+                    "org.astonbitecode.j4rs.errors.InvocationException",
+                    &[InvocationArg::try_from(error)?],
+                )?;
+                self.invoke(&completable_future, "completeExceptionally", &[InvocationArg::from(exception)])?;
+            }
+        }
+        Ok(())
+    }
+
     /// Invokes the method `method_name` of a created `Instance` asynchronously, passing an array of `InvocationArg`s.
     /// It returns an `Instance` as the result of the invocation.
     pub async fn invoke_async(
@@ -48,6 +230,77 @@ impl Jvm {
         Self::do_return(self.jni_env, instance)?
     }
 
+    /// Invokes the method `method_name` of a created `Instance` asynchronously, passing an array of `InvocationArg`s.
+    /// It returns the result of the invocation, converted to a Rust value.
+    ///
+    /// The conversion runs with the executor that is defined with `async_api::set_executor`,
+    /// so that converting big results does not block the task that awaits.
+    pub async fn invoke_async_to_rust<T>(
+        &self,
+        instance: &Instance,
+        method_name: &str,
+        inv_args: &[InvocationArg],
+    ) -> errors::Result<T>
+        where
+            T: DeserializeOwned + Any + Send,
+    {
+        let instance = self.invoke_async(instance, method_name, inv_args).await?;
+        let (sender, rx) = oneshot::channel::<errors::Result<T>>();
+        let executor = EXECUTOR.read().unwrap().clone();
+        execute(executor.as_ref(), Box::new(move || {
+            let converted = Jvm::attach_thread().and_then(|jvm| jvm.to_rust(instance));
+            if sender.send(converted).is_err() {
+                error("Could not send the converted result of an asynchronous invocation");
+            }
+        }));
+        rx.await?
+    }
+
+    /// Runs the closure `f` in a thread of the Java `executor`, which is a `java.util.concurrent.Executor`, like an `ExecutorService`.
+    /// It returns an `Instance` of the result of `f`, transformed to an `InvocationArg`.
+    ///
+    /// This way, Rust work can be scheduled onto existing Java thread pools, along with any context that is bound to their threads.
+    /// If `f` returns an error, the returned error contains the stack trace of the respective Java exception.
+    pub async fn submit_to_executor<F, R>(&self, executor: &Instance, f: F) -> errors::Result<Instance>
+        where
+            F: FnOnce(&Jvm) -> errors::Result<R> + Send + 'static,
+            R: TryInto<InvocationArg, Error = errors::J4RsError>,
+    {
+        debug(&format!("Submitting a Rust closure to an executor of class {}", executor.class_name));
+        // The proxy may be called only once, by the CompletableFuture
+        let f = Mutex::new(Some(f));
+        let supplier = self.new_proxy("java.util.function.Supplier", move |jvm, _, _| {
+            let f = f.lock()?.take().ok_or_else(|| {
+                errors::J4RsError::GeneralError("The Rust closure of the executor is already called".to_string())
+            })?;
+            f(jvm)?.try_into().map(Some)
+        })?;
+        self.invoke_static_async(
+            "java.util.concurrent.CompletableFuture",
+            "supplyAsync",
+            &[InvocationArg::from(supplier), InvocationArg::from(self.clone_instance(executor)?)],
+        )
+        .await
+    }
+
+    /// Invokes the static method `method_name` of the class `class_name` asynchronously, passing an array of `InvocationArg`s.
+    /// It returns an `Instance` as the result of the invocation.
+    pub async fn invoke_static_async(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        inv_args: &[InvocationArg],
+    ) -> errors::Result<Instance> {
+        debug(&format!(
+            "Asynchronously invoking static method {} of class {} using {} arguments",
+            method_name,
+            class_name,
+            inv_args.len()
+        ));
+        let static_class = self.static_class(class_name)?;
+        self.invoke_async(&static_class, method_name, inv_args).await
+    }
+
     /// Invokes the method `method_name` of a created `Instance` asynchronously, passing an array of `InvocationArg`s.
     /// It returns an `Instance` as the result of the invocation.
     /// 
@@ -82,7 +335,7 @@ impl Jvm {
     }
 
     unsafe fn handle_channel_sender(s: &Jvm, sender: oneshot::Sender<errors::Result<Instance>>, instance: &Instance, method_name: &str, inv_args: &[InvocationArg]) -> errors::Result<()> {
-            let tx = Box::new(sender);
+            let tx = Box::new(FutureCompletion::new(sender));
             // First argument: the address of the channel Sender
             let raw_ptr = Box::into_raw(tx);
             // Find the address of tx
@@ -148,7 +401,7 @@ impl Jvm {
 mod api_unit_tests {
     use super::*;
     use crate::lib_unit_tests::create_tests_jvm;
-    use futures::Future;
+    use futures::{Future, StreamExt};
     use tokio;
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -186,6 +439,50 @@ mod api_unit_tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn invoke_async_with_executor() -> errors::Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static EXECUTED: AtomicUsize = AtomicUsize::new(0);
+
+        // Restores the executor of the other tests, even if this test fails
+        struct RestoreExecutor(Option<Executor>);
+        impl Drop for RestoreExecutor {
+            fn drop(&mut self) {
+                *EXECUTOR.write().unwrap() = self.0.take();
+            }
+        }
+        let _restore = RestoreExecutor(EXECUTOR.read().unwrap().clone());
+
+        // The executor applies to the other tests as well while it is set,
+        // so it should not depend on the runtime of this test
+        set_executor(|task| {
+            EXECUTED.fetch_add(1, Ordering::SeqCst);
+            thread::spawn(task);
+        });
+        let s_test = "j4rs_rust";
+        let jvm = create_tests_jvm()?;
+        let future_class = jvm.static_class("java.util.concurrent.CompletableFuture")?;
+        let string: String = jvm
+            .invoke_async_to_rust(&future_class, "completedFuture", &[InvocationArg::try_from(s_test)?])
+            .await?;
+        assert_eq!(s_test, string);
+        let exception = jvm.create_instance("java.lang.Exception", &[InvocationArg::try_from("Boom!")?])?;
+        let instance_result = jvm
+            .invoke_async(&future_class, "failedFuture", &[InvocationArg::from(exception)])
+            .await;
+        assert!(instance_result.is_err());
+        // The completion of each invocation and the conversion
+        assert!(EXECUTED.load(Ordering::SeqCst) >= 3);
+
+        clear_executor();
+        assert!(EXECUTOR.read().unwrap().is_none());
+        let string: String = jvm
+            .invoke_async_to_rust(&future_class, "completedFuture", &[InvocationArg::try_from(s_test)?])
+            .await?;
+        assert_eq!(s_test, string);
+        Ok(())
+    }
+
     #[async_std::test]
     async fn invoke_async_success_w_async_std() -> errors::Result<()> {
         let s_test = "j4rs_rust";
@@ -265,6 +562,64 @@ mod api_unit_tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn invoke_static_async_with_class_name() -> errors::Result<()> {
+        let s_test = "j4rs_rust";
+        let jvm = create_tests_jvm()?;
+        let instance = jvm
+            .invoke_static_async(
+                "org.astonbitecode.j4rs.tests.MyTest",
+                "getCompletableFutureStatic",
+                &[InvocationArg::try_from(s_test)?],
+            )
+            .await?;
+        let string: String = jvm.to_rust(instance)?;
+        assert_eq!(s_test, string);
+
+        let instance_result = jvm
+            .invoke_static_async(
+                "org.astonbitecode.j4rs.tests.MyTest",
+                "nonExistingMethod",
+                InvocationArg::empty(),
+            )
+            .await;
+        assert!(instance_result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn async_instance_receiver() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let my_second_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MySecondTest", InvocationArg::empty())?;
+        let mut stream = jvm
+            .invoke_to_channel(&my_second_test, "performCallbackFromTenThreads", InvocationArg::empty())?
+            .into_async();
+        for _ in 0..10 {
+            let instance = stream.next().await.unwrap()?;
+            let string: String = jvm.to_rust(instance)?;
+            assert_eq!(string, "THIS IS FROM CALLBACK TO A CHANNEL...");
+        }
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn invoke_to_stream() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let my_second_test = jvm.create_instance("org.astonbitecode.j4rs.tests.MySecondTest", InvocationArg::empty())?;
+        let instances: Vec<Instance> = jvm
+            .invoke_to_stream(&my_second_test, "performCallbackFromTenThreads", InvocationArg::empty())?
+            .take(10)
+            .map(|instance| instance.unwrap())
+            .collect()
+            .await;
+        assert_eq!(instances.len(), 10);
+        for instance in instances {
+            let string: String = jvm.to_rust(instance)?;
+            assert_eq!(string, "THIS IS FROM CALLBACK TO A CHANNEL...");
+        }
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn invoke_async_error_before_executing_async() -> errors::Result<()> {
         let s_test = "j4rs_rust";
@@ -293,6 +648,76 @@ mod api_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn spawn_completable_future() -> errors::Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static SPAWNED: AtomicUsize = AtomicUsize::new(0);
+
+        let jvm = create_tests_jvm()?;
+        let instance = jvm.create_instance("java.lang.Integer", &[InvocationArg::try_from(3)?.into_primitive()?])?;
+        let completable_future = jvm.spawn_completable_future(async move { Ok::<_, String>(Some(instance)) })?;
+        let result: i32 = jvm.to_rust(jvm.invoke(&completable_future, "get", InvocationArg::empty())?)?;
+        assert_eq!(result, 3);
+
+        let completable_future = jvm.spawn_completable_future(async move { Ok::<_, String>(None) })?;
+        let result: Option<i32> = jvm.to_rust(jvm.invoke(&completable_future, "get", InvocationArg::empty())?)?;
+        assert_eq!(result, None);
+
+        set_spawner(|future| {
+            SPAWNED.fetch_add(1, Ordering::SeqCst);
+            thread::spawn(move || futures::executor::block_on(future));
+        });
+        let completable_future = jvm.spawn_completable_future(async move { Err::<Option<Instance>, _>("Boom!") })?;
+        assert!(jvm.invoke(&completable_future, "get", InvocationArg::empty()).is_err());
+        let exceptionally: bool = jvm.to_rust(
+            jvm.invoke(&completable_future, "isCompletedExceptionally", InvocationArg::empty())?)?;
+        assert!(exceptionally);
+        assert!(SPAWNED.load(Ordering::SeqCst) > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn future_to_java() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let completable_future = jvm.future_to_java(async { Ok::<_, String>("from a Rust future".to_string()) })?;
+        let result: String = jvm.to_rust(jvm.invoke(&completable_future, "get", InvocationArg::empty())?)?;
+        assert_eq!(result, "from a Rust future");
+
+        let completable_future = jvm.future_to_java(async { Ok::<_, String>(42_i64) })?;
+        let result: i64 = jvm.to_rust(jvm.invoke(&completable_future, "join", InvocationArg::empty())?)?;
+        assert_eq!(result, 42);
+
+        let completable_future = jvm.future_to_java(async { Err::<i32, _>("Boom!") })?;
+        assert!(jvm.invoke(&completable_future, "get", InvocationArg::empty()).is_err());
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn submit_to_executor() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let executor = jvm.invoke_static(
+            "java.util.concurrent.Executors",
+            "newFixedThreadPool",
+            &[InvocationArg::try_from(2)?.into_primitive()?],
+        )?;
+        let thread_name = jvm
+            .submit_to_executor(&executor, |jvm| {
+                let thread = jvm.invoke_static("java.lang.Thread", "currentThread", InvocationArg::empty())?;
+                jvm.to_rust::<String>(jvm.invoke(&thread, "getName", InvocationArg::empty())?)
+            })
+            .await?;
+        let thread_name: String = jvm.to_rust(thread_name)?;
+        assert!(thread_name.starts_with("pool-"));
+
+        let res = jvm
+            .submit_to_executor(&executor, |_| Err::<String, _>(errors::J4RsError::GeneralError("Boom!".to_string())))
+            .await;
+        assert!(matches!(res, Err(errors::J4RsError::JavaError(ref stacktrace)) if stacktrace.contains("Boom!")));
+
+        jvm.invoke(&executor, "shutdown", InvocationArg::empty())?;
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn invoke_into_sendable_async_success() -> errors::Result<()> {
         let s_test = "j4rs_rust";
@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::{Any, TypeId};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
 use std::sync::Mutex;
 
 use jni_sys::{self, jarray, jboolean, jbooleanArray, jbyte, jbyteArray, jchar, jcharArray, jclass,
               jdouble, jdoubleArray, jfloat, jfloatArray, jint, jintArray, jlong, jlongArray,
-              jmethodID, JNIEnv, jobject, jobjectArray, jshort, jshortArray, jsize, jstring, jthrowable};
-use libc::c_char;
+              jmethodID, JNIEnv, jobject, jobjectArray, jshort, jshortArray, jsize, jstring, jthrowable,
+              JNI_TRUE};
+use libc::{c_char, c_void};
 
 use crate::errors::opt_to_res;
 use crate::logger::debug;
@@ -32,6 +36,10 @@ pub(crate) const INVO_BASE_NAME: &str = "org/astonbitecode/j4rs/api/InstanceBase
 pub(crate) const INVO_IFACE_NAME: &str = "org/astonbitecode/j4rs/api/Instance";
 pub(crate) const UNKNOWN_FOR_RUST: &str = "known_in_java_world";
 pub(crate) const J4RS_ARRAY: &str = "org.astonbitecode.j4rs.api.dtos.Array";
+pub(crate) const PREPARED_INVOCATION_CLASS_NAME: &str =
+    "org/astonbitecode/j4rs/api/invocation/PreparedInvocation";
+pub(crate) const INSTANCE_GENERATOR_CLASS_NAME: &str =
+    "org/astonbitecode/j4rs/api/invocation/InstanceGenerator";
 
 pub(crate) type JniGetMethodId = unsafe extern "system" fn(
     *mut jni_sys::JNIEnv,
@@ -92,6 +100,9 @@ pub(crate) type JniCallVoidMethod =
 #[allow(non_snake_case)]
 pub(crate) type JniCallStaticObjectMethod =
     unsafe extern "C" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, ...) -> jobject;
+#[allow(non_snake_case)]
+pub(crate) type JniCallStaticIntMethod =
+    unsafe extern "C" fn(env: *mut JNIEnv, obj: jobject, methodID: jmethodID, ...) -> jint;
 pub(crate) type JniGetArrayLength =
     unsafe extern "system" fn(env: *mut JNIEnv, array: jarray) -> jsize;
 
@@ -214,6 +225,17 @@ pub(crate) type JniThrowNew =
     unsafe extern "system" fn(_: *mut JNIEnv, _: jclass, _: *const c_char) -> jint;
 pub(crate) type JniIsSameObject =
     unsafe extern "system" fn(_: *mut JNIEnv, _: jobject, _: jobject) -> jboolean;
+pub(crate) type JniNewByteArray = unsafe extern "system" fn(_: *mut JNIEnv, _: jsize) -> jbyteArray;
+pub(crate) type JniSetByteArrayRegion =
+    unsafe extern "system" fn(_: *mut JNIEnv, _: jbyteArray, _: jsize, _: jsize, _: *const jbyte);
+pub(crate) type JniGetByteArrayRegion =
+    unsafe extern "system" fn(_: *mut JNIEnv, _: jbyteArray, _: jsize, _: jsize, _: *mut jbyte);
+pub(crate) type JniNewDirectByteBuffer =
+    unsafe extern "system" fn(_: *mut JNIEnv, _: *mut c_void, _: jlong) -> jobject;
+pub(crate) type JniGetDirectBufferAddress =
+    unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> *mut c_void;
+pub(crate) type JniGetDirectBufferCapacity =
+    unsafe extern "system" fn(_: *mut JNIEnv, _: jobject) -> jlong;
 
 const CLASS_CACHING_ENABLED: bool = !(cfg!(target_os = "android"));
 
@@ -224,6 +246,47 @@ lazy_static! {
     pub(crate) static ref JASSETS_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
 }
 
+// Set when the JavaVM is destroyed. A destroyed JavaVM cannot be created again in the same process.
+static JVM_DESTROYED: AtomicBool = AtomicBool::new(false);
+// Set while the JavaVM is being destroyed, so that it is not destroyed twice.
+static JVM_DESTROYING: AtomicBool = AtomicBool::new(false);
+
+/// A process-wide cache entry for a JNI value that is valid in all the threads,
+/// like a global reference of a class or a method ID.
+///
+/// It is set once and read without locking. This way, threads that get attached later
+/// do not need to resolve again the classes and methods that are already resolved by other threads.
+pub(crate) struct SharedCacheEntry(AtomicPtr<c_void>);
+
+impl SharedCacheEntry {
+    pub(crate) const fn new() -> SharedCacheEntry {
+        SharedCacheEntry(AtomicPtr::new(std::ptr::null_mut()))
+    }
+
+    pub(crate) fn get<T>(&self) -> Option<*mut T> {
+        let p = self.0.load(Ordering::Acquire);
+        if p.is_null() || is_jvm_destroyed() {
+            None
+        } else {
+            Some(p.cast())
+        }
+    }
+
+    /// Stores `j`, unless another thread stored a value first. Returns the value that is stored.
+    pub(crate) fn set<T>(&self, j: *mut T) -> *mut T {
+        if j.is_null() {
+            return j;
+        }
+        match self
+            .0
+            .compare_exchange(std::ptr::null_mut(), j.cast(), Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => j,
+            Err(existing) => existing.cast(),
+        }
+    }
+}
+
 thread_local! {
     pub(crate) static JNI_ENV: RefCell<Option<*mut JNIEnv>> = const { RefCell::new(None) };
     pub(crate) static ACTIVE_JVMS: RefCell<i32> = const { RefCell::new(0) };
@@ -244,6 +307,7 @@ thread_local! {
     pub(crate) static JNI_CALL_DOUBLE_METHOD: RefCell<Option<JniCallDoubleMethod>> = RefCell::new(None);
     pub(crate) static JNI_CALL_VOID_METHOD: RefCell<Option<JniCallVoidMethod>> = RefCell::new(None);
     pub(crate) static JNI_CALL_STATIC_OBJECT_METHOD: RefCell<Option<JniCallStaticObjectMethod>> = RefCell::new(None);
+    pub(crate) static JNI_CALL_STATIC_INT_METHOD: RefCell<Option<JniCallStaticIntMethod>> = RefCell::new(None);
     pub(crate) static JNI_GET_ARRAY_LENGTH: RefCell<Option<JniGetArrayLength>> = RefCell::new(None);
     pub(crate) static JNI_NEW_OBJECT_ARRAY: RefCell<Option<JniNewObjectArray>> = RefCell::new(None);
     pub(crate) static JNI_SET_OBJECT_ARRAY_ELEMENT: RefCell<Option<JniSetObjectArrayElement>> = RefCell::new(None);
@@ -256,20 +320,38 @@ thread_local! {
     pub(crate) static JNI_NEW_GLOBAL_REF: RefCell<Option<JniNewGlobalRef>> = RefCell::new(None);
     pub(crate) static JNI_THROW_NEW: RefCell<Option<JniThrowNew>> = RefCell::new(None);
     pub(crate) static JNI_IS_SAME_OBJECT: RefCell<Option<JniIsSameObject>> = RefCell::new(None);
+    pub(crate) static JNI_NEW_BYTE_ARRAY: RefCell<Option<JniNewByteArray>> = RefCell::new(None);
+    pub(crate) static JNI_SET_BYTE_ARRAY_REGION: RefCell<Option<JniSetByteArrayRegion>> = RefCell::new(None);
+    pub(crate) static JNI_GET_BYTE_ARRAY_REGION: RefCell<Option<JniGetByteArrayRegion>> = RefCell::new(None);
+    pub(crate) static JNI_NEW_DIRECT_BYTE_BUFFER: RefCell<Option<JniNewDirectByteBuffer>> = RefCell::new(None);
+    pub(crate) static JNI_GET_DIRECT_BUFFER_ADDRESS: RefCell<Option<JniGetDirectBufferAddress>> = RefCell::new(None);
+    pub(crate) static JNI_GET_DIRECT_BUFFER_CAPACITY: RefCell<Option<JniGetDirectBufferCapacity>> = RefCell::new(None);
     // This is the Utils class.
     pub(crate) static UTILS_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     // Utils throwableToString method
     pub(crate) static UTILS_THROWABLE_TO_STRING_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The Instance that stands for the results of void methods (Utils voidInvocationResult)
+    pub(crate) static VOID_INVOCATION_RESULT: RefCell<Option<jobject>> = const { RefCell::new(None) };
+    // Utils throwableToJson method
+    pub(crate) static UTILS_THROWABLE_TO_JSON_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // Utils throwableToInstance method
+    pub(crate) static UTILS_THROWABLE_TO_INSTANCE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // This is the factory class. It creates instances using reflection. Currently the `NativeInstantiationImpl`.
     pub(crate) static FACTORY_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    // The PreparedInvocation class
+    pub(crate) static PREPARED_INVOCATION_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     // The constructor method of the `NativeInstantiationImpl`.
     pub(crate) static FACTORY_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The method id of the `instantiate` method of the `NativeInstantiation`.
     pub(crate) static FACTORY_INSTANTIATE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `NativeInstantiationImpl.instantiateWithSignature` method
+    pub(crate) static FACTORY_INSTANTIATE_WITH_SIGNATURE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The method id of the `createForStatic` method of the `NativeInstantiation`.
     pub(crate) static FACTORY_CREATE_FOR_STATIC_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The method id of the `createJavaArray` method of the `NativeInstantiation`.
     pub(crate) static FACTORY_CREATE_JAVA_ARRAY_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `NativeInstantiationImpl.createJavaVarargsArray` method
+    pub(crate) static FACTORY_CREATE_JAVA_VARARGS_ARRAY_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The method id of the `createJavaList` method of the `NativeInstantiation`.
     pub(crate) static FACTORY_CREATE_JAVA_LIST_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The method id of the `createJavaMap` method of the `NativeInstantiation`.
@@ -280,10 +362,23 @@ thread_local! {
     pub(crate) static JAVA_INSTANCE_BASE_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     // The `Instance` class.
     pub(crate) static JAVA_INSTANCE_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    // The InstanceGenerator class and its method that creates Instances of Java objects
+    pub(crate) static INSTANCE_GENERATOR_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    pub(crate) static INSTANCE_GENERATOR_CREATE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The Java class for the `InvocationArg`.
     pub(crate) static INVOCATION_ARG_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     // The invoke method
     pub(crate) static INVOKE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `Instance.invokeWithSignature` method
+    pub(crate) static INVOKE_WITH_SIGNATURE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `Instance.invokeOnInterface` method
+    pub(crate) static INVOKE_ON_INTERFACE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `Instance.invokeWithTimeout` method
+    pub(crate) static INVOKE_WITH_TIMEOUT_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The invoke on EDT method
+    pub(crate) static INVOKE_ON_EDT_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The `Instance.invokeForResult` method
+    pub(crate) static INVOKE_FOR_RESULT_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The invoke static method
     pub(crate) static INVOKE_STATIC_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The invoke to channel method
@@ -292,8 +387,11 @@ thread_local! {
     pub(crate) static INVOKE_ASYNC_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The init callback channel method
     pub(crate) static INIT_CALLBACK_CHANNEL_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    pub(crate) static CLOSE_CALLBACK_CHANNEL_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The field method
     pub(crate) static FIELD_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The set field method
+    pub(crate) static SET_FIELD_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     pub(crate) static CLASS_TO_INVOKE_CLONE_AND_CAST: RefCell<Option<jclass>> = const { RefCell::new(None) };
     // The clone method
     pub(crate) static CLONE_STATIC_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
@@ -305,6 +403,16 @@ thread_local! {
     pub(crate) static CHECK_EQUALS_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The get object class name method
     pub(crate) static GET_OBJECT_CLASS_NAME_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The PreparedInvocation constructor
+    pub(crate) static PREPARED_INVOCATION_CONSTRUCTOR_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The PreparedInvocation invoke method
+    pub(crate) static PREPARED_INVOCATION_INVOKE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The getRuntimeClassName method
+    pub(crate) static GET_RUNTIME_CLASS_NAME_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The isInstanceOf method
+    pub(crate) static IS_INSTANCE_OF_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The getClassHierarchy method
+    pub(crate) static GET_CLASS_HIERARCHY_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The get object method
     pub(crate) static GET_OBJECT_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     // The invstatic ocation argument constructor method for objects created by Java
@@ -337,32 +445,145 @@ thread_local! {
     pub(crate) static DOUBLE_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     pub(crate) static INVOCATION_EXCEPTION_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     pub(crate) static STRING_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    // Object equality and hashing
+    pub(crate) static OBJECT_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    pub(crate) static OBJECT_EQUALS_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    pub(crate) static OBJECT_HASH_CODE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    pub(crate) static SYSTEM_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
+    pub(crate) static SYSTEM_IDENTITY_HASH_CODE_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     pub(crate) static CLASSLOADER_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     pub(crate) static GET_CLASSLOADER_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     pub(crate) static ANDROID_CONTEXT_WRAPPER_CLASS: RefCell<Option<jclass>> = const { RefCell::new(None) };
     pub(crate) static GET_CLASS_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
     pub(crate) static GET_LOAD_CLASS_METHOD: RefCell<Option<jmethodID>> = const { RefCell::new(None) };
+    // The method IDs that are resolved by `Jvm::invoke_primitive`, keyed by the method name and signature.
+    // Each method ID is stored along with a global ref of the class it was resolved for.
+    pub(crate) static PRIMITIVE_METHOD_IDS: RefCell<HashMap<String, Vec<(jclass, jmethodID)>>> = RefCell::new(HashMap::new());
+    // The strategies of `Jvm::to_rust`, keyed by the Rust type and, if needed, by the name of the Java class that is transformed to it.
+    static TO_RUST_STRATEGIES: RefCell<HashMap<TypeId, ToRustStrategies>> = RefCell::new(HashMap::new());
+}
+
+/// Transforms a Java object to a boxed Rust value directly, using JNI.
+pub(crate) type JObjectToAny = unsafe fn(jobject, *mut JNIEnv) -> errors::Result<Box<dyn Any>>;
+
+/// How `Jvm::to_rust` transforms the Java objects of a class to a Rust type.
+#[derive(Clone, Copy)]
+pub(crate) enum ToRustStrategy {
+    /// Directly, using JNI
+    Direct(JObjectToAny),
+    /// With a transformation that invokes methods of the object
+    Special(SpecialToRust),
+    /// By deserializing the JSON representation of the object
+    Deserialized,
+}
+
+/// The transformations of `Jvm::to_rust` that invoke methods of the Java objects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SpecialToRust {
+    BigNumber,
+    Unsigned,
+    Time,
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+/// The strategies of `Jvm::to_rust` for a Rust type.
+enum ToRustStrategies {
+    /// The objects of any Java class are deserialized, so their class is not needed
+    Deserialized,
+    /// The strategies for each Java class
+    ByClass(HashMap<String, ToRustStrategy>),
+}
+
+/// Returns true if the Java objects of any class are deserialized to the Rust type `t_type`,
+/// or `None` if this is not known yet.
+pub(crate) fn is_to_rust_deserialized_for_any_class(t_type: TypeId) -> Option<bool> {
+    TO_RUST_STRATEGIES.with(|strategies| {
+        strategies
+            .borrow()
+            .get(&t_type)
+            .map(|type_strategies| matches!(type_strategies, ToRustStrategies::Deserialized))
+    })
+}
+
+pub(crate) fn set_to_rust_deserialized_for_any_class(t_type: TypeId, deserialized: bool) {
+    TO_RUST_STRATEGIES.with(|strategies| {
+        let mut strategies = strategies.borrow_mut();
+        if deserialized {
+            strategies.insert(t_type, ToRustStrategies::Deserialized);
+        } else {
+            strategies
+                .entry(t_type)
+                .or_insert_with(|| ToRustStrategies::ByClass(HashMap::new()));
+        }
+    });
+}
+
+pub(crate) fn get_to_rust_strategy(t_type: TypeId, class_name: &str) -> Option<ToRustStrategy> {
+    TO_RUST_STRATEGIES.with(|strategies| match strategies.borrow().get(&t_type) {
+        Some(ToRustStrategies::Deserialized) => Some(ToRustStrategy::Deserialized),
+        Some(ToRustStrategies::ByClass(by_class)) => by_class.get(class_name).copied(),
+        None => None,
+    })
+}
+
+pub(crate) fn set_to_rust_strategy(t_type: TypeId, class_name: &str, strategy: ToRustStrategy) {
+    TO_RUST_STRATEGIES.with(|strategies| {
+        let mut strategies = strategies.borrow_mut();
+        let type_strategies = strategies
+            .entry(t_type)
+            .or_insert_with(|| ToRustStrategies::ByClass(HashMap::new()));
+        if let ToRustStrategies::ByClass(by_class) = type_strategies {
+            by_class.insert(class_name.to_string(), strategy);
+        }
+    });
 }
 
+// Retrieves a value from the thread local cache. If it is not there, it is taken from the process-wide cache,
+// or, if it is not there either, it is retrieved using `$do_retrieve` and stored in both caches.
+// If another thread stores its own value in the process-wide cache first, the retrieved value is passed to `$discard`.
 macro_rules! get_cached {
-    ($opt_name:ident, $do_retrieve:expr, $setter_name:ident) => {{
+    ($opt_name:ident, $do_retrieve:expr, $setter_name:ident) => {
+        get_cached!($opt_name, $do_retrieve, $setter_name, |_| {})
+    };
+    ($opt_name:ident, $do_retrieve:expr, $setter_name:ident, $discard:expr) => {{
+        static SHARED: SharedCacheEntry = SharedCacheEntry::new();
         let jopt = if CLASS_CACHING_ENABLED {
-            $opt_name.with(|opt| *opt.borrow())
+            $opt_name.with(|opt| *opt.borrow()).or_else(|| {
+                let shared = SHARED.get();
+                if let Some(j) = shared {
+                    $setter_name(j);
+                }
+                shared
+            })
         } else {
             None
         };
         if jopt.is_none() {
             let j = { $do_retrieve };
             if CLASS_CACHING_ENABLED {
-                $setter_name(j);
+                let stored = SHARED.set(j);
+                if stored != j {
+                    ($discard)(j);
+                }
+                $setter_name(stored);
+                Ok(stored)
+            } else {
+                Ok(j)
             }
-            Ok(j)
         } else {
             Ok(jopt.unwrap())
         }
     }};
 }
 
+// Deletes a global reference that was created for the cache, when another thread cached its own first
+fn delete_discarded_global_ref(j: jobject) {
+    if let Ok(env) = get_thread_local_env() {
+        jni_utils::delete_java_ref(env, j);
+    }
+}
+
 pub(crate) fn add_active_jvm() {
     ACTIVE_JVMS.with(|active_jvms| {
         let active_number = { *active_jvms.borrow() + 1 };
@@ -378,10 +599,216 @@ pub(crate) fn remove_active_jvm() -> i32 {
     })
 }
 
+pub(crate) fn set_jvm_destroyed() {
+    JVM_DESTROYED.store(true, Ordering::SeqCst);
+}
+
+pub(crate) fn is_jvm_destroyed() -> bool {
+    JVM_DESTROYED.load(Ordering::SeqCst)
+}
+
+/// Marks that the JavaVM is being destroyed. Returns false if it is already being destroyed.
+pub(crate) fn set_jvm_destroying() -> bool {
+    JVM_DESTROYING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_ok()
+}
+
+/// Marks that the JavaVM is not being destroyed anymore.
+pub(crate) fn unset_jvm_destroying() {
+    JVM_DESTROYING.store(false, Ordering::SeqCst);
+}
+
+/// Resolves the commonly used cached classes and methods, so that they are available to all the threads
+/// without any further lookups. The rest of them are still resolved lazily.
+pub(crate) fn warm_up() -> errors::Result<()> {
+    debug("Warming up the caches");
+    unsafe {
+        get_factory_class()?;
+        get_prepared_invocation_class()?;
+        get_utils_class()?;
+        get_utils_exception_to_string_method()?;
+        get_utils_throwable_to_json_method()?;
+        get_utils_throwable_to_instance_method()?;
+        get_invocation_arg_class()?;
+        get_factory_constructor_method()?;
+        get_factory_instantiate_method()?;
+        get_factory_instantiate_with_signature_method()?;
+        get_factory_create_for_static_method()?;
+        get_factory_create_java_array_method()?;
+        get_factory_create_java_list_method()?;
+        get_factory_create_java_map_method()?;
+        get_java_instance_class()?;
+        get_invoke_method()?;
+        get_invoke_static_method()?;
+        get_invoke_to_channel_method()?;
+        get_invoke_async_method()?;
+        get_init_callback_channel_method()?;
+        get_field_method()?;
+        get_set_field_method()?;
+        get_clone_static_method()?;
+        get_cast_static_method()?;
+        get_get_json_method()?;
+        get_check_equals_method()?;
+        get_get_object_class_name_method()?;
+        get_get_object_method()?;
+        get_inv_arg_java_constructor_method()?;
+        get_inv_arg_rust_constructor_method()?;
+        get_inv_arg_basic_rust_constructor_method()?;
+        get_class_to_invoke_clone_and_cast()?;
+        get_integer_class()?;
+        get_integer_constructor_method()?;
+        get_integer_to_int_method()?;
+        get_long_class()?;
+        get_invocation_exception_class()?;
+        get_long_constructor_method()?;
+        get_long_to_long_method()?;
+        get_short_class()?;
+        get_short_constructor_method()?;
+        get_short_to_short_method()?;
+        get_character_class()?;
+        get_character_constructor_method()?;
+        get_character_to_char_method()?;
+        get_byte_class()?;
+        get_byte_constructor_method()?;
+        get_byte_to_byte_method()?;
+        get_float_class()?;
+        get_float_constructor_method()?;
+        get_float_to_float_method()?;
+        get_double_class()?;
+        get_double_constructor_method()?;
+        get_double_to_double_method()?;
+        get_string_class()?;
+        get_object_class()?;
+        get_instance_generator_class()?;
+        get_instance_generator_create_method()?;
+        get_object_equals_method()?;
+        get_object_hash_code_method()?;
+        get_system_class()?;
+        get_system_identity_hash_code_method()?;
+        #[cfg(target_os = "android")]
+        {
+            get_classloader_class()?;
+            get_get_classloader_method()?;
+            get_load_class_method()?;
+        }
+        get_get_runtime_class_name_method()?;
+        get_is_instance_of_method()?;
+        get_get_class_hierarchy_method()?;
+        get_invoke_with_signature_method()?;
+        get_factory_create_java_varargs_array_method()?;
+        get_prepared_invocation_constructor_method()?;
+        get_prepared_invocation_invoke_method()?;
+        get_invoke_on_interface_method()?;
+        get_invoke_with_timeout_method()?;
+        get_invoke_on_edt_method()?;
+    }
+    Ok(())
+}
+
+// Resets the cached classes and method IDs of the current thread,
+// as they are not valid after the JavaVM is destroyed.
+pub(crate) fn invalidate_thread_local_cache() {
+    macro_rules! invalidate {
+        ($($cell:ident),*) => {
+            $($cell.with(|opt| *opt.borrow_mut() = None);)*
+        };
+    }
+    invalidate!(
+        UTILS_CLASS,
+        UTILS_THROWABLE_TO_STRING_METHOD,
+        VOID_INVOCATION_RESULT,
+        UTILS_THROWABLE_TO_JSON_METHOD,
+        UTILS_THROWABLE_TO_INSTANCE_METHOD,
+        FACTORY_CLASS,
+        PREPARED_INVOCATION_CLASS,
+        FACTORY_CONSTRUCTOR_METHOD,
+        FACTORY_INSTANTIATE_METHOD,
+        FACTORY_INSTANTIATE_WITH_SIGNATURE_METHOD,
+        FACTORY_CREATE_FOR_STATIC_METHOD,
+        FACTORY_CREATE_JAVA_ARRAY_METHOD,
+        FACTORY_CREATE_JAVA_VARARGS_ARRAY_METHOD,
+        FACTORY_CREATE_JAVA_LIST_METHOD,
+        FACTORY_CREATE_JAVA_MAP_METHOD,
+        JAVA_INSTANCE_BASE_CLASS,
+        JAVA_INSTANCE_CLASS,
+        INSTANCE_GENERATOR_CLASS,
+        INSTANCE_GENERATOR_CREATE_METHOD,
+        INVOCATION_ARG_CLASS,
+        INVOKE_METHOD,
+        INVOKE_WITH_SIGNATURE_METHOD,
+        INVOKE_ON_INTERFACE_METHOD,
+        INVOKE_WITH_TIMEOUT_METHOD,
+        INVOKE_ON_EDT_METHOD,
+        INVOKE_FOR_RESULT_METHOD,
+        INVOKE_STATIC_METHOD,
+        INVOKE_TO_CHANNEL_METHOD,
+        INVOKE_ASYNC_METHOD,
+        INIT_CALLBACK_CHANNEL_METHOD,
+        CLOSE_CALLBACK_CHANNEL_METHOD,
+        FIELD_METHOD,
+        SET_FIELD_METHOD,
+        CLASS_TO_INVOKE_CLONE_AND_CAST,
+        CLONE_STATIC_METHOD,
+        CAST_STATIC_METHOD,
+        GET_JSON_METHOD,
+        CHECK_EQUALS_METHOD,
+        GET_OBJECT_CLASS_NAME_METHOD,
+        PREPARED_INVOCATION_CONSTRUCTOR_METHOD,
+        PREPARED_INVOCATION_INVOKE_METHOD,
+        GET_RUNTIME_CLASS_NAME_METHOD,
+        IS_INSTANCE_OF_METHOD,
+        GET_CLASS_HIERARCHY_METHOD,
+        GET_OBJECT_METHOD,
+        INV_ARG_JAVA_CONSTRUCTOR_METHOD,
+        INV_ARG_RUST_CONSTRUCTOR_METHOD,
+        INV_ARG_BASIC_RUST_CONSTRUCTOR_METHOD,
+        INTEGER_CONSTRUCTOR_METHOD,
+        INTEGER_TO_INT_METHOD,
+        INTEGER_CLASS,
+        LONG_CONSTRUCTOR_METHOD,
+        LONG_TO_LONG_METHOD,
+        LONG_CLASS,
+        SHORT_CONSTRUCTOR_METHOD,
+        SHORT_TO_SHORT_METHOD,
+        SHORT_CLASS,
+        CHARACTER_CONSTRUCTOR_METHOD,
+        CHARACTER_TO_CHAR_METHOD,
+        CHARACTER_CLASS,
+        BYTE_CONSTRUCTOR_METHOD,
+        BYTE_TO_BYTE_METHOD,
+        BYTE_CLASS,
+        FLOAT_CONSTRUCTOR_METHOD,
+        FLOAT_TO_FLOAT_METHOD,
+        FLOAT_CLASS,
+        DOUBLE_CONSTRUCTOR_METHOD,
+        DOUBLE_TO_DOUBLE_METHOD,
+        DOUBLE_CLASS,
+        INVOCATION_EXCEPTION_CLASS,
+        STRING_CLASS,
+        OBJECT_CLASS,
+        OBJECT_EQUALS_METHOD,
+        OBJECT_HASH_CODE_METHOD,
+        SYSTEM_CLASS,
+        SYSTEM_IDENTITY_HASH_CODE_METHOD,
+        CLASSLOADER_CLASS,
+        GET_CLASSLOADER_METHOD,
+        ANDROID_CONTEXT_WRAPPER_CLASS,
+        GET_CLASS_METHOD,
+        GET_LOAD_CLASS_METHOD
+    );
+    PRIMITIVE_METHOD_IDS.with(|ids| ids.borrow_mut().clear());
+    TO_RUST_STRATEGIES.with(|strategies| strategies.borrow_mut().clear());
+    ACTIVE_JVMS.with(|active_jvms| *active_jvms.borrow_mut() = 0);
+    set_thread_local_env(None);
+}
+
 pub(crate) fn get_thread_local_env_opt() -> Option<*mut JNIEnv> {
-    JNI_ENV.with(
-        |existing_jni_env_opt| (*existing_jni_env_opt.borrow()),
-    )
+    // The thread local may be already destroyed, if an Instance is dropped while its thread exits
+    JNI_ENV
+        .try_with(|existing_jni_env_opt| *existing_jni_env_opt.borrow())
+        .ok()
+        .flatten()
 }
 
 pub(crate) fn set_thread_local_env(jni_env_opt: Option<*mut JNIEnv>) {
@@ -620,6 +1047,20 @@ pub(crate) fn get_jni_call_static_object_method() -> Option<JniCallStaticObjectM
     JNI_CALL_STATIC_OBJECT_METHOD.with(|opt| *opt.borrow())
 }
 
+pub(crate) fn set_jni_call_static_int_method(
+    j: Option<JniCallStaticIntMethod>,
+) -> Option<JniCallStaticIntMethod> {
+    debug("Called set_jni_call_static_int_method");
+    JNI_CALL_STATIC_INT_METHOD.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_call_static_int_method()
+}
+
+pub(crate) fn get_jni_call_static_int_method() -> Option<JniCallStaticIntMethod> {
+    JNI_CALL_STATIC_INT_METHOD.with(|opt| *opt.borrow())
+}
+
 pub(crate) fn set_jni_get_array_length(
     j: Option<JniGetArrayLength>,
 ) -> Option<JniGetArrayLength> {
@@ -775,6 +1216,104 @@ pub(crate) fn get_is_same_object() -> Option<JniIsSameObject> {
     JNI_IS_SAME_OBJECT.with(|opt| *opt.borrow())
 }
 
+pub(crate) unsafe fn get_primitive_method_id(
+    key: &str,
+    class: jclass,
+    jni_env: *mut JNIEnv,
+) -> errors::Result<Option<jmethodID>> {
+    let is_same_object = opt_to_res(get_is_same_object())?;
+    PRIMITIVE_METHOD_IDS.with(|opt| {
+        Ok(opt.borrow().get(key).and_then(|entries| {
+            entries
+                .iter()
+                .find(|(cached_class, _)| is_same_object(jni_env, *cached_class, class) == JNI_TRUE)
+                .map(|(_, method_id)| *method_id)
+        }))
+    })
+}
+
+pub(crate) fn add_primitive_method_id(key: String, global_class: jclass, method_id: jmethodID) {
+    debug("Called add_primitive_method_id");
+    PRIMITIVE_METHOD_IDS.with(|opt| {
+        opt.borrow_mut()
+            .entry(key)
+            .or_default()
+            .push((global_class, method_id));
+    });
+}
+
+pub(crate) fn set_jni_new_byte_array(j: Option<JniNewByteArray>) -> Option<JniNewByteArray> {
+    debug("Called set_jni_new_byte_array");
+    JNI_NEW_BYTE_ARRAY.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_new_byte_array()
+}
+
+pub(crate) fn get_jni_new_byte_array() -> Option<JniNewByteArray> {
+    JNI_NEW_BYTE_ARRAY.with(|opt| *opt.borrow())
+}
+
+pub(crate) fn set_jni_set_byte_array_region(j: Option<JniSetByteArrayRegion>) -> Option<JniSetByteArrayRegion> {
+    debug("Called set_jni_set_byte_array_region");
+    JNI_SET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_set_byte_array_region()
+}
+
+pub(crate) fn get_jni_set_byte_array_region() -> Option<JniSetByteArrayRegion> {
+    JNI_SET_BYTE_ARRAY_REGION.with(|opt| *opt.borrow())
+}
+
+pub(crate) fn set_jni_get_byte_array_region(j: Option<JniGetByteArrayRegion>) -> Option<JniGetByteArrayRegion> {
+    debug("Called set_jni_get_byte_array_region");
+    JNI_GET_BYTE_ARRAY_REGION.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_byte_array_region()
+}
+
+pub(crate) fn get_jni_get_byte_array_region() -> Option<JniGetByteArrayRegion> {
+    JNI_GET_BYTE_ARRAY_REGION.with(|opt| *opt.borrow())
+}
+
+pub(crate) fn set_jni_new_direct_byte_buffer(j: Option<JniNewDirectByteBuffer>) -> Option<JniNewDirectByteBuffer> {
+    debug("Called set_jni_new_direct_byte_buffer");
+    JNI_NEW_DIRECT_BYTE_BUFFER.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_new_direct_byte_buffer()
+}
+
+pub(crate) fn get_jni_new_direct_byte_buffer() -> Option<JniNewDirectByteBuffer> {
+    JNI_NEW_DIRECT_BYTE_BUFFER.with(|opt| *opt.borrow())
+}
+
+pub(crate) fn set_jni_get_direct_buffer_address(j: Option<JniGetDirectBufferAddress>) -> Option<JniGetDirectBufferAddress> {
+    debug("Called set_jni_get_direct_buffer_address");
+    JNI_GET_DIRECT_BUFFER_ADDRESS.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_direct_buffer_address()
+}
+
+pub(crate) fn get_jni_get_direct_buffer_address() -> Option<JniGetDirectBufferAddress> {
+    JNI_GET_DIRECT_BUFFER_ADDRESS.with(|opt| *opt.borrow())
+}
+
+pub(crate) fn set_jni_get_direct_buffer_capacity(j: Option<JniGetDirectBufferCapacity>) -> Option<JniGetDirectBufferCapacity> {
+    debug("Called set_jni_get_direct_buffer_capacity");
+    JNI_GET_DIRECT_BUFFER_CAPACITY.with(|opt| {
+        *opt.borrow_mut() = j;
+    });
+    get_jni_get_direct_buffer_capacity()
+}
+
+pub(crate) fn get_jni_get_direct_buffer_capacity() -> Option<JniGetDirectBufferCapacity> {
+    JNI_GET_DIRECT_BUFFER_CAPACITY.with(|opt| *opt.borrow())
+}
+
 pub(crate) fn set_factory_class(j: jclass) {
     debug("Called set_factory_class");
     FACTORY_CLASS.with(|opt| {
@@ -790,7 +1329,28 @@ pub(crate) fn get_factory_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, INST_CLASS_NAME)?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_factory_class
+        set_factory_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_prepared_invocation_class(j: jclass) {
+    debug("Called set_prepared_invocation_class");
+    PREPARED_INVOCATION_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_prepared_invocation_class() -> errors::Result<jclass> {
+    get_cached!(
+        PREPARED_INVOCATION_CLASS,
+        {
+            let env = get_thread_local_env()?;
+            let c = tweaks::find_class(env, PREPARED_INVOCATION_CLASS_NAME)?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_prepared_invocation_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -809,7 +1369,8 @@ pub(crate) fn get_utils_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, UTILS_CLASS_NAME)?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_utils_class
+        set_utils_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -845,6 +1406,112 @@ pub(crate) unsafe fn get_utils_exception_to_string_method() -> errors::Result<jm
     )
 }
 
+pub(crate) fn set_void_invocation_result(j: jobject) {
+    debug("Called set_void_invocation_result");
+    VOID_INVOCATION_RESULT.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_void_invocation_result() -> errors::Result<jobject> {
+    get_cached!(
+        VOID_INVOCATION_RESULT,
+        {
+            let env = get_thread_local_env()?;
+            let void_invocation_result_method_signature = format!("()L{};", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("voidInvocationResult");
+            let cstr2 = utils::to_c_string(&void_invocation_result_method_signature);
+            let j = unsafe {
+                let method_id = (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_utils_class()?,
+                    cstr1,
+                    cstr2,
+                );
+                let void_invocation_result = (opt_to_res(get_jni_call_static_object_method())?)(
+                    env,
+                    get_utils_class()?,
+                    method_id,
+                );
+                jni_utils::create_global_ref_from_local_ref(void_invocation_result, env)?
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_void_invocation_result,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_utils_throwable_to_json_method(j: jmethodID) {
+    debug("Called set_utils_throwable_to_json_method");
+    UTILS_THROWABLE_TO_JSON_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_utils_throwable_to_json_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        UTILS_THROWABLE_TO_JSON_METHOD,
+        {
+            let env = get_thread_local_env()?;
+            let throwable_to_json_method_signature = "(Ljava/lang/Throwable;)Ljava/lang/String;".to_string();
+            let cstr1 = utils::to_c_string("throwableToJson");
+            let cstr2 = utils::to_c_string(&throwable_to_json_method_signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_utils_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_utils_throwable_to_json_method
+    )
+}
+
+pub(crate) fn set_utils_throwable_to_instance_method(j: jmethodID) {
+    debug("Called set_utils_throwable_to_instance_method");
+    UTILS_THROWABLE_TO_INSTANCE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_utils_throwable_to_instance_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        UTILS_THROWABLE_TO_INSTANCE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+            let throwable_to_instance_method_signature = format!(
+                "(Ljava/lang/Throwable;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("throwableToInstance");
+            let cstr2 = utils::to_c_string(&throwable_to_instance_method_signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_utils_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_utils_throwable_to_instance_method
+    )
+}
+
 pub(crate) fn set_invocation_arg_class(j: jclass) {
     debug("Called set_invocation_arg_class");
     INVOCATION_ARG_CLASS.with(|opt| {
@@ -860,7 +1527,8 @@ pub(crate) fn get_invocation_arg_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "org/astonbitecode/j4rs/api/dtos/InvocationArg")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_invocation_arg_class
+        set_invocation_arg_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -928,6 +1596,41 @@ pub(crate) unsafe fn get_factory_instantiate_method() -> errors::Result<jmethodI
     )
 }
 
+pub(crate) fn set_factory_instantiate_with_signature_method(j: jmethodID) {
+    debug("Called set_factory_instantiate_with_signature_method");
+    FACTORY_INSTANTIATE_WITH_SIGNATURE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_factory_instantiate_with_signature_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        FACTORY_INSTANTIATE_WITH_SIGNATURE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+            let instantiate_method_signature = format!(
+                "(Ljava/lang/String;[Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("instantiateWithSignature");
+            let cstr2 = utils::to_c_string(&instantiate_method_signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_factory_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_factory_instantiate_with_signature_method
+    )
+}
+
 pub(crate) fn set_factory_create_for_static_method(j: jmethodID) {
     debug("Called set_factory_create_for_static_method");
     FACTORY_CREATE_FOR_STATIC_METHOD.with(|opt| {
@@ -1087,7 +1790,60 @@ pub(crate) fn get_java_instance_base_class() -> errors::Result<jclass> {
 
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_java_instance_base_class
+        set_java_instance_base_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_instance_generator_class(j: jclass) {
+    debug("Called set_instance_generator_class");
+    INSTANCE_GENERATOR_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_instance_generator_class() -> errors::Result<jclass> {
+    get_cached!(
+        INSTANCE_GENERATOR_CLASS,
+        {
+            let env = get_thread_local_env()?;
+            let c = tweaks::find_class(env, INSTANCE_GENERATOR_CLASS_NAME)?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_instance_generator_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_instance_generator_create_method(j: jmethodID) {
+    debug("Called set_instance_generator_create_method");
+    INSTANCE_GENERATOR_CREATE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_instance_generator_create_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INSTANCE_GENERATOR_CREATE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+            let create_method_signature = format!("(Ljava/lang/Object;Ljava/lang/Class;)L{};", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("create");
+            let cstr2 = utils::to_c_string(&create_method_signature);
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_instance_generator_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_instance_generator_create_method
     )
 }
 
@@ -1109,7 +1865,8 @@ pub(crate) fn get_java_instance_class() -> errors::Result<jclass> {
 
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_java_instance_class
+        set_java_instance_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -1291,6 +2048,39 @@ pub(crate) unsafe fn get_init_callback_channel_method() -> errors::Result<jmetho
     )
 }
 
+pub(crate) fn set_close_callback_channel_method(j: jmethodID) {
+    debug("Called set_close_callback_channel_method");
+    CLOSE_CALLBACK_CHANNEL_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_close_callback_channel_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        CLOSE_CALLBACK_CHANNEL_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let cstr1 = utils::to_c_string("closeCallbackChannel");
+            let cstr2 = utils::to_c_string("()V");
+            // Get the method ID for the `Instance.closeCallbackChannel`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_close_callback_channel_method
+    )
+}
+
 pub(crate) fn set_field_method(j: jmethodID) {
     debug("Called set_field_method");
     FIELD_METHOD.with(|opt| {
@@ -1325,6 +2115,41 @@ pub(crate) unsafe fn get_field_method() -> errors::Result<jmethodID> {
     )
 }
 
+pub(crate) fn set_set_field_method(j: jmethodID) {
+    debug("Called set_set_field_method");
+    SET_FIELD_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_set_field_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        SET_FIELD_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let set_field_method_signature =
+                "(Ljava/lang/String;Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)V";
+            let cstr1 = utils::to_c_string("setField");
+            let cstr2 = utils::to_c_string(set_field_method_signature);
+            // Get the method ID for the `Instance.setField`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_set_field_method
+    )
+}
+
 pub(crate) fn set_clone_static_method(j: jmethodID) {
     debug("Called set_clone_static_method");
     CLONE_STATIC_METHOD.with(|opt| {
@@ -1677,7 +2502,8 @@ pub(crate) fn get_integer_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Integer")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_integer_class
+        set_integer_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -1753,13 +2579,14 @@ pub(crate) fn get_long_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Long")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_long_class
+        set_long_class,
+        delete_discarded_global_ref
     )
 }
 
 pub(crate) fn set_invocation_exception_class(j: jclass) {
     debug("Called set_invocation_exception_class");
-    LONG_CLASS.with(|opt| {
+    INVOCATION_EXCEPTION_CLASS.with(|opt| {
         *opt.borrow_mut() = Some(j);
     });
 }
@@ -1773,7 +2600,8 @@ pub(crate) fn get_invocation_exception_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "org/astonbitecode/j4rs/errors/InvocationException")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_invocation_exception_class
+        set_invocation_exception_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -1849,7 +2677,8 @@ pub(crate) fn get_short_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Short")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_short_class
+        set_short_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -1925,7 +2754,8 @@ pub(crate) fn get_character_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Character")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_character_class
+        set_character_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -2001,7 +2831,8 @@ pub(crate) fn get_byte_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Byte")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_byte_class
+        set_byte_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -2079,7 +2910,8 @@ pub(crate) fn get_float_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Float")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_float_class
+        set_float_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -2161,7 +2993,8 @@ pub(crate) fn get_double_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/Double")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_double_class
+        set_double_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -2243,7 +3076,131 @@ pub(crate) fn get_string_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/String")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_string_class
+        set_string_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_object_class(j: jclass) {
+    debug("Called set_object_class");
+    OBJECT_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_object_class() -> errors::Result<jclass> {
+    get_cached!(
+        OBJECT_CLASS,
+        {
+            let env = get_thread_local_env()?;
+
+            let c = tweaks::find_class(env, "java/lang/Object")?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_object_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_object_equals_method(j: jmethodID) {
+    debug("Called set_object_equals_method");
+    OBJECT_EQUALS_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_object_equals_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        OBJECT_EQUALS_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let cstr1 = utils::to_c_string("equals");
+            let cstr2 = utils::to_c_string("(Ljava/lang/Object;)Z");
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(env, get_object_class()?, cstr1, cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_object_equals_method
+    )
+}
+
+pub(crate) fn set_object_hash_code_method(j: jmethodID) {
+    debug("Called set_object_hash_code_method");
+    OBJECT_HASH_CODE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_object_hash_code_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        OBJECT_HASH_CODE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let cstr1 = utils::to_c_string("hashCode");
+            let cstr2 = utils::to_c_string("()I");
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(env, get_object_class()?, cstr1, cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_object_hash_code_method
+    )
+}
+
+pub(crate) fn set_system_class(j: jclass) {
+    debug("Called set_system_class");
+    SYSTEM_CLASS.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) fn get_system_class() -> errors::Result<jclass> {
+    get_cached!(
+        SYSTEM_CLASS,
+        {
+            let env = get_thread_local_env()?;
+
+            let c = tweaks::find_class(env, "java/lang/System")?;
+            jni_utils::create_global_ref_from_local_ref(c, env)?
+        },
+        set_system_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_system_identity_hash_code_method(j: jmethodID) {
+    debug("Called set_system_identity_hash_code_method");
+    SYSTEM_IDENTITY_HASH_CODE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_system_identity_hash_code_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        SYSTEM_IDENTITY_HASH_CODE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let cstr1 = utils::to_c_string("identityHashCode");
+            let cstr2 = utils::to_c_string("(Ljava/lang/Object;)I");
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(env, get_system_class()?, cstr1, cstr2)
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_system_identity_hash_code_method
     )
 }
 
@@ -2264,7 +3221,8 @@ pub(crate) fn get_classloader_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "java/lang/ClassLoader")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_classloader_class
+        set_classloader_class,
+        delete_discarded_global_ref
     )
 }
 
@@ -2339,6 +3297,410 @@ pub(crate) fn get_android_context_wrapper_class() -> errors::Result<jclass> {
             let c = tweaks::find_class(env, "android/content/ContextWrapper")?;
             jni_utils::create_global_ref_from_local_ref(c, env)?
         },
-        set_android_context_wrapper_class
+        set_android_context_wrapper_class,
+        delete_discarded_global_ref
+    )
+}
+
+pub(crate) fn set_get_runtime_class_name_method(j: jmethodID) {
+    debug("Called set_get_runtime_class_name_method");
+    GET_RUNTIME_CLASS_NAME_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_get_runtime_class_name_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        GET_RUNTIME_CLASS_NAME_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let get_runtime_class_name_method_signature = "()Ljava/lang/String;";
+            let cstr1 = utils::to_c_string("getRuntimeClassName");
+            let cstr2 = utils::to_c_string(get_runtime_class_name_method_signature);
+
+            // Get the method ID for the `Instance.getRuntimeClassName`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_get_runtime_class_name_method
+    )
+}
+
+pub(crate) fn set_is_instance_of_method(j: jmethodID) {
+    debug("Called set_is_instance_of_method");
+    IS_INSTANCE_OF_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_is_instance_of_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        IS_INSTANCE_OF_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let is_instance_of_method_signature = "(Ljava/lang/String;)Z";
+            let cstr1 = utils::to_c_string("isInstanceOf");
+            let cstr2 = utils::to_c_string(is_instance_of_method_signature);
+
+            // Get the method ID for the `Instance.isInstanceOf`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_is_instance_of_method
+    )
+}
+
+pub(crate) fn set_get_class_hierarchy_method(j: jmethodID) {
+    debug("Called set_get_class_hierarchy_method");
+    GET_CLASS_HIERARCHY_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_get_class_hierarchy_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        GET_CLASS_HIERARCHY_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let get_class_hierarchy_method_signature = format!("()L{};", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("getClassHierarchy");
+            let cstr2 = utils::to_c_string(get_class_hierarchy_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.getClassHierarchy`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_get_class_hierarchy_method
+    )
+}
+
+pub(crate) fn set_invoke_with_signature_method(j: jmethodID) {
+    debug("Called set_invoke_with_signature_method");
+    INVOKE_WITH_SIGNATURE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_invoke_with_signature_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INVOKE_WITH_SIGNATURE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let invoke_with_signature_method_signature = format!(
+                "(Ljava/lang/String;[Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("invokeWithSignature");
+            let cstr2 = utils::to_c_string(invoke_with_signature_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.invokeWithSignature`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_invoke_with_signature_method
+    )
+}
+
+pub(crate) fn set_factory_create_java_varargs_array_method(j: jmethodID) {
+    debug("Called set_factory_create_java_varargs_array_method");
+    FACTORY_CREATE_JAVA_VARARGS_ARRAY_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_factory_create_java_varargs_array_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        FACTORY_CREATE_JAVA_VARARGS_ARRAY_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let factory_create_java_varargs_array_method_signature = format!(
+                "(Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("createJavaVarargsArray");
+            let cstr2 = utils::to_c_string(factory_create_java_varargs_array_method_signature.as_ref());
+
+            // Get the method ID for the `Utils.createJavaVarargsArray`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_static_method_id())?)(
+                    env,
+                    get_factory_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_factory_create_java_varargs_array_method
+    )
+}
+
+pub(crate) fn set_prepared_invocation_constructor_method(j: jmethodID) {
+    debug("Called set_prepared_invocation_constructor_method");
+    PREPARED_INVOCATION_CONSTRUCTOR_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_prepared_invocation_constructor_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        PREPARED_INVOCATION_CONSTRUCTOR_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let prepared_invocation_constructor_method_signature = format!("(L{};Ljava/lang/String;[Ljava/lang/String;)V", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("<init>");
+            let cstr2 = utils::to_c_string(prepared_invocation_constructor_method_signature.as_ref());
+
+            // Get the method ID for the `PreparedInvocation` constructor
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_prepared_invocation_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_prepared_invocation_constructor_method
+    )
+}
+
+pub(crate) fn set_prepared_invocation_invoke_method(j: jmethodID) {
+    debug("Called set_prepared_invocation_invoke_method");
+    PREPARED_INVOCATION_INVOKE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_prepared_invocation_invoke_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        PREPARED_INVOCATION_INVOKE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let prepared_invocation_invoke_method_signature = format!("([Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};", INVO_IFACE_NAME);
+            let cstr1 = utils::to_c_string("invoke");
+            let cstr2 = utils::to_c_string(prepared_invocation_invoke_method_signature.as_ref());
+
+            // Get the method ID for the `PreparedInvocation.invoke`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_prepared_invocation_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_prepared_invocation_invoke_method
+    )
+}
+
+pub(crate) fn set_invoke_on_interface_method(j: jmethodID) {
+    debug("Called set_invoke_on_interface_method");
+    INVOKE_ON_INTERFACE_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_invoke_on_interface_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INVOKE_ON_INTERFACE_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let invoke_on_interface_method_signature = format!(
+                "(Ljava/lang/String;Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("invokeOnInterface");
+            let cstr2 = utils::to_c_string(invoke_on_interface_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.invokeOnInterface`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_invoke_on_interface_method
+    )
+}
+
+pub(crate) fn set_invoke_with_timeout_method(j: jmethodID) {
+    debug("Called set_invoke_with_timeout_method");
+    INVOKE_WITH_TIMEOUT_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_invoke_with_timeout_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INVOKE_WITH_TIMEOUT_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let invoke_with_timeout_method_signature = format!(
+                "(JLjava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("invokeWithTimeout");
+            let cstr2 = utils::to_c_string(invoke_with_timeout_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.invokeWithTimeout`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_invoke_with_timeout_method
+    )
+}
+
+pub(crate) fn set_invoke_on_edt_method(j: jmethodID) {
+    debug("Called set_invoke_on_edt_method");
+    INVOKE_ON_EDT_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_invoke_on_edt_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INVOKE_ON_EDT_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let invoke_on_edt_method_signature = format!(
+                "(Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("invokeOnEdt");
+            let cstr2 = utils::to_c_string(invoke_on_edt_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.invokeOnEdt`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_invoke_on_edt_method
+    )
+}
+
+pub(crate) fn set_invoke_for_result_method(j: jmethodID) {
+    debug("Called set_invoke_for_result_method");
+    INVOKE_FOR_RESULT_METHOD.with(|opt| {
+        *opt.borrow_mut() = Some(j);
+    });
+}
+
+pub(crate) unsafe fn get_invoke_for_result_method() -> errors::Result<jmethodID> {
+    get_cached!(
+        INVOKE_FOR_RESULT_METHOD,
+        {
+            let env = get_thread_local_env()?;
+
+            let invoke_for_result_method_signature = format!(
+                "(Ljava/lang/String;[Lorg/astonbitecode/j4rs/api/dtos/InvocationArg;)L{};",
+                INVO_IFACE_NAME
+            );
+            let cstr1 = utils::to_c_string("invokeForResult");
+            let cstr2 = utils::to_c_string(invoke_for_result_method_signature.as_ref());
+
+            // Get the method ID for the `Instance.invokeForResult`
+            let j = unsafe {
+                (opt_to_res(get_jni_get_method_id())?)(
+                    env,
+                    get_java_instance_class()?,
+                    cstr1,
+                    cstr2,
+                )
+            };
+            utils::drop_c_string(cstr1);
+            utils::drop_c_string(cstr2);
+
+            j
+        },
+        set_invoke_for_result_method
     )
 }
@@ -0,0 +1,389 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::collections::{BTreeMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::api::reflection::CLASS_REFLECTION;
+use crate::logger::debug;
+use crate::{errors, InvocationArg, JavaMethod, Jvm};
+
+pub mod rt;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+    "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Generates Rust bindings with typed methods for Java classes.
+///
+/// For each class, the generated code contains the trait `<SimpleName>Methods`, which is implemented for `Instance`
+/// and has the public instance methods of the class, the struct `<SimpleName>Statics`, which has its public static
+/// methods, and the `ClassMarker` `<SimpleName>Class`, for `TypedInstance`s of the class. For example, for
+/// `java.lang.String`:
+///
+/// ```ignore
+/// let text = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a text")?])?;
+/// let sub: String = text.substring(&jvm, 2)?;
+/// let number: String = StringStatics::value_of_int(&jvm, 3)?;
+/// ```
+///
+/// The generator uses reflection, so the classes need to be in the classpath of the `jvm`. It is typically used
+/// in a build script, writing the bindings to the `OUT_DIR`, from where they are included in the crate.
+pub struct BindingGenerator<'a> {
+    jvm: &'a Jvm,
+    classes: Vec<String>,
+    annotation: Option<String>,
+}
+
+impl<'a> BindingGenerator<'a> {
+    /// Creates a generator that uses the `jvm` for reflection.
+    pub fn new(jvm: &'a Jvm) -> BindingGenerator<'a> {
+        BindingGenerator {
+            jvm,
+            classes: Vec::new(),
+            annotation: None,
+        }
+    }
+
+    /// Adds a class to generate bindings for.
+    pub fn class(mut self, class_name: &str) -> BindingGenerator<'a> {
+        self.classes.push(class_name.to_string());
+        self
+    }
+
+    /// Adds the public top-level classes of the jar `jar_path`, which needs to be in the classpath.
+    pub fn jar<P: AsRef<Path>>(mut self, jar_path: P) -> errors::Result<BindingGenerator<'a>> {
+        self.classes.extend(self.jvm.list_jar_classes(jar_path)?);
+        Ok(self)
+    }
+
+    /// Generates bindings only for the classes that are annotated with the runtime-visible annotation
+    /// `annotation_class`.
+    pub fn annotated_with(mut self, annotation_class: &str) -> BindingGenerator<'a> {
+        self.annotation = Some(annotation_class.to_string());
+        self
+    }
+
+    /// Generates the bindings.
+    pub fn generate(&self) -> errors::Result<String> {
+        let mut code = String::from(
+            "// Generated by j4rs. Do not edit.\n\nuse j4rs::codegen::rt;\nuse j4rs::errors::Result;\nuse j4rs::{ClassMarker, Instance, InvocationArg, Jvm};\n",
+        );
+        let mut generated = HashSet::new();
+        for class_name in &self.classes {
+            if !generated.insert(class_name) || !self.is_selected(class_name)? {
+                continue;
+            }
+            debug(&format!("Generating the bindings of {}", class_name));
+            let methods = self.jvm.list_methods(&self.jvm.static_class(class_name)?)?;
+            code.push_str(&class_bindings(class_name, &methods));
+        }
+        Ok(code)
+    }
+
+    /// Generates the bindings and writes them to the file `path`.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> errors::Result<()> {
+        fs::write(path, self.generate()?)?;
+        Ok(())
+    }
+
+    fn is_selected(&self, class_name: &str) -> errors::Result<bool> {
+        match &self.annotation {
+            Some(annotation) => {
+                let annotated = self.jvm.invoke_static(
+                    CLASS_REFLECTION,
+                    "hasAnnotation",
+                    &[InvocationArg::try_from(class_name)?, InvocationArg::try_from(annotation.as_str())?],
+                )?;
+                self.jvm.to_rust(annotated)
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+// The Java types, as the generated code passes and returns them
+enum JavaType {
+    Void,
+    Primitive(&'static str),
+    Boxed(&'static str),
+    Str,
+    Object,
+}
+
+impl JavaType {
+    fn of(class_name: &str) -> JavaType {
+        match class_name {
+            "void" => JavaType::Void,
+            "boolean" => JavaType::Primitive("bool"),
+            "byte" => JavaType::Primitive("i8"),
+            "short" => JavaType::Primitive("i16"),
+            "int" => JavaType::Primitive("i32"),
+            "long" => JavaType::Primitive("i64"),
+            "float" => JavaType::Primitive("f32"),
+            "double" => JavaType::Primitive("f64"),
+            "char" => JavaType::Primitive("char"),
+            "java.lang.Boolean" => JavaType::Boxed("bool"),
+            "java.lang.Byte" => JavaType::Boxed("i8"),
+            "java.lang.Short" => JavaType::Boxed("i16"),
+            "java.lang.Integer" => JavaType::Boxed("i32"),
+            "java.lang.Long" => JavaType::Boxed("i64"),
+            "java.lang.Float" => JavaType::Boxed("f32"),
+            "java.lang.Double" => JavaType::Boxed("f64"),
+            "java.lang.Character" => JavaType::Boxed("char"),
+            "java.lang.String" => JavaType::Str,
+            _ => JavaType::Object,
+        }
+    }
+
+    fn param_type(&self) -> &'static str {
+        match self {
+            JavaType::Primitive(rust_type) | JavaType::Boxed(rust_type) => rust_type,
+            JavaType::Str => "&str",
+            JavaType::Void | JavaType::Object => "&Instance",
+        }
+    }
+
+    fn arg(&self, param_name: &str) -> String {
+        match self {
+            JavaType::Primitive(rust_type) => format!("rt::primitive_arg::<{}>({})?", rust_type, param_name),
+            JavaType::Boxed(_) | JavaType::Str => format!("rt::value_arg::<{}>({})?", self.param_type(), param_name),
+            JavaType::Void | JavaType::Object => format!("rt::instance_arg(jvm, {})?", param_name),
+        }
+    }
+
+    fn return_type(&self) -> &'static str {
+        match self {
+            JavaType::Void => "()",
+            JavaType::Primitive(rust_type) | JavaType::Boxed(rust_type) => rust_type,
+            JavaType::Str => "String",
+            JavaType::Object => "Instance",
+        }
+    }
+
+    fn returned(&self) -> &'static str {
+        match self {
+            JavaType::Void => "Ok(())",
+            JavaType::Object => "Ok(result)",
+            _ => "jvm.to_rust(result)",
+        }
+    }
+}
+
+fn class_bindings(class_name: &str, methods: &[JavaMethod]) -> String {
+    let simple_name = class_name.rsplit('.').next().unwrap_or(class_name).replace('$', "_");
+    // The overloads of the methods, without the ones that only differ in their return types,
+    // which are bridges that the compiler generates
+    let mut overloads: BTreeMap<(bool, &str), Vec<&JavaMethod>> = BTreeMap::new();
+    for method in methods.iter().filter(|method| method.declaring_class() != "java.lang.Object") {
+        let same_name = overloads.entry((method.is_static(), method.name())).or_default();
+        match same_name.iter_mut().find(|m| m.parameter_types() == method.parameter_types()) {
+            Some(existing) if existing.return_type() == "java.lang.Object" => *existing = method,
+            Some(_) => {}
+            None => same_name.push(method),
+        }
+    }
+
+    let mut instance_methods = String::new();
+    let mut static_methods = String::new();
+    for ((is_static, _), mut same_name) in overloads {
+        same_name.sort_by(|a, b| {
+            (a.parameter_types().len(), a.parameter_types()).cmp(&(b.parameter_types().len(), b.parameter_types()))
+        });
+        let mut used_names = HashSet::new();
+        for (index, method) in same_name.iter().enumerate() {
+            let mut fn_name = snake_case(method.name());
+            if index > 0 {
+                for param_type in method.parameter_types() {
+                    fn_name.push('_');
+                    fn_name.push_str(&type_token(param_type));
+                }
+            }
+            while !used_names.insert(fn_name.clone()) {
+                fn_name.push('_');
+            }
+            if is_static {
+                static_methods.push_str(&method_binding(class_name, method, &fn_name));
+            } else {
+                instance_methods.push_str(&method_binding(class_name, method, &fn_name));
+            }
+        }
+    }
+
+    let mut code = String::new();
+    let _ = write!(
+        code,
+        "\n/// The marker type of `{}`.\npub struct {}Class;\n\nimpl ClassMarker for {}Class {{\n    const CLASS_NAME: &'static str = \"{}\";\n}}\n",
+        class_name, simple_name, simple_name, class_name
+    );
+    if !instance_methods.is_empty() {
+        let signatures: String = instance_methods
+            .lines()
+            .filter(|line| line.starts_with("    fn "))
+            .map(|line| format!("{};\n", line.trim_end_matches(" {")))
+            .collect();
+        let _ = write!(
+            code,
+            "\n/// The methods of `{}`.\npub trait {}Methods {{\n{}}}\n\nimpl {}Methods for Instance {{\n{}}}\n",
+            class_name, simple_name, signatures, simple_name, instance_methods
+        );
+    }
+    if !static_methods.is_empty() {
+        let static_methods = static_methods.replace("    fn ", "    pub fn ");
+        let _ = write!(
+            code,
+            "\n/// The static methods of `{}`.\npub struct {}Statics;\n\nimpl {}Statics {{\n{}}}\n",
+            class_name, simple_name, simple_name, static_methods
+        );
+    }
+    code
+}
+
+fn method_binding(class_name: &str, method: &JavaMethod, fn_name: &str) -> String {
+    let mut params = if method.is_static() {
+        String::from("jvm: &Jvm")
+    } else {
+        String::from("&self, jvm: &Jvm")
+    };
+    let mut args = Vec::new();
+    for (index, param_type) in method.parameter_types().iter().enumerate() {
+        let java_type = JavaType::of(param_type);
+        let _ = write!(params, ", arg{}: {}", index, java_type.param_type());
+        args.push(java_type.arg(&format!("arg{}", index)));
+    }
+    let return_type = JavaType::of(method.return_type());
+    let invocation = if method.is_static() {
+        format!("jvm.invoke_static(\"{}\", \"{}\"", class_name, method.name())
+    } else {
+        format!("jvm.invoke(self, \"{}\"", method.name())
+    };
+    let binding = if matches!(return_type, JavaType::Void) { "" } else { "let result = " };
+    let args = if args.is_empty() {
+        "InvocationArg::empty()".to_string()
+    } else {
+        format!("&[{}]", args.join(", "))
+    };
+    format!(
+        "    fn {}({}) -> Result<{}> {{\n        {}{}, {})?;\n        {}\n    }}\n",
+        rust_ident(fn_name),
+        params,
+        return_type.return_type(),
+        binding,
+        invocation,
+        args,
+        return_type.returned()
+    )
+}
+
+// A part of the names of overloaded methods that describes the type of a parameter
+fn type_token(param_type: &str) -> String {
+    match param_type.strip_prefix('[') {
+        Some(element_type) => {
+            let element = match element_type {
+                "Z" => "boolean".to_string(),
+                "B" => "byte".to_string(),
+                "S" => "short".to_string(),
+                "I" => "int".to_string(),
+                "J" => "long".to_string(),
+                "F" => "float".to_string(),
+                "D" => "double".to_string(),
+                "C" => "char".to_string(),
+                other => type_token(other.trim_start_matches('L').trim_end_matches(';')),
+            };
+            format!("{}_array", element)
+        }
+        None => snake_case(param_type.rsplit(['.', '$']).next().unwrap_or(param_type)),
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = index > 0 && (chars[index - 1].is_lowercase() || chars[index - 1].is_ascii_digit());
+            let acronym_end = index > 0
+                && chars[index - 1].is_uppercase()
+                && chars.get(index + 1).map(|next| next.is_lowercase()).unwrap_or(false);
+            if after_lower || acronym_end {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if *c == '$' {
+            snake.push('_');
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+fn rust_ident(name: &str) -> String {
+    if matches!(name, "self" | "super" | "crate") {
+        format!("{}_", name)
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod codegen_unit_tests {
+    use super::*;
+    use crate::lib_unit_tests::create_tests_jvm;
+
+    #[test]
+    fn names() {
+        assert_eq!(snake_case("toUpperCase"), "to_upper_case");
+        assert_eq!(snake_case("getURLPath"), "get_url_path");
+        assert_eq!(snake_case("parseInt"), "parse_int");
+        assert_eq!(type_token("java.lang.CharSequence"), "char_sequence");
+        assert_eq!(type_token("[I"), "int_array");
+        assert_eq!(type_token("[Ljava.lang.String;"), "string_array");
+        assert_eq!(rust_ident("match"), "r#match");
+        assert_eq!(rust_ident("self"), "self_");
+    }
+
+    #[test]
+    fn generate_bindings() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let bindings = BindingGenerator::new(&jvm).class("java.lang.String").generate()?;
+        assert!(bindings.contains("pub struct StringClass;"));
+        assert!(bindings.contains("    const CLASS_NAME: &'static str = \"java.lang.String\";\n"));
+        assert!(bindings.contains("pub trait StringMethods {"));
+        assert!(bindings.contains("    fn substring(&self, jvm: &Jvm, arg0: i32) -> Result<String>;\n"));
+        assert!(bindings.contains("    fn substring_int_int(&self, jvm: &Jvm, arg0: i32, arg1: i32) -> Result<String> {\n"));
+        assert!(bindings.contains("        let result = jvm.invoke(self, \"substring\", &[rt::primitive_arg::<i32>(arg0)?])?;\n"));
+        assert!(bindings.contains("    pub fn value_of_int(jvm: &Jvm, arg0: i32) -> Result<String> {\n"));
+        assert!(!bindings.contains("fn wait("));
+
+        let annotated = BindingGenerator::new(&jvm)
+            .class("java.lang.String")
+            .class("java.lang.FunctionalInterface")
+            .class("java.util.function.Supplier")
+            .annotated_with("java.lang.FunctionalInterface")
+            .generate()?;
+        assert!(annotated.contains("pub trait SupplierMethods {"));
+        assert!(!annotated.contains("StringMethods"));
+        Ok(())
+    }
+}
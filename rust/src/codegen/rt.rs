@@ -0,0 +1,40 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::convert::TryFrom;
+
+use crate::errors::J4RsError;
+use crate::{errors, Instance, InvocationArg, Jvm};
+
+/// Creates the argument of a Java primitive parameter.
+pub fn primitive_arg<T>(value: T) -> errors::Result<InvocationArg>
+where
+    InvocationArg: TryFrom<T, Error = J4RsError>,
+{
+    InvocationArg::try_from(value)?.into_primitive()
+}
+
+/// Creates the argument of a `String`, or a boxed primitive parameter.
+pub fn value_arg<T>(value: T) -> errors::Result<InvocationArg>
+where
+    InvocationArg: TryFrom<T, Error = J4RsError>,
+{
+    InvocationArg::try_from(value)
+}
+
+/// Creates the argument of an object parameter. The `instance` is cloned, so that it can be used again.
+pub fn instance_arg(jvm: &Jvm, instance: &Instance) -> errors::Result<InvocationArg> {
+    Ok(InvocationArg::from(jvm.clone_instance(instance)?))
+}
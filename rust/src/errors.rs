@@ -18,7 +18,7 @@ use std::error::Error;
 use std::ffi::NulError;
 use std::io;
 use std::sync::mpsc::RecvError;
-use std::sync::{PoisonError, TryLockError};
+use std::sync::{Arc, Mutex, PoisonError, TryLockError};
 use std::{fmt, result};
 
 use cesu8::Cesu8DecodingError;
@@ -26,6 +26,9 @@ use fs_extra;
 use serde_json;
 
 use futures::channel::oneshot::Canceled;
+use serde::Deserialize;
+
+use crate::{Instance, Jvm};
 
 pub type Result<T> = result::Result<T, J4RsError>;
 
@@ -45,18 +48,38 @@ pub(crate) fn res_to_opt<T>(res: Result<T>) -> Option<T> {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum J4RsError {
     GeneralError(String),
+    /// An error coming from Java that is described only by a message.
+    ///
+    /// Since 0.23.0, the exceptions that Java throws while being called are returned as `JavaException`s instead.
     JavaError(String),
+    /// An exception that Java threw while being called.
+    JavaException(Box<JavaException>),
+    /// An exception that Java threw while being called, mapped to a Rust error with `Jvm::map_exception`.
+    MappedException(Box<MappedException>),
     JniError(String),
     RustError(String),
     ParseError(String),
     Timeout,
 }
 
+impl J4RsError {
+    /// Returns the exception that Java threw, if this error is a `JavaException` or a `MappedException`.
+    pub fn java_exception(&self) -> Option<&JavaException> {
+        match self {
+            J4RsError::JavaException(java_exception) => Some(java_exception),
+            J4RsError::MappedException(mapped) => Some(mapped.java_exception()),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for J4RsError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             J4RsError::GeneralError(message) => write!(f, "{}", message),
             J4RsError::JavaError(message) => write!(f, "{}", message),
+            J4RsError::JavaException(exception) => write!(f, "{}", exception),
+            J4RsError::MappedException(mapped) => write!(f, "{}", mapped),
             J4RsError::JniError(message) => write!(f, "{}", message),
             J4RsError::RustError(message) => write!(f, "{}", message),
             J4RsError::ParseError(message) => write!(f, "{}", message),
@@ -70,6 +93,8 @@ impl Error for J4RsError {
         match *self {
             J4RsError::GeneralError(_) => "A general error occured",
             J4RsError::JavaError(_) => "An error coming from Java occured",
+            J4RsError::JavaException(_) => "An exception was thrown by Java",
+            J4RsError::MappedException(_) => "An exception was thrown by Java and mapped to a Rust error",
             J4RsError::JniError(_) => "A JNI error occured",
             J4RsError::RustError(_) => "An error coming from Rust occured",
             J4RsError::ParseError(_) => "A parsing error occured",
@@ -78,6 +103,198 @@ impl Error for J4RsError {
     }
 }
 
+/// An element of the stacktrace of a `JavaException`
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaStackFrame {
+    class_name: String,
+    method_name: String,
+    file_name: Option<String>,
+    line_number: i32,
+}
+
+impl JavaStackFrame {
+    /// Returns the fully qualified name of the class that contains the executed method
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Returns the name of the executed method
+    pub fn method_name(&self) -> &str {
+        &self.method_name
+    }
+
+    /// Returns the name of the source file, if available
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Returns the line number in the source file, or a negative number if not available
+    pub fn line_number(&self) -> i32 {
+        self.line_number
+    }
+}
+
+impl fmt::Display for JavaStackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.file_name, self.line_number) {
+            (Some(file_name), line_number) if line_number >= 0 => write!(
+                f,
+                "{}.{}({}:{})",
+                self.class_name, self.method_name, file_name, line_number
+            ),
+            (Some(file_name), _) => write!(f, "{}.{}({})", self.class_name, self.method_name, file_name),
+            (None, _) => write!(f, "{}.{}(Unknown Source)", self.class_name, self.method_name),
+        }
+    }
+}
+
+/// A Java Throwable that was thrown while calling Java.
+///
+/// It contains the class name, the message, the stacktrace and the cause of the Throwable.
+/// The Throwable itself is also kept and can be retrieved as an `Instance` using the `throwable` function.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaException {
+    class_name: String,
+    message: Option<String>,
+    stack_trace: Vec<JavaStackFrame>,
+    cause: Option<Box<JavaException>>,
+    #[serde(skip)]
+    throwable: Option<Arc<Mutex<Instance>>>,
+}
+
+impl JavaException {
+    /// Returns the fully qualified name of the class of the Throwable
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Returns the message of the Throwable, if any
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the stacktrace of the Throwable
+    pub fn stack_trace(&self) -> &[JavaStackFrame] {
+        &self.stack_trace
+    }
+
+    /// Returns the cause of the Throwable, if any
+    pub fn cause(&self) -> Option<&JavaException> {
+        self.cause.as_deref()
+    }
+
+    /// Returns a new `Instance` of the Throwable, if it was kept.
+    ///
+    /// The `Instance` can be used like any other `Instance`, for example to invoke methods of the Throwable,
+    /// or to pass it as an argument to Java in order to be rethrown.
+    pub fn throwable(&self, jvm: &Jvm) -> Result<Option<Instance>> {
+        match &self.throwable {
+            Some(throwable) => {
+                let throwable = throwable.lock()?;
+                let mut instance = jvm.clone_instance(&throwable)?;
+                instance.class_name = throwable.class_name.clone();
+                Ok(Some(instance))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn set_throwable(&mut self, throwable: Instance) {
+        self.throwable = Some(Arc::new(Mutex::new(throwable)));
+    }
+}
+
+impl fmt::Debug for JavaException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JavaException")
+            .field("class_name", &self.class_name)
+            .field("message", &self.message)
+            .field("stack_trace", &self.stack_trace)
+            .field("cause", &self.cause)
+            .finish()
+    }
+}
+
+impl PartialEq for JavaException {
+    fn eq(&self, other: &Self) -> bool {
+        self.class_name == other.class_name
+            && self.message == other.message
+            && self.stack_trace == other.stack_trace
+            && self.cause == other.cause
+    }
+}
+
+impl Eq for JavaException {}
+
+impl fmt::Display for JavaException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => writeln!(f, "{}: {}", self.class_name, message)?,
+            None => writeln!(f, "{}", self.class_name)?,
+        }
+        for frame in &self.stack_trace {
+            writeln!(f, "\tat {}", frame)?;
+        }
+        if let Some(cause) = &self.cause {
+            write!(f, "Caused by: {}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+/// A Java exception that was converted to a Rust error, using a mapping that is registered with `Jvm::map_exception`.
+#[derive(Clone)]
+pub struct MappedException {
+    error: Arc<dyn Error + Send + Sync>,
+    java_exception: JavaException,
+}
+
+impl MappedException {
+    pub(crate) fn new(error: Arc<dyn Error + Send + Sync>, java_exception: JavaException) -> MappedException {
+        MappedException { error, java_exception }
+    }
+
+    /// Returns the Rust error that the Java exception was mapped to
+    pub fn error(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.error.as_ref()
+    }
+
+    /// Returns the Rust error that the Java exception was mapped to, if it is of type `E`
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref::<E>()
+    }
+
+    /// Returns the Java exception that was mapped
+    pub fn java_exception(&self) -> &JavaException {
+        &self.java_exception
+    }
+}
+
+impl fmt::Debug for MappedException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MappedException")
+            .field("error", &self.error)
+            .field("java_exception", &self.java_exception)
+            .finish()
+    }
+}
+
+impl PartialEq for MappedException {
+    fn eq(&self, other: &Self) -> bool {
+        self.error.to_string() == other.error.to_string() && self.java_exception == other.java_exception
+    }
+}
+
+impl Eq for MappedException {}
+
+impl fmt::Display for MappedException {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
 impl From<NulError> for J4RsError {
     fn from(err: NulError) -> J4RsError {
         J4RsError::JniError(format!("{:?}", err))
@@ -143,3 +360,45 @@ impl From<Cesu8DecodingError> for J4RsError {
         J4RsError::ParseError(format!("{:?}", err))
     }
 }
+
+#[cfg(feature = "jni-interop")]
+impl From<jni::errors::Error> for J4RsError {
+    fn from(err: jni::errors::Error) -> J4RsError {
+        J4RsError::JniError(format!("{:?}", err))
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<T: fmt::Debug> From<ciborium::de::Error<T>> for J4RsError {
+    fn from(err: ciborium::de::Error<T>) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<T: fmt::Debug> From<ciborium::ser::Error<T>> for J4RsError {
+    fn from(err: ciborium::ser::Error<T>) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::de::Error> for J4RsError {
+    fn from(err: toml::de::Error) -> J4RsError {
+        J4RsError::ParseError(format!("{:?}", err))
+    }
+}
+
+#[cfg(feature = "provision-jre")]
+impl From<ureq::Error> for J4RsError {
+    fn from(err: ureq::Error) -> J4RsError {
+        J4RsError::GeneralError(format!("{:?}", err))
+    }
+}
+
+#[cfg(feature = "provision-jre")]
+impl From<zip::result::ZipError> for J4RsError {
+    fn from(err: zip::result::ZipError) -> J4RsError {
+        J4RsError::GeneralError(format!("{:?}", err))
+    }
+}
@@ -12,15 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::convert::TryFrom;
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::thread;
 
 use crate::api::instance::{Instance, InstanceReceiver};
 use crate::api::{
-    self, CLASS_J4RS_EVENT_HANDLER, CLASS_J4RS_FXML_LOADER, CLASS_NATIVE_CALLBACK_TO_RUST_CHANNEL_SUPPORT,
+    self, CLASS_J4RS_CHANGE_LISTENER, CLASS_J4RS_EVENT_HANDLER, CLASS_J4RS_FXML_LOADER,
+    CLASS_NATIVE_CALLBACK_TO_RUST_CHANNEL_SUPPORT,
 };
 use crate::errors;
 use crate::errors::{opt_to_res, J4RsError};
+use crate::logger::error;
 use crate::{InvocationArg, Jvm, MavenArtifact};
 
 /// Provides JavaFx support.
@@ -47,8 +51,45 @@ pub trait JavaFxSupport {
     ///
     /// The instance passed as argument needs to be of class `javafx.stage.Stage`.
     fn on_close_event_receiver(&self, stage: &Instance) -> errors::Result<InstanceReceiver>;
+    /// Creates an instance receiver that will be receiving the new values of the property `property_name` of the `instance`,
+    /// whenever the property changes.
+    ///
+    /// The property is retrieved by calling the `<property_name>Property` method of the `instance`, e.g. `textProperty` for `text`.
+    /// It may be any `javafx.beans.value.ObservableValue`. Changes to `null` are not received.
+    ///
+    /// When the `InstanceReceiver` is closed, the listener is removed from the property on the next change.
+    fn observe_property(&self, instance: &Instance, property_name: &str) -> errors::Result<InstanceReceiver>;
     /// Loads a FXML and returns a Result of a FxController for it.
     fn load_fxml(&self, path: &PathBuf, stage: &Instance) -> errors::Result<FxController>;
+    /// Loads a FXML and binds its nodes to a new `T`.
+    ///
+    /// The nodes with the ids that are returned by `T::node_ids` are fetched with one call,
+    /// `T` is created from them and then its event handlers get registered.
+    fn load_fxml_with_controller<T: FxmlController>(&self, path: &Path, stage: &Instance) -> errors::Result<T>;
+    /// Calls the `callback` with an `FxActionEvent` whenever an action event is fired by the `instance`, e.g. a `javafx.scene.control.Button`.
+    ///
+    /// The `callback` is called by a dedicated thread that is attached to the JVM.
+    fn on_action<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxActionEvent) + Send + 'static;
+    /// Calls the `callback` with an `FxKeyEvent` whenever a key is pressed while the `instance` (e.g. a `javafx.scene.Scene`) has the focus.
+    ///
+    /// The `callback` is called by a dedicated thread that is attached to the JVM.
+    fn on_key_pressed<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxKeyEvent) + Send + 'static;
+    /// Calls the `callback` with an `FxKeyEvent` whenever a key is released while the `instance` (e.g. a `javafx.scene.Scene`) has the focus.
+    ///
+    /// The `callback` is called by a dedicated thread that is attached to the JVM.
+    fn on_key_released<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxKeyEvent) + Send + 'static;
+    /// Calls the `callback` with an `FxMouseEvent` whenever the `instance` (e.g. a `javafx.scene.Node`) is clicked.
+    ///
+    /// The `callback` is called by a dedicated thread that is attached to the JVM.
+    fn on_mouse_clicked<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxMouseEvent) + Send + 'static;
 }
 
 impl JavaFxSupport for Jvm {
@@ -107,6 +148,18 @@ impl JavaFxSupport for Jvm {
         Ok(action_channel)
     }
 
+    fn observe_property(&self, instance: &Instance, property_name: &str) -> errors::Result<InstanceReceiver> {
+        let property = self.invoke(instance, &format!("{}Property", property_name), InvocationArg::empty())?;
+        let change_listener = self.create_instance(CLASS_J4RS_CHANGE_LISTENER, InvocationArg::empty())?;
+        let change_channel = self.init_callback_channel(&change_listener)?;
+        self.invoke(
+            &property,
+            "addListener",
+            &[InvocationArg::from(change_listener)],
+        )?;
+        Ok(change_channel)
+    }
+
     /// Deploys the required dependencies to run a JavaFX application in order to be able to be used by j4rs.
     fn deploy_javafx_dependencies(&self) -> errors::Result<()> {
         let target_os_res = env::var("CARGO_CFG_TARGET_OS");
@@ -169,6 +222,169 @@ impl JavaFxSupport for Jvm {
         )?;
         Ok(FxController::new(controller))
     }
+
+    fn load_fxml_with_controller<T: FxmlController>(&self, path: &Path, stage: &Instance) -> errors::Result<T> {
+        let controller = self.load_fxml(&path.to_path_buf(), stage)?;
+        let nodes = controller.get_nodes(T::node_ids(), self)?;
+        let bound = T::from_nodes(nodes)?;
+        bound.register_handlers(self)?;
+        Ok(bound)
+    }
+
+    fn on_action<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxActionEvent) + Send + 'static,
+    {
+        let receiver = self.get_javafx_event_receiver(instance, FxEventType::ActionEvent_Action)?;
+        handle_typed_events(receiver, FxActionEvent::from_instance, callback)
+    }
+
+    fn on_key_pressed<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxKeyEvent) + Send + 'static,
+    {
+        let receiver = self.get_javafx_event_receiver(instance, FxEventType::KeyEvent_KeyPressed)?;
+        handle_typed_events(receiver, FxKeyEvent::from_instance, callback)
+    }
+
+    fn on_key_released<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxKeyEvent) + Send + 'static,
+    {
+        let receiver = self.get_javafx_event_receiver(instance, FxEventType::KeyEvent_KeyReleased)?;
+        handle_typed_events(receiver, FxKeyEvent::from_instance, callback)
+    }
+
+    fn on_mouse_clicked<F>(&self, instance: &Instance, callback: F) -> errors::Result<()>
+    where
+        F: Fn(&Jvm, FxMouseEvent) + Send + 'static,
+    {
+        let receiver = self.get_javafx_event_receiver(instance, FxEventType::MouseEvent_MouseClicked)?;
+        handle_typed_events(receiver, FxMouseEvent::from_instance, callback)
+    }
+}
+
+// Spawns a thread that converts the event Instances of the receiver to typed events and passes them to the callback
+fn handle_typed_events<T, F>(
+    receiver: InstanceReceiver,
+    convert: fn(&Jvm, &Instance) -> errors::Result<T>,
+    callback: F,
+) -> errors::Result<()>
+where
+    T: 'static,
+    F: Fn(&Jvm, T) + Send + 'static,
+{
+    thread::Builder::new()
+        .name("j4rs-fx-events".to_string())
+        .spawn(move || {
+            let jvm = match Jvm::attach_thread_as_daemon() {
+                Ok(jvm) => jvm,
+                Err(e) => {
+                    error(&format!("Could not attach the JavaFX events thread to the JVM: {}", e));
+                    return;
+                }
+            };
+            for instance in receiver.rx() {
+                match convert(&jvm, &instance) {
+                    Ok(event) => callback(&jvm, event),
+                    Err(e) => error(&format!("Could not read a JavaFX event: {}", e)),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// A `javafx.event.ActionEvent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxActionEvent {
+    /// The id of the node that fired the event, if it has one.
+    pub source_id: Option<String>,
+}
+
+impl FxActionEvent {
+    fn from_instance(jvm: &Jvm, event: &Instance) -> errors::Result<FxActionEvent> {
+        let source = jvm.invoke(event, "getSource", InvocationArg::empty())?;
+        let source_id = jvm
+            .invoke_to_rust::<Option<String>>(&source, "getId", InvocationArg::empty())
+            .ok()
+            .flatten();
+        Ok(FxActionEvent { source_id })
+    }
+}
+
+/// A `javafx.scene.input.KeyEvent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxKeyEvent {
+    /// The name of the `javafx.scene.input.KeyCode` of the key, e.g. `Enter` or `A`.
+    pub code: String,
+    /// The text that the key generates, for key pressed and key released events.
+    pub text: String,
+    /// The character that is typed, for key typed events.
+    pub character: String,
+    pub shift_down: bool,
+    pub control_down: bool,
+    pub alt_down: bool,
+    pub meta_down: bool,
+}
+
+impl FxKeyEvent {
+    fn from_instance(jvm: &Jvm, event: &Instance) -> errors::Result<FxKeyEvent> {
+        let code = jvm.invoke(event, "getCode", InvocationArg::empty())?;
+        Ok(FxKeyEvent {
+            code: jvm.invoke_to_rust(&code, "getName", InvocationArg::empty())?,
+            text: jvm.invoke_to_rust(event, "getText", InvocationArg::empty())?,
+            character: jvm.invoke_to_rust(event, "getCharacter", InvocationArg::empty())?,
+            shift_down: jvm.invoke_to_rust(event, "isShiftDown", InvocationArg::empty())?,
+            control_down: jvm.invoke_to_rust(event, "isControlDown", InvocationArg::empty())?,
+            alt_down: jvm.invoke_to_rust(event, "isAltDown", InvocationArg::empty())?,
+            meta_down: jvm.invoke_to_rust(event, "isMetaDown", InvocationArg::empty())?,
+        })
+    }
+}
+
+/// A `javafx.scene.input.MouseEvent`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FxMouseEvent {
+    /// The horizontal position of the event, relative to the origin of the source node.
+    pub x: f64,
+    /// The vertical position of the event, relative to the origin of the source node.
+    pub y: f64,
+    /// The horizontal position of the event, relative to the origin of the scene.
+    pub scene_x: f64,
+    /// The vertical position of the event, relative to the origin of the scene.
+    pub scene_y: f64,
+    /// The absolute horizontal position of the event on the screen.
+    pub screen_x: f64,
+    /// The absolute vertical position of the event on the screen.
+    pub screen_y: f64,
+    /// The name of the `javafx.scene.input.MouseButton` that is responsible for the event, e.g. `PRIMARY`.
+    pub button: String,
+    /// The number of the mouse clicks associated with the event.
+    pub click_count: i32,
+    pub shift_down: bool,
+    pub control_down: bool,
+    pub alt_down: bool,
+    pub meta_down: bool,
+}
+
+impl FxMouseEvent {
+    fn from_instance(jvm: &Jvm, event: &Instance) -> errors::Result<FxMouseEvent> {
+        let button = jvm.invoke(event, "getButton", InvocationArg::empty())?;
+        Ok(FxMouseEvent {
+            x: jvm.invoke_to_rust(event, "getX", InvocationArg::empty())?,
+            y: jvm.invoke_to_rust(event, "getY", InvocationArg::empty())?,
+            scene_x: jvm.invoke_to_rust(event, "getSceneX", InvocationArg::empty())?,
+            scene_y: jvm.invoke_to_rust(event, "getSceneY", InvocationArg::empty())?,
+            screen_x: jvm.invoke_to_rust(event, "getScreenX", InvocationArg::empty())?,
+            screen_y: jvm.invoke_to_rust(event, "getScreenY", InvocationArg::empty())?,
+            button: jvm.invoke_to_rust(&button, "name", InvocationArg::empty())?,
+            click_count: jvm.invoke_to_rust(event, "getClickCount", InvocationArg::empty())?,
+            shift_down: jvm.invoke_to_rust(event, "isShiftDown", InvocationArg::empty())?,
+            control_down: jvm.invoke_to_rust(event, "isControlDown", InvocationArg::empty())?,
+            alt_down: jvm.invoke_to_rust(event, "isAltDown", InvocationArg::empty())?,
+            meta_down: jvm.invoke_to_rust(event, "isMetaDown", InvocationArg::empty())?,
+        })
+    }
 }
 
 fn maven(s: &str, jvm: &Jvm) {
@@ -227,6 +443,73 @@ impl FxController {
         )?;
         Ok(event_channel)
     }
+
+    /// Returns the JavaFX node with the specified node_id (id attribute of the fxml element).
+    pub fn get_node(&self, node_id: &str, jvm: &Jvm) -> errors::Result<Instance> {
+        jvm.invoke(&self.controller, "getNodeById", &[InvocationArg::try_from(node_id)?])
+    }
+
+    /// Returns the JavaFX nodes with the specified node_ids, fetched with one call.
+    ///
+    /// An error is returned if any of the nodes is not found.
+    pub fn get_nodes(&self, node_ids: &[&str], jvm: &Jvm) -> errors::Result<FxNodes> {
+        let nodes_list = jvm.invoke(&self.controller, "getNodesByIds", &[InvocationArg::try_from(node_ids)?])?;
+        let mut nodes = HashMap::with_capacity(node_ids.len());
+        for (node_id, node) in node_ids.iter().zip(jvm.iterate(&nodes_list)?) {
+            nodes.insert(node_id.to_string(), node?);
+        }
+        Ok(FxNodes { nodes })
+    }
+}
+
+/// The JavaFX nodes of a loaded FXML, keyed by their ids.
+pub struct FxNodes {
+    nodes: HashMap<String, Instance>,
+}
+
+impl FxNodes {
+    /// Removes and returns the node with the specified node_id.
+    pub fn take(&mut self, node_id: &str) -> errors::Result<Instance> {
+        self.nodes.remove(node_id).ok_or_else(|| {
+            J4RsError::GeneralError(format!("Node with id {} was not fetched", node_id))
+        })
+    }
+}
+
+/// A Rust struct that is bound to the nodes of a FXML, loaded with `JavaFxSupport::load_fxml_with_controller`.
+///
+/// ```ignore
+/// struct MyController {
+///     hello_button: Instance,
+///     message_label: Instance,
+/// }
+///
+/// impl FxmlController for MyController {
+///     fn node_ids() -> &'static [&'static str] {
+///         &["helloButton", "messageLabel"]
+///     }
+///
+///     fn from_nodes(mut nodes: FxNodes) -> errors::Result<Self> {
+///         Ok(MyController {
+///             hello_button: nodes.take("helloButton")?,
+///             message_label: nodes.take("messageLabel")?,
+///         })
+///     }
+///
+///     fn register_handlers(&self, jvm: &Jvm) -> errors::Result<()> {
+///         jvm.on_action(&self.hello_button, |_jvm, _event| println!("Hello!"))
+///     }
+/// }
+/// ```
+pub trait FxmlController: Sized {
+    /// The ids of the nodes to fetch.
+    fn node_ids() -> &'static [&'static str];
+    /// Creates the struct from the fetched nodes.
+    fn from_nodes(nodes: FxNodes) -> errors::Result<Self>;
+    /// Registers the event handlers of the nodes. By default, no handlers are registered.
+    fn register_handlers(&self, _jvm: &Jvm) -> errors::Result<()> {
+        Ok(())
+    }
 }
 
 #[allow(non_camel_case_types)]
@@ -12,18 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
 use std::mem;
 use std::os::raw::{c_char, c_double};
 use std::ptr;
 
-use jni_sys::{jint, jobject, jobjectRefType, jstring, JNIEnv, JNI_TRUE};
+use jni_sys::{jbyte, jint, jobject, jobjectArray, jobjectRefType, jsize, jstring, JNIEnv, JNI_TRUE};
 
+use crate::api::metrics;
 use crate::cache;
 use crate::errors;
 use crate::errors::opt_to_res;
 use crate::logger::{debug, error};
 use crate::utils;
-use crate::{InvocationArg, Jvm};
+use crate::{Instance, InvocationArg, Jvm};
 
 pub(crate) fn invocation_arg_jobject_from_rust_serialized(
     ia: &InvocationArg,
@@ -187,6 +189,7 @@ pub fn create_global_ref_from_local_ref(
             jni_env,
             local_ref,
         );
+        metrics::global_ref_created();
         // If local ref, delete it
         if gort(jni_env, local_ref) as jint == jobjectRefType::JNILocalRefType as jint {
             delete_java_local_ref(jni_env, local_ref);
@@ -233,6 +236,7 @@ pub fn delete_java_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
         let exd = (**jni_env).v1_6.ExceptionDescribe;
         let exclear = (**jni_env).v1_6.ExceptionClear;
         dgr(jni_env, jinstance);
+        metrics::global_ref_deleted();
         if (exc)(jni_env) == JNI_TRUE {
             (exd)(jni_env);
             (exclear)(jni_env);
@@ -243,6 +247,19 @@ pub fn delete_java_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
     }
 }
 
+/// Creates `Instance`s with global refs out of the elements of a Java object array
+pub(crate) unsafe fn instances_from_jobject_array(
+    jni_env: *mut JNIEnv,
+    array: jobjectArray,
+) -> errors::Result<Vec<Instance>> {
+    let gal = (**jni_env).v1_6.GetArrayLength;
+    let goae = (**jni_env).v1_6.GetObjectArrayElement;
+    let length = gal(jni_env, array);
+    (0..length)
+        .map(|i| Instance::from_jobject_with_global_ref(goae(jni_env, array, i)))
+        .collect()
+}
+
 /// Deletes the java ref from the memory
 pub(crate) fn delete_java_local_ref(jni_env: *mut JNIEnv, jinstance: jobject) {
     unsafe {
@@ -270,6 +287,27 @@ pub(crate) fn global_jobject_from_str(
     Ok(gr)
 }
 
+/// Creates a global reference of a Java `String[]` that contains the `strings`.
+pub(crate) fn global_jobject_array_from_strs(
+    strings: &[&str],
+    jni_env: *mut JNIEnv,
+) -> errors::Result<jobject> {
+    unsafe {
+        let array = (opt_to_res(cache::get_jni_new_object_array())?)(
+            jni_env,
+            strings.len() as i32,
+            cache::get_string_class()?,
+            ptr::null_mut(),
+        );
+        for (i, string) in strings.iter().enumerate() {
+            let jstring = local_jobject_from_str(string, jni_env)?;
+            (opt_to_res(cache::get_jni_set_object_array_element())?)(jni_env, array, i as i32, jstring);
+            delete_java_local_ref(jni_env, jstring);
+        }
+        create_global_ref_from_local_ref(array, jni_env)
+    }
+}
+
 pub(crate) fn local_jobject_from_str(
     string: &str,
     jni_env: *mut JNIEnv,
@@ -522,6 +560,72 @@ primitive_array_from_jobject!(f32_array_from_jobject, f32, cache::get_jni_get_fl
 primitive_array_from_jobject!(f64_array_from_jobject, f64, cache::get_jni_get_double_array_elements, cache::get_jni_release_double_array_elements);
 primitive_array_from_jobject!(boolean_array_from_jobject, bool, cache::get_jni_get_boolean_array_elements, cache::get_jni_release_boolean_array_elements);
 
+/// Transforms a two-dimensional Java array, using `row_transformation` for each one of its rows.
+pub(crate) unsafe fn nested_array_from_jobject<T: 'static>(
+    obj: jobject,
+    jni_env: *mut JNIEnv,
+    row_transformation: unsafe fn(jobject, *mut JNIEnv) -> errors::Result<Vec<T>>,
+) -> errors::Result<Box<dyn Any>> {
+    if obj.is_null() {
+        return Err(errors::J4RsError::JniError(
+            "Attempt to create a nested array from null".to_string(),
+        ));
+    }
+    let goae = (**jni_env).v1_6.GetObjectArrayElement;
+    let length = (opt_to_res(cache::get_jni_get_array_length())?)(jni_env, obj);
+    let mut rows = Vec::with_capacity(length as usize);
+    for i in 0..length {
+        let row = goae(jni_env, obj, i);
+        Jvm::do_return(jni_env, ())?;
+        let transformed = row_transformation(row, jni_env);
+        delete_java_local_ref(jni_env, row);
+        rows.push(transformed?);
+    }
+    Ok(Box::new(rows))
+}
+
+/// Creates a Java `byte[]` that contains a copy of `bytes`.
+pub(crate) fn global_jobject_from_u8_slice(bytes: &[u8], jni_env: *mut JNIEnv) -> errors::Result<jobject> {
+    let length = jsize::try_from(bytes.len()).map_err(|_| {
+        errors::J4RsError::RustError(format!("Cannot create a Java byte array of {} bytes", bytes.len()))
+    })?;
+    unsafe {
+        let array = (opt_to_res(cache::get_jni_new_byte_array())?)(jni_env, length);
+        Jvm::do_return(jni_env, ())?;
+        if array.is_null() {
+            return Err(errors::J4RsError::JniError("NewByteArray failed".to_string()));
+        }
+        (opt_to_res(cache::get_jni_set_byte_array_region())?)(
+            jni_env,
+            array,
+            0,
+            length,
+            bytes.as_ptr() as *const jbyte,
+        );
+        create_global_ref_from_local_ref(array, jni_env)
+    }
+}
+
+/// Copies the contents of a Java `byte[]` to a `Vec<u8>`.
+pub(crate) unsafe fn u8_array_from_jobject(obj: jobject, jni_env: *mut JNIEnv) -> errors::Result<Vec<u8>> {
+    if obj.is_null() {
+        Err(errors::J4RsError::JniError(
+            "Attempt to create an u8 array from null".to_string(),
+        ))
+    } else {
+        let length = (opt_to_res(cache::get_jni_get_array_length())?)(jni_env, obj);
+        let mut vec = vec![0_u8; length as usize];
+        (opt_to_res(cache::get_jni_get_byte_array_region())?)(
+            jni_env,
+            obj,
+            0,
+            length,
+            vec.as_mut_ptr() as *mut jbyte,
+        );
+        Jvm::do_return(jni_env, vec)
+    }
+}
+
 pub(crate) unsafe fn string_from_jobject(
     obj: jobject,
     jni_env: *mut JNIEnv,
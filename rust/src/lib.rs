@@ -30,41 +30,83 @@ extern crate libc;
 extern crate log;
 extern crate serde;
 extern crate serde_json;
+// Allows the code that is generated by the j4rs_derive macros to be used in the tests of the crate
+#[cfg(test)]
+extern crate self as j4rs;
 
-use futures::channel::oneshot;
-use std::mem;
 use std::os::raw::c_void;
-use std::sync::mpsc::Sender;
+use crate::api::instance::InstanceSender;
 
 pub use jni_sys;
-use jni_sys::{jlong, jobject, jstring, JNIEnv};
+use jni_sys::{jint, jlong, jobject, jobjectArray, jstring, JNIEnv};
+
+use crate::api::proxy::ProxyHandler;
+use crate::async_api::FutureCompletion;
 
 pub use api::instance::Instance;
+pub use api::instance::InstanceKey;
+pub use api::instance::AsyncInstanceReceiver;
 pub use api::instance::InstanceReceiver;
+pub use api::instance::ChannelOverflow;
+pub use api::instance::TypedInstanceReceiver;
 
 pub use self::api::invocation_arg::InvocationArg;
+pub use self::api::invocation_result::InvocationResult;
+pub use self::api::iterator::{ChunkedToRustIterator, JavaIterator, JavaToRustIterator};
+pub use self::api::jassets::{JarStatus, JarVerification};
+pub use self::api::actor::{ActorArg, ActorInstance, JvmActor};
+pub use self::api::bean::{FromJava, IntoJava};
+pub use self::api::class_scope::ClassScope;
+pub use self::api::cluster::JvmCluster;
+pub use self::api::codec::SerializationFormat;
+pub use self::api::eval::EvalContext;
+pub use self::api::frame::{LocalFrame, LocalInstance};
+pub use self::api::hooks::{InvocationHook, InvocationInfo, InvocationKind};
+pub use self::api::prepared::PreparedInvocation;
+pub use self::api::publisher::PublisherStream;
+pub use self::api::reflection::JavaMethod;
+pub use self::api::remote::{RemoteArg, RemoteInstance, RemoteJvm};
+pub use self::api::shared::{SendableJvm, SharedJvm};
+pub use self::api::std_streams::StdStream;
+pub use self::api::stream::JavaStream;
+pub use self::api::threads::JavaThreadFactory;
+pub use self::api::tuple::JavaTuple;
+pub use self::api::typed_instance::{ClassMarker, TypedInstance};
+pub use self::api::primitives::{JavaPrimitive, PrimitiveArg};
 pub use self::api::Callback;
 pub use self::api::ClasspathEntry;
 pub use self::api::JavaClass;
 pub use self::api::JavaOpt;
 pub use self::api::Jvm;
 pub use self::api::JvmBuilder;
+pub use self::api::jvm_discovery::JvmInstallation;
+pub use self::api::jvm_options::JvmOptions;
+#[cfg(feature = "provision-jre")]
+pub use self::api::jre::JreProvisioning;
+pub use self::api::management::{GarbageCollectorStats, JavaThreadInfo, Management, MemoryUsage};
+pub use self::api::metrics::{MetricsSnapshot, OperationMetrics};
 pub use self::api::Null;
 pub use self::api_tweaks::{get_created_java_vms, set_java_vm};
 pub use self::jni_utils::jstring_to_rust_string;
+pub use self::provisioning::IvyArtifact;
 pub use self::provisioning::LocalJarArtifact;
 pub use self::provisioning::MavenArtifact;
 pub use self::provisioning::MavenArtifactRepo;
+pub use self::provisioning::MavenProxy;
 pub use self::provisioning::MavenSettings;
 
+#[cfg(target_os = "android")]
+pub mod android;
 mod api;
 pub(crate) mod api_tweaks;
 pub mod async_api;
 mod cache;
+pub mod codegen;
 pub mod errors;
 pub mod jfx;
 mod jni_utils;
 mod logger;
+pub mod natives;
 pub mod prelude;
 mod provisioning;
 mod utils;
@@ -86,28 +128,31 @@ pub extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRus
     _class: *const c_void,
     ptr_address: jlong,
     java_instance: jobject,
-) {
+) -> jint {
     let mut jvm = Jvm::attach_thread()
         .expect("Could not create a j4rs Jvm while invoking callback to channel.");
     jvm.detach_thread_on_drop(false);
     let instance_res = Instance::from_jobject_with_global_ref(java_instance);
     if let Ok(instance) = instance_res {
-        let p = ptr_address as *mut Sender<Instance>;
-        let tx = unsafe { Box::from_raw(p) };
-
-        let result = tx.send(instance);
-        mem::forget(tx);
-        if let Err(error) = result {
-            panic!(
-                "Could not send to the defined callback channel: {:?}",
-                error
-            );
-        }
+        // The sender is owned by Java, which does not release it while a callback is in progress
+        let tx = unsafe { &*(ptr_address as *const InstanceSender) };
+        tx.send(instance) as jint
     } else {
         panic!("Could not create Rust Instance from the Java Instance object...");
     }
 }
 
+#[no_mangle]
+pub extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRustChannelSupport_releasechannel(
+    _jni_env: *mut JNIEnv,
+    _class: *const c_void,
+    ptr_address: jlong,
+) {
+    if ptr_address != 0 {
+        drop(unsafe { Box::from_raw(ptr_address as *mut InstanceSender) });
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRustFutureSupport_docallbacktochannel(
     _jni_env: *mut JNIEnv,
@@ -121,13 +166,9 @@ pub extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallbackToRus
     jvm.detach_thread_on_drop(false);
     let instance_res = Instance::from_jobject_with_global_ref(java_instance);
     if let Ok(instance) = instance_res {
-        let p = ptr_address as *mut oneshot::Sender<errors::Result<Instance>>;
-        let tx = unsafe { Box::from_raw(p) };
-
-        let result = tx.send(Ok(instance));
-        if let Err(_) = result {
-            panic!("Could not send to the defined callback channel to complete the future");
-        }
+        let p = ptr_address as *mut FutureCompletion;
+        let completion = unsafe { Box::from_raw(p) };
+        completion.complete(Ok(instance));
     } else {
         panic!("Could not create Rust Instance from the Java Instance object...");
     }
@@ -146,18 +187,77 @@ pub unsafe extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeCallba
     jvm.detach_thread_on_drop(false);
     let stacktrace = jstring_to_rust_string(&jvm, stacktrace);
     if let Ok(st) = stacktrace {
-        let p = ptr_address as *mut oneshot::Sender<errors::Result<Instance>>;
-        let tx = unsafe { Box::from_raw(p) };
-
-        let result = tx.send(Err(errors::J4RsError::JavaError(st)));
-        if let Err(_) = result {
-            panic!("Could not send to the defined callback channel to fail a future");
-        }
+        let p = ptr_address as *mut FutureCompletion;
+        let completion = unsafe { Box::from_raw(p) };
+        completion.complete(Err(errors::J4RsError::JavaError(st)));
     } else {
         panic!("Could not create Rust String from the Java jstring while invoking callback to channel for failing a Future...");
     }
 }
 
+/// Called by Java when a method of a proxy that is created with `Jvm::new_proxy` is invoked.
+///
+/// # Safety
+///
+/// The `ptr_address` must point to a `ProxyHandler` that is created by `Jvm::new_proxy` and not yet dropped.
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeInvocationHandler_invokenative(
+    jni_env: *mut JNIEnv,
+    _class: *const c_void,
+    ptr_address: jlong,
+    method_name: jstring,
+    args: jobjectArray,
+) -> jobject {
+    let handler = &*(ptr_address as *const ProxyHandler);
+    handler(jni_env, method_name, args)
+}
+
+/// Called by Java when a proxy that is created with `Jvm::new_proxy` is garbage collected.
+///
+/// # Safety
+///
+/// The `ptr_address` must point to a `ProxyHandler` that is created by `Jvm::new_proxy` and not yet dropped.
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_astonbitecode_j4rs_api_invocation_NativeInvocationHandler_dropnative(
+    _jni_env: *mut JNIEnv,
+    _class: *const c_void,
+    ptr_address: jlong,
+) {
+    drop(Box::from_raw(ptr_address as *mut ProxyHandler));
+}
+
+/// Called by Java when the `RustLogHandler` that is installed with `Jvm::redirect_java_logging` publishes a log record.
+///
+/// # Safety
+///
+/// The `target` and `message` must be valid `jstring`s.
+#[no_mangle]
+pub unsafe extern "C" fn Java_org_astonbitecode_j4rs_api_logging_RustLogHandler_lognative(
+    _jni_env: *mut JNIEnv,
+    _class: *const c_void,
+    level: jint,
+    target: jstring,
+    message: jstring,
+) {
+    let mut jvm = Jvm::attach_thread().expect(
+        "Could not create a j4rs Jvm while forwarding a Java log record.",
+    );
+    jvm.detach_thread_on_drop(false);
+    let level = match level {
+        1 => log::Level::Error,
+        2 => log::Level::Warn,
+        3 => log::Level::Info,
+        4 => log::Level::Debug,
+        _ => log::Level::Trace,
+    };
+    if let (Ok(target), Ok(message)) = (
+        jstring_to_rust_string(&jvm, target),
+        jstring_to_rust_string(&jvm, message),
+    ) {
+        log!(target: &target, level, "{}", message);
+    }
+}
+
 #[cfg(test)]
 mod lib_unit_tests {
     use std::collections::HashMap;
@@ -169,7 +269,7 @@ mod lib_unit_tests {
     use std::sync::Mutex;
     use crate::api::{self, JavaClass};
     use crate::provisioning::JavaArtifact;
-    use crate::{LocalJarArtifact, MavenArtifactRepo, MavenSettings, Null};
+    use crate::{IvyArtifact, LocalJarArtifact, MavenArtifactRepo, MavenProxy, MavenSettings, Null};
     use super::utils::jassets_path;
     use super::{errors, InvocationArg, Jvm, JvmBuilder, MavenArtifact};
 
@@ -747,6 +847,78 @@ mod lib_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn deploy_maven_artifact_transitively_with_settings() -> errors::Result<()> {
+        let tmp = std::env::temp_dir().join("j4rs_deploy_maven_artifact_transitively");
+        let _ = std::fs::remove_dir_all(&tmp);
+        let repo = tmp.join("repo");
+        let target = tmp.join("target");
+        let write_artifact = |id: &str, dependencies: &str| -> errors::Result<()> {
+            let dir = repo.join("io/github/astonbitecode").join(id).join("1.0");
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(dir.join(format!("{}-1.0.jar", id)), "dummy")?;
+            std::fs::write(
+                dir.join(format!("{}-1.0.pom", id)),
+                format!("<project><groupId>io.github.astonbitecode</groupId><artifactId>{}</artifactId>\
+                <version>1.0</version><dependencies>{}</dependencies></project>", id, dependencies),
+            )?;
+            Ok(())
+        };
+        write_artifact("j4rs-dummy-a", "<dependency><groupId>io.github.astonbitecode</groupId>\
+            <artifactId>j4rs-dummy-b</artifactId><version>${project.version}</version></dependency>\
+            <dependency><groupId>io.github.astonbitecode</groupId><artifactId>j4rs-dummy-c</artifactId>\
+            <version>1.0</version><scope>test</scope></dependency>")?;
+        write_artifact("j4rs-dummy-b", "")?;
+        write_artifact("j4rs-dummy-c", "")?;
+        let settings_xml = tmp.join("settings.xml");
+        std::fs::write(&settings_xml, "<settings><servers><server><id>local</id>\
+            <username>user</username><password>pass</password></server></servers></settings>")?;
+
+        let repo_uri = format!("local::file://{}", repo.to_str().unwrap());
+        let jvm: Jvm = JvmBuilder::new()
+            .with_maven_settings(MavenSettings::new(vec![MavenArtifactRepo::from(repo_uri.as_str())])
+                .with_proxy(MavenProxy::new("localhost", 3128).with_non_proxy_hosts("localhost|*.local"))
+                .with_settings_xml(settings_xml.to_str().unwrap()))
+            .build()?;
+
+        let mut artifact = MavenArtifact::from("io.github.astonbitecode:j4rs-dummy-a:1.0").with_transitive(true);
+        artifact.base = target.to_str().unwrap().to_string();
+        jvm.deploy_artifact(&artifact)?;
+
+        assert!(target.join("j4rs-dummy-a-1.0.jar").exists());
+        assert!(target.join("j4rs-dummy-b-1.0.jar").exists());
+        assert!(!target.join("j4rs-dummy-c-1.0.jar").exists());
+
+        let _ = std::fs::remove_dir_all(&tmp);
+        Ok(())
+    }
+
+    #[test]
+    fn deploy_ivy_artifact() -> errors::Result<()> {
+        let tmp = std::env::temp_dir().join("j4rs_deploy_ivy_artifact");
+        let _ = std::fs::remove_dir_all(&tmp);
+        let repo = tmp.join("repo");
+        let target = tmp.join("target");
+        let dir = repo.join("io.github.astonbitecode/j4rs-dummy/1.0");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("j4rs-dummy-1.0.jar"), "dummy")?;
+
+        let jvm: Jvm = super::new_jvm(Vec::new(), Vec::new())?;
+        let repo_uri = format!("file://{}", repo.to_str().unwrap());
+        let mut artifact = IvyArtifact::new(&repo_uri, "io.github.astonbitecode:j4rs-dummy:1.0")
+            .with_pattern(IvyArtifact::GRADLE_PATTERN);
+        artifact.base = target.to_str().unwrap().to_string();
+        jvm.deploy_artifact(&artifact)?;
+        assert!(target.join("j4rs-dummy-1.0.jar").exists());
+
+        let mut missing = IvyArtifact::new(&repo_uri, "io.github.astonbitecode:j4rs-dummy:1.0");
+        missing.base = target.join("other").to_str().unwrap().to_string();
+        assert!(jvm.deploy_artifact(&missing).is_err());
+
+        let _ = std::fs::remove_dir_all(&tmp);
+        Ok(())
+    }
+
     #[test]
     fn deploy_local_artifact() -> errors::Result<()> {
         let jvm: Jvm = super::new_jvm(Vec::new(), Vec::new())?;
@@ -873,6 +1045,54 @@ mod lib_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn invoke_varargs() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+        let test_instance = jvm
+            .create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())
+            ?;
+
+        let no_fixed_args: &[InvocationArg] = &[];
+        let i = jvm.invoke_varargs(
+            &test_instance,
+            "addInts",
+            no_fixed_args,
+            &[InvocationArg::try_from(1)?, InvocationArg::try_from(2)?, InvocationArg::try_from(3)?],
+            "java.lang.Integer",
+        )?;
+        let num: i32 = jvm.to_rust(i)?;
+        assert_eq!(num, 6);
+
+        let i = jvm.invoke_static_varargs(
+            "java.lang.String",
+            "format",
+            &[InvocationArg::try_from("%s-%s-%s")?],
+            &[InvocationArg::try_from("a")?, InvocationArg::try_from(1)?, InvocationArg::try_from(true)?],
+            "java.lang.Object",
+        )?;
+        let s: String = jvm.to_rust(i)?;
+        assert_eq!(s, "a-1-true");
+
+        // Primitive arrays: the arguments are unboxed and widened
+        let _ = jvm.invoke_static_varargs(
+            "org.astonbitecode.j4rs.tests.MyTest",
+            "useLongPrimitivesArray",
+            no_fixed_args,
+            &[InvocationArg::try_from(1_i64)?, InvocationArg::try_from(2)?.into_primitive()?],
+            "long",
+        )?;
+
+        assert!(jvm.invoke_static_varargs(
+            "java.lang.String",
+            "format",
+            &[InvocationArg::try_from("%s")?],
+            &[InvocationArg::try_from(1)?],
+            "java.lang.String",
+        ).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn instance_invocation_chain_and_collect() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
@@ -960,6 +1180,25 @@ mod lib_unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn set_class_field() -> errors::Result<()> {
+        let jvm = create_tests_jvm()?;
+
+        let test_instance = jvm.create_instance("org.astonbitecode.j4rs.tests.MyTest", InvocationArg::empty())?;
+        jvm.set_field(&test_instance, "publicInteger", InvocationArg::try_from(5)?)?;
+        let i: i32 = jvm.to_rust(jvm.field(&test_instance, "publicInteger")?)?;
+        assert_eq!(i, 5);
+
+        jvm.set_static_field("org.astonbitecode.j4rs.tests.MyTest", "StaticCounter", InvocationArg::try_from(3)?)?;
+        let i: i32 = jvm.to_rust(jvm.static_class_field("org.astonbitecode.j4rs.tests.MyTest", "StaticCounter")?)?;
+        assert_eq!(i, 3);
+
+        let res = jvm.set_field(&test_instance, "nonExistingField", InvocationArg::try_from(5)?);
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn java_hello_world() -> errors::Result<()> {
         let jvm = create_tests_jvm()?;
@@ -1323,4 +1562,75 @@ mod lib_unit_tests {
         assert!(jobj != null_mut());
         Ok(())
     }
+
+    #[test]
+    fn call_from_java_with_typed_args() -> errors::Result<()> {
+        use std::sync::atomic::{AtomicI64, Ordering};
+        use crate::prelude::*;
+        use j4rs_derive::call_from_java;
+
+        static STORED: AtomicI64 = AtomicI64::new(0);
+
+        #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.concat")]
+        fn concat(s: String, i: i32, instance: Instance) -> errors::Result<Instance> {
+            let jvm = Jvm::attach_thread()?;
+            let class_name = jvm.invoke(&instance, "getName", InvocationArg::empty())?;
+            let class_name: String = jvm.to_rust(class_name)?;
+            Instance::try_from(InvocationArg::try_from(format!("{}{}{}", s, i, class_name))?)
+        }
+
+        #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.store")]
+        fn store(value: i64) {
+            STORED.store(value, Ordering::SeqCst);
+        }
+
+        #[call_from_java("org.astonbitecode.j4rs.tests.MyTest.asyncLength")]
+        async fn async_length(s: String) -> errors::Result<Instance> {
+            let length = s.len() as i32;
+            let jvm = Jvm::attach_thread()?;
+            let instance = jvm.create_instance("java.lang.Integer", &[InvocationArg::try_from(length)?.into_primitive()?])?;
+            Ok(instance)
+        }
+
+        let jvm = create_tests_jvm()?;
+        // The JNI functions receive local references
+        let local_ref = |instance: &Instance| unsafe { ((**jvm.jni_env).v1_6.NewLocalRef)(jvm.jni_env, instance.jinstance) };
+        let s = jvm.create_instance("java.lang.String", &[InvocationArg::try_from("a")?])?;
+        let i = jvm.create_instance("java.lang.Integer", &[InvocationArg::try_from(1)?.into_primitive()?])?;
+        let class = jvm.invoke_static("java.lang.Class", "forName", &[InvocationArg::try_from("java.lang.String")?])?;
+
+        let result = Java_org_astonbitecode_j4rs_tests_MyTest_concat(
+            jvm.jni_env, ptr::null(), local_ref(&s), local_ref(&i), local_ref(&class));
+        let result: String = jvm.to_rust(Instance::from_jobject_with_global_ref(result)?)?;
+        assert_eq!(result, "a1java.lang.String");
+
+        let l = jvm.create_instance("java.lang.Long", &[InvocationArg::try_from(33_i64)?.into_primitive()?])?;
+        Java_org_astonbitecode_j4rs_tests_MyTest_store(jvm.jni_env, ptr::null(), local_ref(&l));
+        assert_eq!(STORED.load(Ordering::SeqCst), 33);
+
+        // Async functions return a CompletableFuture
+        let completable_future = Java_org_astonbitecode_j4rs_tests_MyTest_asyncLength(jvm.jni_env, ptr::null(), local_ref(&s));
+        assert!(!completable_future.is_null());
+        let length = unsafe {
+            let jni_env = jvm.jni_env;
+            let class = ((**jni_env).v1_6.GetObjectClass)(jni_env, completable_future);
+            let join = ((**jni_env).v1_6.GetMethodID)(jni_env, class, c"join".as_ptr(), c"()Ljava/lang/Object;".as_ptr());
+            let integer = ((**jni_env).v1_6.CallObjectMethod)(jni_env, completable_future, join);
+            let class = ((**jni_env).v1_6.GetObjectClass)(jni_env, integer);
+            let int_value = ((**jni_env).v1_6.GetMethodID)(jni_env, class, c"intValue".as_ptr(), c"()I".as_ptr());
+            ((**jni_env).v1_6.CallIntMethod)(jni_env, integer, int_value)
+        };
+        assert_eq!(length, 1);
+
+        // An argument that cannot be converted results in a Java exception
+        let result = Java_org_astonbitecode_j4rs_tests_MyTest_concat(
+            jvm.jni_env, ptr::null(), local_ref(&s), local_ref(&s), local_ref(&class));
+        assert!(result.is_null());
+        unsafe {
+            assert!(((**jvm.jni_env).v1_6.ExceptionCheck)(jvm.jni_env) == jni_sys::JNI_TRUE);
+            ((**jvm.jni_env).v1_6.ExceptionClear)(jvm.jni_env);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,129 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of the Java classes that declare the native methods of the `call_from_java` functions.
+//!
+//! When the `J4RS_NATIVES_DIR` environment variable is set while building a crate that uses the `call_from_java`
+//! attribute of `j4rs_derive`, a descriptor file is written in that directory for each annotated function.
+//! `generate_java_sources` creates the Java sources out of these descriptors, so that the Java declarations
+//! always match the Rust functions.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors;
+use crate::errors::J4RsError;
+
+/// The environment variable that defines the directory where the `call_from_java` macro writes its descriptors.
+pub const NATIVES_DIR_ENV_VAR: &str = "J4RS_NATIVES_DIR";
+
+const DESCRIPTOR_EXTENSION: &str = "native";
+
+/// Generates a Java source file in `java_src_dir` for each Java class that is found in the descriptors
+/// of `descriptors_dir`. The generated classes contain the native method declarations of the `call_from_java` functions.
+///
+/// Returns the paths of the generated files.
+pub fn generate_java_sources(descriptors_dir: &Path, java_src_dir: &Path) -> errors::Result<Vec<PathBuf>> {
+    let mut classes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for entry in fs::read_dir(descriptors_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(DESCRIPTOR_EXTENSION) {
+            continue;
+        }
+        let contents = fs::read_to_string(&path)?;
+        let mut lines = contents.lines();
+        match (lines.next(), lines.next()) {
+            (Some(class_name), Some(declaration)) => classes
+                .entry(class_name.trim().to_string())
+                .or_default()
+                .push(declaration.trim().to_string()),
+            _ => {
+                return Err(J4RsError::ParseError(format!(
+                    "Invalid native method descriptor {:?}",
+                    path
+                )))
+            }
+        }
+    }
+
+    let mut generated = Vec::new();
+    for (class_name, mut declarations) in classes {
+        declarations.sort();
+        let (package, simple_name) = match class_name.rfind('.') {
+            Some(index) => (Some(&class_name[..index]), &class_name[index + 1..]),
+            None => (None, class_name.as_str()),
+        };
+        let mut source_dir = java_src_dir.to_path_buf();
+        if let Some(package) = package {
+            source_dir.extend(package.split('.'));
+        }
+        fs::create_dir_all(&source_dir)?;
+        let source_path = source_dir.join(format!("{}.java", simple_name));
+        fs::write(&source_path, java_source(package, simple_name, &declarations))?;
+        generated.push(source_path);
+    }
+    Ok(generated)
+}
+
+fn java_source(package: Option<&str>, simple_name: &str, declarations: &[String]) -> String {
+    let mut source = String::from("// Generated by j4rs out of the functions that are annotated with call_from_java. Do not edit.\n");
+    if let Some(package) = package {
+        source.push_str(&format!("package {};\n\n", package));
+    }
+    source.push_str("import org.astonbitecode.j4rs.api.Instance;\n\n");
+    source.push_str(&format!("public class {} {{\n", simple_name));
+    for declaration in declarations {
+        source.push_str(&format!("    {}\n", declaration));
+    }
+    source.push_str("}\n");
+    source
+}
+
+#[cfg(test)]
+mod natives_unit_tests {
+    use super::*;
+
+    #[test]
+    fn generate_java_sources_from_descriptors() -> errors::Result<()> {
+        let tmp = std::env::temp_dir().join("j4rs_generate_java_sources");
+        let _ = fs::remove_dir_all(&tmp);
+        let descriptors_dir = tmp.join("descriptors");
+        fs::create_dir_all(&descriptors_dir)?;
+        fs::write(
+            descriptors_dir.join("my.pkg.RustCalls.add.native"),
+            "my.pkg.RustCalls\npublic static native Instance add(Instance<Integer> i1, Instance<Integer> i2);\n",
+        )?;
+        fs::write(
+            descriptors_dir.join("my.pkg.RustCalls.log.native"),
+            "my.pkg.RustCalls\npublic static native void log(Instance<String> message);\n",
+        )?;
+        fs::write(descriptors_dir.join("ignored.txt"), "")?;
+
+        let generated = generate_java_sources(&descriptors_dir, &tmp.join("java"))?;
+        assert_eq!(generated, vec![tmp.join("java").join("my").join("pkg").join("RustCalls.java")]);
+        let source = fs::read_to_string(&generated[0])?;
+        assert!(source.contains("package my.pkg;"));
+        assert!(source.contains("public class RustCalls {"));
+        assert!(source.contains(
+            "    public static native Instance add(Instance<Integer> i1, Instance<Integer> i2);\n    public static native void log(Instance<String> message);"
+        ));
+
+        fs::write(descriptors_dir.join("invalid.native"), "my.pkg.RustCalls\n")?;
+        assert!(generate_java_sources(&descriptors_dir, &tmp.join("java")).is_err());
+
+        let _ = fs::remove_dir_all(&tmp);
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::cell::RefCell;
+use std::fmt;
 
 use crate::utils;
 
@@ -85,6 +86,21 @@ pub struct MavenArtifact {
     pub(crate) id: String,
     pub(crate) version: String,
     pub(crate) qualifier: String,
+    pub(crate) transitive: bool,
+}
+
+impl MavenArtifact {
+    /// Defines whether the transitive dependencies of the artifact should be deployed as well.
+    ///
+    /// When `true`, the POM of the artifact is parsed and its `compile` and `runtime` dependencies
+    /// are resolved (recursively), respecting exclusions and optional dependencies.
+    /// Version conflicts are resolved like Maven does: the nearest declaration wins.
+    ///
+    /// Default is `false`.
+    pub fn with_transitive(mut self, transitive: bool) -> MavenArtifact {
+        self.transitive = transitive;
+        self
+    }
 }
 
 impl JavaArtifact for MavenArtifact {}
@@ -101,6 +117,7 @@ impl From<&[&str]> for MavenArtifact {
             id: slice.get(1).unwrap_or(&"").to_string(),
             version: slice.get(2).unwrap_or(&"").to_string(),
             qualifier: slice.get(3).unwrap_or(&"").to_string(),
+            transitive: false,
         }
     }
 }
@@ -147,10 +164,104 @@ impl From<String> for MavenArtifact {
     }
 }
 
+/// Represents an Artifact that can be fetched by a remote repository with an Ivy layout,
+/// like the ones that are used by Ivy and Gradle.
+/// It can loaded and used by j4rs by calling the `JVM::deploy_artifact` method.
+///
+/// The layout of the repository is described by a pattern (see `IvyArtifact::with_pattern`).
+#[derive(Debug, Clone)]
+pub struct IvyArtifact {
+    pub(crate) base: String,
+    pub(crate) repo: String,
+    pub(crate) pattern: String,
+    pub(crate) organisation: String,
+    pub(crate) module: String,
+    pub(crate) revision: String,
+    pub(crate) artifact: String,
+    pub(crate) artifact_type: String,
+    pub(crate) ext: String,
+    pub(crate) classifier: String,
+}
+
+impl IvyArtifact {
+    /// The default pattern of Ivy repositories.
+    pub const IVY_PATTERN: &'static str =
+        "[organisation]/[module]/[revision]/[type]s/[artifact]-[revision](-[classifier]).[ext]";
+    /// The default pattern of the Gradle `ivy` repository layout.
+    pub const GRADLE_PATTERN: &'static str =
+        "[organisation]/[module]/[revision]/[artifact]-[revision](-[classifier])(.[ext])";
+
+    /// Creates a new IvyArtifact that will be fetched from the repository with the `repo` uri.
+    ///
+    /// The `coordinates` should be formed like following:
+    ///
+    /// __organisation__:__module__:__revision__
+    ///
+    /// E.g:
+    /// _io.github.astonbitecode:j4rs:0.5.1_
+    pub fn new(repo: &str, coordinates: &str) -> IvyArtifact {
+        let v: Vec<&str> = coordinates.split(':').collect();
+        let module = v.get(1).unwrap_or(&"").to_string();
+        IvyArtifact {
+            base: utils::jassets_path()
+                .unwrap_or_default()
+                .to_str()
+                .unwrap_or("")
+                .to_string(),
+            repo: repo.to_string(),
+            pattern: Self::IVY_PATTERN.to_string(),
+            organisation: v.first().unwrap_or(&"").to_string(),
+            artifact: module.clone(),
+            module,
+            revision: v.get(2).unwrap_or(&"").to_string(),
+            artifact_type: "jar".to_string(),
+            ext: "jar".to_string(),
+            classifier: "".to_string(),
+        }
+    }
+
+    /// Defines the layout of the repository.
+    ///
+    /// The pattern may contain the tokens `[organisation]`, `[orgPath]` (the organisation with the dots
+    /// replaced by slashes), `[module]`, `[revision]`, `[artifact]`, `[type]`, `[ext]` and `[classifier]`.
+    /// Parts of the pattern enclosed in parentheses are optional and are omitted if any of the tokens they contain is empty.
+    ///
+    /// Default is `IvyArtifact::IVY_PATTERN`.
+    pub fn with_pattern(mut self, pattern: &str) -> IvyArtifact {
+        self.pattern = pattern.to_string();
+        self
+    }
+
+    /// Defines the name of the artifact, if it is different from the module name.
+    pub fn with_artifact(mut self, artifact: &str) -> IvyArtifact {
+        self.artifact = artifact.to_string();
+        self
+    }
+
+    /// Defines the classifier of the artifact.
+    pub fn with_classifier(mut self, classifier: &str) -> IvyArtifact {
+        self.classifier = classifier.to_string();
+        self
+    }
+}
+
+impl JavaArtifact for IvyArtifact {}
+
 /// Contains Maven settings and configuration
 #[derive(Debug, Clone)]
 pub struct MavenSettings {
     pub(crate) repos: Vec<MavenArtifactRepo>,
+    pub(crate) proxy: Option<MavenProxy>,
+    pub(crate) settings_xml: Option<SettingsXml>,
+}
+
+/// The location of a Maven `settings.xml`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SettingsXml {
+    /// The `settings.xml` of the given path.
+    Path(String),
+    /// The default `settings.xml` location of the user (`~/.m2/settings.xml`), which is resolved by the Java side.
+    User,
 }
 
 impl MavenSettings {
@@ -160,7 +271,33 @@ impl MavenSettings {
         let mut repos = repos;
         repos.push(MavenArtifactRepo::from(MAVEN_CENTRAL));
         repos.push(MavenArtifactRepo::from(OSS_SNAPSHOTS));
-        MavenSettings { repos }
+        MavenSettings {
+            repos,
+            proxy: None,
+            settings_xml: None,
+        }
+    }
+
+    /// Uses the defined proxy in order to access the repositories.
+    pub fn with_proxy(mut self, proxy: MavenProxy) -> MavenSettings {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Applies the servers' credentials and the active proxy that are defined in the `settings.xml` of the given path.
+    ///
+    /// The credentials of a server are used for the repository that has the same id.
+    /// Credentials and proxy that are explicitly defined in the `MavenSettings` take precedence.
+    /// Encrypted passwords are not supported.
+    pub fn with_settings_xml(mut self, path: &str) -> MavenSettings {
+        self.settings_xml = Some(SettingsXml::Path(path.to_string()));
+        self
+    }
+
+    /// Like `with_settings_xml`, using the default `settings.xml` location of the user (`~/.m2/settings.xml`).
+    pub fn with_user_settings_xml(mut self) -> MavenSettings {
+        self.settings_xml = Some(SettingsXml::User);
+        self
     }
 }
 
@@ -170,18 +307,105 @@ impl Default for MavenSettings {
     }
 }
 
+/// An HTTP proxy to use for accessing the Maven repositories.
+#[derive(Clone)]
+pub struct MavenProxy {
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) username: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) non_proxy_hosts: Option<String>,
+}
+
+impl MavenProxy {
+    /// Creates a new MavenProxy.
+    pub fn new(host: &str, port: u16) -> MavenProxy {
+        MavenProxy {
+            host: host.to_string(),
+            port,
+            username: None,
+            password: None,
+            non_proxy_hosts: None,
+        }
+    }
+
+    /// Authenticates to the proxy with the given username and password.
+    ///
+    /// _Note: For https repositories, the JDK disables basic authentication during tunneling by default.
+    /// In order to enable it, the JVM should be created with the option `-Djdk.http.auth.tunneling.disabledSchemes=`._
+    pub fn with_credentials(mut self, username: &str, password: &str) -> MavenProxy {
+        self.username = Some(username.to_string());
+        self.password = Some(password.to_string());
+        self
+    }
+
+    /// Defines the hosts that should be accessed directly, without the proxy.
+    ///
+    /// The format is the one of Maven: hosts separated by `|`, where `*` can be used as a wildcard.
+    /// E.g. `*.mycompany.com|localhost`
+    pub fn with_non_proxy_hosts(mut self, non_proxy_hosts: &str) -> MavenProxy {
+        self.non_proxy_hosts = Some(non_proxy_hosts.to_string());
+        self
+    }
+}
+
+impl fmt::Debug for MavenProxy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MavenProxy")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &redacted(&self.password))
+            .field("non_proxy_hosts", &self.non_proxy_hosts)
+            .finish()
+    }
+}
+
 /// A repository from which Java artifacts can be fetched.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MavenArtifactRepo {
-    pub(crate) _id: String,
+    pub(crate) id: String,
     pub(crate) uri: String,
+    pub(crate) username: Option<String>,
+    pub(crate) password: Option<String>,
+    pub(crate) token: Option<String>,
+}
+
+impl MavenArtifactRepo {
+    /// Uses basic authentication with the given username and password in order to access the repository.
+    pub fn with_credentials(mut self, username: &str, password: &str) -> MavenArtifactRepo {
+        self.username = Some(username.to_string());
+        self.password = Some(password.to_string());
+        self
+    }
+
+    /// Uses the given bearer token in order to access the repository.
+    pub fn with_token(mut self, token: &str) -> MavenArtifactRepo {
+        self.token = Some(token.to_string());
+        self
+    }
+}
+
+impl fmt::Debug for MavenArtifactRepo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MavenArtifactRepo")
+            .field("id", &self.id)
+            .field("uri", &self.uri)
+            .field("username", &self.username)
+            .field("password", &redacted(&self.password))
+            .field("token", &redacted(&self.token))
+            .finish()
+    }
 }
 
 impl From<&[&str]> for MavenArtifactRepo {
     fn from(slice: &[&str]) -> MavenArtifactRepo {
         MavenArtifactRepo {
-            _id: slice.first().unwrap_or(&"").to_string(),
+            id: slice.first().unwrap_or(&"").to_string(),
             uri: slice.get(1).unwrap_or(&"").to_string(),
+            username: None,
+            password: None,
+            token: None,
         }
     }
 }
@@ -215,6 +439,11 @@ impl From<String> for MavenArtifactRepo {
     }
 }
 
+// Hides the secrets in the Debug representations, revealing only whether they are set
+fn redacted(secret: &Option<String>) -> Option<&'static str> {
+    secret.as_ref().map(|_| "<redacted>")
+}
+
 #[cfg(test)]
 mod provisioning_unit_tests {
     use super::*;
@@ -238,12 +467,80 @@ mod provisioning_unit_tests {
         assert_eq!(ma3.id, "j4rs");
         assert_eq!(ma3.version, "0.5.1");
         assert_eq!(ma3.qualifier, "");
+        assert!(!ma3.transitive);
+    }
+
+    #[test]
+    fn maven_artifact_with_transitive() {
+        let ma = MavenArtifact::from("io.github.astonbitecode:j4rs:0.5.1").with_transitive(true);
+        assert!(ma.transitive);
+        assert_eq!(ma.id, "j4rs");
+    }
+
+    #[test]
+    fn ivy_artifact_new() {
+        let ia = IvyArtifact::new("https://my.ivy.repo", "io.github.astonbitecode:j4rs:0.5.1");
+        assert_eq!(ia.repo, "https://my.ivy.repo");
+        assert_eq!(ia.organisation, "io.github.astonbitecode");
+        assert_eq!(ia.module, "j4rs");
+        assert_eq!(ia.artifact, "j4rs");
+        assert_eq!(ia.revision, "0.5.1");
+        assert_eq!(ia.pattern, IvyArtifact::IVY_PATTERN);
+
+        let ia = ia
+            .with_pattern(IvyArtifact::GRADLE_PATTERN)
+            .with_artifact("j4rs-core")
+            .with_classifier("sources");
+        assert_eq!(ia.pattern, IvyArtifact::GRADLE_PATTERN);
+        assert_eq!(ia.artifact, "j4rs-core");
+        assert_eq!(ia.classifier, "sources");
     }
 
     #[test]
     fn maven_artifact_repo_from() {
         let mar = MavenArtifactRepo::from("myrepo::https://myrepo.io");
-        assert_eq!(mar._id, "myrepo");
+        assert_eq!(mar.id, "myrepo");
         assert_eq!(mar.uri, "https://myrepo.io");
+        assert!(mar.username.is_none());
+        assert!(mar.token.is_none());
+    }
+
+    #[test]
+    fn maven_settings_with_auth_and_proxy() {
+        let ms = MavenSettings::new(vec![
+            MavenArtifactRepo::from("myrepo::https://myrepo.io").with_credentials("user", "pass"),
+            MavenArtifactRepo::from("other::https://other.io").with_token("token"),
+        ])
+            .with_proxy(MavenProxy::new("proxy.mycompany.com", 3128)
+                .with_credentials("puser", "ppass")
+                .with_non_proxy_hosts("localhost|*.mycompany.com"))
+            .with_user_settings_xml();
+
+        assert_eq!(ms.repos[0].username.as_deref(), Some("user"));
+        assert_eq!(ms.repos[0].password.as_deref(), Some("pass"));
+        assert_eq!(ms.repos[1].token.as_deref(), Some("token"));
+        let proxy = ms.proxy.unwrap();
+        assert_eq!(proxy.host, "proxy.mycompany.com");
+        assert_eq!(proxy.port, 3128);
+        assert_eq!(proxy.username.as_deref(), Some("puser"));
+        assert_eq!(proxy.non_proxy_hosts.as_deref(), Some("localhost|*.mycompany.com"));
+        assert_eq!(ms.settings_xml, Some(SettingsXml::User));
+
+        let ms = MavenSettings::default().with_settings_xml("/my/settings.xml");
+        assert_eq!(ms.settings_xml, Some(SettingsXml::Path("/my/settings.xml".to_string())));
+    }
+
+    #[test]
+    fn credentials_are_redacted_in_debug() {
+        let ms = MavenSettings::new(vec![
+            MavenArtifactRepo::from("myrepo::https://myrepo.io").with_credentials("user", "secret1"),
+            MavenArtifactRepo::from("other::https://other.io").with_token("secret2"),
+        ])
+            .with_proxy(MavenProxy::new("proxy.mycompany.com", 3128).with_credentials("puser", "secret3"));
+
+        let debug = format!("{:?}", ms);
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("myrepo") && debug.contains("puser") && debug.contains("<redacted>"));
+        assert!(format!("{:?}", MavenArtifactRepo::from("myrepo::https://myrepo.io")).contains("password: None"));
     }
 }
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::ffi::{CStr, CString};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{self, env, fs, str};
 
 use cesu8::{from_java_cesu8, to_java_cesu8};
@@ -68,7 +69,7 @@ pub(crate) fn deps_dir() -> errors::Result<String> {
     let mut pb = jassets_path()?;
     pb.pop();
     pb.push("deps");
-    Ok(pb.to_str().unwrap_or("./deps/").to_owned())
+    Ok(pb.to_string_lossy().into_owned())
 }
 
 pub(crate) fn jassets_path() -> errors::Result<PathBuf> {
@@ -92,18 +93,48 @@ pub(crate) fn default_jassets_path() -> errors::Result<PathBuf> {
     };
     start_path = canonicalize(start_path)?;
 
+    // The ancestors are not listed, as listing e.g. the root of a network share may not be permitted
     while start_path.pop() {
-        for entry in std::fs::read_dir(&start_path)? {
-            let path = entry?.path();
-            if path.file_name().map(|x| x == "jassets").unwrap_or(false) {
-                return Ok(path);
-            }
+        let path = start_path.join("jassets");
+        if path.exists() {
+            return Ok(java_path_buf(&path));
         }
     }
 
     Err(errors::J4RsError::GeneralError("Can not find jassets directory".to_owned()))
 }
 
+/// Returns a path that Java understands, without the `\\?\` prefix of the Windows verbatim paths.
+///
+/// `\\?\C:\dir` becomes `C:\dir` and `\\?\UNC\server\share\dir` becomes `\\server\share\dir`.
+/// Paths that are longer than 260 characters keep working in Rust and Java, which both add the prefix when needed.
+pub(crate) fn java_path(path: &str) -> Cow<'_, str> {
+    if cfg!(windows) {
+        strip_verbatim_prefix(path)
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
+pub(crate) fn java_path_buf(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(path_str) => PathBuf::from(java_path(path_str).as_ref()),
+        None => path.to_path_buf(),
+    }
+}
+
+fn strip_verbatim_prefix(path: &str) -> Cow<'_, str> {
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        Cow::Owned(format!(r"\\{}", unc))
+    } else {
+        match path.strip_prefix(r"\\?\") {
+            // Only drive paths, like \\?\C:\dir. Other verbatim paths (e.g. of volumes) are kept.
+            Some(rest) if rest.as_bytes().get(1) == Some(&b':') => Cow::Borrowed(rest),
+            _ => Cow::Borrowed(path),
+        }
+    }
+}
+
 pub(crate) fn find_j4rs_dynamic_libraries_names() -> errors::Result<Vec<String>> {
     let entries: Vec<String> = find_j4rs_dynamic_libraries_dir_entries()?
         .iter()
@@ -142,7 +173,11 @@ fn find_j4rs_dynamic_libraries_dir_entries() -> errors::Result<Vec<fs::DirEntry>
 }
 
 pub(crate) fn primitive_of(inv_arg: &InvocationArg) -> Option<String> {
-    match get_class_name(inv_arg).into() {
+    primitive_of_class(get_class_name(inv_arg))
+}
+
+pub(crate) fn primitive_of_class(class_name: &str) -> Option<String> {
+    match class_name.into() {
         JavaClass::Boolean => Some(PRIMITIVE_BOOLEAN.to_string()),
         JavaClass::Byte => Some(PRIMITIVE_BYTE.to_string()),
         JavaClass::Short => Some(PRIMITIVE_SHORT.to_string()),
@@ -184,6 +219,15 @@ mod utils_unit_tests {
     use super::*;
     use crate::lib_unit_tests::create_tests_jvm;
 
+    #[test]
+    fn strip_verbatim_prefix_test() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\app\jassets"), r"C:\app\jassets");
+        assert_eq!(strip_verbatim_prefix(r"\\?\UNC\server\share\jassets"), r"\\server\share\jassets");
+        assert_eq!(strip_verbatim_prefix(r"\\server\share\jassets"), r"\\server\share\jassets");
+        assert_eq!(strip_verbatim_prefix(r"\\?\Volume{1234}\jassets"), r"\\?\Volume{1234}\jassets");
+        assert_eq!(strip_verbatim_prefix("/opt/app/jassets"), "/opt/app/jassets");
+    }
+
     #[test]
     fn get_class_name_test() -> errors::Result<()> {
         let _jvm = create_tests_jvm()?;
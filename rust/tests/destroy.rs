@@ -0,0 +1,63 @@
+// Copyright 2024 astonbitecode
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The JavaVM cannot be created again after it is destroyed. Therefore, destroying is tested
+// in its own test binary, which contains a single test.
+
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use j4rs::{errors, InvocationArg, Jvm, JvmBuilder};
+
+#[test]
+fn destroy() -> errors::Result<()> {
+    let jvm = JvmBuilder::new().build()?;
+
+    let hooks_called = Arc::new(AtomicUsize::new(0));
+    for _ in 0..2 {
+        let hooks_called_clone = hooks_called.clone();
+        jvm.on_shutdown(move |jvm| {
+            if jvm.invoke_static("java.lang.Integer", "valueOf", &[InvocationArg::try_from("1").unwrap()]).is_ok() {
+                hooks_called_clone.fetch_add(1, Ordering::SeqCst);
+            }
+        })?;
+    }
+
+    // A non-daemon thread that creates a Jvm while the JavaVM is being destroyed.
+    // DestroyJavaVM waits for it to be detached.
+    let (attached_tx, attached_rx) = channel();
+    let thread_jvm_created = Arc::new(AtomicBool::new(false));
+    let thread_jvm_created_clone = thread_jvm_created.clone();
+    let handle = thread::spawn(move || {
+        let _jvm = Jvm::attach_thread().unwrap();
+        attached_tx.send(()).unwrap();
+        thread::sleep(Duration::from_millis(500));
+        let res = Jvm::attach_thread().and_then(|jvm| jvm.create_instance("java.lang.Object", InvocationArg::empty()));
+        thread_jvm_created_clone.store(res.is_ok(), Ordering::SeqCst);
+    });
+    attached_rx.recv().unwrap();
+
+    jvm.destroy()?;
+    handle.join().unwrap();
+
+    assert_eq!(hooks_called.load(Ordering::SeqCst), 2);
+    assert!(thread_jvm_created.load(Ordering::SeqCst));
+    assert!(Jvm::attach_thread().is_err());
+    assert!(JvmBuilder::new().build().is_err());
+    Ok(())
+}
@@ -21,6 +21,11 @@ public class MyBean {
     public MyBean() {
     }
 
+    public MyBean(String someString, Integer someInteger) {
+        this.someString = someString;
+        this.someInteger = someInteger;
+    }
+
     public String getSomeString() {
         return someString;
     }
@@ -0,0 +1,40 @@
+/*
+ * Copyright 2024 astonbitecode
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+package org.astonbitecode.j4rs.tests;
+
+/**
+ * The shape of the class that the Kotlin compiler generates for a class with a <code>companion object</code>.
+ */
+public final class MyKotlinClass {
+    public static final Companion Companion = new Companion();
+    private final String name;
+
+    private MyKotlinClass(String name) {
+        this.name = name;
+    }
+
+    public String getName() {
+        return name;
+    }
+
+    public static final class Companion {
+        private Companion() {
+        }
+
+        public MyKotlinClass create(String name) {
+            return new MyKotlinClass(name);
+        }
+    }
+}
@@ -20,6 +20,7 @@ import java.util.ArrayList;
 import java.util.Arrays;
 import java.util.List;
 import java.util.Map;
+import java.util.concurrent.CompletableFuture;
 import java.util.concurrent.ExecutorService;
 import java.util.concurrent.Executors;
 import java.util.concurrent.Future;
@@ -29,6 +30,8 @@ import java.util.stream.IntStream;
 public class MyTest {
     private String string;
     public static String StaticString = "This is a static String from Java";
+    public static int StaticCounter = 0;
+    public Integer publicInteger = 0;
 
     private static ExecutorService executor = Executors.newSingleThreadExecutor();
 
@@ -116,6 +119,26 @@ public class MyTest {
         System.out.println("Static");
     }
 
+    public String overloaded(Object o) {
+        return "Object";
+    }
+
+    public String overloaded(String s) {
+        return "String";
+    }
+
+    public String overloaded(int i) {
+        return "int";
+    }
+
+    public static String overloadedStatic(Object o) {
+        return "Object";
+    }
+
+    public static String overloadedStatic(String s) {
+        return "String";
+    }
+
     public <T> T echo(T o) {
         return o;
     }
@@ -155,4 +178,8 @@ public class MyTest {
     public static Future<String> getErrorWithFutureStatic(String string) {
         return executor.submit(() -> string);
     }
+
+    public static CompletableFuture<String> getCompletableFutureStatic(String string) {
+        return CompletableFuture.supplyAsync(() -> string, executor);
+    }
 }